@@ -24,7 +24,7 @@
 
 use composable_rust_core::{
     effect::Effect,
-    reducer::Reducer,
+    reducer::{ReduceContext, Reducer},
     agent::AgentEnvironment,
 };
 use smallvec::SmallVec;
@@ -82,6 +82,16 @@ where
         state: &mut Self::State,
         action: Self::Action,
         env: &Self::Environment,
+    ) -> SmallVec<[Effect<Self::Action>; 4]> {
+        self.reduce_with_context(state, action, env, &ReduceContext::default())
+    }
+
+    fn reduce_with_context(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+        context: &ReduceContext,
     ) -> SmallVec<[Effect<Self::Action>; 4]> {
         // Create span for this reduction
         let span = span!(
@@ -96,7 +106,7 @@ where
         let start = Instant::now();
 
         // Execute inner reducer
-        let effects = self.inner.reduce(state, action, env);
+        let effects = self.inner.reduce_with_context(state, action, env, context);
 
         // Record span attributes
         let duration_ms = start.elapsed().as_millis();
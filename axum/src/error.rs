@@ -0,0 +1,180 @@
+//! Mapping store errors to HTTP responses.
+//!
+//! | Store error                                   | Status |
+//! |-----------------------------------------------|--------|
+//! | `Timeout`                                     | 504    |
+//! | `RateLimited`                                 | 429 (with `Retry-After: 1`) |
+//! | `BudgetExceeded`                              | 422    |
+//! | `ShutdownInProgress`, `MailboxClosed`, `MailboxFull`, `ChannelClosed`, `Degraded` | 503 |
+//! | `EffectFailed`, `TaskJoinError`, `ShutdownTimeout`, `EffectsAborted`, `DrainTimeout`, `NoRuntime`, `BlockingInAsyncContext`, `ReplayFailed` | 500 |
+//!
+//! Internal errors are logged and answered with a generic message so
+//! implementation details do not leak to clients.
+
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use composable_rust_runtime::StoreError;
+use serde::Serialize;
+use thiserror::Error;
+
+/// Error returned by the store extractor and response helpers
+#[derive(Error, Debug)]
+pub enum StoreRejection {
+    /// No [`StoreExtension`](crate::StoreExtension) registered a store of the
+    /// requested type
+    #[error("No store registered for {type_name}")]
+    MissingStore {
+        /// Type name of the requested store
+        type_name: &'static str,
+    },
+
+    /// The store rejected or failed to complete the request
+    #[error(transparent)]
+    Store(#[from] StoreError),
+}
+
+impl StoreRejection {
+    /// HTTP status code for this error
+    #[must_use]
+    pub const fn status(&self) -> StatusCode {
+        match self {
+            Self::MissingStore { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Store(error) => match error {
+                StoreError::Timeout => StatusCode::GATEWAY_TIMEOUT,
+                StoreError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+                StoreError::BudgetExceeded { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                StoreError::ShutdownInProgress
+                | StoreError::MailboxClosed
+                | StoreError::MailboxFull { .. }
+                | StoreError::ChannelClosed
+                | StoreError::Degraded { .. } => StatusCode::SERVICE_UNAVAILABLE,
+                StoreError::EffectFailed(_)
+                | StoreError::EffectsFailed(_)
+                | StoreError::TaskJoinError(_)
+                | StoreError::ShutdownTimeout(_)
+                | StoreError::EffectsAborted(_)
+                | StoreError::DrainTimeout(_)
+                | StoreError::NoRuntime
+                | StoreError::BlockingInAsyncContext
+                | StoreError::ReplayFailed(_)
+                | StoreError::StateCodec(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+        }
+    }
+
+    /// Machine-readable error code for the response body
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::MissingStore { .. } => "INTERNAL_SERVER_ERROR",
+            Self::Store(error) => match error {
+                StoreError::Timeout => "TIMEOUT",
+                StoreError::RateLimited(_) => "RATE_LIMITED",
+                StoreError::BudgetExceeded { .. } => "BUDGET_EXCEEDED",
+                StoreError::ShutdownInProgress => "SHUTTING_DOWN",
+                StoreError::Degraded { .. } => "DEPENDENCY_UNAVAILABLE",
+                StoreError::MailboxFull { .. } => "OVERLOADED",
+                StoreError::MailboxClosed | StoreError::ChannelClosed => "SERVICE_UNAVAILABLE",
+                StoreError::EffectFailed(_)
+                | StoreError::EffectsFailed(_)
+                | StoreError::TaskJoinError(_)
+                | StoreError::ShutdownTimeout(_)
+                | StoreError::EffectsAborted(_)
+                | StoreError::DrainTimeout(_)
+                | StoreError::NoRuntime
+                | StoreError::BlockingInAsyncContext
+                | StoreError::ReplayFailed(_)
+                | StoreError::StateCodec(_) => "INTERNAL_SERVER_ERROR",
+            },
+        }
+    }
+}
+
+/// Error response body (JSON)
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    /// Error code (for client error handling)
+    code: &'static str,
+    /// Human-readable error message
+    message: String,
+}
+
+impl IntoResponse for StoreRejection {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let message = if status == StatusCode::INTERNAL_SERVER_ERROR {
+            tracing::error!(error = %self, "Store request failed");
+            "An internal error occurred".to_string()
+        } else {
+            self.to_string()
+        };
+
+        let body = Json(ErrorResponse {
+            code: self.code(),
+            message,
+        });
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            // Rate limits are enforced per second
+            (status, [(header::RETRY_AFTER, "1")], body).into_response()
+        } else {
+            (status, body).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        let cases = [
+            (StoreError::Timeout, StatusCode::GATEWAY_TIMEOUT),
+            (StoreError::RateLimited(10), StatusCode::TOO_MANY_REQUESTS),
+            (StoreError::ShutdownInProgress, StatusCode::SERVICE_UNAVAILABLE),
+            (StoreError::MailboxFull { capacity: 8 }, StatusCode::SERVICE_UNAVAILABLE),
+            (
+                StoreError::Degraded { dependency: "payments" },
+                StatusCode::SERVICE_UNAVAILABLE,
+            ),
+            (
+                StoreError::EffectFailed("boom".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+
+        for (error, status) in cases {
+            assert_eq!(StoreRejection::from(error).status(), status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_response_has_retry_after() {
+        let response = StoreRejection::from(StoreError::RateLimited(10)).into_response();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+        assert_eq!(body_json(response).await["code"], "RATE_LIMITED");
+    }
+
+    #[tokio::test]
+    async fn test_internal_error_message_is_hidden() {
+        let response =
+            StoreRejection::from(StoreError::EffectFailed("db password wrong".to_string()))
+                .into_response();
+
+        let body = body_json(response).await;
+        assert_eq!(body["code"], "INTERNAL_SERVER_ERROR");
+        assert_eq!(body["message"], "An internal error occurred");
+    }
+}
@@ -0,0 +1,160 @@
+//! Making stores available to handlers.
+//!
+//! [`StoreExtension`] inserts an `Arc<Store<S, A, E, R>>` into every request's
+//! extensions; [`StoreRef`] extracts it again. Unlike router state, this
+//! needs no application-specific state struct and composes across nested
+//! routers.
+
+use crate::error::StoreRejection;
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::request::Parts,
+    middleware::AddExtension,
+    Extension,
+};
+use composable_rust_core::reducer::Reducer;
+use composable_rust_runtime::Store;
+use std::ops::Deref;
+use std::sync::Arc;
+use tower::Layer;
+
+/// Layer registering a store for the [`StoreRef`] extractor
+///
+/// # Example
+///
+/// ```ignore
+/// let app = Router::new()
+///     .route("/orders", post(place_order))
+///     .layer(StoreExtension::new(Arc::new(order_store)));
+/// ```
+pub struct StoreExtension<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    store: Arc<Store<S, A, E, R>>,
+}
+
+impl<S, A, E, R> StoreExtension<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    /// Create a layer registering `store`
+    #[must_use]
+    pub const fn new(store: Arc<Store<S, A, E, R>>) -> Self {
+        Self { store }
+    }
+}
+
+impl<S, A, E, R> Clone for StoreExtension<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            store: Arc::clone(&self.store),
+        }
+    }
+}
+
+impl<S, A, E, R> std::fmt::Debug for StoreExtension<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoreExtension")
+            .field("store", &std::any::type_name::<Store<S, A, E, R>>())
+            .finish()
+    }
+}
+
+impl<Svc, S, A, E, R> Layer<Svc> for StoreExtension<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+    Store<S, A, E, R>: Send + Sync + 'static,
+{
+    type Service = AddExtension<Svc, Arc<Store<S, A, E, R>>>;
+
+    fn layer(&self, inner: Svc) -> Self::Service {
+        Extension(Arc::clone(&self.store)).layer(inner)
+    }
+}
+
+/// Extractor for a store registered with [`StoreExtension`]
+///
+/// Dereferences to the [`Store`]. Rejects with
+/// [`StoreRejection::MissingStore`] (500) if no store of this type was
+/// registered, which is a wiring bug rather than a client error.
+///
+/// # Example
+///
+/// ```ignore
+/// async fn order_count(store: StoreRef<OrderState, OrderAction, OrderEnv, OrderReducer>) -> String {
+///     store.state(|s| s.orders.len()).await.to_string()
+/// }
+/// ```
+pub struct StoreRef<S, A, E, R>(pub Arc<Store<S, A, E, R>>)
+where
+    R: Reducer<State = S, Action = A, Environment = E>;
+
+impl<S, A, E, R> StoreRef<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    /// The shared store handle
+    #[must_use]
+    pub fn into_inner(self) -> Arc<Store<S, A, E, R>> {
+        self.0
+    }
+}
+
+impl<S, A, E, R> Clone for StoreRef<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<S, A, E, R> Deref for StoreRef<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    type Target = Store<S, A, E, R>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S, A, E, R> std::fmt::Debug for StoreRef<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("StoreRef")
+            .field(&std::any::type_name::<Store<S, A, E, R>>())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<St, S, A, E, R> FromRequestParts<St> for StoreRef<S, A, E, R>
+where
+    St: Send + Sync,
+    R: Reducer<State = S, Action = A, Environment = E>,
+    Store<S, A, E, R>: Send + Sync + 'static,
+{
+    type Rejection = StoreRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &St) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Arc<Store<S, A, E, R>>>()
+            .map(|store| Self(Arc::clone(store)))
+            .ok_or(StoreRejection::MissingStore {
+                type_name: std::any::type_name::<Store<S, A, E, R>>(),
+            })
+    }
+}
@@ -0,0 +1,64 @@
+//! Axum integration for Composable Rust stores.
+//!
+//! Wiring a [`Store`](composable_rust_runtime::Store) into HTTP handlers
+//! usually means threading an `Arc<Store<..>>` through router state, calling
+//! `send_and_wait_for`, and translating every
+//! [`StoreError`](composable_rust_runtime::StoreError) into a status code by
+//! hand. This crate packages those steps:
+//!
+//! - [`StoreExtension`]: a layer that makes a store available to handlers
+//! - [`StoreRef`]: an extractor for a typed store registered by the layer
+//! - [`respond_with`] / [`respond_with_map`]: send an action, wait for the
+//!   terminal action and turn the result into a response
+//! - [`StoreRejection`]: maps store errors to HTTP status codes with a JSON
+//!   `{ "code", "message" }` body
+//!
+//! Stores are looked up by type, so one router can carry several stores as
+//! long as their `Store<S, A, E, R>` types differ.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_axum::{respond_with_map, StoreExtension, StoreRef, StoreRejection};
+//!
+//! type OrderStore = Store<OrderState, OrderAction, OrderEnv, OrderReducer>;
+//!
+//! async fn place_order(
+//!     store: StoreRef<OrderState, OrderAction, OrderEnv, OrderReducer>,
+//!     Json(request): Json<PlaceOrderRequest>,
+//! ) -> Result<Response, StoreRejection> {
+//!     let id = Uuid::new_v4();
+//!     respond_with_map(
+//!         &store,
+//!         OrderAction::PlaceOrder { id, items: request.items },
+//!         move |a| a.correlation_id() == Some(id) && a.is_terminal(),
+//!         Duration::from_secs(10),
+//!         |action| match action {
+//!             OrderAction::OrderPlaced { id, .. } => (StatusCode::CREATED, Json(id)).into_response(),
+//!             other => (StatusCode::CONFLICT, Json(other)).into_response(),
+//!         },
+//!     )
+//!     .await
+//! }
+//!
+//! let app = Router::new()
+//!     .route("/orders", post(place_order))
+//!     .layer(StoreExtension::new(Arc::new(order_store)));
+//! ```
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+/// Store-to-status-code error mapping
+pub mod error;
+
+/// Store layer and extractor
+pub mod extract;
+
+/// Request-response helpers built on `send_and_wait_for`
+pub mod respond;
+
+pub use error::StoreRejection;
+pub use extract::{StoreExtension, StoreRef};
+pub use respond::{respond_with, respond_with_map};
@@ -0,0 +1,201 @@
+//! Request-response helpers.
+//!
+//! HTTP handlers are request-response while stores are fire-and-forget:
+//! these helpers send an action with
+//! [`Store::send_and_wait_for`] and convert the terminal action (or the
+//! [`StoreError`](composable_rust_runtime::StoreError)) into a response.
+//!
+//! As with `send_and_wait_for`, the predicate only sees actions produced by
+//! effects, so it should match on a correlation ID to tell concurrent
+//! requests apart.
+
+use crate::error::StoreRejection;
+use axum::{response::IntoResponse, Json};
+use composable_rust_core::reducer::Reducer;
+use composable_rust_runtime::Store;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Send `action` and respond with the first action matching `predicate` as JSON
+///
+/// # Errors
+///
+/// Returns a [`StoreRejection`] mapping the store error (for example 504 if
+/// no matching action arrives within `timeout`, 503 while shutting down).
+pub async fn respond_with<S, A, E, R, F>(
+    store: &Store<S, A, E, R>,
+    action: A,
+    predicate: F,
+    timeout: Duration,
+) -> Result<Json<A>, StoreRejection>
+where
+    R: Reducer<State = S, Action = A, Environment = E> + Clone + Send + Sync + 'static,
+    A: Serialize + Send + Clone + 'static,
+    S: Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+    F: Fn(&A) -> bool,
+{
+    respond_with_map(store, action, predicate, timeout, Json).await
+}
+
+/// Send `action` and convert the first action matching `predicate` with `map`
+///
+/// Use this when the terminal action needs a custom status code or body, for
+/// example mapping a domain failure action to `409 Conflict`.
+///
+/// # Errors
+///
+/// Returns a [`StoreRejection`] mapping the store error (for example 504 if
+/// no matching action arrives within `timeout`, 503 while shutting down).
+pub async fn respond_with_map<S, A, E, R, F, M, T>(
+    store: &Store<S, A, E, R>,
+    action: A,
+    predicate: F,
+    timeout: Duration,
+    map: M,
+) -> Result<T, StoreRejection>
+where
+    R: Reducer<State = S, Action = A, Environment = E> + Clone + Send + Sync + 'static,
+    A: Send + Clone + 'static,
+    S: Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+    F: Fn(&A) -> bool,
+    M: FnOnce(A) -> T,
+    T: IntoResponse,
+{
+    let terminal = store.send_and_wait_for(action, predicate, timeout).await?;
+    Ok(map(terminal))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use crate::StoreExtension;
+    use crate::StoreRef;
+    use axum::{
+        body::Body,
+        extract::Path,
+        http::{Request, StatusCode},
+        response::Response,
+        routing::post,
+        Router,
+    };
+    use composable_rust_core::{effect::Effect, smallvec, SmallVec};
+    use serde::Serialize;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Clone, Serialize)]
+    enum CounterAction {
+        Add(u32),
+        /// Request that never completes
+        Hang,
+        Added { total: u32 },
+        Overflowed,
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct Counter {
+        total: u32,
+    }
+
+    #[derive(Debug, Clone)]
+    struct CounterReducer;
+
+    impl Reducer for CounterReducer {
+        type State = Counter;
+        type Action = CounterAction;
+        type Environment = ();
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            _env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            match action {
+                CounterAction::Add(n) => {
+                    let reply = match state.total.checked_add(n) {
+                        Some(total) => {
+                            state.total = total;
+                            CounterAction::Added { total }
+                        },
+                        None => CounterAction::Overflowed,
+                    };
+                    smallvec![Effect::Future(Box::pin(async move { Some(reply) }))]
+                },
+                CounterAction::Hang | CounterAction::Added { .. } | CounterAction::Overflowed => {
+                    smallvec![Effect::None]
+                },
+            }
+        }
+    }
+
+    type CounterStore = StoreRef<Counter, CounterAction, (), CounterReducer>;
+
+    fn is_terminal(action: &CounterAction) -> bool {
+        matches!(action, CounterAction::Added { .. } | CounterAction::Overflowed)
+    }
+
+    async fn add(store: CounterStore, Path(n): Path<u32>) -> Result<Response, StoreRejection> {
+        respond_with_map(&store, CounterAction::Add(n), is_terminal, Duration::from_secs(1), |a| {
+            match a {
+                CounterAction::Overflowed => StatusCode::CONFLICT.into_response(),
+                other => Json(other).into_response(),
+            }
+        })
+        .await
+    }
+
+    async fn hang(store: CounterStore) -> Result<Json<CounterAction>, StoreRejection> {
+        respond_with(&store, CounterAction::Hang, is_terminal, Duration::from_millis(50)).await
+    }
+
+    fn app(total: u32) -> Router {
+        let store = Store::new(Counter { total }, CounterReducer, ());
+        Router::new()
+            .route("/add/:n", post(add))
+            .route("/hang", post(hang))
+            .layer(StoreExtension::new(Arc::new(store)))
+    }
+
+    async fn post_to(app: Router, uri: &str) -> Response {
+        app.oneshot(Request::post(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_terminal_action_mapped_to_response() {
+        let response = post_to(app(1), "/add/2").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["Added"]["total"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_failure_action_uses_custom_mapping() {
+        let response = post_to(app(u32::MAX), "/add/1").await;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_maps_to_gateway_timeout() {
+        let response = post_to(app(0), "/hang").await;
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_missing_store_layer_is_internal_error() {
+        let router = Router::new().route("/hang", post(hang));
+
+        let response = post_to(router, "/hang").await;
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,177 @@
+//! A standard environment and accessor traits for composing environments
+//!
+//! Most features need the same handful of dependencies: a [`Clock`], an
+//! [`EventStore`], an [`EventBus`] and [`FeatureFlags`]. [`BaseEnvironment`]
+//! bundles them, and
+//! the `Has*` traits let a reducer ask for exactly the dependencies it uses
+//! instead of a concrete environment type:
+//!
+//! ```rust,ignore
+//! use composable_rust_core::base_environment::{HasClock, HasEventStore};
+//!
+//! impl<E: HasClock + HasEventStore> Reducer for OrderReducer<E> {
+//!     type Environment = E;
+//!
+//!     fn reduce(&self, state: &mut OrderState, action: OrderAction, env: &E) -> /* ... */ {
+//!         let placed_at = env.clock().now();
+//!         smallvec![EventStoreEffect::append(env.event_store(), "order-1", events).into_effect()]
+//!     }
+//! }
+//! ```
+//!
+//! A feature environment that holds a [`BaseEnvironment`] gets every `Has*`
+//! trait by implementing `AsRef<BaseEnvironment>`. In tests,
+//! [`with_overrides`](BaseEnvironment::with_overrides) swaps one dependency
+//! and keeps the rest:
+//!
+//! ```rust,ignore
+//! let env = production_env.with_overrides().clock(FixedClock::new(start)).build();
+//! ```
+
+use crate::environment::{Clock, SystemClock};
+use crate::event_bus::EventBus;
+use crate::event_store::EventStore;
+use crate::feature_flags::{FeatureFlags, StaticFeatureFlags};
+use std::sync::Arc;
+
+/// Environments that provide a [`Clock`]
+pub trait HasClock {
+    /// The clock
+    fn clock(&self) -> &dyn Clock;
+}
+
+/// Environments that provide an [`EventStore`]
+pub trait HasEventStore {
+    /// The event store, as taken by
+    /// [`EventStoreEffect`](crate::effect_builders::EventStoreEffect)
+    fn event_store(&self) -> &Arc<dyn EventStore>;
+}
+
+/// Environments that provide an [`EventBus`]
+pub trait HasEventBus {
+    /// The event bus, as taken by
+    /// [`EventBusEffect`](crate::effect_builders::EventBusEffect)
+    fn event_bus(&self) -> &Arc<dyn EventBus>;
+}
+
+/// Environments that provide [`FeatureFlags`]
+pub trait HasFeatureFlags {
+    /// The feature flags
+    fn feature_flags(&self) -> &dyn FeatureFlags;
+}
+
+/// Clock, event store, event bus and feature flags; see the [module documentation](self)
+#[derive(Clone)]
+pub struct BaseEnvironment {
+    clock: Arc<dyn Clock>,
+    event_store: Arc<dyn EventStore>,
+    event_bus: Arc<dyn EventBus>,
+    feature_flags: Arc<dyn FeatureFlags>,
+}
+
+impl BaseEnvironment {
+    /// Bundle an event store and event bus with the [`SystemClock`]
+    ///
+    /// Every feature flag is disabled until flags are supplied with
+    /// [`with_overrides`](Self::with_overrides).
+    #[must_use]
+    pub fn new(event_store: Arc<dyn EventStore>, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            clock: Arc::new(SystemClock),
+            event_store,
+            event_bus,
+            feature_flags: Arc::new(StaticFeatureFlags::new()),
+        }
+    }
+
+    /// Start a copy of this environment with some dependencies replaced
+    pub fn with_overrides(&self) -> EnvironmentOverrides {
+        EnvironmentOverrides { base: self.clone() }
+    }
+}
+
+impl std::fmt::Debug for BaseEnvironment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BaseEnvironment").finish_non_exhaustive()
+    }
+}
+
+impl AsRef<Self> for BaseEnvironment {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl<T: AsRef<BaseEnvironment>> HasClock for T {
+    fn clock(&self) -> &dyn Clock {
+        self.as_ref().clock.as_ref()
+    }
+}
+
+impl<T: AsRef<BaseEnvironment>> HasEventStore for T {
+    fn event_store(&self) -> &Arc<dyn EventStore> {
+        &self.as_ref().event_store
+    }
+}
+
+impl<T: AsRef<BaseEnvironment>> HasEventBus for T {
+    fn event_bus(&self) -> &Arc<dyn EventBus> {
+        &self.as_ref().event_bus
+    }
+}
+
+impl<T: AsRef<BaseEnvironment>> HasFeatureFlags for T {
+    fn feature_flags(&self) -> &dyn FeatureFlags {
+        self.as_ref().feature_flags.as_ref()
+    }
+}
+
+/// Builder returned by [`BaseEnvironment::with_overrides`]
+///
+/// Dependencies that are not replaced are shared with the original.
+#[must_use = "call `build` to get the environment"]
+pub struct EnvironmentOverrides {
+    base: BaseEnvironment,
+}
+
+impl EnvironmentOverrides {
+    /// Replace the clock
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.base.clock = Arc::new(clock);
+        self
+    }
+
+    /// Replace the event store
+    pub fn event_store(mut self, event_store: Arc<dyn EventStore>) -> Self {
+        self.base.event_store = event_store;
+        self
+    }
+
+    /// Replace the event bus
+    pub fn event_bus(mut self, event_bus: Arc<dyn EventBus>) -> Self {
+        self.base.event_bus = event_bus;
+        self
+    }
+
+    /// Replace the feature flags
+    ///
+    /// Shared, so a test can keep a handle to toggle flags while the
+    /// environment is in use.
+    pub fn feature_flags(mut self, feature_flags: Arc<dyn FeatureFlags>) -> Self {
+        self.base.feature_flags = feature_flags;
+        self
+    }
+
+    /// The environment with the overrides applied
+    #[must_use]
+    pub fn build(self) -> BaseEnvironment {
+        self.base
+    }
+}
+
+impl std::fmt::Debug for EnvironmentOverrides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnvironmentOverrides")
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,139 @@
+//! Payload codecs for [`SerializedEvent`](crate::event::SerializedEvent).
+//!
+//! An [`EventCodec`] turns a typed event into the bytes stored in
+//! `SerializedEvent::data` and back. A [`DomainEvent`](crate::event::DomainEvent)
+//! names its codec once, so [`SerializedEvent::encode`](crate::event::SerializedEvent::encode)
+//! and [`SerializedEvent::decode`](crate::event::SerializedEvent::decode)
+//! replace hand-written `serde_json::to_vec` calls and `event_type` strings.
+//!
+//! Available codecs:
+//!
+//! - [`JsonCodec`]: human-readable, easy to inspect in the database
+//! - [`BincodeCodec`]: the compact format used by [`Event::to_bytes`](crate::event::Event::to_bytes)
+//! - `ProstCodec` (feature `protobuf`): protobuf messages generated by `prost`,
+//!   for events shared with non-Rust services
+
+use crate::event::EventError;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Encodes and decodes event payloads of type `T`
+pub trait EventCodec<T>: Default + Send + Sync {
+    /// MIME type of the encoded payload (informational)
+    const CONTENT_TYPE: &'static str;
+
+    /// Encode an event to bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::SerializationError`] if the event cannot be encoded.
+    fn encode(&self, event: &T) -> Result<Vec<u8>, EventError>;
+
+    /// Decode an event from bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::DeserializationError`] if the bytes are not a valid `T`.
+    fn decode(&self, bytes: &[u8]) -> Result<T, EventError>;
+}
+
+/// JSON payloads via `serde_json`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl<T: Serialize + DeserializeOwned> EventCodec<T> for JsonCodec {
+    const CONTENT_TYPE: &'static str = "application/json";
+
+    fn encode(&self, event: &T) -> Result<Vec<u8>, EventError> {
+        serde_json::to_vec(event).map_err(|e| EventError::SerializationError(e.to_string()))
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, EventError> {
+        serde_json::from_slice(bytes).map_err(|e| EventError::DeserializationError(e.to_string()))
+    }
+}
+
+/// Binary payloads via `bincode`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BincodeCodec;
+
+impl<T: Serialize + DeserializeOwned> EventCodec<T> for BincodeCodec {
+    const CONTENT_TYPE: &'static str = "application/x-bincode";
+
+    fn encode(&self, event: &T) -> Result<Vec<u8>, EventError> {
+        bincode::serialize(event).map_err(|e| EventError::SerializationError(e.to_string()))
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, EventError> {
+        bincode::deserialize(bytes).map_err(|e| EventError::DeserializationError(e.to_string()))
+    }
+}
+
+/// Protobuf payloads via `prost`
+#[cfg(feature = "protobuf")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProstCodec;
+
+#[cfg(feature = "protobuf")]
+impl<T: prost::Message + Default> EventCodec<T> for ProstCodec {
+    const CONTENT_TYPE: &'static str = "application/x-protobuf";
+
+    fn encode(&self, event: &T) -> Result<Vec<u8>, EventError> {
+        Ok(event.encode_to_vec())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, EventError> {
+        T::decode(bytes).map_err(|e| EventError::DeserializationError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Placed {
+        order_id: String,
+        total: u64,
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)] // Tests can unwrap
+    fn test_json_and_bincode_roundtrip() {
+        let event = Placed {
+            order_id: "o-1".to_string(),
+            total: 42,
+        };
+
+        let json = JsonCodec.encode(&event).unwrap();
+        assert_eq!(json, br#"{"order_id":"o-1","total":42}"#);
+        assert_eq!(EventCodec::<Placed>::decode(&JsonCodec, &json).unwrap(), event);
+
+        let binary = BincodeCodec.encode(&event).unwrap();
+        assert_eq!(EventCodec::<Placed>::decode(&BincodeCodec, &binary).unwrap(), event);
+    }
+
+    #[test]
+    fn test_decode_garbage_fails() {
+        let result: Result<Placed, _> = JsonCodec.decode(b"not json");
+        assert!(matches!(result, Err(EventError::DeserializationError(_))));
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Shipped {
+        #[prost(string, tag = "1")]
+        order_id: String,
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[test]
+    #[allow(clippy::unwrap_used)] // Tests can unwrap
+    fn test_prost_roundtrip() {
+        let event = Shipped {
+            order_id: "o-1".to_string(),
+        };
+        let bytes = ProstCodec.encode(&event).unwrap();
+        assert_eq!(EventCodec::<Shipped>::decode(&ProstCodec, &bytes).unwrap(), event);
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! This module provides utilities for composing reducers in various ways:
 //! - **`combine_reducers`**: Run multiple reducers on the same state/action
+//! - **`CombineReducers`**: Statically dispatched pair of reducers
 //! - **`scope_reducer`**: Focus a reducer on a subset of state
+//! - **`pullback`**: Lift a feature reducer into the app's state, action and environment
+//! - **`for_each`**: Run a feature reducer on one element of a keyed collection
 //!
 //! # Examples
 //!
@@ -65,7 +68,9 @@
 //! ```
 
 use crate::effect::Effect;
-use crate::reducer::Reducer;
+use crate::reducer::{ReduceContext, Reducer};
+use std::collections::HashMap;
+use std::hash::Hash;
 
 /// Combines multiple reducers that operate on the same state and action types.
 ///
@@ -171,11 +176,21 @@ where
         state: &mut Self::State,
         action: Self::Action,
         env: &Self::Environment,
+    ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
+        self.reduce_with_context(state, action, env, &ReduceContext::default())
+    }
+
+    fn reduce_with_context(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+        context: &ReduceContext,
     ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
         let mut all_effects = smallvec::SmallVec::new();
 
         for reducer in &self.reducers {
-            let effects = reducer.reduce(state, action.clone(), env);
+            let effects = reducer.reduce_with_context(state, action.clone(), env, context);
             all_effects.extend(effects);
         }
 
@@ -304,6 +319,16 @@ where
         state: &mut Self::State,
         action: Self::Action,
         env: &Self::Environment,
+    ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
+        self.reduce_with_context(state, action, env, &ReduceContext::default())
+    }
+
+    fn reduce_with_context(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+        context: &ReduceContext,
     ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
         // Extract the sub-state
         let sub_state = (self.get_state)(state).clone();
@@ -312,7 +337,7 @@ where
         let mut mutable_sub_state = sub_state;
 
         // Run the reducer on the sub-state
-        let effects = self.reducer.reduce(&mut mutable_sub_state, action, env);
+        let effects = self.reducer.reduce_with_context(&mut mutable_sub_state, action, env, context);
 
         // Write the updated sub-state back
         (self.set_state)(state, mutable_sub_state);
@@ -321,6 +346,346 @@ where
     }
 }
 
+/// Runs two reducers in sequence without boxing
+///
+/// A statically dispatched alternative to [`combine_reducers`]; nest pairs to
+/// combine more than two (`CombineReducers(a, CombineReducers(b, c))`).
+/// Effects of the first reducer come first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CombineReducers<R1, R2>(pub R1, pub R2);
+
+impl<R1, R2> Reducer for CombineReducers<R1, R2>
+where
+    R1: Reducer,
+    R1::Action: Clone,
+    R2: Reducer<State = R1::State, Action = R1::Action, Environment = R1::Environment>,
+{
+    type State = R1::State;
+    type Action = R1::Action;
+    type Environment = R1::Environment;
+
+    fn reduce(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+    ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
+        self.reduce_with_context(state, action, env, &ReduceContext::default())
+    }
+
+    fn reduce_with_context(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+        context: &ReduceContext,
+    ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
+        let mut effects = self.0.reduce_with_context(state, action.clone(), env, context);
+        effects.extend(self.1.reduce_with_context(state, action, env, context));
+        effects
+    }
+}
+
+/// Extracts a child action from a parent action and embeds it back
+///
+/// The action half of [`pullback`] and [`for_each`], usually built from an
+/// enum variant:
+///
+/// ```
+/// use composable_rust_core::composition::ActionPrism;
+///
+/// enum CounterAction { Increment }
+/// enum AppAction { Counter(CounterAction), Reset }
+///
+/// let prism = ActionPrism::new(
+///     |action| match action {
+///         AppAction::Counter(action) => Some(action),
+///         _ => None,
+///     },
+///     AppAction::Counter,
+/// );
+/// assert!(prism.extract(AppAction::Reset).is_none());
+/// ```
+pub struct ActionPrism<A, SubA> {
+    extract: fn(A) -> Option<SubA>,
+    embed: fn(SubA) -> A,
+}
+
+impl<A, SubA> ActionPrism<A, SubA> {
+    /// Create a prism from its `extract` and `embed` halves
+    #[must_use]
+    pub const fn new(extract: fn(A) -> Option<SubA>, embed: fn(SubA) -> A) -> Self {
+        Self { extract, embed }
+    }
+
+    /// The child action carried by `action`, if any
+    pub fn extract(&self, action: A) -> Option<SubA> {
+        (self.extract)(action)
+    }
+
+    /// Wrap a child action in the parent action type
+    pub fn embed(&self, action: SubA) -> A {
+        (self.embed)(action)
+    }
+}
+
+impl<A, SubA> Clone for ActionPrism<A, SubA> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, SubA> Copy for ActionPrism<A, SubA> {}
+
+impl<A, SubA> std::fmt::Debug for ActionPrism<A, SubA> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActionPrism").finish_non_exhaustive()
+    }
+}
+
+/// Lifts a child reducer into a parent's state, action and environment
+///
+/// Parent actions the prism does not extract are ignored (no effects). The
+/// child reducer mutates its state in place through `state_lens`, and its
+/// effects are mapped back to parent actions with [`Effect::map`].
+///
+/// # Examples
+///
+/// ```
+/// use composable_rust_core::reducer::Reducer;
+/// use composable_rust_core::effect::Effect;
+/// use composable_rust_core::composition::{pullback, ActionPrism};
+///
+/// #[derive(Default)]
+/// struct CounterState { count: i32 }
+/// enum CounterAction { Increment }
+///
+/// struct CounterReducer;
+///
+/// impl Reducer for CounterReducer {
+///     type State = CounterState;
+///     type Action = CounterAction;
+///     type Environment = ();
+///
+///     fn reduce(&self, state: &mut Self::State, _action: Self::Action, _env: &Self::Environment) -> composable_rust_core::SmallVec<[Effect<Self::Action>; 4]> {
+///         state.count += 1;
+///         composable_rust_core::smallvec![Effect::None]
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct AppState { counter: CounterState }
+/// enum AppAction { Counter(CounterAction) }
+/// struct AppEnv { counter: () }
+///
+/// let app = pullback(
+///     CounterReducer,
+///     |state: &mut AppState| &mut state.counter,
+///     ActionPrism::new(|AppAction::Counter(action)| Some(action), AppAction::Counter),
+///     |env: &AppEnv| &env.counter,
+/// );
+///
+/// let mut state = AppState::default();
+/// let _ = app.reduce(&mut state, AppAction::Counter(CounterAction::Increment), &AppEnv { counter: () });
+/// assert_eq!(state.counter.count, 1);
+/// ```
+pub const fn pullback<S, A, E, R>(
+    reducer: R,
+    state_lens: fn(&mut S) -> &mut R::State,
+    action_prism: ActionPrism<A, R::Action>,
+    env_map: fn(&E) -> &R::Environment,
+) -> Pullback<S, A, E, R>
+where
+    R: Reducer,
+{
+    Pullback {
+        reducer,
+        state_lens,
+        action_prism,
+        env_map,
+    }
+}
+
+/// A child reducer lifted into a parent domain
+///
+/// Created by [`pullback`].
+pub struct Pullback<S, A, E, R>
+where
+    R: Reducer,
+{
+    reducer: R,
+    state_lens: fn(&mut S) -> &mut R::State,
+    action_prism: ActionPrism<A, R::Action>,
+    env_map: fn(&E) -> &R::Environment,
+}
+
+impl<S, A, E, R> Reducer for Pullback<S, A, E, R>
+where
+    R: Reducer,
+    R::Action: 'static,
+    A: Send + 'static,
+{
+    type State = S;
+    type Action = A;
+    type Environment = E;
+
+    fn reduce(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+    ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
+        self.reduce_with_context(state, action, env, &ReduceContext::default())
+    }
+
+    fn reduce_with_context(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+        context: &ReduceContext,
+    ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
+        let Some(action) = self.action_prism.extract(action) else {
+            return smallvec::SmallVec::new();
+        };
+
+        let embed = self.action_prism.embed;
+        self.reducer
+            .reduce_with_context((self.state_lens)(state), action, (self.env_map)(env), context)
+            .into_iter()
+            .map(|effect| effect.map(embed))
+            .collect()
+    }
+}
+
+/// Runs a child reducer on one element of a keyed collection
+///
+/// The prism extracts `(key, child action)` pairs; the child reducer runs on
+/// the element at `key`, and its effects are mapped back with the same key.
+/// Actions for keys not in the collection are ignored (no effects), as are
+/// parent actions the prism does not extract.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use composable_rust_core::reducer::Reducer;
+/// use composable_rust_core::effect::Effect;
+/// use composable_rust_core::composition::{for_each, ActionPrism};
+///
+/// #[derive(Default)]
+/// struct OrderState { items: u32 }
+/// enum OrderAction { AddItem }
+///
+/// struct OrderReducer;
+///
+/// impl Reducer for OrderReducer {
+///     type State = OrderState;
+///     type Action = OrderAction;
+///     type Environment = ();
+///
+///     fn reduce(&self, state: &mut Self::State, _action: Self::Action, _env: &Self::Environment) -> composable_rust_core::SmallVec<[Effect<Self::Action>; 4]> {
+///         state.items += 1;
+///         composable_rust_core::smallvec![Effect::None]
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct AppState { orders: HashMap<u64, OrderState> }
+/// enum AppAction { Order(u64, OrderAction) }
+///
+/// let app = for_each(
+///     OrderReducer,
+///     |state: &mut AppState| &mut state.orders,
+///     ActionPrism::new(
+///         |AppAction::Order(id, action)| Some((id, action)),
+///         |(id, action)| AppAction::Order(id, action),
+///     ),
+///     |env: &()| env,
+/// );
+///
+/// let mut state = AppState::default();
+/// state.orders.insert(7, OrderState::default());
+/// let _ = app.reduce(&mut state, AppAction::Order(7, OrderAction::AddItem), &());
+/// assert_eq!(state.orders[&7].items, 1);
+/// ```
+#[allow(clippy::implicit_hasher)] // Child collections use the default hasher
+pub const fn for_each<S, K, A, E, R>(
+    reducer: R,
+    collection: fn(&mut S) -> &mut HashMap<K, R::State>,
+    action_prism: ActionPrism<A, (K, R::Action)>,
+    env_map: fn(&E) -> &R::Environment,
+) -> ForEach<S, K, A, E, R>
+where
+    R: Reducer,
+{
+    ForEach {
+        reducer,
+        collection,
+        action_prism,
+        env_map,
+    }
+}
+
+/// A child reducer run on elements of a keyed collection
+///
+/// Created by [`for_each`].
+pub struct ForEach<S, K, A, E, R>
+where
+    R: Reducer,
+{
+    reducer: R,
+    collection: fn(&mut S) -> &mut HashMap<K, R::State>,
+    action_prism: ActionPrism<A, (K, R::Action)>,
+    env_map: fn(&E) -> &R::Environment,
+}
+
+impl<S, K, A, E, R> Reducer for ForEach<S, K, A, E, R>
+where
+    R: Reducer,
+    R::Action: 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    A: Send + 'static,
+{
+    type State = S;
+    type Action = A;
+    type Environment = E;
+
+    fn reduce(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+    ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
+        self.reduce_with_context(state, action, env, &ReduceContext::default())
+    }
+
+    fn reduce_with_context(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+        context: &ReduceContext,
+    ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
+        let Some((key, action)) = self.action_prism.extract(action) else {
+            return smallvec::SmallVec::new();
+        };
+        let Some(element) = (self.collection)(state).get_mut(&key) else {
+            return smallvec::SmallVec::new();
+        };
+
+        let embed = self.action_prism.embed;
+        self.reducer
+            .reduce_with_context(element, action, (self.env_map)(env), context)
+            .into_iter()
+            .map(|effect| {
+                let key = key.clone();
+                effect.map(move |action| embed((key.clone(), action)))
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,7 +777,7 @@ mod tests {
         value: i32,
     }
 
-    #[derive(Clone)]
+    #[derive(Clone, Debug, PartialEq)]
     enum SubAction {
         Add(i32),
         Multiply(i32),
@@ -474,4 +839,167 @@ mod tests {
         assert_eq!(state.sub.value, 16);
         assert_eq!(state.other, "test");
     }
+
+    #[test]
+    fn test_combine_reducers_pair() {
+        let combined = CombineReducers(CounterReducer, NameReducer);
+        let mut state = TestState::default();
+
+        let _ = combined.reduce(&mut state, TestAction::Increment, &());
+        let effects = combined.reduce(&mut state, TestAction::SetName("Bob".to_string()), &());
+
+        assert_eq!(state.counter, 1);
+        assert_eq!(state.name, "Bob");
+        assert_eq!(effects.len(), 2);
+    }
+
+    // Pullback and for_each tests
+    struct DelayingReducer;
+
+    impl Reducer for DelayingReducer {
+        type State = SubState;
+        type Action = SubAction;
+        type Environment = i32;
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            let _ = SubReducer.reduce(state, action, &());
+            smallvec![Effect::Delay {
+                duration: std::time::Duration::from_secs(1),
+                action: Box::new(SubAction::Add(*env)),
+            }]
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum AppAction {
+        Sub(SubAction),
+        Item(&'static str, SubAction),
+        Other,
+    }
+
+    struct AppEnv {
+        step: i32,
+    }
+
+    #[derive(Default)]
+    struct AppState {
+        sub: SubState,
+        items: HashMap<&'static str, SubState>,
+    }
+
+    fn delayed_action(effect: Effect<AppAction>) -> Option<AppAction> {
+        match effect {
+            Effect::Delay { action, .. } => Some(*action),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_pullback() {
+        let app = pullback(
+            DelayingReducer,
+            |state: &mut AppState| &mut state.sub,
+            ActionPrism::new(
+                |action| match action {
+                    AppAction::Sub(action) => Some(action),
+                    _ => None,
+                },
+                AppAction::Sub,
+            ),
+            |env: &AppEnv| &env.step,
+        );
+        let env = AppEnv { step: 4 };
+        let mut state = AppState::default();
+
+        let mut effects = app.reduce(&mut state, AppAction::Sub(SubAction::Add(2)), &env);
+        assert_eq!(state.sub.value, 2);
+        assert_eq!(delayed_action(effects.remove(0)), Some(AppAction::Sub(SubAction::Add(4))));
+
+        assert!(app.reduce(&mut state, AppAction::Other, &env).is_empty());
+    }
+
+    #[test]
+    fn test_for_each() {
+        let app = for_each(
+            DelayingReducer,
+            |state: &mut AppState| &mut state.items,
+            ActionPrism::new(
+                |action| match action {
+                    AppAction::Item(key, action) => Some((key, action)),
+                    _ => None,
+                },
+                |(key, action)| AppAction::Item(key, action),
+            ),
+            |env: &AppEnv| &env.step,
+        );
+        let env = AppEnv { step: 1 };
+        let mut state = AppState::default();
+        state.items.insert("a", SubState { value: 1 });
+        state.items.insert("b", SubState { value: 1 });
+
+        let mut effects = app.reduce(&mut state, AppAction::Item("b", SubAction::Multiply(5)), &env);
+        assert_eq!(state.items["a"].value, 1);
+        assert_eq!(state.items["b"].value, 5);
+        assert_eq!(
+            delayed_action(effects.remove(0)),
+            Some(AppAction::Item("b", SubAction::Add(1)))
+        );
+
+        // Unknown keys are ignored
+        assert!(app.reduce(&mut state, AppAction::Item("c", SubAction::Add(1)), &env).is_empty());
+        assert!(!state.items.contains_key("c"));
+    }
+
+    /// Counts live actions, ignoring replayed ones
+    struct LiveCounter;
+
+    impl Reducer for LiveCounter {
+        type State = TestState;
+        type Action = TestAction;
+        type Environment = ();
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            _action: Self::Action,
+            _env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            state.counter += 1;
+            SmallVec::new()
+        }
+
+        fn reduce_with_context(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            env: &Self::Environment,
+            context: &ReduceContext,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            if context.is_replay {
+                return SmallVec::new();
+            }
+            self.reduce(state, action, env)
+        }
+    }
+
+    #[test]
+    fn test_combinators_forward_reduce_context() {
+        let mut state = TestState::default();
+        let replay = ReduceContext::replay();
+
+        let combined = CombineReducers(NameReducer, LiveCounter);
+        let _ = combined.reduce_with_context(&mut state, TestAction::Increment, &(), &replay);
+        assert_eq!(state.counter, 0);
+        let _ = combined.reduce(&mut state, TestAction::Increment, &());
+        assert_eq!(state.counter, 1);
+
+        let boxed = combine_reducers(vec![Box::new(LiveCounter)]);
+        let _ = boxed.reduce_with_context(&mut state, TestAction::Increment, &(), &replay);
+        assert_eq!(state.counter, 1);
+    }
 }
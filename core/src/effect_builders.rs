@@ -0,0 +1,494 @@
+//! Builders for event store and event bus effects
+//!
+//! Constructing [`EventStoreOperation`] and [`EventBusOperation`] variants by
+//! hand means cloning the `Arc`, boxing both callbacks and spelling out every
+//! field. The builders here take the environment's store or bus by reference,
+//! default both callbacks to producing no action, and only ask for what
+//! differs between call sites. They produce the same variants as the
+//! `append_events!`-style macros in [`effect_macros`](crate::effect_macros),
+//! so new optional fields can be added without touching reducers.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use composable_rust_core::effect_builders::{EventBusEffect, EventStoreEffect};
+//!
+//! smallvec![
+//!     EventStoreEffect::append(&env.event_store, "order-123", events)
+//!         .expected_version(state.version)
+//!         .on_success(|version| Some(OrderAction::EventsAppended { version }))
+//!         .on_error(|error| Some(OrderAction::AppendFailed { error: error.to_string() }))
+//!         .into_effect(),
+//!     EventBusEffect::publish(&env.event_bus, "order-events", event).into_effect(),
+//! ]
+//! ```
+
+use crate::effect::{Effect, EventBusOperation, EventStoreOperation};
+use crate::event::{EventMetadata, SerializedEvent};
+use crate::event_bus::{EventBus, EventBusError, PublishOptions};
+use crate::event_store::{EventStore, EventStoreError};
+use crate::stream::{StreamId, Version};
+use std::sync::Arc;
+
+type Callback<T, A> = Box<dyn Fn(T) -> Option<A> + Send + Sync>;
+
+fn ignore<T, A>() -> Callback<T, A> {
+    Box::new(|_| None)
+}
+
+/// Entry points for `Effect::EventStore` builders
+#[derive(Debug, Clone, Copy)]
+pub struct EventStoreEffect;
+
+impl EventStoreEffect {
+    /// Append `events` to `stream_id`
+    pub fn append<A>(
+        event_store: &Arc<dyn EventStore>,
+        stream_id: impl Into<StreamId>,
+        events: Vec<SerializedEvent>,
+    ) -> AppendEvents<A> {
+        AppendEvents {
+            event_store: Arc::clone(event_store),
+            stream_id: stream_id.into(),
+            expected_version: None,
+            events,
+            metadata: None,
+            idempotency_key: None,
+            on_success: ignore(),
+            on_error: ignore(),
+        }
+    }
+
+    /// Load the events of `stream_id`
+    pub fn load<A>(event_store: &Arc<dyn EventStore>, stream_id: impl Into<StreamId>) -> LoadEvents<A> {
+        LoadEvents {
+            event_store: Arc::clone(event_store),
+            stream_id: stream_id.into(),
+            from_version: None,
+            on_success: ignore(),
+            on_error: ignore(),
+        }
+    }
+
+    /// Load the events of `stream_id` in pages of at most `page_size` events
+    ///
+    /// A `page_size` of 0 is treated as 1.
+    pub fn load_paged<A>(
+        event_store: &Arc<dyn EventStore>,
+        stream_id: impl Into<StreamId>,
+        page_size: usize,
+    ) -> LoadEventsPaged<A> {
+        LoadEventsPaged {
+            event_store: Arc::clone(event_store),
+            stream_id: stream_id.into(),
+            from_version: Version::new(0),
+            page_size: page_size.max(1),
+            on_page: ignore(),
+            on_complete: ignore(),
+            on_error: ignore(),
+        }
+    }
+
+    /// Save `state` as the snapshot of `stream_id` at `version`
+    pub fn save_snapshot<A>(
+        event_store: &Arc<dyn EventStore>,
+        stream_id: impl Into<StreamId>,
+        version: Version,
+        state: Vec<u8>,
+    ) -> SaveSnapshot<A> {
+        SaveSnapshot {
+            event_store: Arc::clone(event_store),
+            stream_id: stream_id.into(),
+            version,
+            state,
+            on_success: ignore(),
+            on_error: ignore(),
+        }
+    }
+
+    /// Load the latest snapshot of `stream_id`
+    pub fn load_snapshot<A>(
+        event_store: &Arc<dyn EventStore>,
+        stream_id: impl Into<StreamId>,
+    ) -> LoadSnapshot<A> {
+        LoadSnapshot {
+            event_store: Arc::clone(event_store),
+            stream_id: stream_id.into(),
+            on_success: ignore(),
+            on_error: ignore(),
+        }
+    }
+}
+
+/// Entry points for `Effect::PublishEvent` builders
+#[derive(Debug, Clone, Copy)]
+pub struct EventBusEffect;
+
+impl EventBusEffect {
+    /// Publish `event` to `topic`
+    pub fn publish<A>(
+        event_bus: &Arc<dyn EventBus>,
+        topic: impl Into<String>,
+        event: SerializedEvent,
+    ) -> Publish<A> {
+        Publish {
+            event_bus: Arc::clone(event_bus),
+            topic: topic.into(),
+            event,
+            options: PublishOptions::default(),
+            on_success: ignore(),
+            on_error: ignore(),
+        }
+    }
+}
+
+/// Builder for an `AppendEvents` effect
+///
+/// Created by [`EventStoreEffect::append`].
+#[must_use = "builders do nothing until converted with `into_effect`"]
+pub struct AppendEvents<A> {
+    event_store: Arc<dyn EventStore>,
+    stream_id: StreamId,
+    expected_version: Option<Version>,
+    events: Vec<SerializedEvent>,
+    metadata: Option<EventMetadata>,
+    idempotency_key: Option<String>,
+    on_success: Callback<Version, A>,
+    on_error: Callback<EventStoreError, A>,
+}
+
+impl<A> AppendEvents<A> {
+    /// Require the stream to be at `version` (optimistic concurrency)
+    ///
+    /// Accepts a `Version` or an `Option<Version>` (as kept in aggregate state).
+    pub fn expected_version(mut self, version: impl Into<Option<Version>>) -> Self {
+        self.expected_version = version.into();
+        self
+    }
+
+    /// Merge `metadata` into each appended event
+    pub fn metadata(mut self, metadata: EventMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Identify the write by `key` so retries cannot append it twice
+    ///
+    /// See [`EventStore::append_events_idempotent`].
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Action produced from the new stream version
+    pub fn on_success<F>(mut self, on_success: F) -> Self
+    where
+        F: Fn(Version) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_success = Box::new(on_success);
+        self
+    }
+
+    /// Action produced from the append error
+    pub fn on_error<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(EventStoreError) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_error = Box::new(on_error);
+        self
+    }
+
+    /// Build the effect
+    #[must_use]
+    pub fn into_effect(self) -> Effect<A> {
+        Effect::EventStore(EventStoreOperation::AppendEvents {
+            event_store: self.event_store,
+            stream_id: self.stream_id,
+            expected_version: self.expected_version,
+            events: self.events,
+            metadata: self.metadata,
+            idempotency_key: self.idempotency_key,
+            on_success: self.on_success,
+            on_error: self.on_error,
+        })
+    }
+}
+
+/// Builder for a `LoadEvents` effect
+///
+/// Created by [`EventStoreEffect::load`].
+#[must_use = "builders do nothing until converted with `into_effect`"]
+pub struct LoadEvents<A> {
+    event_store: Arc<dyn EventStore>,
+    stream_id: StreamId,
+    from_version: Option<Version>,
+    on_success: Callback<Vec<SerializedEvent>, A>,
+    on_error: Callback<EventStoreError, A>,
+}
+
+impl<A> LoadEvents<A> {
+    /// Only load events from `version` onwards
+    pub const fn from_version(mut self, version: Version) -> Self {
+        self.from_version = Some(version);
+        self
+    }
+
+    /// Action produced from the loaded events
+    pub fn on_success<F>(mut self, on_success: F) -> Self
+    where
+        F: Fn(Vec<SerializedEvent>) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_success = Box::new(on_success);
+        self
+    }
+
+    /// Action produced from the load error
+    pub fn on_error<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(EventStoreError) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_error = Box::new(on_error);
+        self
+    }
+
+    /// Build the effect
+    #[must_use]
+    pub fn into_effect(self) -> Effect<A> {
+        Effect::EventStore(EventStoreOperation::LoadEvents {
+            event_store: self.event_store,
+            stream_id: self.stream_id,
+            from_version: self.from_version,
+            on_success: self.on_success,
+            on_error: self.on_error,
+        })
+    }
+}
+
+/// Builder for a `LoadEventsPaged` effect
+///
+/// Created by [`EventStoreEffect::load_paged`].
+#[must_use = "builders do nothing until converted with `into_effect`"]
+pub struct LoadEventsPaged<A> {
+    event_store: Arc<dyn EventStore>,
+    stream_id: StreamId,
+    from_version: Version,
+    page_size: usize,
+    on_page: Callback<Vec<SerializedEvent>, A>,
+    on_complete: Callback<Version, A>,
+    on_error: Callback<EventStoreError, A>,
+}
+
+impl<A> LoadEventsPaged<A> {
+    /// Start loading at `version` instead of the start of the stream
+    pub const fn from_version(mut self, version: Version) -> Self {
+        self.from_version = version;
+        self
+    }
+
+    /// Action produced from each page of events
+    pub fn on_page<F>(mut self, on_page: F) -> Self
+    where
+        F: Fn(Vec<SerializedEvent>) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_page = Box::new(on_page);
+        self
+    }
+
+    /// Action produced after the last page, from the version to resume loading at
+    pub fn on_complete<F>(mut self, on_complete: F) -> Self
+    where
+        F: Fn(Version) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_complete = Box::new(on_complete);
+        self
+    }
+
+    /// Action produced from the error that stopped the load
+    pub fn on_error<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(EventStoreError) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_error = Box::new(on_error);
+        self
+    }
+
+    /// Build the effect
+    #[must_use]
+    pub fn into_effect(self) -> Effect<A> {
+        Effect::EventStore(EventStoreOperation::LoadEventsPaged {
+            event_store: self.event_store,
+            stream_id: self.stream_id,
+            from_version: self.from_version,
+            page_size: self.page_size,
+            on_page: self.on_page,
+            on_complete: self.on_complete,
+            on_error: self.on_error,
+        })
+    }
+}
+
+/// Builder for a `SaveSnapshot` effect
+///
+/// Created by [`EventStoreEffect::save_snapshot`].
+#[must_use = "builders do nothing until converted with `into_effect`"]
+pub struct SaveSnapshot<A> {
+    event_store: Arc<dyn EventStore>,
+    stream_id: StreamId,
+    version: Version,
+    state: Vec<u8>,
+    on_success: Callback<(), A>,
+    on_error: Callback<EventStoreError, A>,
+}
+
+impl<A> SaveSnapshot<A> {
+    /// Action produced once the snapshot is saved
+    pub fn on_success<F>(mut self, on_success: F) -> Self
+    where
+        F: Fn() -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_success = Box::new(move |()| on_success());
+        self
+    }
+
+    /// Action produced from the save error
+    pub fn on_error<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(EventStoreError) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_error = Box::new(on_error);
+        self
+    }
+
+    /// Build the effect
+    #[must_use]
+    pub fn into_effect(self) -> Effect<A> {
+        Effect::EventStore(EventStoreOperation::SaveSnapshot {
+            event_store: self.event_store,
+            stream_id: self.stream_id,
+            version: self.version,
+            state: self.state,
+            on_success: self.on_success,
+            on_error: self.on_error,
+        })
+    }
+}
+
+/// Builder for a `LoadSnapshot` effect
+///
+/// Created by [`EventStoreEffect::load_snapshot`].
+#[must_use = "builders do nothing until converted with `into_effect`"]
+pub struct LoadSnapshot<A> {
+    event_store: Arc<dyn EventStore>,
+    stream_id: StreamId,
+    on_success: Callback<Option<(Version, Vec<u8>)>, A>,
+    on_error: Callback<EventStoreError, A>,
+}
+
+impl<A> LoadSnapshot<A> {
+    /// Action produced from the snapshot, if one exists
+    pub fn on_success<F>(mut self, on_success: F) -> Self
+    where
+        F: Fn(Option<(Version, Vec<u8>)>) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_success = Box::new(on_success);
+        self
+    }
+
+    /// Action produced from the load error
+    pub fn on_error<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(EventStoreError) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_error = Box::new(on_error);
+        self
+    }
+
+    /// Build the effect
+    #[must_use]
+    pub fn into_effect(self) -> Effect<A> {
+        Effect::EventStore(EventStoreOperation::LoadSnapshot {
+            event_store: self.event_store,
+            stream_id: self.stream_id,
+            on_success: self.on_success,
+            on_error: self.on_error,
+        })
+    }
+}
+
+/// Builder for a `Publish` effect
+///
+/// Created by [`EventBusEffect::publish`].
+#[must_use = "builders do nothing until converted with `into_effect`"]
+pub struct Publish<A> {
+    event_bus: Arc<dyn EventBus>,
+    topic: String,
+    event: SerializedEvent,
+    options: PublishOptions,
+    on_success: Callback<(), A>,
+    on_error: Callback<EventBusError, A>,
+}
+
+impl<A> Publish<A> {
+    /// Route the event by `key`, keeping events with the same key in order
+    pub fn partition_key(mut self, key: impl Into<String>) -> Self {
+        self.options.partition_key = Some(key.into());
+        self
+    }
+
+    /// Replace the publish options (partition key, headers, delivery timeout)
+    pub fn options(mut self, options: PublishOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Action produced once the event is published
+    pub fn on_success<F>(mut self, on_success: F) -> Self
+    where
+        F: Fn() -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_success = Box::new(move |()| on_success());
+        self
+    }
+
+    /// Action produced from the publish error
+    pub fn on_error<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(EventBusError) -> Option<A> + Send + Sync + 'static,
+    {
+        self.on_error = Box::new(on_error);
+        self
+    }
+
+    /// Build the effect
+    #[must_use]
+    pub fn into_effect(self) -> Effect<A> {
+        Effect::PublishEvent(EventBusOperation::Publish {
+            event_bus: self.event_bus,
+            topic: self.topic,
+            event: self.event,
+            options: self.options,
+            on_success: self.on_success,
+            on_error: self.on_error,
+        })
+    }
+}
+
+macro_rules! impl_into_effect {
+    ($($builder:ident),*) => {
+        $(
+            impl<A> From<$builder<A>> for Effect<A> {
+                fn from(builder: $builder<A>) -> Self {
+                    builder.into_effect()
+                }
+            }
+
+            impl<A> std::fmt::Debug for $builder<A> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_struct(stringify!($builder)).finish_non_exhaustive()
+                }
+            }
+        )*
+    };
+}
+
+impl_into_effect!(AppendEvents, LoadEvents, LoadEventsPaged, SaveSnapshot, LoadSnapshot, Publish);
@@ -0,0 +1,329 @@
+//! Comparable summaries of effects for tests
+//!
+//! [`Effect`] holds futures, streams, trait objects and callbacks, so it can
+//! be neither compared nor printed in full: `Debug` shows
+//! `Effect::Future(<future>)` and hides every callback. [`Effect::describe`]
+//! projects an effect onto an [`EffectDescription`] — the kind of effect plus
+//! the plain data it carries (stream ids, versions, event types, topics,
+//! durations, delayed actions) — which implements `PartialEq` and a stable
+//! `Debug`, so reducer output can be asserted with `assert_eq!` or
+//! snapshot-tested:
+//!
+//! ```rust,ignore
+//! let effects = reducer.reduce(&mut state, OrderAction::Place { .. }, &env);
+//! let described: Vec<_> = effects.iter().map(Effect::describe).collect();
+//! insta::assert_debug_snapshot!(described);
+//! ```
+//!
+//! Callbacks (`on_success`, `on_error`) and the stores and buses an operation
+//! runs against are not part of the description.
+
+use crate::effect::{Effect, EventBusOperation, EventStoreOperation, QueryOperation};
+use crate::read_model::ReadModelQuery;
+use crate::stream::Version;
+use std::time::Duration;
+
+/// Structured, comparable summary of an [`Effect`]; see the [module documentation](self)
+#[derive(Debug, Clone, PartialEq)]
+pub enum EffectDescription {
+    /// `Effect::None`
+    None,
+    /// `Effect::Parallel`, with its effects in order
+    Parallel(Vec<EffectDescription>),
+    /// `Effect::Sequential`, with its effects in order
+    Sequential(Vec<EffectDescription>),
+    /// `Effect::Delay`
+    Delay {
+        /// How long the action is delayed
+        duration: Duration,
+        /// `Debug` rendering of the delayed action
+        action: String,
+    },
+    /// `Effect::DelayKeyed`
+    DelayKeyed {
+        /// Timer key
+        key: String,
+        /// How long the action is delayed
+        duration: Duration,
+        /// `Debug` rendering of the delayed action
+        action: String,
+    },
+    /// `Effect::Future` or `Effect::FutureWithCancel` (opaque until executed)
+    Future,
+    /// `Effect::Stream` (opaque until executed)
+    Stream,
+    /// `EventStoreOperation::AppendEvents`
+    AppendEvents {
+        /// Target stream
+        stream_id: String,
+        /// Expected current version, if checked
+        expected_version: Option<u64>,
+        /// Type of each appended event, in order
+        event_types: Vec<String>,
+    },
+    /// `EventStoreOperation::LoadEvents`
+    LoadEvents {
+        /// Source stream
+        stream_id: String,
+        /// First version to load, if not the start of the stream
+        from_version: Option<u64>,
+    },
+    /// `EventStoreOperation::LoadEventsPaged`
+    LoadEventsPaged {
+        /// Source stream
+        stream_id: String,
+        /// First version to load
+        from_version: u64,
+        /// Maximum number of events per page
+        page_size: usize,
+    },
+    /// `EventStoreOperation::SaveSnapshot`
+    SaveSnapshot {
+        /// Snapshotted stream
+        stream_id: String,
+        /// Version the snapshot reflects
+        version: u64,
+        /// Size of the serialized state in bytes
+        state_size: usize,
+    },
+    /// `EventStoreOperation::LoadSnapshot`
+    LoadSnapshot {
+        /// Snapshotted stream
+        stream_id: String,
+    },
+    /// `EventBusOperation::Publish`
+    Publish {
+        /// Destination topic
+        topic: String,
+        /// Type of the published event
+        event_type: String,
+        /// Partition key the event is routed by, if any
+        partition_key: Option<String>,
+    },
+    /// `QueryOperation::Get`
+    QueryGet {
+        /// Read model collection
+        collection: String,
+        /// Document key
+        key: String,
+    },
+    /// `QueryOperation::Query`
+    Query(ReadModelQuery),
+    /// `Effect::Costed`
+    Costed {
+        /// Cost category
+        kind: String,
+        /// Cost weight
+        weight: u64,
+        /// The wrapped effect
+        effect: Box<EffectDescription>,
+    },
+    /// `Effect::Retrying`
+    Retrying {
+        /// Maximum attempts of the wrapped operations
+        max_attempts: u32,
+        /// The wrapped effect
+        effect: Box<EffectDescription>,
+    },
+}
+
+impl<Action: std::fmt::Debug> Effect<Action> {
+    /// Summarize this effect for assertions; see [`EffectDescription`]
+    #[must_use]
+    pub fn describe(&self) -> EffectDescription {
+        match self {
+            Effect::None => EffectDescription::None,
+            Effect::Parallel(effects) => {
+                EffectDescription::Parallel(effects.iter().map(Effect::describe).collect())
+            },
+            Effect::Sequential(effects) => {
+                EffectDescription::Sequential(effects.iter().map(Effect::describe).collect())
+            },
+            Effect::Delay { duration, action } => EffectDescription::Delay {
+                duration: *duration,
+                action: format!("{action:?}"),
+            },
+            Effect::DelayKeyed {
+                key,
+                duration,
+                action,
+            } => EffectDescription::DelayKeyed {
+                key: key.to_string(),
+                duration: *duration,
+                action: format!("{action:?}"),
+            },
+            Effect::Future(_) | Effect::FutureWithCancel(_) => EffectDescription::Future,
+            Effect::Stream(_) => EffectDescription::Stream,
+            Effect::EventStore(op) => match op {
+                EventStoreOperation::AppendEvents {
+                    stream_id,
+                    expected_version,
+                    events,
+                    ..
+                } => EffectDescription::AppendEvents {
+                    stream_id: stream_id.to_string(),
+                    expected_version: expected_version.map(Version::value),
+                    event_types: events
+                        .iter()
+                        .map(|event| event.event_type.clone())
+                        .collect(),
+                },
+                EventStoreOperation::LoadEvents {
+                    stream_id,
+                    from_version,
+                    ..
+                } => EffectDescription::LoadEvents {
+                    stream_id: stream_id.to_string(),
+                    from_version: from_version.map(Version::value),
+                },
+                EventStoreOperation::LoadEventsPaged {
+                    stream_id,
+                    from_version,
+                    page_size,
+                    ..
+                } => EffectDescription::LoadEventsPaged {
+                    stream_id: stream_id.to_string(),
+                    from_version: from_version.value(),
+                    page_size: *page_size,
+                },
+                EventStoreOperation::SaveSnapshot {
+                    stream_id,
+                    version,
+                    state,
+                    ..
+                } => EffectDescription::SaveSnapshot {
+                    stream_id: stream_id.to_string(),
+                    version: version.value(),
+                    state_size: state.len(),
+                },
+                EventStoreOperation::LoadSnapshot { stream_id, .. } => {
+                    EffectDescription::LoadSnapshot {
+                        stream_id: stream_id.to_string(),
+                    }
+                },
+            },
+            Effect::PublishEvent(EventBusOperation::Publish {
+                topic,
+                event,
+                options,
+                ..
+            }) => EffectDescription::Publish {
+                topic: topic.clone(),
+                event_type: event.event_type.clone(),
+                partition_key: options.partition_key.clone(),
+            },
+            Effect::Query(op) => match op {
+                QueryOperation::Get {
+                    collection, key, ..
+                } => EffectDescription::QueryGet {
+                    collection: collection.clone(),
+                    key: key.clone(),
+                },
+                QueryOperation::Query { query, .. } => EffectDescription::Query(query.clone()),
+            },
+            Effect::Costed { cost, effect } => EffectDescription::Costed {
+                kind: cost.kind.to_string(),
+                weight: cost.weight,
+                effect: Box::new(effect.describe()),
+            },
+            Effect::Retrying { retry, effect } => EffectDescription::Retrying {
+                max_attempts: retry.max_attempts,
+                effect: Box::new(effect.describe()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::EffectCost;
+    use crate::event::SerializedEvent;
+    use crate::event_bus::{EventBus, EventBusError, EventStream, PublishOptions};
+    use std::sync::Arc;
+
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)] // Fields are read through `Debug`
+    enum TestAction {
+        TimedOut { order_id: u32 },
+    }
+
+    struct NullBus;
+
+    impl EventBus for NullBus {
+        fn publish(
+            &self,
+            _topic: &str,
+            _event: &SerializedEvent,
+        ) -> std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<(), EventBusError>> + Send + '_>,
+        > {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn subscribe(
+            &self,
+            _topics: &[&str],
+        ) -> std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<EventStream, EventBusError>> + Send + '_>,
+        > {
+            Box::pin(async { Err(EventBusError::ConnectionFailed("unused".to_string())) })
+        }
+    }
+
+    #[test]
+    fn test_describe_nested_effects() {
+        let effect: Effect<TestAction> = Effect::Parallel(vec![
+            Effect::Delay {
+                duration: Duration::from_secs(30),
+                action: Box::new(TestAction::TimedOut { order_id: 7 }),
+            },
+            Effect::PublishEvent(EventBusOperation::Publish {
+                event_bus: Arc::new(NullBus),
+                topic: "orders".to_string(),
+                event: SerializedEvent::new("OrderPlaced.v1".to_string(), vec![], None),
+                options: PublishOptions::new().with_partition_key("order-7"),
+                on_success: Box::new(|()| None),
+                on_error: Box::new(|_| None),
+            })
+            .with_cost(EffectCost::new("publish", 2)),
+            Effect::Future(Box::pin(async { None })),
+        ]);
+
+        assert_eq!(
+            effect.describe(),
+            EffectDescription::Parallel(vec![
+                EffectDescription::Delay {
+                    duration: Duration::from_secs(30),
+                    action: "TimedOut { order_id: 7 }".to_string(),
+                },
+                EffectDescription::Costed {
+                    kind: "publish".to_string(),
+                    weight: 2,
+                    effect: Box::new(EffectDescription::Publish {
+                        topic: "orders".to_string(),
+                        event_type: "OrderPlaced.v1".to_string(),
+                        partition_key: Some("order-7".to_string()),
+                    }),
+                },
+                EffectDescription::Future,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_equal_effects_describe_equal() {
+        let timeout = |order_id| Effect::DelayKeyed {
+            key: "payment-timeout".into(),
+            duration: Duration::from_secs(5),
+            action: Box::new(TestAction::TimedOut { order_id }),
+        };
+
+        let first: Effect<TestAction> = Effect::Sequential(vec![Effect::None, timeout(1)]);
+        let same: Effect<TestAction> = Effect::Sequential(vec![Effect::None, timeout(1)]);
+        let other: Effect<TestAction> = Effect::Sequential(vec![Effect::None, timeout(2)]);
+
+        assert_eq!(first.describe(), same.describe());
+        assert_ne!(first.describe(), other.describe());
+    }
+}
@@ -2,6 +2,19 @@
 //!
 //! These macros reduce boilerplate when creating `Effect` variants, particularly
 //! for event sourcing and event bus operations.
+//!
+//! `append_events!`, `publish!` and `delayed!` also take their arguments
+//! positionally. Their `on_success` / `on_error` callbacks are optional and
+//! default to producing no action:
+//!
+//! ```rust,ignore
+//! smallvec![
+//!     append_events!(env.event_store, "order-123", Some(state.version), events,
+//!         on_error: |error| Some(OrderAction::AppendFailed { error: error.to_string() })),
+//!     publish!(env.event_bus, "order-events", event),
+//!     delayed!(Duration::from_secs(30), OrderAction::TimeoutExpired),
+//! ]
+//! ```
 
 /// Create an `Effect::EventStore` with `AppendEvents` operation
 ///
@@ -30,6 +43,11 @@
 ///     on_success: |version| Some(OrderAction::EventsAppended { version }),
 ///     on_error: |error| Some(OrderAction::AppendFailed { error: error.to_string() })
 /// }
+///
+/// // Positional: store, stream, expected version, events, optional callbacks
+/// append_events!(env.event_store, "order-123", None, vec![serialized_event]);
+/// append_events!(env.event_store, "order-123", Some(Version::new(5)), events,
+///     on_success: |version| Some(OrderAction::EventsAppended { version }));
 /// ```
 #[macro_export]
 macro_rules! append_events {
@@ -50,6 +68,7 @@ macro_rules! append_events {
                 expected_version: $expected,
                 events: $events,
                 metadata: $metadata,
+                idempotency_key: None,
                 on_success: ::std::boxed::Box::new(move |$success_param| $success_body),
                 on_error: ::std::boxed::Box::new(move |$error_param| $error_body),
             }
@@ -71,11 +90,44 @@ macro_rules! append_events {
                 expected_version: $expected,
                 events: $events,
                 metadata: None,
+                idempotency_key: None,
                 on_success: ::std::boxed::Box::new(move |$success_param| $success_body),
                 on_error: ::std::boxed::Box::new(move |$error_param| $error_body),
             }
         )
     };
+    // Positional, with optional callbacks
+    (
+        $store:expr, $stream:expr, $expected:expr, $events:expr
+        $(, on_success: |$success_param:pat_param| $success_body:expr)?
+        $(, on_error: |$error_param:pat_param| $error_body:expr)?
+        $(,)?
+    ) => {
+        $crate::effect::Effect::EventStore(
+            $crate::effect::EventStoreOperation::AppendEvents {
+                event_store: ::std::sync::Arc::clone(&$store),
+                stream_id: $crate::stream::StreamId::new($stream),
+                expected_version: $expected,
+                events: $events,
+                metadata: None,
+                idempotency_key: None,
+                on_success: $crate::__effect_callback!($(|$success_param| $success_body)?),
+                on_error: $crate::__effect_callback!($(|$error_param| $error_body)?),
+            }
+        )
+    };
+}
+
+/// Boxed effect callback, or one producing no action when none is given
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __effect_callback {
+    () => {
+        ::std::boxed::Box::new(|_| None)
+    };
+    (|$param:pat_param| $body:expr) => {
+        ::std::boxed::Box::new(move |$param| $body)
+    };
 }
 
 /// Create an `Effect::EventStore` with `LoadEvents` operation
@@ -143,6 +195,7 @@ macro_rules! publish_event {
                 event_bus: ::std::sync::Arc::clone(&$bus),
                 topic: $topic.to_string(),
                 event: $event,
+                options: $crate::event_bus::PublishOptions::default(),
                 on_success: ::std::boxed::Box::new(move |()| $success_body),
                 on_error: ::std::boxed::Box::new(move |$error_param| $error_body),
             }
@@ -150,6 +203,44 @@ macro_rules! publish_event {
     };
 }
 
+/// Create an `Effect::PublishEvent` from positional arguments
+///
+/// Takes the bus, topic and event, then optional `on_success` /
+/// `on_error` callbacks; missing callbacks produce no action. The topic is
+/// a string or a typed [`Topic`](crate::topic::Topic).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use composable_rust_core::publish;
+///
+/// publish!(env.event_bus, "order-events", serialized_event);
+/// publish!(env.event_bus, ORDER_EVENTS, ORDER_EVENTS.encode(&placed, None)?);
+///
+/// publish!(env.event_bus, "order-events", serialized_event,
+///     on_error: |error| Some(OrderAction::PublishFailed { error: error.to_string() }));
+/// ```
+#[macro_export]
+macro_rules! publish {
+    (
+        $bus:expr, $topic:expr, $event:expr
+        $(, on_success: || $success_body:expr)?
+        $(, on_error: |$error_param:pat_param| $error_body:expr)?
+        $(,)?
+    ) => {
+        $crate::effect::Effect::PublishEvent(
+            $crate::effect::EventBusOperation::Publish {
+                event_bus: ::std::sync::Arc::clone(&$bus),
+                topic: $topic.to_string(),
+                event: $event,
+                options: $crate::event_bus::PublishOptions::default(),
+                on_success: $crate::__effect_callback!($(|()| $success_body)?),
+                on_error: $crate::__effect_callback!($(|$error_param| $error_body)?),
+            }
+        )
+    };
+}
+
 /// Create an `Effect::Future` from an async block
 ///
 /// # Example
@@ -183,6 +274,13 @@ macro_rules! async_effect {
 ///     duration: Duration::from_secs(30),
 ///     action: OrderAction::TimeoutExpired
 /// }
+///
+/// // Keyed: replaces a pending delay with the same key
+/// delay! {
+///     key: "payment-timeout",
+///     duration: Duration::from_secs(30),
+///     action: OrderAction::PaymentTimedOut
+/// }
 /// ```
 #[macro_export]
 macro_rules! delay {
@@ -195,9 +293,52 @@ macro_rules! delay {
             action: ::std::boxed::Box::new($action),
         }
     };
+    (
+        key: $key:expr,
+        duration: $duration:expr,
+        action: $action:expr
+    ) => {
+        $crate::effect::Effect::DelayKeyed {
+            key: ::std::convert::Into::into($key),
+            duration: $duration,
+            action: ::std::boxed::Box::new($action),
+        }
+    };
+}
+
+/// Create an `Effect::Delay` from positional arguments
+///
+/// A leading `key:` makes it an `Effect::DelayKeyed`, replacing a pending
+/// delay with the same key.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use composable_rust_core::delayed;
+/// use std::time::Duration;
+///
+/// delayed!(Duration::from_secs(30), OrderAction::TimeoutExpired);
+/// delayed!(key: "payment-timeout", Duration::from_secs(30), OrderAction::PaymentTimedOut);
+/// ```
+#[macro_export]
+macro_rules! delayed {
+    (key: $key:expr, $duration:expr, $action:expr $(,)?) => {
+        $crate::delay! {
+            key: $key,
+            duration: $duration,
+            action: $action
+        }
+    };
+    ($duration:expr, $action:expr $(,)?) => {
+        $crate::delay! {
+            duration: $duration,
+            action: $action
+        }
+    };
 }
 
 #[cfg(test)]
+#[allow(clippy::panic)] // Tests can panic for assertions
 mod tests {
     use crate::effect::Effect;
     use std::time::Duration;
@@ -227,9 +368,81 @@ mod tests {
         };
 
         assert!(matches!(effect, Effect::Delay { .. }));
+
+        let keyed = delay! {
+            key: "timeout",
+            duration: Duration::from_secs(30),
+            action: TestAction::TimeoutExpired
+        };
+
+        assert!(matches!(keyed, Effect::DelayKeyed { ref key, .. } if key == "timeout"));
+    }
+
+    #[test]
+    fn test_delayed_macro() {
+        let effect = delayed!(Duration::from_secs(30), TestAction::TimeoutExpired);
+        assert!(matches!(effect, Effect::Delay { duration, .. } if duration == Duration::from_secs(30)));
+
+        let keyed = delayed!(key: "timeout", Duration::from_secs(30), TestAction::TimeoutExpired);
+        assert!(matches!(keyed, Effect::DelayKeyed { ref key, .. } if key == "timeout"));
+    }
+
+    #[test]
+    fn test_publish_macro_callbacks_default_to_no_action() {
+        use crate::effect::EventBusOperation;
+        use crate::event::SerializedEvent;
+        use crate::event_bus::{EventBus, EventBusError, EventStream};
+        use std::sync::Arc;
+
+        struct NullBus;
+
+        impl EventBus for NullBus {
+            fn publish(
+                &self,
+                _topic: &str,
+                _event: &SerializedEvent,
+            ) -> std::pin::Pin<
+                Box<dyn std::future::Future<Output = Result<(), EventBusError>> + Send + '_>,
+            > {
+                Box::pin(async { Ok(()) })
+            }
+
+            fn subscribe(
+                &self,
+                _topics: &[&str],
+            ) -> std::pin::Pin<
+                Box<dyn std::future::Future<Output = Result<EventStream, EventBusError>> + Send + '_>,
+            > {
+                Box::pin(async { Err(EventBusError::ConnectionFailed("unused".to_string())) })
+            }
+        }
+
+        let bus: Arc<dyn EventBus> = Arc::new(NullBus);
+        let event = || SerializedEvent::new("OrderPlaced.v1".to_string(), vec![], None);
+        let failure = || EventBusError::ConnectionFailed("down".to_string());
+
+        let effect: Effect<TestAction> = publish!(bus, "orders", event());
+        let Effect::PublishEvent(EventBusOperation::Publish { topic, on_success, on_error, .. }) = effect
+        else {
+            panic!("Expected PublishEvent effect");
+        };
+        assert_eq!(topic, "orders");
+        assert!(on_success(()).is_none());
+        assert!(on_error(failure()).is_none());
+
+        let Effect::PublishEvent(EventBusOperation::Publish { on_success, on_error, .. }) = publish!(
+            bus,
+            "orders",
+            event(),
+            on_success: || Some(TestAction::TimeoutExpired),
+            on_error: |_| Some(TestAction::AsyncResult { value: 1 }),
+        ) else {
+            panic!("Expected PublishEvent effect");
+        };
+        assert!(matches!(on_success(()), Some(TestAction::TimeoutExpired)));
+        assert!(matches!(on_error(failure()), Some(TestAction::AsyncResult { value: 1 })));
     }
 
-    // Note: append_events!, load_events!, and publish_event! macros are tested
-    // in integration tests where we have access to actual EventStore and EventBus
-    // implementations from the testing crate.
+    // Note: append_events! and load_events! are tested in the testing crate,
+    // which provides an in-memory EventStore.
 }
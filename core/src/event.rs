@@ -35,7 +35,9 @@
 //! }
 //! ```
 
+use crate::codec::EventCodec;
 use serde::{Serialize, de::DeserializeOwned};
+use std::collections::BTreeMap;
 use std::fmt;
 use thiserror::Error;
 
@@ -53,6 +55,19 @@ pub enum EventError {
     /// Unknown event type encountered during deserialization.
     #[error("Unknown event type: {0}")]
     UnknownEventType(String),
+
+    /// Two types were registered under the same event type.
+    #[error("Event type registered twice: {0}")]
+    DuplicateEventType(String),
+
+    /// The event was decoded as a different type than it was stored as.
+    #[error("Event type mismatch: expected {expected}, found {found}")]
+    TypeMismatch {
+        /// Event type of the requested Rust type.
+        expected: String,
+        /// Event type recorded on the serialized event.
+        found: String,
+    },
 }
 
 /// An event that can be stored in an event store and replayed to reconstruct state.
@@ -189,10 +204,70 @@ pub trait Event: Send + Sync + 'static {
     }
 }
 
+/// An event type with a registered name, version and payload codec.
+///
+/// Where [`Event`] returns a type string per value, a `DomainEvent` declares
+/// its name and schema version once, on the type. [`SerializedEvent::encode`]
+/// derives `event_type` from them (`"OrderPlaced.v2"`) and
+/// [`SerializedEvent::decode`] refuses events stored under any other type, so
+/// a typo in an event type string can no longer silently route bytes to the
+/// wrong deserializer.
+///
+/// # Examples
+///
+/// ```
+/// use composable_rust_core::codec::JsonCodec;
+/// use composable_rust_core::event::{DomainEvent, SerializedEvent};
+/// # use serde::{Serialize, Deserialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct OrderPlaced {
+///     order_id: String,
+/// }
+///
+/// impl DomainEvent for OrderPlaced {
+///     const NAME: &'static str = "OrderPlaced";
+///     const VERSION: i32 = 2;
+///     type Codec = JsonCodec;
+/// }
+///
+/// let event = OrderPlaced { order_id: "order-123".to_string() };
+/// let serialized = SerializedEvent::encode(&event, None).unwrap();
+/// assert_eq!(serialized.event_type, "OrderPlaced.v2");
+/// assert_eq!(serialized.event_version, 2);
+/// assert_eq!(serialized.decode::<OrderPlaced>().unwrap(), event);
+/// ```
+pub trait DomainEvent: Sized + Send + Sync + 'static {
+    /// Event name without the version suffix (e.g. `"OrderPlaced"`).
+    const NAME: &'static str;
+
+    /// Schema version, bumped on incompatible payload changes.
+    const VERSION: i32 = 1;
+
+    /// Codec for the payload bytes.
+    type Codec: EventCodec<Self>;
+
+    /// The stored event type, `"{NAME}.v{VERSION}"`.
+    #[must_use]
+    fn event_type() -> String {
+        format!("{}.v{}", Self::NAME, Self::VERSION)
+    }
+}
+
 /// Event metadata with strongly-typed fields.
 ///
 /// This struct provides type-safe metadata for events, replacing the previous
 /// stringly-typed `serde_json::Value` approach.
+///
+/// # Causality
+///
+/// The Store stamps every appended or published event with an `event_id`, and
+/// sets its `causation_id` to the id of the message that triggered it: the
+/// causation id passed to `send_with_metadata`, or otherwise an id generated
+/// for the reduced action. A consumer handling an event passes
+/// [`caused_by`](Self::caused_by) along with the resulting action, so the
+/// events it produces point back at the event that caused them and keep its
+/// correlation id.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct EventMetadata {
     /// Links related events across aggregates (saga coordination).
@@ -206,6 +281,18 @@ pub struct EventMetadata {
 
     /// When the event was created (ISO 8601 timestamp).
     pub timestamp: Option<String>,
+
+    /// Unique id of this event, the target of other events' `causation_id`.
+    #[serde(default)]
+    pub event_id: Option<String>,
+
+    /// The tenant the event belongs to, in multi-tenant deployments.
+    #[serde(default)]
+    pub tenant_id: Option<String>,
+
+    /// Application-defined key/value pairs (e.g. trace context, source system).
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
 }
 
 impl EventMetadata {
@@ -217,6 +304,9 @@ impl EventMetadata {
             causation_id: None,
             user_id: None,
             timestamp: None,
+            event_id: None,
+            tenant_id: None,
+            headers: BTreeMap::new(),
         }
     }
 
@@ -225,10 +315,77 @@ impl EventMetadata {
     pub fn with_correlation_id(correlation_id: impl Into<String>) -> Self {
         Self {
             correlation_id: Some(correlation_id.into()),
-            causation_id: None,
-            user_id: None,
+            ..Self::new()
+        }
+    }
+
+    /// Set the causation ID.
+    #[must_use]
+    pub fn causation_id(mut self, causation_id: impl Into<String>) -> Self {
+        self.causation_id = Some(causation_id.into());
+        self
+    }
+
+    /// Set the user ID.
+    #[must_use]
+    pub fn user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Set the tenant ID.
+    #[must_use]
+    pub fn tenant_id(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Add a custom header, replacing an earlier value for `key`.
+    #[must_use]
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Metadata for work triggered by the event carrying `self`.
+    ///
+    /// Keeps the correlation, user, tenant and headers, and sets the
+    /// causation ID to this event's ID (left unset if it has none).
+    #[must_use]
+    pub fn caused_by(&self) -> Self {
+        Self {
+            correlation_id: self.correlation_id.clone(),
+            causation_id: self.event_id.clone(),
+            user_id: self.user_id.clone(),
             timestamp: None,
+            event_id: None,
+            tenant_id: self.tenant_id.clone(),
+            headers: self.headers.clone(),
+        }
+    }
+
+    /// Overwrite fields with those set in `other`.
+    ///
+    /// Headers are merged, with `other` winning on conflicts. The event ID is
+    /// never copied: it identifies one event.
+    pub fn merge_from(&mut self, other: &Self) {
+        if other.correlation_id.is_some() {
+            self.correlation_id.clone_from(&other.correlation_id);
         }
+        if other.causation_id.is_some() {
+            self.causation_id.clone_from(&other.causation_id);
+        }
+        if other.user_id.is_some() {
+            self.user_id.clone_from(&other.user_id);
+        }
+        if other.timestamp.is_some() {
+            self.timestamp.clone_from(&other.timestamp);
+        }
+        if other.tenant_id.is_some() {
+            self.tenant_id.clone_from(&other.tenant_id);
+        }
+        self.headers
+            .extend(other.headers.iter().map(|(k, v)| (k.clone(), v.clone())));
     }
 
     /// Convert to JSON value for database storage.
@@ -239,11 +396,17 @@ impl EventMetadata {
             "causation_id": self.causation_id,
             "user_id": self.user_id,
             "timestamp": self.timestamp,
+            "event_id": self.event_id,
+            "tenant_id": self.tenant_id,
+            "headers": self.headers,
         })
     }
 
     /// Create from JSON value from database.
     ///
+    /// Rows written before `event_id`, `tenant_id` and `headers` existed are
+    /// read with those fields empty.
+    ///
     /// # Errors
     ///
     /// Returns error if JSON deserialization fails.
@@ -393,6 +556,156 @@ impl SerializedEvent {
             metadata,
         })
     }
+    /// Encode a [`DomainEvent`] with its codec, under its registered type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EventError::SerializationError` if the codec fails.
+    pub fn encode<T: DomainEvent>(event: &T, metadata: Option<EventMetadata>) -> Result<Self, EventError> {
+        Ok(Self {
+            event_type: T::event_type(),
+            event_version: T::VERSION,
+            data: T::Codec::default().encode(event)?,
+            metadata,
+        })
+    }
+
+    /// Decode this event as a [`DomainEvent`].
+    ///
+    /// # Errors
+    ///
+    /// - `EventError::TypeMismatch` if the event was stored under a different type
+    /// - `EventError::DeserializationError` if the codec fails
+    pub fn decode<T: DomainEvent>(&self) -> Result<T, EventError> {
+        let expected = T::event_type();
+        if self.event_type != expected {
+            return Err(EventError::TypeMismatch {
+                expected,
+                found: self.event_type.clone(),
+            });
+        }
+        T::Codec::default().decode(&self.data)
+    }
+
+    /// Whether this event was stored as the given [`DomainEvent`] type.
+    #[must_use]
+    pub fn is<T: DomainEvent>(&self) -> bool {
+        self.event_type == T::event_type()
+    }
+
+    /// Encode this event for a message broker.
+    ///
+    /// bincode is positional, so adding a field to [`EventMetadata`] changes
+    /// the layout. The encoding starts with a format marker so consumers can
+    /// tell layouts apart; see [`from_wire_bytes`](Self::from_wire_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `EventError::SerializationError` if bincode fails.
+    pub fn to_wire_bytes(&self) -> Result<Vec<u8>, EventError> {
+        let mut bytes = WIRE_FORMAT_MARKER.to_vec();
+        bincode::serialize_into(&mut bytes, self).map_err(|e| EventError::SerializationError(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Encode this event the way producers did before the format marker.
+    ///
+    /// Plain bincode with only the original correlation, causation, user and
+    /// timestamp metadata fields; the event id, tenant and headers are
+    /// dropped. Only for rolling upgrades: consumers that predate the marker
+    /// cannot decode [`to_wire_bytes`](Self::to_wire_bytes), so producers
+    /// keep writing this encoding until every consumer has been upgraded, and
+    /// only then switch to the marked format.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EventError::SerializationError` if bincode fails.
+    pub fn to_legacy_wire_bytes(&self) -> Result<Vec<u8>, EventError> {
+        bincode::serialize(&LegacySerializedEvent::from(self))
+            .map_err(|e| EventError::SerializationError(e.to_string()))
+    }
+
+    /// Decode an event encoded by [`to_wire_bytes`](Self::to_wire_bytes)
+    /// or [`to_legacy_wire_bytes`](Self::to_legacy_wire_bytes).
+    ///
+    /// Payloads without the format marker are plain bincode from producers
+    /// that predate it, whose metadata may only have the original
+    /// correlation, causation, user and timestamp fields; both layouts are
+    /// accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EventError::DeserializationError` if the bytes match no known layout.
+    pub fn from_wire_bytes(bytes: &[u8]) -> Result<Self, EventError> {
+        if let Some(payload) = bytes.strip_prefix(&WIRE_FORMAT_MARKER)
+            && let Ok(event) = bincode::deserialize(payload)
+        {
+            return Ok(event);
+        }
+        // Metadata is the last field, so the current layout only fails on
+        // original-layout payloads that carry metadata
+        bincode::deserialize(bytes)
+            .or_else(|_| bincode::deserialize::<LegacySerializedEvent>(bytes).map(Self::from))
+            .map_err(|e| EventError::DeserializationError(e.to_string()))
+    }
+}
+
+/// Prefix of [`SerializedEvent::to_wire_bytes`] payloads: `CRE` and the format version
+///
+/// Read as the little-endian length that starts a plain bincode payload, it
+/// would be an event type tens of megabytes long, so it cannot be mistaken
+/// for one.
+const WIRE_FORMAT_MARKER: [u8; 4] = *b"CRE\x02";
+
+/// [`SerializedEvent`] as encoded before metadata had an event id, tenant and headers
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LegacySerializedEvent {
+    event_type: String,
+    event_version: i32,
+    data: Vec<u8>,
+    metadata: Option<LegacyEventMetadata>,
+}
+
+/// The original four [`EventMetadata`] fields
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LegacyEventMetadata {
+    correlation_id: Option<String>,
+    causation_id: Option<String>,
+    user_id: Option<String>,
+    timestamp: Option<String>,
+}
+
+impl From<LegacySerializedEvent> for SerializedEvent {
+    fn from(legacy: LegacySerializedEvent) -> Self {
+        Self {
+            event_type: legacy.event_type,
+            event_version: legacy.event_version,
+            data: legacy.data,
+            metadata: legacy.metadata.map(|metadata| EventMetadata {
+                correlation_id: metadata.correlation_id,
+                causation_id: metadata.causation_id,
+                user_id: metadata.user_id,
+                timestamp: metadata.timestamp,
+                ..EventMetadata::new()
+            }),
+        }
+    }
+}
+
+impl From<&SerializedEvent> for LegacySerializedEvent {
+    fn from(event: &SerializedEvent) -> Self {
+        Self {
+            event_type: event.event_type.clone(),
+            event_version: event.event_version,
+            data: event.data.clone(),
+            metadata: event.metadata.as_ref().map(|metadata| LegacyEventMetadata {
+                correlation_id: metadata.correlation_id.clone(),
+                causation_id: metadata.causation_id.clone(),
+                user_id: metadata.user_id.clone(),
+                timestamp: metadata.timestamp.clone(),
+            }),
+        }
+    }
 }
 
 impl fmt::Display for SerializedEvent {
@@ -462,6 +775,7 @@ mod tests {
             correlation_id: Some("corr-456".to_string()),
             causation_id: None,
             timestamp: None,
+            ..EventMetadata::default()
         };
 
         let serialized = SerializedEvent::from_event(&event, Some(metadata.clone()))
@@ -510,4 +824,167 @@ mod tests {
         let event_no_version = SerializedEvent::new("OrderPlaced".to_string(), vec![1, 2, 3], None);
         assert_eq!(event_no_version.event_version, 1); // Default to v1
     }
+
+    #[test]
+    fn metadata_caused_by_links_to_triggering_event() {
+        let mut incoming = EventMetadata::with_correlation_id("corr-1")
+            .tenant_id("acme")
+            .header("traceparent", "00-abc-01");
+        incoming.event_id = Some("evt-1".to_string());
+
+        let caused = incoming.caused_by();
+        assert_eq!(caused.causation_id.as_deref(), Some("evt-1"));
+        assert_eq!(caused.correlation_id.as_deref(), Some("corr-1"));
+        assert_eq!(caused.tenant_id.as_deref(), Some("acme"));
+        assert_eq!(caused.headers.get("traceparent").map(String::as_str), Some("00-abc-01"));
+        assert_eq!(caused.event_id, None);
+    }
+
+    #[test]
+    #[allow(clippy::expect_used)] // Panics: Test will fail if deserialization fails
+    fn metadata_merge_and_legacy_json() {
+        let mut event = EventMetadata::new().header("source", "checkout").header("region", "eu");
+        event.event_id = Some("evt-1".to_string());
+        let mut request = EventMetadata::with_correlation_id("corr-1").header("region", "us");
+        request.event_id = Some("ignored".to_string());
+
+        event.merge_from(&request);
+        assert_eq!(event.correlation_id.as_deref(), Some("corr-1"));
+        assert_eq!(event.event_id.as_deref(), Some("evt-1"));
+        assert_eq!(event.headers.get("region").map(String::as_str), Some("us"));
+        assert_eq!(event.headers.len(), 2);
+
+        // Rows stored before event_id, tenant_id and headers existed
+        let legacy = serde_json::json!({
+            "correlation_id": "corr-1",
+            "causation_id": null,
+            "user_id": null,
+            "timestamp": null,
+        });
+        let parsed = EventMetadata::from_json(&legacy).expect("legacy metadata should parse");
+        assert_eq!(parsed, EventMetadata::with_correlation_id("corr-1"));
+        assert_eq!(EventMetadata::from_json(&event.to_json()).expect("round trip"), event);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Renamed {
+        id: String,
+    }
+
+    impl DomainEvent for Renamed {
+        const NAME: &'static str = "Renamed";
+        const VERSION: i32 = 3;
+        type Codec = crate::codec::JsonCodec;
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Deleted {
+        id: String,
+    }
+
+    impl DomainEvent for Deleted {
+        const NAME: &'static str = "Deleted";
+        type Codec = crate::codec::BincodeCodec;
+    }
+
+    #[test]
+    #[allow(clippy::expect_used)] // Panics: Test will fail if encoding fails
+    fn domain_event_encode_decode_checks_type() {
+        let event = Renamed { id: "r-1".to_string() };
+        let serialized = SerializedEvent::encode(&event, None).expect("encode");
+
+        assert_eq!(serialized.event_type, "Renamed.v3");
+        assert_eq!(serialized.event_version, 3);
+        assert_eq!(serialized.data, br#"{"id":"r-1"}"#);
+        assert!(serialized.is::<Renamed>());
+        assert_eq!(serialized.decode::<Renamed>().expect("decode"), event);
+
+        assert_eq!(Deleted::event_type(), "Deleted.v1");
+        assert!(matches!(
+            serialized.decode::<Deleted>(),
+            Err(EventError::TypeMismatch { expected, found }) if expected == "Deleted.v1" && found == "Renamed.v3"
+        ));
+    }
+
+    #[test]
+    #[allow(clippy::expect_used)] // Panics: Test will fail if encoding fails
+    fn wire_bytes_round_trip_full_metadata() {
+        let mut metadata = EventMetadata::with_correlation_id("corr-1").tenant_id("acme");
+        metadata.event_id = Some("evt-1".to_string());
+        metadata.headers.insert("source".to_string(), "billing".to_string());
+        let event = SerializedEvent::new("OrderPlaced.v1".to_string(), vec![1, 2, 3], Some(metadata));
+
+        let decoded = SerializedEvent::from_wire_bytes(&event.to_wire_bytes().expect("encode")).expect("decode");
+        assert_eq!(decoded.metadata, event.metadata);
+        assert_eq!(decoded.data, event.data);
+
+        // Unmarked payloads in the current layout still decode
+        let plain = bincode::serialize(&event).expect("encode");
+        assert_eq!(SerializedEvent::from_wire_bytes(&plain).expect("decode").metadata, event.metadata);
+    }
+
+    #[test]
+    #[allow(clippy::expect_used)] // Panics: Test will fail if encoding fails
+    fn wire_bytes_decode_original_four_field_metadata() {
+        // SerializedEvent as encoded by producers built before the metadata grew
+        #[derive(Serialize)]
+        struct OldMetadata {
+            correlation_id: Option<String>,
+            causation_id: Option<String>,
+            user_id: Option<String>,
+            timestamp: Option<String>,
+        }
+
+        #[derive(Serialize)]
+        struct OldSerializedEvent {
+            event_type: String,
+            event_version: i32,
+            data: Vec<u8>,
+            metadata: Option<OldMetadata>,
+        }
+
+        let old = OldSerializedEvent {
+            event_type: "OrderPlaced.v2".to_string(),
+            event_version: 2,
+            data: vec![9, 8, 7],
+            metadata: Some(OldMetadata {
+                correlation_id: Some("corr-1".to_string()),
+                causation_id: Some("cause-1".to_string()),
+                user_id: None,
+                timestamp: Some("2025-01-01T00:00:00Z".to_string()),
+            }),
+        };
+        let payload = bincode::serialize(&old).expect("encode");
+        assert!(bincode::deserialize::<SerializedEvent>(&payload).is_err());
+
+        let event = SerializedEvent::from_wire_bytes(&payload).expect("decode");
+        assert_eq!(event.event_type, "OrderPlaced.v2");
+        assert_eq!(event.event_version, 2);
+        assert_eq!(event.data, vec![9, 8, 7]);
+        assert_eq!(
+            event.metadata,
+            Some(EventMetadata {
+                timestamp: Some("2025-01-01T00:00:00Z".to_string()),
+                ..EventMetadata::with_correlation_id("corr-1").causation_id("cause-1")
+            })
+        );
+    }
+
+    #[test]
+    #[allow(clippy::expect_used)] // Panics: Test will fail if encoding fails
+    fn legacy_wire_bytes_decode_on_consumers_without_the_marker() {
+        let metadata = EventMetadata::with_correlation_id("corr-1").tenant_id("acme");
+        let event = SerializedEvent::new("OrderPlaced.v1".to_string(), vec![1, 2, 3], Some(metadata));
+        let payload = event.to_legacy_wire_bytes().expect("encode");
+
+        // What a consumer built before the marker decodes
+        let old: LegacySerializedEvent = bincode::deserialize(&payload).expect("decode");
+        assert_eq!(old.event_type, "OrderPlaced.v1");
+        assert_eq!(old.data, vec![1, 2, 3]);
+        assert_eq!(old.metadata.and_then(|m| m.correlation_id).as_deref(), Some("corr-1"));
+
+        // Upgraded consumers read it too, without the fields the old layout lacks
+        let decoded = SerializedEvent::from_wire_bytes(&payload).expect("decode");
+        assert_eq!(decoded.metadata, Some(EventMetadata::with_correlation_id("corr-1")));
+    }
 }
@@ -52,6 +52,9 @@
 //! - `payment-events` - All events from Payment aggregates
 //! - `inventory-events` - All events from Inventory aggregates
 //!
+//! Declaring each topic once as a [`Topic`](crate::topic::Topic) constant ties
+//! its name to its event type; see [`topic`](crate::topic).
+//!
 //! # Implementations
 //!
 //! - [`InMemoryEventBus`](../../composable_rust_testing/event_bus/struct.InMemoryEventBus.html) - For testing (fast, synchronous)
@@ -80,9 +83,11 @@
 //! ```
 
 use crate::event::SerializedEvent;
-use futures::Stream;
+use futures::{Stream, StreamExt};
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::pin::Pin;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur during event bus operations.
@@ -151,6 +156,173 @@ pub enum EventBusError {
 /// ```
 pub type EventStream = Pin<Box<dyn Stream<Item = Result<SerializedEvent, EventBusError>> + Send>>;
 
+/// Settles a [`Delivery`] with the broker it came from.
+///
+/// Implemented by backends with at-least-once delivery, e.g. to commit a
+/// Kafka offset or ack a `JetStream` message once the event is processed.
+pub trait Acknowledger: Send {
+    /// Mark the event processed so it is not redelivered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the broker could not record the acknowledgment.
+    fn ack(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send>>;
+
+    /// Reject the event; with `requeue` the broker delivers it again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the broker could not record the rejection.
+    fn nack(
+        self: Box<Self>,
+        requeue: bool,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send>>;
+}
+
+/// An event received from a subscription, with its acknowledgment handle.
+///
+/// Call [`ack`](Self::ack) once the event is processed, or
+/// [`nack`](Self::nack) to reject it. A delivery dropped without either is
+/// left to the backend, which typically redelivers it after a timeout.
+/// Backends without manual acknowledgment hand out auto-acked deliveries,
+/// for which both calls do nothing.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut deliveries = event_bus.subscribe_with_ack(&["order-events"]).await?;
+/// while let Some(delivery) = deliveries.next().await {
+///     let delivery = delivery?;
+///     match process_event(&delivery.event).await {
+///         Ok(()) => delivery.ack().await?,
+///         Err(_) => delivery.nack(true).await?,
+///     }
+/// }
+/// ```
+pub struct Delivery {
+    /// The delivered event
+    pub event: SerializedEvent,
+    acker: Option<Box<dyn Acknowledger>>,
+}
+
+impl Delivery {
+    /// A delivery settled through `acker`.
+    #[must_use]
+    pub fn new(event: SerializedEvent, acker: impl Acknowledger + 'static) -> Self {
+        Self {
+            event,
+            acker: Some(Box::new(acker)),
+        }
+    }
+
+    /// A delivery the backend already considers processed.
+    #[must_use]
+    pub const fn auto_acked(event: SerializedEvent) -> Self {
+        Self { event, acker: None }
+    }
+
+    /// Whether the backend waits for [`ack`](Self::ack) or [`nack`](Self::nack).
+    #[must_use]
+    pub const fn requires_ack(&self) -> bool {
+        self.acker.is_some()
+    }
+
+    /// Mark the event processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the broker could not record the acknowledgment.
+    pub async fn ack(self) -> Result<(), EventBusError> {
+        match self.acker {
+            Some(acker) => acker.ack().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Reject the event; with `requeue` it is delivered again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the broker could not record the rejection.
+    pub async fn nack(self, requeue: bool) -> Result<(), EventBusError> {
+        match self.acker {
+            Some(acker) => acker.nack(requeue).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Debug for Delivery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Delivery")
+            .field("event", &self.event)
+            .field("requires_ack", &self.requires_ack())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Stream of [`Delivery`] values from [`EventBus::subscribe_with_ack`].
+pub type DeliveryStream = Pin<Box<dyn Stream<Item = Result<Delivery, EventBusError>> + Send>>;
+
+/// Routing and delivery hints for a single publish.
+///
+/// Backends apply what they support and ignore the rest. Kafka-compatible
+/// brokers send the partition key as the message key, so events sharing a
+/// key (typically the aggregate id) land on one partition and keep their
+/// order, and send the headers as message headers. The Store bounds each
+/// publish attempt by the delivery timeout.
+///
+/// # Examples
+///
+/// ```
+/// use composable_rust_core::event_bus::PublishOptions;
+/// use std::time::Duration;
+///
+/// let options = PublishOptions::new()
+///     .with_partition_key("order-42")
+///     .with_header("tenant", "acme")
+///     .with_delivery_timeout(Duration::from_secs(2));
+/// assert_eq!(options.partition_key.as_deref(), Some("order-42"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublishOptions {
+    /// Key selecting the partition; events with the same key stay in order
+    pub partition_key: Option<String>,
+    /// Headers carried alongside the event
+    pub headers: BTreeMap<String, String>,
+    /// Maximum time a single publish attempt may take
+    pub delivery_timeout: Option<Duration>,
+}
+
+impl PublishOptions {
+    /// Options with no key, no headers and no timeout.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route the event by `key`.
+    #[must_use]
+    pub fn with_partition_key(mut self, key: impl Into<String>) -> Self {
+        self.partition_key = Some(key.into());
+        self
+    }
+
+    /// Add a header, replacing any previous value for `name`.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Fail a publish attempt that takes longer than `timeout`.
+    #[must_use]
+    pub const fn with_delivery_timeout(mut self, timeout: Duration) -> Self {
+        self.delivery_timeout = Some(timeout);
+        self
+    }
+}
+
 /// Trait for event bus implementations.
 ///
 /// The [`EventBus`] trait provides publish/subscribe capabilities for cross-aggregate
@@ -268,6 +440,32 @@ pub trait EventBus: Send + Sync {
         event: &SerializedEvent,
     ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>>;
 
+    /// Publish an event to a topic with routing and delivery hints.
+    ///
+    /// The default implementation ignores `options` and calls
+    /// [`publish`](Self::publish); backends that can route by key or carry
+    /// headers override it. The delivery timeout is enforced by the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventBusError::PublishFailed`] if the publish operation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let options = PublishOptions::new().with_partition_key(order_id.to_string());
+    /// event_bus.publish_with_options("order-events", &event, &options).await?;
+    /// ```
+    fn publish_with_options(
+        &self,
+        topic: &str,
+        event: &SerializedEvent,
+        options: &PublishOptions,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+        let _ = options;
+        self.publish(topic, event)
+    }
+
     /// Subscribe to one or more topics and receive a stream of events.
     ///
     /// Returns an [`EventStream`] that yields events from all subscribed topics.
@@ -313,4 +511,30 @@ pub trait EventBus: Send + Sync {
         &self,
         topics: &[&str],
     ) -> Pin<Box<dyn Future<Output = Result<EventStream, EventBusError>> + Send + '_>>;
+
+    /// Subscribe to one or more topics, acknowledging each event explicitly.
+    ///
+    /// Each item is a [`Delivery`] to [`ack`](Delivery::ack) once processed
+    /// or [`nack`](Delivery::nack) to have it redelivered, so at-least-once
+    /// backends only consider an event handled when the subscriber says so.
+    ///
+    /// The default implementation wraps [`subscribe`](Self::subscribe) and
+    /// yields auto-acked deliveries; backends with manual acknowledgment
+    /// override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventBusError::SubscriptionFailed`] if subscription fails.
+    fn subscribe_with_ack(
+        &self,
+        topics: &[&str],
+    ) -> Pin<Box<dyn Future<Output = Result<DeliveryStream, EventBusError>> + Send + '_>> {
+        let subscription = self.subscribe(topics);
+        Box::pin(async move {
+            let events = subscription.await?;
+            let deliveries: DeliveryStream =
+                Box::pin(events.map(|event| event.map(Delivery::auto_acked)));
+            Ok(deliveries)
+        })
+    }
 }
@@ -0,0 +1,219 @@
+//! Typed decoding of [`SerializedEvent`]s by event type.
+//!
+//! An [`EventRegistry`] maps `event_type` strings to the
+//! [`DomainEvent`] types registered under them, and decodes each event into a
+//! common target type: a projection's event enum, or the action a Store
+//! should receive. Consumers such as `EventBusListener::with_registry` and
+//! `ProjectionManager::with_registry` use it instead of hand-written matches
+//! on `event.event_type`.
+//!
+//! # Example
+//!
+//! ```
+//! use composable_rust_core::codec::JsonCodec;
+//! use composable_rust_core::event::{DomainEvent, SerializedEvent};
+//! use composable_rust_core::event_registry::EventRegistry;
+//! # use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Debug, Serialize, Deserialize)]
+//! struct OrderPlaced { order_id: String }
+//!
+//! impl DomainEvent for OrderPlaced {
+//!     const NAME: &'static str = "OrderPlaced";
+//!     type Codec = JsonCodec;
+//! }
+//!
+//! #[derive(Debug)]
+//! enum SagaAction {
+//!     OrderPlaced(OrderPlaced),
+//! }
+//!
+//! let registry = EventRegistry::new()
+//!     .register(SagaAction::OrderPlaced)
+//!     .unwrap();
+//!
+//! let event = SerializedEvent::encode(&OrderPlaced { order_id: "o-1".into() }, None).unwrap();
+//! assert!(matches!(registry.decode(&event), Ok(Some(SagaAction::OrderPlaced(_)))));
+//! ```
+
+use crate::event::{DomainEvent, EventError, SerializedEvent};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Decodes one registered event type into the registry's target type
+type Decode<T> = Box<dyn Fn(&SerializedEvent) -> Result<T, EventError> + Send + Sync>;
+
+/// Maps event types to decoders producing `T`; see the [module documentation](self)
+pub struct EventRegistry<T> {
+    decoders: HashMap<String, Decode<T>>,
+}
+
+impl<T: 'static> fmt::Debug for EventRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut event_types: Vec<&str> = self.event_types().collect();
+        event_types.sort_unstable();
+        f.debug_struct("EventRegistry")
+            .field("event_types", &event_types)
+            .finish()
+    }
+}
+
+impl<T: 'static> Default for EventRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> EventRegistry<T> {
+    /// Create an empty registry
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            decoders: HashMap::new(),
+        }
+    }
+
+    /// Register `E`, mapping each decoded event into `T`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::DuplicateEventType`] if another type is already
+    /// registered under `E::event_type()`.
+    pub fn register<E, F>(mut self, map: F) -> Result<Self, EventError>
+    where
+        E: DomainEvent,
+        F: Fn(E) -> T + Send + Sync + 'static,
+    {
+        let event_type = E::event_type();
+        if self.decoders.contains_key(&event_type) {
+            return Err(EventError::DuplicateEventType(event_type));
+        }
+        self.decoders.insert(
+            event_type,
+            Box::new(move |event| event.decode::<E>().map(&map)),
+        );
+        Ok(self)
+    }
+
+    /// Register `E` for targets it converts into
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::DuplicateEventType`] if another type is already
+    /// registered under `E::event_type()`.
+    pub fn register_into<E>(self) -> Result<Self, EventError>
+    where
+        E: DomainEvent + Into<T>,
+    {
+        self.register(E::into)
+    }
+
+    /// Whether a type is registered under `event_type`
+    #[must_use]
+    pub fn contains(&self, event_type: &str) -> bool {
+        self.decoders.contains_key(event_type)
+    }
+
+    /// Registered event types, in no particular order
+    pub fn event_types(&self) -> impl Iterator<Item = &str> {
+        self.decoders.keys().map(String::as_str)
+    }
+
+    /// Decode an event with the decoder registered for its type
+    ///
+    /// Returns `Ok(None)` for event types that are not registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::DeserializationError`] if the registered codec
+    /// cannot decode the payload.
+    pub fn decode(&self, event: &SerializedEvent) -> Result<Option<T>, EventError> {
+        self.decoders
+            .get(&event.event_type)
+            .map(|decode| decode(event))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{BincodeCodec, JsonCodec};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Placed {
+        id: u32,
+    }
+
+    impl DomainEvent for Placed {
+        const NAME: &'static str = "Placed";
+        type Codec = JsonCodec;
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Shipped {
+        id: u32,
+    }
+
+    impl DomainEvent for Shipped {
+        const NAME: &'static str = "Shipped";
+        const VERSION: i32 = 2;
+        type Codec = BincodeCodec;
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum OrderEvent {
+        Placed(Placed),
+        Shipped(Shipped),
+    }
+
+    impl From<Shipped> for OrderEvent {
+        fn from(event: Shipped) -> Self {
+            Self::Shipped(event)
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)] // Tests can unwrap
+    fn test_decodes_registered_types_and_skips_others() {
+        let registry = EventRegistry::new()
+            .register(OrderEvent::Placed)
+            .unwrap()
+            .register_into::<Shipped>()
+            .unwrap();
+
+        let placed = SerializedEvent::encode(&Placed { id: 1 }, None).unwrap();
+        let shipped = SerializedEvent::encode(&Shipped { id: 2 }, None).unwrap();
+        let unknown = SerializedEvent::new("Cancelled.v1".to_string(), vec![], None);
+
+        assert_eq!(
+            registry.decode(&placed).unwrap(),
+            Some(OrderEvent::Placed(Placed { id: 1 }))
+        );
+        assert_eq!(
+            registry.decode(&shipped).unwrap(),
+            Some(OrderEvent::Shipped(Shipped { id: 2 }))
+        );
+        assert_eq!(registry.decode(&unknown).unwrap(), None);
+        assert!(registry.contains("Shipped.v2"));
+        assert!(!registry.contains("Shipped.v1"));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)] // Tests can unwrap
+    fn test_duplicate_and_corrupt_events_fail() {
+        let registry = EventRegistry::new().register(OrderEvent::Placed).unwrap();
+        let duplicate = EventRegistry::<OrderEvent>::new()
+            .register(OrderEvent::Placed)
+            .unwrap()
+            .register(OrderEvent::Placed);
+        assert!(matches!(duplicate, Err(EventError::DuplicateEventType(t)) if t == "Placed.v1"));
+
+        let corrupt = SerializedEvent::new("Placed.v1".to_string(), b"{".to_vec(), None);
+        assert!(matches!(
+            registry.decode(&corrupt),
+            Err(EventError::DeserializationError(_))
+        ));
+    }
+}
@@ -11,6 +11,7 @@
 //! - Append events to a stream with optimistic concurrency
 //! - Load events from a stream for state reconstruction
 //! - Save and load state snapshots for performance
+//! - Inspect, list and delete streams for administrative tooling
 //!
 //! # Implementations
 //!
@@ -42,6 +43,7 @@
 //! }
 //! ```
 
+use crate::environment::Timestamp;
 use crate::event::SerializedEvent;
 use crate::stream::{StreamId, Version};
 use std::future::Future;
@@ -87,8 +89,50 @@ impl BatchAppend {
     }
 }
 
+/// Summary of a stream, returned by `stream_metadata()` and `list_streams()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamMetadata {
+    /// The stream this metadata describes.
+    pub stream_id: StreamId,
+    /// When the first event of the stream was stored.
+    pub created_at: Timestamp,
+    /// Number of events in the stream.
+    pub event_count: u64,
+    /// Version of the most recent event in the stream.
+    pub last_version: Version,
+}
+
+/// Offset/limit window for `list_streams()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    /// Number of streams to skip.
+    pub offset: usize,
+    /// Maximum number of streams to return.
+    pub limit: usize,
+}
+
+impl Pagination {
+    /// Create a page starting at `offset` holding at most `limit` streams.
+    #[must_use]
+    pub const fn new(offset: usize, limit: usize) -> Self {
+        Self { offset, limit }
+    }
+
+    /// The first page holding at most `limit` streams.
+    #[must_use]
+    pub const fn first(limit: usize) -> Self {
+        Self::new(0, limit)
+    }
+
+    /// The page following this one.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        Self::new(self.offset.saturating_add(self.limit), self.limit)
+    }
+}
+
 /// Errors that can occur during event store operations.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum EventStoreError {
     /// Optimistic concurrency conflict: expected version doesn't match current version.
     ///
@@ -214,6 +258,49 @@ pub trait EventStore: Send + Sync {
         events: Vec<SerializedEvent>,
     ) -> Pin<Box<dyn Future<Output = Result<Version, EventStoreError>> + Send + '_>>;
 
+    /// Append events unless an append with the same `idempotency_key` has
+    /// already been committed to the stream.
+    ///
+    /// An append can fail ambiguously: a timeout after the database committed
+    /// looks like any other failure to the caller, and retrying it would store
+    /// the events twice. Passing a key that identifies the write (typically the
+    /// ID of the command that produced the events) makes such retries safe.
+    ///
+    /// # Contract
+    ///
+    /// Backends that support idempotency keys must:
+    ///
+    /// - Record the key atomically with the events it appended, so a key is
+    ///   never recorded for an append that did not commit
+    /// - On a key already recorded for the stream, append nothing and return
+    ///   the version the original append returned. This is checked before
+    ///   `expected_version`, which the original append has already moved
+    ///   the stream past
+    /// - Scope keys to the stream: the same key on another stream is a new append
+    /// - Identify the write by the key alone, without comparing the events
+    ///
+    /// Recorded keys may expire, but only after the longest window in which
+    /// callers retry. Deleting a stream forgets its keys.
+    ///
+    /// The provided implementation does not deduplicate: it ignores the key
+    /// and calls [`append_events`](Self::append_events). `InMemoryEventStore`
+    /// implements the contract; backends without support keep the provided
+    /// implementation, so callers must not rely on deduplication for them.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`append_events`](Self::append_events).
+    fn append_events_idempotent(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+        idempotency_key: String,
+    ) -> Pin<Box<dyn Future<Output = Result<Version, EventStoreError>> + Send + '_>> {
+        let _ = idempotency_key;
+        self.append_events(stream_id, expected_version, events)
+    }
+
     /// Load events from a stream.
     ///
     /// # Parameters
@@ -257,6 +344,34 @@ pub trait EventStore: Send + Sync {
         from_version: Option<Version>,
     ) -> Pin<Box<dyn Future<Output = Result<Vec<SerializedEvent>, EventStoreError>> + Send + '_>>;
 
+    /// Load at most `limit` events of a stream, starting at `from_version` (inclusive).
+    ///
+    /// Used to fold long streams page by page with bounded memory. Returns
+    /// fewer than `limit` events (possibly none) once the end of the stream
+    /// is reached.
+    ///
+    /// The provided implementation loads the rest of the stream with
+    /// [`load_events`](Self::load_events) and drops everything past `limit`,
+    /// so it is correct but not memory-bounded; backends should override it
+    /// with a limited query.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`load_events`](Self::load_events).
+    fn load_events_page(
+        &self,
+        stream_id: StreamId,
+        from_version: Version,
+        limit: usize,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<SerializedEvent>, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut events = self.load_events(stream_id, Some(from_version)).await?;
+            events.truncate(limit);
+            Ok(events)
+        })
+    }
+
     /// Save a snapshot of aggregate state.
     ///
     /// Snapshots allow rebuilding aggregate state without replaying all events.
@@ -459,6 +574,85 @@ pub trait EventStore: Send + Sync {
         &self,
         batch: Vec<BatchAppend>,
     ) -> Pin<Box<dyn Future<Output = Result<BatchAppendResults, EventStoreError>> + Send + '_>>;
+
+    /// Permanently delete a stream, its events and its snapshot.
+    ///
+    /// Event streams are otherwise immutable; this exists for administrative
+    /// cleanup and erasure workflows (e.g. GDPR "right to be forgotten").
+    /// Appending to the stream afterwards starts a new stream from scratch.
+    ///
+    /// # Errors
+    ///
+    /// - `StreamNotFound`: The stream has no events
+    /// - `DatabaseError`: Database connection or query failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use composable_rust_core::event_store::EventStore;
+    /// use composable_rust_core::stream::StreamId;
+    ///
+    /// async fn erase_customer<E: EventStore>(store: &E) -> Result<(), Box<dyn std::error::Error>> {
+    ///     store.delete_stream(StreamId::new("customer-42")).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    fn delete_stream(
+        &self,
+        stream_id: StreamId,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventStoreError>> + Send + '_>>;
+
+    /// Load the metadata of a stream.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(metadata)`: The stream exists
+    /// - `None`: The stream has no events
+    ///
+    /// # Errors
+    ///
+    /// - `DatabaseError`: Database connection or query failed
+    fn stream_metadata(
+        &self,
+        stream_id: StreamId,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<StreamMetadata>, EventStoreError>> + Send + '_>>;
+
+    /// List streams ordered by stream ID.
+    ///
+    /// # Parameters
+    ///
+    /// - `prefix`: Only list streams whose ID starts with this prefix (`None` lists all)
+    /// - `page`: Window of the ordered result to return
+    ///
+    /// # Errors
+    ///
+    /// - `DatabaseError`: Database connection or query failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use composable_rust_core::event_store::{EventStore, Pagination};
+    ///
+    /// async fn list_orders<E: EventStore>(store: &E) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut page = Pagination::first(100);
+    ///     loop {
+    ///         let streams = store.list_streams(Some("order-".to_string()), page).await?;
+    ///         if streams.is_empty() {
+    ///             break;
+    ///         }
+    ///         for stream in &streams {
+    ///             println!("{}: {} events", stream.stream_id, stream.event_count);
+    ///         }
+    ///         page = page.next();
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    fn list_streams(
+        &self,
+        prefix: Option<String>,
+        page: Pagination,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<StreamMetadata>, EventStoreError>> + Send + '_>>;
 }
 
 #[cfg(test)]
@@ -478,6 +672,12 @@ mod tests {
         assert!(display.contains("found 7"));
     }
 
+    #[test]
+    fn pagination_next_advances_by_limit() {
+        let page = Pagination::first(25).next().next();
+        assert_eq!(page, Pagination::new(50, 25));
+    }
+
     #[test]
     fn stream_not_found_error_display() {
         let error = EventStoreError::StreamNotFound(StreamId::new("missing-stream"));
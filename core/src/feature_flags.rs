@@ -0,0 +1,207 @@
+//! Feature flags as an environment dependency.
+//!
+//! Reducers that branch on a flag read it from their environment through
+//! [`FeatureFlags::is_enabled`], never from a global. The flag check is then
+//! part of the reducer's declared dependencies, and tests flip flags per test
+//! without touching shared state.
+//!
+//! [`StaticFeatureFlags`] answers from a fixed set of rules, which is enough
+//! for flags loaded from configuration at startup. Flag services with remote
+//! evaluation implement the trait themselves; since reducers are synchronous,
+//! such an implementation answers from a locally cached copy of the rules.
+//!
+//! ```rust,ignore
+//! use composable_rust_core::base_environment::HasFeatureFlags;
+//! use composable_rust_core::feature_flags::FlagContext;
+//!
+//! let context = FlagContext::new().with_user(&order.customer_id);
+//! if env.feature_flags().is_enabled("express-checkout", &context) {
+//!     // ...
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Who a flag is being evaluated for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlagContext {
+    /// User the decision is made for
+    pub user_id: Option<String>,
+    /// Tenant the decision is made for
+    pub tenant_id: Option<String>,
+    /// Other attributes a flag implementation may target (region, plan, ...)
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl FlagContext {
+    /// An anonymous context without user, tenant or attributes
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluate the flag for `user_id`
+    #[must_use]
+    pub fn with_user(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Evaluate the flag for `tenant_id`
+    #[must_use]
+    pub fn with_tenant(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Add an attribute to target on
+    #[must_use]
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Source of feature flag decisions
+pub trait FeatureFlags: Send + Sync {
+    /// Whether `flag` is enabled for `context`
+    ///
+    /// Unknown flags are disabled unless the implementation documents
+    /// otherwise.
+    fn is_enabled(&self, flag: &str, context: &FlagContext) -> bool;
+}
+
+/// How a [`StaticFeatureFlags`] decides one flag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlagRule {
+    /// Enabled for everyone
+    On,
+    /// Disabled for everyone
+    Off,
+    /// Enabled only for these user ids
+    Users(HashSet<String>),
+    /// Enabled only for these tenant ids
+    Tenants(HashSet<String>),
+}
+
+impl FlagRule {
+    fn matches(&self, context: &FlagContext) -> bool {
+        match self {
+            Self::On => true,
+            Self::Off => false,
+            Self::Users(users) => context
+                .user_id
+                .as_ref()
+                .is_some_and(|id| users.contains(id)),
+            Self::Tenants(tenants) => context
+                .tenant_id
+                .as_ref()
+                .is_some_and(|id| tenants.contains(id)),
+        }
+    }
+}
+
+/// Feature flags answered from a fixed set of rules
+///
+/// Flags without a rule are disabled. Flags off by default are the safe
+/// choice for anything the environment does not configure, so
+/// `StaticFeatureFlags::default()` disables everything.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_core::feature_flags::{FeatureFlags, FlagContext, StaticFeatureFlags};
+///
+/// let flags = StaticFeatureFlags::new()
+///     .with_enabled("new-pricing")
+///     .with_users("beta-search", ["alice"]);
+///
+/// assert!(flags.is_enabled("new-pricing", &FlagContext::new()));
+/// assert!(flags.is_enabled("beta-search", &FlagContext::new().with_user("alice")));
+/// assert!(!flags.is_enabled("beta-search", &FlagContext::new().with_user("bob")));
+/// assert!(!flags.is_enabled("unknown", &FlagContext::new()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StaticFeatureFlags {
+    rules: HashMap<String, FlagRule>,
+}
+
+impl StaticFeatureFlags {
+    /// Flags with no rules: everything is disabled
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decide `flag` by `rule`, replacing any previous rule
+    #[must_use]
+    pub fn with_rule(mut self, flag: impl Into<String>, rule: FlagRule) -> Self {
+        self.rules.insert(flag.into(), rule);
+        self
+    }
+
+    /// Enable `flag` for everyone
+    #[must_use]
+    pub fn with_enabled(self, flag: impl Into<String>) -> Self {
+        self.with_rule(flag, FlagRule::On)
+    }
+
+    /// Enable `flag` only for the given users
+    #[must_use]
+    pub fn with_users<I, S>(self, flag: impl Into<String>, users: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with_rule(
+            flag,
+            FlagRule::Users(users.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// Enable `flag` only for the given tenants
+    #[must_use]
+    pub fn with_tenants<I, S>(self, flag: impl Into<String>, tenants: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with_rule(
+            flag,
+            FlagRule::Tenants(tenants.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// The rule deciding `flag`, if any
+    #[must_use]
+    pub fn rule(&self, flag: &str) -> Option<&FlagRule> {
+        self.rules.get(flag)
+    }
+}
+
+impl FeatureFlags for StaticFeatureFlags {
+    fn is_enabled(&self, flag: &str, context: &FlagContext) -> bool {
+        self.rules
+            .get(flag)
+            .is_some_and(|rule| rule.matches(context))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_target_users_and_tenants() {
+        let flags = StaticFeatureFlags::new()
+            .with_tenants("bulk-export", ["acme"])
+            .with_enabled("dark-mode")
+            .with_rule("dark-mode", FlagRule::Off);
+
+        let acme = FlagContext::new().with_tenant("acme").with_user("alice");
+        assert!(flags.is_enabled("bulk-export", &acme));
+        assert!(!flags.is_enabled("bulk-export", &FlagContext::new().with_user("alice")));
+        assert!(!flags.is_enabled("dark-mode", &acme));
+        assert_eq!(flags.rule("dark-mode"), Some(&FlagRule::Off));
+    }
+}
@@ -58,30 +58,87 @@
 //! ```
 
 // Re-export commonly used types
+#[cfg(feature = "chrono")]
 pub use chrono::{DateTime, Utc};
 pub use serde::{Deserialize, Serialize};
 pub use smallvec::{smallvec, SmallVec};
 
 // Phase 2: Event sourcing modules
+pub mod codec;
 pub mod event;
+pub mod event_registry;
 pub mod event_store;
 pub mod stream;
 
 // Phase 3: Event bus for cross-aggregate communication
 pub mod event_bus;
 
+// Event bus topics typed by their event type
+pub mod topic;
+
+// Transactional outbox for atomic append + publish
+pub mod outbox;
+
+// Transient vs permanent error classification for retries
+pub mod retry;
+
+// Feature flags provided by the environment
+pub mod feature_flags;
+
 // Phase 3: Reducer composition utilities
 pub mod composition;
 
 // Phase 5: Projection system for read models (query side of CQRS)
 pub mod projection;
 
+// Read model storage and queries (query side of CQRS)
+pub mod read_model;
+
 // Phase 5: Effect helper macros for ergonomic effect construction
 pub mod effect_macros;
 
+// Builders for event store and event bus effects
+pub mod effect_builders;
+
+// Comparable effect summaries for assertions and snapshot tests
+pub mod effect_description;
+
+// Standard environment and `Has*` accessor traits for composing environments
+#[cfg(feature = "chrono")]
+pub mod base_environment;
+
 // Phase 8: Agent types for AI agent systems
+#[cfg(feature = "agent")]
 pub mod agent;
 
+/// Common imports for defining features
+///
+/// ```ignore
+/// use composable_rust_core::prelude::*;
+/// ```
+pub mod prelude {
+    pub use crate::action::Correlatable;
+    #[cfg(feature = "chrono")]
+    pub use crate::base_environment::{
+        BaseEnvironment, HasClock, HasEventBus, HasEventStore, HasFeatureFlags,
+    };
+    pub use crate::effect::Effect;
+    #[cfg(feature = "chrono")]
+    pub use crate::environment::Clock;
+    pub use crate::environment::{SystemClock, TimeProvider, Timestamp};
+    pub use crate::event::{DomainEvent, Event, EventMetadata, SerializedEvent};
+    pub use crate::event_bus::{EventBus, PublishOptions};
+    pub use crate::event_store::EventStore;
+    pub use crate::feature_flags::{FeatureFlags, FlagContext};
+    pub use crate::read_model::ReadModelStore;
+    pub use crate::topic::{Topic, TypedEventBus};
+    pub use crate::reducer::Reducer;
+    pub use crate::stream::{StreamId, Version};
+    pub use crate::{smallvec, Deserialize, Serialize, SmallVec};
+    #[cfg(feature = "chrono")]
+    pub use crate::{DateTime, Utc};
+}
+
 /// Action module - Unified input type for reducers (commands, events, cross-aggregate events)
 ///
 /// # Phase 1 Implementation
@@ -93,7 +150,60 @@ pub mod agent;
 ///
 /// Actions represent all possible state transitions in the system.
 /// They unify commands (requests to change state) and events (facts about what happened).
-pub mod action {}
+pub mod action {
+    pub use uuid::Uuid;
+
+    /// Actions that carry a correlation ID linking a request to its results.
+    ///
+    /// Implementing this trait lets the Store run request-response exchanges
+    /// (`Store::send_correlated`) without hand-written predicates: the request
+    /// is stamped with a correlation ID, and the first effect-produced action
+    /// carrying the same ID is returned. Reducers are responsible for copying
+    /// the ID from a command onto the actions its effects produce.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use composable_rust_core::action::{Correlatable, Uuid};
+    ///
+    /// #[derive(Clone, Debug)]
+    /// enum OrderAction {
+    ///     PlaceOrder { correlation_id: Option<Uuid>, sku: String },
+    ///     OrderPlaced { correlation_id: Option<Uuid>, order_id: u64 },
+    /// }
+    ///
+    /// impl Correlatable for OrderAction {
+    ///     fn correlation_id(&self) -> Option<Uuid> {
+    ///         match self {
+    ///             Self::PlaceOrder { correlation_id, .. }
+    ///             | Self::OrderPlaced { correlation_id, .. } => *correlation_id,
+    ///         }
+    ///     }
+    ///
+    ///     fn with_correlation_id(mut self, id: Uuid) -> Self {
+    ///         match &mut self {
+    ///             Self::PlaceOrder { correlation_id, .. }
+    ///             | Self::OrderPlaced { correlation_id, .. } => *correlation_id = Some(id),
+    ///         }
+    ///         self
+    ///     }
+    /// }
+    ///
+    /// let id = Uuid::from_u128(7);
+    /// let action = OrderAction::PlaceOrder { correlation_id: None, sku: "A1".into() };
+    /// assert_eq!(action.with_correlation_id(id).correlation_id(), Some(id));
+    /// ```
+    pub trait Correlatable {
+        /// The correlation ID carried by this action, if any.
+        fn correlation_id(&self) -> Option<Uuid>;
+
+        /// Return this action stamped with `correlation_id`.
+        ///
+        /// Actions that cannot carry an ID may return `self` unchanged.
+        #[must_use]
+        fn with_correlation_id(self, correlation_id: Uuid) -> Self;
+    }
+}
 
 /// State module - Domain state types and utilities
 ///
@@ -122,8 +232,58 @@ pub mod state {}
 /// They contain all business logic and are deterministic and testable.
 pub mod reducer {
     use super::effect::Effect;
+    use crate::environment::{self, Timestamp};
+    use crate::event::EventMetadata;
     use smallvec::SmallVec;
 
+    /// What the runtime knows about the action being reduced
+    ///
+    /// Passed by the Store to [`Reducer::reduce_with_context`] on every
+    /// reduction.
+    #[derive(Debug, Clone)]
+    pub struct ReduceContext {
+        /// The action is replayed from history (state hydration), not live
+        ///
+        /// Its side effects already happened before the restart; the Store
+        /// discards the effects returned for it, but reducers that charge,
+        /// count or notify while reducing should skip that work too.
+        pub is_replay: bool,
+
+        /// When the runtime dispatched the action
+        pub dispatched_at: Timestamp,
+
+        /// Request metadata the action was sent with (correlation id, user, ...)
+        pub metadata: Option<EventMetadata>,
+    }
+
+    impl ReduceContext {
+        /// Context of a live action, dispatched now
+        #[must_use]
+        pub fn live(metadata: Option<EventMetadata>) -> Self {
+            Self {
+                is_replay: false,
+                dispatched_at: environment::now(),
+                metadata,
+            }
+        }
+
+        /// Context of an action replayed from history, dispatched now
+        #[must_use]
+        pub fn replay() -> Self {
+            Self {
+                is_replay: true,
+                dispatched_at: environment::now(),
+                metadata: None,
+            }
+        }
+    }
+
+    impl Default for ReduceContext {
+        fn default() -> Self {
+            Self::live(None)
+        }
+    }
+
     /// The Reducer trait - core abstraction for business logic
     ///
     /// # Type Parameters
@@ -191,6 +351,32 @@ pub mod reducer {
             action: Self::Action,
             env: &Self::Environment,
         ) -> SmallVec<[Effect<Self::Action>; 4]>;
+
+        /// Reduce an action knowing how the runtime dispatched it
+        ///
+        /// The Store calls this method rather than [`reduce`](Self::reduce).
+        /// The default ignores the context; override it to tell replayed
+        /// actions from live ones, or to read the request metadata:
+        ///
+        /// ```ignore
+        /// fn reduce_with_context(&self, state: &mut State, action: Action, env: &Env, context: &ReduceContext) -> SmallVec<[Effect<Action>; 4]> {
+        ///     let effects = self.reduce(state, action, env);
+        ///     if !context.is_replay {
+        ///         env.billing.record_usage(state.plan);
+        ///     }
+        ///     effects
+        /// }
+        /// ```
+        fn reduce_with_context(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            env: &Self::Environment,
+            context: &ReduceContext,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            let _ = context;
+            self.reduce(state, action, env)
+        }
     }
 }
 
@@ -206,6 +392,7 @@ pub mod reducer {
 /// Effects describe side effects to be performed by the runtime.
 /// They are values (not execution) and are composable and cancellable.
 pub mod effect {
+    use std::borrow::Cow;
     use std::future::Future;
     use std::pin::Pin;
     use std::time::Duration;
@@ -214,11 +401,19 @@ pub mod effect {
     use futures::StreamExt;
 
     use crate::event::SerializedEvent;
-    use crate::event_bus::{EventBus, EventBusError};
+    use crate::event_bus::{EventBus, EventBusError, PublishOptions};
     use crate::event_store::{EventStore, EventStoreError};
+    use crate::read_model::{ReadModelError, ReadModelQuery, ReadModelRow, ReadModelStore};
     use crate::stream::{StreamId, Version};
     use std::sync::Arc;
 
+    pub use tokio_util::sync::CancellationToken;
+
+    /// Starts an [`Effect::FutureWithCancel`] from the Store's cancellation token
+    pub type CancellableFuture<Action> = Box<
+        dyn FnOnce(CancellationToken) -> Pin<Box<dyn Future<Output = Option<Action>> + Send>> + Send,
+    >;
+
     /// Type alias for snapshot data: `(Version, Vec<u8>)`
     type SnapshotData = (Version, Vec<u8>);
 
@@ -268,6 +463,13 @@ pub mod effect {
             /// that should be propagated through all events in this effect.
             /// If an event already has metadata, this will be merged in (field-by-field).
             metadata: Option<crate::event::EventMetadata>,
+            /// Optional key identifying this write, typically the command ID
+            ///
+            /// When set, the append goes through
+            /// [`EventStore::append_events_idempotent`], so a retry after an
+            /// ambiguous failure returns the committed version instead of
+            /// appending the events twice.
+            idempotency_key: Option<String>,
             /// Callback invoked on success with the new version
             on_success: Box<dyn Fn(Version) -> Option<Action> + Send + Sync>,
             /// Callback invoked on error
@@ -288,6 +490,29 @@ pub mod effect {
             on_error: Box<dyn Fn(EventStoreError) -> Option<Action> + Send + Sync>,
         },
 
+        /// Load events from a stream one page at a time, for folding long
+        /// streams with bounded memory.
+        ///
+        /// Each page's action is reduced before the next page is loaded, so
+        /// at most one page is held in memory. A failed page stops the load.
+        LoadEventsPaged {
+            /// The event store implementation to use
+            event_store: Arc<dyn EventStore>,
+            /// The stream to load events from
+            stream_id: StreamId,
+            /// Version of the first event to load
+            from_version: Version,
+            /// Maximum number of events per page (at least 1)
+            page_size: usize,
+            /// Callback invoked with each non-empty page, in stream order
+            on_page: Box<dyn Fn(Vec<SerializedEvent>) -> Option<Action> + Send + Sync>,
+            /// Callback invoked after the last page with the version following
+            /// the last loaded event (the `from_version` to resume from)
+            on_complete: Box<dyn Fn(Version) -> Option<Action> + Send + Sync>,
+            /// Callback invoked on error
+            on_error: Box<dyn Fn(EventStoreError) -> Option<Action> + Send + Sync>,
+        },
+
         /// Save a state snapshot for an aggregate.
         SaveSnapshot {
             /// The event store implementation to use
@@ -338,6 +563,7 @@ pub mod effect {
     ///     event_bus: Arc::clone(&event_bus),
     ///     topic: "order-events".to_string(),
     ///     event: serialized_event,
+    ///     options: PublishOptions::new().with_partition_key(order_id.to_string()),
     ///     on_success: Box::new(|| Some(OrderAction::EventPublished)),
     ///     on_error: Box::new(|error| {
     ///         Some(OrderAction::PublishFailed { error: error.to_string() })
@@ -353,6 +579,8 @@ pub mod effect {
             topic: String,
             /// The event to publish
             event: SerializedEvent,
+            /// Partition key, headers and delivery timeout
+            options: PublishOptions,
             /// Callback invoked on successful publish
             on_success: Box<dyn Fn(()) -> Option<Action> + Send + Sync>,
             /// Callback invoked on error
@@ -360,6 +588,165 @@ pub mod effect {
         },
     }
 
+    /// Read model operation descriptions for the `Effect::Query` variant.
+    ///
+    /// These operations load read models through the `ReadModelStore` in the
+    /// environment, so read-path reducers stay free of direct database access.
+    /// As with event store operations, the callbacks turn the result into an
+    /// optional action fed back into the reducer loop.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use composable_rust_core::effect::QueryOperation;
+    ///
+    /// let op = QueryOperation::Get {
+    ///     store: Arc::clone(&env.read_models),
+    ///     collection: "order_summaries".to_string(),
+    ///     key: order_id.to_string(),
+    ///     on_success: Box::new(|summary| Some(OrderAction::SummaryLoaded { summary })),
+    ///     on_error: Box::new(|error| Some(OrderAction::QueryFailed { error: error.to_string() })),
+    /// };
+    /// ```
+    pub enum QueryOperation<Action> {
+        /// Load one read model by key.
+        Get {
+            /// The read model store to use
+            store: Arc<dyn ReadModelStore>,
+            /// Collection holding the read model
+            collection: String,
+            /// Key of the read model
+            key: String,
+            /// Callback invoked with the document, or `None` if absent
+            on_success: Box<dyn Fn(Option<serde_json::Value>) -> Option<Action> + Send + Sync>,
+            /// Callback invoked on error
+            on_error: Box<dyn Fn(ReadModelError) -> Option<Action> + Send + Sync>,
+        },
+
+        /// Load the read models selected by a query.
+        Query {
+            /// The read model store to use
+            store: Arc<dyn ReadModelStore>,
+            /// Collection, filters, ordering and pagination
+            query: ReadModelQuery,
+            /// Callback invoked with the matching rows
+            on_success: Box<dyn Fn(Vec<ReadModelRow>) -> Option<Action> + Send + Sync>,
+            /// Callback invoked on error
+            on_error: Box<dyn Fn(ReadModelError) -> Option<Action> + Send + Sync>,
+        },
+    }
+
+    /// Declared cost of an effect, for runtime budgeting.
+    ///
+    /// A cost is a weight in a named resource category (e.g. `"http"` or
+    /// `"event_store"`). Stores configured with budgets add up the costs of
+    /// every effect produced for a root action and reject or flag actions
+    /// that exceed the limit for a category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use composable_rust_core::effect::{Effect, EffectCost};
+    ///
+    /// let effect: Effect<()> = Effect::Future(Box::pin(async { None }))
+    ///     .with_cost(EffectCost::new("http", 1));
+    ///
+    /// assert!(matches!(effect, Effect::Costed { .. }));
+    /// ```
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct EffectCost {
+        /// Resource category this cost is charged against
+        pub kind: Cow<'static, str>,
+        /// Weight charged for one execution of the effect
+        pub weight: u64,
+    }
+
+    impl EffectCost {
+        /// Create a cost of `weight` units in the `kind` category
+        #[must_use]
+        pub fn new(kind: impl Into<Cow<'static, str>>, weight: u64) -> Self {
+            Self {
+                kind: kind.into(),
+                weight,
+            }
+        }
+    }
+
+    /// Retry behavior for the event store and event bus operations of one effect.
+    ///
+    /// Overrides the Store's retry policy (and any per-operation policy) for
+    /// the operations inside an effect wrapped with [`Effect::with_retry`].
+    /// Errors that can never succeed on retry are still returned at once, and
+    /// exhausted failures are dead-lettered as the operation's policy says.
+    ///
+    /// The runtime's `RetryPolicy` converts into this type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use composable_rust_core::effect::{Effect, EffectRetry};
+    /// use std::time::Duration;
+    ///
+    /// // A user is waiting: fail fast instead of backing off for half a minute
+    /// let effect: Effect<()> = Effect::Future(Box::pin(async { None })).with_retry(EffectRetry::never());
+    /// assert!(matches!(effect, Effect::Retrying { retry, .. } if retry.max_attempts == 1));
+    ///
+    /// let aggressive = EffectRetry::attempts(10).with_initial_delay(Duration::from_millis(50));
+    /// assert_eq!(aggressive.max_attempts, 10);
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct EffectRetry {
+        /// Maximum number of attempts, including the first
+        pub max_attempts: u32,
+        /// Delay before the first retry
+        pub initial_delay: Duration,
+        /// Cap on the delay between retries
+        pub max_delay: Duration,
+        /// Factor applied to the delay after every retry
+        pub backoff_multiplier: f64,
+    }
+
+    impl EffectRetry {
+        /// Try `max_attempts` times, with the Store's default backoff
+        /// (1 second doubling up to 32 seconds)
+        #[must_use]
+        pub const fn attempts(max_attempts: u32) -> Self {
+            Self {
+                max_attempts,
+                initial_delay: Duration::from_secs(1),
+                max_delay: Duration::from_secs(32),
+                backoff_multiplier: 2.0,
+            }
+        }
+
+        /// Try once and never retry
+        #[must_use]
+        pub const fn never() -> Self {
+            Self::attempts(1)
+        }
+
+        /// Set the delay before the first retry
+        #[must_use]
+        pub const fn with_initial_delay(mut self, delay: Duration) -> Self {
+            self.initial_delay = delay;
+            self
+        }
+
+        /// Set the cap on the delay between retries
+        #[must_use]
+        pub const fn with_max_delay(mut self, delay: Duration) -> Self {
+            self.max_delay = delay;
+            self
+        }
+
+        /// Set the backoff multiplier
+        #[must_use]
+        pub const fn with_backoff_multiplier(mut self, multiplier: f64) -> Self {
+            self.backoff_multiplier = multiplier;
+            self
+        }
+    }
+
     /// Effect type - describes a side effect to be executed
     ///
     /// Effects are NOT executed immediately. They are descriptions of what should happen,
@@ -395,11 +782,46 @@ pub mod effect {
             action: Box<Action>,
         },
 
+        /// Delayed action that replaces any pending delay with the same key
+        ///
+        /// Scheduling a keyed delay while an earlier one with the same `key` is
+        /// still waiting cancels the earlier one, so a timeout re-armed on every
+        /// update fires once, with the latest action. Keys are scoped to the Store.
+        DelayKeyed {
+            /// Identifies the timer; a new delay with this key replaces the pending one
+            key: Cow<'static, str>,
+            /// How long to wait
+            duration: Duration,
+            /// Action to dispatch after delay
+            action: Box<Action>,
+        },
+
         /// Arbitrary async computation
         ///
         /// Returns `Option<Action>` - if Some, the action is fed back into the reducer
         Future(Pin<Box<dyn Future<Output = Option<Action>> + Send>>),
 
+        /// Async computation that can stop early when the Store shuts down
+        ///
+        /// Called with a [`CancellationToken`] that is cancelled as soon as
+        /// the Store starts shutting down, so long-running work (polling,
+        /// streaming responses, waiting on timers) can exit cleanly instead
+        /// of running to completion and having its feedback rejected. The
+        /// returned action, if any, is fed back like that of `Future`. Build
+        /// with [`Effect::future_with_cancel`].
+        ///
+        /// # Examples
+        ///
+        /// ```rust,ignore
+        /// Effect::future_with_cancel(move |token| async move {
+        ///     tokio::select! {
+        ///         () = token.cancelled() => None,
+        ///         rows = poll_until_ready(&client) => Some(Action::Ready { rows }),
+        ///     }
+        /// })
+        /// ```
+        FutureWithCancel(CancellableFuture<Action>),
+
         /// Stream of actions over time (Phase 8)
         ///
         /// Unlike `Future` which yields 0 or 1 action, `Stream` yields 0..N actions
@@ -533,6 +955,7 @@ pub mod effect {
         ///     event_bus: Arc::clone(&event_bus),
         ///     topic: "order-events".to_string(),
         ///     event: serialized_event,
+        ///     options: PublishOptions::default(),
         ///     on_success: Box::new(|| Some(OrderAction::EventPublished)),
         ///     on_error: Box::new(|error| {
         ///         Some(OrderAction::PublishFailed { error: error.to_string() })
@@ -540,6 +963,49 @@ pub mod effect {
         /// });
         /// ```
         PublishEvent(EventBusOperation<Action>),
+
+        /// Read model query (CQRS query side)
+        ///
+        /// Loads read models through a `ReadModelStore`, feeding the result
+        /// back as an action.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,ignore
+        /// use composable_rust_core::effect::{Effect, QueryOperation};
+        /// use composable_rust_core::read_model::{Filter, ReadModelQuery};
+        ///
+        /// let effect = Effect::Query(QueryOperation::Query {
+        ///     store: Arc::clone(&env.read_models),
+        ///     query: ReadModelQuery::new("orders").filter(Filter::eq("customer_id", customer_id)),
+        ///     on_success: Box::new(|rows| Some(OrderAction::OrdersLoaded { rows })),
+        ///     on_error: Box::new(|error| Some(OrderAction::QueryFailed { error: error.to_string() })),
+        /// });
+        /// ```
+        Query(QueryOperation<Action>),
+
+        /// An effect annotated with a declared cost
+        ///
+        /// The runtime executes the wrapped effect unchanged; the cost is only
+        /// used for budget accounting. Build with [`Effect::with_cost`].
+        Costed {
+            /// Cost charged when the effect is executed
+            cost: EffectCost,
+            /// The effect being charged for
+            effect: Box<Effect<Action>>,
+        },
+
+        /// An effect whose operations retry with their own policy
+        ///
+        /// Event store and event bus operations anywhere inside the wrapped
+        /// effect use `retry` instead of the Store's retry policy. Build with
+        /// [`Effect::with_retry`].
+        Retrying {
+            /// Retry behavior for the wrapped operations
+            retry: EffectRetry,
+            /// The effect being retried
+            effect: Box<Effect<Action>>,
+        },
         // Additional effect variants will be added in future phases:
         // - Http { request, on_success, on_error }
         // - Cancellable { id, effect }
@@ -551,6 +1017,7 @@ pub mod effect {
     where
         Action: std::fmt::Debug,
     {
+        #[allow(clippy::too_many_lines)] // One arm per effect and operation variant
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
                 Effect::None => write!(f, "Effect::None"),
@@ -565,7 +1032,18 @@ pub mod effect {
                     .field("duration", duration)
                     .field("action", action)
                     .finish(),
+                Effect::DelayKeyed {
+                    key,
+                    duration,
+                    action,
+                } => f
+                    .debug_struct("Effect::DelayKeyed")
+                    .field("key", key)
+                    .field("duration", duration)
+                    .field("action", action)
+                    .finish(),
                 Effect::Future(_) => write!(f, "Effect::Future(<future>)"),
+                Effect::FutureWithCancel(_) => write!(f, "Effect::FutureWithCancel(<future>)"),
                 Effect::Stream(_) => write!(f, "Effect::Stream(<stream>)"),
                 Effect::EventStore(op) => match op {
                     EventStoreOperation::AppendEvents {
@@ -573,6 +1051,7 @@ pub mod effect {
                         expected_version,
                         events,
                         metadata,
+                        idempotency_key,
                         ..
                     } => f
                         .debug_struct("Effect::EventStore::AppendEvents")
@@ -580,6 +1059,7 @@ pub mod effect {
                         .field("expected_version", expected_version)
                         .field("event_count", &events.len())
                         .field("metadata", metadata)
+                        .field("idempotency_key", idempotency_key)
                         .field("event_store", &"<event_store>")
                         .finish(),
                     EventStoreOperation::LoadEvents {
@@ -592,6 +1072,18 @@ pub mod effect {
                         .field("from_version", from_version)
                         .field("event_store", &"<event_store>")
                         .finish(),
+                    EventStoreOperation::LoadEventsPaged {
+                        stream_id,
+                        from_version,
+                        page_size,
+                        ..
+                    } => f
+                        .debug_struct("Effect::EventStore::LoadEventsPaged")
+                        .field("stream_id", stream_id)
+                        .field("from_version", from_version)
+                        .field("page_size", page_size)
+                        .field("event_store", &"<event_store>")
+                        .finish(),
                     EventStoreOperation::SaveSnapshot {
                         stream_id,
                         version,
@@ -618,6 +1110,29 @@ pub mod effect {
                         .field("event_bus", &"<event_bus>")
                         .finish(),
                 },
+                Effect::Query(op) => match op {
+                    QueryOperation::Get { collection, key, .. } => f
+                        .debug_struct("Effect::Query::Get")
+                        .field("collection", collection)
+                        .field("key", key)
+                        .field("store", &"<read_model_store>")
+                        .finish(),
+                    QueryOperation::Query { query, .. } => f
+                        .debug_struct("Effect::Query::Query")
+                        .field("query", query)
+                        .field("store", &"<read_model_store>")
+                        .finish(),
+                },
+                Effect::Costed { cost, effect } => f
+                    .debug_struct("Effect::Costed")
+                    .field("cost", cost)
+                    .field("effect", effect)
+                    .finish(),
+                Effect::Retrying { retry, effect } => f
+                    .debug_struct("Effect::Retrying")
+                    .field("retry", retry)
+                    .field("effect", effect)
+                    .finish(),
             }
         }
     }
@@ -635,6 +1150,44 @@ pub mod effect {
             Effect::Sequential(effects)
         }
 
+        /// Run `start` as an [`Effect::FutureWithCancel`]
+        ///
+        /// `start` receives a token cancelled when the Store begins shutting
+        /// down.
+        #[must_use]
+        pub fn future_with_cancel<F, Fut>(start: F) -> Effect<Action>
+        where
+            F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+            Fut: Future<Output = Option<Action>> + Send + 'static,
+            Action: 'static,
+        {
+            Effect::FutureWithCancel(Box::new(move |token| Box::pin(start(token))))
+        }
+
+        /// Annotate this effect with a declared cost
+        ///
+        /// The cost is charged against the Store's effect budget for the root
+        /// action (if budgets are configured); execution is otherwise unchanged.
+        #[must_use]
+        pub fn with_cost(self, cost: EffectCost) -> Effect<Action> {
+            Effect::Costed {
+                cost,
+                effect: Box::new(self),
+            }
+        }
+
+        /// Retry this effect's event store and event bus operations with
+        /// `retry` instead of the Store's retry policy
+        ///
+        /// The innermost override wins when wrappers are nested.
+        #[must_use]
+        pub fn with_retry(self, retry: impl Into<EffectRetry>) -> Effect<Action> {
+            Effect::Retrying {
+                retry: retry.into(),
+                effect: Box::new(self),
+            }
+        }
+
         /// Transform the action type of this effect
         ///
         /// This is useful for composing effects from different reducers or
@@ -696,10 +1249,34 @@ pub mod effect {
                     duration,
                     action: Box::new(f(*action)),
                 },
+                Effect::DelayKeyed {
+                    key,
+                    duration,
+                    action,
+                } => Effect::DelayKeyed {
+                    key,
+                    duration,
+                    action: Box::new(f(*action)),
+                },
                 Effect::Future(fut) => Effect::Future(Box::pin(async move { fut.await.map(f) })),
+                Effect::FutureWithCancel(start) => {
+                    Effect::FutureWithCancel(Box::new(move |token| {
+                        let fut = start(token);
+                        Box::pin(async move { fut.await.map(f) })
+                    }))
+                },
                 Effect::Stream(stream) => Effect::Stream(Box::pin(stream.map(f))),
                 Effect::EventStore(op) => Effect::EventStore(map_event_store_operation(op, f)),
                 Effect::PublishEvent(op) => Effect::PublishEvent(map_event_bus_operation(op, f)),
+                Effect::Query(op) => Effect::Query(map_query_operation(op, f)),
+                Effect::Costed { cost, effect } => Effect::Costed {
+                    cost,
+                    effect: Box::new(map_effect(*effect, f)),
+                },
+                Effect::Retrying { retry, effect } => Effect::Retrying {
+                    retry,
+                    effect: Box::new(map_effect(*effect, f)),
+                },
             }
         }
     }
@@ -737,14 +1314,37 @@ pub mod effect {
                 duration,
                 action: Box::new(f(*action)),
             },
+            Effect::DelayKeyed {
+                key,
+                duration,
+                action,
+            } => Effect::DelayKeyed {
+                key,
+                duration,
+                action: Box::new(f(*action)),
+            },
             Effect::Future(fut) => Effect::Future(Box::pin(async move { fut.await.map(f) })),
+            Effect::FutureWithCancel(start) => Effect::FutureWithCancel(Box::new(move |token| {
+                let fut = start(token);
+                Box::pin(async move { fut.await.map(f) })
+            })),
             Effect::Stream(stream) => Effect::Stream(Box::pin(stream.map(f))),
             Effect::EventStore(op) => Effect::EventStore(map_event_store_operation(op, f)),
             Effect::PublishEvent(op) => Effect::PublishEvent(map_event_bus_operation(op, f)),
+            Effect::Query(op) => Effect::Query(map_query_operation(op, f)),
+            Effect::Costed { cost, effect } => Effect::Costed {
+                cost,
+                effect: Box::new(map_effect(*effect, f)),
+            },
+            Effect::Retrying { retry, effect } => Effect::Retrying {
+                retry,
+                effect: Box::new(map_effect(*effect, f)),
+            },
         }
     }
 
     // Helper function to map EventStoreOperation callbacks to new action type
+    #[allow(clippy::too_many_lines)] // One arm per operation variant
     fn map_event_store_operation<A, B, F>(
         op: EventStoreOperation<A>,
         f: F,
@@ -761,6 +1361,7 @@ pub mod effect {
                 expected_version,
                 events,
                 metadata,
+                idempotency_key,
                 on_success,
                 on_error,
             } => {
@@ -772,6 +1373,7 @@ pub mod effect {
                     expected_version,
                     events,
                     metadata,
+                    idempotency_key,
                     on_success: Box::new(move |version| {
                         on_success(version).map(|a| f_success.clone()(a))
                     }),
@@ -797,6 +1399,30 @@ pub mod effect {
                     on_error: Box::new(move |error| on_error(error).map(|a| f_error.clone()(a))),
                 }
             },
+            EventStoreOperation::LoadEventsPaged {
+                event_store,
+                stream_id,
+                from_version,
+                page_size,
+                on_page,
+                on_complete,
+                on_error,
+            } => {
+                let f_page = f.clone();
+                let f_complete = f.clone();
+                let f_error = f.clone();
+                EventStoreOperation::LoadEventsPaged {
+                    event_store,
+                    stream_id,
+                    from_version,
+                    page_size,
+                    on_page: Box::new(move |events| on_page(events).map(|a| f_page.clone()(a))),
+                    on_complete: Box::new(move |version| {
+                        on_complete(version).map(|a| f_complete.clone()(a))
+                    }),
+                    on_error: Box::new(move |error| on_error(error).map(|a| f_error.clone()(a))),
+                }
+            },
             EventStoreOperation::SaveSnapshot {
                 event_store,
                 stream_id,
@@ -838,6 +1464,53 @@ pub mod effect {
         }
     }
 
+    // Helper function to map QueryOperation callbacks to new action type
+    fn map_query_operation<A, B, F>(op: QueryOperation<A>, f: F) -> QueryOperation<B>
+    where
+        F: Fn(A) -> B + Send + Sync + 'static + Clone,
+        A: 'static,
+        B: Send + 'static,
+    {
+        match op {
+            QueryOperation::Get {
+                store,
+                collection,
+                key,
+                on_success,
+                on_error,
+            } => {
+                let f_success = f.clone();
+                let f_error = f;
+                QueryOperation::Get {
+                    store,
+                    collection,
+                    key,
+                    on_success: Box::new(move |value| {
+                        on_success(value).map(|a| f_success.clone()(a))
+                    }),
+                    on_error: Box::new(move |error| on_error(error).map(|a| f_error.clone()(a))),
+                }
+            },
+            QueryOperation::Query {
+                store,
+                query,
+                on_success,
+                on_error,
+            } => {
+                let f_success = f.clone();
+                let f_error = f;
+                QueryOperation::Query {
+                    store,
+                    query,
+                    on_success: Box::new(move |rows| {
+                        on_success(rows).map(|a| f_success.clone()(a))
+                    }),
+                    on_error: Box::new(move |error| on_error(error).map(|a| f_error.clone()(a))),
+                }
+            },
+        }
+    }
+
     // Helper function to map EventBusOperation callbacks to new action type
     fn map_event_bus_operation<A, B, F>(op: EventBusOperation<A>, f: F) -> EventBusOperation<B>
     where
@@ -850,6 +1523,7 @@ pub mod effect {
                 event_bus,
                 topic,
                 event,
+                options,
                 on_success,
                 on_error,
             } => {
@@ -859,6 +1533,7 @@ pub mod effect {
                     event_bus,
                     topic,
                     event,
+                    options,
                     on_success: Box::new(move |unit| {
                         on_success(unit).map(|a| f_success.clone()(a))
                     }),
@@ -881,10 +1556,66 @@ pub mod effect {
 /// All external dependencies are abstracted behind traits and injected
 /// via the Environment parameter.
 pub mod environment {
+    #[cfg(feature = "chrono")]
     use chrono::{DateTime, Utc};
+    use std::time::{Instant, SystemTime};
+
+    /// Wall-clock timestamp used by core types
+    ///
+    /// `DateTime<Utc>` with the `chrono` feature (the default), otherwise
+    /// [`SystemTime`].
+    #[cfg(feature = "chrono")]
+    pub type Timestamp = DateTime<Utc>;
+
+    /// Wall-clock timestamp used by core types
+    ///
+    /// `DateTime<Utc>` with the `chrono` feature (the default), otherwise
+    /// [`SystemTime`].
+    #[cfg(not(feature = "chrono"))]
+    pub type Timestamp = SystemTime;
+
+    /// The current [`Timestamp`]
+    #[must_use]
+    pub fn now() -> Timestamp {
+        #[cfg(feature = "chrono")]
+        {
+            Utc::now()
+        }
+        #[cfg(not(feature = "chrono"))]
+        {
+            SystemTime::now()
+        }
+    }
+
+    /// Time source using only `std` types
+    ///
+    /// The dependency-free counterpart of [`Clock`] for embedded and wasm
+    /// builds without the `chrono` feature. Use [`instant`](Self::instant)
+    /// to measure elapsed time and [`system_time`](Self::system_time) for
+    /// wall-clock timestamps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use composable_rust_core::environment::{SystemClock, TimeProvider};
+    ///
+    /// let clock = SystemClock;
+    /// let started = clock.instant();
+    /// let elapsed = clock.instant() - started;
+    /// ```
+    pub trait TimeProvider: Send + Sync {
+        /// Current wall-clock time
+        fn system_time(&self) -> SystemTime;
+
+        /// Current monotonic instant
+        fn instant(&self) -> Instant;
+    }
 
     /// Clock trait - abstracts time operations for testability
     ///
+    /// Requires the `chrono` feature. Any [`TimeProvider`] can be used as a
+    /// `Clock` through [`ChronoClock`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -894,6 +1625,7 @@ pub mod environment {
     /// let clock = SystemClock;
     /// let now = clock.now();
     /// ```
+    #[cfg(feature = "chrono")]
     pub trait Clock: Send + Sync {
         /// Get the current time
         fn now(&self) -> DateTime<Utc>;
@@ -901,26 +1633,61 @@ pub mod environment {
 
     /// Production clock implementation that uses the system time.
     ///
-    /// This is a zero-sized type that delegates to `chrono::Utc::now()`.
+    /// This is a zero-sized type that delegates to `chrono::Utc::now()` as a
+    /// [`Clock`] and to `SystemTime::now()` / `Instant::now()` as a
+    /// [`TimeProvider`].
     ///
     /// # Example
     ///
     /// ```
-    /// use composable_rust_core::environment::{Clock, SystemClock};
+    /// use composable_rust_core::environment::{SystemClock, TimeProvider};
     ///
     /// let clock = SystemClock;
-    /// let now = clock.now();
-    /// println!("Current time: {}", now);
+    /// let started = clock.instant();
+    /// println!("Current time: {:?}", clock.system_time());
+    /// assert!(started.elapsed() < std::time::Duration::from_secs(60));
     /// ```
     #[derive(Debug, Clone, Copy, Default)]
     pub struct SystemClock;
 
+    #[cfg(feature = "chrono")]
     impl Clock for SystemClock {
         fn now(&self) -> DateTime<Utc> {
             Utc::now()
         }
     }
 
+    impl TimeProvider for SystemClock {
+        fn system_time(&self) -> SystemTime {
+            SystemTime::now()
+        }
+
+        fn instant(&self) -> Instant {
+            Instant::now()
+        }
+    }
+
+    /// Adapter exposing a [`TimeProvider`] as a chrono [`Clock`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use composable_rust_core::environment::{ChronoClock, Clock, SystemClock};
+    ///
+    /// let clock = ChronoClock(SystemClock);
+    /// let now = clock.now();
+    /// ```
+    #[cfg(feature = "chrono")]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct ChronoClock<T>(pub T);
+
+    #[cfg(feature = "chrono")]
+    impl<T: TimeProvider> Clock for ChronoClock<T> {
+        fn now(&self) -> DateTime<Utc> {
+            DateTime::<Utc>::from(self.0.system_time())
+        }
+    }
+
     // Additional traits will be defined during Phase 1:
     // - Database: Event store operations
     // - EventPublisher: Event bus publishing
@@ -934,7 +1701,7 @@ pub mod environment {
 #[allow(clippy::similar_names)] // Test variable names can be similar
 #[allow(clippy::redundant_closure)] // Test closures can be explicit for clarity
 mod tests {
-    use super::effect::Effect;
+    use super::effect::{CancellationToken, Effect};
     use std::time::Duration;
 
     #[derive(Debug, Clone, PartialEq)]
@@ -1101,6 +1868,27 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_effect_map_future_with_cancel() {
+        let effect: Effect<TestAction> = Effect::future_with_cancel(|token| async move {
+            token.cancelled().await;
+            Some(TestAction::Action2)
+        });
+
+        let mapped: Effect<MappedAction> = effect.map(MappedAction::Mapped);
+        assert_eq!(format!("{mapped:?}"), "Effect::FutureWithCancel(<future>)");
+
+        match mapped {
+            Effect::FutureWithCancel(start) => {
+                let token = CancellationToken::new();
+                token.cancel();
+                let result = start(token).await;
+                assert_eq!(result, Some(MappedAction::Mapped(TestAction::Action2)));
+            },
+            _ => panic!("Expected FutureWithCancel effect"),
+        }
+    }
+
     #[test]
     fn test_effect_map_nested() {
         // Test mapping nested effects (Parallel containing Sequential)
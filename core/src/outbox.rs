@@ -0,0 +1,152 @@
+//! Transactional outbox for atomic append + publish.
+//!
+//! Appending to the [`EventStore`] and publishing to the [`EventBus`] are two
+//! separate operations, so a crash between them loses the publish. With the
+//! outbox pattern, the events and one outbox row per event are written in the
+//! **same transaction**; a relay later reads pending outbox rows, publishes
+//! them and marks them published:
+//!
+//! ```text
+//! ┌───────────────────────────────┐
+//! │ append_with_outbox (1 tx)     │
+//! │  - events      (stream, v)    │
+//! │  - outbox rows (topic, v)     │
+//! └──────────────┬────────────────┘
+//!                │ pending_outbox()
+//!                ▼
+//! ┌───────────────────────────────┐      ┌───────────┐
+//! │ Relay: publish, then          │─────▶│ Event Bus │
+//! │        mark_published()       │      └───────────┘
+//! └───────────────────────────────┘
+//! ```
+//!
+//! Delivery is **at-least-once**: a crash after publishing but before marking
+//! republishes the row. Each entry has a stable [`OutboxEntry::message_id`]
+//! (`{stream_id}@{version}`) that relays attach to the published event so
+//! consumers can de-duplicate.
+//!
+//! [`EventBus`]: crate::event_bus::EventBus
+
+use crate::effect::Effect;
+use crate::event::SerializedEvent;
+use crate::event_store::{EventStore, EventStoreError};
+use crate::stream::{StreamId, Version};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// A stored event waiting to be published
+#[derive(Clone, Debug)]
+pub struct OutboxEntry {
+    /// Position in the outbox (monotonically increasing)
+    pub sequence: u64,
+    /// Stream the event was appended to
+    pub stream_id: StreamId,
+    /// Version of the event within its stream
+    pub version: Version,
+    /// Topic the event must be published to
+    pub topic: String,
+    /// The event as stored
+    pub event: SerializedEvent,
+}
+
+impl OutboxEntry {
+    /// Stable de-duplication key for this event: `{stream_id}@{version}`
+    #[must_use]
+    pub fn message_id(&self) -> String {
+        format!("{}@{}", self.stream_id, self.version.value())
+    }
+}
+
+/// Event store that can record outbox rows atomically with events.
+///
+/// # Dyn Compatibility
+///
+/// Like [`EventStore`], methods return `Pin<Box<dyn Future>>` so the trait can
+/// be used as `Arc<dyn OutboxStore>`.
+pub trait OutboxStore: EventStore {
+    /// Append events and enqueue each of them for publishing to `topic`.
+    ///
+    /// Both writes happen in one transaction: either the events and their
+    /// outbox rows are all stored, or nothing is. Concurrency semantics are the
+    /// same as [`EventStore::append_events`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventStoreError::ConcurrencyConflict`] if `expected_version`
+    /// does not match, or [`EventStoreError::DatabaseError`] on storage failure.
+    fn append_with_outbox(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+        topic: String,
+    ) -> Pin<Box<dyn Future<Output = Result<Version, EventStoreError>> + Send + '_>>;
+
+    /// Load up to `limit` unpublished entries, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventStoreError::DatabaseError`] on storage failure.
+    fn pending_outbox(
+        &self,
+        limit: usize,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<OutboxEntry>, EventStoreError>> + Send + '_>>;
+
+    /// Mark entries as published so they are not relayed again.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventStoreError::DatabaseError`] on storage failure.
+    fn mark_published(
+        &self,
+        sequences: Vec<u64>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventStoreError>> + Send + '_>>;
+}
+
+/// Build an effect that appends events and enqueues them for publishing.
+///
+/// This replaces a pair of `AppendEvents` + `PublishEvent` effects: the events
+/// reach the bus through an outbox relay even if the process crashes right
+/// after the append.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use composable_rust_core::outbox::append_and_publish;
+///
+/// smallvec![append_and_publish(
+///     Arc::clone(&env.outbox),
+///     StreamId::new(format!("order-{id}")),
+///     Some(state.version),
+///     vec![serialized_event],
+///     "order-events",
+///     |version| Some(OrderAction::EventsAppended { version }),
+///     |error| Some(OrderAction::AppendFailed { error: error.to_string() }),
+/// )]
+/// ```
+pub fn append_and_publish<A, S, F>(
+    outbox: Arc<dyn OutboxStore>,
+    stream_id: StreamId,
+    expected_version: Option<Version>,
+    events: Vec<SerializedEvent>,
+    topic: impl Into<String>,
+    on_success: S,
+    on_error: F,
+) -> Effect<A>
+where
+    A: Send + 'static,
+    S: FnOnce(Version) -> Option<A> + Send + 'static,
+    F: FnOnce(EventStoreError) -> Option<A> + Send + 'static,
+{
+    let topic = topic.into();
+    Effect::Future(Box::pin(async move {
+        match outbox
+            .append_with_outbox(stream_id, expected_version, events, topic)
+            .await
+        {
+            Ok(version) => on_success(version),
+            Err(error) => on_error(error),
+        }
+    }))
+}
@@ -64,7 +64,7 @@
 //! }
 //! ```
 
-use crate::{DateTime, Utc};
+use crate::environment::{self, Timestamp};
 use serde::{Deserialize, Serialize};
 use std::future::Future;
 use std::pin::Pin;
@@ -306,7 +306,8 @@ pub trait ProjectionStore: Send + Sync {
 ///
 /// # Checkpoint Strategy
 ///
-/// - Save checkpoint every N events (e.g., every 100)
+/// - Save checkpoint every event, every N events, or every T seconds
+///   (see [`CommitStrategy`])
 /// - Save on graceful shutdown
 /// - Use event offset or position for resumption
 ///
@@ -367,6 +368,55 @@ pub trait ProjectionCheckpoint: Send + Sync {
     ) -> Pin<Box<dyn Future<Output = Result<Option<EventPosition>>> + Send + '_>>;
 }
 
+/// When a projection runner saves its checkpoint.
+///
+/// Every save is a write to the checkpoint store, so saving less often
+/// trades throughput against how many events are reprocessed after a crash.
+/// Runners also save on a graceful stop, whatever the strategy.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_core::projection::CommitStrategy;
+/// use std::time::Duration;
+///
+/// let strategy = CommitStrategy::Interval(Duration::from_secs(5));
+/// assert!(!strategy.is_due(10, Duration::from_secs(1)));
+/// assert!(strategy.is_due(10, Duration::from_secs(5)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitStrategy {
+    /// Save after every processed event
+    EveryEvent,
+    /// Save after every `n` processed events (0 behaves like 1)
+    EveryN(u64),
+    /// Save on the first processed event at least this long after the last save
+    Interval(std::time::Duration),
+}
+
+impl CommitStrategy {
+    /// Whether a save is due, given the events processed and the time
+    /// elapsed since the last save.
+    #[must_use]
+    pub const fn is_due(self, events_since_save: u64, since_save: std::time::Duration) -> bool {
+        if events_since_save == 0 {
+            return false;
+        }
+        match self {
+            Self::EveryEvent => true,
+            Self::EveryN(n) => events_since_save >= n,
+            Self::Interval(interval) => since_save.as_nanos() >= interval.as_nanos(),
+        }
+    }
+}
+
+impl Default for CommitStrategy {
+    /// Every 100 events
+    fn default() -> Self {
+        Self::EveryN(100)
+    }
+}
+
 /// Position in the event stream (for checkpoint resumption).
 ///
 /// Represents where a projection has processed up to in the event stream.
@@ -375,12 +425,12 @@ pub trait ProjectionCheckpoint: Send + Sync {
 /// # Example
 ///
 /// ```
+/// use composable_rust_core::environment;
 /// use composable_rust_core::projection::EventPosition;
-/// use chrono::Utc;
 ///
 /// let position = EventPosition {
 ///     offset: 1000,
-///     timestamp: Utc::now(),
+///     timestamp: environment::now(),
 /// };
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -389,7 +439,7 @@ pub struct EventPosition {
     pub offset: u64,
 
     /// Timestamp when this position was reached
-    pub timestamp: DateTime<Utc>,
+    pub timestamp: Timestamp,
 }
 
 impl EventPosition {
@@ -398,13 +448,13 @@ impl EventPosition {
     /// # Example
     ///
     /// ```
+    /// use composable_rust_core::environment;
     /// use composable_rust_core::projection::EventPosition;
-    /// use chrono::Utc;
     ///
-    /// let position = EventPosition::new(1000, Utc::now());
+    /// let position = EventPosition::new(1000, environment::now());
     /// ```
     #[must_use]
-    pub const fn new(offset: u64, timestamp: DateTime<Utc>) -> Self {
+    pub const fn new(offset: u64, timestamp: Timestamp) -> Self {
         Self { offset, timestamp }
     }
 
@@ -422,7 +472,7 @@ impl EventPosition {
     pub fn beginning() -> Self {
         Self {
             offset: 0,
-            timestamp: Utc::now(),
+            timestamp: environment::now(),
         }
     }
 }
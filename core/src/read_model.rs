@@ -0,0 +1,433 @@
+//! Read model storage for the query side of CQRS.
+//!
+//! Projections write denormalized read models; request handlers and
+//! read-path reducers load them. [`ReadModelStore`] is the environment
+//! dependency for both, so loading a read model can be described as an
+//! [`Effect::Query`](crate::effect::Effect::Query) and executed by the Store
+//! like any other effect, instead of handlers calling the database directly.
+//!
+//! Read models are JSON documents stored under a key in a named collection.
+//! Besides lookup by key, a [`ReadModelQuery`] selects documents by simple
+//! field filters with ordering and pagination: the common subset of what a
+//! SQL `WHERE ... ORDER BY ... LIMIT` and a document store can both express.
+//!
+//! # Example
+//!
+//! ```
+//! use composable_rust_core::read_model::{Filter, ReadModelQuery};
+//! use serde_json::json;
+//!
+//! let query = ReadModelQuery::new("orders")
+//!     .filter(Filter::eq("customer_id", "cust-1"))
+//!     .filter(Filter::gte("total", 100))
+//!     .order_by_desc("placed_at")
+//!     .limit(20);
+//!
+//! assert!(query.matches(&json!({"customer_id": "cust-1", "total": 250})));
+//! assert!(!query.matches(&json!({"customer_id": "cust-2", "total": 250})));
+//! ```
+
+use crate::retry::RetryableError;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::future::Future;
+use std::pin::Pin;
+use thiserror::Error;
+
+/// Errors from read model storage.
+#[derive(Error, Debug, Clone)]
+pub enum ReadModelError {
+    /// The underlying storage failed (connection, I/O).
+    #[error("Read model storage error: {0}")]
+    Storage(String),
+
+    /// A document could not be encoded or decoded.
+    #[error("Read model serialization error: {0}")]
+    Serialization(String),
+
+    /// The query cannot be executed by this store.
+    #[error("Invalid read model query: {0}")]
+    InvalidQuery(String),
+}
+
+impl RetryableError for ReadModelError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Storage(_) => true,
+            Self::Serialization(_) | Self::InvalidQuery(_) => false,
+        }
+    }
+}
+
+/// A read model document and the key it is stored under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadModelRow {
+    /// Key of the document within its collection
+    pub key: String,
+    /// The document
+    pub value: Value,
+}
+
+impl ReadModelRow {
+    /// Deserialize the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReadModelError::Serialization`] if the document is not a valid `T`.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, ReadModelError> {
+        serde_json::from_value(self.value.clone())
+            .map_err(|e| ReadModelError::Serialization(e.to_string()))
+    }
+}
+
+/// A condition on one field of a document.
+///
+/// Fields are addressed by dot-separated paths (`"shipping.country"`).
+/// Ordering comparisons apply to numbers and to strings; a document whose
+/// field is missing or of another type does not match.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// Field equals the value
+    Eq(String, Value),
+    /// Field is missing or differs from the value
+    Ne(String, Value),
+    /// Field is greater than the value
+    Gt(String, Value),
+    /// Field is greater than or equal to the value
+    Gte(String, Value),
+    /// Field is less than the value
+    Lt(String, Value),
+    /// Field is less than or equal to the value
+    Lte(String, Value),
+    /// Field equals one of the values
+    In(String, Vec<Value>),
+}
+
+impl Filter {
+    /// `field = value`
+    #[must_use]
+    pub fn eq(field: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::Eq(field.into(), value.into())
+    }
+
+    /// `field <> value`
+    #[must_use]
+    pub fn ne(field: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::Ne(field.into(), value.into())
+    }
+
+    /// `field > value`
+    #[must_use]
+    pub fn gt(field: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::Gt(field.into(), value.into())
+    }
+
+    /// `field >= value`
+    #[must_use]
+    pub fn gte(field: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::Gte(field.into(), value.into())
+    }
+
+    /// `field < value`
+    #[must_use]
+    pub fn lt(field: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::Lt(field.into(), value.into())
+    }
+
+    /// `field <= value`
+    #[must_use]
+    pub fn lte(field: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::Lte(field.into(), value.into())
+    }
+
+    /// `field IN (values)`
+    #[must_use]
+    pub fn is_in<V: Into<Value>>(
+        field: impl Into<String>,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        Self::In(field.into(), values.into_iter().map(Into::into).collect())
+    }
+
+    /// Path of the field this filter applies to
+    #[must_use]
+    pub fn field(&self) -> &str {
+        match self {
+            Self::Eq(field, _)
+            | Self::Ne(field, _)
+            | Self::Gt(field, _)
+            | Self::Gte(field, _)
+            | Self::Lt(field, _)
+            | Self::Lte(field, _)
+            | Self::In(field, _) => field,
+        }
+    }
+
+    /// Whether `document` satisfies this filter
+    #[must_use]
+    pub fn matches(&self, document: &Value) -> bool {
+        let field = lookup(document, self.field());
+        match self {
+            Self::Eq(_, value) => field.is_some_and(|field| equal(field, value)),
+            Self::Ne(_, value) => !field.is_some_and(|field| equal(field, value)),
+            Self::Gt(_, value) => compare(field, value) == Some(Ordering::Greater),
+            Self::Gte(_, value) => matches!(
+                compare(field, value),
+                Some(Ordering::Greater | Ordering::Equal)
+            ),
+            Self::Lt(_, value) => compare(field, value) == Some(Ordering::Less),
+            Self::Lte(_, value) => matches!(
+                compare(field, value),
+                Some(Ordering::Less | Ordering::Equal)
+            ),
+            Self::In(_, values) => {
+                field.is_some_and(|field| values.iter().any(|value| equal(field, value)))
+            },
+        }
+    }
+}
+
+/// Sort order of a [`ReadModelQuery`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderBy {
+    /// Path of the field to sort by
+    pub field: String,
+    /// Sort largest first
+    pub descending: bool,
+}
+
+/// Selects documents from one collection; see the [module documentation](self)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadModelQuery {
+    /// Collection to read from
+    pub collection: String,
+    /// Conditions that must all hold
+    pub filters: Vec<Filter>,
+    /// Sort order (store-defined order when `None`)
+    pub order_by: Option<OrderBy>,
+    /// Maximum number of documents to return
+    pub limit: Option<usize>,
+    /// Number of matching documents to skip
+    pub offset: usize,
+}
+
+impl ReadModelQuery {
+    /// Select every document in `collection`
+    #[must_use]
+    pub fn new(collection: impl Into<String>) -> Self {
+        Self {
+            collection: collection.into(),
+            filters: Vec::new(),
+            order_by: None,
+            limit: None,
+            offset: 0,
+        }
+    }
+
+    /// Add a condition (conditions are combined with AND)
+    #[must_use]
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Sort by `field`, smallest first
+    #[must_use]
+    pub fn order_by(mut self, field: impl Into<String>) -> Self {
+        self.order_by = Some(OrderBy {
+            field: field.into(),
+            descending: false,
+        });
+        self
+    }
+
+    /// Sort by `field`, largest first
+    #[must_use]
+    pub fn order_by_desc(mut self, field: impl Into<String>) -> Self {
+        self.order_by = Some(OrderBy {
+            field: field.into(),
+            descending: true,
+        });
+        self
+    }
+
+    /// Return at most `limit` documents
+    #[must_use]
+    pub const fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip the first `offset` matching documents
+    #[must_use]
+    pub const fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Whether `document` satisfies every filter
+    #[must_use]
+    pub fn matches(&self, document: &Value) -> bool {
+        self.filters.iter().all(|filter| filter.matches(document))
+    }
+
+    /// Filter, sort and paginate `rows` in memory
+    ///
+    /// For stores that cannot push the query down to their backend. Rows
+    /// whose sort field is missing sort last; ties keep their input order.
+    #[must_use]
+    pub fn apply(&self, rows: impl IntoIterator<Item = ReadModelRow>) -> Vec<ReadModelRow> {
+        let mut rows: Vec<ReadModelRow> = rows
+            .into_iter()
+            .filter(|row| self.matches(&row.value))
+            .collect();
+        if let Some(order) = &self.order_by {
+            rows.sort_by(|a, b| {
+                let (a, b) = (
+                    lookup(&a.value, &order.field),
+                    lookup(&b.value, &order.field),
+                );
+                let ordering = match (a, b) {
+                    (Some(a), Some(b)) => compare(Some(a), b).unwrap_or(Ordering::Equal),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                };
+                match (order.descending, a.is_some() && b.is_some()) {
+                    (true, true) => ordering.reverse(),
+                    _ => ordering,
+                }
+            });
+        }
+        rows.into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+/// Value at a dot-separated path
+fn lookup<'a>(document: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(document, |value, segment| value.get(segment))
+}
+
+/// Equality that treats `1` and `1.0` as equal
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a.total_cmp(&b) == Ordering::Equal,
+        _ => a == b,
+    }
+}
+
+/// Order two numbers or two strings; other combinations are unordered
+fn compare(field: Option<&Value>, value: &Value) -> Option<Ordering> {
+    match (field?, value) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Storage for read models, injected through the environment.
+///
+/// # Dyn Compatibility
+///
+/// Like [`EventStore`](crate::event_store::EventStore), this trait returns
+/// boxed futures so it can be shared as `Arc<dyn ReadModelStore>` and
+/// captured by [`Effect::Query`](crate::effect::Effect::Query).
+pub trait ReadModelStore: Send + Sync {
+    /// Load the document stored under `key`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReadModelError::Storage`] if the backend fails.
+    fn get(
+        &self,
+        collection: &str,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<Value>, ReadModelError>> + Send + '_>>;
+
+    /// Store `value` under `key`, replacing any existing document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReadModelError::Storage`] if the backend fails.
+    fn put(
+        &self,
+        collection: &str,
+        key: &str,
+        value: Value,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ReadModelError>> + Send + '_>>;
+
+    /// Remove the document stored under `key`, returning whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReadModelError::Storage`] if the backend fails.
+    fn delete(
+        &self,
+        collection: &str,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, ReadModelError>> + Send + '_>>;
+
+    /// Load the documents selected by `query`.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadModelError::InvalidQuery`] if the store cannot express the query
+    /// - [`ReadModelError::Storage`] if the backend fails
+    fn query(
+        &self,
+        query: &ReadModelQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ReadModelRow>, ReadModelError>> + Send + '_>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn row(key: &str, value: Value) -> ReadModelRow {
+        ReadModelRow {
+            key: key.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_filters_compare_nested_fields() {
+        let order = json!({"total": 120, "status": "open", "shipping": {"country": "FR"}});
+
+        assert!(Filter::eq("shipping.country", "FR").matches(&order));
+        assert!(Filter::eq("total", 120.0).matches(&order));
+        assert!(Filter::gt("total", 100).matches(&order));
+        assert!(!Filter::lt("total", 100).matches(&order));
+        assert!(Filter::is_in("status", ["open", "paid"]).matches(&order));
+        assert!(Filter::ne("missing", 1).matches(&order));
+        // Mismatched types are unordered, so never match
+        assert!(!Filter::gte("status", 1).matches(&order));
+    }
+
+    #[test]
+    fn test_apply_sorts_and_paginates() {
+        let rows = vec![
+            row("a", json!({"n": 2})),
+            row("b", json!({"n": 5})),
+            row("c", json!({})),
+            row("d", json!({"n": 9})),
+        ];
+
+        let keys = |rows: Vec<ReadModelRow>| rows.into_iter().map(|r| r.key).collect::<Vec<_>>();
+        let query = ReadModelQuery::new("numbers").order_by_desc("n");
+        assert_eq!(keys(query.apply(rows.clone())), ["d", "b", "a", "c"]);
+
+        let query = ReadModelQuery::new("numbers")
+            .filter(Filter::gte("n", 2))
+            .order_by("n")
+            .offset(1)
+            .limit(1);
+        assert_eq!(keys(query.apply(rows)), ["b"]);
+    }
+}
@@ -0,0 +1,72 @@
+//! Classifying errors as transient or permanent.
+//!
+//! The Store retries failed event store and event bus operations with
+//! exponential backoff. Retrying only helps for transient failures (a dropped
+//! connection, a broker that is briefly unavailable). Permanent failures are
+//! surfaced immediately instead:
+//!
+//! - **Concurrency conflicts**: the reducer must reload the stream and decide
+//!   again; replaying the same append can never succeed
+//! - **Serialization errors**: the payload will not change between attempts
+//! - **Missing streams or invalid topics**: configuration or business errors
+//!
+//! Custom event store or bus implementations map their own failures onto the
+//! shared error enums, so the classification below applies to them as well.
+
+use crate::event_bus::EventBusError;
+use crate::event_store::EventStoreError;
+
+/// An error that knows whether retrying the failed operation can help
+pub trait RetryableError {
+    /// Whether the operation may succeed if attempted again
+    fn is_retryable(&self) -> bool;
+}
+
+impl RetryableError for EventStoreError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::DatabaseError(_) | Self::IoError(_) => true,
+            Self::ConcurrencyConflict { .. }
+            | Self::StreamNotFound(_)
+            | Self::SerializationError(_) => false,
+        }
+    }
+}
+
+impl RetryableError for EventBusError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::ConnectionFailed(_)
+            | Self::PublishFailed { .. }
+            | Self::SubscriptionFailed { .. }
+            | Self::ConsumerGroupError(_)
+            | Self::TransportError(_)
+            | Self::Other(_) => true,
+            Self::DeserializationFailed(_) | Self::InvalidTopic(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::{StreamId, Version};
+
+    #[test]
+    fn concurrency_conflict_is_not_retryable() {
+        let conflict = EventStoreError::ConcurrencyConflict {
+            stream_id: StreamId::new("order-1"),
+            expected: Version::new(1),
+            actual: Version::new(2),
+        };
+
+        assert!(!conflict.is_retryable());
+        assert!(EventStoreError::DatabaseError("connection reset".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn bus_errors_classified() {
+        assert!(EventBusError::TransportError("timeout".to_string()).is_retryable());
+        assert!(!EventBusError::InvalidTopic("orders!".to_string()).is_retryable());
+    }
+}
@@ -0,0 +1,284 @@
+//! Event bus topics typed by the events they carry
+//!
+//! [`EventBus`] addresses topics by string, so a misspelled topic or an event
+//! published to the wrong topic only shows up at runtime, as messages that
+//! nobody receives or cannot decode. A [`Topic<T>`] fixes the name and the
+//! [`DomainEvent`] type together, once:
+//!
+//! ```rust,ignore
+//! use composable_rust_core::topic::{Topic, TypedEventBus};
+//!
+//! pub const ORDER_EVENTS: Topic<OrderEvent> = Topic::new("order-events");
+//!
+//! // Only `OrderEvent`s can be published here, and subscribers get them decoded
+//! event_bus.publish_to(ORDER_EVENTS, &OrderEvent::Placed { .. }, None).await?;
+//! let mut orders = event_bus.subscribe_to(ORDER_EVENTS).await?;
+//! ```
+//!
+//! Typed topics work wherever a topic string is accepted: the effect macros
+//! and [`EventBusEffect::publish`](crate::effect_builders::EventBusEffect::publish)
+//! take them as is, and [`Topic::encode`] produces the event to publish. The
+//! string-based [`EventBus`] methods stay available for topics whose event
+//! types are only known at runtime.
+
+use crate::event::{DomainEvent, EventError, EventMetadata, SerializedEvent};
+use crate::event_bus::{EventBus, EventBusError};
+use futures::{Stream, StreamExt};
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+/// An event bus topic carrying events of type `T`; see the [module documentation](self)
+pub struct Topic<T> {
+    name: &'static str,
+    event: PhantomData<fn() -> T>,
+}
+
+impl<T: DomainEvent> Topic<T> {
+    /// Declare the topic `name` for events of type `T`
+    #[must_use]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            event: PhantomData,
+        }
+    }
+
+    /// Serialize `event` for publishing to this topic
+    ///
+    /// # Errors
+    ///
+    /// Returns `EventError::SerializationError` if the event's codec fails.
+    pub fn encode(
+        &self,
+        event: &T,
+        metadata: Option<EventMetadata>,
+    ) -> Result<SerializedEvent, EventError> {
+        SerializedEvent::encode(event, metadata)
+    }
+}
+
+impl<T> Topic<T> {
+    /// The topic name on the bus
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+// Manual impls: `T` is only a marker and needs none of these traits
+
+impl<T> Clone for Topic<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Topic<T> {}
+
+impl<T> PartialEq for Topic<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl<T> Eq for Topic<T> {}
+
+impl<T> std::hash::Hash for Topic<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Topic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Topic").field(&self.name).finish()
+    }
+}
+
+impl<T> fmt::Display for Topic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl<T> AsRef<str> for Topic<T> {
+    fn as_ref(&self) -> &str {
+        self.name
+    }
+}
+
+impl<T> From<Topic<T>> for String {
+    fn from(topic: Topic<T>) -> Self {
+        topic.name.to_string()
+    }
+}
+
+/// Stream of decoded events from a [`Topic`]
+pub type TypedEventStream<T> = Pin<Box<dyn Stream<Item = Result<T, EventBusError>> + Send>>;
+
+/// Publishing and subscribing through typed topics
+///
+/// Implemented for every [`EventBus`], including `dyn EventBus`.
+pub trait TypedEventBus: EventBus {
+    /// Encode `event` and publish it to `topic`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventBusError::PublishFailed`] if the event cannot be
+    /// encoded, or the bus's error if publishing fails.
+    fn publish_to<'a, T: DomainEvent>(
+        &'a self,
+        topic: Topic<T>,
+        event: &T,
+        metadata: Option<EventMetadata>,
+    ) -> impl Future<Output = Result<(), EventBusError>> + Send + 'a {
+        let encoded = topic
+            .encode(event, metadata)
+            .map_err(|error| EventBusError::PublishFailed {
+                topic: topic.to_string(),
+                reason: error.to_string(),
+            });
+        async move { self.publish(topic.name(), &encoded?).await }
+    }
+
+    /// Subscribe to `topic`, decoding each event as `T`
+    ///
+    /// Events of any other type yield
+    /// [`EventBusError::DeserializationFailed`] without ending the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns the bus's error if subscribing fails.
+    fn subscribe_to<T: DomainEvent>(
+        &self,
+        topic: Topic<T>,
+    ) -> impl Future<Output = Result<TypedEventStream<T>, EventBusError>> + Send + '_ {
+        async move {
+            let events = self.subscribe(&[topic.name()]).await?;
+            let decoded = events.map(|event| {
+                event?
+                    .decode::<T>()
+                    .map_err(|error| EventBusError::DeserializationFailed(error.to_string()))
+            });
+            Ok(Box::pin(decoded) as TypedEventStream<T>)
+        }
+    }
+}
+
+impl<B: EventBus + ?Sized> TypedEventBus for B {}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+mod tests {
+    use super::*;
+    use crate::codec::JsonCodec;
+    use crate::effect::{Effect, EventBusOperation};
+    use crate::effect_builders::EventBusEffect;
+    use crate::event_bus::EventStream;
+    use serde::{Deserialize, Serialize};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct OrderPlaced {
+        order_id: String,
+    }
+
+    impl DomainEvent for OrderPlaced {
+        const NAME: &'static str = "OrderPlaced";
+        type Codec = JsonCodec;
+    }
+
+    const ORDER_EVENTS: Topic<OrderPlaced> = Topic::new("order-events");
+
+    /// Records published events and replays them to subscribers
+    #[derive(Default)]
+    struct RecordingBus {
+        published: Mutex<Vec<(String, SerializedEvent)>>,
+    }
+
+    impl EventBus for RecordingBus {
+        fn publish(
+            &self,
+            topic: &str,
+            event: &SerializedEvent,
+        ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+            self.published
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), event.clone()));
+            Box::pin(async { Ok(()) })
+        }
+
+        fn subscribe(
+            &self,
+            topics: &[&str],
+        ) -> Pin<Box<dyn Future<Output = Result<EventStream, EventBusError>> + Send + '_>> {
+            let events: Vec<_> = self
+                .published
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(topic, _)| topics.contains(&topic.as_str()))
+                .map(|(_, event)| Ok(event.clone()))
+                .collect();
+            Box::pin(async move { Ok(Box::pin(futures::stream::iter(events)) as EventStream) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_typed_topic_round_trip() {
+        let bus: Arc<dyn EventBus> = Arc::new(RecordingBus::default());
+        let placed = OrderPlaced {
+            order_id: "order-1".to_string(),
+        };
+
+        bus.publish_to(ORDER_EVENTS, &placed, None).await.unwrap();
+        // Raw strings still work, and a foreign event type is reported, not dropped
+        bus.publish(
+            "order-events",
+            &SerializedEvent::new("Other.v1".to_string(), vec![], None),
+        )
+        .await
+        .unwrap();
+
+        let received: Vec<_> = bus
+            .subscribe_to(ORDER_EVENTS)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].as_ref().unwrap(), &placed);
+        assert!(matches!(
+            received[1],
+            Err(EventBusError::DeserializationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_typed_topic_in_effects() {
+        let bus: Arc<dyn EventBus> = Arc::new(RecordingBus::default());
+        let event = ORDER_EVENTS
+            .encode(
+                &OrderPlaced {
+                    order_id: "order-1".to_string(),
+                },
+                None,
+            )
+            .unwrap();
+
+        let from_builder: Effect<()> =
+            EventBusEffect::publish(&bus, ORDER_EVENTS, event.clone()).into_effect();
+        let from_macro: Effect<()> = crate::publish!(bus, ORDER_EVENTS, event);
+
+        for effect in [from_builder, from_macro] {
+            assert!(matches!(
+                effect,
+                Effect::PublishEvent(EventBusOperation::Publish { ref topic, ref event, .. })
+                    if topic == "order-events" && event.is::<OrderPlaced>()
+            ));
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Deterministic end-to-end tests for the checkout saga.
+//!
+//! These tests double as documentation of how to test a saga without races:
+//!
+//! - [`SagaDriver`] runs the reducer with a FIFO feedback queue instead of spawning
+//!   effects, so every action is reduced in a known order and recorded in a log.
+//! - Fixtures intercept the replies produced by the saga's effects to inject
+//!   payment or inventory failures, or to make a downstream service hang.
+//! - Timeouts use Tokio's paused clock (`start_paused = true`), so a 30-second
+//!   deadline elapses instantly and deterministically.
+//!
+//! The runtime-level equivalents (cascading effect tracking, queued feedback in
+//! `TestStore`, virtual-time scheduling) are not available yet; when they land,
+//! the driver here can be replaced with them without changing the scenarios.
+
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)] // Test code can use unwrap/expect/panic
+
+use checkout_saga::{CheckoutAction, CheckoutSaga, CheckoutSagaEnvironment, CheckoutSagaState};
+use composable_rust_core::effect::Effect;
+use composable_rust_core::reducer::Reducer;
+use composable_rust_runtime::Store;
+use composable_rust_testing::mocks::{FixedClock, InMemoryEventBus};
+use composable_rust_testing::test_clock;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+type Saga = CheckoutSaga<FixedClock, InMemoryEventBus>;
+type Env = CheckoutSagaEnvironment<FixedClock, InMemoryEventBus>;
+
+/// What a fixture does with a reply produced by an effect.
+enum Reply {
+    /// Feed this action back into the saga
+    Deliver(CheckoutAction),
+    /// The downstream service never answers
+    Hang,
+}
+
+/// Runs the saga reducer with a deterministic FIFO feedback queue.
+struct SagaDriver {
+    reducer: Saga,
+    env: Env,
+    state: CheckoutSagaState,
+    queue: VecDeque<CheckoutAction>,
+    log: Vec<CheckoutAction>,
+    fixture: Box<dyn FnMut(CheckoutAction) -> Reply + Send>,
+}
+
+impl SagaDriver {
+    fn new() -> Self {
+        Self {
+            reducer: CheckoutSaga::default(),
+            env: environment(),
+            state: CheckoutSagaState::default(),
+            queue: VecDeque::new(),
+            log: Vec::new(),
+            fixture: Box::new(Reply::Deliver),
+        }
+    }
+
+    /// Install a fixture that rewrites or swallows effect replies.
+    fn with_fixture(
+        mut self,
+        fixture: impl FnMut(CheckoutAction) -> Reply + Send + 'static,
+    ) -> Self {
+        self.fixture = Box::new(fixture);
+        self
+    }
+
+    /// Send an action and process feedback until the queue is empty.
+    async fn send(&mut self, action: CheckoutAction) {
+        self.queue.push_back(action);
+        while let Some(action) = self.queue.pop_front() {
+            self.log.push(action.clone());
+            let effects = self.reducer.reduce(&mut self.state, action, &self.env);
+            for effect in effects {
+                self.run_effect(effect).await;
+            }
+        }
+    }
+
+    async fn run_effect(&mut self, effect: Effect<CheckoutAction>) {
+        match effect {
+            Effect::None => {},
+            Effect::Future(fut) => {
+                if let Some(reply) = fut.await {
+                    match (self.fixture)(reply) {
+                        Reply::Deliver(action) => self.queue.push_back(action),
+                        Reply::Hang => std::future::pending::<()>().await,
+                    }
+                }
+            },
+            Effect::Parallel(effects) | Effect::Sequential(effects) => {
+                for effect in effects {
+                    Box::pin(self.run_effect(effect)).await;
+                }
+            },
+            other => panic!("checkout saga produced an unexpected effect: {other:?}"),
+        }
+    }
+
+    /// Names of the actions reduced so far, in order.
+    fn trace(&self) -> Vec<&'static str> {
+        self.log.iter().map(action_name).collect()
+    }
+}
+
+fn environment() -> Env {
+    CheckoutSagaEnvironment {
+        clock: test_clock(),
+        event_bus: Arc::new(InMemoryEventBus::new()),
+    }
+}
+
+fn checkout() -> CheckoutAction {
+    CheckoutAction::InitiateCheckout {
+        customer_id: "customer-1".to_string(),
+        order_total_cents: 10_000,
+        items: vec!["item-1".to_string(), "item-2".to_string()],
+    }
+}
+
+const fn action_name(action: &CheckoutAction) -> &'static str {
+    match action {
+        CheckoutAction::InitiateCheckout { .. } => "InitiateCheckout",
+        CheckoutAction::OrderPlaced { .. } => "OrderPlaced",
+        CheckoutAction::OrderCancelled { .. } => "OrderCancelled",
+        CheckoutAction::PaymentCompleted { .. } => "PaymentCompleted",
+        CheckoutAction::PaymentFailed { .. } => "PaymentFailed",
+        CheckoutAction::PaymentRefunded { .. } => "PaymentRefunded",
+        CheckoutAction::InventoryReserved { .. } => "InventoryReserved",
+        CheckoutAction::InsufficientInventory { .. } => "InsufficientInventory",
+        CheckoutAction::InventoryReleased { .. } => "InventoryReleased",
+        CheckoutAction::CheckoutCompleted { .. } => "CheckoutCompleted",
+        CheckoutAction::CheckoutFailed { .. } => "CheckoutFailed",
+    }
+}
+
+#[tokio::test]
+async fn test_happy_path_completes_checkout() {
+    let mut driver = SagaDriver::new();
+
+    driver.send(checkout()).await;
+
+    assert_eq!(
+        driver.trace(),
+        vec![
+            "InitiateCheckout",
+            "OrderPlaced",
+            "PaymentCompleted",
+            "InventoryReserved",
+            "CheckoutCompleted",
+        ]
+    );
+    assert!(matches!(driver.state, CheckoutSagaState::Completed { .. }));
+}
+
+#[tokio::test]
+async fn test_payment_failure_cancels_order() {
+    let mut driver = SagaDriver::new().with_fixture(|reply| match reply {
+        CheckoutAction::PaymentCompleted { payment_id } => {
+            Reply::Deliver(CheckoutAction::PaymentFailed {
+                payment_id,
+                reason: "Card declined".to_string(),
+            })
+        },
+        other => Reply::Deliver(other),
+    });
+
+    driver.send(checkout()).await;
+
+    assert_eq!(
+        driver.trace(),
+        vec![
+            "InitiateCheckout",
+            "OrderPlaced",
+            "PaymentFailed",
+            "OrderCancelled",
+            "CheckoutFailed",
+        ]
+    );
+    assert_eq!(
+        driver.state,
+        CheckoutSagaState::Failed {
+            reason: "Card declined".to_string()
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_inventory_failure_refunds_payment_and_cancels_order() {
+    let mut driver = SagaDriver::new().with_fixture(|reply| match reply {
+        CheckoutAction::InventoryReserved { reservation_id } => {
+            Reply::Deliver(CheckoutAction::InsufficientInventory { reservation_id })
+        },
+        other => Reply::Deliver(other),
+    });
+
+    driver.send(checkout()).await;
+
+    // Two compensations, in reverse order of the steps they undo
+    assert_eq!(
+        driver.trace(),
+        vec![
+            "InitiateCheckout",
+            "OrderPlaced",
+            "PaymentCompleted",
+            "InsufficientInventory",
+            "PaymentRefunded",
+            "OrderCancelled",
+            "CheckoutFailed",
+        ]
+    );
+    assert_eq!(
+        driver.state,
+        CheckoutSagaState::Failed {
+            reason: "Insufficient inventory".to_string()
+        }
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_hanging_payment_service_times_out() {
+    let mut driver = SagaDriver::new().with_fixture(|reply| match reply {
+        CheckoutAction::PaymentCompleted { .. } => Reply::Hang,
+        other => Reply::Deliver(other),
+    });
+
+    let result = tokio::time::timeout(Duration::from_secs(30), driver.send(checkout())).await;
+
+    // The deadline elapses in virtual time; the saga is left waiting on payment
+    assert!(result.is_err());
+    assert!(matches!(
+        driver.state,
+        CheckoutSagaState::ProcessingPayment { .. }
+    ));
+    assert_eq!(driver.trace(), vec!["InitiateCheckout", "OrderPlaced"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_store_round_trip_reaches_terminal_action() {
+    let store = Store::with_broadcast_capacity(
+        CheckoutSagaState::default(),
+        Saga::default(),
+        environment(),
+        64,
+    );
+
+    let terminal = store
+        .send_and_wait_for(
+            checkout(),
+            |a| {
+                matches!(
+                    a,
+                    CheckoutAction::CheckoutCompleted { .. }
+                        | CheckoutAction::CheckoutFailed { .. }
+                )
+            },
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(terminal, CheckoutAction::CheckoutCompleted { .. }));
+    assert!(
+        store
+            .state(|s| matches!(s, CheckoutSagaState::Completed { .. }))
+            .await
+    );
+}
@@ -32,7 +32,7 @@
 //! # }
 //! ```
 
-use composable_rust_core::{effect::Effect, environment::Clock, reducer::Reducer, smallvec, SmallVec};
+use composable_rust_core::prelude::*;
 
 /// Counter state
 ///
@@ -2,8 +2,7 @@
 //!
 //! Demonstrates the Composable Rust architecture with a simple counter.
 
-use composable_rust_runtime::Store;
-use composable_rust_testing::test_clock;
+use composable_rust_testing::prelude::*;
 use counter::{CounterAction, CounterEnvironment, CounterReducer, CounterState};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -0,0 +1,234 @@
+//! Crash injection for the exactly-once guarantees.
+//!
+//! A [`Chaos`] handle is shared by wrappers around the framework components
+//! the pipeline is built from: [`ChaosBus`] in front of the event bus the
+//! [`OutboxRelay`](composable_rust_runtime::outbox::OutboxRelay) publishes to,
+//! [`ChaosStore`] in front of the projection's read model, and
+//! [`ChaosCheckpoint`] in front of the consumer's checkpoint store. Tests arm
+//! a [`CrashPoint`]; the next wrapper to reach it fails instead of continuing,
+//! which is how a process kill looks from the point of view of durable state.
+
+use crate::{Error, Result};
+use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::event_bus::{EventBus, EventBusError, EventStream};
+use composable_rust_core::projection::{
+    self, EventPosition, ProjectionCheckpoint, ProjectionError, ProjectionStore,
+};
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Places where a simulated crash can be injected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CrashPoint {
+    /// The event and outbox row are committed, but nothing has been published.
+    AfterAppend,
+    /// The message has been published, but the outbox row is not yet marked.
+    AfterPublish,
+    /// The consumer received the event but died before writing the read model.
+    BeforeReadModelWrite,
+    /// The read model is written, but the consumer checkpoint is not saved.
+    BeforeCheckpoint,
+}
+
+/// Shared switchboard of armed crash points.
+///
+/// Each armed point fires exactly once and is then disarmed, mirroring a
+/// process that crashes and is restarted.
+#[derive(Clone, Debug, Default)]
+pub struct Chaos {
+    armed: Arc<Mutex<HashSet<CrashPoint>>>,
+}
+
+impl Chaos {
+    /// Create a switchboard with no crash points armed.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Arm a crash point. The next component that reaches it will crash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Poisoned`] if the internal lock is poisoned.
+    pub fn arm(&self, point: CrashPoint) -> Result<()> {
+        self.armed
+            .lock()
+            .map_err(|_| Error::Poisoned)?
+            .insert(point);
+        Ok(())
+    }
+
+    /// Check whether a crash point is still armed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Poisoned`] if the internal lock is poisoned.
+    pub fn is_armed(&self, point: CrashPoint) -> Result<bool> {
+        Ok(self
+            .armed
+            .lock()
+            .map_err(|_| Error::Poisoned)?
+            .contains(&point))
+    }
+
+    /// Crash (return [`Error::Crashed`]) if `point` is armed, disarming it.
+    pub(crate) fn trip(&self, point: CrashPoint) -> Result<()> {
+        if self
+            .armed
+            .lock()
+            .map_err(|_| Error::Poisoned)?
+            .remove(&point)
+        {
+            tracing::warn!(?point, "Simulated crash");
+            return Err(Error::Crashed(point));
+        }
+        Ok(())
+    }
+}
+
+/// Event bus that crashes the publisher around a publish.
+///
+/// [`CrashPoint::AfterAppend`] fails the publish before it reaches the inner
+/// bus. [`CrashPoint::AfterPublish`] lets it through and then reports a
+/// failure, so the relay never learns that the message went out.
+#[derive(Clone)]
+pub struct ChaosBus {
+    inner: Arc<dyn EventBus>,
+    chaos: Chaos,
+}
+
+impl ChaosBus {
+    /// Wrap `inner`, crashing at the points armed on `chaos`.
+    #[must_use]
+    pub fn new(inner: Arc<dyn EventBus>, chaos: Chaos) -> Self {
+        Self { inner, chaos }
+    }
+}
+
+impl std::fmt::Debug for ChaosBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChaosBus")
+            .field("chaos", &self.chaos)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventBus for ChaosBus {
+    fn publish(
+        &self,
+        topic: &str,
+        event: &SerializedEvent,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<(), EventBusError>> + Send + '_>> {
+        let crashed = |error: Error| EventBusError::PublishFailed {
+            topic: topic.to_string(),
+            reason: error.to_string(),
+        };
+
+        if let Err(error) = self.chaos.trip(CrashPoint::AfterAppend) {
+            return Box::pin(std::future::ready(Err(crashed(error))));
+        }
+
+        let publish = self.inner.publish(topic, event);
+        let after_publish = crashed(Error::Crashed(CrashPoint::AfterPublish));
+        Box::pin(async move {
+            publish.await?;
+            match self.chaos.trip(CrashPoint::AfterPublish) {
+                Ok(()) => Ok(()),
+                Err(_) => Err(after_publish),
+            }
+        })
+    }
+
+    fn subscribe(
+        &self,
+        topics: &[&str],
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<EventStream, EventBusError>> + Send + '_>>
+    {
+        self.inner.subscribe(topics)
+    }
+}
+
+/// Projection store that crashes the consumer before a read-model write.
+///
+/// Fires on [`CrashPoint::BeforeReadModelWrite`]; nothing is written.
+#[derive(Clone, Debug)]
+pub struct ChaosStore<S> {
+    inner: S,
+    chaos: Chaos,
+}
+
+impl<S> ChaosStore<S> {
+    /// Wrap `inner`, crashing at the points armed on `chaos`.
+    #[must_use]
+    pub const fn new(inner: S, chaos: Chaos) -> Self {
+        Self { inner, chaos }
+    }
+}
+
+impl<S: ProjectionStore> ProjectionStore for ChaosStore<S> {
+    async fn save(&self, key: &str, data: &[u8]) -> projection::Result<()> {
+        self.chaos
+            .trip(CrashPoint::BeforeReadModelWrite)
+            .map_err(|e| ProjectionError::Storage(e.to_string()))?;
+        self.inner.save(key, data).await
+    }
+
+    async fn get(&self, key: &str) -> projection::Result<Option<Vec<u8>>> {
+        self.inner.get(key).await
+    }
+
+    async fn delete(&self, key: &str) -> projection::Result<()> {
+        self.inner.delete(key).await
+    }
+}
+
+/// Checkpoint store that crashes the consumer before saving its position.
+///
+/// Fires on [`CrashPoint::BeforeCheckpoint`], after the read model has been
+/// written for the event being checkpointed.
+#[derive(Clone)]
+pub struct ChaosCheckpoint {
+    inner: Arc<dyn ProjectionCheckpoint>,
+    chaos: Chaos,
+}
+
+impl ChaosCheckpoint {
+    /// Wrap `inner`, crashing at the points armed on `chaos`.
+    #[must_use]
+    pub fn new(inner: Arc<dyn ProjectionCheckpoint>, chaos: Chaos) -> Self {
+        Self { inner, chaos }
+    }
+}
+
+impl std::fmt::Debug for ChaosCheckpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChaosCheckpoint")
+            .field("chaos", &self.chaos)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProjectionCheckpoint for ChaosCheckpoint {
+    fn save_position(
+        &self,
+        projection_name: &str,
+        position: EventPosition,
+    ) -> Pin<Box<dyn Future<Output = projection::Result<()>> + Send + '_>> {
+        if let Err(error) = self.chaos.trip(CrashPoint::BeforeCheckpoint) {
+            return Box::pin(std::future::ready(Err(ProjectionError::Checkpoint(
+                error.to_string(),
+            ))));
+        }
+        self.inner.save_position(projection_name, position)
+    }
+
+    fn load_position(
+        &self,
+        projection_name: &str,
+    ) -> Pin<Box<dyn Future<Output = projection::Result<Option<EventPosition>>> + Send + '_>> {
+        self.inner.load_position(projection_name)
+    }
+}
@@ -0,0 +1,212 @@
+//! Exactly-once projection updates with a transactional outbox.
+//!
+//! This example wires the framework's building blocks together so that a
+//! single business operation produces exactly one read-model update, even when
+//! processes crash at the worst possible moment:
+//!
+//! 1. **Transactional outbox** ([`outbox::OrderService`]): each domain event is
+//!    appended with [`OutboxStore::append_with_outbox`], so the event and its
+//!    outbox row are stored together or not at all.
+//! 2. **Outbox relay** ([`OutboxRelay`]): publishes pending outbox rows and
+//!    marks them only after the publish succeeded. A crash in between causes a
+//!    re-publish, so delivery is *at-least-once*.
+//! 3. **Idempotent projection** ([`projection::OrderSummaryProjection`]): a
+//!    [`Projection`] whose read-model rows record the stream version they were
+//!    built from. Duplicates are skipped and gaps are filled by catching up
+//!    from the event store. At-least-once delivery plus version-keyed rows
+//!    yields *effectively exactly-once* updates; the
+//!    [`ProjectionCheckpoint`] is only the position to resume from.
+//!
+//! The [`chaos`] module wraps the event bus, projection store and checkpoint
+//! store so the integration tests can kill each stage mid-flight.
+//!
+//! [`OutboxStore::append_with_outbox`]: composable_rust_core::outbox::OutboxStore::append_with_outbox
+//! [`OutboxRelay`]: composable_rust_runtime::outbox::OutboxRelay
+//! [`Projection`]: composable_rust_core::projection::Projection
+//! [`ProjectionCheckpoint`]: composable_rust_core::projection::ProjectionCheckpoint
+//!
+//! # Example
+//!
+//! ```
+//! use composable_rust_runtime::outbox::OutboxRelay;
+//! use composable_rust_testing::InMemoryProjectionStore;
+//! use composable_rust_testing::mocks::{InMemoryEventBus, InMemoryEventStore};
+//! use exactly_once_projection::{
+//!     outbox::OrderService, projection::OrderSummaryProjection, OrderEvent, ORDERS_TOPIC,
+//! };
+//! use composable_rust_core::event_bus::EventBus;
+//! use futures::StreamExt;
+//! use std::sync::Arc;
+//!
+//! # tokio_test::block_on(async {
+//! let events = Arc::new(InMemoryEventStore::new());
+//! let bus = Arc::new(InMemoryEventBus::new());
+//! let mut deliveries = bus.subscribe(&[ORDERS_TOPIC]).await?;
+//!
+//! let service = OrderService::new(events.clone());
+//! let relay = OutboxRelay::new(events.clone(), bus);
+//! let projection = OrderSummaryProjection::new(InMemoryProjectionStore::new(), events);
+//!
+//! service.place_order("order-1", "alice", 4_200).await?;
+//! relay.relay_once().await?;
+//!
+//! let delivered = deliveries.next().await.expect("published")?;
+//! projection.handle(&OrderEvent::decode(&delivered)?).await?;
+//!
+//! let summary = projection.summary("order-1").await?.expect("projected");
+//! assert_eq!(summary.updates, 1);
+//! # Ok::<(), exactly_once_projection::Error>(())
+//! # });
+//! ```
+
+pub mod chaos;
+pub mod outbox;
+pub mod projection;
+
+use chaos::CrashPoint;
+use composable_rust_core::event::{EventMetadata, SerializedEvent};
+use composable_rust_core::event_bus::EventBusError;
+use composable_rust_core::event_store::EventStoreError;
+use composable_rust_core::projection::ProjectionError;
+use composable_rust_runtime::outbox::OutboxRelayError;
+use serde::{Deserialize, Serialize};
+
+/// Topic the outbox relay publishes order events to.
+pub const ORDERS_TOPIC: &str = "order-events";
+
+/// Errors produced by the example components.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A crash was injected by [`chaos::Chaos`].
+    #[error("Simulated crash at {0:?}")]
+    Crashed(CrashPoint),
+
+    /// The order referenced by a command does not exist.
+    #[error("Unknown order: {0}")]
+    UnknownOrder(String),
+
+    /// An order event could not be encoded or decoded.
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+
+    /// The event store rejected an append or read.
+    #[error("Event store error: {0}")]
+    EventStore(#[from] EventStoreError),
+
+    /// The event bus rejected a publish or subscription.
+    #[error("Event bus error: {0}")]
+    EventBus(#[from] EventBusError),
+
+    /// A relay pass failed.
+    #[error("Outbox relay error: {0}")]
+    Relay(#[from] OutboxRelayError),
+
+    /// The projection failed to apply an event.
+    #[error("Projection error: {0}")]
+    Projection(#[from] ProjectionError),
+
+    /// The chaos switchboard mutex was poisoned by a panicking thread.
+    #[error("Chaos lock poisoned")]
+    Poisoned,
+}
+
+/// Result type used throughout the example.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// What happened to an order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderChange {
+    /// The order was placed.
+    Placed {
+        /// Customer who placed the order
+        customer_id: String,
+        /// Total order value in cents
+        total_cents: u64,
+    },
+    /// The order was shipped.
+    Shipped,
+}
+
+/// Domain event for an order, as stored and published.
+///
+/// Carries the version it was appended at in the order's stream. Consumers
+/// use it as the deduplication key: a read-model row built from version `n`
+/// has seen every event up to `n`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderEvent {
+    /// Order identifier
+    pub order_id: String,
+    /// Version of this event in the order's stream
+    pub version: u64,
+    /// The change recorded by this event
+    pub change: OrderChange,
+}
+
+impl OrderEvent {
+    /// Versioned event type name used on the wire.
+    #[must_use]
+    pub const fn event_type(&self) -> &'static str {
+        match self.change {
+            OrderChange::Placed { .. } => "OrderPlaced.v1",
+            OrderChange::Shipped => "OrderShipped.v1",
+        }
+    }
+
+    /// Encode the event as a [`SerializedEvent`] for the event store.
+    ///
+    /// The payload is bincode, which is what
+    /// [`ProjectionManager`](composable_rust_projections::ProjectionManager)
+    /// decodes when no event registry is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Serialization`] if bincode encoding fails.
+    pub fn encode(&self) -> Result<SerializedEvent> {
+        let data = bincode::serialize(self).map_err(|e| Error::Serialization(e.to_string()))?;
+        Ok(SerializedEvent::new(
+            self.event_type().to_string(),
+            data,
+            Some(EventMetadata::with_correlation_id(&self.order_id)),
+        ))
+    }
+
+    /// Decode an event loaded from the store or received from the bus.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Serialization`] if the payload is not an order event.
+    pub fn decode(event: &SerializedEvent) -> Result<Self> {
+        bincode::deserialize(&event.data).map_err(|e| Error::Serialization(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_event_roundtrip() {
+        let event = OrderEvent {
+            order_id: "order-1".to_string(),
+            version: 1,
+            change: OrderChange::Shipped,
+        };
+
+        let encoded = event.encode().unwrap();
+        assert_eq!(encoded.event_type, "OrderShipped.v1");
+        assert_eq!(encoded.event_version, 1);
+        assert_eq!(
+            encoded.metadata.and_then(|m| m.correlation_id).as_deref(),
+            Some("order-1")
+        );
+
+        let decoded = OrderEvent::decode(&SerializedEvent::new(
+            encoded.event_type,
+            encoded.data,
+            None,
+        ))
+        .unwrap();
+        assert_eq!(decoded, event);
+    }
+}
@@ -0,0 +1,91 @@
+//! Exactly-once projection example binary
+//!
+//! Places an order, crashes the outbox relay and the consumer at the points
+//! where naive pipelines lose or duplicate updates, and shows that the read
+//! model still ends up with exactly one update per event.
+
+use composable_rust_core::event_bus::EventBus;
+use composable_rust_core::outbox::OutboxStore;
+use composable_rust_runtime::outbox::OutboxRelay;
+use composable_rust_testing::InMemoryProjectionStore;
+use composable_rust_testing::mocks::{InMemoryEventBus, InMemoryEventStore};
+use exactly_once_projection::chaos::{Chaos, ChaosBus, ChaosStore, CrashPoint};
+use exactly_once_projection::outbox::OrderService;
+use exactly_once_projection::projection::OrderSummaryProjection;
+use exactly_once_projection::{ORDERS_TOPIC, OrderEvent, Result};
+use futures::StreamExt;
+use std::sync::Arc;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "exactly_once_projection=info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    println!("=== Exactly-Once Projection with Transactional Outbox ===\n");
+
+    let events = Arc::new(InMemoryEventStore::new());
+    let chaos = Chaos::new();
+    let bus = Arc::new(InMemoryEventBus::new());
+    let mut stream = bus.subscribe(&[ORDERS_TOPIC]).await?;
+
+    let service = OrderService::new(events.clone());
+    let relay = OutboxRelay::new(events.clone(), Arc::new(ChaosBus::new(bus, chaos.clone())));
+    let read_model = ChaosStore::new(InMemoryProjectionStore::new(), chaos.clone());
+    let projection = || OrderSummaryProjection::new(read_model.clone(), events.clone());
+
+    println!(">>> Placing order-1 (event + outbox row in one transaction)");
+    service.place_order("order-1", "alice", 4_200).await?;
+
+    println!(">>> Relay crashes after publishing, before marking the outbox row");
+    chaos.arm(CrashPoint::AfterPublish)?;
+    if let Err(e) = relay.relay_once().await {
+        println!("    relay: {e}");
+    }
+    println!(
+        "    pending outbox rows: {}",
+        events.pending_outbox(10).await?.len()
+    );
+
+    println!(">>> Restarted relay publishes the row again");
+    relay.relay_once().await?;
+
+    println!(">>> Consumer crashes before writing its first delivery");
+    chaos.arm(CrashPoint::BeforeReadModelWrite)?;
+    let mut consumer = projection();
+    for _ in 0..2 {
+        let Some(Ok(delivered)) = stream.next().await else {
+            break;
+        };
+        match consumer.handle(&OrderEvent::decode(&delivered)?).await {
+            Ok(outcome) => println!("    consumer: {outcome:?}"),
+            Err(e) => {
+                println!("    consumer: {e} — restarting");
+                consumer = projection();
+                println!(
+                    "    catch-up replayed {} event(s)",
+                    consumer.catch_up().await?
+                );
+            },
+        }
+    }
+
+    if let Some(summary) = consumer.summary("order-1").await? {
+        println!(
+            "\nRead model: {} for {} ({} cents), status {:?}, version {}, updates = {}",
+            summary.order_id,
+            summary.customer_id,
+            summary.total_cents,
+            summary.status,
+            summary.version,
+            summary.updates
+        );
+    }
+
+    Ok(())
+}
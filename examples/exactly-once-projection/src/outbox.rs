@@ -0,0 +1,160 @@
+//! Write side: transactional outbox.
+//!
+//! [`OrderService`] appends each domain event with
+//! [`OutboxStore::append_with_outbox`], which stores the event and its outbox
+//! row in one transaction. The framework's
+//! [`OutboxRelay`](composable_rust_runtime::outbox::OutboxRelay) later drains
+//! the outbox onto the event bus. Because a row is only marked published
+//! *after* a successful publish, a crash in between leads to the message being
+//! published again — never to it being lost.
+
+use crate::{Error, ORDERS_TOPIC, OrderChange, OrderEvent, Result};
+use composable_rust_core::outbox::OutboxStore;
+use composable_rust_core::stream::{StreamId, Version};
+use std::sync::Arc;
+
+/// Prefix of the event streams holding order events.
+pub const ORDER_STREAM_PREFIX: &str = "orders-";
+
+/// Event stream of order `order_id`.
+#[must_use]
+pub fn order_stream(order_id: &str) -> StreamId {
+    StreamId::new(format!("{ORDER_STREAM_PREFIX}{order_id}"))
+}
+
+/// Command handler for orders.
+#[derive(Clone)]
+pub struct OrderService {
+    store: Arc<dyn OutboxStore>,
+}
+
+impl OrderService {
+    /// Create a service appending to `store`.
+    #[must_use]
+    pub fn new(store: Arc<dyn OutboxStore>) -> Self {
+        Self { store }
+    }
+
+    /// Place an order.
+    ///
+    /// Returns the stream version of the `Placed` event.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EventStore`] if the append fails (including a
+    /// concurrent write to the same order), or [`Error::Serialization`] if the
+    /// event cannot be encoded.
+    pub async fn place_order(
+        &self,
+        order_id: impl Into<String>,
+        customer_id: impl Into<String>,
+        total_cents: u64,
+    ) -> Result<Version> {
+        self.record(
+            order_id.into(),
+            OrderChange::Placed {
+                customer_id: customer_id.into(),
+                total_cents,
+            },
+        )
+        .await
+    }
+
+    /// Ship a previously placed order.
+    ///
+    /// Returns the stream version of the `Shipped` event.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownOrder`] if the order was never placed, plus the
+    /// errors of [`OrderService::place_order`].
+    pub async fn ship_order(&self, order_id: impl Into<String>) -> Result<Version> {
+        self.record(order_id.into(), OrderChange::Shipped).await
+    }
+
+    /// Append the event and its outbox row in a single transaction.
+    async fn record(&self, order_id: String, change: OrderChange) -> Result<Version> {
+        let stream_id = order_stream(&order_id);
+        let version = self
+            .store
+            .stream_metadata(stream_id.clone())
+            .await?
+            .map_or(0, |metadata| metadata.event_count);
+
+        if change == OrderChange::Shipped && version == 0 {
+            return Err(Error::UnknownOrder(order_id));
+        }
+
+        let event = OrderEvent {
+            order_id,
+            version,
+            change,
+        }
+        .encode()?;
+
+        let appended = self
+            .store
+            .append_with_outbox(
+                stream_id,
+                Some(Version::new(version)),
+                vec![event],
+                ORDERS_TOPIC.to_string(),
+            )
+            .await?;
+
+        tracing::debug!(version = appended.value(), "Appended event and outbox row");
+        Ok(appended)
+    }
+}
+
+impl std::fmt::Debug for OrderService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OrderService").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use composable_rust_core::event_store::EventStore;
+    use composable_rust_testing::mocks::InMemoryEventStore;
+
+    #[tokio::test]
+    async fn test_append_writes_event_and_outbox_row_together() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let service = OrderService::new(store.clone());
+
+        service.place_order("order-1", "alice", 100).await.unwrap();
+        let shipped = service.ship_order("order-1").await.unwrap();
+
+        assert_eq!(shipped, Version::new(1));
+        let events = store
+            .load_events(order_stream("order-1"), None)
+            .await
+            .unwrap();
+        assert_eq!(OrderEvent::decode(&events[1]).unwrap().version, 1);
+
+        let pending = store.pending_outbox(10).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert!(pending.iter().all(|entry| entry.topic == ORDERS_TOPIC));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_command_writes_nothing() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let service = OrderService::new(store.clone());
+
+        let result = service.ship_order("missing").await;
+
+        assert!(matches!(result, Err(Error::UnknownOrder(_))));
+        assert!(
+            store
+                .stream_metadata(order_stream("missing"))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(store.pending_outbox(10).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,316 @@
+//! Read side: idempotent projection keyed on stream versions.
+//!
+//! Every [`OrderSummary`] row records the version of the last event of its
+//! order's stream that it reflects, and the row is written in one
+//! [`ProjectionStore::save`]. That gives three cases for every delivered event:
+//!
+//! - `version <= row.version`: already applied, skip it (redelivery).
+//! - `version == row.version + 1`: apply it and record the new version.
+//! - `version > row.version + 1`: something was missed (e.g. the consumer
+//!   crashed before writing), so catch the order up from the event store.
+//!
+//! Because deduplication lives in the row itself, the consumer checkpoint kept
+//! by [`ProjectionManager`](composable_rust_projections::ProjectionManager) in
+//! a [`ProjectionCheckpoint`](composable_rust_core::projection::ProjectionCheckpoint)
+//! only decides where to resume; losing it causes redeliveries, never double
+//! updates.
+
+use crate::outbox::{ORDER_STREAM_PREFIX, order_stream};
+use crate::{OrderChange, OrderEvent};
+use composable_rust_core::event_store::{EventStore, Pagination};
+use composable_rust_core::projection::{Projection, ProjectionError, ProjectionStore, Result};
+use composable_rust_core::stream::Version;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Name of the order summary projection, used for its checkpoint.
+pub const PROJECTION_NAME: &str = "order_summaries";
+
+/// Streams scanned per page by [`OrderSummaryProjection::catch_up`].
+const CATCH_UP_PAGE_SIZE: usize = 100;
+
+/// Fulfilment status of an order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    /// The order was placed.
+    Placed,
+    /// The order was shipped.
+    Shipped,
+}
+
+/// Read-model row for one order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderSummary {
+    /// Order identifier
+    pub order_id: String,
+    /// Customer who placed the order
+    pub customer_id: String,
+    /// Total order value in cents
+    pub total_cents: u64,
+    /// Current status
+    pub status: OrderStatus,
+    /// Stream version of the last event applied to this row
+    pub version: u64,
+    /// Number of times this row was updated (one per event when exactly-once)
+    pub updates: u64,
+}
+
+/// What happened to a delivered event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApplyOutcome {
+    /// The event was applied to the read model.
+    Applied,
+    /// The event had already been applied and was skipped.
+    Duplicate,
+    /// A gap was detected; this many events were replayed from the store.
+    CaughtUp(usize),
+}
+
+/// Projection maintaining [`OrderSummary`] rows.
+#[derive(Clone)]
+pub struct OrderSummaryProjection<P> {
+    store: P,
+    events: Arc<dyn EventStore>,
+}
+
+impl<P: ProjectionStore> OrderSummaryProjection<P> {
+    /// Create a projection persisting rows to `store` and catching up from
+    /// `events`.
+    #[must_use]
+    pub fn new(store: P, events: Arc<dyn EventStore>) -> Self {
+        Self { store, events }
+    }
+
+    /// The summary of `order_id`, if the projection has seen it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails or the row cannot be decoded.
+    pub async fn summary(&self, order_id: &str) -> Result<Option<OrderSummary>> {
+        self.store
+            .get(&Self::key(order_id))
+            .await?
+            .map(|data| {
+                bincode::deserialize(&data)
+                    .map_err(|e| ProjectionError::Serialization(e.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Handle one delivered event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the read model or event store fails. Nothing has
+    /// been written for the event in that case; a later delivery or
+    /// [`catch_up`](Self::catch_up) applies it.
+    pub async fn handle(&self, event: &OrderEvent) -> Result<ApplyOutcome> {
+        let current = self.summary(&event.order_id).await?;
+        let next = current.as_ref().map_or(0, |row| row.version + 1);
+
+        if event.version < next {
+            tracing::debug!(order_id = %event.order_id, version = event.version, "Skipping duplicate");
+            return Ok(ApplyOutcome::Duplicate);
+        }
+        if event.version > next {
+            tracing::info!(order_id = %event.order_id, version = event.version, next, "Gap detected, catching up");
+            return self
+                .replay(&event.order_id, current)
+                .await
+                .map(ApplyOutcome::CaughtUp);
+        }
+
+        self.save(&apply(current, event)?).await?;
+        Ok(ApplyOutcome::Applied)
+    }
+
+    /// Replay every stored event that the read model has not applied yet.
+    ///
+    /// Called on startup to recover events whose delivery was consumed by a
+    /// crashed consumer. Returns the number of events applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the read model or event store fails. Rows written
+    /// before the failure keep their progress.
+    pub async fn catch_up(&self) -> Result<usize> {
+        let mut replayed = 0;
+        let mut page = Pagination::first(CATCH_UP_PAGE_SIZE);
+        loop {
+            let streams = self
+                .events
+                .list_streams(Some(ORDER_STREAM_PREFIX.to_string()), page)
+                .await
+                .map_err(|e| ProjectionError::Storage(e.to_string()))?;
+
+            for stream in &streams {
+                let Some(order_id) = stream.stream_id.as_str().strip_prefix(ORDER_STREAM_PREFIX)
+                else {
+                    continue;
+                };
+                let current = self.summary(order_id).await?;
+                let applied = current.as_ref().map_or(0, |row| row.version + 1);
+                if applied < stream.event_count {
+                    replayed += self.replay(order_id, current).await?;
+                }
+            }
+
+            if streams.len() < page.limit {
+                return Ok(replayed);
+            }
+            page = page.next();
+        }
+    }
+
+    /// Apply the events of `order_id` after `current` from the event store.
+    async fn replay(&self, order_id: &str, mut current: Option<OrderSummary>) -> Result<usize> {
+        let from = current.as_ref().map_or(0, |row| row.version + 1);
+        let stored = self
+            .events
+            .load_events(order_stream(order_id), Some(Version::new(from)))
+            .await
+            .map_err(|e| ProjectionError::Storage(e.to_string()))?;
+
+        let mut replayed = 0;
+        for event in &stored {
+            let event = OrderEvent::decode(event)
+                .map_err(|e| ProjectionError::Serialization(e.to_string()))?;
+            let row = apply(current, &event)?;
+            self.save(&row).await?;
+            current = Some(row);
+            replayed += 1;
+        }
+        Ok(replayed)
+    }
+
+    async fn save(&self, summary: &OrderSummary) -> Result<()> {
+        let data = bincode::serialize(summary)
+            .map_err(|e| ProjectionError::Serialization(e.to_string()))?;
+        self.store.save(&Self::key(&summary.order_id), &data).await
+    }
+
+    fn key(order_id: &str) -> String {
+        format!("order_summary:{order_id}")
+    }
+}
+
+impl<P: ProjectionStore> Projection for OrderSummaryProjection<P> {
+    type Event = OrderEvent;
+
+    fn name(&self) -> &'static str {
+        PROJECTION_NAME
+    }
+
+    async fn apply_event(&self, event: &OrderEvent) -> Result<()> {
+        self.handle(event).await.map(|_| ())
+    }
+}
+
+impl<P> std::fmt::Debug for OrderSummaryProjection<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OrderSummaryProjection")
+            .finish_non_exhaustive()
+    }
+}
+
+/// The row that results from applying `event` on top of `current`.
+fn apply(current: Option<OrderSummary>, event: &OrderEvent) -> Result<OrderSummary> {
+    let updates = current.as_ref().map_or(0, |row| row.updates) + 1;
+    let row = match (&event.change, current) {
+        (
+            OrderChange::Placed {
+                customer_id,
+                total_cents,
+            },
+            _,
+        ) => OrderSummary {
+            order_id: event.order_id.clone(),
+            customer_id: customer_id.clone(),
+            total_cents: *total_cents,
+            status: OrderStatus::Placed,
+            version: event.version,
+            updates,
+        },
+        (OrderChange::Shipped, Some(row)) => OrderSummary {
+            status: OrderStatus::Shipped,
+            version: event.version,
+            updates,
+            ..row
+        },
+        (OrderChange::Shipped, None) => {
+            return Err(ProjectionError::EventProcessing(format!(
+                "Shipped unknown order {}",
+                event.order_id
+            )));
+        },
+    };
+    Ok(row)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use crate::outbox::OrderService;
+    use composable_rust_testing::InMemoryProjectionStore;
+    use composable_rust_testing::mocks::InMemoryEventStore;
+
+    async fn stored(events: &InMemoryEventStore, order_id: &str) -> Vec<OrderEvent> {
+        events
+            .load_events(order_stream(order_id), None)
+            .await
+            .unwrap()
+            .iter()
+            .map(|event| OrderEvent::decode(event).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_delivery_is_skipped() {
+        let events = Arc::new(InMemoryEventStore::new());
+        OrderService::new(events.clone())
+            .place_order("order-1", "alice", 100)
+            .await
+            .unwrap();
+        let placed = stored(&events, "order-1").await.remove(0);
+        let projection = OrderSummaryProjection::new(InMemoryProjectionStore::new(), events);
+
+        assert_eq!(
+            projection.handle(&placed).await.unwrap(),
+            ApplyOutcome::Applied
+        );
+        assert_eq!(
+            projection.handle(&placed).await.unwrap(),
+            ApplyOutcome::Duplicate
+        );
+        assert_eq!(
+            projection
+                .summary("order-1")
+                .await
+                .unwrap()
+                .unwrap()
+                .updates,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gap_triggers_catch_up() {
+        let events = Arc::new(InMemoryEventStore::new());
+        let service = OrderService::new(events.clone());
+        service.place_order("order-1", "alice", 100).await.unwrap();
+        service.ship_order("order-1").await.unwrap();
+        let shipped = stored(&events, "order-1").await.remove(1);
+        let projection = OrderSummaryProjection::new(InMemoryProjectionStore::new(), events);
+
+        assert_eq!(
+            projection.handle(&shipped).await.unwrap(),
+            ApplyOutcome::CaughtUp(2)
+        );
+
+        let summary = projection.summary("order-1").await.unwrap().unwrap();
+        assert_eq!(summary.status, OrderStatus::Shipped);
+        assert_eq!(summary.version, 1);
+        assert_eq!(summary.updates, 2);
+    }
+}
@@ -0,0 +1,392 @@
+//! Chaos tests for the exactly-once projection pipeline.
+//!
+//! The pipeline is built from the framework's `OutboxStore`, `OutboxRelay`,
+//! `Projection` / `ProjectionManager` and `ProjectionCheckpoint`. Each test
+//! kills a stage at a specific point, "restarts" it by building a fresh
+//! instance over the same stores, and checks that every business operation
+//! resulted in exactly one read-model update.
+
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)] // Test code can use unwrap/expect/panic
+
+use composable_rust_core::event_bus::{EventBus, EventStream};
+use composable_rust_core::outbox::OutboxStore;
+use composable_rust_core::projection::ProjectionCheckpoint;
+use composable_rust_projections::ProjectionManager;
+use composable_rust_runtime::outbox::{OutboxRelay, OutboxRelayError};
+use composable_rust_testing::mocks::{InMemoryEventBus, InMemoryEventStore};
+use composable_rust_testing::{InMemoryProjectionCheckpoint, InMemoryProjectionStore};
+use exactly_once_projection::chaos::{Chaos, ChaosBus, ChaosCheckpoint, ChaosStore, CrashPoint};
+use exactly_once_projection::outbox::OrderService;
+use exactly_once_projection::projection::{
+    ApplyOutcome, OrderStatus, OrderSummary, OrderSummaryProjection, PROJECTION_NAME,
+};
+use exactly_once_projection::{ORDERS_TOPIC, OrderEvent};
+use futures::{FutureExt, StreamExt};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+type Projection = OrderSummaryProjection<ChaosStore<InMemoryProjectionStore>>;
+
+struct Harness {
+    events: Arc<InMemoryEventStore>,
+    bus: Arc<InMemoryEventBus>,
+    read_model: ChaosStore<InMemoryProjectionStore>,
+    checkpoints: InMemoryProjectionCheckpoint,
+    chaos: Chaos,
+    service: OrderService,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let events = Arc::new(InMemoryEventStore::new());
+        let chaos = Chaos::new();
+        Self {
+            service: OrderService::new(events.clone()),
+            events,
+            bus: Arc::new(InMemoryEventBus::new()),
+            read_model: ChaosStore::new(InMemoryProjectionStore::new(), chaos.clone()),
+            checkpoints: InMemoryProjectionCheckpoint::new(),
+            chaos,
+        }
+    }
+
+    /// A fresh relay process over the shared outbox.
+    fn relay(&self) -> OutboxRelay {
+        OutboxRelay::new(
+            self.events.clone(),
+            Arc::new(ChaosBus::new(self.bus.clone(), self.chaos.clone())),
+        )
+    }
+
+    /// A fresh consumer process over the shared read model.
+    fn projection(&self) -> Projection {
+        OrderSummaryProjection::new(self.read_model.clone(), self.events.clone())
+    }
+
+    /// A projection manager whose checkpoint saves crash when armed.
+    fn manager(
+        &self,
+    ) -> (
+        ProjectionManager<Projection>,
+        tokio::sync::watch::Sender<bool>,
+    ) {
+        let checkpoint =
+            ChaosCheckpoint::new(Arc::new(self.checkpoints.clone()), self.chaos.clone());
+        let (manager, shutdown) = ProjectionManager::new(
+            self.projection(),
+            self.bus.clone(),
+            Arc::new(checkpoint),
+            ORDERS_TOPIC,
+            "order-summaries",
+        );
+        (manager.with_checkpoint_interval(1), shutdown)
+    }
+
+    async fn subscribe(&self) -> EventStream {
+        self.bus.subscribe(&[ORDERS_TOPIC]).await.unwrap()
+    }
+
+    async fn pending(&self) -> usize {
+        self.events.pending_outbox(1_000).await.unwrap().len()
+    }
+
+    async fn summary(&self, order_id: &str) -> Option<OrderSummary> {
+        self.projection().summary(order_id).await.unwrap()
+    }
+
+    async fn checkpoint(&self) -> Option<u64> {
+        self.checkpoints
+            .load_position(PROJECTION_NAME)
+            .await
+            .unwrap()
+            .map(|position| position.offset)
+    }
+}
+
+/// Deliver every message currently buffered in `stream` to `projection`.
+async fn drain(stream: &mut EventStream, projection: &Projection) -> Vec<ApplyOutcome> {
+    let mut outcomes = Vec::new();
+    while let Some(Some(delivery)) = stream.next().now_or_never() {
+        let event = OrderEvent::decode(&delivery.unwrap()).unwrap();
+        outcomes.push(projection.handle(&event).await.unwrap());
+    }
+    outcomes
+}
+
+/// Poll `condition` until it holds, failing the test after five seconds.
+async fn wait_until<F, Fut>(what: &str, mut condition: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !condition().await {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out waiting for {what}"));
+}
+
+#[tokio::test]
+async fn test_crash_between_append_and_publish() {
+    let h = Harness::new();
+    let mut stream = h.subscribe().await;
+
+    h.service
+        .place_order("order-1", "alice", 1_000)
+        .await
+        .unwrap();
+    h.chaos.arm(CrashPoint::AfterAppend).unwrap();
+
+    let crashed = h.relay().relay_once().await;
+    assert!(matches!(crashed, Err(OutboxRelayError::Publish { .. })));
+    assert_eq!(h.pending().await, 1, "outbox row must survive the crash");
+    assert!(drain(&mut stream, &h.projection()).await.is_empty());
+
+    // Restarted relay picks up the pending row.
+    assert_eq!(h.relay().relay_once().await.unwrap(), 1);
+    assert_eq!(
+        drain(&mut stream, &h.projection()).await,
+        vec![ApplyOutcome::Applied]
+    );
+
+    assert_eq!(h.pending().await, 0);
+    assert_eq!(h.summary("order-1").await.unwrap().updates, 1);
+}
+
+#[tokio::test]
+async fn test_crash_between_publish_and_outbox_mark() {
+    let h = Harness::new();
+    let mut stream = h.subscribe().await;
+    let projection = h.projection();
+
+    h.service
+        .place_order("order-1", "alice", 1_000)
+        .await
+        .unwrap();
+    h.chaos.arm(CrashPoint::AfterPublish).unwrap();
+
+    let crashed = h.relay().relay_once().await;
+    assert!(matches!(crashed, Err(OutboxRelayError::Publish { .. })));
+    assert_eq!(h.pending().await, 1);
+    assert_eq!(
+        drain(&mut stream, &projection).await,
+        vec![ApplyOutcome::Applied]
+    );
+
+    // Restarted relay publishes the same message again.
+    assert_eq!(h.relay().relay_once().await.unwrap(), 1);
+    assert_eq!(
+        drain(&mut stream, &projection).await,
+        vec![ApplyOutcome::Duplicate]
+    );
+
+    let published = h.bus.history(ORDERS_TOPIC);
+    assert_eq!(published.len(), 2);
+    let message_ids: Vec<_> = published
+        .iter()
+        .map(|event| event.metadata.as_ref().and_then(|m| m.causation_id.clone()))
+        .collect();
+    assert_eq!(
+        message_ids[0], message_ids[1],
+        "redelivery keeps its message id"
+    );
+
+    assert_eq!(h.pending().await, 0);
+    assert_eq!(h.summary("order-1").await.unwrap().updates, 1);
+}
+
+#[tokio::test]
+async fn test_crash_before_read_model_write() {
+    let h = Harness::new();
+    let mut stream = h.subscribe().await;
+
+    h.service
+        .place_order("order-1", "alice", 1_000)
+        .await
+        .unwrap();
+    h.chaos.arm(CrashPoint::BeforeReadModelWrite).unwrap();
+    h.relay().relay_once().await.unwrap();
+
+    // Consumer dies while applying: the row is not written.
+    let delivered = OrderEvent::decode(&stream.next().await.unwrap().unwrap()).unwrap();
+    assert!(h.projection().handle(&delivered).await.is_err());
+    assert!(h.summary("order-1").await.is_none());
+
+    // The message was already marked by the relay, so the restarted consumer
+    // recovers it from the event store.
+    let restarted = h.projection();
+    assert_eq!(restarted.catch_up().await.unwrap(), 1);
+    assert_eq!(
+        restarted.handle(&delivered).await.unwrap(),
+        ApplyOutcome::Duplicate
+    );
+
+    let summary = h.summary("order-1").await.unwrap();
+    assert_eq!(summary.version, 0);
+    assert_eq!(summary.updates, 1);
+}
+
+#[tokio::test]
+async fn test_crash_before_checkpoint_is_redelivered_without_double_update() {
+    let h = Harness::new();
+    let (mut manager, shutdown) = h.manager();
+    let consumer = tokio::spawn(async move { manager.start().await });
+    wait_until("the manager to subscribe", || async {
+        h.bus.subscriber_count(ORDERS_TOPIC) > 0
+    })
+    .await;
+
+    h.service
+        .place_order("order-1", "alice", 1_000)
+        .await
+        .unwrap();
+    h.chaos.arm(CrashPoint::AfterPublish).unwrap();
+    h.chaos.arm(CrashPoint::BeforeCheckpoint).unwrap();
+
+    // The relay publishes but dies before marking; the consumer writes the
+    // row but dies before checkpointing.
+    assert!(h.relay().relay_once().await.is_err());
+    wait_until("the read model", || async {
+        h.summary("order-1").await.is_some()
+    })
+    .await;
+    assert!(!h.chaos.is_armed(CrashPoint::BeforeCheckpoint).unwrap());
+    assert_eq!(h.checkpoint().await, None);
+
+    // The restarted relay redelivers; the manager checkpoints both
+    // deliveries, but the row is only updated once.
+    assert_eq!(h.relay().relay_once().await.unwrap(), 1);
+    wait_until("the checkpoint", || async {
+        h.checkpoint().await == Some(2)
+    })
+    .await;
+
+    shutdown.send(true).unwrap();
+    consumer.await.unwrap().unwrap();
+
+    let summary = h.summary("order-1").await.unwrap();
+    assert_eq!(summary.version, 0);
+    assert_eq!(summary.updates, 1);
+}
+
+#[tokio::test]
+async fn test_consumer_offline_during_publish_catches_up() {
+    let h = Harness::new();
+
+    h.service
+        .place_order("order-1", "alice", 1_000)
+        .await
+        .unwrap();
+    h.relay().relay_once().await.unwrap();
+
+    // Consumer subscribes after the first message was published.
+    let mut stream = h.subscribe().await;
+    h.service.ship_order("order-1").await.unwrap();
+    h.service.place_order("order-2", "bob", 500).await.unwrap();
+    h.relay().relay_once().await.unwrap();
+
+    assert_eq!(
+        drain(&mut stream, &h.projection()).await,
+        vec![ApplyOutcome::CaughtUp(2), ApplyOutcome::Applied]
+    );
+
+    let first = h.summary("order-1").await.unwrap();
+    assert_eq!(first.status, OrderStatus::Shipped);
+    assert_eq!(first.updates, 2);
+    assert_eq!(h.summary("order-2").await.unwrap().updates, 1);
+}
+
+#[tokio::test]
+async fn test_repeated_crashes_at_every_point_yield_exactly_once() {
+    let h = Harness::new();
+    let mut stream = h.subscribe().await;
+    let points = [
+        CrashPoint::AfterAppend,
+        CrashPoint::AfterPublish,
+        CrashPoint::BeforeReadModelWrite,
+    ];
+
+    for i in 0..30 {
+        let order_id = format!("order-{i}");
+        h.service
+            .place_order(&order_id, "alice", 100)
+            .await
+            .unwrap();
+        if i % 2 == 0 {
+            h.service.ship_order(&order_id).await.unwrap();
+        }
+        h.chaos.arm(points[i % points.len()]).unwrap();
+
+        // Keep restarting crashed processes until the pipeline is quiescent.
+        loop {
+            let relayed = h.relay().relay_once().await;
+            let mut consumer_crashed = false;
+            while let Some(Some(delivery)) = stream.next().now_or_never() {
+                let event = OrderEvent::decode(&delivery.unwrap()).unwrap();
+                if h.projection().handle(&event).await.is_err() {
+                    consumer_crashed = true;
+                }
+            }
+            if consumer_crashed {
+                h.projection().catch_up().await.unwrap();
+            }
+            if relayed.is_ok() && h.pending().await == 0 {
+                break;
+            }
+        }
+    }
+
+    for i in 0..30 {
+        let summary = h.summary(&format!("order-{i}")).await.unwrap();
+        let expected = if i % 2 == 0 { 2 } else { 1 };
+        assert_eq!(summary.updates, expected, "order-{i}");
+        assert_eq!(summary.version + 1, expected, "order-{i}");
+    }
+}
+
+#[tokio::test]
+async fn test_running_pipeline_projects_every_order_once() {
+    let h = Harness::new();
+    let (mut manager, stop_consumer) = h.manager();
+    let consumer = tokio::spawn(async move { manager.start().await });
+    wait_until("the manager to subscribe", || async {
+        h.bus.subscriber_count(ORDERS_TOPIC) > 0
+    })
+    .await;
+
+    let (stop_relay, relay_stopped) = tokio::sync::oneshot::channel::<()>();
+    let relay = h.relay().with_poll_interval(Duration::from_millis(5));
+    let relay_task = tokio::spawn(relay.run_until(async {
+        let _ = relay_stopped.await;
+    }));
+
+    h.chaos.arm(CrashPoint::AfterPublish).unwrap();
+    h.chaos.arm(CrashPoint::BeforeCheckpoint).unwrap();
+    for i in 0..10 {
+        h.service
+            .place_order(format!("order-{i}"), "alice", 100)
+            .await
+            .unwrap();
+    }
+
+    wait_until("the outbox to drain", || async { h.pending().await == 0 }).await;
+    let deliveries = h.bus.history(ORDERS_TOPIC).len() as u64;
+    assert!(deliveries > 10, "the relay crash must cause a redelivery");
+    wait_until("the checkpoint", || async {
+        h.checkpoint().await == Some(deliveries)
+    })
+    .await;
+
+    let _ = stop_relay.send(());
+    relay_task.await.unwrap();
+    stop_consumer.send(true).unwrap();
+    consumer.await.unwrap().unwrap();
+
+    for i in 0..10 {
+        let summary = h.summary(&format!("order-{i}")).await.unwrap();
+        assert_eq!(summary.updates, 1, "order-{i}");
+    }
+}
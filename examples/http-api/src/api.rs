@@ -0,0 +1,351 @@
+//! HTTP routes of the example.
+//!
+//! | Route                      | Backed by                          |
+//! |----------------------------|------------------------------------|
+//! | `POST /orders`             | store, waits for the reply action  |
+//! | `POST /orders/:id/cancel`  | store, waits for the reply action  |
+//! | `GET /orders/:id`          | order summary projection           |
+//! | `GET /healthz`             | health registry                    |
+//!
+//! Errors use the same `{ "code", "message" }` body as
+//! [`StoreRejection`].
+
+use crate::orders::{
+    OrderAction, OrderEnvironment, OrderReducer, OrderState, OrderStore, RejectionReason,
+};
+use crate::projection::{OrderSummaryProjection, ProjectionWorker};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use composable_rust_axum::{StoreExtension, StoreRef, StoreRejection, respond_with_map};
+use composable_rust_core::event_bus::{EventBus, EventBusError};
+use composable_rust_runtime::health::HealthRegistry;
+use composable_rust_runtime::{Store, StoreError};
+use composable_rust_testing::InMemoryProjectionStore;
+use composable_rust_web::handlers::health_report;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How long a command waits for the store's reply
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Projection served by `GET /orders/:id`
+type Summaries = Arc<OrderSummaryProjection<InMemoryProjectionStore>>;
+
+/// Extractor for the order store registered by [`Services::router`]
+type Orders = StoreRef<OrderState, OrderAction, OrderEnvironment, OrderReducer>;
+
+/// Everything the HTTP API serves from
+pub struct Services {
+    /// Write side
+    pub store: Arc<OrderStore>,
+    /// Read side
+    pub summaries: Summaries,
+    /// Health of the store and the projection worker
+    pub health: Arc<HealthRegistry>,
+    worker: ProjectionWorker,
+}
+
+impl Services {
+    /// Create the store, start the projection worker and register both for
+    /// health checks
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the projection worker cannot subscribe to
+    /// `event_bus`.
+    pub async fn start(event_bus: Arc<dyn EventBus>) -> Result<Self, EventBusError> {
+        let summaries = Arc::new(OrderSummaryProjection::new(InMemoryProjectionStore::new()));
+        let worker = ProjectionWorker::spawn(&event_bus, Arc::clone(&summaries)).await?;
+        let store = Arc::new(Store::new(
+            OrderState::default(),
+            OrderReducer,
+            OrderEnvironment::new(event_bus),
+        ));
+
+        let health = Arc::new(HealthRegistry::new());
+        health.register("orders", Arc::clone(&store) as _);
+        health.register("order-summaries", worker.health());
+
+        Ok(Self {
+            store,
+            summaries,
+            health,
+            worker,
+        })
+    }
+
+    /// Router serving every route of the API
+    pub fn router(&self) -> Router {
+        let commands = Router::new()
+            .route("/orders", post(place_order))
+            .route("/orders/:id/cancel", post(cancel_order))
+            .layer(StoreExtension::new(Arc::clone(&self.store)));
+        let queries = Router::new()
+            .route("/orders/:id", get(get_order))
+            .with_state(Arc::clone(&self.summaries));
+        let health = Router::new()
+            .route("/healthz", get(health_report))
+            .with_state(Arc::clone(&self.health));
+
+        commands.merge(queries).merge(health)
+    }
+
+    /// Stop the projection worker, then drain the store's in-flight effects
+    ///
+    /// Call this once the server has stopped accepting requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store does not drain within `timeout`.
+    pub async fn shutdown(self, timeout: Duration) -> Result<(), StoreError> {
+        let result = self.store.shutdown(timeout).await;
+        self.worker.shutdown().await;
+        result
+    }
+}
+
+/// Body of `POST /orders`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaceOrderRequest {
+    /// Customer placing the order
+    pub customer: String,
+    /// Order total in cents
+    pub total_cents: u64,
+}
+
+/// Body of a successful command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderCreated {
+    /// ID of the new order
+    pub id: Uuid,
+}
+
+/// Error response body (JSON)
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    /// Error code (for client error handling)
+    code: &'static str,
+    /// Human-readable error message
+    message: String,
+}
+
+fn error(status: StatusCode, code: &'static str, message: impl Into<String>) -> Response {
+    let body = ErrorResponse {
+        code,
+        message: message.into(),
+    };
+    (status, Json(body)).into_response()
+}
+
+/// Turn any reply other than the expected one into an error response
+fn failure(reply: OrderAction) -> Response {
+    match reply {
+        OrderAction::OrderRejected { reason, .. } => {
+            let status = match reason {
+                RejectionReason::AlreadyExists | RejectionReason::AlreadyCancelled => {
+                    StatusCode::CONFLICT
+                },
+                RejectionReason::InvalidTotal => StatusCode::UNPROCESSABLE_ENTITY,
+                RejectionReason::NotFound => StatusCode::NOT_FOUND,
+            };
+            let code = match reason {
+                RejectionReason::AlreadyExists => "ALREADY_EXISTS",
+                RejectionReason::InvalidTotal => "INVALID_TOTAL",
+                RejectionReason::NotFound => "NOT_FOUND",
+                RejectionReason::AlreadyCancelled => "ALREADY_CANCELLED",
+            };
+            error(status, code, reason.message())
+        },
+        OrderAction::PublishFailed { reason, .. } => {
+            tracing::error!(%reason, "Order event could not be published");
+            error(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "PUBLISH_FAILED",
+                "Order could not be recorded, try again",
+            )
+        },
+        other => {
+            tracing::error!(action = ?other, "Unexpected reply to order command");
+            error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                "An internal error occurred",
+            )
+        },
+    }
+}
+
+/// `POST /orders`: place an order and answer `201` with its ID
+async fn place_order(
+    store: Orders,
+    Json(request): Json<PlaceOrderRequest>,
+) -> Result<Response, StoreRejection> {
+    let id = Uuid::new_v4();
+    let command = OrderAction::PlaceOrder {
+        id,
+        customer: request.customer,
+        total_cents: request.total_cents,
+    };
+
+    respond_with_map(
+        &store,
+        command,
+        move |a| a.order_id() == id && a.is_terminal(),
+        COMMAND_TIMEOUT,
+        |reply| match reply {
+            OrderAction::OrderPlaced { id } => {
+                (StatusCode::CREATED, Json(OrderCreated { id })).into_response()
+            },
+            other => failure(other),
+        },
+    )
+    .await
+}
+
+/// `POST /orders/:id/cancel`: cancel an order and answer `204`
+async fn cancel_order(store: Orders, Path(id): Path<Uuid>) -> Result<Response, StoreRejection> {
+    respond_with_map(
+        &store,
+        OrderAction::CancelOrder { id },
+        move |a| a.order_id() == id && a.is_terminal(),
+        COMMAND_TIMEOUT,
+        |reply| match reply {
+            OrderAction::OrderCancelled { .. } => StatusCode::NO_CONTENT.into_response(),
+            other => failure(other),
+        },
+    )
+    .await
+}
+
+/// `GET /orders/:id`: the order's summary from the projection
+async fn get_order(State(summaries): State<Summaries>, Path(id): Path<Uuid>) -> Response {
+    match summaries.summary(id).await {
+        Ok(Some(summary)) => Json(summary).into_response(),
+        Ok(None) => error(StatusCode::NOT_FOUND, "NOT_FOUND", "Order not found"),
+        Err(e) => {
+            tracing::error!(error = %e, "Order summary query failed");
+            error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                "An internal error occurred",
+            )
+        },
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)] // Test code can use unwrap/expect/panic
+mod tests {
+    use super::*;
+    use crate::orders::OrderStatus;
+    use crate::projection::OrderSummary;
+    use axum::body::Body;
+    use axum::http::Request;
+    use composable_rust_testing::mocks::InMemoryEventBus;
+    use tower::ServiceExt;
+
+    async fn call(
+        router: &Router,
+        method: &str,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> Response {
+        let request = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => request
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        };
+        router.clone().oneshot(request.unwrap()).await.unwrap()
+    }
+
+    async fn body_json<T: serde::de::DeserializeOwned>(response: Response) -> T {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    /// Poll the query endpoint until the projection has caught up with `status`
+    async fn summary_with(router: &Router, id: Uuid, status: OrderStatus) -> OrderSummary {
+        for _ in 0..100 {
+            let response = call(router, "GET", &format!("/orders/{id}"), None).await;
+            if response.status() == StatusCode::OK {
+                let summary: OrderSummary = body_json(response).await;
+                if summary.status == status {
+                    return summary;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let health = call(router, "GET", "/healthz", None).await;
+        let health: serde_json::Value = body_json(health).await;
+        panic!("projection never reported order {id} as {status:?}: {health}");
+    }
+
+    #[tokio::test]
+    async fn test_commands_update_projection_and_health() {
+        let services = Services::start(Arc::new(InMemoryEventBus::new()))
+            .await
+            .unwrap();
+        let router = services.router();
+
+        let order = serde_json::json!({ "customer": "alice", "total_cents": 2500 });
+        let response = call(&router, "POST", "/orders", Some(order)).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let OrderCreated { id } = body_json(response).await;
+
+        let summary = summary_with(&router, id, OrderStatus::Placed).await;
+        assert_eq!(summary.customer, "alice");
+        assert_eq!(summary.total_cents, 2500);
+
+        let cancel = format!("/orders/{id}/cancel");
+        assert_eq!(
+            call(&router, "POST", &cancel, None).await.status(),
+            StatusCode::NO_CONTENT
+        );
+        summary_with(&router, id, OrderStatus::Cancelled).await;
+
+        let response = call(&router, "POST", &cancel, None).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body: serde_json::Value = body_json(response).await;
+        assert_eq!(body["code"], "ALREADY_CANCELLED");
+
+        let response = call(&router, "GET", "/healthz", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let report: serde_json::Value = body_json(response).await;
+        assert_eq!(report["status"], "healthy");
+        assert_eq!(report["checks"].as_array().unwrap().len(), 2);
+
+        services.shutdown(Duration::from_secs(1)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejections_map_to_status_codes() {
+        let services = Services::start(Arc::new(InMemoryEventBus::new()))
+            .await
+            .unwrap();
+        let router = services.router();
+
+        let order = serde_json::json!({ "customer": "bob", "total_cents": 0 });
+        let response = call(&router, "POST", "/orders", Some(order)).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let unknown = Uuid::new_v4();
+        let cancel = format!("/orders/{unknown}/cancel");
+        assert_eq!(
+            call(&router, "POST", &cancel, None).await.status(),
+            StatusCode::NOT_FOUND
+        );
+        let response = call(&router, "GET", &format!("/orders/{unknown}"), None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        services.shutdown(Duration::from_secs(1)).await.unwrap();
+    }
+}
@@ -0,0 +1,63 @@
+//! HTTP API Example
+//!
+//! Reference integration of a Store behind an Axum server: command endpoints
+//! that wait for the store's answer, a query endpoint served from a
+//! projection, an aggregated `/healthz`, and graceful shutdown.
+//!
+//! # Architecture
+//!
+//! ```text
+//! POST /orders ────────► OrderStore ──► PublishEvent ──► EventBus ("orders")
+//! POST /orders/:id/cancel   │                                │
+//!        ▲                  │ OrderPlaced / OrderCancelled   ▼
+//!        └──────────────────┘ (send_and_wait_for)      ProjectionWorker
+//!                                                            │
+//! GET /orders/:id ◄──────── OrderSummaryProjection ◄─────────┘
+//!
+//! GET /healthz ◄─────────── HealthRegistry (store + projection worker)
+//! ```
+//!
+//! # Key Concepts Demonstrated
+//!
+//! - **Request-response over a store**: [`composable_rust_axum::respond_with_map`]
+//!   sends a command and maps the terminal action to a status code
+//! - **CQRS**: writes go through the store, reads come from a projection
+//!   that is updated asynchronously from the event bus
+//! - **Health aggregation**: the store and the projection worker report
+//!   into one [`HealthRegistry`](composable_rust_runtime::health::HealthRegistry)
+//! - **Graceful shutdown**: the server stops accepting requests, then the
+//!   store drains its in-flight effects
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use composable_rust_core::event_bus::EventBus;
+//! use composable_rust_testing::mocks::InMemoryEventBus;
+//! use http_api::Services;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let event_bus: Arc<dyn EventBus> = Arc::new(InMemoryEventBus::new());
+//! let services = Services::start(event_bus).await?;
+//!
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+//! axum::serve(listener, services.router()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+/// HTTP routes and handlers
+pub mod api;
+
+/// Order domain: actions, events and reducer
+pub mod orders;
+
+/// Order summary read model and the worker that keeps it up to date
+pub mod projection;
+
+pub use api::Services;
+pub use orders::{
+    ORDERS_TOPIC, OrderAction, OrderEnvironment, OrderEvent, OrderReducer, OrderState, OrderStatus,
+    OrderStore, RejectionReason,
+};
+pub use projection::{OrderSummary, OrderSummaryProjection, ProjectionWorker};
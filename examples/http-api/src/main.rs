@@ -0,0 +1,52 @@
+//! HTTP API Example - Runnable Server
+//!
+//! Serves the order API on `127.0.0.1:3000` (override with `BIND_ADDR`):
+//!
+//! ```text
+//! curl -X POST localhost:3000/orders -H 'content-type: application/json' \
+//!      -d '{"customer":"alice","total_cents":2500}'
+//! curl localhost:3000/orders/<id>
+//! curl -X POST localhost:3000/orders/<id>/cancel
+//! curl localhost:3000/healthz
+//! ```
+//!
+//! Ctrl-C stops accepting connections, lets in-flight requests finish, then
+//! drains the store before exiting.
+
+use composable_rust_core::event_bus::EventBus;
+use composable_rust_testing::mocks::InMemoryEventBus;
+use http_api::Services;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{Level, info};
+
+/// How long the store may take to drain in-flight effects on shutdown
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    let event_bus: Arc<dyn EventBus> = Arc::new(InMemoryEventBus::new());
+    let services = Services::start(event_bus).await?;
+
+    let addr = std::env::var("BIND_ADDR").unwrap_or_else(|_| "127.0.0.1:3000".to_string());
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    info!("Listening on http://{addr}");
+
+    axum::serve(listener, services.router())
+        .with_graceful_shutdown(async {
+            if let Err(error) = tokio::signal::ctrl_c().await {
+                tracing::error!(%error, "Failed to listen for Ctrl-C");
+            }
+            info!("Shutting down: no longer accepting connections");
+        })
+        .await?;
+
+    services.shutdown(SHUTDOWN_TIMEOUT).await?;
+    info!("Store drained, bye");
+    Ok(())
+}
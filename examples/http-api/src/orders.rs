@@ -0,0 +1,261 @@
+//! Order aggregate served by the HTTP API.
+//!
+//! Commands are answered with an action produced by an effect, never by the
+//! reducer directly: `send_and_wait_for` only sees actions fed back by
+//! effects, so even rejections are returned through an `Effect::Future`.
+
+use composable_rust_core::effect::Effect;
+use composable_rust_core::effect_builders::EventBusEffect;
+use composable_rust_core::event::{Event, SerializedEvent};
+use composable_rust_core::event_bus::EventBus;
+use composable_rust_core::reducer::Reducer;
+use composable_rust_core::{SmallVec, smallvec};
+use composable_rust_runtime::Store;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Topic the order events are published to
+pub const ORDERS_TOPIC: &str = "orders";
+
+/// Store type served by the API
+pub type OrderStore = Store<OrderState, OrderAction, OrderEnvironment, OrderReducer>;
+
+/// Lifecycle of an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    /// Order has been placed
+    Placed,
+    /// Order has been cancelled
+    Cancelled,
+}
+
+/// Why a command was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RejectionReason {
+    /// An order with this ID already exists
+    AlreadyExists,
+    /// The order total is zero
+    InvalidTotal,
+    /// No order with this ID exists
+    NotFound,
+    /// The order was already cancelled
+    AlreadyCancelled,
+}
+
+impl RejectionReason {
+    /// Human-readable explanation
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            Self::AlreadyExists => "Order already exists",
+            Self::InvalidTotal => "Order total must be positive",
+            Self::NotFound => "Order not found",
+            Self::AlreadyCancelled => "Order already cancelled",
+        }
+    }
+}
+
+/// Write-side state: the status of every known order
+#[derive(Debug, Clone, Default)]
+pub struct OrderState {
+    /// Orders by ID
+    pub orders: HashMap<Uuid, OrderStatus>,
+}
+
+/// Commands sent by the API and the replies fed back by effects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type")]
+pub enum OrderAction {
+    /// Command: place a new order
+    PlaceOrder {
+        /// Order ID chosen by the caller
+        id: Uuid,
+        /// Customer placing the order
+        customer: String,
+        /// Order total in cents
+        total_cents: u64,
+    },
+    /// Command: cancel a placed order
+    CancelOrder {
+        /// Order to cancel
+        id: Uuid,
+    },
+    /// Reply: the order was placed and its event published
+    OrderPlaced {
+        /// Placed order
+        id: Uuid,
+    },
+    /// Reply: the order was cancelled and its event published
+    OrderCancelled {
+        /// Cancelled order
+        id: Uuid,
+    },
+    /// Reply: the command was refused by a business rule
+    OrderRejected {
+        /// Order the command was about
+        id: Uuid,
+        /// Why the command was refused
+        reason: RejectionReason,
+    },
+    /// Reply: the command was accepted but its event could not be published
+    PublishFailed {
+        /// Order the command was about
+        id: Uuid,
+        /// Encoding or event bus error
+        reason: String,
+    },
+}
+
+impl OrderAction {
+    /// The order this action is about, used to correlate replies with requests
+    #[must_use]
+    pub const fn order_id(&self) -> Uuid {
+        match self {
+            Self::PlaceOrder { id, .. }
+            | Self::CancelOrder { id }
+            | Self::OrderPlaced { id }
+            | Self::OrderCancelled { id }
+            | Self::OrderRejected { id, .. }
+            | Self::PublishFailed { id, .. } => *id,
+        }
+    }
+
+    /// Whether this action answers a command
+    #[must_use]
+    pub const fn is_terminal(&self) -> bool {
+        !matches!(self, Self::PlaceOrder { .. } | Self::CancelOrder { .. })
+    }
+
+    fn rejected(id: Uuid, reason: RejectionReason) -> Effect<Self> {
+        Effect::Future(Box::pin(
+            async move { Some(Self::OrderRejected { id, reason }) },
+        ))
+    }
+}
+
+/// Events published for the read side
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderEvent {
+    /// An order was placed
+    Placed {
+        /// Order ID
+        id: Uuid,
+        /// Customer who placed it
+        customer: String,
+        /// Order total in cents
+        total_cents: u64,
+    },
+    /// An order was cancelled
+    Cancelled {
+        /// Order ID
+        id: Uuid,
+    },
+}
+
+impl Event for OrderEvent {
+    fn event_type(&self) -> &'static str {
+        match self {
+            Self::Placed { .. } => "OrderPlaced.v1",
+            Self::Cancelled { .. } => "OrderCancelled.v1",
+        }
+    }
+}
+
+/// Dependencies of the order reducer
+#[derive(Clone)]
+pub struct OrderEnvironment {
+    /// Bus the order events are published to
+    pub event_bus: Arc<dyn EventBus>,
+}
+
+impl OrderEnvironment {
+    /// Create an environment publishing to `event_bus`
+    #[must_use]
+    pub fn new(event_bus: Arc<dyn EventBus>) -> Self {
+        Self { event_bus }
+    }
+
+    /// Publish `event`, replying with `reply` once it is on the bus
+    fn publish(&self, event: &OrderEvent, reply: OrderAction) -> Effect<OrderAction> {
+        let id = reply.order_id();
+        let serialized = match SerializedEvent::from_event(event, None) {
+            Ok(serialized) => serialized,
+            Err(error) => {
+                let reason = error.to_string();
+                return Effect::Future(Box::pin(async move {
+                    Some(OrderAction::PublishFailed { id, reason })
+                }));
+            },
+        };
+
+        EventBusEffect::publish(&self.event_bus, ORDERS_TOPIC, serialized)
+            .partition_key(id.to_string())
+            .on_success(move || Some(reply.clone()))
+            .on_error(move |error| {
+                Some(OrderAction::PublishFailed {
+                    id,
+                    reason: error.to_string(),
+                })
+            })
+            .into_effect()
+    }
+}
+
+/// Reducer of the order aggregate
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrderReducer;
+
+impl Reducer for OrderReducer {
+    type State = OrderState;
+    type Action = OrderAction;
+    type Environment = OrderEnvironment;
+
+    fn reduce(
+        &self,
+        state: &mut OrderState,
+        action: OrderAction,
+        env: &OrderEnvironment,
+    ) -> SmallVec<[Effect<OrderAction>; 4]> {
+        match action {
+            OrderAction::PlaceOrder {
+                id,
+                customer,
+                total_cents,
+            } => {
+                if state.orders.contains_key(&id) {
+                    return smallvec![OrderAction::rejected(id, RejectionReason::AlreadyExists)];
+                }
+                if total_cents == 0 {
+                    return smallvec![OrderAction::rejected(id, RejectionReason::InvalidTotal)];
+                }
+
+                state.orders.insert(id, OrderStatus::Placed);
+                let event = OrderEvent::Placed {
+                    id,
+                    customer,
+                    total_cents,
+                };
+                smallvec![env.publish(&event, OrderAction::OrderPlaced { id })]
+            },
+            OrderAction::CancelOrder { id } => match state.orders.get_mut(&id) {
+                None => smallvec![OrderAction::rejected(id, RejectionReason::NotFound)],
+                Some(OrderStatus::Cancelled) => {
+                    smallvec![OrderAction::rejected(id, RejectionReason::AlreadyCancelled)]
+                },
+                Some(status) => {
+                    *status = OrderStatus::Cancelled;
+                    let event = OrderEvent::Cancelled { id };
+                    smallvec![env.publish(&event, OrderAction::OrderCancelled { id })]
+                },
+            },
+            // Replies only carry the answer back to the caller
+            OrderAction::OrderPlaced { .. }
+            | OrderAction::OrderCancelled { .. }
+            | OrderAction::OrderRejected { .. }
+            | OrderAction::PublishFailed { .. } => smallvec![Effect::None],
+        }
+    }
+}
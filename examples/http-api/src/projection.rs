@@ -0,0 +1,243 @@
+//! Read side of the HTTP API.
+//!
+//! [`OrderSummaryProjection`] keeps one JSON document per order in a
+//! [`ProjectionStore`]; [`ProjectionWorker`] feeds it from the `orders`
+//! topic. The read model is eventually consistent: a `GET` issued right
+//! after a successful `POST` may briefly answer 404.
+
+use crate::orders::{ORDERS_TOPIC, OrderEvent, OrderStatus};
+use composable_rust_core::event::Event;
+use composable_rust_core::event_bus::{EventBus, EventBusError};
+use composable_rust_core::projection::{Projection, ProjectionError, ProjectionStore, Result};
+use composable_rust_runtime::HealthCheck;
+use composable_rust_runtime::health::HealthCheckProvider;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Read model of one order, as returned by `GET /orders/:id`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderSummary {
+    /// Order ID
+    pub id: Uuid,
+    /// Customer who placed the order
+    pub customer: String,
+    /// Order total in cents
+    pub total_cents: u64,
+    /// Current status
+    pub status: OrderStatus,
+}
+
+/// Projection maintaining an [`OrderSummary`] per order
+#[derive(Debug, Clone)]
+pub struct OrderSummaryProjection<P> {
+    store: P,
+}
+
+impl<P: ProjectionStore> OrderSummaryProjection<P> {
+    /// Create a projection persisting summaries to `store`
+    #[must_use]
+    pub const fn new(store: P) -> Self {
+        Self { store }
+    }
+
+    /// The summary of order `id`, if the projection has seen it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails or the summary cannot be decoded.
+    pub async fn summary(&self, id: Uuid) -> Result<Option<OrderSummary>> {
+        self.store
+            .get(&Self::key(id))
+            .await?
+            .map(|data| {
+                serde_json::from_slice(&data)
+                    .map_err(|e| ProjectionError::Serialization(e.to_string()))
+            })
+            .transpose()
+    }
+
+    async fn save(&self, summary: &OrderSummary) -> Result<()> {
+        let data = serde_json::to_vec(summary)
+            .map_err(|e| ProjectionError::Serialization(e.to_string()))?;
+        self.store.save(&Self::key(summary.id), &data).await
+    }
+
+    fn key(id: Uuid) -> String {
+        format!("order_summary:{id}")
+    }
+}
+
+impl<P: ProjectionStore> Projection for OrderSummaryProjection<P> {
+    type Event = OrderEvent;
+
+    fn name(&self) -> &'static str {
+        "order_summaries"
+    }
+
+    async fn apply_event(&self, event: &OrderEvent) -> Result<()> {
+        match event {
+            OrderEvent::Placed {
+                id,
+                customer,
+                total_cents,
+            } => {
+                self.save(&OrderSummary {
+                    id: *id,
+                    customer: customer.clone(),
+                    total_cents: *total_cents,
+                    status: OrderStatus::Placed,
+                })
+                .await
+            },
+            OrderEvent::Cancelled { id } => {
+                let mut summary = self.summary(*id).await?.ok_or_else(|| {
+                    ProjectionError::EventProcessing(format!("Cancelled unknown order {id}"))
+                })?;
+                summary.status = OrderStatus::Cancelled;
+                self.save(&summary).await
+            },
+        }
+    }
+}
+
+/// Progress of a [`ProjectionWorker`], shared with its health check
+#[derive(Debug, Default)]
+struct WorkerStatus {
+    applied: AtomicU64,
+    failed: AtomicU64,
+    last_error: Mutex<Option<String>>,
+    stopped: AtomicBool,
+}
+
+impl WorkerStatus {
+    fn record_failure(&self, error: String) {
+        tracing::warn!(%error, "Order summary projection failed to apply event");
+        self.failed.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut last_error) = self.last_error.lock() {
+            *last_error = Some(error);
+        }
+    }
+}
+
+impl HealthCheckProvider for WorkerStatus {
+    fn check(&self) -> Pin<Box<dyn Future<Output = HealthCheck> + Send + '_>> {
+        let failed = self.failed.load(Ordering::Relaxed);
+        let health = if self.stopped.load(Ordering::Relaxed) {
+            HealthCheck::unhealthy("projection", "Projection worker has stopped")
+        } else if failed > 0 {
+            let last_error = self
+                .last_error
+                .lock()
+                .ok()
+                .and_then(|error| error.clone())
+                .unwrap_or_default();
+            HealthCheck::degraded(
+                "projection",
+                format!("{failed} events failed, last: {last_error}"),
+            )
+        } else {
+            HealthCheck::healthy("projection")
+        };
+
+        let health = health
+            .with_metadata("applied", self.applied.load(Ordering::Relaxed).to_string())
+            .with_metadata("failed", failed.to_string());
+        Box::pin(async move { health })
+    }
+}
+
+/// Background task applying events from the `orders` topic to a projection
+///
+/// Events that fail to decode or apply are logged and counted, and turn the
+/// worker's health check degraded; they do not stop the worker.
+#[derive(Debug)]
+pub struct ProjectionWorker {
+    status: Arc<WorkerStatus>,
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl ProjectionWorker {
+    /// Subscribe to the `orders` topic and start applying its events
+    ///
+    /// The subscription is established before this returns, so no event
+    /// published afterwards is missed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if subscribing fails.
+    pub async fn spawn<P>(
+        event_bus: &Arc<dyn EventBus>,
+        projection: Arc<OrderSummaryProjection<P>>,
+    ) -> std::result::Result<Self, EventBusError>
+    where
+        P: ProjectionStore + 'static,
+    {
+        let mut events = event_bus.subscribe(&[ORDERS_TOPIC]).await?;
+        let status = Arc::new(WorkerStatus::default());
+        let (stop, mut stopped) = oneshot::channel();
+
+        let task = tokio::spawn({
+            let status = Arc::clone(&status);
+            async move {
+                loop {
+                    let event = tokio::select! {
+                        _ = &mut stopped => break,
+                        event = events.next() => event,
+                    };
+                    let Some(event) = event else {
+                        status.stopped.store(true, Ordering::Relaxed);
+                        break;
+                    };
+
+                    let applied = match event {
+                        Ok(event) => match OrderEvent::from_bytes(&event.data) {
+                            Ok(event) => projection
+                                .apply_event(&event)
+                                .await
+                                .map_err(|e| e.to_string()),
+                            Err(error) => Err(error.to_string()),
+                        },
+                        Err(error) => Err(error.to_string()),
+                    };
+                    match applied {
+                        Ok(()) => {
+                            status.applied.fetch_add(1, Ordering::Relaxed);
+                        },
+                        Err(error) => status.record_failure(error),
+                    }
+                }
+            }
+        });
+
+        Ok(Self { status, stop, task })
+    }
+
+    /// Health check reporting whether the worker runs and keeps up
+    #[must_use]
+    pub fn health(&self) -> Arc<dyn HealthCheckProvider> {
+        Arc::clone(&self.status) as Arc<dyn HealthCheckProvider>
+    }
+
+    /// Number of events applied so far
+    #[must_use]
+    pub fn applied(&self) -> u64 {
+        self.status.applied.load(Ordering::Relaxed)
+    }
+
+    /// Stop after the event being applied, if any
+    pub async fn shutdown(self) {
+        let _ = self.stop.send(());
+        if let Err(error) = self.task.await {
+            tracing::error!(%error, "Projection worker task failed");
+        }
+        self.status.stopped.store(true, Ordering::Relaxed);
+    }
+}
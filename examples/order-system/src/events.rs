@@ -0,0 +1,178 @@
+//! Integration events exchanged between the services.
+//!
+//! Each service owns one topic and publishes only its own facts to it;
+//! other services subscribe to the topics they react to. Payloads are JSON
+//! so any consumer can read them without sharing Rust types.
+
+use composable_rust_core::codec::JsonCodec;
+use composable_rust_core::effect::Effect;
+use composable_rust_core::event::{DomainEvent, SerializedEvent};
+use composable_rust_core::outbox::{OutboxStore, append_and_publish};
+use composable_rust_core::stream::{StreamId, Version};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Topic of the order service
+pub const ORDER_EVENTS: &str = "order-events";
+
+/// Topic of the inventory service
+pub const INVENTORY_EVENTS: &str = "inventory-events";
+
+/// Topic of the payment service
+pub const PAYMENT_EVENTS: &str = "payment-events";
+
+/// A customer placed an order; inventory and payment start working on it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderPlaced {
+    /// Order ID
+    pub order_id: Uuid,
+    /// Customer who placed the order
+    pub customer: String,
+    /// Product ordered
+    pub sku: String,
+    /// Units ordered
+    pub quantity: u32,
+    /// Amount to charge in cents
+    pub amount_cents: u64,
+}
+
+impl DomainEvent for OrderPlaced {
+    const NAME: &'static str = "OrderPlaced";
+    type Codec = JsonCodec;
+}
+
+/// The order was paid for and its stock reserved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderConfirmed {
+    /// Order ID
+    pub order_id: Uuid,
+}
+
+impl DomainEvent for OrderConfirmed {
+    const NAME: &'static str = "OrderConfirmed";
+    type Codec = JsonCodec;
+}
+
+/// The order could not be fulfilled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderCancelled {
+    /// Order ID
+    pub order_id: Uuid,
+    /// Why the order was cancelled
+    pub reason: String,
+}
+
+impl DomainEvent for OrderCancelled {
+    const NAME: &'static str = "OrderCancelled";
+    type Codec = JsonCodec;
+}
+
+/// Stock was set aside for the order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StockReserved {
+    /// Order ID
+    pub order_id: Uuid,
+    /// Product reserved
+    pub sku: String,
+    /// Units reserved
+    pub quantity: u32,
+}
+
+impl DomainEvent for StockReserved {
+    const NAME: &'static str = "StockReserved";
+    type Codec = JsonCodec;
+}
+
+/// Not enough stock for the order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StockRejected {
+    /// Order ID
+    pub order_id: Uuid,
+    /// Why stock could not be reserved
+    pub reason: String,
+}
+
+impl DomainEvent for StockRejected {
+    const NAME: &'static str = "StockRejected";
+    type Codec = JsonCodec;
+}
+
+/// A reservation was returned to stock after its order was cancelled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StockReleased {
+    /// Order ID
+    pub order_id: Uuid,
+    /// Product released
+    pub sku: String,
+    /// Units released
+    pub quantity: u32,
+}
+
+impl DomainEvent for StockReleased {
+    const NAME: &'static str = "StockReleased";
+    type Codec = JsonCodec;
+}
+
+/// The customer's payment was authorized
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentAuthorized {
+    /// Order ID
+    pub order_id: Uuid,
+    /// Amount authorized in cents
+    pub amount_cents: u64,
+}
+
+impl DomainEvent for PaymentAuthorized {
+    const NAME: &'static str = "PaymentAuthorized";
+    type Codec = JsonCodec;
+}
+
+/// The customer's payment was declined
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentDeclined {
+    /// Order ID
+    pub order_id: Uuid,
+    /// Why the payment was declined
+    pub reason: String,
+}
+
+impl DomainEvent for PaymentDeclined {
+    const NAME: &'static str = "PaymentDeclined";
+    type Codec = JsonCodec;
+}
+
+/// Append `event` to `stream_id` and enqueue it for `topic` in one write
+///
+/// `version` is the number of events already in the stream, so a decision
+/// recorded twice (e.g. by a retried listener) fails instead of duplicating
+/// the event. `failed` turns an encoding or store error into an action.
+pub(crate) fn record<A, T, F>(
+    outbox: &Arc<dyn OutboxStore>,
+    stream_id: String,
+    version: u64,
+    event: &T,
+    topic: &str,
+    failed: F,
+) -> Effect<A>
+where
+    A: Send + 'static,
+    T: DomainEvent,
+    F: FnOnce(String) -> A + Send + 'static,
+{
+    match SerializedEvent::encode(event, None) {
+        Ok(serialized) => append_and_publish(
+            Arc::clone(outbox),
+            StreamId::new(stream_id),
+            Some(Version::new(version)),
+            vec![serialized],
+            topic,
+            |_| None,
+            move |error| Some(failed(error.to_string())),
+        ),
+        Err(error) => {
+            let error = error.to_string();
+            Effect::Future(Box::pin(async move { Some(failed(error)) }))
+        },
+    }
+}
@@ -0,0 +1,190 @@
+//! Inventory service: reserves stock for placed orders and releases it for
+//! cancelled ones.
+
+use crate::events::{
+    INVENTORY_EVENTS, OrderCancelled, OrderPlaced, StockRejected, StockReleased, StockReserved,
+    record,
+};
+use composable_rust_core::effect::Effect;
+use composable_rust_core::event::EventError;
+use composable_rust_core::event_registry::EventRegistry;
+use composable_rust_core::outbox::OutboxStore;
+use composable_rust_core::reducer::Reducer;
+use composable_rust_core::{SmallVec, smallvec};
+use composable_rust_runtime::Store;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Store type of the inventory service
+pub type InventoryStore =
+    Store<InventoryState, InventoryAction, InventoryEnvironment, InventoryReducer>;
+
+/// What the inventory service decided for one order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reservation {
+    /// Units held for the order
+    Reserved {
+        /// Product reserved
+        sku: String,
+        /// Units reserved
+        quantity: u32,
+    },
+    /// Not enough stock when the order arrived
+    Rejected,
+    /// Units returned to stock after the order was cancelled
+    Released,
+}
+
+/// State of the inventory service
+#[derive(Debug, Clone, Default)]
+pub struct InventoryState {
+    /// Units available by SKU
+    pub stock: HashMap<String, u32>,
+    /// Decision and number of recorded events per order
+    pub reservations: HashMap<Uuid, (Reservation, u64)>,
+}
+
+impl InventoryState {
+    /// Inventory holding `stock` units per SKU
+    #[must_use]
+    pub fn with_stock<'a>(stock: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Self {
+            stock: stock
+                .into_iter()
+                .map(|(sku, units)| (sku.to_string(), units))
+                .collect(),
+            reservations: HashMap::new(),
+        }
+    }
+}
+
+/// Actions of the inventory service
+#[derive(Debug, Clone)]
+pub enum InventoryAction {
+    /// An order needs stock
+    OrderPlaced(OrderPlaced),
+    /// An order was cancelled; its stock can be released
+    OrderCancelled(OrderCancelled),
+    /// Recording an inventory event failed
+    RecordFailed {
+        /// Order the event was about
+        order_id: Uuid,
+        /// Store or encoding error
+        error: String,
+    },
+}
+
+impl InventoryAction {
+    /// Decoder for the events the inventory service listens to
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::DuplicateEventType`] if two registrations clash.
+    pub fn registry() -> Result<EventRegistry<Self>, EventError> {
+        EventRegistry::new()
+            .register(Self::OrderPlaced)?
+            .register(Self::OrderCancelled)
+    }
+}
+
+/// Dependencies of the inventory service
+#[derive(Clone)]
+pub struct InventoryEnvironment {
+    /// Event store recording inventory events together with their outbox rows
+    pub outbox: Arc<dyn OutboxStore>,
+}
+
+impl InventoryEnvironment {
+    fn record<T: composable_rust_core::event::DomainEvent>(
+        &self,
+        order_id: Uuid,
+        version: u64,
+        event: &T,
+    ) -> Effect<InventoryAction> {
+        record(
+            &self.outbox,
+            format!("reservation-{order_id}"),
+            version,
+            event,
+            INVENTORY_EVENTS,
+            move |error| InventoryAction::RecordFailed { order_id, error },
+        )
+    }
+}
+
+/// Reducer of the inventory service
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InventoryReducer;
+
+impl Reducer for InventoryReducer {
+    type State = InventoryState;
+    type Action = InventoryAction;
+    type Environment = InventoryEnvironment;
+
+    fn reduce(
+        &self,
+        state: &mut InventoryState,
+        action: InventoryAction,
+        env: &InventoryEnvironment,
+    ) -> SmallVec<[Effect<InventoryAction>; 4]> {
+        match action {
+            InventoryAction::OrderPlaced(placed) => {
+                let order_id = placed.order_id;
+                // Redelivered order: it was already decided
+                if state.reservations.contains_key(&order_id) {
+                    return smallvec![Effect::None];
+                }
+
+                let available = state.stock.get(&placed.sku).copied().unwrap_or(0);
+                let Some(remaining) = available.checked_sub(placed.quantity) else {
+                    state
+                        .reservations
+                        .insert(order_id, (Reservation::Rejected, 1));
+                    let reason = format!(
+                        "only {available} of {} units of {} in stock",
+                        placed.quantity, placed.sku
+                    );
+                    return smallvec![env.record(order_id, 0, &StockRejected { order_id, reason })];
+                };
+
+                state.stock.insert(placed.sku.clone(), remaining);
+                let reservation = Reservation::Reserved {
+                    sku: placed.sku.clone(),
+                    quantity: placed.quantity,
+                };
+                state.reservations.insert(order_id, (reservation, 1));
+                let reserved = StockReserved {
+                    order_id,
+                    sku: placed.sku,
+                    quantity: placed.quantity,
+                };
+                smallvec![env.record(order_id, 0, &reserved)]
+            },
+            InventoryAction::OrderCancelled(OrderCancelled { order_id, .. }) => {
+                let Some((reservation, events)) = state.reservations.get_mut(&order_id) else {
+                    return smallvec![Effect::None];
+                };
+                let Reservation::Reserved { sku, quantity } = reservation.clone() else {
+                    // Nothing held (rejected) or already released
+                    return smallvec![Effect::None];
+                };
+                *reservation = Reservation::Released;
+
+                *state.stock.entry(sku.clone()).or_default() += quantity;
+                let version = *events;
+                *events += 1;
+                let released = StockReleased {
+                    order_id,
+                    sku,
+                    quantity,
+                };
+                smallvec![env.record(order_id, version, &released)]
+            },
+            InventoryAction::RecordFailed { order_id, error } => {
+                tracing::error!(%order_id, %error, "Failed to record inventory event");
+                smallvec![Effect::None]
+            },
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Order System Example
+//!
+//! Order, Payment and Inventory services that coordinate purely through the
+//! event bus (choreography): no service calls another and no coordinator
+//! knows the whole workflow. Compare with `checkout-saga`, where one saga
+//! drives every step.
+//!
+//! # Architecture
+//!
+//! ```text
+//!                 ┌──────────── order-events ─────────────┐
+//!                 │  OrderPlaced / Confirmed / Cancelled  │
+//!                 ▼                                       │
+//! ┌───────────────────┐  StockReserved   ┌──────────────┐ │
+//! │ Inventory service │────────────────► │   Payment    │ │
+//! │  reserve/release  │  inventory-events│   service    │ │
+//! └─────────┬─────────┘                  └──────┬───────┘ │
+//!           │ StockRejected                     │ PaymentAuthorized
+//!           │                                   │ PaymentDeclined
+//!           ▼                                   ▼ payment-events
+//!         ┌───────────────────────────────────────┐       │
+//!         │            Order service              │───────┘
+//!         └───────────────────────────────────────┘
+//!                             │ order-events
+//!                             ▼
+//!                    ┌─────────────────┐
+//!                    │   Order board   │  (projection)
+//!                    └─────────────────┘
+//! ```
+//!
+//! 1. The order service records `OrderPlaced`
+//! 2. Inventory reserves stock (`StockReserved`) or refuses (`StockRejected`)
+//! 3. Payment charges reserved orders (`PaymentAuthorized` / `PaymentDeclined`)
+//! 4. The order service confirms or cancels; inventory releases the stock of
+//!    cancelled orders it had reserved
+//!
+//! # Key Concepts Demonstrated
+//!
+//! - **Choreography**: each service reacts to facts on the bus
+//! - **Transactional outbox**: every decision is appended together with its
+//!   outbox row and published by an [`OutboxRelay`](composable_rust_runtime::outbox::OutboxRelay)
+//! - **Listeners**: [`EventBusListener`](composable_rust_runtime::listener::EventBusListener)s
+//!   decode the topics a service reacts to with an
+//!   [`EventRegistry`](composable_rust_core::event_registry::EventRegistry)
+//! - **Projections**: a `ProjectionManager` builds the order board
+//! - **At-least-once delivery**: every consumer tolerates redelivered events
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use composable_rust_testing::mocks::InMemoryEventBus;
+//! use order_system::{OrderSystem, SystemConfig};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = SystemConfig::default().with_stock("widget", 10);
+//! let system = OrderSystem::start(InMemoryEventBus::new(), config).await?;
+//!
+//! let order_id = system.place_order("alice", "widget", 2, 2_500).await?;
+//! let view = system.wait_until_settled(order_id, Duration::from_secs(5)).await;
+//! println!("{view:?}");
+//!
+//! system.shutdown(Duration::from_secs(5)).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+/// Integration events and topics
+pub mod events;
+
+/// Inventory service
+pub mod inventory;
+
+/// Order service
+pub mod order;
+
+/// Payment service
+pub mod payment;
+
+/// Order board read model
+pub mod projection;
+
+/// Wiring of services, relays and projection
+pub mod system;
+
+pub use order::OrderStatus;
+pub use projection::{OrderBoard, OrderView};
+pub use system::{OrderSystem, SystemConfig, SystemError};
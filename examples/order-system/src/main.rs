@@ -0,0 +1,59 @@
+//! Order System Example - Runnable Demo
+//!
+//! Places three orders and prints how the services settled each of them:
+//! one confirmed, one cancelled for lack of stock, and one cancelled by
+//! payment after its stock had been reserved (and then released).
+
+use composable_rust_testing::mocks::InMemoryEventBus;
+use order_system::{OrderSystem, SystemConfig};
+use std::time::Duration;
+use tracing::{Level, info};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("Order System Example: choreography over the event bus");
+
+    let config = SystemConfig::default()
+        .with_stock("widget", 5)
+        .with_payment_limit(10_000);
+    let system = OrderSystem::start(InMemoryEventBus::new(), config).await?;
+
+    let orders = [
+        system.place_order("alice", "widget", 2, 2_000).await?,
+        system.place_order("bob", "widget", 10, 9_000).await?,
+        system.place_order("carol", "widget", 1, 50_000).await?,
+    ];
+
+    for order_id in orders {
+        let Some(view) = system
+            .wait_until_settled(order_id, Duration::from_secs(5))
+            .await
+        else {
+            info!(%order_id, "Order did not settle in time");
+            continue;
+        };
+        info!(
+            customer = %view.customer,
+            quantity = view.quantity,
+            amount_cents = view.amount_cents,
+            status = ?view.status,
+            "Order settled"
+        );
+    }
+
+    // Releasing the declined order's stock is the last step of the workflow
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    info!(
+        units = system.stock("widget").await,
+        "Widgets left in stock"
+    );
+
+    system.shutdown(Duration::from_secs(5)).await?;
+    info!("Demo complete");
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! Order service: accepts orders and settles them from the other services' events.
+//!
+//! The order service never calls inventory or payment. It publishes
+//! `OrderPlaced` and waits for their verdicts on the bus: `PaymentAuthorized`
+//! confirms the order, `StockRejected` or `PaymentDeclined` cancels it.
+
+use crate::events::{
+    ORDER_EVENTS, OrderCancelled, OrderConfirmed, OrderPlaced, PaymentAuthorized, PaymentDeclined,
+    StockRejected, record,
+};
+use composable_rust_core::effect::Effect;
+use composable_rust_core::event::EventError;
+use composable_rust_core::event_registry::EventRegistry;
+use composable_rust_core::outbox::OutboxStore;
+use composable_rust_core::reducer::Reducer;
+use composable_rust_core::{SmallVec, smallvec};
+use composable_rust_runtime::Store;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Store type of the order service
+pub type OrderStore = Store<OrderState, OrderAction, OrderEnvironment, OrderReducer>;
+
+/// Lifecycle of an order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    /// Waiting for inventory and payment
+    Pending,
+    /// Stock reserved and payment authorized
+    Confirmed,
+    /// Stock or payment refused
+    Cancelled {
+        /// Why the order was cancelled
+        reason: String,
+    },
+}
+
+/// One order as tracked by the order service
+#[derive(Debug, Clone)]
+pub struct OrderRecord {
+    /// Current status
+    pub status: OrderStatus,
+    /// Events recorded in the order's stream
+    events: u64,
+}
+
+/// State of the order service
+#[derive(Debug, Clone, Default)]
+pub struct OrderState {
+    /// Orders by ID
+    pub orders: HashMap<Uuid, OrderRecord>,
+}
+
+/// Actions of the order service
+#[derive(Debug, Clone)]
+pub enum OrderAction {
+    /// Command: place an order
+    PlaceOrder(OrderPlaced),
+    /// Inventory had no stock for the order
+    StockRejected(StockRejected),
+    /// Payment authorized the order's amount
+    PaymentAuthorized(PaymentAuthorized),
+    /// Payment declined the order's amount
+    PaymentDeclined(PaymentDeclined),
+    /// Recording an order event failed
+    RecordFailed {
+        /// Order the event was about
+        order_id: Uuid,
+        /// Store or encoding error
+        error: String,
+    },
+}
+
+impl OrderAction {
+    /// Decoder for the events the order service listens to
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::DuplicateEventType`] if two registrations clash.
+    pub fn registry() -> Result<EventRegistry<Self>, EventError> {
+        EventRegistry::new()
+            .register(Self::StockRejected)?
+            .register(Self::PaymentAuthorized)?
+            .register(Self::PaymentDeclined)
+    }
+}
+
+/// Dependencies of the order service
+#[derive(Clone)]
+pub struct OrderEnvironment {
+    /// Event store recording order events together with their outbox rows
+    pub outbox: Arc<dyn OutboxStore>,
+}
+
+/// Reducer of the order service
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrderReducer;
+
+impl OrderReducer {
+    /// Move a pending order to `status`, recording `event`
+    fn settle<T: composable_rust_core::event::DomainEvent>(
+        state: &mut OrderState,
+        env: &OrderEnvironment,
+        order_id: Uuid,
+        status: OrderStatus,
+        event: &T,
+    ) -> SmallVec<[Effect<OrderAction>; 4]> {
+        // Verdicts are delivered at least once; only the first one counts
+        let Some(order) = state
+            .orders
+            .get_mut(&order_id)
+            .filter(|order| order.status == OrderStatus::Pending)
+        else {
+            return smallvec![Effect::None];
+        };
+
+        order.status = status;
+        let version = order.events;
+        order.events += 1;
+        smallvec![record(
+            &env.outbox,
+            format!("order-{order_id}"),
+            version,
+            event,
+            ORDER_EVENTS,
+            move |error| OrderAction::RecordFailed { order_id, error },
+        )]
+    }
+}
+
+impl Reducer for OrderReducer {
+    type State = OrderState;
+    type Action = OrderAction;
+    type Environment = OrderEnvironment;
+
+    fn reduce(
+        &self,
+        state: &mut OrderState,
+        action: OrderAction,
+        env: &OrderEnvironment,
+    ) -> SmallVec<[Effect<OrderAction>; 4]> {
+        match action {
+            OrderAction::PlaceOrder(placed) => {
+                let order_id = placed.order_id;
+                if state.orders.contains_key(&order_id) {
+                    return smallvec![Effect::None];
+                }
+
+                state.orders.insert(
+                    order_id,
+                    OrderRecord {
+                        status: OrderStatus::Pending,
+                        events: 1,
+                    },
+                );
+                smallvec![record(
+                    &env.outbox,
+                    format!("order-{order_id}"),
+                    0,
+                    &placed,
+                    ORDER_EVENTS,
+                    move |error| OrderAction::RecordFailed { order_id, error },
+                )]
+            },
+            OrderAction::PaymentAuthorized(authorized) => {
+                let order_id = authorized.order_id;
+                Self::settle(
+                    state,
+                    env,
+                    order_id,
+                    OrderStatus::Confirmed,
+                    &OrderConfirmed { order_id },
+                )
+            },
+            OrderAction::StockRejected(StockRejected { order_id, reason })
+            | OrderAction::PaymentDeclined(PaymentDeclined { order_id, reason }) => Self::settle(
+                state,
+                env,
+                order_id,
+                OrderStatus::Cancelled {
+                    reason: reason.clone(),
+                },
+                &OrderCancelled { order_id, reason },
+            ),
+            OrderAction::RecordFailed { order_id, error } => {
+                tracing::error!(%order_id, %error, "Failed to record order event");
+                smallvec![Effect::None]
+            },
+        }
+    }
+}
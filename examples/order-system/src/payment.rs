@@ -0,0 +1,170 @@
+//! Payment service: charges orders once their stock is reserved.
+//!
+//! The amount comes from `OrderPlaced` and the go-ahead from `StockReserved`,
+//! two topics with no ordering guarantee between them, so the service
+//! decides as soon as it has seen both, whichever arrives first.
+
+use crate::events::{
+    OrderPlaced, PAYMENT_EVENTS, PaymentAuthorized, PaymentDeclined, StockReserved, record,
+};
+use composable_rust_core::effect::Effect;
+use composable_rust_core::event::EventError;
+use composable_rust_core::event_registry::EventRegistry;
+use composable_rust_core::outbox::OutboxStore;
+use composable_rust_core::reducer::Reducer;
+use composable_rust_core::{SmallVec, smallvec};
+use composable_rust_runtime::Store;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Store type of the payment service
+pub type PaymentStore = Store<PaymentState, PaymentAction, PaymentEnvironment, PaymentReducer>;
+
+/// What the payment service knows about one order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaymentRecord {
+    /// Amount to charge, once `OrderPlaced` was seen
+    pub amount_cents: Option<u64>,
+    /// Whether `StockReserved` was seen
+    pub stock_reserved: bool,
+    /// Whether the payment was authorized or declined
+    pub decided: bool,
+}
+
+/// State of the payment service
+#[derive(Debug, Clone, Default)]
+pub struct PaymentState {
+    /// Largest amount authorized for a single order
+    pub limit_cents: u64,
+    /// Payments by order
+    pub payments: HashMap<Uuid, PaymentRecord>,
+}
+
+impl PaymentState {
+    /// Payment service authorizing orders of up to `limit_cents`
+    #[must_use]
+    pub fn with_limit(limit_cents: u64) -> Self {
+        Self {
+            limit_cents,
+            payments: HashMap::new(),
+        }
+    }
+}
+
+/// Actions of the payment service
+#[derive(Debug, Clone)]
+pub enum PaymentAction {
+    /// An order was placed; remember what to charge
+    OrderPlaced(OrderPlaced),
+    /// Stock was reserved; the order can be charged
+    StockReserved(StockReserved),
+    /// Recording a payment event failed
+    RecordFailed {
+        /// Order the event was about
+        order_id: Uuid,
+        /// Store or encoding error
+        error: String,
+    },
+}
+
+impl PaymentAction {
+    /// Decoder for the events the payment service listens to
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::DuplicateEventType`] if two registrations clash.
+    pub fn registry() -> Result<EventRegistry<Self>, EventError> {
+        EventRegistry::new()
+            .register(Self::OrderPlaced)?
+            .register(Self::StockReserved)
+    }
+}
+
+/// Dependencies of the payment service
+#[derive(Clone)]
+pub struct PaymentEnvironment {
+    /// Event store recording payment events together with their outbox rows
+    pub outbox: Arc<dyn OutboxStore>,
+}
+
+/// Reducer of the payment service
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PaymentReducer;
+
+impl PaymentReducer {
+    /// Authorize or decline the order once both its amount and stock are known
+    fn decide(
+        state: &mut PaymentState,
+        env: &PaymentEnvironment,
+        order_id: Uuid,
+    ) -> SmallVec<[Effect<PaymentAction>; 4]> {
+        let limit_cents = state.limit_cents;
+        let Some(payment) = state.payments.get_mut(&order_id) else {
+            return smallvec![Effect::None];
+        };
+        let (Some(amount_cents), true, false) = (
+            payment.amount_cents,
+            payment.stock_reserved,
+            payment.decided,
+        ) else {
+            return smallvec![Effect::None];
+        };
+
+        payment.decided = true;
+        let failed = move |error| PaymentAction::RecordFailed { order_id, error };
+        let stream_id = format!("payment-{order_id}");
+        let effect = if amount_cents <= limit_cents {
+            let authorized = PaymentAuthorized {
+                order_id,
+                amount_cents,
+            };
+            record(
+                &env.outbox,
+                stream_id,
+                0,
+                &authorized,
+                PAYMENT_EVENTS,
+                failed,
+            )
+        } else {
+            let declined = PaymentDeclined {
+                order_id,
+                reason: format!("{amount_cents} cents exceeds the limit of {limit_cents}"),
+            };
+            record(&env.outbox, stream_id, 0, &declined, PAYMENT_EVENTS, failed)
+        };
+        smallvec![effect]
+    }
+}
+
+impl Reducer for PaymentReducer {
+    type State = PaymentState;
+    type Action = PaymentAction;
+    type Environment = PaymentEnvironment;
+
+    fn reduce(
+        &self,
+        state: &mut PaymentState,
+        action: PaymentAction,
+        env: &PaymentEnvironment,
+    ) -> SmallVec<[Effect<PaymentAction>; 4]> {
+        match action {
+            PaymentAction::OrderPlaced(placed) => {
+                let order_id = placed.order_id;
+                state.payments.entry(order_id).or_default().amount_cents =
+                    Some(placed.amount_cents);
+                Self::decide(state, env, order_id)
+            },
+            PaymentAction::StockReserved(reserved) => {
+                let order_id = reserved.order_id;
+                state.payments.entry(order_id).or_default().stock_reserved = true;
+                Self::decide(state, env, order_id)
+            },
+            PaymentAction::RecordFailed { order_id, error } => {
+                tracing::error!(%order_id, %error, "Failed to record payment event");
+                smallvec![Effect::None]
+            },
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Order board: a read model of every order, built from `order-events`.
+//!
+//! The board only reads the order service's topic. Because the order service
+//! turns the inventory and payment verdicts into `OrderConfirmed` and
+//! `OrderCancelled`, that one topic carries each order's full lifecycle.
+
+use crate::events::{OrderCancelled, OrderConfirmed, OrderPlaced};
+use crate::order::OrderStatus;
+use composable_rust_core::event::EventError;
+use composable_rust_core::event_registry::EventRegistry;
+use composable_rust_core::projection::{Projection, ProjectionError, ProjectionStore, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// One order as shown on the board
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderView {
+    /// Order ID
+    pub order_id: Uuid,
+    /// Customer who placed the order
+    pub customer: String,
+    /// Product ordered
+    pub sku: String,
+    /// Units ordered
+    pub quantity: u32,
+    /// Amount in cents
+    pub amount_cents: u64,
+    /// Current status
+    pub status: OrderStatus,
+}
+
+/// Events the board is built from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OrderBoardEvent {
+    /// An order was placed
+    Placed(OrderPlaced),
+    /// An order was confirmed
+    Confirmed(OrderConfirmed),
+    /// An order was cancelled
+    Cancelled(OrderCancelled),
+}
+
+impl OrderBoardEvent {
+    /// Decoder for the events of `order-events`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::DuplicateEventType`] if two registrations clash.
+    pub fn registry() -> std::result::Result<EventRegistry<Self>, EventError> {
+        EventRegistry::new()
+            .register(Self::Placed)?
+            .register(Self::Confirmed)?
+            .register(Self::Cancelled)
+    }
+}
+
+/// Projection keeping one [`OrderView`] per order
+///
+/// Clones share the underlying store when `P` does (as
+/// `InMemoryProjectionStore` does), so one clone can be handed to the
+/// projection manager while another answers queries.
+#[derive(Debug, Clone)]
+pub struct OrderBoard<P> {
+    store: P,
+}
+
+impl<P: ProjectionStore> OrderBoard<P> {
+    /// Create a board persisting views to `store`
+    #[must_use]
+    pub const fn new(store: P) -> Self {
+        Self { store }
+    }
+
+    /// The view of `order_id`, if the board has seen the order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails or the view cannot be decoded.
+    pub async fn view(&self, order_id: Uuid) -> Result<Option<OrderView>> {
+        self.store
+            .get(&Self::key(order_id))
+            .await?
+            .map(|data| {
+                serde_json::from_slice(&data)
+                    .map_err(|e| ProjectionError::Serialization(e.to_string()))
+            })
+            .transpose()
+    }
+
+    async fn save(&self, view: &OrderView) -> Result<()> {
+        let data =
+            serde_json::to_vec(view).map_err(|e| ProjectionError::Serialization(e.to_string()))?;
+        self.store.save(&Self::key(view.order_id), &data).await
+    }
+
+    async fn set_status(&self, order_id: Uuid, status: OrderStatus) -> Result<()> {
+        let mut view = self.view(order_id).await?.ok_or_else(|| {
+            ProjectionError::EventProcessing(format!(
+                "Order {order_id} settled before it was placed"
+            ))
+        })?;
+        view.status = status;
+        self.save(&view).await
+    }
+
+    fn key(order_id: Uuid) -> String {
+        format!("order_board:{order_id}")
+    }
+}
+
+impl<P: ProjectionStore> Projection for OrderBoard<P> {
+    type Event = OrderBoardEvent;
+
+    fn name(&self) -> &'static str {
+        "order_board"
+    }
+
+    async fn apply_event(&self, event: &OrderBoardEvent) -> Result<()> {
+        match event {
+            OrderBoardEvent::Placed(placed) => {
+                // Redelivered: keep the status the order has reached since
+                if self.view(placed.order_id).await?.is_some() {
+                    return Ok(());
+                }
+                self.save(&OrderView {
+                    order_id: placed.order_id,
+                    customer: placed.customer.clone(),
+                    sku: placed.sku.clone(),
+                    quantity: placed.quantity,
+                    amount_cents: placed.amount_cents,
+                    status: OrderStatus::Pending,
+                })
+                .await
+            },
+            OrderBoardEvent::Confirmed(confirmed) => {
+                self.set_status(confirmed.order_id, OrderStatus::Confirmed)
+                    .await
+            },
+            OrderBoardEvent::Cancelled(cancelled) => {
+                let status = OrderStatus::Cancelled {
+                    reason: cancelled.reason.clone(),
+                };
+                self.set_status(cancelled.order_id, status).await
+            },
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//! Wiring of the three services, the outbox relays and the order board.
+//!
+//! Every service runs as its own set of tasks sharing nothing but the bus:
+//!
+//! - a Store with its own event store (the service's outbox)
+//! - an [`EventBusListener`] feeding the topics it reacts to into the Store
+//! - an [`OutboxRelay`] publishing what the Store recorded
+//!
+//! Relays only start once every consumer has subscribed. Until then the
+//! services' decisions wait in their outboxes, so nothing published during
+//! startup is lost.
+
+use crate::events::{INVENTORY_EVENTS, ORDER_EVENTS, OrderPlaced, PAYMENT_EVENTS};
+use crate::inventory::{
+    InventoryAction, InventoryEnvironment, InventoryReducer, InventoryState, InventoryStore,
+};
+use crate::order::{
+    OrderAction, OrderEnvironment, OrderReducer, OrderState, OrderStatus, OrderStore,
+};
+use crate::payment::{
+    PaymentAction, PaymentEnvironment, PaymentReducer, PaymentState, PaymentStore,
+};
+use crate::projection::{OrderBoard, OrderBoardEvent, OrderView};
+use composable_rust_core::event::EventError;
+use composable_rust_core::event_bus::EventBus;
+use composable_rust_core::outbox::OutboxStore;
+use composable_rust_projections::ProjectionManager;
+use composable_rust_runtime::listener::{EventBusListener, ListenerHandle};
+use composable_rust_runtime::outbox::OutboxRelay;
+use composable_rust_runtime::{Store, StoreError};
+use composable_rust_testing::mocks::{InMemoryEventBus, InMemoryEventStore};
+use composable_rust_testing::{InMemoryProjectionCheckpoint, InMemoryProjectionStore};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Subscribers each topic has once the system is running
+const SUBSCRIBERS: [(&str, usize); 3] = [
+    // Inventory, payment and the order board
+    (ORDER_EVENTS, 3),
+    // Payment and order
+    (INVENTORY_EVENTS, 2),
+    // Order
+    (PAYMENT_EVENTS, 1),
+];
+
+/// How long startup waits for every consumer to subscribe
+const SUBSCRIBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Errors starting the order system
+#[derive(Error, Debug)]
+pub enum SystemError {
+    /// An event registry could not be built
+    #[error("Invalid event registry: {0}")]
+    Registry(#[from] EventError),
+
+    /// A consumer did not subscribe in time
+    #[error("Topic {topic} has {subscribed} of {expected} subscribers")]
+    NotSubscribed {
+        /// Topic missing subscribers
+        topic: &'static str,
+        /// Subscribers present when startup gave up
+        subscribed: usize,
+        /// Subscribers expected
+        expected: usize,
+    },
+}
+
+/// Catalog and policies the system starts with
+#[derive(Debug, Clone)]
+pub struct SystemConfig {
+    stock: Vec<(String, u32)>,
+    payment_limit_cents: u64,
+    relay_interval: Duration,
+}
+
+impl Default for SystemConfig {
+    fn default() -> Self {
+        Self {
+            stock: Vec::new(),
+            payment_limit_cents: 100_000,
+            relay_interval: Duration::from_millis(20),
+        }
+    }
+}
+
+impl SystemConfig {
+    /// Stock `units` of `sku`
+    #[must_use]
+    pub fn with_stock(mut self, sku: impl Into<String>, units: u32) -> Self {
+        self.stock.push((sku.into(), units));
+        self
+    }
+
+    /// Decline orders above `limit_cents` (1000.00 by default)
+    #[must_use]
+    pub const fn with_payment_limit(mut self, limit_cents: u64) -> Self {
+        self.payment_limit_cents = limit_cents;
+        self
+    }
+
+    /// Poll the outboxes every `interval` (20ms by default)
+    #[must_use]
+    pub const fn with_relay_interval(mut self, interval: Duration) -> Self {
+        self.relay_interval = interval;
+        self
+    }
+}
+
+/// The running order, payment and inventory services
+pub struct OrderSystem {
+    orders: OrderStore,
+    inventory: InventoryStore,
+    payments: PaymentStore,
+    board: OrderBoard<InMemoryProjectionStore>,
+    listeners: Vec<ListenerHandle>,
+    relays: Vec<JoinHandle<u64>>,
+    stop_relays: watch::Sender<bool>,
+    projection: JoinHandle<()>,
+    stop_projection: watch::Sender<bool>,
+}
+
+impl OrderSystem {
+    /// Start every service on `bus`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an event registry is invalid or a consumer does
+    /// not subscribe within 5 seconds.
+    pub async fn start(bus: InMemoryEventBus, config: SystemConfig) -> Result<Self, SystemError> {
+        let shared: Arc<dyn EventBus> = Arc::new(bus.clone());
+        let outboxes: [Arc<dyn OutboxStore>; 3] = [
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemoryEventStore::new()),
+        ];
+        let [order_outbox, inventory_outbox, payment_outbox] = outboxes.clone();
+
+        let orders = Store::new(
+            OrderState::default(),
+            OrderReducer,
+            OrderEnvironment {
+                outbox: order_outbox,
+            },
+        );
+        let stock = config
+            .stock
+            .iter()
+            .map(|(sku, units)| (sku.as_str(), *units));
+        let inventory = Store::new(
+            InventoryState::with_stock(stock),
+            InventoryReducer,
+            InventoryEnvironment {
+                outbox: inventory_outbox,
+            },
+        );
+        let payments = Store::new(
+            PaymentState::with_limit(config.payment_limit_cents),
+            PaymentReducer,
+            PaymentEnvironment {
+                outbox: payment_outbox,
+            },
+        );
+
+        let listeners = vec![
+            EventBusListener::with_registry(
+                Arc::clone(&shared),
+                orders.clone(),
+                &[INVENTORY_EVENTS, PAYMENT_EVENTS],
+                OrderAction::registry()?,
+            )
+            .spawn(),
+            EventBusListener::with_registry(
+                Arc::clone(&shared),
+                inventory.clone(),
+                &[ORDER_EVENTS],
+                InventoryAction::registry()?,
+            )
+            .spawn(),
+            EventBusListener::with_registry(
+                Arc::clone(&shared),
+                payments.clone(),
+                &[ORDER_EVENTS, INVENTORY_EVENTS],
+                PaymentAction::registry()?,
+            )
+            .spawn(),
+        ];
+
+        let board = OrderBoard::new(InMemoryProjectionStore::new());
+        let (manager, stop_projection) = ProjectionManager::new(
+            board.clone(),
+            Arc::clone(&shared),
+            Arc::new(InMemoryProjectionCheckpoint::new()),
+            ORDER_EVENTS,
+            "order-board",
+        );
+        let mut manager = manager.with_registry(OrderBoardEvent::registry()?);
+        let projection = tokio::spawn(async move {
+            if let Err(error) = manager.start().await {
+                tracing::error!(%error, "Order board stopped");
+            }
+        });
+
+        wait_for_subscribers(&bus).await?;
+
+        let (stop_relays, stopped) = watch::channel(false);
+        let relays = outboxes
+            .into_iter()
+            .map(|outbox| {
+                let relay = OutboxRelay::new(outbox, Arc::clone(&shared))
+                    .with_poll_interval(config.relay_interval);
+                let mut stopped = stopped.clone();
+                tokio::spawn(relay.run_until(async move {
+                    let _ = stopped.wait_for(|stop| *stop).await;
+                }))
+            })
+            .collect();
+
+        Ok(Self {
+            orders,
+            inventory,
+            payments,
+            board,
+            listeners,
+            relays,
+            stop_relays,
+            projection,
+            stop_projection,
+        })
+    }
+
+    /// Place an order, returning its ID
+    ///
+    /// The order is settled asynchronously; see [`Self::wait_until_settled`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the order service is shutting down.
+    pub async fn place_order(
+        &self,
+        customer: &str,
+        sku: &str,
+        quantity: u32,
+        amount_cents: u64,
+    ) -> Result<Uuid, StoreError> {
+        let order_id = Uuid::new_v4();
+        let placed = OrderPlaced {
+            order_id,
+            customer: customer.to_string(),
+            sku: sku.to_string(),
+            quantity,
+            amount_cents,
+        };
+        self.orders.send(OrderAction::PlaceOrder(placed)).await?;
+        Ok(order_id)
+    }
+
+    /// Wait until the order board shows `order_id` confirmed or cancelled
+    ///
+    /// Returns `None` if that does not happen within `timeout`.
+    pub async fn wait_until_settled(&self, order_id: Uuid, timeout: Duration) -> Option<OrderView> {
+        tokio::time::timeout(timeout, async {
+            loop {
+                match self.board.view(order_id).await {
+                    Ok(Some(view)) if view.status != OrderStatus::Pending => return view,
+                    Ok(_) => {},
+                    Err(error) => tracing::warn!(%error, "Order board query failed"),
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .ok()
+    }
+
+    /// Units of `sku` the inventory service has available
+    pub async fn stock(&self, sku: &str) -> u32 {
+        self.inventory
+            .state(|state| state.stock.get(sku).copied().unwrap_or(0))
+            .await
+    }
+
+    /// The order board read model
+    #[must_use]
+    pub const fn board(&self) -> &OrderBoard<InMemoryProjectionStore> {
+        &self.board
+    }
+
+    /// Stop consuming, drain the stores, then stop the relays and the board
+    ///
+    /// Events recorded but not yet relayed stay in the outboxes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first store that failed to drain within `timeout`.
+    pub async fn shutdown(self, timeout: Duration) -> Result<(), StoreError> {
+        stop_listeners(self.listeners).await;
+
+        let drained = [
+            self.orders.shutdown(timeout).await,
+            self.inventory.shutdown(timeout).await,
+            self.payments.shutdown(timeout).await,
+        ];
+
+        let _ = self.stop_relays.send(true);
+        join_relays(self.relays).await;
+
+        let _ = self.stop_projection.send(true);
+        if let Err(error) = self.projection.await {
+            tracing::warn!(%error, "Order board task failed");
+        }
+
+        drained.into_iter().collect()
+    }
+}
+
+/// Stop feeding bus events into the stores
+async fn stop_listeners(listeners: Vec<ListenerHandle>) {
+    for listener in listeners {
+        if let Err(error) = listener.shutdown().await {
+            tracing::warn!(%error, "Listener stopped with an error");
+        }
+    }
+}
+
+/// Wait for the (already signalled) outbox relays to finish
+async fn join_relays(relays: Vec<JoinHandle<u64>>) {
+    for relay in relays {
+        if let Err(error) = relay.await {
+            tracing::warn!(%error, "Outbox relay task failed");
+        }
+    }
+}
+
+/// Wait until every consumer listed in [`SUBSCRIBERS`] has subscribed
+async fn wait_for_subscribers(bus: &InMemoryEventBus) -> Result<(), SystemError> {
+    let deadline = tokio::time::Instant::now() + SUBSCRIBE_TIMEOUT;
+    for (topic, expected) in SUBSCRIBERS {
+        loop {
+            let subscribed = bus.subscriber_count(topic);
+            if subscribed >= expected {
+                break;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(SystemError::NotSubscribed {
+                    topic,
+                    subscribed,
+                    expected,
+                });
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+
+    const SETTLE: Duration = Duration::from_secs(5);
+
+    #[tokio::test]
+    async fn test_orders_settle_through_choreography() {
+        let config = SystemConfig::default()
+            .with_stock("widget", 5)
+            .with_payment_limit(10_000);
+        let system = OrderSystem::start(InMemoryEventBus::new(), config)
+            .await
+            .unwrap();
+
+        let confirmed = system
+            .place_order("alice", "widget", 2, 2_000)
+            .await
+            .unwrap();
+        let view = system.wait_until_settled(confirmed, SETTLE).await.unwrap();
+        assert_eq!(view.status, OrderStatus::Confirmed);
+        assert_eq!(view.customer, "alice");
+        assert_eq!(system.stock("widget").await, 3);
+
+        let out_of_stock = system.place_order("bob", "widget", 4, 4_000).await.unwrap();
+        let view = system
+            .wait_until_settled(out_of_stock, SETTLE)
+            .await
+            .unwrap();
+        assert!(matches!(view.status, OrderStatus::Cancelled { .. }));
+        assert_eq!(system.stock("widget").await, 3);
+
+        // Stock is reserved before payment declines, then released
+        let declined = system
+            .place_order("carol", "widget", 1, 50_000)
+            .await
+            .unwrap();
+        let view = system.wait_until_settled(declined, SETTLE).await.unwrap();
+        let OrderStatus::Cancelled { reason } = view.status else {
+            unreachable!("over-limit order was {:?}", view.status);
+        };
+        assert!(reason.contains("exceeds the limit"), "{reason}");
+        let mut stock = 0;
+        for _ in 0..100 {
+            stock = system.stock("widget").await;
+            if stock == 3 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(stock, 3, "declined order's stock is released");
+
+        system.shutdown(Duration::from_secs(1)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_redelivered_order_is_reserved_once() {
+        let bus = InMemoryEventBus::new();
+        let config = SystemConfig::default().with_stock("widget", 5);
+        let system = OrderSystem::start(bus.clone(), config).await.unwrap();
+
+        let order_id = system
+            .place_order("dave", "widget", 2, 1_000)
+            .await
+            .unwrap();
+        system.wait_until_settled(order_id, SETTLE).await.unwrap();
+
+        // At-least-once delivery: the relay may publish the same entry again
+        let placed = bus.history(ORDER_EVENTS)[0].clone();
+        assert!(placed.is::<OrderPlaced>());
+        bus.publish(ORDER_EVENTS, &placed).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(system.stock("widget").await, 3);
+        let view = system.board().view(order_id).await.unwrap().unwrap();
+        assert_eq!(view.status, OrderStatus::Confirmed);
+
+        system.shutdown(Duration::from_secs(1)).await.unwrap();
+    }
+}
@@ -12,6 +12,7 @@ use composable_rust_agent_patterns::security::SecurityMonitor;
 use composable_rust_core::{append_events, async_effect};
 use composable_rust_core::effect::Effect;
 use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::event_bus::PublishOptions;
 use composable_rust_core::reducer::Reducer;
 use composable_rust_core::stream::{StreamId, Version};
 use smallvec::smallvec;
@@ -402,6 +403,7 @@ impl<A: AuditLogger + Send + Sync + 'static> Reducer for ProductionAgentReducer<
                     event_bus: Arc::clone(env.event_bus()),
                     topic: "agent-events".to_string(),
                     event: serialized_event,
+                    options: PublishOptions::default(),
                     on_success: Box::new(|()| {
                         info!("Event published to event bus successfully");
                         None
@@ -13,6 +13,7 @@ fn test_bincode_serialization_with_metadata() {
         user_id: Some("user123".to_string()),
         timestamp: Some("2025-11-16T09:00:00Z".to_string()),
         causation_id: None,
+        ..EventMetadata::default()
     };
 
     let event = SerializedEvent {
@@ -47,6 +47,7 @@ fn test_event_metadata_with_correlation_id() {
         user_id: Some("test-user-123".to_string()),
         timestamp: Some(chrono::Utc::now().to_rfc3339()),
         causation_id: None,
+        ..EventMetadata::default()
     };
 
     assert_eq!(metadata.correlation_id, Some(correlation_id.clone()));
@@ -233,6 +234,7 @@ fn test_correlation_id_uuid_format() {
             user_id: None,
             timestamp: None,
             causation_id: None,
+            ..EventMetadata::default()
         };
 
         assert_eq!(metadata.correlation_id, Some(correlation_id.to_string()));
@@ -29,6 +29,7 @@ fn test_serialized_event_with_metadata_bincode() {
         causation_id: None,
         user_id: None,
         timestamp: None,
+        ..EventMetadata::default()
     });
 
     let serialized_event = SerializedEvent::new(
@@ -7,6 +7,9 @@
 //!
 //! - `#[derive(Action)]` - Generates helpers for action enums (commands/events)
 //! - `#[derive(State)]` - Generates common state traits and helpers
+//! - `#[derive(ComposableAction)]` - Generates embed/extract prisms for child actions
+//! - `#[derive(EnvironmentAccess)]` - Generates field accessors for child environments
+//! - `#[derive(DomainEvent)]` - Registers an event type's name, version and codec
 //!
 //! # Example
 //!
@@ -279,6 +282,319 @@ pub fn derive_state(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Derive macro for parent action enums wrapping child actions
+///
+/// For every tuple variant with one or two fields, generates a
+/// `<variant>_prism()` constructor returning an
+/// `ActionPrism` for use with `pullback` and `for_each` (from
+/// `composable_rust_core::composition`). Single-field variants also get a
+/// `From<Child>` impl, so child actions can be embedded with `.into()`.
+///
+/// Two-field variants are keyed: their prism extracts `(key, child)` pairs,
+/// the shape `for_each` expects.
+///
+/// # Attributes
+///
+/// - `#[composable(skip)]` - Generate nothing for this variant (e.g. when two
+///   variants wrap the same type, which would produce conflicting `From` impls)
+///
+/// # Panics
+///
+/// This macro will produce a compile error (not a runtime panic) if:
+/// - Applied to a non-enum type
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(ComposableAction, Clone, Debug)]
+/// enum AppAction {
+///     Counter(CounterAction),
+///     Order(OrderId, OrderAction),
+///     Reset,
+/// }
+///
+/// let counter = pullback(
+///     CounterReducer,
+///     |state: &mut AppState| &mut state.counter,
+///     AppAction::counter_prism(),
+///     AppEnvironment::counter,
+/// );
+/// let orders = for_each(
+///     OrderReducer,
+///     |state: &mut AppState| &mut state.orders,
+///     AppAction::order_prism(),
+///     AppEnvironment::orders,
+/// );
+///
+/// let action: AppAction = CounterAction::Increment.into();
+/// ```
+#[proc_macro_derive(ComposableAction, attributes(composable))]
+pub fn derive_composable_action(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Enum(data_enum) = &input.data else {
+        return syn::Error::new_spanned(
+            input,
+            "#[derive(ComposableAction)] can only be used on enums"
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    let mut prisms = Vec::new();
+    let mut from_impls = Vec::new();
+
+    for variant in &data_enum.variants {
+        if is_skipped(&variant.attrs) {
+            continue;
+        }
+        let Fields::Unnamed(fields) = &variant.fields else {
+            continue;
+        };
+        let variant_name = &variant.ident;
+        let prism_name = quote::format_ident!("{}_prism", to_snake_case(&variant_name.to_string()));
+        let types: Vec<_> = fields.unnamed.iter().map(|field| &field.ty).collect();
+
+        match types.as_slice() {
+            [child] => {
+                let doc = format!("Prism between `{name}` and the `{variant_name}` child action");
+                prisms.push(quote! {
+                    #[doc = #doc]
+                    #[must_use]
+                    pub fn #prism_name() -> composable_rust_core::composition::ActionPrism<Self, #child> {
+                        composable_rust_core::composition::ActionPrism::new(
+                            |action| match action {
+                                Self::#variant_name(child) => Some(child),
+                                #[allow(unreachable_patterns)]
+                                _ => None,
+                            },
+                            Self::#variant_name,
+                        )
+                    }
+                });
+                from_impls.push(quote! {
+                    impl #impl_generics From<#child> for #name #ty_generics #where_clause {
+                        fn from(action: #child) -> Self {
+                            Self::#variant_name(action)
+                        }
+                    }
+                });
+            },
+            [key, child] => {
+                let doc = format!("Prism between `{name}` and keyed `{variant_name}` child actions");
+                prisms.push(quote! {
+                    #[doc = #doc]
+                    #[must_use]
+                    pub fn #prism_name() -> composable_rust_core::composition::ActionPrism<Self, (#key, #child)> {
+                        composable_rust_core::composition::ActionPrism::new(
+                            |action| match action {
+                                Self::#variant_name(key, child) => Some((key, child)),
+                                #[allow(unreachable_patterns)]
+                                _ => None,
+                            },
+                            |(key, child)| Self::#variant_name(key, child),
+                        )
+                    }
+                });
+            },
+            _ => {},
+        }
+    }
+
+    let expanded = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#prisms)*
+        }
+
+        #(#from_impls)*
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Derive macro for environment structs composed of child environments
+///
+/// Generates a `const fn` accessor per named field returning a reference to
+/// it. Accessors coerce to `fn(&Parent) -> &Child`, the environment mapping
+/// taken by `pullback` and `for_each`.
+///
+/// # Attributes
+///
+/// - `#[composable(skip)]` - Generate no accessor for this field
+///
+/// # Panics
+///
+/// This macro will produce a compile error (not a runtime panic) if:
+/// - Applied to anything but a struct with named fields
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(EnvironmentAccess, Clone)]
+/// struct AppEnvironment {
+///     counter: CounterEnvironment,
+///     orders: OrderEnvironment,
+/// }
+///
+/// let counter = pullback(
+///     CounterReducer,
+///     |state: &mut AppState| &mut state.counter,
+///     AppAction::counter_prism(),
+///     AppEnvironment::counter,
+/// );
+/// ```
+#[proc_macro_derive(EnvironmentAccess, attributes(composable))]
+pub fn derive_environment_access(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Struct(syn::DataStruct { fields: Fields::Named(fields), .. }) = &input.data else {
+        return syn::Error::new_spanned(
+            input,
+            "#[derive(EnvironmentAccess)] can only be used on structs with named fields"
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    let accessors = fields
+        .named
+        .iter()
+        .filter(|field| !is_skipped(&field.attrs))
+        .filter_map(|field| {
+            let field_name = field.ident.as_ref()?;
+            let ty = &field.ty;
+            let doc = format!("The `{field_name}` environment");
+            Some(quote! {
+                #[doc = #doc]
+                #[must_use]
+                pub const fn #field_name(&self) -> &#ty {
+                    &self.#field_name
+                }
+            })
+        });
+
+    let expanded = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#accessors)*
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Derive macro for event payload types
+///
+/// Implements `composable_rust_core::event::DomainEvent`, so the type can be
+/// encoded with `SerializedEvent::encode`, decoded with
+/// `SerializedEvent::decode` and registered in an `EventRegistry`.
+///
+/// # Attributes
+///
+/// `#[event(...)]` on the type, all keys optional:
+///
+/// - `name = "OrderPlaced"` - Event name (defaults to the type name)
+/// - `version = 2` - Schema version (defaults to 1)
+/// - `codec = "json"` - `"bincode"` (default), `"json"`, `"protobuf"`, or the
+///   path of any `EventCodec` type
+///
+/// # Panics
+///
+/// This macro will produce a compile error (not a runtime panic) if:
+/// - An `#[event]` key is unknown or its value has the wrong type
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(DomainEvent, Serialize, Deserialize)]
+/// #[event(name = "OrderPlaced", version = 2, codec = "json")]
+/// struct OrderPlacedV2 {
+///     order_id: String,
+///     total_cents: u64,
+/// }
+///
+/// assert_eq!(OrderPlacedV2::event_type(), "OrderPlaced.v2");
+///
+/// let registry = EventRegistry::new()
+///     .register(OrderAction::OrderPlaced)?;
+/// ```
+#[proc_macro_derive(DomainEvent, attributes(event))]
+pub fn derive_domain_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut event_name = name.to_string();
+    let mut version: i32 = 1;
+    let mut codec = quote! { composable_rust_core::codec::BincodeCodec };
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("event")) {
+        let parsed = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                event_name = meta.value()?.parse::<syn::LitStr>()?.value();
+            } else if meta.path.is_ident("version") {
+                version = meta.value()?.parse::<syn::LitInt>()?.base10_parse()?;
+            } else if meta.path.is_ident("codec") {
+                let value = meta.value()?.parse::<syn::LitStr>()?;
+                codec = match value.value().as_str() {
+                    "bincode" => quote! { composable_rust_core::codec::BincodeCodec },
+                    "json" => quote! { composable_rust_core::codec::JsonCodec },
+                    "protobuf" => quote! { composable_rust_core::codec::ProstCodec },
+                    _ => {
+                        let path = value.parse::<syn::Path>()?;
+                        quote! { #path }
+                    },
+                };
+            } else {
+                return Err(meta.error("expected `name`, `version` or `codec`"));
+            }
+            Ok(())
+        });
+        if let Err(error) = parsed {
+            return error.to_compile_error().into();
+        }
+    }
+
+    let expanded = quote! {
+        impl #impl_generics composable_rust_core::event::DomainEvent for #name #ty_generics #where_clause {
+            const NAME: &'static str = #event_name;
+            const VERSION: i32 = #version;
+            type Codec = #codec;
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Helper function to check for `#[composable(skip)]`
+fn is_skipped(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("composable")
+            && attr
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|arg| arg == "skip")
+    })
+}
+
+/// Convert a `PascalCase` variant name to `snake_case`
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
 /// Helper function to check if an attribute list contains a specific attribute
 fn has_attribute(attrs: &[Attribute], name: &str) -> bool {
     attrs.iter().any(|attr| {
@@ -0,0 +1,115 @@
+//! Tests for #[derive(ComposableAction)] and #[derive(EnvironmentAccess)]
+
+use composable_rust_core::composition::{for_each, pullback};
+use composable_rust_core::effect::Effect;
+use composable_rust_core::reducer::Reducer;
+use composable_rust_core::{smallvec, SmallVec};
+use composable_rust_macros::{ComposableAction, EnvironmentAccess};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+enum CounterAction {
+    Add(i32),
+}
+
+#[derive(ComposableAction, Clone, Debug, PartialEq)]
+enum AppAction {
+    Counter(CounterAction),
+    LineItem(u32, CounterAction),
+    #[composable(skip)]
+    #[allow(dead_code)] // Skipped variant only checks that no conflicting `From` is generated
+    Mirror(CounterAction),
+    Reset,
+}
+
+#[derive(EnvironmentAccess)]
+struct AppEnvironment {
+    counter: i32,
+    line_items: i32,
+    #[composable(skip)]
+    #[allow(dead_code)] // Skipped field has no accessor
+    secret: String,
+}
+
+#[derive(Default)]
+struct AppState {
+    counter: i32,
+    line_items: HashMap<u32, i32>,
+}
+
+/// Adds to the count, and echoes the environment value back as a delayed action
+struct CounterReducer;
+
+impl Reducer for CounterReducer {
+    type State = i32;
+    type Action = CounterAction;
+    type Environment = i32;
+
+    fn reduce(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        env: &Self::Environment,
+    ) -> SmallVec<[Effect<Self::Action>; 4]> {
+        let CounterAction::Add(n) = action;
+        *state += n;
+        smallvec![Effect::Delay {
+            duration: std::time::Duration::from_secs(1),
+            action: Box::new(CounterAction::Add(*env)),
+        }]
+    }
+}
+
+fn delayed(mut effects: SmallVec<[Effect<AppAction>; 4]>) -> Option<AppAction> {
+    match effects.pop()? {
+        Effect::Delay { action, .. } => Some(*action),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_prism_and_from() {
+    let prism = AppAction::counter_prism();
+
+    assert_eq!(prism.extract(AppAction::Counter(CounterAction::Add(1))), Some(CounterAction::Add(1)));
+    assert_eq!(prism.extract(AppAction::Reset), None);
+    assert_eq!(AppAction::from(CounterAction::Add(2)), AppAction::Counter(CounterAction::Add(2)));
+
+    let keyed = AppAction::line_item_prism();
+    assert_eq!(
+        keyed.extract(AppAction::LineItem(3, CounterAction::Add(1))),
+        Some((3, CounterAction::Add(1)))
+    );
+    assert_eq!(keyed.embed((4, CounterAction::Add(1))), AppAction::LineItem(4, CounterAction::Add(1)));
+}
+
+#[test]
+fn test_derived_items_drive_combinators() {
+    let env = AppEnvironment {
+        counter: 10,
+        line_items: 20,
+        secret: String::from("unused"),
+    };
+    let mut state = AppState::default();
+    state.line_items.insert(1, 0);
+
+    let counter = pullback(
+        CounterReducer,
+        |state: &mut AppState| &mut state.counter,
+        AppAction::counter_prism(),
+        AppEnvironment::counter,
+    );
+    let effects = counter.reduce(&mut state, CounterAction::Add(5).into(), &env);
+    assert_eq!(state.counter, 5);
+    assert_eq!(delayed(effects), Some(AppAction::Counter(CounterAction::Add(10))));
+
+    let line_items = for_each(
+        CounterReducer,
+        |state: &mut AppState| &mut state.line_items,
+        AppAction::line_item_prism(),
+        AppEnvironment::line_items,
+    );
+    let effects = line_items.reduce(&mut state, AppAction::LineItem(1, CounterAction::Add(2)), &env);
+    assert_eq!(state.line_items[&1], 2);
+    assert_eq!(delayed(effects), Some(AppAction::LineItem(1, CounterAction::Add(20))));
+}
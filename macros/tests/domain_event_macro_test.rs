@@ -0,0 +1,78 @@
+//! Tests for #[derive(DomainEvent)] macro
+
+use composable_rust_core::codec::{BincodeCodec, EventCodec};
+use composable_rust_core::event::{DomainEvent, SerializedEvent};
+use composable_rust_core::event_registry::EventRegistry;
+use composable_rust_macros::DomainEvent;
+use serde::{Deserialize, Serialize};
+
+#[derive(DomainEvent, Serialize, Deserialize, Debug, PartialEq)]
+#[event(name = "OrderPlaced", version = 2, codec = "json")]
+struct OrderPlacedV2 {
+    order_id: String,
+}
+
+#[derive(DomainEvent, Serialize, Deserialize, Debug, PartialEq)]
+struct OrderShipped {
+    order_id: String,
+}
+
+#[derive(DomainEvent, Serialize, Deserialize, Debug, PartialEq)]
+#[event(codec = "composable_rust_core::codec::JsonCodec")]
+struct OrderCancelled {
+    order_id: String,
+}
+
+#[derive(Debug, PartialEq)]
+enum OrderEvent {
+    Placed(OrderPlacedV2),
+    Shipped(OrderShipped),
+}
+
+#[test]
+fn test_attributes_set_name_version_and_codec() {
+    assert_eq!(OrderPlacedV2::event_type(), "OrderPlaced.v2");
+    assert_eq!(
+        <<OrderPlacedV2 as DomainEvent>::Codec as EventCodec<OrderPlacedV2>>::CONTENT_TYPE,
+        "application/json"
+    );
+    assert_eq!(OrderCancelled::event_type(), "OrderCancelled.v1");
+}
+
+#[test]
+fn test_defaults_to_type_name_version_one_and_bincode() {
+    assert_eq!(OrderShipped::event_type(), "OrderShipped.v1");
+    assert_eq!(
+        <<OrderShipped as DomainEvent>::Codec as EventCodec<OrderShipped>>::CONTENT_TYPE,
+        <BincodeCodec as EventCodec<OrderShipped>>::CONTENT_TYPE
+    );
+}
+
+#[test]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+fn test_derived_events_decode_through_registry() {
+    let registry = EventRegistry::new()
+        .register(OrderEvent::Placed)
+        .unwrap()
+        .register(OrderEvent::Shipped)
+        .unwrap();
+
+    let placed = OrderPlacedV2 {
+        order_id: "o-1".to_string(),
+    };
+    let serialized = SerializedEvent::encode(&placed, None).unwrap();
+    assert_eq!(serialized.data, br#"{"order_id":"o-1"}"#);
+    assert_eq!(
+        registry.decode(&serialized).unwrap(),
+        Some(OrderEvent::Placed(placed))
+    );
+
+    let cancelled = SerializedEvent::encode(
+        &OrderCancelled {
+            order_id: "o-1".to_string(),
+        },
+        None,
+    )
+    .unwrap();
+    assert_eq!(registry.decode(&cancelled).unwrap(), None);
+}
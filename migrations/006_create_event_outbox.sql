@@ -0,0 +1,27 @@
+-- Create event_outbox table for the transactional outbox pattern
+--
+-- Rows are inserted in the same transaction as the events they reference, so an
+-- event is never stored without being queued for publishing. A relay publishes
+-- pending rows in sequence order and then sets published_at (at-least-once).
+
+CREATE TABLE IF NOT EXISTS event_outbox (
+    sequence BIGSERIAL PRIMARY KEY,     -- Relay order
+    stream_id TEXT NOT NULL,
+    version BIGINT NOT NULL,
+    topic TEXT NOT NULL,                -- Event bus topic to publish to
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    published_at TIMESTAMPTZ,           -- NULL until relayed
+
+    FOREIGN KEY (stream_id, version) REFERENCES events (stream_id, version)
+);
+
+-- Index for the relay's pending scan
+CREATE INDEX IF NOT EXISTS idx_event_outbox_pending
+    ON event_outbox(sequence)
+    WHERE published_at IS NULL;
+
+-- Comments for documentation
+COMMENT ON TABLE event_outbox IS 'Transactional outbox: events queued for publishing to the event bus';
+COMMENT ON COLUMN event_outbox.sequence IS 'Monotonic position used as relay order';
+COMMENT ON COLUMN event_outbox.topic IS 'Event bus topic the event is published to';
+COMMENT ON COLUMN event_outbox.published_at IS 'When the relay published the event (NULL = pending)';
@@ -8,6 +8,7 @@
 //! - State snapshots for performance
 //! - Connection pooling
 //! - Transaction support
+//! - Transactional outbox ([`OutboxStore`](composable_rust_core::outbox::OutboxStore))
 //!
 //! # Example
 //!
@@ -24,14 +25,17 @@
 #![warn(missing_docs)]
 
 mod dead_letter_queue;
+mod outbox;
 
 pub use dead_letter_queue::{DLQStatus, DeadLetterQueue, FailedEvent};
 
 use composable_rust_core::event::{EventMetadata, SerializedEvent};
-use composable_rust_core::event_store::{BatchAppend, EventStore, EventStoreError};
+use composable_rust_core::event_store::{
+    BatchAppend, EventStore, EventStoreError, Pagination, StreamMetadata,
+};
 use composable_rust_core::stream::{StreamId, Version};
 use sqlx::Row;
-use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::postgres::{PgPool, PgPoolOptions, PgRow};
 use tracing::Instrument;
 
 /// Connection pool statistics for monitoring and observability.
@@ -411,6 +415,37 @@ pub async fn run_migrations(database_url: &str) -> Result<(), EventStoreError> {
     Ok(())
 }
 
+/// Build a [`SerializedEvent`] from a row of the `events` table
+fn event_from_row(row: &PgRow) -> SerializedEvent {
+    let metadata_json: Option<sqlx::types::JsonValue> = row.get("metadata");
+    SerializedEvent {
+        event_type: row.get("event_type"),
+        event_version: row.get("event_version"),
+        data: row.get("event_data"),
+        metadata: metadata_json.and_then(|json| EventMetadata::from_json(&json).ok()),
+    }
+}
+
+/// Build [`StreamMetadata`] from a row of the per-stream aggregate query
+fn stream_metadata_from_row(row: &PgRow) -> Result<StreamMetadata, EventStoreError> {
+    let stream_id: String = row.get("stream_id");
+    let event_count: i64 = row.get("event_count");
+    let last_version: i64 = row.get("last_version");
+
+    Ok(StreamMetadata {
+        stream_id: StreamId::new(stream_id),
+        created_at: row.get("created_at"),
+        event_count: u64::try_from(event_count).map_err(|e| {
+            EventStoreError::DatabaseError(format!("Invalid event count {event_count}: {e}"))
+        })?,
+        last_version: Version::new(u64::try_from(last_version).map_err(|e| {
+            EventStoreError::DatabaseError(format!(
+                "Invalid negative version {last_version} in database: {e}"
+            ))
+        })?),
+    })
+}
+
 impl EventStore for PostgresEventStore {
     #[allow(clippy::cognitive_complexity)] // Complex due to race condition handling
     #[allow(clippy::too_many_lines)] // TODO: Refactor in Phase 4
@@ -647,21 +682,7 @@ impl EventStore for PostgresEventStore {
             }
             .map_err(|e| EventStoreError::DatabaseError(e.to_string()))?;
 
-            let event_vec: Vec<SerializedEvent> = events
-                .into_iter()
-                .map(|row| {
-                    let metadata_json: Option<sqlx::types::JsonValue> = row.get("metadata");
-                    let metadata = metadata_json.and_then(|json| {
-                        EventMetadata::from_json(&json).ok()
-                    });
-                    SerializedEvent {
-                        event_type: row.get("event_type"),
-                        event_version: row.get("event_version"),
-                        data: row.get("event_data"),
-                        metadata,
-                    }
-                })
-                .collect();
+            let event_vec: Vec<SerializedEvent> = events.iter().map(event_from_row).collect();
 
             tracing::debug!(
                 stream_id = %stream_id,
@@ -683,6 +704,48 @@ impl EventStore for PostgresEventStore {
         }.instrument(span))
     }
 
+    fn load_events_page(
+        &self,
+        stream_id: StreamId,
+        from_version: Version,
+        limit: usize,
+    ) -> std::pin::Pin<
+        Box<
+            dyn std::future::Future<Output = Result<Vec<SerializedEvent>, EventStoreError>>
+                + Send
+                + '_,
+        >,
+    > {
+        let span = tracing::info_span!(
+            "event_store.load_events_page",
+            stream_id = %stream_id,
+            from_version = %from_version,
+            limit,
+        );
+
+        Box::pin(async move {
+            let rows = sqlx::query(
+                r"
+            SELECT event_type, event_version, event_data, metadata
+            FROM events
+            WHERE stream_id = $1 AND version >= $2
+            ORDER BY version ASC
+            LIMIT $3
+            ",
+            )
+            .bind(stream_id.as_str())
+            .bind(i64::try_from(from_version.value()).map_err(|e| {
+                EventStoreError::DatabaseError(format!("Version overflow: {e}"))
+            })?)
+            .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| EventStoreError::DatabaseError(e.to_string()))?;
+
+            Ok(rows.iter().map(event_from_row).collect())
+        }.instrument(span))
+    }
+
     fn save_snapshot(
         &self,
         stream_id: StreamId,
@@ -934,6 +997,131 @@ impl EventStore for PostgresEventStore {
             Ok(results)
         }.instrument(span))
     }
+
+    fn delete_stream(
+        &self,
+        stream_id: StreamId,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), EventStoreError>> + Send + '_>>
+    {
+        let span = tracing::info_span!("event_store.delete_stream", stream_id = %stream_id);
+
+        Box::pin(async move {
+            let mut tx = self
+                .pool
+                .begin()
+                .await
+                .map_err(|e| EventStoreError::DatabaseError(e.to_string()))?;
+
+            // Outbox rows reference the events, so they go first
+            sqlx::query("DELETE FROM event_outbox WHERE stream_id = $1")
+                .bind(stream_id.as_str())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| EventStoreError::DatabaseError(e.to_string()))?;
+
+            let deleted = sqlx::query("DELETE FROM events WHERE stream_id = $1")
+                .bind(stream_id.as_str())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| EventStoreError::DatabaseError(e.to_string()))?
+                .rows_affected();
+
+            if deleted == 0 {
+                return Err(EventStoreError::StreamNotFound(stream_id));
+            }
+
+            sqlx::query("DELETE FROM snapshots WHERE stream_id = $1")
+                .bind(stream_id.as_str())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| EventStoreError::DatabaseError(e.to_string()))?;
+
+            tx.commit()
+                .await
+                .map_err(|e| EventStoreError::DatabaseError(e.to_string()))?;
+
+            tracing::info!(stream_id = %stream_id, event_count = deleted, "Stream deleted");
+            metrics::counter!("event_store.delete_stream.total").increment(1);
+
+            Ok(())
+        }.instrument(span))
+    }
+
+    fn stream_metadata(
+        &self,
+        stream_id: StreamId,
+    ) -> std::pin::Pin<
+        Box<
+            dyn std::future::Future<Output = Result<Option<StreamMetadata>, EventStoreError>>
+                + Send
+                + '_,
+        >,
+    > {
+        let span = tracing::info_span!("event_store.stream_metadata", stream_id = %stream_id);
+
+        Box::pin(async move {
+            let row = sqlx::query(
+                r"
+            SELECT stream_id, MIN(created_at) AS created_at,
+                   COUNT(*) AS event_count, MAX(version) AS last_version
+            FROM events
+            WHERE stream_id = $1
+            GROUP BY stream_id
+            ",
+            )
+            .bind(stream_id.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EventStoreError::DatabaseError(e.to_string()))?;
+
+            row.as_ref().map(stream_metadata_from_row).transpose()
+        }.instrument(span))
+    }
+
+    fn list_streams(
+        &self,
+        prefix: Option<String>,
+        page: Pagination,
+    ) -> std::pin::Pin<
+        Box<
+            dyn std::future::Future<Output = Result<Vec<StreamMetadata>, EventStoreError>>
+                + Send
+                + '_,
+        >,
+    > {
+        let span = tracing::info_span!(
+            "event_store.list_streams",
+            prefix = ?prefix,
+            offset = page.offset,
+            limit = page.limit,
+        );
+
+        Box::pin(async move {
+            let overflow = |e: std::num::TryFromIntError| {
+                EventStoreError::DatabaseError(format!("Pagination overflow: {e}"))
+            };
+
+            let rows = sqlx::query(
+                r"
+            SELECT stream_id, MIN(created_at) AS created_at,
+                   COUNT(*) AS event_count, MAX(version) AS last_version
+            FROM events
+            WHERE $1::TEXT IS NULL OR starts_with(stream_id, $1)
+            GROUP BY stream_id
+            ORDER BY stream_id
+            LIMIT $2 OFFSET $3
+            ",
+            )
+            .bind(prefix.as_deref())
+            .bind(i64::try_from(page.limit).map_err(overflow)?)
+            .bind(i64::try_from(page.offset).map_err(overflow)?)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| EventStoreError::DatabaseError(e.to_string()))?;
+
+            rows.iter().map(stream_metadata_from_row).collect()
+        }.instrument(span))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,190 @@
+//! Transactional outbox backed by the `event_outbox` table.
+//!
+//! Events and their outbox rows are inserted in one transaction, so every
+//! stored event is eventually relayed to the event bus.
+
+use crate::PostgresEventStore;
+use composable_rust_core::event::{EventMetadata, SerializedEvent};
+use composable_rust_core::event_store::EventStoreError;
+use composable_rust_core::outbox::{OutboxEntry, OutboxStore};
+use composable_rust_core::stream::{StreamId, Version};
+use sqlx::Row;
+
+/// `PostgreSQL` unique constraint violation
+const UNIQUE_VIOLATION: &str = "23505";
+
+fn db_error(e: impl std::fmt::Display) -> EventStoreError {
+    EventStoreError::DatabaseError(e.to_string())
+}
+
+impl OutboxStore for PostgresEventStore {
+    fn append_with_outbox(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+        topic: String,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Version, EventStoreError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            if events.is_empty() {
+                return Err(EventStoreError::DatabaseError(
+                    "Cannot append empty event list".to_string(),
+                ));
+            }
+
+            let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+            let current: i64 = sqlx::query_scalar(
+                "SELECT COALESCE(MAX(version), 0) FROM events WHERE stream_id = $1",
+            )
+            .bind(stream_id.as_str())
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(db_error)?;
+            let current_version = Version::new(u64::try_from(current).map_err(db_error)?);
+
+            if let Some(expected) = expected_version {
+                if current_version != expected {
+                    return Err(EventStoreError::ConcurrencyConflict {
+                        stream_id,
+                        expected,
+                        actual: current_version,
+                    });
+                }
+            }
+
+            let mut version = current_version;
+            for event in &events {
+                version = version.next();
+                let version_i64 = i64::try_from(version.value()).map_err(db_error)?;
+
+                let inserted = sqlx::query(
+                    r"
+                    INSERT INTO events (stream_id, version, event_type, event_version, event_data, metadata, created_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, now())
+                    ",
+                )
+                .bind(stream_id.as_str())
+                .bind(version_i64)
+                .bind(&event.event_type)
+                .bind(event.event_version)
+                .bind(&event.data)
+                .bind(event.metadata.as_ref().map(EventMetadata::to_json))
+                .execute(&mut *tx)
+                .await;
+
+                if let Err(e) = inserted {
+                    let conflict = e
+                        .as_database_error()
+                        .and_then(sqlx::error::DatabaseError::code)
+                        .is_some_and(|code| code == UNIQUE_VIOLATION);
+                    if conflict {
+                        // A concurrent writer won the race for this version
+                        return Err(EventStoreError::ConcurrencyConflict {
+                            stream_id,
+                            expected: expected_version.unwrap_or(current_version),
+                            actual: version,
+                        });
+                    }
+                    return Err(db_error(e));
+                }
+
+                sqlx::query("INSERT INTO event_outbox (stream_id, version, topic) VALUES ($1, $2, $3)")
+                    .bind(stream_id.as_str())
+                    .bind(version_i64)
+                    .bind(&topic)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(db_error)?;
+            }
+
+            tx.commit().await.map_err(db_error)?;
+
+            metrics::counter!("event_store.outbox.enqueued")
+                .increment(events.len() as u64);
+
+            Ok(version)
+        })
+    }
+
+    fn pending_outbox(
+        &self,
+        limit: usize,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Vec<OutboxEntry>, EventStoreError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+
+            let rows = sqlx::query(
+                r"
+                SELECT o.sequence, o.stream_id, o.version, o.topic,
+                       e.event_type, e.event_version, e.event_data, e.metadata
+                FROM event_outbox o
+                JOIN events e ON e.stream_id = o.stream_id AND e.version = o.version
+                WHERE o.published_at IS NULL
+                ORDER BY o.sequence
+                LIMIT $1
+                ",
+            )
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+            rows.into_iter()
+                .map(|row| {
+                    let sequence: i64 = row.get("sequence");
+                    let version: i64 = row.get("version");
+                    let stream_id: String = row.get("stream_id");
+                    let metadata_json: Option<sqlx::types::JsonValue> = row.get("metadata");
+
+                    Ok(OutboxEntry {
+                        sequence: u64::try_from(sequence).map_err(db_error)?,
+                        stream_id: StreamId::new(stream_id),
+                        version: Version::new(u64::try_from(version).map_err(db_error)?),
+                        topic: row.get("topic"),
+                        event: SerializedEvent {
+                            event_type: row.get("event_type"),
+                            event_version: row.get("event_version"),
+                            data: row.get("event_data"),
+                            metadata: metadata_json
+                                .and_then(|json| EventMetadata::from_json(&json).ok()),
+                        },
+                    })
+                })
+                .collect()
+        })
+    }
+
+    fn mark_published(
+        &self,
+        sequences: Vec<u64>,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<(), EventStoreError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            if sequences.is_empty() {
+                return Ok(());
+            }
+
+            let sequences = sequences
+                .into_iter()
+                .map(i64::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(db_error)?;
+
+            sqlx::query(
+                "UPDATE event_outbox SET published_at = now() WHERE sequence = ANY($1) AND published_at IS NULL",
+            )
+            .bind(&sequences)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+            Ok(())
+        })
+    }
+}
@@ -10,7 +10,7 @@
 #![allow(clippy::expect_used)] // Test code uses expect for clear failure messages
 
 use composable_rust_core::event::{EventMetadata, SerializedEvent};
-use composable_rust_core::event_store::{EventStore, EventStoreError};
+use composable_rust_core::event_store::{EventStore, EventStoreError, Pagination};
 use composable_rust_core::stream::{StreamId, Version};
 use composable_rust_postgres::PostgresEventStore;
 use testcontainers::{ContainerAsync, runners::AsyncRunner};
@@ -400,6 +400,69 @@ async fn test_multiple_streams_isolation() {
     assert_eq!(events2[0].event_type, "Event2");
 }
 
+// ========== Stream Administration Tests ==========
+
+#[tokio::test]
+async fn test_stream_metadata_and_list_streams() {
+    let (_container, store) = setup_postgres_event_store().await;
+
+    for id in ["order-2", "customer-1", "order-1"] {
+        store
+            .append_events(
+                StreamId::new(id),
+                None,
+                vec![create_test_event("Event", b"data".to_vec())],
+            )
+            .await
+            .expect("Failed to append events");
+    }
+
+    let metadata = store
+        .stream_metadata(StreamId::new("order-1"))
+        .await
+        .expect("Failed to load metadata")
+        .expect("Stream should exist");
+    assert_eq!(metadata.event_count, 1);
+
+    let streams = store
+        .list_streams(Some("order-".to_string()), Pagination::first(10))
+        .await
+        .expect("Failed to list streams");
+    let ids: Vec<_> = streams.iter().map(|s| s.stream_id.as_str()).collect();
+    assert_eq!(ids, ["order-1", "order-2"]);
+}
+
+#[tokio::test]
+async fn test_delete_stream() {
+    let (_container, store) = setup_postgres_event_store().await;
+    let stream_id = StreamId::new("customer-42");
+
+    let version = store
+        .append_events(
+            stream_id.clone(),
+            None,
+            vec![create_test_event("Registered", b"data".to_vec())],
+        )
+        .await
+        .expect("Failed to append events");
+    store
+        .save_snapshot(stream_id.clone(), version, vec![1, 2, 3])
+        .await
+        .expect("Failed to save snapshot");
+
+    store
+        .delete_stream(stream_id.clone())
+        .await
+        .expect("Failed to delete stream");
+
+    assert!(store.load_events(stream_id.clone(), None).await.expect("load").is_empty());
+    assert!(store.load_snapshot(stream_id.clone()).await.expect("load").is_none());
+    assert!(matches!(
+        store.delete_stream(stream_id).await,
+        Err(EventStoreError::StreamNotFound(_))
+    ));
+}
+
 // ========== append_batch Tests ==========
 
 #[tokio::test]
@@ -0,0 +1,343 @@
+//! Catch-up subscriptions: replay history, then follow live events.
+//!
+//! A projection started against a store that already holds events must first
+//! replay them and then keep up with new ones. Reading history and then
+//! subscribing loses events published in between; subscribing first delivers
+//! some of them twice. A [`CatchUpSubscription`] does the latter and removes
+//! the overlap:
+//!
+//! 1. Subscribes to the live topic, so the bus buffers everything published
+//!    from now on
+//! 2. Replays the source streams from the [`EventStore`], remembering the
+//!    `event_id` of every replayed event
+//! 3. Switches to the buffered live events, dropping those whose `event_id`
+//!    was already replayed
+//!
+//! Live events without an `event_id` in their metadata cannot be matched and
+//! are always delivered, so projections fed by publishers that do not set one
+//! must tolerate duplicates around the switch.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_projections::catch_up::{CatchUpSubscription, EventSource};
+//!
+//! let mut subscription = CatchUpSubscription::new(event_store, event_bus, "order-events")
+//!     .with_stream_prefix("order-");
+//!
+//! while let Some(result) = subscription.next().await {
+//!     let caught_up = result?;
+//!     if caught_up.source == EventSource::Live && !announced {
+//!         tracing::info!("Order projection is live");
+//!         announced = true;
+//!     }
+//!     projection.apply(&caught_up.event).await?;
+//! }
+//! ```
+
+use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::event_bus::{EventBus, EventBusError};
+use composable_rust_core::event_store::{EventStore, EventStoreError, Pagination};
+use composable_rust_core::stream::{StreamId, Version};
+use futures::stream::{BoxStream, StreamExt};
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Number of stream ids requested per `list_streams` call.
+const LIST_PAGE_SIZE: usize = 100;
+
+/// Errors reported by a [`CatchUpSubscription`].
+#[derive(Error, Debug)]
+pub enum CatchUpError {
+    /// Listing or reading a source stream failed.
+    #[error("Event store error: {0}")]
+    EventStore(#[from] EventStoreError),
+
+    /// Subscribing to or reading from the live topic failed.
+    #[error("Event bus error: {0}")]
+    EventBus(#[from] EventBusError),
+}
+
+/// Where a delivered event came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventSource {
+    /// Replayed from the event store
+    History {
+        /// Source stream
+        stream_id: StreamId,
+        /// Version of the event within its stream
+        version: Version,
+    },
+    /// Received from the event bus after catching up
+    Live,
+}
+
+/// An event delivered by a [`CatchUpSubscription`].
+#[derive(Debug, Clone)]
+pub struct CatchUpEvent {
+    /// Where the event came from
+    pub source: EventSource,
+    /// The event itself
+    pub event: SerializedEvent,
+}
+
+/// Replays source streams, then delivers live events without gaps or duplicates.
+///
+/// See the [module documentation](self) for an overview.
+pub struct CatchUpSubscription {
+    event_store: Arc<dyn EventStore>,
+    event_bus: Arc<dyn EventBus>,
+    topic: String,
+    stream_prefix: Option<String>,
+    /// Streams still to replay, with the version to start from
+    pending: VecDeque<(StreamId, Version)>,
+    /// Loaded events of the stream being replayed
+    replaying: VecDeque<CatchUpEvent>,
+    /// `event_id`s replayed that may still arrive live
+    replayed_ids: HashSet<String>,
+    live: Option<BoxStream<'static, Result<SerializedEvent, EventBusError>>>,
+    caught_up: bool,
+}
+
+impl CatchUpSubscription {
+    /// Create a subscription to `topic`; nothing is read until [`next`](Self::next).
+    ///
+    /// # Arguments
+    ///
+    /// - `event_store`: Store holding the history to replay
+    /// - `event_bus`: Bus the same events are published to
+    /// - `topic`: Topic the live events arrive on
+    #[must_use]
+    pub fn new(
+        event_store: Arc<dyn EventStore>,
+        event_bus: Arc<dyn EventBus>,
+        topic: impl Into<String>,
+    ) -> Self {
+        Self {
+            event_store,
+            event_bus,
+            topic: topic.into(),
+            stream_prefix: None,
+            pending: VecDeque::new(),
+            replaying: VecDeque::new(),
+            replayed_ids: HashSet::new(),
+            live: None,
+            caught_up: false,
+        }
+    }
+
+    /// Add source streams to replay from the start (in order).
+    #[must_use]
+    pub fn with_sources(mut self, sources: impl IntoIterator<Item = StreamId>) -> Self {
+        self.pending.extend(
+            sources
+                .into_iter()
+                .map(|stream_id| (stream_id, Version::new(0))),
+        );
+        self
+    }
+
+    /// Add a source stream to replay from `version`, e.g. a saved checkpoint.
+    #[must_use]
+    pub fn with_source_from(mut self, stream_id: StreamId, version: Version) -> Self {
+        self.pending.push_back((stream_id, version));
+        self
+    }
+
+    /// Also replay every stream whose id starts with `prefix`, in id order,
+    /// after the explicitly added sources.
+    #[must_use]
+    pub fn with_stream_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.stream_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Whether history has been replayed and events now come from the bus.
+    #[must_use]
+    pub const fn is_live(&self) -> bool {
+        self.caught_up
+    }
+
+    /// Get the next event: replayed history first, then live events.
+    ///
+    /// The first call subscribes to the topic and lists prefixed streams.
+    /// Returns `None` when the live stream ends.
+    pub async fn next(&mut self) -> Option<Result<CatchUpEvent, CatchUpError>> {
+        if self.live.is_none()
+            && let Err(error) = self.subscribe().await
+        {
+            return Some(Err(error));
+        }
+
+        while !self.caught_up {
+            if let Some(replayed) = self.replaying.pop_front() {
+                if let Some(event_id) = event_id(&replayed.event) {
+                    self.replayed_ids.insert(event_id.to_string());
+                }
+                return Some(Ok(replayed));
+            }
+            if let Some((stream_id, from)) = self.pending.pop_front() {
+                if let Err(error) = self.load(&stream_id, from).await {
+                    // Retried by the next call
+                    self.pending.push_front((stream_id, from));
+                    return Some(Err(error));
+                }
+            } else {
+                tracing::info!(
+                    topic = %self.topic,
+                    replayed = self.replayed_ids.len(),
+                    "Caught up, switching to live events"
+                );
+                self.caught_up = true;
+            }
+        }
+
+        let live = self.live.as_mut()?;
+        while let Some(result) = live.next().await {
+            let event = match result {
+                Ok(event) => event,
+                Err(error) => return Some(Err(error.into())),
+            };
+            if event_id(&event).is_some_and(|id| self.replayed_ids.remove(id)) {
+                tracing::debug!(topic = %self.topic, "Skipping live event already replayed");
+                continue;
+            }
+            return Some(Ok(CatchUpEvent {
+                source: EventSource::Live,
+                event,
+            }));
+        }
+        None
+    }
+
+    /// Subscribe before reading any history, so nothing published meanwhile is missed
+    async fn subscribe(&mut self) -> Result<(), CatchUpError> {
+        let live = self.event_bus.subscribe(&[self.topic.as_str()]).await?;
+        self.live = Some(live.boxed());
+
+        if let Some(prefix) = self.stream_prefix.clone() {
+            let mut page = Pagination::first(LIST_PAGE_SIZE);
+            loop {
+                let streams = self
+                    .event_store
+                    .list_streams(Some(prefix.clone()), page)
+                    .await?;
+                if streams.is_empty() {
+                    break;
+                }
+                self.pending.extend(
+                    streams
+                        .into_iter()
+                        .map(|stream| (stream.stream_id, Version::new(0))),
+                );
+                page = page.next();
+            }
+        }
+        Ok(())
+    }
+
+    async fn load(&mut self, stream_id: &StreamId, from: Version) -> Result<(), CatchUpError> {
+        let events = self
+            .event_store
+            .load_events(stream_id.clone(), Some(from))
+            .await?;
+        tracing::debug!(stream = %stream_id, events = events.len(), "Replaying stream");
+
+        self.replaying
+            .extend(
+                (from.value()..)
+                    .map(Version::new)
+                    .zip(events)
+                    .map(|(version, event)| CatchUpEvent {
+                        source: EventSource::History {
+                            stream_id: stream_id.clone(),
+                            version,
+                        },
+                        event,
+                    }),
+            );
+        Ok(())
+    }
+}
+
+fn event_id(event: &SerializedEvent) -> Option<&str> {
+    event.metadata.as_ref()?.event_id.as_deref()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+mod tests {
+    use super::*;
+    use composable_rust_core::event::EventMetadata;
+    use composable_rust_testing::mocks::{InMemoryEventBus, InMemoryEventStore};
+
+    fn event(id: &str) -> SerializedEvent {
+        let metadata = EventMetadata {
+            event_id: Some(id.to_string()),
+            ..EventMetadata::new()
+        };
+        SerializedEvent::new("OrderPlaced.v1".to_string(), vec![], Some(metadata))
+    }
+
+    fn ids(events: &[CatchUpEvent]) -> Vec<&str> {
+        events.iter().filter_map(|e| event_id(&e.event)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_replays_history_then_live_without_duplicates() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let bus = Arc::new(InMemoryEventBus::new());
+        for (stream, id) in [("order-1", "a"), ("order-2", "b"), ("order-1", "c")] {
+            store
+                .append_events(StreamId::new(stream), None, vec![event(id)])
+                .await
+                .unwrap();
+        }
+
+        let mut subscription = CatchUpSubscription::new(store.clone(), bus.clone(), "order-events")
+            .with_stream_prefix("order-");
+
+        // Subscribes, then replays the first event
+        let first = subscription.next().await.unwrap().unwrap();
+        assert_eq!(
+            first.source,
+            EventSource::History {
+                stream_id: StreamId::new("order-1"),
+                version: Version::new(0),
+            }
+        );
+
+        // "c" was published live while being replayed; "d" is new
+        bus.publish("order-events", &event("c")).await.unwrap();
+        bus.publish("order-events", &event("d")).await.unwrap();
+
+        let mut delivered = vec![first];
+        for _ in 0..3 {
+            delivered.push(subscription.next().await.unwrap().unwrap());
+        }
+        assert_eq!(ids(&delivered), ["a", "c", "b", "d"]);
+        assert_eq!(delivered[3].source, EventSource::Live);
+        assert!(subscription.is_live());
+    }
+
+    #[tokio::test]
+    async fn test_resumes_sources_from_version() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let bus = Arc::new(InMemoryEventBus::new());
+        store
+            .append_events(StreamId::new("order-1"), None, vec![event("a"), event("b")])
+            .await
+            .unwrap();
+
+        let mut subscription = CatchUpSubscription::new(store, bus.clone(), "order-events")
+            .with_source_from(StreamId::new("order-1"), Version::new(1));
+        let replayed = subscription.next().await.unwrap().unwrap();
+        assert_eq!(ids(&[replayed]), ["b"]);
+
+        bus.publish("order-events", &event("e")).await.unwrap();
+        let live = subscription.next().await.unwrap().unwrap();
+        assert_eq!(live.source, EventSource::Live);
+        assert_eq!(ids(&[live]), ["e"]);
+    }
+}
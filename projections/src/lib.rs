@@ -6,6 +6,9 @@
 //! - **`PostgreSQL`**: Persistent projection store with JSONB support
 //! - **Checkpointing**: PostgreSQL-backed checkpoint tracking
 //! - **`ProjectionStream`**: Type-agnostic event stream helper for building projections
+//! - **`BackfillJob`**: Resumable derivation of new event streams from existing ones
+//! - **`CatchUpSubscription`**: Replay of stored history followed by live bus events
+//! - **`TransactionalProjection`**: Exactly-once processing when the read model shares the checkpoint database
 //!
 //! # CQRS Separation
 //!
@@ -44,9 +47,12 @@
 //! }
 //! ```
 
+pub mod backfill;
+pub mod catch_up;
 pub mod manager;
 pub mod postgres;
 pub mod stream;
+pub mod transactional;
 
 // Re-export main types for convenience
 #[deprecated(
@@ -54,5 +60,8 @@ pub mod stream;
     note = "Use ProjectionStream instead for better type safety with bincode deserialization"
 )]
 pub use manager::ProjectionManager;
+pub use backfill::{BackfillJob, BackfillReport, DerivedEvent, SourceEvent};
+pub use catch_up::{CatchUpEvent, CatchUpSubscription, EventSource};
 pub use postgres::{PostgresProjectionCheckpoint, PostgresProjectionStore};
 pub use stream::ProjectionStream;
+pub use transactional::TransactionalProjection;
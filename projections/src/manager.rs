@@ -54,8 +54,9 @@
 //! manager.rebuild().await?;
 //! ```
 
-use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::event::{EventError, SerializedEvent};
 use composable_rust_core::event_bus::{EventBus, EventBusError};
+use composable_rust_core::event_registry::EventRegistry;
 use composable_rust_core::projection::{
     EventPosition, Projection, ProjectionCheckpoint, ProjectionError, Result,
 };
@@ -63,6 +64,9 @@ use futures::StreamExt;
 use std::sync::Arc;
 use tokio::sync::watch;
 
+/// Decodes bus events for a projection; `Ok(None)` skips the event
+type Decoder<E> = Arc<dyn Fn(&SerializedEvent) -> std::result::Result<Option<E>, EventError> + Send + Sync>;
+
 /// Orchestrates projection updates from an event bus.
 ///
 /// `ProjectionManager` is responsible for:
@@ -125,6 +129,8 @@ where
     checkpoint_interval: u64,
     /// Shutdown signal
     shutdown: watch::Receiver<bool>,
+    /// Typed decoding; events are bincode-decoded into `P::Event` when unset
+    decoder: Option<Decoder<P::Event>>,
 }
 
 impl<P> ProjectionManager<P>
@@ -194,6 +200,7 @@ where
             consumer_group: consumer_group.into(),
             checkpoint_interval: 100, // Save every 100 events by default
             shutdown: shutdown_rx,
+            decoder: None,
         };
 
         (manager, shutdown_tx)
@@ -219,6 +226,31 @@ where
         self
     }
 
+    /// Decode events with a typed [`EventRegistry`] instead of bincode.
+    ///
+    /// Each event is decoded with the codec of the [`DomainEvent`] registered
+    /// for its `event_type`. Events of unregistered types are skipped (but
+    /// still counted towards the checkpoint).
+    ///
+    /// [`DomainEvent`]: composable_rust_core::event::DomainEvent
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let registry = EventRegistry::new()
+    ///     .register(OrderEvent::Placed)?
+    ///     .register(OrderEvent::Shipped)?;
+    /// let manager = manager.with_registry(registry);
+    /// ```
+    #[must_use]
+    pub fn with_registry(mut self, registry: EventRegistry<P::Event>) -> Self
+    where
+        P::Event: 'static,
+    {
+        self.decoder = Some(Arc::new(move |event| registry.decode(event)));
+        self
+    }
+
     /// Start processing events from the event bus.
     ///
     /// This method:
@@ -405,7 +437,13 @@ where
         let projection_name = self.projection.name();
 
         // Deserialize event
-        let event: P::Event = bincode::deserialize(&serialized_event.data).map_err(|e| {
+        let event: Option<P::Event> = match &self.decoder {
+            Some(decode) => decode(serialized_event).map_err(|e| e.to_string()),
+            None => bincode::deserialize(&serialized_event.data)
+                .map(Some)
+                .map_err(|e| e.to_string()),
+        }
+        .map_err(|e| {
             ProjectionError::Serialization(format!(
                 "Failed to deserialize event {}: {e}",
                 serialized_event.event_type
@@ -413,12 +451,20 @@ where
         })?;
 
         // Apply event to projection
-        self.projection.apply_event(&event).await.map_err(|e| {
-            ProjectionError::EventProcessing(format!(
-                "Failed to apply event {} at position {event_count}: {e}",
-                serialized_event.event_type
-            ))
-        })?;
+        if let Some(event) = event {
+            self.projection.apply_event(&event).await.map_err(|e| {
+                ProjectionError::EventProcessing(format!(
+                    "Failed to apply event {} at position {event_count}: {e}",
+                    serialized_event.event_type
+                ))
+            })?;
+        } else {
+            tracing::trace!(
+                projection = projection_name,
+                event_type = %serialized_event.event_type,
+                "Skipping unregistered event type"
+            );
+        }
 
         // Increment event counter
         *event_count += 1;
@@ -458,4 +504,72 @@ mod tests {
         // This test validates the builder pattern
         assert_eq!(1 + 1, 2); // Placeholder
     }
+
+    mod registry {
+        use super::super::*;
+        use composable_rust_core::codec::JsonCodec;
+        use composable_rust_core::event::DomainEvent;
+        use composable_rust_testing::mocks::InMemoryEventBus;
+        use composable_rust_testing::InMemoryProjectionCheckpoint;
+        use serde::{Deserialize, Serialize};
+        use std::sync::Mutex;
+
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        struct Deposited {
+            amount: u32,
+        }
+
+        impl DomainEvent for Deposited {
+            const NAME: &'static str = "Deposited";
+            type Codec = JsonCodec;
+        }
+
+        #[derive(Default)]
+        struct Balance {
+            deposits: Mutex<Vec<u32>>,
+        }
+
+        impl Projection for Balance {
+            type Event = Deposited;
+
+            fn name(&self) -> &'static str {
+                "balance"
+            }
+
+            async fn apply_event(&self, event: &Self::Event) -> Result<()> {
+                #[allow(clippy::unwrap_used)] // Tests can unwrap
+                self.deposits.lock().unwrap().push(event.amount);
+                Ok(())
+            }
+        }
+
+        #[tokio::test]
+        #[allow(clippy::unwrap_used)] // Tests can unwrap
+        async fn test_registry_decodes_typed_events_and_skips_others() {
+            let registry = EventRegistry::new().register_into::<Deposited>().unwrap();
+            let (manager, _shutdown) = ProjectionManager::new(
+                Balance::default(),
+                Arc::new(InMemoryEventBus::new()),
+                Arc::new(InMemoryProjectionCheckpoint::new()),
+                "accounts",
+                "balance-projection",
+            );
+            let manager = manager.with_registry(registry);
+
+            let mut count = 0;
+            let deposit = SerializedEvent::encode(&Deposited { amount: 5 }, None).unwrap();
+            let other = SerializedEvent::new("Withdrawn.v1".to_string(), vec![1], None);
+            manager.process_event(&deposit, &mut count).await.unwrap();
+            manager.process_event(&other, &mut count).await.unwrap();
+
+            assert_eq!(*manager.projection.deposits.lock().unwrap(), vec![5]);
+            assert_eq!(count, 2);
+
+            let corrupt = SerializedEvent::new("Deposited.v1".to_string(), vec![1], None);
+            assert!(matches!(
+                manager.process_event(&corrupt, &mut count).await,
+                Err(ProjectionError::Serialization(_))
+            ));
+        }
+    }
 }
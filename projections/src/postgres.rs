@@ -380,30 +380,7 @@ impl ProjectionCheckpoint for PostgresProjectionCheckpoint {
         position: EventPosition,
     ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
         let projection_name = projection_name.to_string();
-        Box::pin(async move {
-            // EventPosition uses u64 for offset, but PostgreSQL BIGINT is i64.
-            // Wrapping would occur at 2^63 events (~9 quintillion), which is acceptable.
-            // At 1 million events/sec, this would take 292,471 years to wrap.
-            #[allow(clippy::cast_possible_wrap)]
-            let offset_i64 = position.offset as i64;
-
-            sqlx::query(
-                "INSERT INTO projection_checkpoints (projection_name, event_offset, event_timestamp, updated_at)
-                 VALUES ($1, $2, $3, now())
-                 ON CONFLICT (projection_name) DO UPDATE
-                 SET event_offset = EXCLUDED.event_offset,
-                     event_timestamp = EXCLUDED.event_timestamp,
-                     updated_at = now()"
-            )
-            .bind(projection_name)
-            .bind(offset_i64)
-            .bind(position.timestamp)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| ProjectionError::Checkpoint(format!("Failed to save checkpoint: {e}")))?;
-
-            Ok(())
-        })
+        Box::pin(async move { upsert_checkpoint(&self.pool, &projection_name, position).await })
     }
 
     fn load_position(
@@ -433,6 +410,36 @@ impl ProjectionCheckpoint for PostgresProjectionCheckpoint {
     }
 }
 
+/// Save `position` for `projection_name` on `executor` (a pool, connection or transaction)
+pub(crate) async fn upsert_checkpoint<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    projection_name: &str,
+    position: EventPosition,
+) -> Result<()> {
+    // EventPosition uses u64 for offset, but PostgreSQL BIGINT is i64.
+    // Wrapping would occur at 2^63 events (~9 quintillion), which is acceptable.
+    // At 1 million events/sec, this would take 292,471 years to wrap.
+    #[allow(clippy::cast_possible_wrap)]
+    let offset_i64 = position.offset as i64;
+
+    sqlx::query(
+        "INSERT INTO projection_checkpoints (projection_name, event_offset, event_timestamp, updated_at)
+         VALUES ($1, $2, $3, now())
+         ON CONFLICT (projection_name) DO UPDATE
+         SET event_offset = EXCLUDED.event_offset,
+             event_timestamp = EXCLUDED.event_timestamp,
+             updated_at = now()"
+    )
+    .bind(projection_name)
+    .bind(offset_i64)
+    .bind(position.timestamp)
+    .execute(executor)
+    .await
+    .map_err(|e| ProjectionError::Checkpoint(format!("Failed to save checkpoint: {e}")))?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use composable_rust_core::projection::EventPosition;
@@ -44,9 +44,14 @@
 
 use composable_rust_core::event::SerializedEvent;
 use composable_rust_core::event_bus::{EventBus, EventBusError};
-use composable_rust_core::projection::{EventPosition, ProjectionCheckpoint, ProjectionError};
+use composable_rust_core::projection::{
+    CommitStrategy, EventPosition, ProjectionCheckpoint, ProjectionError,
+};
+use crate::postgres::PostgresProjectionCheckpoint;
+use crate::transactional::TransactionalProjection;
 use futures::stream::StreamExt;
 use std::sync::Arc;
+use std::time::Instant;
 
 /// Result type for projection operations.
 pub type Result<T> = std::result::Result<T, ProjectionError>;
@@ -130,10 +135,12 @@ pub struct ProjectionStream {
     projection_name: String,
     /// Current position (event count)
     position: u64,
-    /// Checkpoint save interval (save every N events)
-    checkpoint_interval: u64,
+    /// When checkpoints are saved
+    commit_strategy: CommitStrategy,
     /// Events processed since last checkpoint
     events_since_checkpoint: u64,
+    /// When the last checkpoint was saved (or the stream created)
+    last_checkpoint: Instant,
 }
 
 impl ProjectionStream {
@@ -229,8 +236,9 @@ impl ProjectionStream {
             checkpoint,
             projection_name,
             position,
-            checkpoint_interval: 100, // Save every 100 events by default
+            commit_strategy: CommitStrategy::default(),
             events_since_checkpoint: 0,
+            last_checkpoint: Instant::now(),
         })
     }
 
@@ -249,8 +257,24 @@ impl ProjectionStream {
     /// let stream = stream.with_checkpoint_interval(50); // Save every 50 events
     /// ```
     #[must_use]
-    pub const fn with_checkpoint_interval(mut self, interval: u64) -> Self {
-        self.checkpoint_interval = interval;
+    pub const fn with_checkpoint_interval(self, interval: u64) -> Self {
+        self.with_commit_strategy(CommitStrategy::EveryN(interval))
+    }
+
+    /// Set when checkpoints are saved: every event, every N events, or
+    /// every T seconds.
+    ///
+    /// Defaults to every 100 events.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Save at most once every 5 seconds
+    /// let stream = stream.with_commit_strategy(CommitStrategy::Interval(Duration::from_secs(5)));
+    /// ```
+    #[must_use]
+    pub const fn with_commit_strategy(mut self, strategy: CommitStrategy) -> Self {
+        self.commit_strategy = strategy;
         self
     }
 
@@ -279,7 +303,8 @@ impl ProjectionStream {
     /// Commit the current position to the checkpoint.
     ///
     /// Call this after successfully processing an event. Checkpoints are saved
-    /// periodically based on `checkpoint_interval` (not on every commit call).
+    /// according to the [`CommitStrategy`] (not necessarily on every commit
+    /// call); call [`flush`](Self::flush) before stopping to save the rest.
     ///
     /// # Errors
     ///
@@ -297,26 +322,78 @@ impl ProjectionStream {
         self.position += 1;
         self.events_since_checkpoint += 1;
 
-        // Save checkpoint periodically
-        if self.events_since_checkpoint >= self.checkpoint_interval {
-            let position = EventPosition::new(self.position, chrono::Utc::now());
-
-            self.checkpoint
-                .save_position(&self.projection_name, position)
-                .await?;
+        if self
+            .commit_strategy
+            .is_due(self.events_since_checkpoint, self.last_checkpoint.elapsed())
+        {
+            self.save_checkpoint().await?;
+        }
 
-            tracing::debug!(
-                projection = %self.projection_name,
-                offset = self.position,
-                "Checkpoint saved"
-            );
+        Ok(())
+    }
 
-            self.events_since_checkpoint = 0;
+    /// Save the current position if commits since the last checkpoint
+    /// have not been saved yet.
+    ///
+    /// Call this on graceful shutdown so a restart does not reprocess them.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if checkpoint save fails.
+    pub async fn flush(&mut self) -> Result<()> {
+        if self.events_since_checkpoint > 0 {
+            self.save_checkpoint().await?;
         }
+        Ok(())
+    }
+
+    async fn save_checkpoint(&mut self) -> Result<()> {
+        let position = EventPosition::new(self.position, chrono::Utc::now());
+
+        self.checkpoint
+            .save_position(&self.projection_name, position)
+            .await?;
 
+        tracing::debug!(
+            projection = %self.projection_name,
+            offset = self.position,
+            "Checkpoint saved"
+        );
+
+        self.events_since_checkpoint = 0;
+        self.last_checkpoint = Instant::now();
         Ok(())
     }
 
+    /// Apply an event and save its position in one transaction.
+    ///
+    /// Use instead of [`commit`](Self::commit) when the read model shares a
+    /// database with the checkpoint; see [`crate::transactional`]. The
+    /// checkpoint is saved for every event regardless of the
+    /// [`CommitStrategy`], and events already covered by it are skipped.
+    /// Returns whether the event was applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the event or checkpoint cannot be written; the
+    /// position is not advanced.
+    pub async fn apply_exactly_once<P: TransactionalProjection + ?Sized>(
+        &mut self,
+        checkpoint: &PostgresProjectionCheckpoint,
+        projection: &P,
+        event: &SerializedEvent,
+    ) -> Result<bool> {
+        let offset = self.position + 1;
+        let applied = checkpoint
+            .apply_exactly_once(projection, offset, event)
+            .await?;
+
+        self.position = offset;
+        self.events_since_checkpoint = 0;
+        self.last_checkpoint = Instant::now();
+        Ok(applied)
+    }
+
     /// Get the current position (event count).
     ///
     /// Useful for logging and monitoring.
@@ -331,3 +408,77 @@ impl ProjectionStream {
         &self.projection_name
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+mod tests {
+    use super::*;
+    use composable_rust_testing::mocks::InMemoryEventBus;
+    use composable_rust_testing::InMemoryProjectionCheckpoint;
+    use std::time::Duration;
+
+    async fn stream(checkpoint: &Arc<InMemoryProjectionCheckpoint>) -> ProjectionStream {
+        ProjectionStream::new(
+            Arc::new(InMemoryEventBus::new()),
+            Arc::clone(checkpoint) as Arc<dyn ProjectionCheckpoint>,
+            "order-events",
+            "orders",
+            "orders",
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn saved(checkpoint: &InMemoryProjectionCheckpoint) -> Option<u64> {
+        checkpoint
+            .load_position("orders")
+            .await
+            .unwrap()
+            .map(|position| position.offset)
+    }
+
+    #[tokio::test]
+    async fn test_commit_strategies() {
+        let checkpoint = Arc::new(InMemoryProjectionCheckpoint::new());
+
+        let mut every_event = stream(&checkpoint)
+            .await
+            .with_commit_strategy(CommitStrategy::EveryEvent);
+        every_event.commit().await.unwrap();
+        assert_eq!(saved(&checkpoint).await, Some(1));
+
+        let mut every_third = stream(&checkpoint)
+            .await
+            .with_commit_strategy(CommitStrategy::EveryN(3));
+        assert_eq!(every_third.position(), 1);
+        every_third.commit().await.unwrap();
+        every_third.commit().await.unwrap();
+        assert_eq!(saved(&checkpoint).await, Some(1));
+        every_third.commit().await.unwrap();
+        assert_eq!(saved(&checkpoint).await, Some(4));
+
+        let mut timed = stream(&checkpoint)
+            .await
+            .with_commit_strategy(CommitStrategy::Interval(Duration::from_millis(50)));
+        timed.commit().await.unwrap();
+        assert_eq!(saved(&checkpoint).await, Some(4));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        timed.commit().await.unwrap();
+        assert_eq!(saved(&checkpoint).await, Some(6));
+    }
+
+    #[tokio::test]
+    async fn test_flush_saves_uncommitted_position() {
+        let checkpoint = Arc::new(InMemoryProjectionCheckpoint::new());
+        let mut stream = stream(&checkpoint).await;
+
+        stream.flush().await.unwrap();
+        assert_eq!(saved(&checkpoint).await, None);
+
+        stream.commit().await.unwrap();
+        stream.commit().await.unwrap();
+        assert_eq!(saved(&checkpoint).await, None);
+        stream.flush().await.unwrap();
+        assert_eq!(saved(&checkpoint).await, Some(2));
+    }
+}
@@ -0,0 +1,136 @@
+//! Exactly-once projection processing.
+//!
+//! [`ProjectionStream`](crate::ProjectionStream) delivers events at least
+//! once: a crash between updating the read model and saving the checkpoint
+//! replays the event on restart, and a projection that is not naturally
+//! idempotent (counters, running totals) applies it twice.
+//!
+//! When the read model lives in the same `PostgreSQL` database as the
+//! checkpoint table, both writes can share one transaction. A
+//! [`TransactionalProjection`] applies each event on the transaction it is
+//! handed, and [`PostgresProjectionCheckpoint::apply_exactly_once`] commits
+//! it together with the new checkpoint, skipping events at or below the
+//! saved position. Either both writes land or neither does.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_projections::transactional::TransactionalProjection;
+//!
+//! struct OrderCount;
+//!
+//! impl TransactionalProjection for OrderCount {
+//!     fn name(&self) -> &str {
+//!         "order-count"
+//!     }
+//!
+//!     fn apply_in_transaction<'a>(
+//!         &'a self,
+//!         conn: &'a mut PgConnection,
+//!         event: &'a SerializedEvent,
+//!     ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+//!         Box::pin(async move {
+//!             sqlx::query("UPDATE order_count SET total = total + 1")
+//!                 .execute(conn)
+//!                 .await
+//!                 .map_err(|e| ProjectionError::Storage(e.to_string()))?;
+//!             Ok(())
+//!         })
+//!     }
+//! }
+//!
+//! while let Some(event) = stream.next().await {
+//!     stream.apply_exactly_once(&checkpoint, &OrderCount, &event?).await?;
+//! }
+//! ```
+
+use crate::postgres::{PostgresProjectionCheckpoint, upsert_checkpoint};
+use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::projection::{EventPosition, ProjectionError, Result};
+use sqlx::PgConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+/// A projection whose updates can join the checkpoint's transaction.
+///
+/// The read model must live in the database of the
+/// [`PostgresProjectionCheckpoint`] it is used with.
+pub trait TransactionalProjection: Send + Sync {
+    /// Checkpoint key of this projection.
+    fn name(&self) -> &str;
+
+    /// Apply `event` using `conn`, which is inside an open transaction.
+    ///
+    /// Do not commit or roll back; returning an error rolls back the event
+    /// and leaves the checkpoint unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the event cannot be applied.
+    fn apply_in_transaction<'a>(
+        &'a self,
+        conn: &'a mut PgConnection,
+        event: &'a SerializedEvent,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+}
+
+impl PostgresProjectionCheckpoint {
+    /// Apply the event at `offset` and save `offset` as the checkpoint, in
+    /// one transaction.
+    ///
+    /// The checkpoint row is locked first, so concurrent runners of the same
+    /// projection are serialized. Returns `false` without applying anything
+    /// if the checkpoint is already at or past `offset`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the projection's error, or [`ProjectionError::Checkpoint`] if
+    /// the transaction fails; in both cases nothing is committed.
+    pub async fn apply_exactly_once<P: TransactionalProjection + ?Sized>(
+        &self,
+        projection: &P,
+        offset: u64,
+        event: &SerializedEvent,
+    ) -> Result<bool> {
+        let mut tx = self.pool().begin().await.map_err(|e| {
+            ProjectionError::Checkpoint(format!("Failed to begin transaction: {e}"))
+        })?;
+
+        let saved: Option<(i64,)> = sqlx::query_as(
+            "SELECT event_offset
+             FROM projection_checkpoints
+             WHERE projection_name = $1
+             FOR UPDATE",
+        )
+        .bind(projection.name())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| ProjectionError::Checkpoint(format!("Failed to lock checkpoint: {e}")))?;
+
+        #[allow(clippy::cast_sign_loss)] // Offset is always positive in our system
+        if let Some((saved,)) = saved
+            && saved as u64 >= offset
+        {
+            tracing::debug!(
+                projection = %projection.name(),
+                offset,
+                checkpoint = saved,
+                "Skipping already applied event"
+            );
+            return Ok(false);
+        }
+
+        projection.apply_in_transaction(&mut tx, event).await?;
+        upsert_checkpoint(
+            &mut *tx,
+            projection.name(),
+            EventPosition::new(offset, chrono::Utc::now()),
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| {
+            ProjectionError::Checkpoint(format!("Failed to commit transaction: {e}"))
+        })?;
+        Ok(true)
+    }
+}
@@ -93,10 +93,10 @@
 #![warn(missing_docs)]
 
 use composable_rust_core::event::SerializedEvent;
-use composable_rust_core::event_bus::{EventBus, EventBusError, EventStream};
+use composable_rust_core::event_bus::{EventBus, EventBusError, EventStream, PublishOptions};
 use rdkafka::config::ClientConfig;
 use rdkafka::consumer::{Consumer, StreamConsumer};
-use rdkafka::message::Message;
+use rdkafka::message::{Header, Message, OwnedHeaders};
 use rdkafka::producer::{FutureProducer, FutureRecord};
 use rdkafka::util::Timeout;
 use std::future::Future;
@@ -160,6 +160,8 @@ pub struct RedpandaEventBus {
     buffer_size: usize,
     /// Auto offset reset policy
     auto_offset_reset: String,
+    /// Publish in the encoding that predates the wire format marker
+    legacy_wire_format: bool,
 }
 
 impl RedpandaEventBus {
@@ -248,6 +250,7 @@ pub struct RedpandaEventBusBuilder {
     consumer_group: Option<String>,
     buffer_size: Option<usize>,
     auto_offset_reset: Option<String>,
+    legacy_wire_format: bool,
 }
 
 impl RedpandaEventBusBuilder {
@@ -390,6 +393,27 @@ impl RedpandaEventBusBuilder {
         self
     }
 
+    /// Keep publishing events in the encoding that predates the wire format marker.
+    ///
+    /// Consumers built before the marker cannot decode
+    /// [`SerializedEvent::to_wire_bytes`] payloads, while upgraded consumers
+    /// decode both encodings. Roll out in this order:
+    ///
+    /// 1. Deploy the new version with `legacy_wire_format(true)` on every
+    ///    producer, so consumers still on the old version keep working.
+    /// 2. Once every consumer runs the new version, drop the setting so
+    ///    producers switch to the marked format.
+    ///
+    /// The legacy encoding drops the event id, tenant and headers from
+    /// event metadata. See [`SerializedEvent::to_legacy_wire_bytes`].
+    ///
+    /// Default: `false`
+    #[must_use]
+    pub const fn legacy_wire_format(mut self, legacy: bool) -> Self {
+        self.legacy_wire_format = legacy;
+        self
+    }
+
     /// Build the [`RedpandaEventBus`].
     ///
     /// # Errors
@@ -425,6 +449,7 @@ impl RedpandaEventBusBuilder {
             compression = self.compression.as_deref().unwrap_or("none"),
             buffer_size = self.buffer_size.unwrap_or(1000),
             auto_offset_reset = self.auto_offset_reset.as_deref().unwrap_or("latest"),
+            legacy_wire_format = self.legacy_wire_format,
             "RedpandaEventBus created successfully"
         );
 
@@ -437,6 +462,7 @@ impl RedpandaEventBusBuilder {
             auto_offset_reset: self
                 .auto_offset_reset
                 .unwrap_or_else(|| "latest".to_string()),
+            legacy_wire_format: self.legacy_wire_format,
         })
     }
 }
@@ -446,28 +472,61 @@ impl EventBus for RedpandaEventBus {
         &self,
         topic: &str,
         event: &SerializedEvent,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+        self.publish_with_options(topic, event, &PublishOptions::default())
+    }
+
+    fn publish_with_options(
+        &self,
+        topic: &str,
+        event: &SerializedEvent,
+        options: &PublishOptions,
     ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
         // Clone data before moving into async block
         let topic = topic.to_string();
         let event = event.clone();
-        let timeout = self.timeout;
+        let options = options.clone();
+        let timeout = options.delivery_timeout.unwrap_or(self.timeout);
+        let legacy_wire_format = self.legacy_wire_format;
 
         Box::pin(async move {
             // Metrics: Start timing
             let start = std::time::Instant::now();
 
-            // Serialize event using bincode
-            let payload = bincode::serialize(&event).map_err(|e| EventBusError::PublishFailed {
+            // Serialize event in the versioned wire format, unless consumers
+            // that predate it are still being upgraded
+            let payload = if legacy_wire_format {
+                event.to_legacy_wire_bytes()
+            } else {
+                event.to_wire_bytes()
+            };
+            let payload = payload.map_err(|e| EventBusError::PublishFailed {
                 topic: topic.clone(),
                 reason: format!("Failed to serialize event: {e}"),
             })?;
 
-            // Use event_type as the message key for partitioning
-            // Events of the same type go to the same partition (ordering guarantee)
-            let key = event.event_type.as_bytes();
+            // The partition key (e.g. the aggregate id) is the message key;
+            // without one, events of the same type share a partition
+            let key = options
+                .partition_key
+                .as_deref()
+                .unwrap_or(&event.event_type)
+                .as_bytes();
 
             // Create Kafka record
-            let record = FutureRecord::to(&topic).payload(&payload).key(key);
+            let mut record = FutureRecord::to(&topic).payload(&payload).key(key);
+            if !options.headers.is_empty() {
+                let headers = options.headers.iter().fold(
+                    OwnedHeaders::new_with_capacity(options.headers.len()),
+                    |headers, (name, value)| {
+                        headers.insert(Header {
+                            key: name,
+                            value: Some(value.as_str()),
+                        })
+                    },
+                );
+                record = record.headers(headers);
+            }
 
             // Send the message
             let send_result = self.producer.send(record, Timeout::After(timeout)).await;
@@ -601,7 +660,7 @@ impl EventBus for RedpandaEventBus {
                                 };
 
                                 // Deserialize event
-                                match bincode::deserialize::<SerializedEvent>(payload) {
+                                match SerializedEvent::from_wire_bytes(payload) {
                                     Ok(event) => {
                                         tracing::trace!(
                                             topic = message.topic(),
@@ -0,0 +1,116 @@
+//! Store send hot path benchmarks
+//!
+//! Measures the per-send cost of the Store for the effect shapes that
+//! dominate high-volume workloads:
+//! - actions whose reducer returns no work (`Effect::None`, empty, `Parallel` of `None`)
+//! - actions with a single `Future` effect (tracking channel and spawned task)
+//! - the same with the caller waiting on the returned handle
+//!
+//! Run with: `cargo bench --bench send_hot_path`
+
+#![allow(missing_docs)] // Benchmarks don't need extensive docs
+#![allow(clippy::expect_used)] // Benchmarks can use expect for setup
+
+use composable_rust_core::{SmallVec, effect::Effect, reducer::Reducer, smallvec};
+use composable_rust_runtime::Store;
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+#[derive(Clone, Debug)]
+enum HotAction {
+    None,
+    Empty,
+    ParallelNone,
+    Future,
+    Done,
+}
+
+#[derive(Clone)]
+struct HotReducer;
+
+impl Reducer for HotReducer {
+    type State = u64;
+    type Action = HotAction;
+    type Environment = ();
+
+    fn reduce(
+        &self,
+        state: &mut Self::State,
+        action: Self::Action,
+        _env: &Self::Environment,
+    ) -> SmallVec<[Effect<Self::Action>; 4]> {
+        *state += 1;
+        match action {
+            HotAction::None | HotAction::Done => smallvec![Effect::None],
+            HotAction::Empty => SmallVec::new(),
+            HotAction::ParallelNone => {
+                smallvec![Effect::Parallel(vec![
+                    Effect::None,
+                    Effect::None,
+                    Effect::None
+                ])]
+            },
+            HotAction::Future => {
+                smallvec![Effect::Future(Box::pin(async { Some(HotAction::Done) }))]
+            },
+        }
+    }
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build runtime")
+}
+
+/// Sends whose effects need no execution
+fn benchmark_no_effect_sends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("send_no_effects");
+    group.throughput(Throughput::Elements(1));
+    let runtime = runtime();
+
+    for (name, action) in [
+        ("effect_none", HotAction::None),
+        ("empty", HotAction::Empty),
+        ("parallel_of_none", HotAction::ParallelNone),
+    ] {
+        group.bench_function(name, |b| {
+            let store = Store::new(0, HotReducer, ());
+            b.to_async(&runtime).iter(|| async {
+                let _ = store.send(black_box(action.clone())).await;
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Sends that spawn a tracked effect
+fn benchmark_effect_sends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("send_with_effects");
+    group.throughput(Throughput::Elements(1));
+    let runtime = runtime();
+
+    group.bench_function("future", |b| {
+        let store = Store::new(0, HotReducer, ());
+        b.to_async(&runtime).iter(|| async {
+            let _ = store.send(black_box(HotAction::Future)).await;
+        });
+    });
+
+    group.bench_function("future_and_wait", |b| {
+        let store = Store::new(0, HotReducer, ());
+        b.to_async(&runtime).iter(|| async {
+            let mut handle = store
+                .send(black_box(HotAction::Future))
+                .await
+                .expect("send failed");
+            handle.wait().await;
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_no_effect_sends, benchmark_effect_sends);
+criterion_main!(benches);
@@ -0,0 +1,538 @@
+//! Typed aggregate repository facade over [`Store`].
+//!
+//! Most of the framework is action-oriented: call sites build an action, `send()` it,
+//! and observe what comes back. Codebases coming from a CRUD/service-layer background
+//! often prefer an imperative shape:
+//!
+//! ```ignore
+//! let event = orders.execute(&order_id, OrderCommand::Ship { tracking }).await?;
+//! ```
+//!
+//! [`AggregateRepository`] provides exactly that on top of the normal Store runtime:
+//!
+//! - **Load-or-create by id**: a Store is created per aggregate id, optionally hydrated
+//!   by an [`Aggregate::load_action`] (e.g. one that loads events from the event store).
+//! - **Ask pattern**: the command is sent with [`Store::send_and_wait_for`] and the first
+//!   action classified by [`Aggregate::outcome`] is returned as a typed event or error.
+//!   Commands for the same aggregate are serialized so replies cannot be confused.
+//! - **Optional caching**: with [`AggregateRepository::with_cache`], hot aggregates keep
+//!   their Store in memory (least recently used are evicted). Without it, every call uses
+//!   a fresh Store — the per-message pattern used by the ticketing example.
+//!
+//! # Outcomes must come from effects
+//!
+//! `send_and_wait_for` only observes actions produced by effects, so the reducer must
+//! report both success and rejection through an effect (typically the `on_success` /
+//! `on_error` callbacks of `AppendEvents`, or an `Effect::Future`).
+
+use crate::{Store, StoreError};
+use composable_rust_core::reducer::Reducer;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Store type backing an aggregate.
+pub type AggregateStore<Agg> = Store<
+    <Agg as Aggregate>::State,
+    <Agg as Aggregate>::Action,
+    <Agg as Aggregate>::Environment,
+    <Agg as Aggregate>::Reducer,
+>;
+
+/// Describes how an aggregate maps onto the Store runtime.
+///
+/// Implementors hold whatever dependencies are needed to build the
+/// environment (event store, clock, ...) for a given aggregate id.
+pub trait Aggregate: Send + Sync + 'static {
+    /// Aggregate identifier
+    type Id: Clone + Eq + Hash + Send + Sync + 'static;
+    /// Aggregate state
+    type State: Send + Sync + 'static;
+    /// Store action type (commands, events, and internal actions)
+    type Action: Clone + Send + 'static;
+    /// Reducer environment
+    type Environment: Clone + Send + Sync + 'static;
+    /// Reducer implementing the aggregate's business logic
+    type Reducer: Reducer<State = Self::State, Action = Self::Action, Environment = Self::Environment>
+        + Clone
+        + Send
+        + Sync
+        + 'static;
+    /// Command accepted by [`AggregateRepository::execute`]
+    type Command;
+    /// Successful outcome of a command
+    type Event;
+    /// Rejection outcome of a command
+    type Error;
+
+    /// State of a new, empty aggregate.
+    fn initial_state(&self, id: &Self::Id) -> Self::State;
+
+    /// Reducer instance.
+    fn reducer(&self) -> Self::Reducer;
+
+    /// Environment for the aggregate with the given id.
+    fn environment(&self, id: &Self::Id) -> Self::Environment;
+
+    /// Convert a command into the action sent to the Store.
+    fn command(&self, id: &Self::Id, command: Self::Command) -> Self::Action;
+
+    /// Classify an action produced by an effect.
+    ///
+    /// Returns `Some(Ok(event))` or `Some(Err(error))` for the terminal action
+    /// answering a command, and `None` for everything else.
+    fn outcome(action: &Self::Action) -> Option<Result<Self::Event, Self::Error>>;
+
+    /// Action sent once when a Store is created, to hydrate its state.
+    ///
+    /// The repository waits for the action's effects to finish before
+    /// executing commands. Defaults to no hydration.
+    fn load_action(&self, _id: &Self::Id) -> Option<Self::Action> {
+        None
+    }
+}
+
+/// Errors returned by [`AggregateRepository`].
+#[derive(Error, Debug)]
+pub enum RepositoryError<E> {
+    /// The aggregate rejected the command.
+    #[error("Command rejected: {0}")]
+    Rejected(E),
+
+    /// The underlying Store failed (timeout, shutdown, rate limit, ...).
+    #[error(transparent)]
+    Store(#[from] StoreError),
+}
+
+/// A Store plus the lock serializing commands sent to it.
+struct Entry<Agg: Aggregate> {
+    store: AggregateStore<Agg>,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl<Agg: Aggregate> Clone for Entry<Agg> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            lock: Arc::clone(&self.lock),
+        }
+    }
+}
+
+/// Least-recently-used cache of aggregate Stores.
+struct Cache<Agg: Aggregate> {
+    entries: HashMap<Agg::Id, Entry<Agg>>,
+    recency: VecDeque<Agg::Id>,
+}
+
+impl<Agg: Aggregate> Cache<Agg> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            recency: VecDeque::new(),
+        }
+    }
+
+    fn touch(&mut self, id: &Agg::Id) {
+        if let Some(pos) = self.recency.iter().position(|cached| cached == id) {
+            self.recency.remove(pos);
+        }
+        self.recency.push_back(id.clone());
+    }
+
+    fn get(&mut self, id: &Agg::Id) -> Option<Entry<Agg>> {
+        let entry = self.entries.get(id).cloned()?;
+        self.touch(id);
+        Some(entry)
+    }
+
+    /// Insert unless another task won the race, returning the cached entry
+    fn insert(&mut self, id: &Agg::Id, entry: Entry<Agg>, capacity: usize) -> Entry<Agg> {
+        let entry = self.entries.entry(id.clone()).or_insert(entry).clone();
+        self.touch(id);
+        while self.entries.len() > capacity {
+            let Some(oldest) = self.recency.pop_front() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        entry
+    }
+
+    fn remove(&mut self, id: &Agg::Id) {
+        self.entries.remove(id);
+        self.recency.retain(|cached| cached != id);
+    }
+}
+
+/// Imperative `execute(id, command)` facade over per-aggregate Stores.
+///
+/// # Example
+///
+/// ```ignore
+/// let orders = AggregateRepository::new(OrderAggregate::new(event_store, clock))
+///     .with_cache(1_000)
+///     .with_timeout(Duration::from_secs(5));
+///
+/// match orders.execute(&order_id, OrderCommand::Cancel { reason }).await {
+///     Ok(OrderEvent::Cancelled { .. }) => { /* ... */ }
+///     Err(RepositoryError::Rejected(reason)) => { /* business rule violated */ }
+///     Err(RepositoryError::Store(e)) => { /* infrastructure failure */ }
+/// }
+/// ```
+pub struct AggregateRepository<Agg: Aggregate> {
+    aggregate: Arc<Agg>,
+    cache: Arc<Mutex<Cache<Agg>>>,
+    cache_capacity: Option<usize>,
+    timeout: Duration,
+}
+
+impl<Agg: Aggregate> AggregateRepository<Agg> {
+    /// Create a repository without caching and a 10 second command timeout.
+    #[must_use]
+    pub fn new(aggregate: Agg) -> Self {
+        Self {
+            aggregate: Arc::new(aggregate),
+            cache: Arc::new(Mutex::new(Cache::new())),
+            cache_capacity: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Keep up to `capacity` aggregate Stores in memory (least recently used evicted).
+    #[must_use]
+    pub const fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Set the timeout for hydration and for waiting on a command's outcome.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The aggregate definition.
+    #[must_use]
+    pub fn aggregate(&self) -> &Agg {
+        &self.aggregate
+    }
+
+    /// Execute a command against the aggregate with the given id.
+    ///
+    /// Loads (or creates) the aggregate, sends the command, and waits for the
+    /// action classified by [`Aggregate::outcome`].
+    ///
+    /// # Errors
+    ///
+    /// - [`RepositoryError::Rejected`] if the aggregate rejected the command
+    /// - [`RepositoryError::Store`] with [`StoreError::Timeout`] if no outcome
+    ///   arrived within the timeout, or any other error from the Store
+    pub async fn execute(
+        &self,
+        id: &Agg::Id,
+        command: Agg::Command,
+    ) -> Result<Agg::Event, RepositoryError<Agg::Error>> {
+        let entry = self.checkout(id).await?;
+        let _serialized = entry.lock.lock().await;
+
+        let action = self.aggregate.command(id, command);
+        let reply = entry
+            .store
+            .send_and_wait_for(action, |a| Agg::outcome(a).is_some(), self.timeout)
+            .await?;
+
+        Agg::outcome(&reply)
+            .ok_or(RepositoryError::Store(StoreError::Timeout))?
+            .map_err(RepositoryError::Rejected)
+    }
+
+    /// Read the state of the aggregate with the given id.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StoreError`] if hydrating a newly created aggregate fails.
+    pub async fn state<F, T>(&self, id: &Agg::Id, f: F) -> Result<T, StoreError>
+    where
+        F: FnOnce(&Agg::State) -> T,
+    {
+        let entry = self.checkout(id).await?;
+        Ok(entry.store.state(f).await)
+    }
+
+    /// Get the Store backing an aggregate, loading it if necessary.
+    ///
+    /// Useful for subscribing to the aggregate's actions.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StoreError`] if hydrating a newly created aggregate fails.
+    pub async fn load(&self, id: &Agg::Id) -> Result<AggregateStore<Agg>, StoreError> {
+        Ok(self.checkout(id).await?.store)
+    }
+
+    /// Drop a cached aggregate so the next call reloads it.
+    pub fn evict(&self, id: &Agg::Id) {
+        self.lock_cache().remove(id);
+    }
+
+    /// Number of aggregates currently cached.
+    #[must_use]
+    pub fn cached_len(&self) -> usize {
+        self.lock_cache().entries.len()
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, Cache<Agg>> {
+        self.cache
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    async fn checkout(&self, id: &Agg::Id) -> Result<Entry<Agg>, StoreError> {
+        if self.cache_capacity.is_some() {
+            if let Some(entry) = self.lock_cache().get(id) {
+                metrics::counter!("aggregate_repository.cache.hits").increment(1);
+                return Ok(entry);
+            }
+            metrics::counter!("aggregate_repository.cache.misses").increment(1);
+        }
+
+        let store = Store::new(
+            self.aggregate.initial_state(id),
+            self.aggregate.reducer(),
+            self.aggregate.environment(id),
+        );
+
+        if let Some(action) = self.aggregate.load_action(id) {
+            let mut handle = store.send(action).await?;
+            handle
+                .wait_with_timeout(self.timeout)
+                .await
+                .map_err(|()| StoreError::Timeout)?;
+        }
+
+        let entry = Entry {
+            store,
+            lock: Arc::new(tokio::sync::Mutex::new(())),
+        };
+
+        Ok(match self.cache_capacity {
+            Some(capacity) => self.lock_cache().insert(id, entry, capacity),
+            None => entry,
+        })
+    }
+}
+
+impl<Agg: Aggregate> Clone for AggregateRepository<Agg> {
+    fn clone(&self) -> Self {
+        Self {
+            aggregate: Arc::clone(&self.aggregate),
+            cache: Arc::clone(&self.cache),
+            cache_capacity: self.cache_capacity,
+            timeout: self.timeout,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use composable_rust_core::{effect::Effect, smallvec, SmallVec};
+
+    /// Balances persisted by effects, standing in for an event store
+    type Ledger = Arc<Mutex<HashMap<String, i64>>>;
+
+    #[derive(Debug, Default)]
+    struct AccountState {
+        balance: i64,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum AccountAction {
+        Hydrate,
+        Loaded(i64),
+        Deposit(i64),
+        Withdraw(i64),
+        Changed(i64),
+        Rejected(String),
+    }
+
+    #[derive(Clone)]
+    struct AccountEnv {
+        id: String,
+        ledger: Ledger,
+    }
+
+    #[derive(Clone)]
+    struct AccountReducer;
+
+    impl Reducer for AccountReducer {
+        type State = AccountState;
+        type Action = AccountAction;
+        type Environment = AccountEnv;
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            let persist = |balance: i64| {
+                let env = env.clone();
+                Effect::Future(Box::pin(async move {
+                    env.ledger.lock().unwrap().insert(env.id, balance);
+                    Some(AccountAction::Changed(balance))
+                }))
+            };
+            match action {
+                AccountAction::Hydrate => {
+                    let env = env.clone();
+                    smallvec![Effect::Future(Box::pin(async move {
+                        let balance = env.ledger.lock().unwrap().get(&env.id).copied();
+                        balance.map(AccountAction::Loaded)
+                    }))]
+                },
+                AccountAction::Loaded(balance) => {
+                    state.balance = balance;
+                    smallvec![Effect::None]
+                },
+                AccountAction::Deposit(amount) => {
+                    state.balance += amount;
+                    smallvec![persist(state.balance)]
+                },
+                AccountAction::Withdraw(amount) if amount > state.balance => {
+                    smallvec![Effect::Future(Box::pin(async {
+                        Some(AccountAction::Rejected("insufficient funds".to_string()))
+                    }))]
+                },
+                AccountAction::Withdraw(amount) => {
+                    state.balance -= amount;
+                    smallvec![persist(state.balance)]
+                },
+                AccountAction::Changed(_) | AccountAction::Rejected(_) => smallvec![Effect::None],
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct Accounts {
+        ledger: Ledger,
+    }
+
+    impl Aggregate for Accounts {
+        type Id = String;
+        type State = AccountState;
+        type Action = AccountAction;
+        type Environment = AccountEnv;
+        type Reducer = AccountReducer;
+        type Command = AccountAction;
+        type Event = i64;
+        type Error = String;
+
+        fn initial_state(&self, _id: &String) -> AccountState {
+            AccountState::default()
+        }
+
+        fn reducer(&self) -> AccountReducer {
+            AccountReducer
+        }
+
+        fn environment(&self, id: &String) -> AccountEnv {
+            AccountEnv {
+                id: id.clone(),
+                ledger: Arc::clone(&self.ledger),
+            }
+        }
+
+        fn command(&self, _id: &String, command: AccountAction) -> AccountAction {
+            command
+        }
+
+        fn outcome(action: &AccountAction) -> Option<Result<i64, String>> {
+            match action {
+                AccountAction::Changed(balance) => Some(Ok(*balance)),
+                AccountAction::Rejected(reason) => Some(Err(reason.clone())),
+                _ => None,
+            }
+        }
+
+        fn load_action(&self, _id: &String) -> Option<AccountAction> {
+            Some(AccountAction::Hydrate)
+        }
+    }
+
+    fn id(name: &str) -> String {
+        name.to_string()
+    }
+
+    #[tokio::test]
+    async fn test_execute_returns_typed_event() {
+        let repo = AggregateRepository::new(Accounts::default()).with_cache(10);
+
+        let balance = repo.execute(&id("a"), AccountAction::Deposit(100)).await.unwrap();
+        assert_eq!(balance, 100);
+
+        let balance = repo.execute(&id("a"), AccountAction::Withdraw(30)).await.unwrap();
+        assert_eq!(balance, 70);
+        assert_eq!(repo.state(&id("a"), |s| s.balance).await.unwrap(), 70);
+    }
+
+    #[tokio::test]
+    async fn test_execute_returns_typed_rejection() {
+        let repo = AggregateRepository::new(Accounts::default());
+
+        let result = repo.execute(&id("a"), AccountAction::Withdraw(1)).await;
+
+        assert!(matches!(result, Err(RepositoryError::Rejected(reason)) if reason == "insufficient funds"));
+    }
+
+    #[tokio::test]
+    async fn test_uncached_repository_hydrates_each_call() {
+        let repo = AggregateRepository::new(Accounts::default());
+
+        repo.execute(&id("a"), AccountAction::Deposit(50)).await.unwrap();
+        let balance = repo.execute(&id("a"), AccountAction::Deposit(25)).await.unwrap();
+
+        assert_eq!(balance, 75);
+        assert_eq!(repo.cached_len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cache_evicts_least_recently_used() {
+        let repo = AggregateRepository::new(Accounts::default()).with_cache(2);
+
+        repo.execute(&id("a"), AccountAction::Deposit(1)).await.unwrap();
+        repo.execute(&id("b"), AccountAction::Deposit(1)).await.unwrap();
+        repo.execute(&id("a"), AccountAction::Deposit(1)).await.unwrap();
+        repo.execute(&id("c"), AccountAction::Deposit(1)).await.unwrap();
+
+        assert_eq!(repo.cached_len(), 2);
+        let cache = repo.lock_cache();
+        assert!(cache.entries.contains_key("a"));
+        assert!(!cache.entries.contains_key("b"));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_commands_on_same_aggregate_are_serialized() {
+        let repo = AggregateRepository::new(Accounts::default()).with_cache(10);
+
+        let mut tasks = Vec::new();
+        for _ in 0..20 {
+            let repo = repo.clone();
+            tasks.push(tokio::spawn(async move {
+                repo.execute(&id("a"), AccountAction::Deposit(1)).await
+            }));
+        }
+        let mut balances = Vec::new();
+        for task in tasks {
+            balances.push(task.await.unwrap().unwrap());
+        }
+        balances.sort_unstable();
+
+        assert_eq!(balances, (1..=20).collect::<Vec<_>>());
+    }
+}
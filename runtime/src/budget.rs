@@ -0,0 +1,401 @@
+//! Effect cost accounting and per-action budgets.
+//!
+//! Effects can declare a cost with [`Effect::with_cost`]. In addition, the
+//! runtime charges implicit costs for infrastructure operations:
+//!
+//! - [`EVENT_STORE`]: 1 per `Effect::EventStore` operation
+//! - [`EVENT_BUS`]: 1 per `Effect::PublishEvent` operation
+//!
+//! Costs accumulate per **root action**: an action sent by a caller opens a
+//! cost ledger, and every action fed back by its effects (directly or through
+//! further feedback) is charged to the same ledger. An [`EffectBudgets`]
+//! configuration maps each action kind to a [`Budget`] of per-category limits.
+//!
+//! Costs are tallied from the effect tree right after the reducer runs, before
+//! any effect is started. When a limit would be exceeded the budget's
+//! [`OverBudget`] policy decides what happens:
+//!
+//! - [`OverBudget::Reject`]: the effects are dropped (state changes made by the
+//!   reducer are kept) and `send()` returns [`StoreError::BudgetExceeded`]
+//! - [`OverBudget::Flag`]: the effects run anyway, and the overrun is logged
+//!
+//! # Metrics
+//!
+//! Reported through the Store's [`MetricsRecorder`]:
+//!
+//! - `store.effects.cost{action, cost}`: units charged (counter)
+//! - `store.budget.exceeded{action, cost, outcome}`: budget overruns (counter)
+//! - `store.budget.root_cost{action, cost}`: total cost of a root action and its
+//!   cascade, recorded once the cascade finishes (histogram)
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_core::effect::EffectCost;
+//! use composable_rust_runtime::budget::{Budget, EffectBudgets, EVENT_STORE};
+//!
+//! // In the reducer: declare an HTTP call
+//! let effect = Effect::Future(Box::pin(fetch_rates())).with_cost(EffectCost::new("http", 1));
+//!
+//! // At wiring time: at most 2 HTTP calls and 10 event store ops per checkout
+//! let budgets = EffectBudgets::new(|action: &ShopAction| match action {
+//!     ShopAction::Checkout { .. } => "checkout",
+//!     _ => "other",
+//! })
+//! .with_budget("checkout", Budget::new().with_limit("http", 2).with_limit(EVENT_STORE, 10));
+//!
+//! let store = Store::new(state, reducer, env).with_effect_budgets(budgets);
+//! ```
+//!
+//! [`StoreError::BudgetExceeded`]: crate::StoreError::BudgetExceeded
+
+use crate::StoreError;
+use crate::metrics::{MetricLabel, MetricsRecorder, StoreLabels};
+use composable_rust_core::effect::Effect;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Cost category charged for every event store operation
+pub const EVENT_STORE: &str = "event_store";
+
+/// Cost category charged for every event bus publish
+pub const EVENT_BUS: &str = "event_bus";
+
+/// Cost category charged for every read model query
+pub const READ_MODEL: &str = "read_model";
+
+/// What the Store does when an action's effects exceed its budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverBudget {
+    /// Drop the effects and return [`StoreError::BudgetExceeded`]
+    #[default]
+    Reject,
+    /// Run the effects, logging and counting the overrun
+    Flag,
+}
+
+/// Per-category cost limits for one kind of root action
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    limits: HashMap<Cow<'static, str>, u64>,
+    on_exceed: OverBudget,
+}
+
+impl Budget {
+    /// Create a budget with no limits that rejects overruns
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow at most `max` units of `kind` per root action
+    #[must_use]
+    pub fn with_limit(mut self, kind: impl Into<Cow<'static, str>>, max: u64) -> Self {
+        self.limits.insert(kind.into(), max);
+        self
+    }
+
+    /// Set the policy applied when a limit is exceeded
+    #[must_use]
+    pub const fn with_on_exceed(mut self, policy: OverBudget) -> Self {
+        self.on_exceed = policy;
+        self
+    }
+
+    /// Limit configured for `kind`, if any
+    #[must_use]
+    pub fn limit(&self, kind: &str) -> Option<u64> {
+        self.limits.get(kind).copied()
+    }
+
+    /// Policy applied when a limit is exceeded
+    #[must_use]
+    pub const fn on_exceed(&self) -> OverBudget {
+        self.on_exceed
+    }
+}
+
+/// Classifier returning the kind label of an action
+type Classifier<A> = Arc<dyn Fn(&A) -> &'static str + Send + Sync>;
+
+/// Budgets for a Store, keyed by action kind
+///
+/// The classifier maps each root action to a kind label, which selects the
+/// budget and labels the cost metrics. Kinds without a budget fall back to the
+/// default budget, or are only metered when no default is set.
+pub struct EffectBudgets<A> {
+    classify: Classifier<A>,
+    budgets: HashMap<&'static str, Budget>,
+    default_budget: Option<Budget>,
+}
+
+impl<A> EffectBudgets<A> {
+    /// Create budgets using `classify` to label root actions
+    #[must_use]
+    pub fn new(classify: impl Fn(&A) -> &'static str + Send + Sync + 'static) -> Self {
+        Self {
+            classify: Arc::new(classify),
+            budgets: HashMap::new(),
+            default_budget: None,
+        }
+    }
+
+    /// Set the budget for root actions classified as `action_kind`
+    #[must_use]
+    pub fn with_budget(mut self, action_kind: &'static str, budget: Budget) -> Self {
+        self.budgets.insert(action_kind, budget);
+        self
+    }
+
+    /// Set the budget for action kinds without a dedicated budget
+    #[must_use]
+    pub fn with_default(mut self, budget: Budget) -> Self {
+        self.default_budget = Some(budget);
+        self
+    }
+
+    /// Open a ledger for a root action, reporting to `metrics`
+    pub(crate) fn open(
+        &self,
+        action: &A,
+        metrics: Arc<dyn MetricsRecorder>,
+        labels: Arc<StoreLabels>,
+    ) -> Arc<CostLedger> {
+        let action_kind = (self.classify)(action);
+        let budget = self
+            .budgets
+            .get(action_kind)
+            .or(self.default_budget.as_ref())
+            .cloned();
+
+        Arc::new(CostLedger {
+            action_kind,
+            budget,
+            spent: Mutex::new(HashMap::new()),
+            metrics,
+            labels,
+        })
+    }
+}
+
+impl<A> std::fmt::Debug for EffectBudgets<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EffectBudgets")
+            .field("budgets", &self.budgets)
+            .field("default_budget", &self.default_budget)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Accumulated cost of one root action and its feedback cascade
+///
+/// Shared by every action in the cascade; the total is recorded as a metric
+/// when the last reference is dropped.
+#[derive(Debug)]
+pub(crate) struct CostLedger {
+    action_kind: &'static str,
+    budget: Option<Budget>,
+    spent: Mutex<HashMap<Cow<'static, str>, u64>>,
+    metrics: Arc<dyn MetricsRecorder>,
+    labels: Arc<StoreLabels>,
+}
+
+impl CostLedger {
+    /// Charge the costs of a batch of effects
+    ///
+    /// With [`OverBudget::Reject`], nothing is charged if any limit would be
+    /// exceeded.
+    pub(crate) fn charge(&self, costs: HashMap<Cow<'static, str>, u64>) -> Result<(), StoreError> {
+        if costs.is_empty() {
+            return Ok(());
+        }
+
+        let mut spent = self.spent.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        if let Some(budget) = &self.budget {
+            for (kind, weight) in &costs {
+                let Some(limit) = budget.limit(kind) else {
+                    continue;
+                };
+                let total = spent.get(kind).copied().unwrap_or(0).saturating_add(*weight);
+                if total <= limit {
+                    continue;
+                }
+
+                let outcome = match budget.on_exceed {
+                    OverBudget::Reject => "rejected",
+                    OverBudget::Flag => "flagged",
+                };
+                tracing::warn!(
+                    action = self.action_kind,
+                    cost = %kind,
+                    limit,
+                    total,
+                    outcome,
+                    "Effect budget exceeded"
+                );
+                self.metrics.increment_counter(
+                    "store.budget.exceeded",
+                    &[
+                        ("action", MetricLabel::from_static(self.action_kind)),
+                        ("cost", self.labels.interner.intern_cow(kind)),
+                        ("outcome", MetricLabel::from_static(outcome)),
+                    ],
+                    1,
+                );
+
+                if budget.on_exceed == OverBudget::Reject {
+                    return Err(StoreError::BudgetExceeded {
+                        action: self.action_kind,
+                        cost: kind.to_string(),
+                        limit,
+                        total,
+                    });
+                }
+            }
+        }
+
+        for (kind, weight) in costs {
+            self.metrics.increment_counter(
+                "store.effects.cost",
+                &[
+                    ("action", MetricLabel::from_static(self.action_kind)),
+                    ("cost", self.labels.interner.intern_cow(&kind)),
+                ],
+                weight,
+            );
+            *spent.entry(kind).or_insert(0) += weight;
+        }
+        Ok(())
+    }
+
+    /// Units of `kind` charged so far
+    #[cfg(test)]
+    pub(crate) fn spent(&self, kind: &str) -> u64 {
+        self.spent
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(kind)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl Drop for CostLedger {
+    fn drop(&mut self) {
+        let spent = self.spent.get_mut().unwrap_or_else(std::sync::PoisonError::into_inner);
+        for (kind, total) in spent.drain() {
+            #[allow(clippy::cast_precision_loss)] // Costs are far below 2^52
+            self.metrics.record_histogram(
+                "store.budget.root_cost",
+                &[
+                    ("action", MetricLabel::from_static(self.action_kind)),
+                    ("cost", self.labels.interner.intern_cow(&kind)),
+                ],
+                total as f64,
+            );
+        }
+    }
+}
+
+/// Add up the declared and implicit costs of an effect tree
+pub(crate) fn tally<A>(effect: &Effect<A>, costs: &mut HashMap<Cow<'static, str>, u64>) {
+    match effect {
+        Effect::None
+        | Effect::Delay { .. }
+        | Effect::DelayKeyed { .. }
+        | Effect::Future(_)
+        | Effect::FutureWithCancel(_)
+        | Effect::Stream(_) => {},
+        Effect::Parallel(effects) | Effect::Sequential(effects) => {
+            for effect in effects {
+                tally(effect, costs);
+            }
+        },
+        Effect::EventStore(_) => *costs.entry(Cow::Borrowed(EVENT_STORE)).or_insert(0) += 1,
+        Effect::PublishEvent(_) => *costs.entry(Cow::Borrowed(EVENT_BUS)).or_insert(0) += 1,
+        Effect::Query(_) => *costs.entry(Cow::Borrowed(READ_MODEL)).or_insert(0) += 1,
+        Effect::Costed { cost, effect } => {
+            *costs.entry(cost.kind.clone()).or_insert(0) += cost.weight;
+            tally(effect, costs);
+        },
+        Effect::Retrying { effect, .. } => tally(effect, costs),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use crate::metrics::{CapturingRecorder, NoopRecorder};
+    use composable_rust_core::effect::EffectCost;
+
+    fn http() -> Effect<u32> {
+        Effect::Future(Box::pin(async { None })).with_cost(EffectCost::new("http", 1))
+    }
+
+    fn costs_of(effects: &[Effect<u32>]) -> HashMap<Cow<'static, str>, u64> {
+        let mut costs = HashMap::new();
+        for effect in effects {
+            tally(effect, &mut costs);
+        }
+        costs
+    }
+
+    #[test]
+    fn test_tally_sums_nested_declared_costs() {
+        let effects = vec![
+            http(),
+            Effect::Parallel(vec![http(), Effect::Sequential(vec![http()])]),
+            Effect::None,
+        ];
+
+        let costs = costs_of(&effects);
+
+        assert_eq!(costs.get("http"), Some(&3));
+        assert_eq!(costs.len(), 1);
+    }
+
+    #[test]
+    fn test_ledger_rejects_without_charging() {
+        let budgets = EffectBudgets::new(|_: &u32| "any")
+            .with_default(Budget::new().with_limit("http", 2));
+        let recorder = CapturingRecorder::new();
+        let ledger = budgets.open(&0, Arc::new(recorder.clone()), Arc::default());
+
+        ledger.charge(costs_of(&[http(), http()])).unwrap();
+        let result = ledger.charge(costs_of(&[http()]));
+
+        assert!(matches!(
+            result,
+            Err(StoreError::BudgetExceeded { limit: 2, total: 3, .. })
+        ));
+        assert_eq!(ledger.spent("http"), 2);
+        let exceeded = recorder.events_named("store.budget.exceeded");
+        assert_eq!(exceeded.len(), 1);
+        assert_eq!(exceeded[0].label("outcome"), Some("rejected"));
+    }
+
+    #[test]
+    fn test_ledger_flags_and_keeps_charging() {
+        let budgets = EffectBudgets::new(|_: &u32| "any")
+            .with_default(Budget::new().with_limit("http", 1).with_on_exceed(OverBudget::Flag));
+        let ledger = budgets.open(&0, Arc::new(NoopRecorder), Arc::default());
+
+        ledger.charge(costs_of(&[http(), http()])).unwrap();
+
+        assert_eq!(ledger.spent("http"), 2);
+    }
+
+    #[test]
+    fn test_budget_selected_by_action_kind() {
+        let budgets = EffectBudgets::new(|n: &u32| if *n == 0 { "strict" } else { "lenient" })
+            .with_budget("strict", Budget::new().with_limit("http", 0));
+
+        let open = |n: u32| budgets.open(&n, Arc::new(NoopRecorder), Arc::default());
+
+        assert!(open(0).charge(costs_of(&[http()])).is_err());
+        assert!(open(1).charge(costs_of(&[http()])).is_ok());
+    }
+}
@@ -0,0 +1,387 @@
+//! Best-effort two-phase commit across stores in the same process.
+//!
+//! Occasionally two local stores must both accept an action or neither, for
+//! example a transfer between two account shards. [`coordinate`] runs a
+//! prepare/commit protocol over a set of [`Participant`]s:
+//!
+//! 1. **Prepare**: every participant's prepare action is sent concurrently.
+//!    The reducer validates it, reserves whatever it needs, and replies with a
+//!    vote action from an effect (e.g. `Prepared` or `PrepareRejected`).
+//! 2. **Commit**: if every participant voted yes before the timeout, every
+//!    participant's commit action is sent.
+//! 3. **Abort**: otherwise every participant that did not explicitly vote no
+//!    (it may have voted yes, or timed out after reserving) is sent its abort
+//!    action.
+//!
+//! # Guarantees (and non-guarantees)
+//!
+//! This is **not** distributed or durable 2PC:
+//!
+//! - There is no coordinator log. If the process crashes between prepare and
+//!   commit, reservations are left in place, and reducers need their own
+//!   expiry or recovery for them.
+//! - A commit action can still fail to be admitted (store shutting down, rate
+//!   limited, ...). The other participants stay committed and
+//!   [`CoordinationError::CommitIncomplete`] names the ones that were not; the
+//!   caller must reconcile.
+//! - Isolation is whatever the reducers implement: between prepare and commit,
+//!   other actions are reduced as usual. Reducers should treat prepared
+//!   reservations as locked.
+//! - Commit and abort actions must be accepted unconditionally by reducers
+//!   once they voted yes.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::coordination::{coordinate, StoreParticipant, Vote};
+//!
+//! let vote = |a: &AccountAction| match a {
+//!     AccountAction::Prepared { tx } if *tx == tx_id => Some(Vote::Yes),
+//!     AccountAction::PrepareRejected { tx, reason } if *tx == tx_id => Some(Vote::No(reason.clone())),
+//!     _ => None,
+//! };
+//!
+//! let from = StoreParticipant::new(
+//!     "shard-a", shard_a.clone(), AccountAction::Reserve { tx: tx_id, delta: -100 }, vote,
+//!     AccountAction::Commit { tx: tx_id }, AccountAction::Abort { tx: tx_id },
+//! );
+//! let to = StoreParticipant::new(
+//!     "shard-b", shard_b.clone(), AccountAction::Reserve { tx: tx_id, delta: 100 }, vote,
+//!     AccountAction::Commit { tx: tx_id }, AccountAction::Abort { tx: tx_id },
+//! );
+//!
+//! coordinate(&[&from, &to], Duration::from_secs(5)).await?;
+//! ```
+
+use crate::{Store, StoreError};
+use composable_rust_core::reducer::Reducer;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use thiserror::Error;
+
+/// A participant's answer to the prepare phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Vote {
+    /// The participant prepared and can commit
+    Yes,
+    /// The participant refused, with a reason
+    No(String),
+}
+
+/// Errors returned by [`coordinate`]
+#[derive(Error, Debug)]
+pub enum CoordinationError {
+    /// A participant voted no or failed to prepare; abort actions were sent
+    #[error("Coordination aborted by {participant}: {reason}")]
+    Aborted {
+        /// First participant (in the given order) that did not vote yes
+        participant: String,
+        /// Its refusal reason, or the error that prevented its vote
+        reason: String,
+    },
+
+    /// Every participant voted yes, but some commit actions were not admitted
+    #[error("Commit incomplete for {failed:?}")]
+    CommitIncomplete {
+        /// Participants whose commit action failed
+        failed: Vec<String>,
+    },
+}
+
+/// Boxed future returned by [`Participant`] methods
+pub type ParticipantFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// One side of a coordinated action
+///
+/// Implemented by [`StoreParticipant`]; dyn-compatible so stores of different
+/// types can be coordinated together.
+pub trait Participant: Send + Sync {
+    /// Name used in errors and logs
+    fn name(&self) -> &str;
+
+    /// Send the prepare action and wait up to `timeout` for the vote
+    fn prepare(&self, timeout: Duration) -> ParticipantFuture<'_, Result<Vote, StoreError>>;
+
+    /// Send the commit action
+    fn commit(&self) -> ParticipantFuture<'_, Result<(), StoreError>>;
+
+    /// Send the abort action
+    fn abort(&self) -> ParticipantFuture<'_, Result<(), StoreError>>;
+}
+
+/// Classifies effect-produced actions as votes (`None` = not a vote)
+type VoteFn<A> = Box<dyn Fn(&A) -> Option<Vote> + Send + Sync>;
+
+/// A [`Store`] taking part in [`coordinate`]
+pub struct StoreParticipant<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    name: String,
+    store: Store<S, A, E, R>,
+    prepare: A,
+    vote: VoteFn<A>,
+    commit: A,
+    abort: A,
+}
+
+impl<S, A, E, R> StoreParticipant<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    /// Describe the prepare, commit and abort actions for `store`
+    ///
+    /// `vote` is applied to the actions produced by the store's effects after
+    /// the prepare action is sent; the first one it maps to a [`Vote`] is the
+    /// participant's answer. Include a transaction ID in the actions so
+    /// concurrent coordinations cannot observe each other's votes.
+    #[must_use]
+    pub fn new(
+        name: impl Into<String>,
+        store: Store<S, A, E, R>,
+        prepare: A,
+        vote: impl Fn(&A) -> Option<Vote> + Send + Sync + 'static,
+        commit: A,
+        abort: A,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            store,
+            prepare,
+            vote: Box::new(vote),
+            commit,
+            abort,
+        }
+    }
+}
+
+impl<S, A, E, R> Participant for StoreParticipant<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E> + Clone + Send + Sync + 'static,
+    A: Send + Sync + Clone + 'static,
+    S: Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn prepare(&self, timeout: Duration) -> ParticipantFuture<'_, Result<Vote, StoreError>> {
+        Box::pin(async move {
+            let reply = self
+                .store
+                .send_and_wait_for(self.prepare.clone(), |a| (self.vote)(a).is_some(), timeout)
+                .await?;
+            Ok((self.vote)(&reply).unwrap_or(Vote::No("unrecognized vote".to_string())))
+        })
+    }
+
+    fn commit(&self) -> ParticipantFuture<'_, Result<(), StoreError>> {
+        Box::pin(async move { self.store.send(self.commit.clone()).await.map(|_| ()) })
+    }
+
+    fn abort(&self) -> ParticipantFuture<'_, Result<(), StoreError>> {
+        Box::pin(async move { self.store.send(self.abort.clone()).await.map(|_| ()) })
+    }
+}
+
+/// Prepare every participant, then commit all of them or abort
+///
+/// Prepares run concurrently and each waits up to `timeout` for its vote. See
+/// the [module documentation](self) for the exact guarantees.
+///
+/// # Errors
+///
+/// - [`CoordinationError::Aborted`]: a participant voted no, timed out, or
+///   rejected the prepare action; abort actions were sent
+/// - [`CoordinationError::CommitIncomplete`]: all votes were yes but some
+///   commit actions were not admitted
+pub async fn coordinate(
+    participants: &[&dyn Participant],
+    timeout: Duration,
+) -> Result<(), CoordinationError> {
+    let votes =
+        futures::future::join_all(participants.iter().map(|p| p.prepare(timeout))).await;
+
+    let refusal = participants.iter().zip(&votes).find_map(|(p, vote)| match vote {
+        Ok(Vote::Yes) => None,
+        Ok(Vote::No(reason)) => Some((p.name().to_string(), reason.clone())),
+        Err(error) => Some((p.name().to_string(), error.to_string())),
+    });
+
+    if let Some((participant, reason)) = refusal {
+        tracing::warn!(%participant, %reason, "Coordination aborted");
+        let to_abort = participants
+            .iter()
+            .zip(&votes)
+            .filter(|(_, vote)| !matches!(vote, Ok(Vote::No(_))))
+            .map(|(p, _)| *p);
+        for (p, result) in
+            futures::future::join_all(to_abort.map(|p| async move { (p, p.abort().await) })).await
+        {
+            if let Err(error) = result {
+                tracing::error!(participant = p.name(), %error, "Failed to send abort action");
+            }
+        }
+        return Err(CoordinationError::Aborted { participant, reason });
+    }
+
+    let results =
+        futures::future::join_all(participants.iter().map(|p| p.commit())).await;
+    let failed: Vec<String> = participants
+        .iter()
+        .zip(results)
+        .filter_map(|(p, result)| {
+            result.err().map(|error| {
+                tracing::error!(participant = p.name(), %error, "Failed to send commit action");
+                p.name().to_string()
+            })
+        })
+        .collect();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(CoordinationError::CommitIncomplete { failed })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use composable_rust_core::{effect::Effect, smallvec, SmallVec};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum AccountAction {
+        Reserve(i64),
+        /// Prepare that never votes
+        Hang,
+        Prepared,
+        Refused(String),
+        Commit,
+        Abort,
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct Account {
+        balance: i64,
+        reserved: Option<i64>,
+        aborted: usize,
+    }
+
+    #[derive(Debug, Clone)]
+    struct AccountReducer;
+
+    fn reply(action: AccountAction) -> Effect<AccountAction> {
+        Effect::Future(Box::pin(async move { Some(action) }))
+    }
+
+    impl Reducer for AccountReducer {
+        type State = Account;
+        type Action = AccountAction;
+        type Environment = ();
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            _env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            match action {
+                AccountAction::Reserve(delta) if state.balance + delta >= 0 => {
+                    state.reserved = Some(delta);
+                    smallvec![reply(AccountAction::Prepared)]
+                },
+                AccountAction::Reserve(_) => {
+                    smallvec![reply(AccountAction::Refused("insufficient funds".into()))]
+                },
+                AccountAction::Hang => {
+                    state.reserved = Some(0);
+                    smallvec![Effect::None]
+                },
+                AccountAction::Commit => {
+                    state.balance += state.reserved.take().unwrap_or(0);
+                    smallvec![Effect::None]
+                },
+                AccountAction::Abort => {
+                    state.reserved = None;
+                    state.aborted += 1;
+                    smallvec![Effect::None]
+                },
+                AccountAction::Prepared | AccountAction::Refused(_) => smallvec![Effect::None],
+            }
+        }
+    }
+
+    type AccountStore = Store<Account, AccountAction, (), AccountReducer>;
+
+    fn account(balance: i64) -> AccountStore {
+        Store::new(Account { balance, ..Account::default() }, AccountReducer, ())
+    }
+
+    fn participant(
+        name: &str,
+        store: &AccountStore,
+        prepare: AccountAction,
+    ) -> StoreParticipant<Account, AccountAction, (), AccountReducer> {
+        StoreParticipant::new(
+            name,
+            store.clone(),
+            prepare,
+            |a| match a {
+                AccountAction::Prepared => Some(Vote::Yes),
+                AccountAction::Refused(reason) => Some(Vote::No(reason.clone())),
+                _ => None,
+            },
+            AccountAction::Commit,
+            AccountAction::Abort,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_all_yes_commits_everywhere() {
+        let (a, b) = (account(100), account(0));
+        let from = participant("a", &a, AccountAction::Reserve(-60));
+        let to = participant("b", &b, AccountAction::Reserve(60));
+
+        coordinate(&[&from, &to], Duration::from_secs(1)).await.unwrap();
+
+        assert_eq!(a.state(|s| s.balance).await, 40);
+        assert_eq!(b.state(|s| s.balance).await, 60);
+    }
+
+    #[tokio::test]
+    async fn test_no_vote_aborts_prepared_participants() {
+        let (a, b) = (account(10), account(0));
+        let from = participant("a", &a, AccountAction::Reserve(-60));
+        let to = participant("b", &b, AccountAction::Reserve(60));
+
+        let result = coordinate(&[&from, &to], Duration::from_secs(1)).await;
+
+        assert!(matches!(
+            result,
+            Err(CoordinationError::Aborted { ref participant, ref reason })
+                if participant == "a" && reason == "insufficient funds"
+        ));
+        assert_eq!(a.state(|s| (s.balance, s.aborted)).await, (10, 0));
+        assert_eq!(b.state(|s| (s.balance, s.reserved, s.aborted)).await, (0, None, 1));
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_participant_is_aborted() {
+        let (a, b) = (account(100), account(0));
+        let from = participant("a", &a, AccountAction::Reserve(-60));
+        let to = participant("b", &b, AccountAction::Hang);
+
+        let result = coordinate(&[&from, &to], Duration::from_millis(50)).await;
+
+        assert!(matches!(
+            result,
+            Err(CoordinationError::Aborted { ref participant, .. }) if participant == "b"
+        ));
+        assert_eq!(a.state(|s| (s.balance, s.aborted)).await, (100, 1));
+        assert_eq!(b.state(|s| s.aborted).await, 1);
+    }
+}
@@ -0,0 +1,549 @@
+//! Store-level graceful degradation while a dependency is down.
+//!
+//! A [`DegradationPolicy`] ties a dependency's [`CircuitBreaker`] to the
+//! actions that need it. While the breaker is rejecting calls, affected
+//! actions are handled according to the policy's [`DegradationMode`] before
+//! they reach the reducer:
+//!
+//! - [`DegradationMode::Reject`]: `send()` returns [`StoreError::Degraded`]
+//! - [`DegradationMode::Queue`]: the action is held and replayed, in order,
+//!   once the breaker stops rejecting
+//! - [`DegradationMode::Fallback`]: the action is mapped to an alternative
+//!   action (e.g. an "accept offline" branch of the reducer) which is reduced
+//!   instead
+//!
+//! The breaker itself is still driven by the effects that call the dependency
+//! (`breaker.call(...)`); the policy only reads its state. Queued actions are
+//! replayed on the next action the Store admits after recovery, or explicitly
+//! with [`Store::replay_degraded`](crate::Store::replay_degraded).
+//!
+//! Each dependency is reported in [`Store::health`](crate::Store::health):
+//! the store is `Degraded` while any breaker is rejecting, and the metadata
+//! lists every dependency's circuit state and queue length.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::degradation::{Degradation, DegradationMode, DegradationPolicy};
+//!
+//! let payments = CircuitBreaker::new().with_failure_threshold(5);
+//! // Effects calling the payment gateway go through `payments.call(...)`
+//!
+//! let degradation = Degradation::new().with_policy(
+//!     DegradationPolicy::new("payments", payments.clone(), |a: &OrderAction| {
+//!         matches!(a, OrderAction::ChargeCard { .. })
+//!     })
+//!     .with_mode(DegradationMode::Queue { max_queued: 1_000 }),
+//! );
+//!
+//! let store = Store::new(state, reducer, env).with_degradation(degradation);
+//! ```
+//!
+//! [`StoreError::Degraded`]: crate::StoreError::Degraded
+
+use crate::metrics::{MetricLabel, MetricsRecorder};
+use crate::{CircuitBreaker, CircuitState};
+use composable_rust_core::event::EventMetadata;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Predicate selecting the actions that depend on a dependency
+type Affects<A> = Arc<dyn Fn(&A) -> bool + Send + Sync>;
+
+/// Maps an action to its fallback (`None` drops it)
+type FallbackFn<A> = Arc<dyn Fn(A) -> Option<A> + Send + Sync>;
+
+/// How affected actions are handled while a dependency is down
+pub enum DegradationMode<A> {
+    /// Fail `send()` with [`StoreError::Degraded`](crate::StoreError::Degraded)
+    Reject,
+    /// Hold up to `max_queued` actions and replay them after recovery
+    ///
+    /// Actions beyond the limit are rejected.
+    Queue {
+        /// Maximum number of held actions
+        max_queued: usize,
+    },
+    /// Reduce the returned action instead (`None` drops the action)
+    Fallback(FallbackFn<A>),
+}
+
+impl<A> DegradationMode<A> {
+    /// Fallback mode mapping each affected action with `f`
+    #[must_use]
+    pub fn fallback(f: impl Fn(A) -> Option<A> + Send + Sync + 'static) -> Self {
+        Self::Fallback(Arc::new(f))
+    }
+
+    /// Short label used in metrics and health checks
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Reject => "reject",
+            Self::Queue { .. } => "queue",
+            Self::Fallback(_) => "fallback",
+        }
+    }
+}
+
+impl<A> std::fmt::Debug for DegradationMode<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Reject => f.write_str("Reject"),
+            Self::Queue { max_queued } => f.debug_struct("Queue").field("max_queued", max_queued).finish(),
+            Self::Fallback(_) => f.write_str("Fallback(..)"),
+        }
+    }
+}
+
+/// Degradation behaviour for one dependency
+pub struct DegradationPolicy<A> {
+    dependency: &'static str,
+    breaker: CircuitBreaker,
+    affects: Affects<A>,
+    mode: DegradationMode<A>,
+    queue: Mutex<VecDeque<(A, Option<EventMetadata>)>>,
+}
+
+impl<A> DegradationPolicy<A> {
+    /// Guard the actions matched by `affects` with `breaker`
+    ///
+    /// The default mode is [`DegradationMode::Reject`]. Pass a clone of the
+    /// breaker the effects use, so both share its state.
+    #[must_use]
+    pub fn new(
+        dependency: &'static str,
+        breaker: CircuitBreaker,
+        affects: impl Fn(&A) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            dependency,
+            breaker,
+            affects: Arc::new(affects),
+            mode: DegradationMode::Reject,
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Set how affected actions are handled while the dependency is down
+    #[must_use]
+    pub fn with_mode(mut self, mode: DegradationMode<A>) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Name of the guarded dependency
+    #[must_use]
+    pub const fn dependency(&self) -> &'static str {
+        self.dependency
+    }
+
+    /// Number of actions currently held for replay
+    #[must_use]
+    pub fn queued(&self) -> usize {
+        self.lock_queue().len()
+    }
+
+    fn lock_queue(&self) -> std::sync::MutexGuard<'_, VecDeque<(A, Option<EventMetadata>)>> {
+        self.queue.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<A> std::fmt::Debug for DegradationPolicy<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DegradationPolicy")
+            .field("dependency", &self.dependency)
+            .field("state", &self.breaker.state())
+            .field("mode", &self.mode)
+            .field("queued", &self.queued())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Snapshot of one dependency's degradation state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyStatus {
+    /// Name of the dependency
+    pub dependency: &'static str,
+    /// Current circuit breaker state
+    pub state: CircuitState,
+    /// Whether affected actions are currently being degraded
+    pub degraded: bool,
+    /// Label of the configured [`DegradationMode`]
+    pub mode: &'static str,
+    /// Number of actions held for replay
+    pub queued: usize,
+}
+
+/// Outcome of admitting an action through the degradation policies
+pub(crate) enum Admission<A> {
+    /// Reduce this action (possibly a fallback of the original)
+    Proceed(A, Option<EventMetadata>),
+    /// The action was queued or dropped by a fallback
+    Absorbed,
+    /// The action was rejected because `dependency` is down
+    Rejected(&'static str),
+}
+
+/// Degradation policies of a Store
+#[derive(Debug)]
+pub struct Degradation<A> {
+    policies: Vec<DegradationPolicy<A>>,
+}
+
+impl<A> Degradation<A> {
+    /// Create an empty set of policies
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { policies: Vec::new() }
+    }
+
+    /// Add the policy for one dependency
+    ///
+    /// Policies are checked in the order they were added; the first one whose
+    /// dependency is down and that matches the action decides.
+    #[must_use]
+    pub fn with_policy(mut self, policy: DegradationPolicy<A>) -> Self {
+        self.policies.push(policy);
+        self
+    }
+
+    /// Current state of every dependency
+    #[must_use]
+    pub fn statuses(&self) -> Vec<DependencyStatus> {
+        self.policies
+            .iter()
+            .map(|policy| DependencyStatus {
+                dependency: policy.dependency,
+                state: policy.breaker.state(),
+                degraded: policy.breaker.is_rejecting(),
+                mode: policy.mode.label(),
+                queued: policy.queued(),
+            })
+            .collect()
+    }
+
+    /// Apply the policies of the dependencies that are down to `action`
+    pub(crate) fn admit(
+        &self,
+        action: A,
+        metadata: Option<EventMetadata>,
+        metrics: &dyn MetricsRecorder,
+    ) -> Admission<A> {
+        let Some(policy) = self
+            .policies
+            .iter()
+            .find(|policy| (policy.affects)(&action) && policy.breaker.is_rejecting())
+        else {
+            return Admission::Proceed(action, metadata);
+        };
+
+        Self::record(policy, metrics);
+
+        match &policy.mode {
+            DegradationMode::Reject => Admission::Rejected(policy.dependency),
+            DegradationMode::Queue { max_queued } => {
+                let mut queue = policy.lock_queue();
+                if queue.len() >= *max_queued {
+                    tracing::warn!(
+                        dependency = policy.dependency,
+                        max_queued,
+                        "Degradation queue full, rejecting action"
+                    );
+                    return Admission::Rejected(policy.dependency);
+                }
+                queue.push_back((action, metadata));
+                tracing::debug!(
+                    dependency = policy.dependency,
+                    queued = queue.len(),
+                    "Dependency down, queued action"
+                );
+                Admission::Absorbed
+            },
+            DegradationMode::Fallback(fallback) => match fallback(action) {
+                Some(action) => Admission::Proceed(action, metadata),
+                None => Admission::Absorbed,
+            },
+        }
+    }
+
+    /// Apply the policies to a batch of actions, all or nothing
+    ///
+    /// Every action is checked before any is queued, so when one of them is
+    /// rejected the whole batch is, and nothing is held for replay. Returns
+    /// the actions to reduce, in order.
+    pub(crate) fn admit_batch(
+        &self,
+        actions: Vec<(A, Option<EventMetadata>)>,
+        metrics: &dyn MetricsRecorder,
+    ) -> Result<Vec<(A, Option<EventMetadata>)>, &'static str> {
+        // One breaker reading and one queue lock per policy for the whole batch
+        let rejecting: Vec<bool> = self.policies.iter().map(|policy| policy.breaker.is_rejecting()).collect();
+        let mut queues: Vec<_> = self
+            .policies
+            .iter()
+            .zip(&rejecting)
+            .map(|(policy, rejecting)| {
+                (*rejecting && matches!(policy.mode, DegradationMode::Queue { .. })).then(|| policy.lock_queue())
+            })
+            .collect();
+
+        let mut routes = Vec::with_capacity(actions.len());
+        let mut held = vec![0; self.policies.len()];
+        for (action, _) in &actions {
+            let route = self
+                .policies
+                .iter()
+                .zip(&rejecting)
+                .position(|(policy, rejecting)| *rejecting && (policy.affects)(action));
+            if let Some(index) = route {
+                let policy = &self.policies[index];
+                match &policy.mode {
+                    DegradationMode::Reject => {
+                        Self::record(policy, metrics);
+                        return Err(policy.dependency);
+                    },
+                    DegradationMode::Queue { max_queued } => {
+                        held[index] += 1;
+                        let waiting = queues[index].as_ref().map_or(0, |queue| queue.len());
+                        if waiting + held[index] > *max_queued {
+                            Self::record(policy, metrics);
+                            tracing::warn!(
+                                dependency = policy.dependency,
+                                max_queued,
+                                "Degradation queue full, rejecting batch"
+                            );
+                            return Err(policy.dependency);
+                        }
+                    },
+                    DegradationMode::Fallback(_) => {},
+                }
+            }
+            routes.push(route);
+        }
+
+        let mut proceed = Vec::with_capacity(actions.len());
+        for ((action, metadata), route) in actions.into_iter().zip(routes) {
+            let Some(index) = route else {
+                proceed.push((action, metadata));
+                continue;
+            };
+            let policy = &self.policies[index];
+            Self::record(policy, metrics);
+            match (&policy.mode, queues[index].as_mut()) {
+                (DegradationMode::Fallback(fallback), _) => {
+                    if let Some(action) = fallback(action) {
+                        proceed.push((action, metadata));
+                    }
+                },
+                (DegradationMode::Queue { .. }, Some(queue)) => queue.push_back((action, metadata)),
+                // Rejections returned above; a queue policy always holds its lock
+                _ => {},
+            }
+        }
+        Ok(proceed)
+    }
+
+    fn record(policy: &DegradationPolicy<A>, metrics: &dyn MetricsRecorder) {
+        metrics.increment_counter(
+            "store.degraded.actions",
+            &[
+                ("dependency", MetricLabel::from_static(policy.dependency)),
+                ("mode", MetricLabel::from_static(policy.mode.label())),
+            ],
+            1,
+        );
+    }
+
+    /// Take the held actions of every dependency that has recovered
+    pub(crate) fn take_recovered(&self) -> Vec<(A, Option<EventMetadata>)> {
+        self.policies
+            .iter()
+            .filter(|policy| !policy.breaker.is_rejecting())
+            .flat_map(|policy| policy.lock_queue().drain(..).collect::<Vec<_>>())
+            .collect()
+    }
+}
+
+impl<A> Default for Degradation<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Label of a circuit state as reported in health checks
+pub(crate) const fn circuit_label(state: CircuitState) -> &'static str {
+    match state {
+        CircuitState::Closed => "closed",
+        CircuitState::Open => "open",
+        CircuitState::HalfOpen => "half_open",
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use crate::metrics::NoopRecorder;
+    use crate::{HealthStatus, Store, StoreError};
+    use composable_rust_core::effect::Effect;
+    use composable_rust_core::reducer::Reducer;
+    use composable_rust_core::{smallvec, SmallVec};
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum PayAction {
+        Charge(u32),
+        ChargeOffline(u32),
+        Browse,
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct PayState {
+        charged: Vec<u32>,
+        offline: Vec<u32>,
+        browsed: usize,
+    }
+
+    #[derive(Debug, Clone)]
+    struct PayReducer;
+
+    impl Reducer for PayReducer {
+        type State = PayState;
+        type Action = PayAction;
+        type Environment = ();
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            _env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            match action {
+                PayAction::Charge(n) => state.charged.push(n),
+                PayAction::ChargeOffline(n) => state.offline.push(n),
+                PayAction::Browse => state.browsed += 1,
+            }
+            smallvec![Effect::None]
+        }
+    }
+
+    fn open_breaker() -> CircuitBreaker {
+        open_breaker_for(Duration::from_secs(60))
+    }
+
+    fn open_breaker_for(timeout: Duration) -> CircuitBreaker {
+        let breaker = CircuitBreaker::new()
+            .with_failure_threshold(1)
+            .with_timeout(timeout);
+        breaker.record_failure();
+        breaker
+    }
+
+    fn policy(breaker: CircuitBreaker, mode: DegradationMode<PayAction>) -> Degradation<PayAction> {
+        Degradation::new().with_policy(
+            DegradationPolicy::new("payments", breaker, |a| matches!(a, PayAction::Charge(_)))
+                .with_mode(mode),
+        )
+    }
+
+    #[test]
+    fn test_closed_breaker_admits_everything() {
+        let degradation = policy(CircuitBreaker::new(), DegradationMode::Reject);
+
+        let admission = degradation.admit(PayAction::Charge(1), None, &NoopRecorder);
+
+        assert!(matches!(admission, Admission::Proceed(PayAction::Charge(1), None)));
+    }
+
+    #[test]
+    fn test_queue_mode_holds_until_limit() {
+        let degradation = policy(open_breaker(), DegradationMode::Queue { max_queued: 1 });
+
+        assert!(matches!(
+            degradation.admit(PayAction::Charge(1), None, &NoopRecorder),
+            Admission::Absorbed
+        ));
+        assert!(matches!(
+            degradation.admit(PayAction::Charge(2), None, &NoopRecorder),
+            Admission::Rejected("payments")
+        ));
+        assert!(matches!(
+            degradation.admit(PayAction::Browse, None, &NoopRecorder),
+            Admission::Proceed(PayAction::Browse, None)
+        ));
+        assert_eq!(degradation.statuses()[0].queued, 1);
+        assert!(degradation.take_recovered().is_empty());
+    }
+
+    #[test]
+    fn test_batch_over_queue_limit_holds_nothing() {
+        let degradation = policy(open_breaker(), DegradationMode::Queue { max_queued: 2 });
+        degradation.admit(PayAction::Charge(1), None, &NoopRecorder);
+
+        let batch = vec![(PayAction::Browse, None), (PayAction::Charge(2), None), (PayAction::Charge(3), None)];
+        assert_eq!(degradation.admit_batch(batch, &NoopRecorder).err(), Some("payments"));
+        assert_eq!(degradation.statuses()[0].queued, 1);
+
+        let batch = vec![(PayAction::Browse, None), (PayAction::Charge(2), None)];
+        let proceed = degradation.admit_batch(batch, &NoopRecorder).unwrap();
+        assert!(matches!(proceed.as_slice(), [(PayAction::Browse, None)]));
+        assert_eq!(degradation.statuses()[0].queued, 2);
+    }
+
+    #[tokio::test]
+    async fn test_store_rejects_with_degraded_error() {
+        let store = Store::new(PayState::default(), PayReducer, ())
+            .with_degradation(policy(open_breaker(), DegradationMode::Reject));
+
+        let result = store.send(PayAction::Charge(1)).await;
+
+        assert!(matches!(result, Err(StoreError::Degraded { dependency: "payments" })));
+        assert!(store.state(|s| s.charged.is_empty()).await);
+    }
+
+    #[tokio::test]
+    async fn test_store_routes_to_fallback_branch() {
+        let store = Store::new(PayState::default(), PayReducer, ()).with_degradation(policy(
+            open_breaker(),
+            DegradationMode::fallback(|action| match action {
+                PayAction::Charge(n) => Some(PayAction::ChargeOffline(n)),
+                other => Some(other),
+            }),
+        ));
+
+        store.send(PayAction::Charge(7)).await.unwrap();
+
+        assert_eq!(store.state(|s| s.offline.clone()).await, vec![7]);
+    }
+
+    #[tokio::test]
+    async fn test_store_replays_queue_after_recovery_and_reports_health() {
+        let store = Store::new(PayState::default(), PayReducer, ()).with_degradation(policy(
+            open_breaker_for(Duration::from_millis(50)),
+            DegradationMode::Queue { max_queued: 10 },
+        ));
+
+        store.send(PayAction::Charge(1)).await.unwrap();
+        store.send(PayAction::Charge(2)).await.unwrap();
+        let health = store.health();
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert!(health
+            .metadata
+            .contains(&("degradation.payments.queued".to_string(), "2".to_string())));
+
+        // Breaker timeout elapses: calls are let through again
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        store.send(PayAction::Browse).await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while store.state(|s| s.charged.len()).await < 2 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(store.state(|s| s.charged.clone()).await, vec![1, 2]);
+        assert_eq!(store.health().status, HealthStatus::Healthy);
+    }
+}
@@ -0,0 +1,194 @@
+//! Action envelopes: an action together with how and when it reached the Store.
+//!
+//! Domain actions say *what* should happen. Audit logs, tracing and
+//! correlation also need to know *where the action came from* and *when*,
+//! without every action type growing fields for it. The Store wraps each
+//! action it accepts in an [`Envelope`] carrying:
+//!
+//! - the dispatch timestamp, read from the Store's [`Clock`]
+//!   (see [`Store::with_clock`](crate::Store::with_clock))
+//! - the [`ActionSource`]: sent by a caller, fed back by an effect, or
+//!   replayed from history
+//! - the request metadata it was sent with (correlation, causation and user ids)
+//!
+//! Envelopes travel through the mailbox and are unwrapped right before the
+//! reducer runs; reducers see the same information as a
+//! [`ReduceContext`]. Observers registered with
+//! [`Store::with_envelope_observer`](crate::Store::with_envelope_observer)
+//! see every envelope before its action is reduced.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::envelope::ActionSource;
+//!
+//! let store = Store::new(state, reducer, env).with_envelope_observer(|envelope: &Envelope<OrderAction>| {
+//!     if envelope.source == ActionSource::External {
+//!         tracing::info!(
+//!             action = ?envelope.action,
+//!             at = %envelope.dispatched_at,
+//!             correlation_id = envelope.correlation_id(),
+//!             "Audit"
+//!         );
+//!     }
+//! });
+//! ```
+
+use composable_rust_core::environment::{Clock, Timestamp};
+use composable_rust_core::event::EventMetadata;
+use composable_rust_core::reducer::ReduceContext;
+use std::sync::Arc;
+
+/// Where an action came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionSource {
+    /// Sent by a caller (`send`, `send_batch`, `try_send`, ...)
+    External,
+    /// Produced by one of the Store's effects and fed back
+    Feedback,
+    /// Replayed from history to rebuild the state
+    Replay,
+}
+
+impl ActionSource {
+    /// Stable lowercase name, for logs and metric labels
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::External => "external",
+            Self::Feedback => "feedback",
+            Self::Replay => "replay",
+        }
+    }
+}
+
+impl std::fmt::Display for ActionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An action with the context it was dispatched in
+#[derive(Debug, Clone)]
+pub struct Envelope<A> {
+    /// The domain action
+    pub action: A,
+    /// Where the action came from
+    pub source: ActionSource,
+    /// When the Store accepted the action, according to its clock
+    pub dispatched_at: Timestamp,
+    /// Request metadata the action was sent with
+    pub metadata: Option<EventMetadata>,
+}
+
+impl<A> Envelope<A> {
+    /// Wrap `action`, stamping it with the current time of `clock`
+    #[must_use]
+    pub fn new(
+        action: A,
+        source: ActionSource,
+        metadata: Option<EventMetadata>,
+        clock: &dyn Clock,
+    ) -> Self {
+        Self {
+            action,
+            source,
+            dispatched_at: clock.now(),
+            metadata,
+        }
+    }
+
+    /// Correlation id of the request the action belongs to, if any
+    #[must_use]
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.metadata.as_ref()?.correlation_id.as_deref()
+    }
+
+    /// Id of whatever caused the action, if any
+    #[must_use]
+    pub fn causation_id(&self) -> Option<&str> {
+        self.metadata.as_ref()?.causation_id.as_deref()
+    }
+
+    /// What the reducer is told about the action
+    #[must_use]
+    pub fn context(&self) -> ReduceContext {
+        ReduceContext {
+            is_replay: self.source == ActionSource::Replay,
+            dispatched_at: self.dispatched_at,
+            metadata: self.metadata.clone(),
+        }
+    }
+}
+
+/// Callback notified of every envelope before its action is reduced
+pub type EnvelopeObserver<A> = dyn Fn(&Envelope<A>) + Send + Sync;
+
+/// Observers registered on a Store, in registration order
+pub(crate) struct EnvelopeObservers<A> {
+    observers: Vec<Arc<EnvelopeObserver<A>>>,
+}
+
+impl<A> EnvelopeObservers<A> {
+    /// No observers
+    pub(crate) const fn new() -> Self {
+        Self {
+            observers: Vec::new(),
+        }
+    }
+
+    /// These observers followed by `observer`
+    pub(crate) fn with(&self, observer: Arc<EnvelopeObserver<A>>) -> Self {
+        let mut observers = self.observers.clone();
+        observers.push(observer);
+        Self { observers }
+    }
+
+    /// Call every observer with `envelope`
+    pub(crate) fn notify(&self, envelope: &Envelope<A>) {
+        for observer in &self.observers {
+            observer(envelope);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    struct FixedClock(DateTime<Utc>);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_envelope_context_reflects_source_and_metadata() {
+        let at = Utc
+            .with_ymd_and_hms(2025, 1, 2, 3, 4, 5)
+            .single()
+            .unwrap_or_default();
+        let metadata = EventMetadata::with_correlation_id("order-42");
+
+        let live = Envelope::new(
+            "place",
+            ActionSource::Feedback,
+            Some(metadata),
+            &FixedClock(at),
+        );
+        assert_eq!(live.dispatched_at, at);
+        assert_eq!(live.correlation_id(), Some("order-42"));
+        assert_eq!(live.causation_id(), None);
+        let context = live.context();
+        assert!(!context.is_replay);
+        assert_eq!(context.dispatched_at, at);
+
+        let replayed = Envelope::new("place", ActionSource::Replay, None, &FixedClock(at));
+        assert!(replayed.context().is_replay);
+        assert_eq!(replayed.correlation_id(), None);
+        assert_eq!(ActionSource::Replay.to_string(), "replay");
+    }
+}
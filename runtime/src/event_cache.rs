@@ -0,0 +1,545 @@
+//! Read-side caching in front of any [`EventStore`].
+//!
+//! Event-sourced commands load the aggregate's stream before every decision,
+//! which gets slow for long streams. [`CachingEventStore`] keeps the events
+//! and snapshot of recently loaded streams in memory and serves repeated loads
+//! from there. Every write through the decorator (append, batch append,
+//! snapshot save, delete) invalidates the affected streams, so reads never
+//! return stale data **as long as all writes go through the same
+//! `CachingEventStore`**. Writers in other processes bypass the invalidation;
+//! don't put the cache in front of a stream with multiple writing processes.
+//!
+//! Memory use is bounded by [`CachingEventStore::with_max_bytes`]: the least
+//! recently used streams are evicted first. Hits, misses and evictions are
+//! reported as the `event_store.cache.hits`, `event_store.cache.misses` and
+//! `event_store.cache.evictions` counters, and the cache size as the
+//! `event_store.cache.bytes` gauge.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::event_cache::CachingEventStore;
+//!
+//! let events = CachingEventStore::new(Arc::new(PostgresEventStore::new(&url).await?))
+//!     .with_max_bytes(256 * 1024 * 1024);
+//! ```
+
+use crate::metrics::{MetricsRecorder, MetricsRsRecorder};
+use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::event_store::{
+    BatchAppend, BatchAppendResults, EventStore, EventStoreError, Pagination, StreamMetadata,
+};
+use composable_rust_core::stream::{StreamId, Version};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Boxed future returned by [`EventStore`] methods
+type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, EventStoreError>> + Send + 'a>>;
+
+/// Default memory budget: 64 MiB
+const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// Hit and miss counts since the cache was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Loads served from memory
+    pub hits: u64,
+    /// Loads forwarded to the inner store
+    pub misses: u64,
+    /// Streams dropped to stay within the memory budget
+    pub evictions: u64,
+    /// Estimated size of the cached data
+    pub bytes: usize,
+}
+
+/// Cached data of one stream
+#[derive(Debug, Default)]
+struct CachedStream {
+    /// Contiguous events starting at version `from` (inclusive)
+    events: Option<(u64, Vec<SerializedEvent>)>,
+    /// Latest snapshot (`Some(None)` caches "no snapshot")
+    #[allow(clippy::option_option)] // Outer: cached or not; inner: the stream's snapshot
+    snapshot: Option<Option<(Version, Vec<u8>)>>,
+    /// Access stamp for least-recently-used eviction
+    last_used: u64,
+}
+
+impl CachedStream {
+    fn bytes(&self) -> usize {
+        let events = self.events.as_ref().map_or(0, |(_, events)| {
+            events
+                .iter()
+                .map(|event| {
+                    std::mem::size_of::<SerializedEvent>()
+                        + event.event_type.len()
+                        + event.data.len()
+                })
+                .sum()
+        });
+        let snapshot = match &self.snapshot {
+            Some(Some((_, state))) => state.len(),
+            _ => 0,
+        };
+        std::mem::size_of::<Self>() + events + snapshot
+    }
+}
+
+/// Cached streams and statistics
+#[derive(Debug, Default)]
+struct CacheState {
+    streams: HashMap<StreamId, CachedStream>,
+    clock: u64,
+    /// Number of writes seen, so loads racing a write don't cache stale data
+    writes: u64,
+    stats: CacheStats,
+}
+
+impl CacheState {
+    fn touch(&mut self, stream_id: &StreamId) -> Option<&mut CachedStream> {
+        self.clock += 1;
+        let clock = self.clock;
+        let cached = self.streams.get_mut(stream_id)?;
+        cached.last_used = clock;
+        Some(cached)
+    }
+
+    fn recompute_bytes(&mut self) {
+        self.stats.bytes = self.streams.values().map(CachedStream::bytes).sum();
+    }
+}
+
+/// [`EventStore`] decorator caching loaded events and snapshots per stream
+pub struct CachingEventStore {
+    inner: Arc<dyn EventStore>,
+    max_bytes: usize,
+    metrics: Arc<dyn MetricsRecorder>,
+    state: Mutex<CacheState>,
+}
+
+impl std::fmt::Debug for CachingEventStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachingEventStore")
+            .field("max_bytes", &self.max_bytes)
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CachingEventStore {
+    /// Cache reads from `inner`, using up to 64 MiB
+    #[must_use]
+    pub fn new(inner: Arc<dyn EventStore>) -> Self {
+        Self {
+            inner,
+            max_bytes: DEFAULT_MAX_BYTES,
+            metrics: Arc::new(MetricsRsRecorder),
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Maximum estimated size of the cached events and snapshots
+    ///
+    /// A stream larger than the whole budget is never cached.
+    #[must_use]
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Report hits, misses and evictions to `recorder` instead of the global `metrics` facade
+    #[must_use]
+    pub fn with_metrics_recorder(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.metrics = recorder;
+        self
+    }
+
+    /// Hit and miss counts and the current cache size
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats
+    }
+
+    /// Drop everything cached for `stream_id`
+    pub fn invalidate(&self, stream_id: &StreamId) {
+        let mut state = self.lock();
+        state.writes += 1;
+        if state.streams.remove(stream_id).is_some() {
+            state.recompute_bytes();
+            self.report_size(&state);
+        }
+    }
+
+    /// Drop the whole cache
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.writes += 1;
+        state.streams.clear();
+        state.stats.bytes = 0;
+        self.report_size(&state);
+    }
+
+    /// Events from `from`, if cached
+    fn cached_events(&self, stream_id: &StreamId, from: u64) -> Option<Vec<SerializedEvent>> {
+        let mut state = self.lock();
+        let events = state
+            .touch(stream_id)
+            .and_then(|cached| cached.events.as_ref())
+            .filter(|(start, _)| *start <= from)
+            .map(|(start, events)| {
+                let skip = usize::try_from(from - start).unwrap_or(usize::MAX);
+                events.get(skip..).unwrap_or(&[]).to_vec()
+            });
+        self.record_lookup(&mut state, events.is_some());
+        events
+    }
+
+    /// Snapshot, if cached
+    #[allow(clippy::option_option)] // Outer: cached or not; inner: the stream's snapshot
+    fn cached_snapshot(&self, stream_id: &StreamId) -> Option<Option<(Version, Vec<u8>)>> {
+        let mut state = self.lock();
+        let snapshot = state
+            .touch(stream_id)
+            .and_then(|cached| cached.snapshot.clone());
+        self.record_lookup(&mut state, snapshot.is_some());
+        snapshot
+    }
+
+    fn record_lookup(&self, state: &mut CacheState, hit: bool) {
+        if hit {
+            state.stats.hits += 1;
+            self.metrics
+                .increment_counter("event_store.cache.hits", &[], 1);
+        } else {
+            state.stats.misses += 1;
+            self.metrics
+                .increment_counter("event_store.cache.misses", &[], 1);
+        }
+    }
+
+    /// Merge freshly loaded data into the cache, then evict down to the budget
+    ///
+    /// Nothing is cached if a write happened since `writes` was read, as the
+    /// loaded data may predate it.
+    fn store(&self, stream_id: &StreamId, writes: u64, update: impl FnOnce(&mut CachedStream)) {
+        let mut state = self.lock();
+        if state.writes != writes {
+            return;
+        }
+        state.clock += 1;
+        let clock = state.clock;
+        let cached = state.streams.entry(stream_id.clone()).or_default();
+        update(cached);
+        cached.last_used = clock;
+        if cached.bytes() > self.max_bytes {
+            state.streams.remove(stream_id);
+        }
+        state.recompute_bytes();
+
+        while state.stats.bytes > self.max_bytes {
+            let Some(oldest) = state
+                .streams
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            if let Some(evicted) = state.streams.remove(&oldest) {
+                state.stats.bytes = state.stats.bytes.saturating_sub(evicted.bytes());
+            }
+            state.stats.evictions += 1;
+            self.metrics
+                .increment_counter("event_store.cache.evictions", &[], 1);
+        }
+        self.report_size(&state);
+    }
+
+    #[allow(clippy::cast_precision_loss)] // Cache sizes are far below 2^52 bytes
+    fn report_size(&self, state: &CacheState) {
+        self.metrics
+            .set_gauge("event_store.cache.bytes", &[], state.stats.bytes as f64);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl EventStore for CachingEventStore {
+    fn append_events(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+    ) -> StoreFuture<'_, Version> {
+        Box::pin(async move {
+            // Invalidate even on failure: a conflict means someone else wrote
+            let result = self
+                .inner
+                .append_events(stream_id.clone(), expected_version, events)
+                .await;
+            self.invalidate(&stream_id);
+            result
+        })
+    }
+
+    fn append_events_idempotent(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+        idempotency_key: String,
+    ) -> StoreFuture<'_, Version> {
+        Box::pin(async move {
+            let result = self
+                .inner
+                .append_events_idempotent(
+                    stream_id.clone(),
+                    expected_version,
+                    events,
+                    idempotency_key,
+                )
+                .await;
+            self.invalidate(&stream_id);
+            result
+        })
+    }
+
+    fn load_events(
+        &self,
+        stream_id: StreamId,
+        from_version: Option<Version>,
+    ) -> StoreFuture<'_, Vec<SerializedEvent>> {
+        Box::pin(async move {
+            let from = from_version.map_or(0, Version::value);
+            if let Some(events) = self.cached_events(&stream_id, from) {
+                return Ok(events);
+            }
+
+            let writes = self.lock().writes;
+            let events = self
+                .inner
+                .load_events(stream_id.clone(), from_version)
+                .await?;
+            let cached = events.clone();
+            self.store(&stream_id, writes, move |stream| {
+                // Keep whichever copy covers more of the stream
+                if stream
+                    .events
+                    .as_ref()
+                    .is_none_or(|(start, _)| from <= *start)
+                {
+                    stream.events = Some((from, cached));
+                }
+            });
+            Ok(events)
+        })
+    }
+
+    fn load_events_page(
+        &self,
+        stream_id: StreamId,
+        from_version: Version,
+        limit: usize,
+    ) -> StoreFuture<'_, Vec<SerializedEvent>> {
+        Box::pin(async move {
+            if let Some(mut events) = self.cached_events(&stream_id, from_version.value()) {
+                events.truncate(limit);
+                return Ok(events);
+            }
+            // Pages are not cached: paging exists to keep long streams out of memory
+            self.inner
+                .load_events_page(stream_id, from_version, limit)
+                .await
+        })
+    }
+
+    fn save_snapshot(
+        &self,
+        stream_id: StreamId,
+        version: Version,
+        state: Vec<u8>,
+    ) -> StoreFuture<'_, ()> {
+        Box::pin(async move {
+            let result = self
+                .inner
+                .save_snapshot(stream_id.clone(), version, state)
+                .await;
+            let mut cache = self.lock();
+            cache.writes += 1;
+            if let Some(cached) = cache.streams.get_mut(&stream_id) {
+                cached.snapshot = None;
+                cache.recompute_bytes();
+                self.report_size(&cache);
+            }
+            result
+        })
+    }
+
+    fn load_snapshot(&self, stream_id: StreamId) -> StoreFuture<'_, Option<(Version, Vec<u8>)>> {
+        Box::pin(async move {
+            if let Some(snapshot) = self.cached_snapshot(&stream_id) {
+                return Ok(snapshot);
+            }
+
+            let writes = self.lock().writes;
+            let snapshot = self.inner.load_snapshot(stream_id.clone()).await?;
+            let cached = snapshot.clone();
+            self.store(&stream_id, writes, move |stream| {
+                stream.snapshot = Some(cached);
+            });
+            Ok(snapshot)
+        })
+    }
+
+    fn append_batch(&self, batch: Vec<BatchAppend>) -> StoreFuture<'_, BatchAppendResults> {
+        Box::pin(async move {
+            let stream_ids: Vec<StreamId> = batch
+                .iter()
+                .map(|append| append.stream_id.clone())
+                .collect();
+            let result = self.inner.append_batch(batch).await;
+            for stream_id in &stream_ids {
+                self.invalidate(stream_id);
+            }
+            result
+        })
+    }
+
+    fn delete_stream(&self, stream_id: StreamId) -> StoreFuture<'_, ()> {
+        Box::pin(async move {
+            let result = self.inner.delete_stream(stream_id.clone()).await;
+            self.invalidate(&stream_id);
+            result
+        })
+    }
+
+    fn stream_metadata(&self, stream_id: StreamId) -> StoreFuture<'_, Option<StreamMetadata>> {
+        self.inner.stream_metadata(stream_id)
+    }
+
+    fn list_streams(
+        &self,
+        prefix: Option<String>,
+        page: Pagination,
+    ) -> StoreFuture<'_, Vec<StreamMetadata>> {
+        self.inner.list_streams(prefix, page)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+mod tests {
+    use super::*;
+    use crate::metrics::CapturingRecorder;
+    use composable_rust_testing::mocks::{FlakyEventStore, InMemoryEventStore};
+
+    fn event(data: &[u8]) -> SerializedEvent {
+        SerializedEvent::new("Deposited.v1".to_string(), data.to_vec(), None)
+    }
+
+    /// Cache over a counting wrapper, so tests can see which loads reached the inner store
+    fn cache() -> (CachingEventStore, Arc<FlakyEventStore>, CapturingRecorder) {
+        let counting = Arc::new(FlakyEventStore::new(Arc::new(InMemoryEventStore::new())));
+        let recorder = CapturingRecorder::new();
+        let cache = CachingEventStore::new(counting.clone())
+            .with_metrics_recorder(Arc::new(recorder.clone()));
+        (cache, counting, recorder)
+    }
+
+    #[tokio::test]
+    async fn test_repeated_loads_hit_cache_until_append() {
+        let (cache, inner, recorder) = cache();
+        let stream = StreamId::new("account-1");
+        cache
+            .append_events(stream.clone(), None, vec![event(b"a"), event(b"b")])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            cache.load_events(stream.clone(), None).await.unwrap().len(),
+            2
+        );
+        assert_eq!(
+            cache.load_events(stream.clone(), None).await.unwrap().len(),
+            2
+        );
+        // A later tail is served from the cached full stream
+        let tail = cache
+            .load_events(stream.clone(), Some(Version::new(1)))
+            .await
+            .unwrap();
+        assert_eq!(tail[0].data, b"b");
+        assert_eq!(inner.calls("load_events"), 1);
+
+        cache
+            .append_events(stream.clone(), Some(Version::new(2)), vec![event(b"c")])
+            .await
+            .unwrap();
+        assert_eq!(
+            cache.load_events(stream.clone(), None).await.unwrap().len(),
+            3
+        );
+        assert_eq!(inner.calls("load_events"), 2);
+
+        assert_eq!(cache.stats().hits, 2);
+        assert_eq!(cache.stats().misses, 2);
+        assert_eq!(recorder.counter("event_store.cache.hits"), 2);
+        assert_eq!(recorder.counter("event_store.cache.misses"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_cached_and_invalidated_on_save() {
+        let (cache, inner, _) = cache();
+        let stream = StreamId::new("account-1");
+
+        assert_eq!(cache.load_snapshot(stream.clone()).await.unwrap(), None);
+        assert_eq!(cache.load_snapshot(stream.clone()).await.unwrap(), None);
+        assert_eq!(inner.calls("load_snapshot"), 1);
+
+        cache
+            .save_snapshot(stream.clone(), Version::new(4), b"state".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            cache.load_snapshot(stream.clone()).await.unwrap(),
+            Some((Version::new(4), b"state".to_vec()))
+        );
+        assert_eq!(inner.calls("load_snapshot"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_least_recently_used_stream_evicted_over_budget() {
+        let (cache, inner, recorder) = cache();
+        let per_stream = CachedStream {
+            events: Some((0, vec![event(&[0; 1_000])])),
+            ..CachedStream::default()
+        }
+        .bytes();
+        let cache = cache.with_max_bytes(per_stream * 5 / 2);
+        let (a, b, c) = (StreamId::new("a"), StreamId::new("b"), StreamId::new("c"));
+        for stream in [&a, &b, &c] {
+            cache
+                .append_events(stream.clone(), None, vec![event(&[0; 1_000])])
+                .await
+                .unwrap();
+        }
+
+        cache.load_events(a.clone(), None).await.unwrap();
+        cache.load_events(b.clone(), None).await.unwrap();
+        cache.load_events(a.clone(), None).await.unwrap();
+        // Loading `c` pushes the cache over budget; `b` is least recently used
+        cache.load_events(c.clone(), None).await.unwrap();
+        assert_eq!(cache.stats().evictions, 1);
+        assert_eq!(cache.stats().bytes, 2 * per_stream);
+
+        cache.load_events(a.clone(), None).await.unwrap();
+        cache.load_events(b.clone(), None).await.unwrap();
+        assert_eq!(inner.calls("load_events"), 4);
+        assert_eq!(recorder.counter("event_store.cache.evictions"), 2);
+    }
+}
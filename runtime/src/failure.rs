@@ -0,0 +1,213 @@
+//! Structured failures recorded in the Store's dead letter queue.
+//!
+//! A [`DeadLetter`](crate::DeadLetter) on its own carries only an error
+//! message. The Store's queue holds an [`OperationFailure`] as the payload of
+//! every entry instead, so tooling can filter on what failed and why without
+//! parsing strings:
+//!
+//! ```ignore
+//! use composable_rust_runtime::failure::FailureError;
+//!
+//! // Replay database outages, keep everything else parked
+//! let replayable = store
+//!     .dlq()
+//!     .remove_where(|entry| matches!(entry.payload.error, FailureError::EventStore(_)) && entry.payload.is_retryable());
+//! ```
+//!
+//! The `error` label of the Store's `store.retry.exhausted` and
+//! `store.retry.non_retryable` counters is [`FailureError::kind`]; the
+//! `reason` label of `store.retry.exhausted` is [`RetryExhausted::kind`].
+
+use crate::RetryExhausted;
+use composable_rust_core::event_bus::EventBusError;
+use composable_rust_core::event_store::EventStoreError;
+use composable_rust_core::read_model::ReadModelError;
+use composable_rust_core::retry::RetryableError;
+use std::borrow::Cow;
+use std::time::SystemTime;
+use thiserror::Error;
+
+/// The error that made an operation fail
+#[derive(Error, Debug, Clone)]
+pub enum FailureError {
+    /// An event store operation failed
+    #[error(transparent)]
+    EventStore(#[from] EventStoreError),
+
+    /// An event bus operation failed
+    #[error(transparent)]
+    EventBus(#[from] EventBusError),
+
+    /// A read model query failed
+    #[error(transparent)]
+    ReadModel(#[from] ReadModelError),
+
+    /// The reducer panicked while handling an action
+    #[error("Reducer panicked: {0}")]
+    ReducerPanic(String),
+
+    /// The Store refused to run the effect (e.g. the concurrency limit was reached)
+    #[error("Effect rejected: {0}")]
+    Rejected(String),
+}
+
+impl FailureError {
+    /// Short, stable name of the error source, used as a metric label
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::EventStore(_) => "event_store",
+            Self::EventBus(_) => "event_bus",
+            Self::ReadModel(_) => "read_model",
+            Self::ReducerPanic(_) => "reducer_panic",
+            Self::Rejected(_) => "rejected",
+        }
+    }
+}
+
+impl RetryableError for FailureError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::EventStore(error) => error.is_retryable(),
+            Self::EventBus(error) => error.is_retryable(),
+            Self::ReadModel(error) => error.is_retryable(),
+            Self::ReducerPanic(_) => false,
+            Self::Rejected(_) => true,
+        }
+    }
+}
+
+/// A failed operation, as recorded in the Store's dead letter queue
+#[derive(Debug, Clone)]
+pub struct OperationFailure {
+    /// Operation that failed (`append_events`, `publish`, `reducer`, ...)
+    pub operation: Cow<'static, str>,
+
+    /// Stream id, topic or collection the operation targeted, if any
+    pub target: Option<String>,
+
+    /// The last error
+    pub error: FailureError,
+
+    /// Serialized data the operation carried, when available (the events
+    /// to append or publish), for replaying it by hand
+    pub payload: Option<Vec<u8>>,
+
+    /// Number of attempts made
+    pub attempts: usize,
+
+    /// Why the Store stopped retrying, `None` if it never retried
+    /// (e.g. a reducer panic)
+    pub exhausted: Option<RetryExhausted>,
+
+    /// When the first attempt failed
+    pub first_failed_at: SystemTime,
+
+    /// When the last attempt failed
+    pub last_failed_at: SystemTime,
+}
+
+impl OperationFailure {
+    /// A failure of `operation` after a single attempt, failing now
+    #[must_use]
+    pub fn new(operation: impl Into<Cow<'static, str>>, error: impl Into<FailureError>) -> Self {
+        let now = SystemTime::now();
+        Self {
+            operation: operation.into(),
+            target: None,
+            error: error.into(),
+            payload: None,
+            attempts: 1,
+            exhausted: None,
+            first_failed_at: now,
+            last_failed_at: now,
+        }
+    }
+
+    /// Set the stream id, topic or collection the operation targeted
+    #[must_use]
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Attach the serialized data the operation carried
+    #[must_use]
+    pub fn with_payload(mut self, payload: Option<Vec<u8>>) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    /// Record `attempts` attempts, the first of which failed at `first_failed_at`
+    #[must_use]
+    pub const fn with_attempts(mut self, attempts: usize, first_failed_at: SystemTime) -> Self {
+        self.attempts = attempts;
+        self.first_failed_at = first_failed_at;
+        self
+    }
+
+    /// Record why retrying stopped
+    #[must_use]
+    pub const fn with_exhausted(mut self, exhausted: RetryExhausted) -> Self {
+        self.exhausted = Some(exhausted);
+        self
+    }
+
+    /// Whether replaying the operation may succeed
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        self.error.is_retryable()
+    }
+}
+
+impl std::fmt::Display for OperationFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.operation)?;
+        if let Some(target) = &self.target {
+            write!(f, " on {target}")?;
+        }
+        write!(
+            f,
+            " failed after {} attempts: {}",
+            self.attempts, self.error
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use composable_rust_core::stream::{StreamId, Version};
+
+    #[test]
+    fn test_failure_keeps_error_type_and_retryability() {
+        let conflict = OperationFailure::new(
+            "append_events",
+            EventStoreError::ConcurrencyConflict {
+                stream_id: StreamId::new("order-1"),
+                expected: Version::new(1),
+                actual: Version::new(2),
+            },
+        )
+        .with_target("order-1");
+        assert_eq!(conflict.error.kind(), "event_store");
+        assert!(!conflict.is_retryable());
+        assert_eq!(
+            conflict.to_string(),
+            "append_events on order-1 failed after 1 attempts: \
+             Concurrency conflict: expected version 1, found 2"
+        );
+
+        let outage = OperationFailure::new(
+            "publish",
+            EventBusError::TransportError("broker down".to_string()),
+        )
+        .with_attempts(5, SystemTime::UNIX_EPOCH);
+        assert!(outage.is_retryable());
+        assert!(matches!(
+            outage.error,
+            FailureError::EventBus(EventBusError::TransportError(_))
+        ));
+        assert_eq!(outage.attempts, 5);
+    }
+}
@@ -0,0 +1,258 @@
+//! Health aggregation across stores and components.
+//!
+//! Applications host many stores, projections and event bus consumers, each
+//! with its own health. Components implement [`HealthCheckProvider`] and are
+//! registered under a name in a [`HealthRegistry`]; [`HealthRegistry::report`]
+//! runs every check concurrently and combines them into one [`HealthReport`].
+//!
+//! [`HealthReport`] serializes to JSON, so it can be returned directly from
+//! an axum `/healthz` handler (see `composable_rust_web::handlers::health`):
+//!
+//! ```json
+//! {
+//!   "status": "degraded",
+//!   "checks": [
+//!     { "component": "orders", "status": "healthy", "message": null, "metadata": { "dlq_size": "0" } },
+//!     { "component": "payments-listener", "status": "degraded", "message": "...", "metadata": {} }
+//!   ],
+//!   "timestamp": "2025-01-01T00:00:00Z"
+//! }
+//! ```
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::health::HealthRegistry;
+//!
+//! let registry = HealthRegistry::new();
+//! registry.register("orders", Arc::new(order_store.clone()));
+//! registry.register("inventory", Arc::new(inventory_store.clone()));
+//! registry.register("payments-listener", Arc::new(listener_handle));
+//!
+//! let report = registry.report().await;
+//! ```
+
+use crate::listener::ListenerHandle;
+use crate::{HealthCheck, HealthReport, Store};
+use composable_rust_core::reducer::Reducer;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// A component that can report its health
+///
+/// # Dyn Compatibility
+///
+/// Returns `Pin<Box<dyn Future>>` so providers can be stored as
+/// `Arc<dyn HealthCheckProvider>` and perform async checks (e.g. a database
+/// ping).
+pub trait HealthCheckProvider: Send + Sync {
+    /// Check the component's current health
+    fn check(&self) -> Pin<Box<dyn Future<Output = HealthCheck> + Send + '_>>;
+}
+
+impl<S, A, E, R> HealthCheckProvider for Store<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E> + Send + Sync + 'static,
+    A: Send + Clone + 'static,
+    S: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    fn check(&self) -> Pin<Box<dyn Future<Output = HealthCheck> + Send + '_>> {
+        let health = self.health();
+        Box::pin(async move { health })
+    }
+}
+
+impl HealthCheckProvider for ListenerHandle {
+    fn check(&self) -> Pin<Box<dyn Future<Output = HealthCheck> + Send + '_>> {
+        let health = if self.is_finished() {
+            HealthCheck::unhealthy("listener", "Listener task has stopped")
+        } else {
+            HealthCheck::healthy("listener")
+        };
+        Box::pin(async move { health })
+    }
+}
+
+/// Registry of named health check providers
+///
+/// Registration is possible at any time, including while reports are being
+/// produced.
+pub struct HealthRegistry {
+    providers: RwLock<Vec<(String, Arc<dyn HealthCheckProvider>)>>,
+    check_timeout: Duration,
+}
+
+impl HealthRegistry {
+    /// Create an empty registry with a 5 second per-check timeout
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            providers: RwLock::new(Vec::new()),
+            check_timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Set how long a single check may take before it is reported unhealthy
+    #[must_use]
+    pub const fn with_check_timeout(mut self, timeout: Duration) -> Self {
+        self.check_timeout = timeout;
+        self
+    }
+
+    /// Register `provider` under `name`
+    ///
+    /// The name replaces the component name of the provider's checks, so
+    /// several stores can be told apart. Registering an existing name
+    /// replaces its provider.
+    pub fn register(&self, name: impl Into<String>, provider: Arc<dyn HealthCheckProvider>) {
+        let name = name.into();
+        let mut providers = self.write();
+        match providers.iter_mut().find(|(existing, _)| *existing == name) {
+            Some(entry) => entry.1 = provider,
+            None => providers.push((name, provider)),
+        }
+    }
+
+    /// Remove the provider registered under `name`
+    ///
+    /// Returns `true` if a provider was removed.
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut providers = self.write();
+        let before = providers.len();
+        providers.retain(|(existing, _)| existing != name);
+        providers.len() != before
+    }
+
+    /// Names of the registered components, in registration order
+    #[must_use]
+    pub fn components(&self) -> Vec<String> {
+        self.read().iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Run every check concurrently and combine the results
+    ///
+    /// Checks that exceed the check timeout are reported unhealthy. The
+    /// overall status is the worst status of all checks (healthy when nothing
+    /// is registered).
+    pub async fn report(&self) -> HealthReport {
+        let providers = self.read().clone();
+        let timeout = self.check_timeout;
+
+        let checks = futures::future::join_all(providers.iter().map(|(name, provider)| async move {
+            let mut check = match tokio::time::timeout(timeout, provider.check()).await {
+                Ok(check) => check,
+                Err(_) => HealthCheck::unhealthy(
+                    name.clone(),
+                    format!("Health check timed out after {}ms", timeout.as_millis()),
+                ),
+            };
+            check.component.clone_from(name);
+            check
+        }))
+        .await;
+
+        HealthReport::new(checks)
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<(String, Arc<dyn HealthCheckProvider>)>> {
+        self.providers.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<(String, Arc<dyn HealthCheckProvider>)>> {
+        self.providers.write().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Default for HealthRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for HealthRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HealthRegistry")
+            .field("components", &self.components())
+            .field("check_timeout", &self.check_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use crate::HealthStatus;
+
+    struct Fixed(HealthCheck);
+
+    impl HealthCheckProvider for Fixed {
+        fn check(&self) -> Pin<Box<dyn Future<Output = HealthCheck> + Send + '_>> {
+            Box::pin(async move { self.0.clone() })
+        }
+    }
+
+    struct Hanging;
+
+    impl HealthCheckProvider for Hanging {
+        fn check(&self) -> Pin<Box<dyn Future<Output = HealthCheck> + Send + '_>> {
+            Box::pin(std::future::pending())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_takes_worst_status_and_renames_components() {
+        let registry = HealthRegistry::new();
+        registry.register("orders", Arc::new(Fixed(HealthCheck::healthy("store"))));
+        registry.register(
+            "inventory",
+            Arc::new(Fixed(HealthCheck::degraded("store", "DLQ filling up"))),
+        );
+
+        let report = registry.report().await;
+
+        assert_eq!(report.status, HealthStatus::Degraded);
+        let names: Vec<_> = report.checks.iter().map(|c| c.component.as_str()).collect();
+        assert_eq!(names, vec!["orders", "inventory"]);
+    }
+
+    #[tokio::test]
+    async fn test_register_replaces_and_unregister_removes() {
+        let registry = HealthRegistry::new();
+        registry.register("orders", Arc::new(Fixed(HealthCheck::unhealthy("store", "down"))));
+        registry.register("orders", Arc::new(Fixed(HealthCheck::healthy("store"))));
+
+        assert!(registry.report().await.is_healthy());
+        assert!(registry.unregister("orders"));
+        assert!(!registry.unregister("orders"));
+        assert!(registry.components().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_check_reported_unhealthy() {
+        let registry = HealthRegistry::new().with_check_timeout(Duration::from_millis(100));
+        registry.register("hanging", Arc::new(Hanging));
+
+        let report = registry.report().await;
+
+        assert!(report.is_unhealthy());
+        assert!(report.checks[0].message.as_deref().unwrap().contains("timed out"));
+    }
+
+    #[test]
+    fn test_report_serializes_to_json() {
+        let report = HealthReport::new(vec![
+            HealthCheck::healthy("orders").with_metadata("dlq_size", "0"),
+        ]);
+
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["status"], "healthy");
+        assert_eq!(json["checks"][0]["component"], "orders");
+        assert_eq!(json["checks"][0]["metadata"]["dlq_size"], "0");
+        assert!(json["timestamp"].is_string());
+    }
+}
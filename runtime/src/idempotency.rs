@@ -0,0 +1,225 @@
+//! Dropping duplicate actions before they reach the reducer.
+//!
+//! Sagas and event bus consumers see the same message more than once
+//! (at-least-once delivery, retries, redelivery after a rebalance). An
+//! [`IdempotencyGuard`] extracts an idempotency key from each action and
+//! remembers the keys it has admitted in a bounded cache with a time-to-live.
+//! An action whose key is still in the cache is dropped: `send()` returns an
+//! already-completed [`EffectHandle`](crate::EffectHandle) and the state is
+//! unchanged. Actions without a key always pass.
+//!
+//! Keys are recorded only once an action has passed the rate limit and any
+//! degradation policy, so a rejected action can be retried with the same key.
+//! Dropped duplicates are counted in the `store.actions.duplicates` metric.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::idempotency::IdempotencyGuard;
+//!
+//! let guard = IdempotencyGuard::new(|action: &SagaAction| match action {
+//!     SagaAction::PaymentCompleted { payment_id, .. } => Some(payment_id.to_string()),
+//!     _ => None,
+//! })
+//! .with_ttl(Duration::from_secs(600))
+//! .with_capacity(50_000);
+//!
+//! let store = Store::new(state, reducer, env).with_idempotency(guard);
+//! ```
+
+use crate::metrics::MetricsRecorder;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Extracts the idempotency key of an action (`None` if it has none)
+type KeyExtractor<A> = Arc<dyn Fn(&A) -> Option<String> + Send + Sync>;
+
+/// Admitted keys, in admission order
+#[derive(Debug, Default)]
+struct SeenKeys {
+    /// Key to its admission time and sequence number
+    expiry: HashMap<String, (Instant, u64)>,
+    /// Sequence numbers and keys in admission order, for eviction
+    ///
+    /// Entries whose sequence number no longer matches `expiry` are stale
+    /// (the key was forgotten or re-admitted) and are skipped.
+    order: VecDeque<(u64, String)>,
+    /// Next sequence number
+    next: u64,
+}
+
+impl SeenKeys {
+    /// Remove the oldest entry (forgetting its key if it is live); `false` if empty
+    fn pop_oldest(&mut self) -> bool {
+        let Some((sequence, key)) = self.order.pop_front() else {
+            return false;
+        };
+        if self.expiry.get(&key).is_some_and(|&(_, live)| live == sequence) {
+            self.expiry.remove(&key);
+        }
+        true
+    }
+}
+
+/// Bounded TTL cache of recently admitted idempotency keys
+pub struct IdempotencyGuard<A> {
+    extractor: KeyExtractor<A>,
+    ttl: Duration,
+    capacity: usize,
+    seen: Mutex<SeenKeys>,
+}
+
+impl<A> std::fmt::Debug for IdempotencyGuard<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdempotencyGuard")
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.capacity)
+            .field("tracked", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A> IdempotencyGuard<A> {
+    /// Create a guard using `extractor` to find each action's key
+    ///
+    /// Defaults: keys are remembered for 5 minutes, up to 10,000 keys.
+    #[must_use]
+    pub fn new<F>(extractor: F) -> Self
+    where
+        F: Fn(&A) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            extractor: Arc::new(extractor),
+            ttl: Duration::from_secs(300),
+            capacity: 10_000,
+            seen: Mutex::new(SeenKeys::default()),
+        }
+    }
+
+    /// How long an admitted key suppresses duplicates
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Maximum number of keys remembered; the oldest are forgotten first
+    #[must_use]
+    pub const fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Number of keys currently remembered (including expired ones not yet evicted)
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().expiry.len()
+    }
+
+    /// Whether no keys are remembered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget `key`, so the next action carrying it is admitted
+    pub fn forget(&self, key: &str) {
+        // The stale `order` entry is skipped when it is evicted
+        self.lock().expiry.remove(key);
+    }
+
+    /// Whether `action` should be reduced, recording its key if so
+    pub(crate) fn admit(&self, action: &A, metrics: &dyn MetricsRecorder) -> bool {
+        let Some(key) = (self.extractor)(action) else {
+            return true;
+        };
+
+        let now = Instant::now();
+        let mut seen = self.lock();
+        self.evict(&mut seen, now);
+
+        if seen.expiry.contains_key(&key) {
+            tracing::debug!(key = %key, "Dropping duplicate action");
+            metrics.increment_counter("store.actions.duplicates", &[], 1);
+            return false;
+        }
+
+        if self.capacity == 0 {
+            return true;
+        }
+        while seen.expiry.len() >= self.capacity && seen.pop_oldest() {}
+
+        let sequence = seen.next;
+        seen.next += 1;
+        seen.expiry.insert(key.clone(), (now, sequence));
+        seen.order.push_back((sequence, key));
+        true
+    }
+
+    /// Forget the key recorded for `action` when it was admitted but never
+    /// reached the reducer
+    pub(crate) fn release(&self, action: &A) {
+        if let Some(key) = (self.extractor)(action) {
+            self.forget(&key);
+        }
+    }
+
+    /// Drop keys admitted more than `ttl` ago
+    fn evict(&self, seen: &mut SeenKeys, now: Instant) {
+        while let Some((sequence, key)) = seen.order.front() {
+            if let Some(&(admitted, live)) = seen.expiry.get(key) {
+                if live == *sequence && now.duration_since(admitted) < self.ttl {
+                    break;
+                }
+            }
+            seen.pop_oldest();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SeenKeys> {
+        self.seen
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::NoopRecorder;
+
+    fn guard() -> IdempotencyGuard<(u32, &'static str)> {
+        IdempotencyGuard::new(|(id, _): &(u32, &str)| (*id != 0).then(|| id.to_string()))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_duplicates_dropped_until_ttl_expires() {
+        let guard = guard().with_ttl(Duration::from_secs(60));
+
+        assert!(guard.admit(&(1, "first"), &NoopRecorder));
+        assert!(!guard.admit(&(1, "redelivered"), &NoopRecorder));
+        // Actions without a key always pass
+        assert!(guard.admit(&(0, "keyless"), &NoopRecorder));
+        assert!(guard.admit(&(0, "keyless"), &NoopRecorder));
+
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert!(guard.admit(&(1, "after ttl"), &NoopRecorder));
+    }
+
+    #[tokio::test]
+    async fn test_capacity_forgets_oldest_keys() {
+        let guard = guard().with_capacity(2);
+
+        for id in 1..=3 {
+            assert!(guard.admit(&(id, "first"), &NoopRecorder));
+        }
+        assert_eq!(guard.len(), 2);
+        assert!(guard.admit(&(1, "evicted"), &NoopRecorder));
+        assert!(!guard.admit(&(3, "still tracked"), &NoopRecorder));
+
+        guard.forget("3");
+        assert!(guard.admit(&(3, "forgotten"), &NoopRecorder));
+    }
+}
@@ -17,10 +17,16 @@
 //!
 //! - **`Effect::None`**: No-op, completes immediately
 //! - **`Effect::Future`**: Spawns async task, yields 0 or 1 action
+//! - **`Effect::FutureWithCancel`**: Like `Future`, with a token cancelled when shutdown starts
 //! - **`Effect::Stream`**: Spawns async task, yields 0..N actions over time (Phase 8)
 //! - **`Effect::Delay`**: Sleeps for duration, then yields action
+//! - **`Effect::DelayKeyed`**: Like `Delay`, but replaces a pending delay with the same key
 //! - **`Effect::Parallel`**: Executes multiple effects concurrently
-//! - **`Effect::Sequential`**: Executes effects in sequence, waiting for each to complete
+//! - **`Effect::Sequential`**: Executes effects in sequence, waiting for each one's effect tree
+//!   (including actions it feeds back and their effects) to complete
+//! - **`Effect::Query`**: Loads read models from a `ReadModelStore`, with the same retries as event store operations
+//! - **`Effect::Costed`**: Executes the wrapped effect; its cost is charged to the action's budget (see [`budget`])
+//! - **`Effect::Retrying`**: Executes the wrapped effect; its event store and event bus operations use the effect's retry policy
 //!
 //! ### Stream Execution (Phase 8)
 //!
@@ -38,6 +44,14 @@
 //!
 //! **Use cases**: LLM token streaming, WebSocket message streams, database cursors, SSE, multi-agent progress tracking
 //!
+//! ### Trace Propagation
+//!
+//! Effect tasks run inside the `tracing` span that was current when the
+//! reducer returned them, and mailbox mode re-enters the sender's span in the
+//! event loop. Actions fed back by effects are therefore reduced within the
+//! trace of the action that started the workflow. With `tracing-opentelemetry`
+//! the OpenTelemetry context travels with the span.
+//!
 //! ## Example
 //!
 //! ```ignore
@@ -57,7 +71,7 @@
 //! let value = store.state(|s| s.some_field).await;
 //! ```
 
-use composable_rust_core::{effect::Effect, reducer::Reducer};
+use composable_rust_core::{effect::Effect, reducer::{ReduceContext, Reducer}};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -70,6 +84,97 @@ pub mod circuit_breaker;
 /// Prometheus metrics for observability
 pub mod metrics;
 
+/// OpenTelemetry (OTLP) export of runtime metrics
+#[cfg(feature = "otel")]
+pub mod otel;
+
+/// Hot-reloadable runtime configuration
+pub mod runtime_config;
+
+/// Typed aggregate repository facade over Store
+pub mod aggregate;
+
+/// Effect cost accounting and per-action budgets
+pub mod budget;
+
+/// Event bus subscriber that feeds external topics into a Store
+pub mod listener;
+
+/// Relay publishing transactional outbox entries to the event bus
+pub mod outbox;
+
+/// Store-level graceful degradation while a dependency is down
+pub mod degradation;
+
+/// Health aggregation across stores and components
+pub mod health;
+
+/// Best-effort two-phase commit across stores in the same process
+pub mod coordination;
+
+/// Filtered, lag-aware action streaming for WebSocket connections
+pub mod streaming;
+
+/// Action broadcast subscriptions with lag statistics and adaptive capacity
+pub mod subscription;
+
+/// Accepted-vs-completed tracking for asynchronous request patterns
+pub mod tracking;
+
+/// Subscriptions to selected slices of Store state
+pub mod observe;
+
+/// Dead letter queue persisted in an event stream
+pub mod persistent_dlq;
+
+/// Serialization of Store state for export and import
+pub mod state_codec;
+
+/// Recurring actions dispatched to a Store on interval or cron schedules
+pub mod scheduler;
+
+/// Duplicate action suppression by idempotency key
+pub mod idempotency;
+
+/// Structural diffs of Store state around each reduction
+pub mod state_diff;
+
+/// Actions wrapped with their dispatch time, source and correlation
+pub mod envelope;
+
+/// Read-side caching decorator for event stores
+pub mod event_cache;
+
+/// Catching reducer panics so a Store keeps serving
+pub mod supervision;
+
+/// Keyed state split across independently locked Store partitions
+pub mod partition;
+
+/// Structured failures recorded in the Store's dead letter queue
+pub mod failure;
+
+/// Seeded, single-threaded execution for reproducing effect interleavings
+pub mod simulation;
+
+/// Common imports for wiring stores: the core prelude plus the Store types
+///
+/// ```ignore
+/// use composable_rust_runtime::prelude::*;
+/// ```
+pub mod prelude {
+    pub use crate::envelope::{ActionSource, Envelope};
+    pub use crate::observe::StateSubscription;
+    pub use crate::subscription::ActionSubscription;
+    pub use crate::supervision::SupervisionStrategy;
+    pub use crate::tracking::{CompletionStatus, TrackingId};
+    pub use crate::{
+        DeadLetterQueue, EffectLimitPolicy, EffectOutcome, HealthStatus, OperationPolicy, RetryPolicy, ShutdownMode, Store,
+        StoreConfig, StoreError, TrackingMode,
+    };
+    pub use composable_rust_core::prelude::*;
+}
+
 /// Error types for the Store runtime
 pub mod error {
     use thiserror::Error;
@@ -107,6 +212,19 @@ pub mod error {
         #[error("Shutdown timed out with {0} effects still running")]
         ShutdownTimeout(usize),
 
+        /// Shutdown timed out and aborted the effects still running
+        ///
+        /// Lists the operation of each aborted effect task, such as `future`
+        /// or `event_store.append_events`. Aborted effects fed no actions back.
+        #[error("Shutdown timed out and aborted {} effects: {}", .0.len(), .0.join(", "))]
+        EffectsAborted(Vec<&'static str>),
+
+        /// Shutdown timed out draining the mailbox
+        ///
+        /// Some accepted actions had not been reduced when the timeout elapsed.
+        #[error("Shutdown timed out with {0} queued actions not yet processed")]
+        DrainTimeout(usize),
+
         /// Timeout waiting for terminal action
         ///
         /// Returned by `send_and_wait_for` when the timeout expires before
@@ -120,13 +238,102 @@ pub mod error {
         /// store is shutting down.
         #[error("Action broadcast channel closed")]
         ChannelClosed,
+
+        /// One or more tracked effects failed
+        ///
+        /// Returned by [`EffectOutcome::into_result`](crate::EffectOutcome::into_result)
+        /// with the failures recorded for the handle.
+        #[error("{} effect(s) failed: {}", .0.len(), .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+        EffectsFailed(Vec<crate::EffectFailure>),
+
+        /// Action rejected by the configured rate limit
+        ///
+        /// Returned by `send()` when `max_actions_per_second` from the
+        /// runtime configuration has been reached for the current second.
+        /// Actions fed back by effects are not counted against the limit.
+        #[error("Rate limit exceeded: {0} actions per second")]
+        RateLimited(u32),
+
+        /// Mailbox event loop is no longer running
+        ///
+        /// Returned by `send()` in mailbox mode when the event-loop task has
+        /// stopped (for example because the reducer panicked).
+        #[error("Store mailbox closed")]
+        MailboxClosed,
+
+        /// Mailbox has no room for the action
+        ///
+        /// Returned by `try_send()` in mailbox mode instead of waiting for
+        /// queue space, so producers can shed load.
+        #[error("Store mailbox full (capacity {capacity})")]
+        MailboxFull {
+            /// Configured mailbox capacity
+            capacity: usize,
+        },
+
+        /// Loading the events to replay failed
+        ///
+        /// Returned by `hydrate_from_events()`; the state is left unchanged.
+        #[error("Failed to load events for replay: {0}")]
+        ReplayFailed(#[source] composable_rust_core::event_store::EventStoreError),
+
+        /// Action rejected because its effects exceed the configured budget
+        ///
+        /// Returned by `send()` when the effects produced for a root action
+        /// (and its feedback cascade) would exceed a limit of its
+        /// [`Budget`](crate::budget::Budget). The reducer's state changes are
+        /// kept; the effects are dropped.
+        #[error("Effect budget exceeded for {action}: {total} {cost} (limit {limit})")]
+        BudgetExceeded {
+            /// Kind label of the root action
+            action: &'static str,
+            /// Cost category that exceeded its limit
+            cost: String,
+            /// Configured limit
+            limit: u64,
+            /// Total that would have been charged
+            total: u64,
+        },
+
+        /// Action rejected because a dependency it needs is unavailable
+        ///
+        /// Returned by `send()` when the circuit breaker of a
+        /// [`DegradationPolicy`](crate::degradation::DegradationPolicy)
+        /// matching the action is open and the policy rejects (or its queue
+        /// is full).
+        #[error("Dependency unavailable: {dependency}")]
+        Degraded {
+            /// Name of the unavailable dependency
+            dependency: &'static str,
+        },
+
+        /// No tokio runtime to run a blocking call on
+        ///
+        /// Returned by `send_blocking()` when the Store was built outside a
+        /// runtime and no handle was attached with `with_runtime_handle()`.
+        #[error("No tokio runtime available for blocking call")]
+        NoRuntime,
+
+        /// Blocking call made from inside an async context
+        ///
+        /// Blocking a runtime worker thread could deadlock the Store; use the
+        /// async methods there instead.
+        #[error("Blocking Store call made from an async context")]
+        BlockingInAsyncContext,
+
+        /// Exported state could not be encoded, or imported state decoded
+        ///
+        /// Returned by `export_state()` and `import_state()`.
+        #[error(transparent)]
+        StateCodec(#[from] crate::state_codec::StateCodecError),
     }
 }
 
 /// Health check status levels
 ///
 /// Indicates the current health state of a component or system.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     /// Component is fully operational
     Healthy,
@@ -179,7 +386,9 @@ impl std::fmt::Display for HealthStatus {
 }
 
 /// Health check result for a component
-#[derive(Debug, Clone)]
+///
+/// Serializes to JSON with `metadata` as an object.
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct HealthCheck {
     /// Name of the component being checked
     pub component: String,
@@ -191,9 +400,18 @@ pub struct HealthCheck {
     pub message: Option<String>,
 
     /// Optional metadata (e.g., metrics, error counts)
+    #[serde(serialize_with = "serialize_metadata")]
     pub metadata: Vec<(String, String)>,
 }
 
+/// Serialize health check metadata pairs as a JSON object
+fn serialize_metadata<S>(metadata: &[(String, String)], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_map(metadata.iter().map(|(k, v)| (k, v)))
+}
+
 impl HealthCheck {
     /// Create a healthy check result
     #[must_use]
@@ -238,8 +456,9 @@ impl HealthCheck {
 
 /// Aggregated health report
 ///
-/// Combines multiple health checks into an overall system status.
-#[derive(Debug, Clone)]
+/// Combines multiple health checks into an overall system status. Build one
+/// from registered components with [`health::HealthRegistry::report`].
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct HealthReport {
     /// Overall system status (worst of all checks)
     pub status: HealthStatus,
@@ -302,6 +521,12 @@ impl HealthReport {
 /// let policy = RetryPolicy::new()
 ///     .with_max_attempts(10)
 ///     .with_initial_delay(Duration::from_millis(500));
+///
+/// // Give up once retrying would take more than 2 seconds overall
+/// let policy = RetryPolicy::new().with_max_total_delay(Duration::from_secs(2));
+///
+/// // Exact, reproducible delays for tests
+/// let policy = RetryPolicy::new().with_jitter(JitterStrategy::None);
 /// ```
 #[derive(Debug, Clone)]
 pub struct RetryPolicy {
@@ -316,6 +541,18 @@ pub struct RetryPolicy {
 
     /// Multiplier for exponential backoff (2.0 = double each time)
     backoff_multiplier: f64,
+
+    /// Cap on the time spent retrying, measured from the first attempt
+    max_total_delay: Option<Duration>,
+
+    /// Shared budget each retry draws a token from
+    budget: Option<RetryBudget>,
+
+    /// How delays are randomized
+    jitter: JitterStrategy,
+
+    /// Seeded generator for the jitter (thread RNG when `None`), shared by clones
+    rng: Option<Arc<Mutex<rand::rngs::StdRng>>>,
 }
 
 impl RetryPolicy {
@@ -333,6 +570,10 @@ impl RetryPolicy {
             initial_delay: Duration::from_secs(1),
             max_delay: Duration::from_secs(32),
             backoff_multiplier: 2.0,
+            max_total_delay: None,
+            budget: None,
+            jitter: JitterStrategy::Equal,
+            rng: None,
         }
     }
 
@@ -364,16 +605,87 @@ impl RetryPolicy {
         self
     }
 
+    /// Stop retrying once the next retry would end more than `max_total_delay`
+    /// after the first attempt started
+    ///
+    /// Bounds the worst-case latency of an operation regardless of the number
+    /// of attempts left, so backoff cannot outlive a request deadline.
+    #[must_use]
+    pub const fn with_max_total_delay(mut self, max_total_delay: Duration) -> Self {
+        self.max_total_delay = Some(max_total_delay);
+        self
+    }
+
+    /// Draw a token from `budget` for every retry, and stop when it is empty
+    ///
+    /// Share one budget between the policies of a dependency to cap the
+    /// retry load sent to it while it is failing.
+    #[must_use]
+    pub fn with_budget(mut self, budget: RetryBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Set how retry delays are randomized (default: [`JitterStrategy::Equal`])
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: JitterStrategy) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Seed the jitter so the delay sequence is reproducible
+    ///
+    /// Clones of the policy draw from the same seeded generator.
+    #[must_use]
+    pub fn with_jitter_seed(mut self, seed: u64) -> Self {
+        use rand::SeedableRng;
+
+        self.rng = Some(Arc::new(Mutex::new(rand::rngs::StdRng::seed_from_u64(seed))));
+        self
+    }
+
+    /// Decide whether to retry after `attempts` failed attempts
+    ///
+    /// `elapsed` is the time since the first attempt started. Returns the
+    /// delay to wait before the next attempt, taking a token from the budget
+    /// if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns why retrying stopped: no attempts left, the total delay cap
+    /// would be exceeded, or the budget is empty.
+    pub fn next_retry(&self, attempts: u32, elapsed: Duration) -> Result<Duration, RetryExhausted> {
+        if !self.should_retry(attempts) {
+            return Err(RetryExhausted::Attempts { attempts });
+        }
+
+        let delay = self.delay_for_attempt(attempts.saturating_sub(1));
+        if let Some(max_total_delay) = self.max_total_delay
+            && elapsed.saturating_add(delay) > max_total_delay
+        {
+            return Err(RetryExhausted::DeadlineExceeded {
+                elapsed,
+                max_total_delay,
+            });
+        }
+
+        if let Some(budget) = &self.budget
+            && !budget.try_acquire()
+        {
+            return Err(RetryExhausted::BudgetExhausted);
+        }
+        Ok(delay)
+    }
+
     /// Calculate delay for a given attempt number (0-indexed)
     ///
-    /// Uses exponential backoff with jitter:
-    /// `delay = min(initial_delay * multiplier^attempt, max_delay) * (0.5 + random(0.5))`
+    /// Uses exponential backoff, randomized by the [`JitterStrategy`]:
+    /// `delay = min(initial_delay * multiplier^attempt, max_delay)`, then
+    /// `delay * (0.5 + random(0.5))` with the default equal jitter.
     ///
     /// Jitter prevents thundering herd problem.
     #[must_use]
     pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
-        use rand::Rng;
-
         // Calculate exponential backoff: initial * multiplier^attempt
         // Note: Cast is safe since max_attempts defaults to 5 (well within i32 range)
         #[allow(clippy::cast_possible_wrap)]
@@ -383,20 +695,61 @@ impl RetryPolicy {
         // Cap at max_delay
         let capped_secs = base_delay_secs.min(self.max_delay.as_secs_f64());
 
-        // Add jitter: multiply by random value between 0.5 and 1.0
-        // This spreads out retries to prevent thundering herd
-        let jitter = rand::thread_rng().gen_range(0.5..=1.0);
+        // Add jitter: spreads out retries to prevent thundering herd
+        let low = match self.jitter {
+            JitterStrategy::None => return Duration::from_secs_f64(capped_secs),
+            JitterStrategy::Equal => 0.5,
+            JitterStrategy::Full => 0.0,
+        };
+        let jitter = self.random_factor(low);
         let final_secs = capped_secs * jitter;
 
         Duration::from_secs_f64(final_secs)
     }
 
+    /// Random factor between `low` and 1.0, from the seeded RNG if any
+    fn random_factor(&self, low: f64) -> f64 {
+        use rand::Rng;
+
+        match &self.rng {
+            Some(rng) => rng
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .gen_range(low..=1.0),
+            None => rand::thread_rng().gen_range(low..=1.0),
+        }
+    }
+
     /// Get maximum number of attempts
     #[must_use]
     pub const fn max_attempts(&self) -> u32 {
         self.max_attempts
     }
 
+    /// Get the initial delay before the first retry
+    #[must_use]
+    pub const fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    /// Get the maximum delay between retries
+    #[must_use]
+    pub const fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// Get the backoff multiplier
+    #[must_use]
+    pub const fn backoff_multiplier(&self) -> f64 {
+        self.backoff_multiplier
+    }
+
+    /// Get the cap on the total time spent retrying, if any
+    #[must_use]
+    pub const fn max_total_delay(&self) -> Option<Duration> {
+        self.max_total_delay
+    }
+
     /// Check if we should retry based on attempt number
     #[must_use]
     pub const fn should_retry(&self, attempt: u32) -> bool {
@@ -410,6 +763,190 @@ impl Default for RetryPolicy {
     }
 }
 
+impl From<RetryPolicy> for composable_rust_core::effect::EffectRetry {
+    fn from(policy: RetryPolicy) -> Self {
+        Self::attempts(policy.max_attempts)
+            .with_initial_delay(policy.initial_delay)
+            .with_max_delay(policy.max_delay)
+            .with_backoff_multiplier(policy.backoff_multiplier)
+    }
+}
+
+impl From<composable_rust_core::effect::EffectRetry> for RetryPolicy {
+    fn from(retry: composable_rust_core::effect::EffectRetry) -> Self {
+        Self {
+            max_attempts: retry.max_attempts,
+            initial_delay: retry.initial_delay,
+            max_delay: retry.max_delay,
+            backoff_multiplier: retry.backoff_multiplier,
+            ..Self::new()
+        }
+    }
+}
+
+/// How a [`RetryPolicy`] randomizes its backoff delays
+///
+/// Each variant applies to the capped exponential delay `d` of an attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JitterStrategy {
+    /// A random delay between 0 and `d`; spreads retries the most
+    Full,
+
+    /// A random delay between `d / 2` and `d`
+    #[default]
+    Equal,
+
+    /// Exactly `d`, for reproducible schedules in tests
+    None,
+}
+
+/// Why a [`RetryPolicy`] stopped retrying
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum RetryExhausted {
+    /// Every attempt allowed by `max_attempts` was made
+    #[error("Gave up after {attempts} attempts")]
+    Attempts {
+        /// Attempts made
+        attempts: u32,
+    },
+
+    /// The next retry would end past the policy's `max_total_delay`
+    #[error("Retry deadline exceeded after {elapsed:?} (limit {max_total_delay:?})")]
+    DeadlineExceeded {
+        /// Time spent since the first attempt
+        elapsed: Duration,
+        /// Configured cap
+        max_total_delay: Duration,
+    },
+
+    /// The shared [`RetryBudget`] had no tokens left
+    #[error("Retry budget exhausted")]
+    BudgetExhausted,
+}
+
+impl RetryExhausted {
+    /// Short, stable name of the reason, used as a metric label
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Attempts { .. } => "attempts",
+            Self::DeadlineExceeded { .. } => "deadline_exceeded",
+            Self::BudgetExhausted => "budget_exhausted",
+        }
+    }
+}
+
+/// Token bucket limiting retries across operations
+///
+/// Each retry takes one token; tokens refill continuously at a fixed rate up
+/// to the capacity. Clones share the same bucket, so one budget can be handed
+/// to the retry policies of every operation hitting the same dependency.
+///
+/// ```ignore
+/// // Bursts of 20 retries, then 2 per second
+/// let budget = RetryBudget::new(20, 2.0);
+/// let policy = RetryPolicy::new().with_budget(budget.clone());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    capacity: f64,
+    refill_per_second: f64,
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: tokio::time::Instant,
+}
+
+impl RetryBudget {
+    /// A full budget of `capacity` tokens, refilling `refill_per_second`
+    #[must_use]
+    pub fn new(capacity: u32, refill_per_second: f64) -> Self {
+        let capacity = f64::from(capacity);
+        Self {
+            capacity,
+            refill_per_second: refill_per_second.max(0.0),
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                tokens: capacity,
+                refilled_at: tokio::time::Instant::now(),
+            })),
+        }
+    }
+
+    /// Take a token, returning `false` if none is available
+    #[must_use]
+    pub fn try_acquire(&self) -> bool {
+        let mut bucket = self.refill();
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whole tokens currently available
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Bounded by the u32 capacity
+    pub fn available(&self) -> u32 {
+        self.refill().tokens.floor() as u32
+    }
+
+    fn refill(&self) -> std::sync::MutexGuard<'_, TokenBucket> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let now = tokio::time::Instant::now();
+        let earned = now.duration_since(bucket.refilled_at).as_secs_f64() * self.refill_per_second;
+        bucket.tokens = (bucket.tokens + earned).min(self.capacity);
+        bucket.refilled_at = now;
+        bucket
+    }
+}
+
+/// Retry and dead-letter behavior for one effect operation
+///
+/// Registered per operation name with [`StoreConfig::with_operation_policy`].
+/// The Store's operations are `append_events`, `load_events`,
+/// `save_snapshot`, `load_snapshot` and `publish`; operations without a
+/// policy use the Store's retry policy and dead-letter exhausted failures.
+///
+/// # Example
+///
+/// ```ignore
+/// let config = StoreConfig::default()
+///     .with_operation_policy("append_events", OperationPolicy::new(RetryPolicy::new().with_max_attempts(5)))
+///     .with_operation_policy(
+///         "publish",
+///         OperationPolicy::new(RetryPolicy::new().with_max_attempts(10)).without_dead_letter(),
+///     );
+/// ```
+#[derive(Debug, Clone)]
+pub struct OperationPolicy {
+    /// Attempts and backoff for this operation
+    pub retry: RetryPolicy,
+    /// Whether exhausted failures are pushed to the DLQ
+    pub dead_letter: bool,
+}
+
+impl OperationPolicy {
+    /// Retry with `retry`, dead-lettering exhausted failures
+    #[must_use]
+    pub const fn new(retry: RetryPolicy) -> Self {
+        Self {
+            retry,
+            dead_letter: true,
+        }
+    }
+
+    /// Drop exhausted failures instead of pushing them to the DLQ
+    #[must_use]
+    pub const fn without_dead_letter(mut self) -> Self {
+        self.dead_letter = false;
+        self
+    }
+}
+
 /// Circuit breaker state
 ///
 /// A circuit breaker prevents cascading failures by "opening" after
@@ -435,6 +972,92 @@ pub enum CircuitBreakerError {
     Open,
 }
 
+/// Point-in-time view of a [`CircuitBreaker`], from [`CircuitBreaker::stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerStats {
+    /// Current state
+    pub state: CircuitState,
+
+    /// Failures recorded since the breaker was created
+    pub failure_count: u64,
+
+    /// Current run of consecutive failures
+    pub consecutive_failures: usize,
+
+    /// Number of times the circuit has opened
+    pub open_count: u64,
+
+    /// When the state last changed, `None` if it never has
+    pub last_transition: Option<std::time::SystemTime>,
+}
+
+/// Recent calls a failure-rate circuit breaker looks at
+///
+/// Converts from a `usize` (a count of calls) or a [`Duration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureWindow {
+    /// The last `n` calls
+    Calls(usize),
+
+    /// The calls made during the last period
+    Time(Duration),
+}
+
+impl From<usize> for FailureWindow {
+    fn from(calls: usize) -> Self {
+        Self::Calls(calls)
+    }
+}
+
+impl From<Duration> for FailureWindow {
+    fn from(period: Duration) -> Self {
+        Self::Time(period)
+    }
+}
+
+/// Failure-rate trip condition of a [`CircuitBreaker`]
+#[derive(Debug, Clone, Copy)]
+struct FailureRate {
+    /// Fraction of failed calls (0.0 to 1.0) that opens the circuit
+    threshold: f64,
+    window: FailureWindow,
+}
+
+/// Outcomes of the calls inside the failure-rate window, oldest first
+#[derive(Debug, Default)]
+struct CallWindow {
+    /// `(when, failed)` per call
+    calls: VecDeque<(std::time::Instant, bool)>,
+    failures: usize,
+}
+
+impl CallWindow {
+    /// Record a call and return `(calls, failures)` in the window
+    fn record(&mut self, failed: bool, window: FailureWindow) -> (usize, usize) {
+        let now = std::time::Instant::now();
+        self.calls.push_back((now, failed));
+        self.failures += usize::from(failed);
+
+        while let Some(&(at, failed)) = self.calls.front() {
+            let expired = match window {
+                FailureWindow::Calls(n) => self.calls.len() > n,
+                FailureWindow::Time(period) => now.duration_since(at) > period,
+            };
+            if !expired {
+                break;
+            }
+            self.calls.pop_front();
+            self.failures -= usize::from(failed);
+        }
+        (self.calls.len(), self.failures)
+    }
+
+    fn clear(&mut self) {
+        self.calls.clear();
+        self.failures = 0;
+    }
+}
+
 /// Circuit breaker for preventing cascading failures
 ///
 /// The circuit breaker pattern prevents cascading failures by tracking
@@ -449,11 +1072,17 @@ pub enum CircuitBreakerError {
 ///
 /// # State Transitions
 ///
-/// - `Closed` → `Open`: After `failure_threshold` consecutive failures
+/// - `Closed` → `Open`: After `failure_threshold` consecutive failures, or
+///   once the failure rate over the window set with
+///   [`with_failure_rate`](Self::with_failure_rate) reaches its threshold
 /// - `Open` → `HalfOpen`: After `timeout` duration
 /// - `HalfOpen` → `Closed`: After `success_threshold` consecutive successes
 /// - `HalfOpen` → `Open`: On any failure
 ///
+/// By default every call is let through while `HalfOpen`; use
+/// [`with_half_open_max_calls`](Self::with_half_open_max_calls) to cap the
+/// number of concurrent probes.
+///
 /// # Example
 ///
 /// ```ignore
@@ -491,6 +1120,36 @@ pub struct CircuitBreaker {
 
     /// Number of consecutive successes in `HalfOpen` to close circuit
     success_threshold: usize,
+
+    /// Maximum concurrent calls in `HalfOpen` (unlimited when `None`)
+    half_open_max_calls: Option<usize>,
+
+    /// Probe calls in flight in `HalfOpen`
+    half_open_in_flight: Arc<AtomicUsize>,
+
+    /// Failure-rate mode; consecutive failures are counted when `None`
+    failure_rate: Option<FailureRate>,
+
+    /// Calls the failure-rate window must hold before it can open the circuit
+    minimum_calls: usize,
+
+    /// Calls inside the failure-rate window (shared between clones)
+    window: Arc<Mutex<CallWindow>>,
+
+    /// Total failures recorded
+    total_failures: Arc<AtomicU64>,
+
+    /// Number of transitions to `Open`
+    open_count: Arc<AtomicU64>,
+
+    /// Timestamp of the last state change (nanoseconds since epoch, 0 if none)
+    last_transition: Arc<AtomicU64>,
+
+    /// Publishes every state change to subscribers
+    transitions: Arc<watch::Sender<CircuitState>>,
+
+    /// Optional hot-reloadable thresholds (override the fixed values above)
+    config: Option<runtime_config::ConfigHandle>,
 }
 
 impl CircuitBreaker {
@@ -510,6 +1169,16 @@ impl CircuitBreaker {
             failure_threshold: 5,
             timeout: Duration::from_secs(60),
             success_threshold: 2,
+            half_open_max_calls: None,
+            half_open_in_flight: Arc::new(AtomicUsize::new(0)),
+            failure_rate: None,
+            minimum_calls: 10,
+            window: Arc::new(Mutex::new(CallWindow::default())),
+            total_failures: Arc::new(AtomicU64::new(0)),
+            open_count: Arc::new(AtomicU64::new(0)),
+            last_transition: Arc::new(AtomicU64::new(0)),
+            transitions: Arc::new(watch::Sender::new(CircuitState::Closed)),
+            config: None,
         }
     }
 
@@ -540,39 +1209,179 @@ impl CircuitBreaker {
         self
     }
 
-    /// Get current circuit state
+    /// Limit the probe calls let through while `HalfOpen`
+    ///
+    /// At most `max_calls` (at least 1) calls run at once while the circuit
+    /// tests recovery; the rest fail fast with [`CircuitBreakerError::Open`]
+    /// until a probe completes, the circuit closes, or it reopens. Without a
+    /// limit every caller is let through, which can overwhelm a dependency
+    /// that is still recovering.
     #[must_use]
-    pub fn state(&self) -> CircuitState {
-        match self.state.load(Ordering::Acquire) {
-            1 => CircuitState::Open,
-            2 => CircuitState::HalfOpen,
-            _ => CircuitState::Closed, // Includes 0 and any unexpected values
-        }
+    pub const fn with_half_open_max_calls(mut self, max_calls: usize) -> Self {
+        self.half_open_max_calls = Some(if max_calls == 0 { 1 } else { max_calls });
+        self
     }
 
-    /// Check if we should allow a request through
+    /// Open on the failure rate over a window of recent calls
     ///
-    /// Returns `Ok(())` if request should proceed, `Err` if circuit is open.
-    fn check(&self) -> Result<(), CircuitBreakerError> {
-        let current_state = self.state();
+    /// Replaces the consecutive-failure mode: while closed, the circuit opens
+    /// once at least `threshold` (0.0 to 1.0) of the calls in `window` failed.
+    /// The rate is only evaluated once the window holds the minimum number of
+    /// calls (10 by default, see [`with_minimum_calls`](Self::with_minimum_calls)),
+    /// so a couple of early failures do not trip it.
+    ///
+    /// ```ignore
+    /// // Open when half the calls of the last 30 seconds failed
+    /// let breaker = CircuitBreaker::new().with_failure_rate(0.5, Duration::from_secs(30));
+    ///
+    /// // Open when 20 of the last 100 calls failed
+    /// let breaker = CircuitBreaker::new().with_failure_rate(0.2, 100);
+    /// ```
+    #[must_use]
+    pub fn with_failure_rate(mut self, threshold: f64, window: impl Into<FailureWindow>) -> Self {
+        self.failure_rate = Some(FailureRate {
+            threshold: threshold.clamp(0.0, 1.0),
+            window: window.into(),
+        });
+        self
+    }
 
-        match current_state {
-            CircuitState::Open => {
-                // Check if timeout has elapsed
-                let opened_at_nanos = self.opened_at.load(Ordering::Acquire);
-                // Note: Truncation acceptable for nanosecond timestamps (wraps every ~584 years)
-                #[allow(clippy::cast_possible_truncation)]
-                let now_nanos = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or(Duration::ZERO)
-                    .as_nanos() as u64;
+    /// Set the call volume the failure-rate window needs before it can open
+    /// the circuit
+    ///
+    /// Only used with [`with_failure_rate`](Self::with_failure_rate).
+    #[must_use]
+    pub const fn with_minimum_calls(mut self, calls: usize) -> Self {
+        self.minimum_calls = calls;
+        self
+    }
+
+    /// Read thresholds from a hot-reloadable configuration handle
+    ///
+    /// When set, the `circuit_breaker` settings of the handle's current
+    /// configuration take precedence over the values passed to the other
+    /// builder methods, and are re-read on every state transition check.
+    #[must_use]
+    pub fn with_config_handle(mut self, handle: runtime_config::ConfigHandle) -> Self {
+        self.config = Some(handle);
+        self
+    }
+
+    /// Effective `(failure_threshold, timeout, success_threshold)`
+    fn thresholds(&self) -> (usize, Duration, usize) {
+        self.config.as_ref().map_or(
+            (self.failure_threshold, self.timeout, self.success_threshold),
+            |handle| {
+                let settings = &handle.current().circuit_breaker;
+                (settings.failure_threshold, settings.timeout, settings.success_threshold)
+            },
+        )
+    }
+
+    /// Get current circuit state
+    #[must_use]
+    pub fn state(&self) -> CircuitState {
+        match self.state.load(Ordering::Acquire) {
+            1 => CircuitState::Open,
+            2 => CircuitState::HalfOpen,
+            _ => CircuitState::Closed, // Includes 0 and any unexpected values
+        }
+    }
+
+    /// Watch the circuit's state changes
+    ///
+    /// The receiver starts at the current state and is updated on every
+    /// transition, so health checks can react to an opening circuit without
+    /// polling or parsing metrics. Clones of the breaker share the channel.
+    ///
+    /// ```ignore
+    /// let mut changes = breaker.subscribe();
+    /// while changes.changed().await.is_ok() {
+    ///     health.set_degraded("payments", *changes.borrow() == CircuitState::Open);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<CircuitState> {
+        self.transitions.subscribe()
+    }
+
+    /// Snapshot of the circuit's state and counters
+    #[must_use]
+    pub fn stats(&self) -> CircuitBreakerStats {
+        let last_transition = self.last_transition.load(Ordering::Acquire);
+        CircuitBreakerStats {
+            state: self.state(),
+            failure_count: self.total_failures.load(Ordering::Relaxed),
+            consecutive_failures: self.failure_count.load(Ordering::Acquire),
+            open_count: self.open_count.load(Ordering::Relaxed),
+            last_transition: (last_transition != 0).then(|| {
+                std::time::UNIX_EPOCH + Duration::from_nanos(last_transition)
+            }),
+        }
+    }
+
+    /// Publish a state change that was just stored
+    fn notify_transition(&self, to: CircuitState) {
+        // Note: Truncation acceptable for nanosecond timestamps (wraps every ~584 years)
+        #[allow(clippy::cast_possible_truncation)]
+        let now_nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_nanos() as u64;
+        self.last_transition.store(now_nanos, Ordering::Release);
+        if to == CircuitState::Open {
+            self.open_count.fetch_add(1, Ordering::Relaxed);
+        }
+        self.transitions.send_replace(to);
+    }
+
+    /// Whether a call made now would be rejected
+    ///
+    /// True while the circuit is open and its timeout has not elapsed, or
+    /// while it is half-open with every probe slot taken. Unlike
+    /// [`call`](Self::call), this never transitions the circuit.
+    #[must_use]
+    pub fn is_rejecting(&self) -> bool {
+        match self.state() {
+            CircuitState::Open => self.open_for() < self.thresholds().1,
+            CircuitState::HalfOpen => self
+                .half_open_max_calls
+                .is_some_and(|max| self.half_open_in_flight.load(Ordering::Acquire) >= max),
+            CircuitState::Closed => false,
+        }
+    }
+
+    /// Time elapsed since the circuit was last opened
+    fn open_for(&self) -> Duration {
+        let opened_at_nanos = self.opened_at.load(Ordering::Acquire);
+        // Note: Truncation acceptable for nanosecond timestamps (wraps every ~584 years)
+        #[allow(clippy::cast_possible_truncation)]
+        let now_nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_nanos() as u64;
+
+        Duration::from_nanos(now_nanos.saturating_sub(opened_at_nanos))
+    }
+
+    /// Check if we should allow a request through
+    ///
+    /// Returns `Ok(())` if request should proceed, `Err` if circuit is open.
+    fn check(&self) -> Result<(), CircuitBreakerError> {
+        let current_state = self.state();
 
-                let elapsed = Duration::from_nanos(now_nanos.saturating_sub(opened_at_nanos));
+        match current_state {
+            CircuitState::Open => {
+                // Check if timeout has elapsed
+                let (_, timeout, _) = self.thresholds();
 
-                if elapsed >= self.timeout {
+                if self.open_for() >= timeout {
                     // Transition to HalfOpen
                     self.state.store(CircuitState::HalfOpen as u8, Ordering::Release);
                     self.success_count.store(0, Ordering::Release);
+                    // This call is the first probe
+                    self.half_open_in_flight.store(1, Ordering::Release);
+                    self.notify_transition(CircuitState::HalfOpen);
 
                     metrics::counter!("circuit_breaker.state_change", "from" => "open", "to" => "half_open")
                         .increment(1);
@@ -583,10 +1392,34 @@ impl CircuitBreaker {
                     Err(CircuitBreakerError::Open)
                 }
             },
-            CircuitState::Closed | CircuitState::HalfOpen => Ok(()),
+            CircuitState::HalfOpen => self.acquire_probe(),
+            CircuitState::Closed => Ok(()),
         }
     }
 
+    /// Take a `HalfOpen` probe slot, if the calls are limited
+    fn acquire_probe(&self) -> Result<(), CircuitBreakerError> {
+        let Some(max_calls) = self.half_open_max_calls else {
+            return Ok(());
+        };
+        self.half_open_in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |in_flight| {
+                (in_flight < max_calls).then_some(in_flight + 1)
+            })
+            .map(|_| ())
+            .map_err(|_| {
+                tracing::debug!(max_calls, "Circuit breaker half-open probe limit reached, rejecting");
+                CircuitBreakerError::Open
+            })
+    }
+
+    /// Free the probe slot of a completed `HalfOpen` call
+    fn release_probe(&self) {
+        let _ = self
+            .half_open_in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |in_flight| in_flight.checked_sub(1));
+    }
+
     /// Record a successful operation
     pub fn record_success(&self) {
         let current_state = self.state();
@@ -595,15 +1428,22 @@ impl CircuitBreaker {
             CircuitState::Closed => {
                 // Reset failure count
                 self.failure_count.store(0, Ordering::Release);
+                if let Some(rate) = self.failure_rate {
+                    self.failure_rate_reached(rate, false);
+                }
             },
             CircuitState::HalfOpen => {
+                self.release_probe();
                 let successes = self.success_count.fetch_add(1, Ordering::AcqRel) + 1;
+                let (_, _, success_threshold) = self.thresholds();
 
-                if successes >= self.success_threshold {
+                if successes >= success_threshold {
                     // Close the circuit
                     self.state.store(CircuitState::Closed as u8, Ordering::Release);
                     self.failure_count.store(0, Ordering::Release);
                     self.success_count.store(0, Ordering::Release);
+                    self.half_open_in_flight.store(0, Ordering::Release);
+                    self.notify_transition(CircuitState::Closed);
 
                     metrics::counter!("circuit_breaker.state_change", "from" => "half_open", "to" => "closed")
                         .increment(1);
@@ -618,15 +1458,22 @@ impl CircuitBreaker {
 
     /// Record a failed operation
     pub fn record_failure(&self) {
+        self.total_failures.fetch_add(1, Ordering::Relaxed);
         let current_state = self.state();
 
         match current_state {
             CircuitState::Closed => {
                 let failures = self.failure_count.fetch_add(1, Ordering::AcqRel) + 1;
+                let (failure_threshold, _, _) = self.thresholds();
+                let tripped = match self.failure_rate {
+                    Some(rate) => self.failure_rate_reached(rate, true),
+                    None => failures >= failure_threshold,
+                };
 
-                if failures >= self.failure_threshold {
+                if tripped {
                     // Open the circuit
                     self.state.store(CircuitState::Open as u8, Ordering::Release);
+                    self.lock_window().clear();
 
                     // Note: Truncation acceptable for nanosecond timestamps (wraps every ~584 years)
                     #[allow(clippy::cast_possible_truncation)]
@@ -635,12 +1482,13 @@ impl CircuitBreaker {
                         .unwrap_or(Duration::ZERO)
                         .as_nanos() as u64;
                     self.opened_at.store(now_nanos, Ordering::Release);
+                    self.notify_transition(CircuitState::Open);
 
                     metrics::counter!("circuit_breaker.state_change", "from" => "closed", "to" => "open")
                         .increment(1);
                     tracing::warn!(
                         failures = failures,
-                        threshold = self.failure_threshold,
+                        threshold = failure_threshold,
                         "Circuit breaker opening due to failures"
                     );
                 }
@@ -649,6 +1497,7 @@ impl CircuitBreaker {
                 // Any failure in HalfOpen opens circuit immediately
                 self.state.store(CircuitState::Open as u8, Ordering::Release);
                 self.success_count.store(0, Ordering::Release);
+                self.half_open_in_flight.store(0, Ordering::Release);
 
                 // Note: Truncation acceptable for nanosecond timestamps (wraps every ~584 years)
                 #[allow(clippy::cast_possible_truncation)]
@@ -657,6 +1506,7 @@ impl CircuitBreaker {
                     .unwrap_or(Duration::ZERO)
                     .as_nanos() as u64;
                 self.opened_at.store(now_nanos, Ordering::Release);
+                self.notify_transition(CircuitState::Open);
 
                 metrics::counter!("circuit_breaker.state_change", "from" => "half_open", "to" => "open")
                     .increment(1);
@@ -668,6 +1518,32 @@ impl CircuitBreaker {
         }
     }
 
+    /// Record a call in the failure-rate window and check it against the rate
+    fn failure_rate_reached(&self, rate: FailureRate, failed: bool) -> bool {
+        let (calls, failures) = self.lock_window().record(failed, rate.window);
+        if calls < self.minimum_calls.max(1) {
+            return false;
+        }
+
+        #[allow(clippy::cast_precision_loss)] // Window sizes are far below 2^52
+        let observed = failures as f64 / calls as f64;
+        let reached = observed >= rate.threshold;
+        if reached {
+            tracing::warn!(
+                calls,
+                failures,
+                rate = observed,
+                threshold = rate.threshold,
+                "Circuit breaker failure rate reached threshold"
+            );
+        }
+        reached
+    }
+
+    fn lock_window(&self) -> std::sync::MutexGuard<'_, CallWindow> {
+        self.window.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
     /// Execute an operation with circuit breaker protection
     ///
     /// # Errors
@@ -704,6 +1580,16 @@ impl Clone for CircuitBreaker {
             failure_threshold: self.failure_threshold,
             timeout: self.timeout,
             success_threshold: self.success_threshold,
+            half_open_max_calls: self.half_open_max_calls,
+            half_open_in_flight: Arc::clone(&self.half_open_in_flight),
+            failure_rate: self.failure_rate,
+            minimum_calls: self.minimum_calls,
+            window: Arc::clone(&self.window),
+            total_failures: Arc::clone(&self.total_failures),
+            open_count: Arc::clone(&self.open_count),
+            last_transition: Arc::clone(&self.last_transition),
+            transitions: Arc::clone(&self.transitions),
+            config: self.config.clone(),
         }
     }
 }
@@ -801,18 +1687,35 @@ impl<T> DeadLetter<T> {
 /// // Check queue size
 /// println!("Failed operations: {}", dlq.len());
 ///
+/// // Or get told as entries arrive
+/// dlq.on_push(|entry| alert_on_call(&entry.error_message));
+///
 /// // Drain and retry
 /// for entry in dlq.drain() {
 ///     println!("Retry: {:?}", entry);
 /// }
 /// ```
-#[derive(Debug)]
 pub struct DeadLetterQueue<T> {
     /// The queue storage
     queue: Arc<Mutex<VecDeque<DeadLetter<T>>>>,
 
     /// Maximum queue size
     max_size: usize,
+
+    /// Callbacks run for every pushed entry, shared by clones
+    hooks: Arc<std::sync::RwLock<Vec<DeadLetterHook<T>>>>,
+}
+
+/// Callback registered with [`DeadLetterQueue::on_push`]
+type DeadLetterHook<T> = Arc<dyn Fn(&DeadLetter<T>) + Send + Sync>;
+
+impl<T> std::fmt::Debug for DeadLetterQueue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeadLetterQueue")
+            .field("len", &self.len())
+            .field("max_size", &self.max_size)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<T> DeadLetterQueue<T> {
@@ -830,9 +1733,23 @@ impl<T> DeadLetterQueue<T> {
         Self {
             queue: Arc::new(Mutex::new(VecDeque::new())),
             max_size,
+            hooks: Arc::default(),
         }
     }
 
+    /// Run `hook` for every entry pushed from now on
+    ///
+    /// Use this to page on-call or publish an alert instead of polling
+    /// [`len`](Self::len). Hooks run synchronously on the task that pushes,
+    /// before the entry is queued, and apply to every clone of this queue;
+    /// hand slow work (network calls) off to a spawned task.
+    pub fn on_push(&self, hook: impl Fn(&DeadLetter<T>) + Send + Sync + 'static) {
+        self.hooks
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(Arc::new(hook));
+    }
+
     /// Push a failed operation onto the queue
     ///
     /// If the queue is full, the oldest entry is dropped.
@@ -843,6 +1760,18 @@ impl<T> DeadLetterQueue<T> {
     /// - `error_message`: Description of the failure
     /// - `retry_count`: Number of times operation was retried
     pub fn push(&self, payload: T, error_message: String, retry_count: usize) {
+        let entry = DeadLetter::new(payload, error_message, retry_count);
+
+        // Run hooks without holding the queue lock, so they may inspect the queue
+        let hooks = self
+            .hooks
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone();
+        for hook in &hooks {
+            hook(&entry);
+        }
+
         let mut queue = self
             .queue
             .lock()
@@ -858,7 +1787,6 @@ impl<T> DeadLetterQueue<T> {
             );
         }
 
-        let entry = DeadLetter::new(payload, error_message, retry_count);
         queue.push_back(entry);
 
         // Intentional cast for metrics - queue size limited by max_size (usize) and f64 can
@@ -907,6 +1835,76 @@ impl<T> DeadLetterQueue<T> {
         entries
     }
 
+    /// Remove and return the entries matching `predicate`, oldest first
+    ///
+    /// Entries that do not match stay queued in their original order, so
+    /// operators can reprocess one kind of failure and leave the rest parked.
+    pub fn remove_where(&self, mut predicate: impl FnMut(&DeadLetter<T>) -> bool) -> Vec<DeadLetter<T>> {
+        let mut queue = self
+            .queue
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        let mut removed = Vec::new();
+        let mut kept = VecDeque::with_capacity(queue.len());
+        for entry in queue.drain(..) {
+            if predicate(&entry) {
+                removed.push(entry);
+            } else {
+                kept.push_back(entry);
+            }
+        }
+        *queue = kept;
+
+        #[allow(clippy::cast_precision_loss)] // Same as `push`
+        metrics::gauge!("dlq.size").set(queue.len() as f64);
+        metrics::counter!("dlq.drained").increment(removed.len() as u64);
+
+        tracing::info!(
+            count = removed.len(),
+            remaining = queue.len(),
+            "Removed entries from dead letter queue"
+        );
+
+        removed
+    }
+
+    /// Keep only the entries matching `predicate`, discarding the rest
+    pub fn retain(&self, mut predicate: impl FnMut(&DeadLetter<T>) -> bool) {
+        self.remove_where(|entry| !predicate(entry));
+    }
+
+    /// Iterate over a snapshot of the queue, oldest first
+    ///
+    /// The queue is not locked while iterating; entries pushed or removed
+    /// meanwhile are not reflected.
+    #[must_use]
+    pub fn iter(&self) -> std::vec::IntoIter<DeadLetter<T>>
+    where
+        T: Clone,
+    {
+        self.page(Pagination::new(0, usize::MAX)).into_iter()
+    }
+
+    /// Copy out one window of the queue, oldest first
+    ///
+    /// Lets tooling walk a large queue with [`Pagination::next`] without
+    /// cloning all of it at once.
+    #[must_use]
+    pub fn page(&self, page: Pagination) -> Vec<DeadLetter<T>>
+    where
+        T: Clone,
+    {
+        self.queue
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .skip(page.offset)
+            .take(page.limit)
+            .cloned()
+            .collect()
+    }
+
     /// Peek at the oldest entry without removing it
     #[must_use]
     pub fn peek(&self) -> Option<DeadLetter<T>>
@@ -927,11 +1925,21 @@ impl<T> DeadLetterQueue<T> {
     }
 }
 
+impl<T: Clone> IntoIterator for &DeadLetterQueue<T> {
+    type Item = DeadLetter<T>;
+    type IntoIter = std::vec::IntoIter<DeadLetter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<T> Clone for DeadLetterQueue<T> {
     fn clone(&self) -> Self {
         Self {
             queue: Arc::clone(&self.queue),
             max_size: self.max_size,
+            hooks: Arc::clone(&self.hooks),
         }
     }
 }
@@ -943,7 +1951,10 @@ impl<T> Default for DeadLetterQueue<T> {
 }
 
 pub use error::StoreError;
+pub use failure::{FailureError, OperationFailure};
 
+use composable_rust_core::effect::CancellationToken;
+use composable_rust_core::event_store::Pagination;
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering};
 use std::sync::{Mutex, Weak};
@@ -975,6 +1986,28 @@ pub struct StoreConfig {
     pub retry_policy: RetryPolicy,
     /// Default timeout for graceful shutdown
     pub default_shutdown_timeout: Duration,
+    /// Hot-reloadable runtime knobs (overrides `retry_policy` when set)
+    pub runtime_config: Option<runtime_config::ConfigHandle>,
+    /// Bounded action queue capacity (`None` = reduce on the caller's task)
+    pub mailbox_capacity: Option<usize>,
+    /// Destination for Store metrics (`None` = the global `metrics` facade)
+    pub metrics_recorder: Option<Arc<dyn crate::metrics::MetricsRecorder>>,
+    /// Grow the action broadcast channel under sustained lag (`None` = fixed)
+    pub broadcast_auto_resize: Option<subscription::BroadcastAutoResize>,
+    /// Completed `send_tracked` requests whose status is kept
+    pub tracking_retention: usize,
+    /// Per-operation overrides of `retry_policy` and dead-lettering, by operation name
+    pub operation_policies: Vec<(String, OperationPolicy)>,
+    /// Maximum number of `Future` and `Stream` effects running at once (`None` = unlimited)
+    pub max_concurrent_effects: Option<usize>,
+    /// What happens to effects beyond `max_concurrent_effects`
+    pub effect_limit_policy: EffectLimitPolicy,
+    /// Catch reducer panics instead of propagating them (`None` = propagate)
+    pub supervision: Option<supervision::SupervisionStrategy>,
+    /// Warn when a reducer runs longer than this while holding the state lock (`None` = never)
+    pub warn_if_reduce_exceeds: Option<Duration>,
+    /// Warn when a dispatch waits longer than this for the state lock (`None` = never)
+    pub warn_if_lock_wait_exceeds: Option<Duration>,
 }
 
 impl StoreConfig {
@@ -995,6 +2028,17 @@ impl StoreConfig {
             dlq_max_size,
             retry_policy,
             default_shutdown_timeout,
+            runtime_config: None,
+            mailbox_capacity: None,
+            metrics_recorder: None,
+            broadcast_auto_resize: None,
+            tracking_retention: 10_000,
+            operation_policies: Vec::new(),
+            max_concurrent_effects: None,
+            effect_limit_policy: EffectLimitPolicy::Queue,
+            supervision: None,
+            warn_if_reduce_exceeds: None,
+            warn_if_lock_wait_exceeds: None,
         }
     }
 
@@ -1007,7 +2051,7 @@ impl StoreConfig {
 
     /// Set the retry policy
     #[must_use]
-    pub const fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
         self.retry_policy = policy;
         self
     }
@@ -1018,6 +2062,140 @@ impl StoreConfig {
         self.default_shutdown_timeout = timeout;
         self
     }
+
+    /// Attach a hot-reloadable runtime configuration
+    ///
+    /// The Store samples the handle on every operation: its retry policy
+    /// replaces `retry_policy`, and its rate limit and DLQ thresholds are
+    /// applied to `send()` and `health()`.
+    #[must_use]
+    pub fn with_config_handle(mut self, handle: runtime_config::ConfigHandle) -> Self {
+        self.runtime_config = Some(handle);
+        self
+    }
+
+    /// Enable mailbox mode with a bounded action queue
+    ///
+    /// Instead of every `send()` contending on the state lock, actions are
+    /// pushed into a bounded channel and reduced one at a time by a single
+    /// event-loop task. This gives:
+    /// - **Backpressure**: `send()` waits while the queue holds `capacity` actions
+    /// - **FIFO ordering**: actions are reduced in the order they were enqueued
+    /// - **Lower contention**: only the event loop takes the state write lock
+    ///
+    /// A capacity of 0 is treated as 1.
+    #[must_use]
+    pub const fn with_mailbox(mut self, capacity: usize) -> Self {
+        self.mailbox_capacity = Some(capacity);
+        self
+    }
+
+    /// Send Store metrics to `recorder` instead of the global `metrics` facade
+    ///
+    /// Use [`NoopRecorder`](metrics::NoopRecorder) to disable metrics, or a
+    /// [`CapturingRecorder`](metrics::CapturingRecorder) to assert on them in
+    /// tests.
+    #[must_use]
+    pub fn with_metrics_recorder(mut self, recorder: Arc<dyn crate::metrics::MetricsRecorder>) -> Self {
+        self.metrics_recorder = Some(recorder);
+        self
+    }
+
+    /// Grow the action broadcast channel when subscribers lag persistently
+    ///
+    /// Live subscriptions migrate to the larger channel without losing or
+    /// reordering actions. See the [`subscription`] module.
+    #[must_use]
+    pub const fn with_broadcast_auto_resize(
+        mut self,
+        policy: subscription::BroadcastAutoResize,
+    ) -> Self {
+        self.broadcast_auto_resize = Some(policy);
+        self
+    }
+
+    /// Set how many completed `send_tracked` requests keep their status
+    #[must_use]
+    pub const fn with_tracking_retention(mut self, retention: usize) -> Self {
+        self.tracking_retention = retention;
+        self
+    }
+
+    /// Override retries and dead-lettering for the operation named `operation`
+    ///
+    /// Replaces an earlier policy for the same operation. Operations without
+    /// a policy use `retry_policy` (or the runtime configuration's) and
+    /// dead-letter exhausted failures.
+    #[must_use]
+    pub fn with_operation_policy(
+        mut self,
+        operation: impl Into<String>,
+        policy: OperationPolicy,
+    ) -> Self {
+        let operation = operation.into();
+        self.operation_policies.retain(|(name, _)| *name != operation);
+        self.operation_policies.push((operation, policy));
+        self
+    }
+
+    /// Limit how many `Future` and `Stream` effects run at once
+    ///
+    /// Effects beyond the limit are handled according to
+    /// [`with_effect_limit_policy`](Self::with_effect_limit_policy): queued
+    /// by default. Delays and other effect kinds are not limited. A limit of
+    /// 0 is treated as 1.
+    #[must_use]
+    pub const fn with_max_concurrent_effects(mut self, max: usize) -> Self {
+        self.max_concurrent_effects = Some(max);
+        self
+    }
+
+    /// Set what happens to effects beyond `max_concurrent_effects`
+    #[must_use]
+    pub const fn with_effect_limit_policy(mut self, policy: EffectLimitPolicy) -> Self {
+        self.effect_limit_policy = policy;
+        self
+    }
+
+    /// Catch reducer panics and keep serving subsequent actions
+    ///
+    /// See the [`supervision`](crate::supervision) module for what happens
+    /// to the poison action and the state.
+    #[must_use]
+    pub const fn with_supervision(mut self, strategy: supervision::SupervisionStrategy) -> Self {
+        self.supervision = Some(strategy);
+        self
+    }
+
+    /// Log a warning whenever a reducer runs longer than `threshold`
+    ///
+    /// The reducer holds the state write lock, so a slow reducer stalls
+    /// every other action. The warning carries the action label (see
+    /// [`Store::with_action_label`]) and the time taken.
+    #[must_use]
+    pub const fn with_slow_reduce_warning(mut self, threshold: Duration) -> Self {
+        self.warn_if_reduce_exceeds = Some(threshold);
+        self
+    }
+
+    /// Log a warning whenever an action waits longer than `threshold` for the state lock
+    ///
+    /// Long waits mean the Store is contended: slow reducers, or long
+    /// [`Store::state`] reads, hold the lock others need.
+    #[must_use]
+    pub const fn with_lock_wait_warning(mut self, threshold: Duration) -> Self {
+        self.warn_if_lock_wait_exceeds = Some(threshold);
+        self
+    }
+
+    /// The policy registered for `operation`, if any
+    #[must_use]
+    pub fn operation_policy(&self, operation: &str) -> Option<&OperationPolicy> {
+        self.operation_policies
+            .iter()
+            .find(|(name, _)| name == operation)
+            .map(|(_, policy)| policy)
+    }
 }
 
 impl Default for StoreConfig {
@@ -1026,6 +2204,17 @@ impl Default for StoreConfig {
             dlq_max_size: 1000,
             retry_policy: RetryPolicy::default(),
             default_shutdown_timeout: Duration::from_secs(30),
+            runtime_config: None,
+            mailbox_capacity: None,
+            metrics_recorder: None,
+            broadcast_auto_resize: None,
+            tracking_retention: 10_000,
+            operation_policies: Vec::new(),
+            max_concurrent_effects: None,
+            effect_limit_policy: EffectLimitPolicy::Queue,
+            supervision: None,
+            warn_if_reduce_exceeds: None,
+            warn_if_lock_wait_exceeds: None,
         }
     }
 }
@@ -1034,8 +2223,9 @@ impl Default for StoreConfig {
 ///
 /// # Modes
 ///
-/// - **Direct**: Tracks only immediate effects (default)
+/// - **Direct**: Tracks only immediate effects (default, [`Store::send()`])
 /// - **Cascading**: Tracks effects transitively, following the entire effect tree
+///   ([`Store::send_cascading()`])
 #[derive(Debug, Clone)]
 pub enum TrackingMode {
     /// Track only immediate effects spawned by this action
@@ -1049,6 +2239,76 @@ pub enum TrackingMode {
     },
 }
 
+/// A tracked effect whose operation failed
+///
+/// Recorded when an event store, event bus or read model operation fails
+/// after its retries, before its `on_error` callback runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectFailure {
+    /// Effect kind (`event_store`, `publish_event`, `query`)
+    pub effect: &'static str,
+    /// Operation that failed (`append_events`, `publish`, ...)
+    pub operation: &'static str,
+    /// The last error returned by the operation
+    pub error: String,
+}
+
+impl std::fmt::Display for EffectFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} failed: {}", self.effect, self.operation, self.error)
+    }
+}
+
+/// Summary of the effects tracked by an [`EffectHandle`]
+///
+/// Returned by [`EffectHandle::wait()`]. With cascading tracking it covers
+/// the effects of every feedback action as well.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EffectOutcome {
+    /// Effects that finished without a recorded failure
+    pub completed: usize,
+    /// Effects whose operation failed
+    pub failed: Vec<EffectFailure>,
+}
+
+impl EffectOutcome {
+    /// Whether no tracked effect failed
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// Turn recorded failures into [`StoreError::EffectsFailed`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StoreError::EffectsFailed`] if any tracked effect failed.
+    pub fn into_result(self) -> Result<usize, StoreError> {
+        if self.failed.is_empty() {
+            Ok(self.completed)
+        } else {
+            Err(StoreError::EffectsFailed(self.failed))
+        }
+    }
+}
+
+/// Finished effects and failures shared by a handle and its tracking
+#[derive(Debug, Default)]
+struct OutcomeLog {
+    /// Effects finished, successfully or not
+    finished: usize,
+    failed: Vec<EffectFailure>,
+}
+
+impl OutcomeLog {
+    fn summary(&self) -> EffectOutcome {
+        EffectOutcome {
+            completed: self.finished.saturating_sub(self.failed.len()),
+            failed: self.failed.clone(),
+        }
+    }
+}
+
 /// Handle for tracking effect completion
 ///
 /// Returned by [`Store::send()`] to allow waiting for effects to complete.
@@ -1059,14 +2319,18 @@ pub enum TrackingMode {
 ///
 /// ```ignore
 /// let handle = store.send(Action::Start).await;
-/// handle.wait_with_timeout(Duration::from_secs(5)).await?;
+/// let outcome = handle.wait_with_timeout(Duration::from_secs(5)).await?;
 /// // All effects from Action::Start are now complete
+/// for failure in &outcome.failed {
+///     tracing::warn!(%failure, "effect failed");
+/// }
 /// ```
 #[derive(Clone)]
 pub struct EffectHandle {
     mode: TrackingMode,
     effects: Arc<AtomicUsize>,
     completion: watch::Receiver<()>,
+    outcome: Arc<Mutex<OutcomeLog>>,
 }
 
 impl EffectHandle {
@@ -1081,21 +2345,23 @@ impl EffectHandle {
     /// A tuple of `(EffectHandle, EffectTracking)` where:
     /// - `EffectHandle` is returned to the caller for waiting
     /// - `EffectTracking` is used internally for effect execution
-    fn new<A>(mode: TrackingMode) -> (Self, EffectTracking<A>) {
+    fn new(mode: TrackingMode) -> (Self, EffectTracking) {
         let counter = Arc::new(AtomicUsize::new(0));
+        let outcome = Arc::new(Mutex::new(OutcomeLog::default()));
         let (tx, rx) = watch::channel(());
 
         let handle = Self {
             mode: mode.clone(),
             effects: Arc::clone(&counter),
             completion: rx,
+            outcome: Arc::clone(&outcome),
         };
 
         let tracking = EffectTracking {
             mode,
             counter,
             notifier: tx,
-            feedback_dest: FeedbackDestination::Auto(Weak::new()),
+            outcome,
         };
 
         (handle, tracking)
@@ -1123,9 +2389,18 @@ impl EffectHandle {
             mode: TrackingMode::Direct,
             effects: Arc::new(AtomicUsize::new(0)),
             completion: rx,
+            outcome: Arc::new(Mutex::new(OutcomeLog::default())),
         }
     }
 
+    /// A handle that completes once every one of `handles` has completed
+    fn join(handles: Vec<Self>) -> Self {
+        let (handle, _tracking) = Self::new(TrackingMode::Cascading {
+            children: Arc::new(Mutex::new(handles)),
+        });
+        handle
+    }
+
     /// Wait for all effects to complete
     ///
     /// Blocks until the effect counter reaches zero.
@@ -1137,9 +2412,10 @@ impl EffectHandle {
     ///
     /// # Returns
     ///
-    /// Returns when all effects are complete
+    /// How many tracked effects completed and which of them failed, once all
+    /// effects are complete
     #[allow(clippy::unwrap_used)] // Mutex poison is unrecoverable
-    pub async fn wait(&mut self) {
+    pub async fn wait(&mut self) -> EffectOutcome {
         // Wait for counter to reach zero
         while self.effects.load(Ordering::SeqCst) > 0 {
             let _ = self.completion.changed().await;
@@ -1159,10 +2435,18 @@ impl EffectHandle {
                 };
 
                 for mut handle in handles {
-                    Box::pin(handle.wait()).await;
+                    let child = Box::pin(handle.wait()).await;
+                    let mut outcome = self.outcome.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+                    outcome.finished += child.completed + child.failed.len();
+                    outcome.failed.extend(child.failed);
                 }
             }
         }
+
+        self.outcome
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .summary()
     }
 
     /// Wait for all effects to complete with a timeout
@@ -1173,7 +2457,7 @@ impl EffectHandle {
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if all effects completed in time
+    /// - `Ok(outcome)` if all effects completed in time (see [`wait()`](Self::wait))
     /// - `Err(())` if timeout was reached
     ///
     /// # Errors
@@ -1189,7 +2473,7 @@ impl EffectHandle {
     /// ```ignore
     /// handle.wait_with_timeout(Duration::from_secs(5)).await?;
     /// ```
-    pub async fn wait_with_timeout(&mut self, timeout: Duration) -> Result<(), ()> {
+    pub async fn wait_with_timeout(&mut self, timeout: Duration) -> Result<EffectOutcome, ()> {
         tokio::time::timeout(timeout, self.wait())
             .await
             .map_err(|_| ())
@@ -1231,14 +2515,14 @@ impl std::fmt::Debug for EffectHandle {
 ///
 /// This type is internal to the runtime and not exposed to users.
 /// It carries the tracking state through effect execution.
-struct EffectTracking<A> {
+struct EffectTracking {
     mode: TrackingMode,
     counter: Arc<AtomicUsize>,
     notifier: watch::Sender<()>,
-    feedback_dest: FeedbackDestination<A>,
+    outcome: Arc<Mutex<OutcomeLog>>,
 }
 
-impl<A> EffectTracking<A> {
+impl EffectTracking {
     /// Increment the effect counter (effect started)
     fn increment(&self) {
         self.counter.fetch_add(1, Ordering::SeqCst);
@@ -1246,65 +2530,521 @@ impl<A> EffectTracking<A> {
 
     /// Decrement the effect counter (effect completed)
     fn decrement(&self) {
+        self.outcome
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .finished += 1;
         self.counter.fetch_sub(1, Ordering::SeqCst);
         // Notify waiters on every completion (supports wait_until_n_complete)
         let _ = self.notifier.send(());
     }
-}
 
-impl<A> Clone for EffectTracking<A> {
-    fn clone(&self) -> Self {
-        Self {
-            mode: self.mode.clone(),
-            counter: Arc::clone(&self.counter),
-            notifier: self.notifier.clone(),
-            feedback_dest: self.feedback_dest.clone(),
-        }
+    /// Record that the running effect's operation failed
+    ///
+    /// Must be called before the effect's [`DecrementGuard`] drops.
+    fn fail(&self, effect: &'static str, operation: &'static str, error: &impl std::fmt::Display) {
+        self.record_failures([EffectFailure {
+            effect,
+            operation,
+            error: error.to_string(),
+        }]);
     }
-}
 
-/// Internal: Destination for actions produced by effects
-///
-/// Controls where actions go when effects produce them:
-/// - Auto: Send back to the Store automatically (production)
-/// - Queued: Push to a queue for manual processing (testing)
-enum FeedbackDestination<A> {
-    /// Auto-feedback to store (production mode)
-    Auto(Weak<()>), // Will hold Weak<Store> in full implementation
-
-    /// Queue for manual processing (test mode)
-    Queued(Arc<Mutex<VecDeque<A>>>),
-}
-
-impl<A> Clone for FeedbackDestination<A> {
-    fn clone(&self) -> Self {
-        match self {
-            Self::Auto(weak) => Self::Auto(Weak::clone(weak)),
-            Self::Queued(queue) => Self::Queued(Arc::clone(queue)),
+    /// Record failures reported by a nested handle
+    fn record_failures(&self, failures: impl IntoIterator<Item = EffectFailure>) {
+        self.outcome
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .failed
+            .extend(failures);
+    }
+
+    /// Tracking mode for an action fed back by this effect
+    ///
+    /// Cascading tracking gives the feedback action its own cascading
+    /// handle, so its effects (and theirs) are followed as well.
+    fn feedback_mode(&self) -> TrackingMode {
+        match self.mode {
+            TrackingMode::Direct => TrackingMode::Direct,
+            TrackingMode::Cascading { .. } => TrackingMode::Cascading {
+                children: Arc::new(Mutex::new(Vec::new())),
+            },
+        }
+    }
+
+    /// Record the handle of an action fed back by this effect
+    ///
+    /// Only cascading tracking keeps it; must be called before the effect's
+    /// [`DecrementGuard`] drops so waiters cannot miss the child.
+    fn adopt(&self, handle: EffectHandle) {
+        if let TrackingMode::Cascading { children } = &self.mode {
+            children
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .push(handle);
+        }
+    }
+}
+
+impl Clone for EffectTracking {
+    fn clone(&self) -> Self {
+        Self {
+            mode: self.mode.clone(),
+            counter: Arc::clone(&self.counter),
+            notifier: self.notifier.clone(),
+            outcome: Arc::clone(&self.outcome),
         }
     }
 }
 
+/// Internal: Alive while any user handle on a Store exists
+///
+/// Dropping it (with the last user handle) cancels the Store's token, so
+/// `Effect::FutureWithCancel` effects stop early.
+struct Liveness(CancellationToken);
+
+impl Drop for Liveness {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+/// Internal: Whether a Store handle keeps the Store alive
+///
+/// Handles given to users are strong. Effect tasks and the mailbox event
+/// loop hold weak handles, so they don't keep a Store nobody can reach
+/// running: once the last strong handle is dropped, actions fed back by
+/// effects are discarded.
+#[derive(Clone)]
+enum StoreHandle {
+    /// User handle
+    Strong(Arc<Liveness>),
+    /// Effect task handle
+    Weak(Weak<Liveness>),
+}
+
 /// Internal: RAII guard that decrements effect counter on drop
 ///
 /// Ensures the effect counter is always decremented, even if the effect panics.
-struct DecrementGuard<A>(EffectTracking<A>);
+struct DecrementGuard(EffectTracking);
 
-impl<A> Drop for DecrementGuard<A> {
+impl Drop for DecrementGuard {
     fn drop(&mut self) {
         self.0.decrement();
     }
 }
 
-/// Guard that decrements an atomic counter on drop (for shutdown tracking)
-struct AtomicCounterGuard(Arc<AtomicUsize>);
+/// Effects started and not yet finished, shared by a Store and its clones
+///
+/// Shutdown waits on `settled` instead of polling the count, and aborts the
+/// tasks still registered here once its grace period runs out.
+struct PendingEffects {
+    count: AtomicUsize,
+    /// Notified when the count, or the mailbox queue, drops to zero
+    settled: tokio::sync::Notify,
+    /// Operation and abort handle of spawned effect tasks, by task id
+    tasks: Mutex<std::collections::HashMap<u64, (&'static str, tokio::task::AbortHandle)>>,
+    next_task: AtomicU64,
+}
+
+impl PendingEffects {
+    fn new() -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            settled: tokio::sync::Notify::new(),
+            tasks: Mutex::new(std::collections::HashMap::new()),
+            next_task: AtomicU64::new(0),
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Abort every registered effect task, returning their operations
+    ///
+    /// Aborted tasks drop their guards, and with them their pending count,
+    /// the next time the runtime polls them.
+    fn abort_all(&self) -> Vec<&'static str> {
+        let mut tasks: Vec<_> = self
+            .tasks
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(|(id, (operation, task))| (*id, *operation, task.clone()))
+            .collect();
+        // In spawn order
+        tasks.sort_unstable_by_key(|(id, _, _)| *id);
+        tasks
+            .into_iter()
+            .map(|(_, operation, task)| {
+                task.abort();
+                operation
+            })
+            .collect()
+    }
+}
+
+/// Guard that counts one pending effect until dropped (for shutdown tracking)
+///
+/// Keeps the `store.effects.pending` gauge in step with the counter.
+struct PendingEffectGuard {
+    pending: Arc<PendingEffects>,
+    metrics: Arc<dyn crate::metrics::MetricsRecorder>,
+    /// Registration in `PendingEffects::tasks`, once spawned
+    task: Option<u64>,
+}
+
+impl PendingEffectGuard {
+    fn new(pending: &Arc<PendingEffects>, metrics: &Arc<dyn crate::metrics::MetricsRecorder>) -> Self {
+        let count = pending.count.fetch_add(1, Ordering::SeqCst) + 1;
+        Self::report(metrics.as_ref(), count);
+        Self {
+            pending: Arc::clone(pending),
+            metrics: Arc::clone(metrics),
+            task: None,
+        }
+    }
+
+    /// Spawn the effect task that owns this guard, registering it for abort
+    ///
+    /// `operation` names the task in [`StoreError::EffectsAborted`].
+    fn spawn<F>(mut self, operation: &'static str, task: F) -> tokio::task::JoinHandle<()>
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let pending = Arc::clone(&self.pending);
+        // Held across the spawn so the task cannot deregister before it is registered
+        let mut tasks = pending.tasks.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let id = pending.next_task.fetch_add(1, Ordering::Relaxed);
+        self.task = Some(id);
+        let handle = store::spawn_effect(async move {
+            let _pending_guard = self; // Decrement on drop, also when aborted
+            task.await;
+        });
+        tasks.insert(id, (operation, handle.abort_handle()));
+        handle
+    }
+
+    #[allow(clippy::cast_precision_loss)] // Effect counts are far below 2^52
+    fn report(metrics: &dyn crate::metrics::MetricsRecorder, count: usize) {
+        metrics.set_gauge("store.effects.pending", &[], count as f64);
+    }
+}
+
+impl Drop for PendingEffectGuard {
+    fn drop(&mut self) {
+        if let Some(id) = self.task {
+            self.pending
+                .tasks
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .remove(&id);
+        }
+        let count = self.pending.count.fetch_sub(1, Ordering::SeqCst) - 1;
+        Self::report(self.metrics.as_ref(), count);
+        if count == 0 {
+            self.pending.settled.notify_waiters();
+        }
+    }
+}
+
+/// Concurrency limit on spawned effects (`max_concurrent_effects`)
+struct EffectLimiter {
+    permits: Arc<tokio::sync::Semaphore>,
+    policy: EffectLimitPolicy,
+    /// Effects queued for a permit
+    waiting: AtomicUsize,
+}
+
+impl EffectLimiter {
+    fn new(max: usize, policy: EffectLimitPolicy) -> Self {
+        Self {
+            permits: Arc::new(tokio::sync::Semaphore::new(max.max(1))),
+            policy,
+            waiting: AtomicUsize::new(0),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)] // Effect counts are far below 2^52
+    fn report_waiting(metrics: &dyn crate::metrics::MetricsRecorder, count: usize) {
+        metrics.set_gauge("store.effects.waiting", &[], count as f64);
+    }
+}
+
+/// An effect's claim on the concurrency limit, taken before it is spawned
+enum EffectSlot {
+    /// No limit configured
+    Unlimited,
+    /// A permit was free
+    Running(tokio::sync::OwnedSemaphorePermit),
+    /// Wait for a permit inside the spawned task
+    Queued(Arc<EffectLimiter>, Arc<dyn crate::metrics::MetricsRecorder>),
+}
+
+impl EffectSlot {
+    /// Wait until the effect may run; the permit is held until it is dropped
+    async fn acquire(self) -> Option<tokio::sync::OwnedSemaphorePermit> {
+        match self {
+            Self::Unlimited => None,
+            Self::Running(permit) => Some(permit),
+            Self::Queued(limiter, metrics) => {
+                let permit = Arc::clone(&limiter.permits).acquire_owned().await.ok();
+                let waiting = limiter.waiting.fetch_sub(1, Ordering::SeqCst) - 1;
+                EffectLimiter::report_waiting(metrics.as_ref(), waiting);
+                permit
+            },
+        }
+    }
+}
+
+/// Pending `Effect::DelayKeyed` timers, by key
+#[derive(Debug, Default)]
+struct KeyedDelays {
+    next_id: AtomicU64,
+    pending: Mutex<std::collections::HashMap<std::borrow::Cow<'static, str>, (u64, tokio::task::AbortHandle)>>,
+}
+
+impl KeyedDelays {
+    /// Register the timer spawned by `spawn` under `key`, cancelling the one it replaces
+    ///
+    /// Returns whether a pending timer was replaced.
+    fn schedule(
+        &self,
+        key: std::borrow::Cow<'static, str>,
+        spawn: impl FnOnce(u64) -> tokio::task::JoinHandle<()>,
+    ) -> bool {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        // Held across the spawn so the timer cannot look itself up before it is registered
+        let mut pending = self.pending.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let timer = spawn(id).abort_handle();
+        pending.insert(key, (id, timer)).is_some_and(|(_, replaced)| {
+            replaced.abort();
+            true
+        })
+    }
+
+    /// Claim the timer `id` for `key` once it has elapsed
+    ///
+    /// Returns `false` if it was replaced in the meantime.
+    fn fire(&self, key: &str, id: u64) -> bool {
+        let mut pending = self.pending.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if pending.get(key).is_some_and(|(current, _)| *current == id) {
+            pending.remove(key);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Fixed one-second window used to enforce `max_actions_per_second`
+#[derive(Debug, Default)]
+struct RateWindow {
+    started: Option<std::time::Instant>,
+    count: u32,
+}
+
+impl RateWindow {
+    /// Record `count` actions, returning `false` (and recording none) if they
+    /// do not all fit in the limit for the current window
+    fn try_acquire(&mut self, limit: u32, count: u32) -> bool {
+        let now = std::time::Instant::now();
+        match self.started {
+            Some(started) if now.duration_since(started) < Duration::from_secs(1) => {
+                if self.count.saturating_add(count) > limit {
+                    return false;
+                }
+                self.count += count;
+            },
+            _ => {
+                if count > limit {
+                    return false;
+                }
+                self.started = Some(now);
+                self.count = count;
+            },
+        }
+        true
+    }
+
+    /// Forget `count` recorded actions of the current window
+    const fn release(&mut self, count: u32) {
+        self.count = self.count.saturating_sub(count);
+    }
+}
+
+/// An action waiting in the Store's mailbox
+struct MailboxMessage<A> {
+    envelope: envelope::Envelope<A>,
+    ledger: Option<Arc<budget::CostLedger>>,
+    mode: TrackingMode,
+    /// Span of the sender, re-entered while the event loop reduces the action
+    span: tracing::Span,
+    reply: tokio::sync::oneshot::Sender<Result<EffectHandle, StoreError>>,
+}
+
+/// Bounded action queue for mailbox mode
+///
+/// The event-loop task is started lazily by the first `send()`, since it needs
+/// a running Tokio runtime and a clonable Store. It exits once every Store
+/// handle sharing this mailbox is dropped.
+struct Mailbox<A> {
+    capacity: usize,
+    sender: std::sync::OnceLock<tokio::sync::mpsc::Sender<MailboxMessage<A>>>,
+    /// Actions accepted by `send()` whose reduction has not finished yet
+    ///
+    /// Includes senders still waiting for queue space.
+    queued: AtomicUsize,
+    /// Set by an immediate shutdown: queued actions are answered, not reduced
+    discard: AtomicBool,
+}
+
+impl<A> Mailbox<A> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            sender: std::sync::OnceLock::new(),
+            queued: AtomicUsize::new(0),
+            discard: AtomicBool::new(false),
+        }
+    }
+
+    /// Count one more queued action until the returned slot is handed off or dropped
+    fn reserve<'a>(&'a self, settled: &'a tokio::sync::Notify) -> QueuedSlot<'a, A> {
+        self.queued.fetch_add(1, Ordering::AcqRel);
+        QueuedSlot { mailbox: self, settled }
+    }
+
+    /// Stop counting one queued action, waking shutdown when none are left
+    fn release(&self, settled: &tokio::sync::Notify) {
+        if self.queued.fetch_sub(1, Ordering::AcqRel) == 1 {
+            settled.notify_waiters();
+        }
+    }
+}
+
+/// A sender counted in [`Mailbox::queued`] whose message is not in the queue yet
+///
+/// Dropping the slot releases the count, so a send that fails, or whose
+/// future is dropped while it waits for queue space, is not left counted.
+/// Once the message is queued the event loop releases it instead.
+struct QueuedSlot<'a, A> {
+    mailbox: &'a Mailbox<A>,
+    settled: &'a tokio::sync::Notify,
+}
+
+impl<A> QueuedSlot<'_, A> {
+    /// The message is queued: the event loop releases the count after reducing it
+    const fn hand_off(self) {
+        std::mem::forget(self);
+    }
+}
 
-impl Drop for AtomicCounterGuard {
+impl<A> Drop for QueuedSlot<'_, A> {
     fn drop(&mut self) {
-        self.0.fetch_sub(1, Ordering::SeqCst);
+        self.mailbox.release(self.settled);
+    }
+}
+
+/// What happens to an effect when `max_concurrent_effects` are already running
+///
+/// See [`StoreConfig::with_max_concurrent_effects`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EffectLimitPolicy {
+    /// Start the effect once a running one completes
+    ///
+    /// Queued effects count as pending for `send()` handles and shutdown.
+    /// Counted in `store.effects.queued`; `store.effects.waiting` reports
+    /// the current queue length.
+    #[default]
+    Queue,
+
+    /// Drop the effect and record it in the dead letter queue
+    ///
+    /// Counted in `store.effects.throttled`. The effect's action is never
+    /// produced.
+    Reject,
+}
+
+/// How [`Store::shutdown_with`] treats work accepted before shutdown
+///
+/// Every mode rejects new actions with [`StoreError::ShutdownInProgress`].
+/// Actions fed back by effects are new actions too, so they are rejected
+/// once shutdown starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownMode {
+    /// Stop without waiting
+    ///
+    /// Actions still queued in the mailbox are answered with
+    /// [`StoreError::ShutdownInProgress`] instead of being reduced. Running
+    /// effects are not awaited.
+    Immediate,
+
+    /// Reduce every action already accepted into the mailbox
+    ///
+    /// Returns once the mailbox is empty, without waiting for effects.
+    DrainMailbox {
+        /// Maximum time to wait for the mailbox to empty
+        timeout: Duration,
+    },
+
+    /// Reduce every queued action, then wait for all effects to complete
+    DrainMailboxAndEffects {
+        /// Maximum time for both phases together
+        timeout: Duration,
+    },
+}
+
+/// Phase of a Store shutdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownPhase {
+    /// Shutdown has not started
+    Running,
+    /// Waiting for queued mailbox actions to be reduced
+    DrainingMailbox,
+    /// Waiting for running effects to complete
+    DrainingEffects,
+    /// Waiting for aborted effect tasks to stop
+    AbortingEffects,
+    /// Shutdown finished
+    Stopped,
+    /// Shutdown gave up waiting
+    TimedOut,
+}
+
+/// Shutdown progress, published through [`Store::shutdown_signal`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownProgress {
+    /// Current phase
+    pub phase: ShutdownPhase,
+    /// Actions accepted into the mailbox and not yet reduced
+    pub queued_actions: usize,
+    /// Effects still running
+    pub pending_effects: usize,
+}
+
+impl ShutdownProgress {
+    const fn running() -> Self {
+        Self {
+            phase: ShutdownPhase::Running,
+            queued_actions: 0,
+            pending_effects: 0,
+        }
     }
 }
 
+/// Runtime statistics, returned by [`Store::stats`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreStats {
+    /// Effects started but not yet finished, across all actions
+    pub pending_effects: usize,
+    /// Live action broadcast receivers, including untracked ones
+    pub broadcast_subscribers: usize,
+    /// Action broadcast capacity and per-subscriber lag
+    pub broadcast: subscription::BroadcastStats,
+}
+
 /// Store module - The runtime for reducers
 ///
 /// # Phase 1 Implementation
@@ -1318,11 +3058,55 @@ impl Drop for AtomicCounterGuard {
 /// Store runtime for coordinating reducer execution and effect handling.
 pub mod store {
     use super::{
-        Arc, AtomicBool, AtomicCounterGuard, AtomicUsize, DeadLetterQueue, DecrementGuard,
-        Duration, Effect, EffectHandle, EffectTracking, HealthCheck, Ordering, Reducer,
-        RetryPolicy, RwLock, StoreConfig, StoreError, TrackingMode,
+        Arc, AtomicBool, AtomicU64, DeadLetterQueue, DecrementGuard,
+        Duration, Effect, EffectHandle, EffectLimitPolicy, EffectLimiter, EffectSlot, EffectTracking, HealthCheck, HealthStatus, KeyedDelays, Mailbox,
+        Liveness, MailboxMessage, Mutex, Ordering, PendingEffectGuard, PendingEffects, RateWindow, ReduceContext, Reducer, RetryPolicy, RwLock,
+        OperationPolicy, ShutdownMode, ShutdownPhase, ShutdownProgress, StoreConfig, StoreError,
+        StoreHandle, StoreStats, TrackingMode,
     };
-    use tokio::sync::{broadcast, watch};
+    use crate::budget::{self, CostLedger, EffectBudgets};
+    use crate::envelope::{ActionSource, Envelope, EnvelopeObserver, EnvelopeObservers};
+    use crate::failure::{FailureError, OperationFailure};
+    use crate::degradation::{self, Admission, Degradation};
+    use crate::idempotency::IdempotencyGuard;
+    use crate::state_diff::{StateDiff, StateDiffer};
+    use crate::supervision::{self, Checkpoint, SupervisionStrategy};
+    use crate::metrics::{MetricLabel, MetricsRecorder, MetricsRsRecorder, StoreLabels};
+    use crate::runtime_config::ConfigHandle;
+    use crate::subscription::{ActionBroadcast, ActionSubscription};
+    use crate::observe::{StateObservers, StateSubscription};
+    use crate::tracking::{CompletionStatus, CompletionTracker, TrackingId};
+    use composable_rust_core::action::Correlatable;
+    use composable_rust_core::effect::CancellationToken;
+    use composable_rust_core::environment::{Clock, SystemClock};
+    use composable_rust_core::event::EventMetadata;
+    use composable_rust_core::retry::RetryableError;
+    use std::sync::Weak;
+    use tokio::sync::{broadcast, mpsc, oneshot, watch};
+    use tracing::Instrument;
+
+    /// How often shutdown progress is published while draining
+    const SHUTDOWN_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// How long [`Store::shutdown`] waits for aborted effects to stop
+    pub const ABORTED_EFFECTS_TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// Spawn an effect task inside the current span
+    ///
+    /// The span is that of the action that produced the effect, so the work
+    /// the task does and the actions it feeds back stay in the same trace.
+    /// Under a [`Simulation`](crate::simulation::Simulation), the start is
+    /// held back by a seeded number of scheduler turns.
+    pub(crate) fn spawn_effect<F>(task: F) -> tokio::task::JoinHandle<()>
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        match crate::simulation::next_delay() {
+            // Let the simulation's scheduler decide when the effect starts
+            Some(turns) => tokio::spawn(crate::simulation::delayed(turns, task).in_current_span()),
+            None => tokio::spawn(task.in_current_span()),
+        }
+    }
 
     /// The Store - runtime coordinator for a reducer
     ///
@@ -1332,6 +3116,10 @@ pub mod store {
     /// 3. Environment (injected dependencies)
     /// 4. Effect execution (with feedback loop)
     ///
+    /// Clones are handles on the same Store. Running effects do not keep it
+    /// alive: once every handle is dropped, the actions they produce are
+    /// discarded and `Effect::FutureWithCancel` effects are cancelled.
+    ///
     /// # Type Parameters
     ///
     /// - `S`: State type
@@ -1361,17 +3149,87 @@ pub mod store {
         reducer: R,
         environment: E,
         retry_policy: RetryPolicy,
-        dlq: DeadLetterQueue<String>,
+        /// Per-operation retry overrides, by operation name
+        operation_policies: Arc<Vec<(String, OperationPolicy)>>,
+        dlq: DeadLetterQueue<OperationFailure>,
         shutdown: Arc<AtomicBool>,
-        pending_effects: Arc<AtomicUsize>,
+        pending_effects: Arc<PendingEffects>,
         /// Action broadcast channel for observing actions produced by effects.
         ///
         /// All actions produced by effects (e.g., from `Effect::Future`) are
         /// broadcast to observers. This enables HTTP request-response patterns
         /// and real-time event streaming via `WebSockets`.
-        action_broadcast: broadcast::Sender<A>,
+        action_broadcast: Arc<ActionBroadcast<A>>,
+        /// Hot-reloadable runtime knobs, sampled per operation
+        runtime_config: Option<ConfigHandle>,
+        /// Window state for `max_actions_per_second`
+        rate_window: Arc<Mutex<RateWindow>>,
+        /// Bounded action queue (mailbox mode only)
+        ///
+        /// `None` on the event loop's own handle, so the loop does not keep
+        /// its channel alive.
+        mailbox: Option<Arc<Mailbox<A>>>,
+        /// Per-action effect cost budgets (metering disabled when `None`)
+        budgets: Option<Arc<EffectBudgets<A>>>,
+        /// Destination for Store metrics
+        metrics: Arc<dyn MetricsRecorder>,
+        /// Per-dependency degradation policies (none when `None`)
+        degradation: Option<Arc<Degradation<A>>>,
+        /// Duplicate suppression (disabled when `None`)
+        idempotency: Option<Arc<IdempotencyGuard<A>>>,
+        /// State diffing around each reduction (disabled when `None`)
+        state_differ: Option<Arc<StateDiffer<S, A>>>,
+        /// Concurrency limit on `Future` and `Stream` effects (unlimited when `None`)
+        effect_limiter: Option<Arc<EffectLimiter>>,
+        /// Metric label handles pre-registered at construction
+        labels: Arc<StoreLabels>,
+        /// Shutdown progress for [`Store::shutdown_signal`] subscribers
+        shutdown_progress: Arc<watch::Sender<ShutdownProgress>>,
+        /// Statuses of `send_tracked` requests
+        tracker: Arc<CompletionTracker>,
+        /// Action fed back when a tracked request completes
+        completion_action: Option<Arc<CompletionAction<A>>>,
+        /// Runtime that blocking calls run on (the one the Store was built in)
+        runtime: Option<tokio::runtime::Handle>,
+        /// Selectors notified after every reduction
+        state_observers: Arc<StateObservers<S>>,
+        /// Last snapshot taken by `snapshot_state`, with the reduction count it reflects
+        snapshot: Arc<Mutex<CachedSnapshot<S>>>,
+        /// Number of reductions so far (changes only under the state write lock)
+        reductions: Arc<AtomicU64>,
+        /// Pending `Effect::DelayKeyed` timers
+        keyed_delays: Arc<KeyedDelays>,
+        /// Reducer panic handling (panics propagate when `None`)
+        supervision: Option<SupervisionStrategy>,
+        /// Copies the state before each supervised reduction
+        checkpoint: Option<Checkpoint<S>>,
+        /// Completed handle shared by actions that produce no effects
+        idle_handle: EffectHandle,
+        /// Cancelled when shutdown starts; `Effect::FutureWithCancel` gets child tokens
+        cancellation: CancellationToken,
+        /// Strong for user handles, weak for effect tasks
+        handle: StoreHandle,
+        /// Stamps envelopes with their dispatch time
+        clock: Arc<dyn Clock>,
+        /// Notified of every envelope before its action is reduced
+        envelope_observers: Arc<EnvelopeObservers<A>>,
+        /// Names the action type for per-action metric labels (unlabeled when `None`)
+        action_label: Option<Arc<ActionLabel<A>>>,
+        /// Reducer duration above which a warning is logged
+        warn_if_reduce_exceeds: Option<Duration>,
+        /// State lock wait above which a warning is logged
+        warn_if_lock_wait_exceeds: Option<Duration>,
     }
 
+    /// Builds the action fed back when a tracked request completes
+    type CompletionAction<A> = dyn Fn(TrackingId) -> Option<A> + Send + Sync;
+
+    /// Names the type of an action for metric labels
+    type ActionLabel<A> = dyn Fn(&A) -> &'static str + Send + Sync;
+
+    /// Cached snapshot and the reduction count it was taken at
+    type CachedSnapshot<S> = Option<(u64, Arc<S>)>;
+
     impl<S, A, E, R> Store<S, A, E, R>
     where
         R: Reducer<State = S, Action = A, Environment = E> + Send + Sync + 'static,
@@ -1397,18 +3255,7 @@ pub mod store {
         /// A new Store instance ready to process actions
         #[must_use]
         pub fn new(initial_state: S, reducer: R, environment: E) -> Self {
-            let (action_broadcast, _) = broadcast::channel(16);
-
-            Self {
-                state: Arc::new(RwLock::new(initial_state)),
-                reducer,
-                environment,
-                retry_policy: RetryPolicy::default(),
-                dlq: DeadLetterQueue::default(),
-                shutdown: Arc::new(AtomicBool::new(false)),
-                pending_effects: Arc::new(AtomicUsize::new(0)),
-                action_broadcast,
-            }
+            Self::from_parts(initial_state, reducer, environment, StoreConfig::default(), 16)
         }
 
         /// Create a new Store with a custom retry policy
@@ -1425,18 +3272,8 @@ pub mod store {
             environment: E,
             retry_policy: RetryPolicy,
         ) -> Self {
-            let (action_broadcast, _) = broadcast::channel(16);
-
-            Self {
-                state: Arc::new(RwLock::new(initial_state)),
-                reducer,
-                environment,
-                retry_policy,
-                dlq: DeadLetterQueue::default(),
-                shutdown: Arc::new(AtomicBool::new(false)),
-                pending_effects: Arc::new(AtomicUsize::new(0)),
-                action_broadcast,
-            }
+            let config = StoreConfig::default().with_retry_policy(retry_policy);
+            Self::from_parts(initial_state, reducer, environment, config, 16)
         }
 
         /// Create a new Store with custom configuration
@@ -1469,18 +3306,7 @@ pub mod store {
             environment: E,
             config: StoreConfig,
         ) -> Self {
-            let (action_broadcast, _) = broadcast::channel(16);
-
-            Self {
-                state: Arc::new(RwLock::new(initial_state)),
-                reducer,
-                environment,
-                retry_policy: config.retry_policy,
-                dlq: DeadLetterQueue::new(config.dlq_max_size),
-                shutdown: Arc::new(AtomicBool::new(false)),
-                pending_effects: Arc::new(AtomicUsize::new(0)),
-                action_broadcast,
-            }
+            Self::from_parts(initial_state, reducer, environment, config, 16)
         }
 
         /// Create a new Store with custom action broadcast capacity
@@ -1515,85 +3341,480 @@ pub mod store {
             environment: E,
             capacity: usize,
         ) -> Self {
-            let (action_broadcast, _) = broadcast::channel(capacity);
+            Self::from_parts(initial_state, reducer, environment, StoreConfig::default(), capacity)
+        }
+
+        /// Shared constructor used by all public constructors
+        fn from_parts(
+            initial_state: S,
+            reducer: R,
+            environment: E,
+            config: StoreConfig,
+            broadcast_capacity: usize,
+        ) -> Self {
+            let metrics = config
+                .metrics_recorder
+                .unwrap_or_else(|| Arc::new(MetricsRsRecorder));
+            let action_broadcast = Arc::new(ActionBroadcast::new(
+                broadcast_capacity,
+                config.broadcast_auto_resize,
+                Arc::clone(&metrics),
+            ));
+            let max_attempts = config
+                .operation_policies
+                .iter()
+                .map(|(_, policy)| policy.retry.max_attempts())
+                .fold(config.retry_policy.max_attempts(), u32::max);
+            let labels = Arc::new(StoreLabels::new(max_attempts));
+
+            let cancellation = CancellationToken::new();
 
             Self {
                 state: Arc::new(RwLock::new(initial_state)),
                 reducer,
                 environment,
-                retry_policy: RetryPolicy::default(),
-                dlq: DeadLetterQueue::default(),
+                retry_policy: config.retry_policy,
+                operation_policies: Arc::new(config.operation_policies),
+                dlq: DeadLetterQueue::new(config.dlq_max_size),
                 shutdown: Arc::new(AtomicBool::new(false)),
-                pending_effects: Arc::new(AtomicUsize::new(0)),
+                pending_effects: Arc::new(PendingEffects::new()),
                 action_broadcast,
+                runtime_config: config.runtime_config,
+                rate_window: Arc::new(Mutex::new(RateWindow::default())),
+                mailbox: config.mailbox_capacity.map(|capacity| Arc::new(Mailbox::new(capacity))),
+                budgets: None,
+                metrics,
+                degradation: None,
+                idempotency: None,
+                state_differ: None,
+                effect_limiter: config
+                    .max_concurrent_effects
+                    .map(|max| Arc::new(EffectLimiter::new(max, config.effect_limit_policy))),
+                labels,
+                shutdown_progress: Arc::new(watch::Sender::new(ShutdownProgress::running())),
+                tracker: Arc::new(CompletionTracker::new(config.tracking_retention)),
+                completion_action: None,
+                runtime: tokio::runtime::Handle::try_current().ok(),
+                state_observers: Arc::new(StateObservers::new()),
+                snapshot: Arc::new(Mutex::new(None)),
+                reductions: Arc::new(AtomicU64::new(0)),
+                keyed_delays: Arc::new(KeyedDelays::default()),
+                supervision: config.supervision,
+                checkpoint: None,
+                idle_handle: EffectHandle::completed(),
+                handle: StoreHandle::Strong(Arc::new(Liveness(cancellation.clone()))),
+                cancellation,
+                clock: Arc::new(SystemClock),
+                envelope_observers: Arc::new(EnvelopeObservers::new()),
+                action_label: None,
+                warn_if_reduce_exceeds: config.warn_if_reduce_exceeds,
+                warn_if_lock_wait_exceeds: config.warn_if_lock_wait_exceeds,
             }
         }
 
-        /// Get access to the dead letter queue
+        /// Meter effect costs and enforce per-action budgets
         ///
-        /// Returns a clone of the DLQ for inspecting failed operations.
+        /// Every root action opens a cost ledger shared with the actions its
+        /// effects feed back. See the [`budget`](crate::budget) module for how
+        /// costs are tallied and what happens when a budget is exceeded.
         #[must_use]
-        pub fn dlq(&self) -> DeadLetterQueue<String> {
-            self.dlq.clone()
+        pub fn with_effect_budgets(mut self, budgets: EffectBudgets<A>) -> Self {
+            self.budgets = Some(Arc::new(budgets));
+            self
         }
 
-        /// Perform a health check on the Store
+        /// Degrade actions that need an unavailable dependency
         ///
-        /// Checks:
-        /// - Dead letter queue size (degraded if > 50% capacity, unhealthy if full)
-        /// - Store is operational
-        ///
-        /// Returns a `HealthCheck` with current status and metadata.
+        /// Actions are checked against the policies before they are reduced.
+        /// See the [`degradation`](crate::degradation) module for the
+        /// available modes.
         #[must_use]
-        pub fn health(&self) -> HealthCheck {
-            let dlq_size = self.dlq.len();
-            let dlq_capacity = self.dlq.max_size();
-            // Note: Precision loss acceptable for health check percentage (queue sizes < 2^52)
-            #[allow(clippy::cast_precision_loss)]
-            let dlq_usage = (dlq_size as f64 / dlq_capacity as f64) * 100.0;
-
-            let mut check = if dlq_size >= dlq_capacity {
-                HealthCheck::unhealthy("store", "Dead letter queue is full")
-            } else if dlq_usage > 50.0 {
-                // Note: Truncation intentional for display percentage
-                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                let usage_pct = dlq_usage as u32;
-                HealthCheck::degraded(
-                    "store",
-                    format!("Dead letter queue is {usage_pct}% full"),
-                )
-            } else {
-                HealthCheck::healthy("store")
-            };
-
-            check = check
-                .with_metadata("dlq_size", dlq_size.to_string())
-                .with_metadata("dlq_capacity", dlq_capacity.to_string())
-                .with_metadata("dlq_usage_pct", format!("{dlq_usage:.1}"));
+        pub fn with_degradation(mut self, degradation: Degradation<A>) -> Self {
+            self.degradation = Some(Arc::new(degradation));
+            self
+        }
 
-            check
+        /// Drop actions whose idempotency key was recently admitted
+        ///
+        /// Duplicates are dropped before they reach the reducer, including
+        /// actions fed back by effects. See the
+        /// [`idempotency`](crate::idempotency) module.
+        #[must_use]
+        pub fn with_idempotency(mut self, guard: IdempotencyGuard<A>) -> Self {
+            self.idempotency = Some(Arc::new(guard));
+            self
         }
 
-        /// Initiate graceful shutdown of the store
+        /// Diff the state around every reduction, for debugging reducers
         ///
-        /// This method:
-        /// 1. Sets the shutdown flag (rejecting new actions)
-        /// 2. Waits for pending effects to complete (with timeout)
-        /// 3. Returns when all effects finish or timeout expires
+        /// Non-empty diffs are logged at `debug` level and broadcast to
+        /// [`subscribe_state_diffs`](Self::subscribe_state_diffs). See the
+        /// [`state_diff`](crate::state_diff) module.
+        #[must_use]
+        pub fn with_state_diff(mut self, differ: StateDiffer<S, A>) -> Self {
+            self.state_differ = Some(Arc::new(differ));
+            self
+        }
+
+        /// Stamp envelopes with `clock` instead of the system clock
         ///
-        /// # Arguments
+        /// The time is also what reducers see as
+        /// [`ReduceContext::dispatched_at`], so a fixed or simulated clock
+        /// makes it deterministic in tests.
+        #[must_use]
+        pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+            self.clock = clock;
+            self
+        }
+
+        /// Call `observer` with the [`Envelope`] of every action before it is reduced
         ///
-        /// - `timeout`: Maximum time to wait for effects to complete
+        /// Observers run in registration order on the dispatching task,
+        /// before the state lock is taken, and see actions from every
+        /// [`ActionSource`]. Keep them cheap: an audit log line or a metric,
+        /// not I/O. See the [`envelope`](crate::envelope) module.
+        #[must_use]
+        pub fn with_envelope_observer<F>(mut self, observer: F) -> Self
+        where
+            F: Fn(&Envelope<A>) + Send + Sync + 'static,
+        {
+            let observer: Arc<EnvelopeObserver<A>> = Arc::new(observer);
+            self.envelope_observers = Arc::new(self.envelope_observers.with(observer));
+            self
+        }
+
+        /// Label per-action metrics with the action type named by `label`
         ///
-        /// # Returns
+        /// `store.reducer.duration_seconds`, `store.effects.count` and
+        /// `store.reducer.panics` get an `action` label, so slow or failing
+        /// reducers can be told apart. `label` must return one of a small,
+        /// fixed set of names (typically the enum variant), never ids.
         ///
-        /// - `Ok(())` if all effects completed within timeout
-        /// - `Err(StoreError::ShutdownTimeout)` if timeout expired with effects still running
+        /// ```ignore
+        /// let store = Store::new(state, reducer, env).with_action_label(|action: &OrderAction| match action {
+        ///     OrderAction::Place { .. } => "place",
+        ///     OrderAction::Cancel { .. } => "cancel",
+        /// });
+        /// ```
+        #[must_use]
+        pub fn with_action_label<F>(mut self, label: F) -> Self
+        where
+            F: Fn(&A) -> &'static str + Send + Sync + 'static,
+        {
+            self.action_label = Some(Arc::new(label));
+            self
+        }
+
+        /// Copy the state before each reduction so a supervised Store can restore it
+        ///
+        /// Only used with [`SupervisionStrategy::RestartFromSnapshot`]: a
+        /// reducer panic then rolls the state back to the copy taken before
+        /// the poison action. Every reduction pays for a clone of the state.
+        #[must_use]
+        pub fn with_state_checkpoints(mut self) -> Self
+        where
+            S: Clone,
+        {
+            self.checkpoint = Some(S::clone);
+            self
+        }
+
+        /// Receive the diff of every subsequent reduction that changed the state
+        ///
+        /// Returns `None` unless the Store was built with
+        /// [`with_state_diff`](Self::with_state_diff).
+        #[must_use]
+        pub fn subscribe_state_diffs(&self) -> Option<broadcast::Receiver<StateDiff>> {
+            self.state_differ.as_ref().map(|differ| differ.subscribe())
+        }
+
+        /// Feed an action back whenever a `send_tracked` request completes
+        ///
+        /// `build` receives the request's tracking id; returning `None` feeds
+        /// nothing back. Use it to notify clients, e.g. by reducing the action
+        /// into a webhook effect.
+        #[must_use]
+        pub fn with_completion_action<F>(mut self, build: F) -> Self
+        where
+            F: Fn(TrackingId) -> Option<A> + Send + Sync + 'static,
+        {
+            self.completion_action = Some(Arc::new(build));
+            self
+        }
+
+        /// Run blocking calls on `handle`
+        ///
+        /// Stores capture the runtime they are built in; attach one explicitly
+        /// when building the Store outside a runtime (for example behind FFI).
+        #[must_use]
+        pub fn with_runtime_handle(mut self, handle: tokio::runtime::Handle) -> Self {
+            self.runtime = Some(handle);
+            self
+        }
+
+        /// Current state of every dependency with a degradation policy
+        #[must_use]
+        pub fn degradation_status(&self) -> Vec<degradation::DependencyStatus> {
+            self.degradation
+                .as_ref()
+                .map_or_else(Vec::new, |degradation| degradation.statuses())
+        }
+
+        /// Get access to the dead letter queue
+        ///
+        /// Returns a clone of the DLQ for inspecting failed operations; each
+        /// entry's payload describes what failed and why.
+        #[must_use]
+        pub fn dlq(&self) -> DeadLetterQueue<OperationFailure> {
+            self.dlq.clone()
+        }
+
+        /// Get the hot-reloadable configuration handle, if one was configured
+        #[must_use]
+        pub const fn config_handle(&self) -> Option<&ConfigHandle> {
+            self.runtime_config.as_ref()
+        }
+
+        /// Number of actions waiting in the mailbox
+        ///
+        /// Returns `None` when mailbox mode is disabled.
+        #[must_use]
+        pub fn mailbox_depth(&self) -> Option<usize> {
+            self.mailbox.as_ref().map(|mailbox| {
+                mailbox
+                    .sender
+                    .get()
+                    .map_or(0, |sender| mailbox.capacity - sender.capacity())
+            })
+        }
+
+        /// Retry policy currently in effect (sampled from the config handle if present)
+        fn effective_retry_policy(&self) -> RetryPolicy {
+            self.runtime_config
+                .as_ref()
+                .map_or_else(|| self.retry_policy.clone(), |h| h.current().retry_policy.clone())
+        }
+
+        /// Retry policy and dead-lettering for the operation named `operation`
+        fn operation_policy(&self, operation: &str) -> (RetryPolicy, bool) {
+            self.operation_policies
+                .iter()
+                .find(|(name, _)| name == operation)
+                .map_or_else(
+                    || (self.effective_retry_policy(), true),
+                    |(_, policy)| (policy.retry.clone(), policy.dead_letter),
+                )
+        }
+
+        /// Count an executed effect of kind `kind`
+        fn record_effect(&self, kind: &'static str) {
+            self.metrics.increment_counter(
+                "store.effects.executed",
+                &[("type", MetricLabel::from_static(kind))],
+                1,
+            );
+        }
+
+        /// The label of `action`, if actions are labeled
+        fn action_name(&self, action: &A) -> Option<&'static str> {
+            self.action_label.as_ref().map(|label| label(action))
+        }
+
+        /// The `action` metric label for an action named `name`
+        fn action_metric_label(name: Option<&'static str>) -> Option<(&'static str, MetricLabel)> {
+            name.map(|name| ("action", MetricLabel::from_static(name)))
+        }
+
+        /// Warn if a reducer for `action` ran past `warn_if_reduce_exceeds`
+        fn warn_if_slow_reduce(&self, action: Option<&'static str>, elapsed: Duration) {
+            if let Some(threshold) = self.warn_if_reduce_exceeds
+                && elapsed > threshold
+            {
+                tracing::warn!(
+                    action = action.unwrap_or(std::any::type_name::<A>()),
+                    elapsed_ms = elapsed.as_millis(),
+                    threshold_ms = threshold.as_millis(),
+                    "Slow reducer held the state lock"
+                );
+            }
+        }
+
+        /// Warn if `action` waited past `warn_if_lock_wait_exceeds` for the state lock
+        fn warn_if_slow_lock_wait(&self, action: Option<&'static str>, waited: Duration) {
+            if let Some(threshold) = self.warn_if_lock_wait_exceeds
+                && waited > threshold
+            {
+                tracing::warn!(
+                    action = action.unwrap_or(std::any::type_name::<A>()),
+                    waited_ms = waited.as_millis(),
+                    threshold_ms = threshold.as_millis(),
+                    "Action waited long for the state lock"
+                );
+            }
+        }
+
+        /// Claim a slot under `max_concurrent_effects` for a `kind` effect
+        ///
+        /// Returns `None` if the effect is rejected (and dead-lettered) by
+        /// [`EffectLimitPolicy::Reject`].
+        fn claim_effect_slot(&self, kind: &'static str) -> Option<EffectSlot> {
+            let Some(limiter) = &self.effect_limiter else {
+                return Some(EffectSlot::Unlimited);
+            };
+            if let Ok(permit) = Arc::clone(&limiter.permits).try_acquire_owned() {
+                return Some(EffectSlot::Running(permit));
+            }
+
+            let labels = [("type", MetricLabel::from_static(kind))];
+            match limiter.policy {
+                EffectLimitPolicy::Queue => {
+                    self.metrics.increment_counter("store.effects.queued", &labels, 1);
+                    let waiting = limiter.waiting.fetch_add(1, Ordering::SeqCst) + 1;
+                    EffectLimiter::report_waiting(self.metrics.as_ref(), waiting);
+                    Some(EffectSlot::Queued(Arc::clone(limiter), Arc::clone(&self.metrics)))
+                },
+                EffectLimitPolicy::Reject => {
+                    tracing::warn!(effect = kind, "Rejected effect: concurrent effect limit reached");
+                    self.metrics.increment_counter("store.effects.throttled", &labels, 1);
+                    let failure = OperationFailure::new(
+                        format!("effect:{kind}"),
+                        FailureError::Rejected("concurrent effect limit reached".to_string()),
+                    )
+                    .with_attempts(0, std::time::SystemTime::now());
+                    self.dlq.push(failure, "concurrent effect limit reached".to_string(), 0);
+                    None
+                },
+            }
+        }
+
+        /// Enforce `max_actions_per_second` from the runtime configuration
+        ///
+        /// Admits `count` actions at once or none of them.
+        fn check_rate_limit(&self, count: u32) -> Result<(), StoreError> {
+            let Some(limit) = self.rate_limit() else {
+                return Ok(());
+            };
+
+            let acquired = self
+                .rate_window
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .try_acquire(limit, count);
+
+            if acquired {
+                Ok(())
+            } else {
+                self.metrics.increment_counter("store.rate_limited", &[], 1);
+                tracing::warn!(limit, "Rejected action: rate limit exceeded");
+                Err(StoreError::RateLimited(limit))
+            }
+        }
+
+        /// Give back `count` actions admitted by [`Self::check_rate_limit`]
+        /// that were rejected later on
+        fn refund_rate_limit(&self, count: u32) {
+            if self.rate_limit().is_some() {
+                self.rate_window
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .release(count);
+            }
+        }
+
+        fn rate_limit(&self) -> Option<u32> {
+            self.runtime_config
+                .as_ref()
+                .and_then(|h| h.current().max_actions_per_second)
+        }
+
+        /// Perform a health check on the Store
+        ///
+        /// Checks:
+        /// - Dead letter queue size (degraded if > 50% capacity, or the configured
+        ///   `dlq_degraded_ratio`; unhealthy if full)
+        /// - Store is operational
+        ///
+        /// Returns a `HealthCheck` with current status and metadata.
+        #[must_use]
+        pub fn health(&self) -> HealthCheck {
+            let dlq_size = self.dlq.len();
+            let dlq_capacity = self.dlq.max_size();
+            // Note: Precision loss acceptable for health check percentage (queue sizes < 2^52)
+            #[allow(clippy::cast_precision_loss)]
+            let dlq_usage = (dlq_size as f64 / dlq_capacity as f64) * 100.0;
+
+            let degraded_pct = self
+                .runtime_config
+                .as_ref()
+                .map_or(50.0, |h| h.current().dlq_degraded_ratio * 100.0);
+
+            let mut check = if dlq_size >= dlq_capacity {
+                HealthCheck::unhealthy("store", "Dead letter queue is full")
+            } else if dlq_usage > degraded_pct {
+                // Note: Truncation intentional for display percentage
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let usage_pct = dlq_usage as u32;
+                HealthCheck::degraded(
+                    "store",
+                    format!("Dead letter queue is {usage_pct}% full"),
+                )
+            } else {
+                HealthCheck::healthy("store")
+            };
+
+            check = check
+                .with_metadata("dlq_size", dlq_size.to_string())
+                .with_metadata("dlq_capacity", dlq_capacity.to_string())
+                .with_metadata("dlq_usage_pct", format!("{dlq_usage:.1}"));
+
+            for status in self.degradation_status() {
+                if status.degraded && check.status.is_healthy() {
+                    check.status = HealthStatus::Degraded;
+                    check.message = Some(format!(
+                        "Dependency {} unavailable ({} mode)",
+                        status.dependency, status.mode
+                    ));
+                }
+                check = check
+                    .with_metadata(
+                        format!("degradation.{}.circuit", status.dependency),
+                        degradation::circuit_label(status.state),
+                    )
+                    .with_metadata(
+                        format!("degradation.{}.queued", status.dependency),
+                        status.queued.to_string(),
+                    );
+            }
+
+            check
+        }
+
+        /// Initiate graceful shutdown of the store
+        ///
+        /// This method:
+        /// 1. Sets the shutdown flag (rejecting new actions)
+        /// 2. Waits for queued mailbox actions to be reduced (mailbox mode)
+        /// 3. Waits for pending effects to complete (with timeout)
+        /// 4. Aborts the effect tasks still running at the timeout
+        ///
+        /// Equivalent to [`shutdown_with`](Self::shutdown_with) with
+        /// [`ShutdownMode::DrainMailboxAndEffects`].
+        ///
+        /// # Arguments
+        ///
+        /// - `timeout`: Maximum time to wait for the mailbox and effects
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` if all work completed within timeout
+        /// - `Err(StoreError::EffectsAborted)` listing the effects aborted at the timeout
         ///
         /// # Errors
         ///
-        /// Returns [`StoreError::ShutdownTimeout`] if the timeout expires before all
-        /// pending effects complete.
+        /// Returns [`StoreError::DrainTimeout`] if queued actions remain when
+        /// the timeout expires, [`StoreError::EffectsAborted`] if effects had
+        /// to be aborted, or [`StoreError::ShutdownTimeout`] if aborted
+        /// effects have still not stopped a second later.
         ///
         /// # Example
         ///
@@ -1601,46 +3822,219 @@ pub mod store {
         /// // Graceful shutdown with 30 second timeout
         /// store.shutdown(Duration::from_secs(30)).await?;
         /// ```
-        #[allow(clippy::cognitive_complexity)] // TODO: Refactor in Phase 5
         pub async fn shutdown(&self, timeout: Duration) -> Result<(), StoreError> {
-            tracing::info!("Initiating graceful shutdown");
-            metrics::counter!("store.shutdown.initiated").increment(1);
+            self.shutdown_with(ShutdownMode::DrainMailboxAndEffects { timeout })
+                .await
+        }
+
+        /// Shut the store down, choosing how much accepted work to finish
+        ///
+        /// New actions are rejected from the moment this is called. Progress
+        /// (phase, queued actions, pending effects) is published to
+        /// [`shutdown_signal`](Self::shutdown_signal) subscribers while
+        /// waiting. Effects still running when the timeout of
+        /// [`ShutdownMode::DrainMailboxAndEffects`] expires are aborted, so
+        /// no effect work outlives the shutdown; the store waits up to
+        /// [`ABORTED_EFFECTS_TIMEOUT`] for them to stop.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`StoreError::DrainTimeout`] if queued mailbox actions
+        /// remain when the mode's timeout expires,
+        /// [`StoreError::EffectsAborted`] if effects had to be aborted, or
+        /// [`StoreError::ShutdownTimeout`] if aborted effects did not stop.
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// // Finish accepted requests, but don't wait for slow effects
+        /// store
+        ///     .shutdown_with(ShutdownMode::DrainMailbox { timeout: Duration::from_secs(5) })
+        ///     .await?;
+        /// ```
+        pub async fn shutdown_with(&self, mode: ShutdownMode) -> Result<(), StoreError> {
+            self.shutdown_and_abort(mode, ABORTED_EFFECTS_TIMEOUT).await
+        }
+
+        /// Shut down like [`shutdown_with`](Self::shutdown_with), waiting
+        /// up to `kill_timeout` for aborted effects to stop
+        async fn shutdown_and_abort(&self, mode: ShutdownMode, kill_timeout: Duration) -> Result<(), StoreError> {
+            tracing::info!(?mode, "Initiating graceful shutdown");
+            self.metrics.increment_counter("store.shutdown.initiated", &[], 1);
 
             // Set shutdown flag to reject new actions
             self.shutdown.store(true, Ordering::Release);
 
-            // Wait for pending effects with timeout
-            let start = std::time::Instant::now();
-            let poll_interval = Duration::from_millis(100);
+            // Let cancellable effects stop early instead of running to completion
+            self.cancellation.cancel();
+
+            let deadline = match mode {
+                ShutdownMode::Immediate => {
+                    if let Some(mailbox) = &self.mailbox {
+                        mailbox.discard.store(true, Ordering::Release);
+                    }
+                    None
+                },
+                ShutdownMode::DrainMailbox { timeout }
+                | ShutdownMode::DrainMailboxAndEffects { timeout } => {
+                    Some(tokio::time::Instant::now() + timeout)
+                },
+            };
+
+            if let Some(deadline) = deadline {
+                self.drain(ShutdownPhase::DrainingMailbox, deadline, Self::queued_actions)
+                    .await
+                    .map_err(StoreError::DrainTimeout)?;
+
+                if matches!(mode, ShutdownMode::DrainMailboxAndEffects { .. })
+                    && self
+                        .drain(ShutdownPhase::DrainingEffects, deadline, |store| {
+                            store.pending_effects.count()
+                        })
+                        .await
+                        .is_err()
+                {
+                    return self.abort_effects(kill_timeout).await;
+                }
+            }
+
+            tracing::info!("Shutdown successful");
+            self.report_shutdown(ShutdownPhase::Stopped);
+            self.metrics.increment_counter("store.shutdown.completed", &[], 1);
+            Ok(())
+        }
+
+        /// Shut down, choosing how long effects get before they are aborted
+        ///
+        /// Drains the mailbox and effects like [`shutdown`](Self::shutdown)
+        /// for up to `drain_timeout`. Effect tasks still running then are
+        /// aborted, and the store waits up to `kill_timeout` for them to stop
+        /// (phase [`ShutdownPhase::AbortingEffects`]). Aborted effects feed
+        /// no actions back and complete their handles as finished.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`StoreError::DrainTimeout`] if queued actions remain after
+        /// `drain_timeout`, [`StoreError::EffectsAborted`] listing the effects
+        /// that had to be aborted, or [`StoreError::ShutdownTimeout`] if they
+        /// are still running after `kill_timeout`.
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// // Give effects 10 seconds, then cut them off
+        /// store
+        ///     .shutdown_with_grace(Duration::from_secs(10), Duration::from_secs(1))
+        ///     .await?;
+        /// ```
+        pub async fn shutdown_with_grace(
+            &self,
+            drain_timeout: Duration,
+            kill_timeout: Duration,
+        ) -> Result<(), StoreError> {
+            self.shutdown_and_abort(
+                ShutdownMode::DrainMailboxAndEffects { timeout: drain_timeout },
+                kill_timeout,
+            )
+            .await
+        }
+
+        /// Abort the effect tasks still running and wait for them to stop
+        async fn abort_effects(&self, kill_timeout: Duration) -> Result<(), StoreError> {
+            let operations = self.pending_effects.abort_all();
+            tracing::warn!(?operations, "Aborting effects still running at shutdown timeout");
+            self.metrics
+                .increment_counter("store.shutdown.effects_aborted", &[], operations.len() as u64);
+
+            let deadline = tokio::time::Instant::now() + kill_timeout;
+            self.drain(ShutdownPhase::AbortingEffects, deadline, |store| store.pending_effects.count())
+                .await
+                .map_err(StoreError::ShutdownTimeout)?;
+
+            self.report_shutdown(ShutdownPhase::Stopped);
+            if operations.is_empty() {
+                // The last effects finished just after the timeout
+                self.metrics.increment_counter("store.shutdown.completed", &[], 1);
+                return Ok(());
+            }
+            Err(StoreError::EffectsAborted(operations))
+        }
+
+        /// Subscribe to shutdown progress
+        ///
+        /// The receiver holds [`ShutdownPhase::Running`] until shutdown starts,
+        /// then is updated on every phase change and periodically while
+        /// waiting.
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// let mut progress = store.shutdown_signal();
+        /// while progress.changed().await.is_ok() {
+        ///     let p = *progress.borrow();
+        ///     tracing::info!(queued = p.queued_actions, effects = p.pending_effects, "Draining");
+        /// }
+        /// ```
+        #[must_use]
+        pub fn shutdown_signal(&self) -> watch::Receiver<ShutdownProgress> {
+            self.shutdown_progress.subscribe()
+        }
 
+        /// Wait until `remaining` reaches zero, reporting `phase` progress
+        ///
+        /// Wakes as soon as the count settles at zero, and every
+        /// `SHUTDOWN_PROGRESS_INTERVAL` to publish progress. Returns the
+        /// remaining count if `deadline` passes first.
+        async fn drain(
+            &self,
+            phase: ShutdownPhase,
+            deadline: tokio::time::Instant,
+            remaining: impl Fn(&Self) -> usize,
+        ) -> Result<(), usize> {
             loop {
-                let pending = self.pending_effects.load(Ordering::Acquire);
+                // Registered before checking, so a drop to zero in between is not missed
+                let settled = self.pending_effects.settled.notified();
+                tokio::pin!(settled);
+                settled.as_mut().enable();
+
+                self.report_shutdown(phase);
+                let left = remaining(self);
 
-                if pending == 0 {
-                    tracing::info!("All effects completed, shutdown successful");
-                    metrics::counter!("store.shutdown.completed").increment(1);
+                if left == 0 {
                     return Ok(());
                 }
 
-                if start.elapsed() >= timeout {
-                    tracing::error!(
-                        pending_effects = pending,
-                        "Shutdown timeout: {} effects still running", pending
-                    );
-                    metrics::counter!("store.shutdown.timeout").increment(1);
-                    return Err(StoreError::ShutdownTimeout(pending));
+                if tokio::time::Instant::now() >= deadline {
+                    tracing::error!(?phase, remaining = left, "Shutdown timeout");
+                    self.report_shutdown(ShutdownPhase::TimedOut);
+                    self.metrics.increment_counter("store.shutdown.timeout", &[], 1);
+                    return Err(left);
                 }
 
-                tracing::debug!(
-                    pending_effects = pending,
-                    elapsed_ms = start.elapsed().as_millis(),
-                    "Waiting for effects to complete"
-                );
-
-                tokio::time::sleep(poll_interval).await;
+                tracing::debug!(?phase, remaining = left, "Waiting for shutdown to drain");
+                let progress = tokio::time::Instant::now() + SHUTDOWN_PROGRESS_INTERVAL;
+                tokio::select! {
+                    () = &mut settled => {},
+                    () = tokio::time::sleep_until(deadline.min(progress)) => {},
+                }
             }
         }
 
+        /// Actions accepted into the mailbox and not yet reduced
+        fn queued_actions(&self) -> usize {
+            self.mailbox
+                .as_ref()
+                .map_or(0, |mailbox| mailbox.queued.load(Ordering::Acquire))
+        }
+
+        fn report_shutdown(&self, phase: ShutdownPhase) {
+            self.shutdown_progress.send_replace(ShutdownProgress {
+                phase,
+                queued_actions: self.queued_actions(),
+                pending_effects: self.pending_effects.count(),
+            });
+        }
+
         /// Send an action to the store
         ///
         /// This is the primary way to interact with the store:
@@ -1665,6 +4059,10 @@ pub mod store {
         /// - Multiple concurrent `send()` calls serialize at the reducer level
         /// - Effects may complete in non-deterministic order
         ///
+        /// With [`StoreConfig::with_mailbox`], `send()` instead enqueues the action
+        /// into a bounded FIFO queue drained by a single event-loop task, and waits
+        /// (backpressure) while the queue is full.
+        ///
         /// # Effect Timing
         ///
         /// ```ignore
@@ -1678,6 +4076,8 @@ pub mod store {
         /// # Errors
         ///
         /// Returns [`StoreError::ShutdownInProgress`] if the store is shutting down.
+        /// Returns [`StoreError::RateLimited`] if the runtime configuration's rate limit is reached.
+        /// Returns [`StoreError::MailboxClosed`] if mailbox mode is enabled and the event loop has stopped.
         ///
         /// # Panics
         ///
@@ -1723,6 +4123,9 @@ pub mod store {
         /// # Errors
         ///
         /// Returns [`StoreError::ShutdownInProgress`] if the store is shutting down
+        /// Returns [`StoreError::RateLimited`] if the runtime configuration's rate limit is reached
+        /// Returns [`StoreError::MailboxClosed`] if mailbox mode is enabled and the event loop has stopped
+        /// Returns [`StoreError::BudgetExceeded`] if the action's effects exceed its rejecting budget
         ///
         /// # Example
         ///
@@ -1753,423 +4156,803 @@ pub mod store {
             E: Clone,
             A: Clone + Send + 'static,
         {
-            // Check if store is shutting down
-            if self.shutdown.load(Ordering::Acquire) {
-                tracing::warn!("Rejected action: store is shutting down");
-                metrics::counter!("store.shutdown.rejected_actions").increment(1);
-                return Err(StoreError::ShutdownInProgress);
-            }
+            self.submit(action, metadata, ActionSource::External, None, TrackingMode::Direct)
+                .await
+        }
 
-            tracing::debug!(?metadata, "Processing action with metadata");
+        /// Send an action and track its effects transitively
+        ///
+        /// Like [`Self::send`], but the returned handle uses
+        /// [`TrackingMode::Cascading`]: `wait()` completes only once the
+        /// action's effects, the actions they feed back, and all of their
+        /// effects in turn have finished. Useful in integration tests and
+        /// request handlers that must know when a workflow has settled.
+        ///
+        /// A workflow that never settles (e.g. a periodic timer re-arming
+        /// itself) never completes the handle; prefer `wait_with_timeout()`.
+        ///
+        /// # Errors
+        ///
+        /// Same as [`Self::send`].
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// let mut handle = store.send_cascading(OrderAction::PlaceOrder { .. }).await?;
+        /// handle.wait_with_timeout(Duration::from_secs(5)).await?;
+        /// // The order and every follow-up action it triggered are done
+        /// ```
+        pub async fn send_cascading(&self, action: A) -> Result<EffectHandle, StoreError>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let mode = TrackingMode::Cascading {
+                children: Arc::new(Mutex::new(Vec::new())),
+            };
+            self.submit(action, None, ActionSource::External, None, mode).await
+        }
 
-            // Metrics: Increment command counter
-            metrics::counter!("store.commands.total").increment(1);
+        /// Send several actions, reducing them in order under one lock acquisition
+        ///
+        /// Equivalent to calling [`Self::send`] for each action, without
+        /// re-acquiring the state lock and allocating a handle per action:
+        /// the reducer runs for every action back to back, state observers
+        /// are notified once, and the effects of the whole batch are tracked
+        /// by the single returned [`EffectHandle`]. Useful for replays and
+        /// bulk imports.
+        ///
+        /// The batch is admitted as a whole before any action is reduced; an
+        /// action absorbed by a degradation policy or the idempotency guard
+        /// is skipped. With [`StoreConfig::with_mailbox`] the actions are
+        /// queued one by one instead, keeping FIFO order with other senders,
+        /// and the handle completes once all of their effects have.
+        ///
+        /// # Errors
+        ///
+        /// Same as [`Self::send`]. An admission error rejects the whole batch
+        /// and leaves no trace: no rate-limit capacity is used, nothing is
+        /// held by a degradation policy and no idempotency key is recorded,
+        /// so the batch can be retried as is. A batch larger than
+        /// `max_actions_per_second` is always rate limited. If the mailbox
+        /// closes mid-batch, the keys of the actions not yet queued are
+        /// forgotten. If an action's effects exceed a rejecting budget, its
+        /// effects are not started, the rest of the batch proceeds, and the
+        /// first [`StoreError::BudgetExceeded`] is returned.
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// let actions = rows.into_iter().map(|row| ImportAction::Row(row)).collect();
+        /// let mut handle = store.send_batch(actions).await?;
+        /// handle.wait_with_timeout(Duration::from_secs(30)).await?;
+        /// ```
+        #[tracing::instrument(skip(self, actions), fields(batch = actions.len()), name = "store_send_batch")]
+        pub async fn send_batch(&self, actions: Vec<A>) -> Result<EffectHandle, StoreError>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let admitted: Vec<_> = self
+                .admit_batch(actions)?
+                .into_iter()
+                .map(|(action, metadata)| Envelope::new(action, ActionSource::External, metadata, self.clock.as_ref()))
+                .collect();
+            if admitted.is_empty() {
+                return Ok(EffectHandle::completed());
+            }
 
-            // Create tracking for this action
-            let (handle, tracking) = EffectHandle::new::<A>(TrackingMode::Direct);
+            if let Some(mailbox) = &self.mailbox {
+                return self.enqueue_batch(mailbox, admitted).await;
+            }
 
-            let effects = {
-                let mut state = self.state.write().await;
-                tracing::trace!("Acquired write lock on state");
-
-                // Create span for reducer execution
-                let span = tracing::debug_span!("reducer_execution");
-                let _enter = span.enter();
-
-                // Metrics: Time reducer execution
-                let start = std::time::Instant::now();
-                let effects = self.reducer.reduce(&mut *state, action, &self.environment);
-                let duration = start.elapsed();
-                metrics::histogram!("store.reducer.duration_seconds")
-                    .record(duration.as_secs_f64());
-
-                tracing::trace!("Reducer completed, returned {} effects", effects.len());
-
-                // Metrics: Record number of effects produced
-                #[allow(clippy::cast_precision_loss)]
-                metrics::histogram!("store.effects.count").record(effects.len() as f64);
-
-                effects
-            };
-
-            // Post-process effects to inject metadata into AppendEvents
-            let effects_with_metadata = if let Some(ref meta) = metadata {
-                effects
-                    .into_iter()
-                    .map(|effect| Self::inject_metadata_into_effect(effect, meta.clone()))
-                    .collect()
-            } else {
-                effects
-            };
-
-            // Execute effects with tracking
-            tracing::trace!("Executing {} effects", effects_with_metadata.len());
-            for effect in effects_with_metadata {
-                self.execute_effect_internal(effect, tracking.clone(), metadata.clone());
-            }
-            tracing::debug!("Action processing completed, returning handle");
-
-            Ok(handle)
+            self.dispatch_batch(admitted).await
         }
 
-        /// Recursively inject metadata into all `AppendEvents` and `PublishEvent` effects in an effect tree
-        fn inject_metadata_into_effect(effect: Effect<A>, metadata: composable_rust_core::event::EventMetadata) -> Effect<A>
+        /// Send an action and track its completion under a new [`TrackingId`]
+        ///
+        /// Returns once the action is reduced, so callers can acknowledge it
+        /// right away (e.g. `202 Accepted` with the id) and report completion
+        /// later via [`Self::completion_status`] or the action configured with
+        /// [`Self::with_completion_action`].
+        ///
+        /// # Errors
+        ///
+        /// Same as [`Self::send`]; rejected actions are not tracked.
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// let (id, _handle) = store.send_tracked(ExportAction::Start { report }).await?;
+        /// // Respond 202 with `id`; `GET /exports/{id}` calls
+        /// // `store.completion_status(id)`
+        /// ```
+        pub async fn send_tracked(&self, action: A) -> Result<(TrackingId, EffectHandle), StoreError>
         where
-            A: Clone + Send + 'static,
+            R: Clone,
+            E: Clone,
         {
-            use composable_rust_core::effect::{EventStoreOperation, EventBusOperation};
-
-            match effect {
-                Effect::EventStore(EventStoreOperation::AppendEvents {
-                    event_store,
-                    stream_id,
-                    expected_version,
-                    events,
-                    metadata: existing_metadata,
-                    on_success,
-                    on_error,
-                }) => {
-                    tracing::debug!(
-                        "inject_metadata_into_effect: AppendEvents stream_id={} event_count={} has_existing_metadata={} request_correlation_id={:?}",
-                        stream_id,
-                        events.len(),
-                        existing_metadata.is_some(),
-                        metadata.correlation_id
-                    );
-
-                    // Merge metadata: request metadata takes precedence
-                    let merged_metadata = if let Some(mut existing) = existing_metadata {
-                        // Both exist - merge field by field (request metadata overwrites)
-                        if metadata.correlation_id.is_some() {
-                            existing.correlation_id.clone_from(&metadata.correlation_id);
-                        }
-                        if metadata.causation_id.is_some() {
-                            existing.causation_id.clone_from(&metadata.causation_id);
-                        }
-                        if metadata.user_id.is_some() {
-                            existing.user_id.clone_from(&metadata.user_id);
-                        }
-                        if metadata.timestamp.is_some() {
-                            existing.timestamp.clone_from(&metadata.timestamp);
-                        }
-                        Some(existing)
-                    } else {
-                        // Only request metadata exists
-                        Some(metadata.clone())
-                    };
-
-                    // Also inject metadata into each SerializedEvent in the events vector
-                    let updated_events = events
-                        .into_iter()
-                        .enumerate()
-                        .map(|(idx, mut event)| {
-                            let event_had_metadata = event.metadata.is_some();
-                            // Merge metadata into each event (using function parameter 'metadata')
-                            let event_metadata = if let Some(mut existing) = event.metadata {
-                                if metadata.correlation_id.is_some() {
-                                    existing.correlation_id.clone_from(&metadata.correlation_id);
-                                }
-                                if metadata.causation_id.is_some() {
-                                    existing.causation_id.clone_from(&metadata.causation_id);
-                                }
-                                if metadata.user_id.is_some() {
-                                    existing.user_id.clone_from(&metadata.user_id);
-                                }
-                                if metadata.timestamp.is_some() {
-                                    existing.timestamp.clone_from(&metadata.timestamp);
-                                }
-                                Some(existing)
-                            } else {
-                                Some(metadata.clone())
-                            };
-                            event.metadata = event_metadata;
-                            tracing::debug!(
-                                "inject_metadata_into_effect: Event {} had_metadata={} after_metadata={} correlation_id={:?}",
-                                idx,
-                                event_had_metadata,
-                                event.metadata.is_some(),
-                                event.metadata.as_ref().and_then(|m| m.correlation_id.as_ref())
-                            );
-                            event
-                        })
-                        .collect();
+            let id = TrackingId::new();
+            self.tracker.accept(id);
+
+            let handle = match self.send(action).await {
+                Ok(handle) => handle,
+                Err(error) => {
+                    self.tracker.reject(id);
+                    return Err(error);
+                },
+            };
 
-                    Effect::EventStore(EventStoreOperation::AppendEvents {
-                        event_store,
-                        stream_id,
-                        expected_version,
-                        events: updated_events,
-                        metadata: merged_metadata,
-                        on_success,
-                        on_error,
-                    })
+            let store = self.downgrade();
+            let mut completion = handle.clone();
+            spawn_effect(async move {
+                completion.wait().await;
+                store.tracker.complete(id);
+                let Some(store) = store.upgrade() else {
+                    return;
+                };
+                if let Some(action) = store.completion_action.as_ref().and_then(|build| build(id)) {
+                    if let Err(error) = store.send(action).await {
+                        tracing::warn!(%error, tracking_id = %id, "Failed to send completion action");
+                    }
                 }
-                Effect::PublishEvent(EventBusOperation::Publish {
-                    event_bus,
-                    topic,
-                    event,
-                    on_success,
-                    on_error,
-                }) => {
-                    tracing::debug!(
-                        "inject_metadata_into_effect: PublishEvent topic={} had_metadata={} request_correlation_id={:?}",
-                        topic,
-                        event.metadata.is_some(),
-                        metadata.correlation_id
-                    );
-
-                    // Inject metadata into the SerializedEvent
-                    let mut updated_event = event;
+            });
 
-                    // Merge metadata: request metadata takes precedence
-                    let merged_metadata = if let Some(mut existing) = updated_event.metadata {
-                        // Both exist - merge field by field (request metadata overwrites)
-                        if metadata.correlation_id.is_some() {
-                            existing.correlation_id.clone_from(&metadata.correlation_id);
-                        }
-                        if metadata.causation_id.is_some() {
-                            existing.causation_id.clone_from(&metadata.causation_id);
-                        }
-                        if metadata.user_id.is_some() {
-                            existing.user_id.clone_from(&metadata.user_id);
-                        }
-                        if metadata.timestamp.is_some() {
-                            existing.timestamp.clone_from(&metadata.timestamp);
-                        }
-                        Some(existing)
-                    } else {
-                        // Only request metadata exists
-                        Some(metadata.clone())
-                    };
+            Ok((id, handle))
+        }
 
-                    updated_event.metadata = merged_metadata;
+        /// Status of a request sent with [`Self::send_tracked`]
+        ///
+        /// `None` if the id is unknown, or its request completed long enough
+        /// ago to fall outside [`StoreConfig::tracking_retention`].
+        #[must_use]
+        pub fn completion_status(&self, id: TrackingId) -> Option<CompletionStatus> {
+            self.tracker.status(id)
+        }
 
-                    tracing::debug!(
-                        "inject_metadata_into_effect: PublishEvent after_metadata={} correlation_id={:?}",
-                        updated_event.metadata.is_some(),
-                        updated_event.metadata.as_ref().and_then(|m| m.correlation_id.as_ref())
-                    );
+        /// Admit an action and reduce it, charging its effects to `ledger`
+        ///
+        /// Root actions pass `None` and open a new ledger (when budgets are
+        /// configured); actions fed back by effects pass their root's ledger.
+        async fn submit(
+            &self,
+            action: A,
+            metadata: Option<EventMetadata>,
+            source: ActionSource,
+            ledger: Option<Arc<CostLedger>>,
+            mode: TrackingMode,
+        ) -> Result<EffectHandle, StoreError>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let Some((action, metadata)) = self.admit(action, metadata, source)? else {
+                return Ok(EffectHandle::completed());
+            };
+            let envelope = Envelope::new(action, source, metadata, self.clock.as_ref());
 
-                    Effect::PublishEvent(EventBusOperation::Publish {
-                        event_bus,
-                        topic,
-                        event: updated_event,
-                        on_success,
-                        on_error,
-                    })
-                }
-                // Recursively process composed effects
-                Effect::Parallel(effects) => Effect::Parallel(
-                    effects
-                        .into_iter()
-                        .map(|e| Self::inject_metadata_into_effect(e, metadata.clone()))
-                        .collect(),
-                ),
-                Effect::Sequential(effects) => Effect::Sequential(
-                    effects
-                        .into_iter()
-                        .map(|e| Self::inject_metadata_into_effect(e, metadata.clone()))
-                        .collect(),
-                ),
-                // Other effect types pass through unchanged
-                other => other,
+            if let Some(mailbox) = &self.mailbox {
+                return self.enqueue(mailbox, envelope, ledger, mode).await;
             }
+
+            self.dispatch(envelope, ledger, mode).await
         }
 
-        /// Send an action and wait for a matching result action
-        ///
-        /// This method is designed for request-response patterns (HTTP, RPC).
-        /// It subscribes to the action broadcast, sends the initial action,
-        /// then waits for an action matching the predicate.
-        ///
-        /// # How It Works
-        ///
-        /// 1. Subscribe to action broadcast BEFORE sending (avoids race conditions)
-        /// 2. Send the initial action through the store
-        /// 3. Wait for actions produced by effects
-        /// 4. Return the first action matching the predicate
-        ///
-        /// # Arguments
+        /// Send an action without waiting, failing if the mailbox is full
         ///
-        /// - `action`: The initial action to send
-        /// - `predicate`: Function to test if an action is the terminal result
-        /// - `timeout`: Maximum time to wait for matching action
+        /// In mailbox mode the action is queued for the event loop when there
+        /// is room, and rejected with [`StoreError::MailboxFull`] otherwise,
+        /// instead of waiting for queue space like [`Self::send`]. Real-time
+        /// producers (sensor ingest, market data) use it to shed load
+        /// explicitly rather than fall behind.
         ///
-        /// # Returns
+        /// Returns once the action is queued: its effects cannot be awaited.
+        /// Without a mailbox the action is reduced on a spawned task, so
+        /// `try_send()` never reports a full queue but actions sent from
+        /// the same caller may be reduced out of order.
         ///
-        /// The first action matching the predicate, or timeout error.
+        /// Must be called within a Tokio runtime.
         ///
         /// # Errors
         ///
-        /// - [`StoreError::Timeout`]: Timeout expired before matching action received
-        /// - [`StoreError::ChannelClosed`]: Action broadcast channel closed (store shutting down)
-        /// - [`StoreError::ShutdownInProgress`]: Store is shutting down
+        /// Returns [`StoreError::MailboxFull`] if the mailbox has no room.
+        /// Otherwise the same admission errors as [`Self::send`]:
+        /// [`StoreError::ShutdownInProgress`], [`StoreError::RateLimited`],
+        /// [`StoreError::Degraded`] and [`StoreError::MailboxClosed`].
         ///
         /// # Example
         ///
         /// ```ignore
-        /// use std::time::Duration;
-        ///
-        /// let result = store.send_and_wait_for(
-        ///     OrderAction::PlaceOrder {
-        ///         correlation_id: Uuid::new_v4(),
-        ///         customer_id,
-        ///         items,
-        ///     },
-        ///     |a| matches!(a,
-        ///         OrderAction::OrderPlaced { .. } |
-        ///         OrderAction::OrderFailed { .. }
-        ///     ),
-        ///     Duration::from_secs(10),
-        /// ).await?;
-        ///
-        /// match result {
-        ///     OrderAction::OrderPlaced { order_id, .. } => {
-        ///         println!("Order placed: {}", order_id);
-        ///     }
-        ///     OrderAction::OrderFailed { reason, .. } => {
-        ///         eprintln!("Order failed: {}", reason);
-        ///     }
-        ///     _ => unreachable!("Predicate ensures only terminal actions"),
+        /// match store.try_send(SensorAction::Reading(sample)) {
+        ///     Ok(()) => {},
+        ///     Err(StoreError::MailboxFull { .. }) => dropped.increment(1),
+        ///     Err(error) => return Err(error.into()),
         /// }
         /// ```
+        pub fn try_send(&self, action: A) -> Result<(), StoreError>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let Some((action, metadata)) = self.admit(action, None, ActionSource::External)? else {
+                return Ok(());
+            };
+            let envelope = Envelope::new(action, ActionSource::External, metadata, self.clock.as_ref());
+
+            if let Some(mailbox) = &self.mailbox {
+                return self.try_enqueue(mailbox, envelope);
+            }
+
+            let store = self.clone();
+            tokio::spawn(async move {
+                if let Err(error) = store.dispatch(envelope, None, TrackingMode::Direct).await {
+                    tracing::warn!(%error, "Failed to reduce action sent with try_send");
+                }
+            });
+            Ok(())
+        }
+
+        /// Checks every action goes through before it is reduced or queued
         ///
-        /// # Notes
-        ///
-        /// - Only actions produced by effects are broadcast (not the initial action)
-        /// - If the channel lags and drops actions, continues waiting (timeout catches it)
-        /// - Use correlation IDs to distinguish concurrent requests
-        pub async fn send_and_wait_for<F>(
+        /// Returns `None` when a degradation policy or the idempotency guard
+        /// absorbed the action. The rate limit only applies to external
+        /// ingress: actions fed back by effects were already paid for by the
+        /// action that started them.
+        #[allow(clippy::type_complexity)] // Action with its optional metadata
+        fn admit(
             &self,
             action: A,
-            predicate: F,
-            timeout: Duration,
-        ) -> Result<A, StoreError>
+            metadata: Option<composable_rust_core::event::EventMetadata>,
+            source: ActionSource,
+        ) -> Result<Option<(A, Option<composable_rust_core::event::EventMetadata>)>, StoreError>
         where
             R: Clone,
             E: Clone,
-            A: Clone,
-            F: Fn(&A) -> bool,
         {
-            // Subscribe BEFORE sending to avoid race condition
-            let mut rx = self.action_broadcast.subscribe();
+            // Check if store is shutting down
+            if self.shutdown.load(Ordering::Acquire) {
+                tracing::warn!("Rejected action: store is shutting down");
+                self.metrics.increment_counter("store.shutdown.rejected_actions", &[], 1);
+                return Err(StoreError::ShutdownInProgress);
+            }
 
-            // Send the initial action
-            self.send(action).await?;
+            if source == ActionSource::External {
+                self.check_rate_limit(1)?;
+            }
 
-            // Wait for matching action with timeout
-            tokio::time::timeout(timeout, async {
-                loop {
-                    match rx.recv().await {
-                        Ok(action) if predicate(&action) => return Ok(action),
-                        Ok(_) => {} // Not the action we want, keep waiting
-                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
-                            // Slow consumer, some actions were dropped
-                            // Continue waiting - if terminal action was dropped, timeout will catch it
-                            tracing::warn!(
-                                skipped,
-                                "Action observer lagged, {} actions skipped",
-                                skipped
-                            );
-                        }
-                        Err(broadcast::error::RecvError::Closed) => {
-                            return Err(StoreError::ChannelClosed);
-                        }
+            let (action, metadata) = match &self.degradation {
+                Some(degradation) => {
+                    self.replay_degraded();
+                    match degradation.admit(action, metadata, self.metrics.as_ref()) {
+                        Admission::Proceed(action, metadata) => (action, metadata),
+                        Admission::Absorbed => return Ok(None),
+                        Admission::Rejected(dependency) => {
+                            return Err(StoreError::Degraded { dependency });
+                        },
                     }
+                },
+                None => (action, metadata),
+            };
+
+            if let Some(guard) = &self.idempotency {
+                if !guard.admit(&action, self.metrics.as_ref()) {
+                    return Ok(None);
                 }
-            })
-            .await
-            .map_err(|_| StoreError::Timeout)?
+            }
+
+            Ok(Some((action, metadata)))
         }
 
-        /// Subscribe to all actions from this store
+        /// [`Self::admit`] for a whole batch, all or nothing
         ///
-        /// This method is designed for event streaming (`WebSockets`, SSE).
-        /// Returns a receiver that gets a clone of every action produced by effects.
-        ///
-        /// # Returns
-        ///
-        /// A broadcast receiver that receives all actions produced by effects.
+        /// Every check that can reject runs before any side effect, so a
+        /// rejected batch uses no rate-limit capacity, holds nothing for
+        /// replay and records no idempotency keys.
+        #[allow(clippy::type_complexity)] // Actions with their optional metadata
+        fn admit_batch(
+            &self,
+            actions: Vec<A>,
+        ) -> Result<Vec<(A, Option<composable_rust_core::event::EventMetadata>)>, StoreError>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            if self.shutdown.load(Ordering::Acquire) {
+                tracing::warn!(batch = actions.len(), "Rejected batch: store is shutting down");
+                self.metrics.increment_counter("store.shutdown.rejected_actions", &[], actions.len() as u64);
+                return Err(StoreError::ShutdownInProgress);
+            }
+
+            let count = u32::try_from(actions.len()).unwrap_or(u32::MAX);
+            self.check_rate_limit(count)?;
+
+            let actions: Vec<_> = actions.into_iter().map(|action| (action, None)).collect();
+            let actions = match &self.degradation {
+                Some(degradation) => {
+                    self.replay_degraded();
+                    match degradation.admit_batch(actions, self.metrics.as_ref()) {
+                        Ok(actions) => actions,
+                        Err(dependency) => {
+                            self.refund_rate_limit(count);
+                            return Err(StoreError::Degraded { dependency });
+                        },
+                    }
+                },
+                None => actions,
+            };
+
+            // Cannot fail, so recording keys last keeps the batch all or nothing
+            Ok(match &self.idempotency {
+                Some(guard) => actions
+                    .into_iter()
+                    .filter(|(action, _)| guard.admit(action, self.metrics.as_ref()))
+                    .collect(),
+                None => actions,
+            })
+        }
+
+        /// Broadcast an action produced by an effect and send it back into the Store
         ///
-        /// # Notes
+        /// Under cascading tracking the action's handle is attached to the
+        /// producing effect, so waiters follow its effects too. The action is
+        /// discarded if every user handle on the Store has been dropped.
+        async fn feed_back(
+            &self,
+            action: A,
+            metadata: Option<composable_rust_core::event::EventMetadata>,
+            ledger: Option<Arc<CostLedger>>,
+            tracking: &EffectTracking,
+        ) where
+            R: Clone,
+            E: Clone,
+            A: Clone,
+        {
+            let Some(store) = self.upgrade() else {
+                tracing::debug!("Store dropped, discarding action produced by an effect");
+                self.metrics.increment_counter("store.effects.orphaned", &[], 1);
+                return;
+            };
+
+            // Broadcast to observers (HTTP handlers, WebSockets, metrics)
+            store.action_broadcast.send(action.clone());
+
+            let submitted = store
+                .submit(action, metadata, ActionSource::Feedback, ledger, tracking.feedback_mode())
+                .await;
+            match submitted {
+                Ok(handle) => tracking.adopt(handle),
+                Err(error) => {
+                    tracing::warn!(%error, "Rejected action produced by an effect");
+                    self.metrics.increment_counter("store.feedback.rejected", &[], 1);
+                },
+            }
+        }
+
+        /// A handle for effect tasks that does not keep the Store alive
+        fn downgrade(&self) -> Self
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let mut handle = self.clone();
+            if let StoreHandle::Strong(liveness) = &self.handle {
+                handle.handle = StoreHandle::Weak(Arc::downgrade(liveness));
+            }
+            handle
+        }
+
+        /// A user handle, unless every user handle has been dropped
+        fn upgrade(&self) -> Option<Self>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let liveness = match &self.handle {
+                StoreHandle::Strong(liveness) => Arc::clone(liveness),
+                StoreHandle::Weak(liveness) => Weak::upgrade(liveness)?,
+            };
+            let mut handle = self.clone();
+            handle.handle = StoreHandle::Strong(liveness);
+            Some(handle)
+        }
+
+        /// Whether every user handle on the Store has been dropped
+        fn is_dropped(&self) -> bool {
+            match &self.handle {
+                StoreHandle::Strong(_) => false,
+                StoreHandle::Weak(liveness) => liveness.strong_count() == 0,
+            }
+        }
+
+        /// Replay actions held by degradation policies whose dependency recovered
         ///
-        /// - Only actions produced by effects are broadcast (not initial actions sent via `send`)
-        /// - If the receiver lags, it will skip old actions and receive [`RecvError::Lagged`]
-        /// - The receiver must be consumed in a loop or it will block the channel
+        /// Runs automatically whenever the Store admits an action; call it
+        /// directly to flush the queues of an otherwise idle Store. The actions
+        /// are re-sent in order on a background task. Returns how many were
+        /// scheduled.
+        pub fn replay_degraded(&self) -> usize
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let Some(degradation) = &self.degradation else {
+                return 0;
+            };
+            let recovered = degradation.take_recovered();
+            let count = recovered.len();
+            if count == 0 {
+                return 0;
+            }
+
+            tracing::info!(count, "Dependency recovered, replaying degraded actions");
+            let store = self.clone();
+            tokio::spawn(async move {
+                for (action, metadata) in recovered {
+                    let replayed = store
+                        .submit(action, metadata, ActionSource::External, None, TrackingMode::Direct)
+                        .await;
+                    if let Err(error) = replayed {
+                        tracing::warn!(%error, "Failed to replay degraded action");
+                    }
+                }
+            });
+            count
+        }
+
+        /// Rebuild the state by replaying a stream's events as actions
         ///
-        /// # Example
+        /// Loads every event of `stream_id`, converts each with `decode`
+        /// (events it maps to `None` are skipped) and reduces the actions in
+        /// order with their effects discarded, so replaying does not append,
+        /// publish or feed back anything. Starting from the same initial
+        /// state, this yields the state the Store had before a restart.
+        /// Reducers see [`ReduceContext::is_replay`] set.
         ///
-        /// ```ignore
-        /// let mut rx = store.subscribe_actions();
+        /// Call it at boot, before sending actions: the state lock is held for
+        /// the whole replay, and state observers are notified once at the end.
+        /// Action broadcasts and state diffs are not produced for replayed
+        /// actions. Returns the number of actions reduced.
         ///
-        /// // Stream to WebSocket client
-        /// while let Ok(action) = rx.recv().await {
-        ///     let json = serde_json::to_string(&action)?;
-        ///     ws.send(json).await?;
-        /// }
-        /// ```
+        /// # Errors
         ///
-        /// # Filtered Streaming
+        /// Returns [`StoreError::ReplayFailed`] if the events cannot be
+        /// loaded; the state is not modified.
         ///
-        /// You can filter actions for specific users or criteria:
+        /// # Example
         ///
         /// ```ignore
-        /// let mut rx = store.subscribe_actions();
-        /// let user_id = session.user_id;
-        ///
-        /// while let Ok(action) = rx.recv().await {
-        ///     // Only stream actions for this user
-        ///     if action.user_id() == Some(&user_id) {
-        ///         ws.send(serde_json::to_string(&action)?).await?;
-        ///     }
-        /// }
+        /// let store = Store::new(OrderState::default(), OrderReducer, env.clone());
+        /// store
+        ///     .hydrate_from_events(env.event_store.as_ref(), StreamId::new("order-42"), |event| {
+        ///         bincode::deserialize::<OrderEvent>(&event.data).ok().map(OrderAction::Event)
+        ///     })
+        ///     .await?;
         /// ```
-        #[must_use]
-        pub fn subscribe_actions(&self) -> broadcast::Receiver<A> {
-            self.action_broadcast.subscribe()
+        pub async fn hydrate_from_events<F>(
+            &self,
+            event_store: &dyn composable_rust_core::event_store::EventStore,
+            stream_id: composable_rust_core::stream::StreamId,
+            decode: F,
+        ) -> Result<usize, StoreError>
+        where
+            F: Fn(composable_rust_core::event::SerializedEvent) -> Option<A>,
+        {
+            let events = event_store
+                .load_events(stream_id.clone(), None)
+                .await
+                .map_err(StoreError::ReplayFailed)?;
+            let loaded = events.len();
+
+            let mut state = self.state.write().await;
+            let mut replayed = 0;
+            for action in events.into_iter().filter_map(&decode) {
+                let envelope = Envelope::new(action, ActionSource::Replay, None, self.clock.as_ref());
+                self.envelope_observers.notify(&envelope);
+                let context = envelope.context();
+                // Replay mode: the effects already ran before the restart
+                drop(self.reduce_supervised(&mut state, envelope.action, &context));
+                replayed += 1;
+            }
+            self.reductions.fetch_add(replayed as u64, Ordering::Release);
+            self.state_observers.notify(&state);
+
+            self.metrics.increment_counter("store.replay.actions", &[], replayed as u64);
+            tracing::info!(stream = %stream_id, loaded, replayed, "Hydrated state from events");
+            Ok(replayed)
         }
 
-        /// Internal send implementation with tracking control
+        /// Push an action into the mailbox and wait for the event loop to reduce it
         ///
-        /// This method is used by both production `send()` and test `TestStore::send()`.
+        /// Waits for queue space when the mailbox is full (backpressure).
+        async fn enqueue(
+            &self,
+            mailbox: &Arc<Mailbox<A>>,
+            envelope: Envelope<A>,
+            ledger: Option<Arc<CostLedger>>,
+            mode: TrackingMode,
+        ) -> Result<EffectHandle, StoreError>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let response = self
+                .post(mailbox, envelope, ledger, mode)
+                .await
+                .map_err(|_| StoreError::MailboxClosed)?;
+            response.await.map_err(|_| StoreError::MailboxClosed)?
+        }
+
+        /// Put an action into the mailbox, waiting for space
         ///
-        /// # Arguments
+        /// Returns the receiver of the event loop's reply, or the envelope
+        /// back if the mailbox is closed.
+        async fn post(
+            &self,
+            mailbox: &Arc<Mailbox<A>>,
+            envelope: Envelope<A>,
+            ledger: Option<Arc<CostLedger>>,
+            mode: TrackingMode,
+        ) -> Result<oneshot::Receiver<Result<EffectHandle, StoreError>>, Envelope<A>>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let sender = mailbox
+                .sender
+                .get_or_init(|| self.spawn_event_loop(Arc::clone(mailbox)));
+
+            let (reply, response) = oneshot::channel();
+            let slot = mailbox.reserve(&self.pending_effects.settled);
+            let sent = sender
+                .send(MailboxMessage {
+                    envelope,
+                    ledger,
+                    mode,
+                    span: tracing::Span::current(),
+                    reply,
+                })
+                .await;
+            if let Err(mpsc::error::SendError(message)) = sent {
+                return Err(message.envelope);
+            }
+            slot.hand_off();
+
+            #[allow(clippy::cast_precision_loss)] // Queue depth is far below 2^52
+            self.metrics.set_gauge("store.mailbox.depth", &[], (mailbox.capacity - sender.capacity()) as f64);
+
+            Ok(response)
+        }
+
+        /// Queue a batch of admitted actions, then wait for all of them
         ///
-        /// - `action`: The action to process
-        /// - `tracking_mode`: Whether to track effects directly or cascading
+        /// Every action is queued before any reply is awaited, so a failure
+        /// of one action does not drop the ones after it. If the mailbox
+        /// closes, the idempotency keys of the actions that were not queued
+        /// are forgotten so that a retry admits them.
+        async fn enqueue_batch(
+            &self,
+            mailbox: &Arc<Mailbox<A>>,
+            actions: Vec<Envelope<A>>,
+        ) -> Result<EffectHandle, StoreError>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let mut replies = Vec::with_capacity(actions.len());
+            let mut actions = actions.into_iter();
+            while let Some(envelope) = actions.next() {
+                match self.post(mailbox, envelope, None, TrackingMode::Direct).await {
+                    Ok(reply) => replies.push(reply),
+                    Err(unqueued) => {
+                        if let Some(guard) = &self.idempotency {
+                            for envelope in std::iter::once(unqueued).chain(actions) {
+                                guard.release(&envelope.action);
+                            }
+                        }
+                        return Err(StoreError::MailboxClosed);
+                    },
+                }
+            }
+
+            let mut handles = Vec::with_capacity(replies.len());
+            let mut first_error = None;
+            for reply in replies {
+                match reply.await.map_err(|_| StoreError::MailboxClosed).and_then(|result| result) {
+                    Ok(handle) => handles.push(handle),
+                    Err(error) => {
+                        first_error.get_or_insert(error);
+                    },
+                }
+            }
+            match first_error {
+                Some(error) => Err(error),
+                None => Ok(EffectHandle::join(handles)),
+            }
+        }
+
+        /// Push an action into the mailbox if it has room, without waiting
+        fn try_enqueue(&self, mailbox: &Arc<Mailbox<A>>, envelope: Envelope<A>) -> Result<(), StoreError>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let sender = mailbox
+                .sender
+                .get_or_init(|| self.spawn_event_loop(Arc::clone(mailbox)));
+
+            // Nobody waits for the reply; the event loop ignores the dropped receiver
+            let (reply, _response) = oneshot::channel();
+            let slot = mailbox.reserve(&self.pending_effects.settled);
+            let sent = sender.try_send(MailboxMessage {
+                envelope,
+                ledger: None,
+                mode: TrackingMode::Direct,
+                span: tracing::Span::current(),
+                reply,
+            });
+            if let Err(error) = sent {
+                return Err(match error {
+                    mpsc::error::TrySendError::Full(_) => {
+                        self.metrics.increment_counter("store.mailbox.rejected", &[], 1);
+                        StoreError::MailboxFull {
+                            capacity: mailbox.capacity,
+                        }
+                    },
+                    mpsc::error::TrySendError::Closed(_) => StoreError::MailboxClosed,
+                });
+            }
+            slot.hand_off();
+
+            #[allow(clippy::cast_precision_loss)] // Queue depth is far below 2^52
+            self.metrics.set_gauge("store.mailbox.depth", &[], (mailbox.capacity - sender.capacity()) as f64);
+            Ok(())
+        }
+
+        /// Start the single task that drains the mailbox
         ///
-        /// # Returns
+        /// The loop reduces actions on a weak handle without a mailbox, so
+        /// actions fed back by effects are reduced directly rather than
+        /// re-enqueued, and the loop exits once every user handle is dropped.
+        /// After an immediate shutdown it answers queued actions without
+        /// reducing them.
+        fn spawn_event_loop(&self, mailbox: Arc<Mailbox<A>>) -> mpsc::Sender<MailboxMessage<A>>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            let capacity = mailbox.capacity;
+            let (sender, mut receiver) = mpsc::channel::<MailboxMessage<A>>(capacity);
+            let mut worker = self.downgrade();
+            worker.mailbox = None;
+
+            tokio::spawn(async move {
+                tracing::debug!(capacity, "Mailbox event loop started");
+                while let Some(message) = receiver.recv().await {
+                    let result = if mailbox.discard.load(Ordering::Acquire) {
+                        Err(StoreError::ShutdownInProgress)
+                    } else {
+                        worker
+                            .dispatch(message.envelope, message.ledger, message.mode)
+                            .instrument(message.span)
+                            .await
+                    };
+                    mailbox.release(&worker.pending_effects.settled);
+                    // Caller may have given up waiting; the action was still reduced
+                    let _ = message.reply.send(result);
+                }
+                tracing::debug!("Mailbox closed, event loop exiting");
+            });
+
+            sender
+        }
+
+        /// Run the reducer, catching its panics when the Store is supervised
         ///
-        /// An [`EffectHandle`] for waiting on effect completion
+        /// A caught panic dead-letters the action, restores the checkpoint
+        /// if the strategy asks for one, and yields no effects.
+        fn reduce_supervised(
+            &self,
+            state: &mut S,
+            action: A,
+            context: &ReduceContext,
+        ) -> composable_rust_core::SmallVec<[Effect<A>; 4]> {
+            let Some(strategy) = self.supervision else {
+                return self.reducer.reduce_with_context(state, action, &self.environment, context);
+            };
+            let checkpoint = match (strategy, self.checkpoint) {
+                (SupervisionStrategy::RestartFromSnapshot, Some(checkpoint)) => Some(checkpoint(state)),
+                _ => None,
+            };
+            let action_label = Self::action_metric_label(self.action_name(&action));
+
+            let reduced = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                self.reducer.reduce_with_context(state, action, &self.environment, context)
+            }));
+            match reduced {
+                Ok(effects) => effects,
+                Err(payload) => {
+                    let message = supervision::panic_message(payload.as_ref());
+                    let restored = checkpoint.is_some();
+                    tracing::error!(panic = %message, restored, "Reducer panicked; action dead-lettered");
+                    self.metrics.increment_counter("store.reducer.panics", action_label.as_slice(), 1);
+                    let failure = OperationFailure::new("reducer", FailureError::ReducerPanic(message.clone()))
+                        .with_target(std::any::type_name::<A>())
+                        .with_attempts(0, std::time::SystemTime::now());
+                    self.dlq.push(failure, message, 0);
+                    if let Some(checkpoint) = checkpoint {
+                        *state = checkpoint;
+                    }
+                    composable_rust_core::SmallVec::new()
+                },
+            }
+        }
+
+        /// Whether executing `effect` would only record metrics
         ///
-        /// # Errors
+        /// True for `Effect::None` and for `Parallel`/`Sequential` effects
+        /// made only of inert effects.
+        fn is_inert(effect: &Effect<A>) -> bool {
+            match effect {
+                Effect::None => true,
+                Effect::Parallel(effects) | Effect::Sequential(effects) => effects.iter().all(Self::is_inert),
+                _ => false,
+            }
+        }
+
+        /// Record the metrics that executing an inert effect would record
+        fn record_inert(&self, effect: &Effect<A>) {
+            match effect {
+                Effect::Parallel(effects) => {
+                    self.record_effect("parallel");
+                    for effect in effects {
+                        self.record_inert(effect);
+                    }
+                },
+                Effect::Sequential(effects) => {
+                    self.record_effect("sequential");
+                    for effect in effects {
+                        self.record_inert(effect);
+                    }
+                },
+                _ => self.record_effect("none"),
+            }
+        }
+
+        /// Run the reducer for one action and start its effects
         ///
-        /// Returns [`StoreError::ShutdownInProgress`] if the store is shutting down.
-        #[allow(clippy::cognitive_complexity)] // TODO: Refactor in Phase 4
-        #[tracing::instrument(skip(self, action, tracking_mode), name = "store_send_internal")]
-        async fn send_internal(&self, action: A, tracking_mode: TrackingMode) -> Result<EffectHandle, StoreError>
+        /// Fails only when the effects exceed a rejecting budget, in which
+        /// case none of them are started.
+        #[allow(clippy::cognitive_complexity)] // Tracing and metrics macros inflate the score
+        async fn dispatch(
+            &self,
+            envelope: Envelope<A>,
+            ledger: Option<Arc<CostLedger>>,
+            mode: TrackingMode,
+        ) -> Result<EffectHandle, StoreError>
         where
             R: Clone,
             E: Clone,
-            A: Clone,
         {
-            // Check if store is shutting down
-            if self.shutdown.load(Ordering::Acquire) {
-                tracing::warn!("Rejected action: store is shutting down");
-                metrics::counter!("store.shutdown.rejected_actions").increment(1);
-                return Err(StoreError::ShutdownInProgress);
-            }
-
-            tracing::debug!("Processing action");
+            tracing::debug!(source = %envelope.source, metadata = ?envelope.metadata, "Processing action with metadata");
+            self.envelope_observers.notify(&envelope);
+            let context = envelope.context();
+            let Envelope { action, metadata, .. } = envelope;
+
+            let ledger = ledger.or_else(|| {
+                self.budgets
+                    .as_ref()
+                    .map(|budgets| budgets.open(&action, Arc::clone(&self.metrics), Arc::clone(&self.labels)))
+            });
 
             // Metrics: Increment command counter
-            metrics::counter!("store.commands.total").increment(1);
-
-            // Create tracking for this action
-            let (handle, tracking) = EffectHandle::new::<A>(tracking_mode);
+            self.metrics.increment_counter("store.commands.total", &[], 1);
 
+            let action_name = self.action_name(&action);
+            let action_label = Self::action_metric_label(action_name);
             let effects = {
+                let waiting = std::time::Instant::now();
                 let mut state = self.state.write().await;
+                self.warn_if_slow_lock_wait(action_name, waiting.elapsed());
                 tracing::trace!("Acquired write lock on state");
 
                 // Create span for reducer execution
@@ -2177,2309 +4960,6373 @@ pub mod store {
                 let _enter = span.enter();
 
                 // Metrics: Time reducer execution
+                let diff = self.state_differ.as_ref().and_then(|differ| differ.begin(&state, &action));
                 let start = std::time::Instant::now();
-                let effects = self.reducer.reduce(&mut *state, action, &self.environment);
+                let effects = self.reduce_supervised(&mut state, action, &context);
                 let duration = start.elapsed();
-                metrics::histogram!("store.reducer.duration_seconds")
-                    .record(duration.as_secs_f64());
+                self.warn_if_slow_reduce(action_name, duration);
+                if let (Some(differ), Some(diff)) = (&self.state_differ, diff) {
+                    differ.finish(diff, &state);
+                }
+                self.reductions.fetch_add(1, Ordering::Release);
+                self.state_observers.notify(&state);
+                self.metrics.record_histogram(
+                    "store.reducer.duration_seconds",
+                    action_label.as_slice(),
+                    duration.as_secs_f64(),
+                );
 
                 tracing::trace!("Reducer completed, returned {} effects", effects.len());
 
                 // Metrics: Record number of effects produced
-                // Note: Precision loss acceptable for metrics (effect counts < 2^52)
                 #[allow(clippy::cast_precision_loss)]
-                metrics::histogram!("store.effects.count").record(effects.len() as f64);
+                self.metrics.record_histogram("store.effects.count", action_label.as_slice(), effects.len() as f64);
 
                 effects
             };
 
+            if let Some(ledger) = &ledger {
+                let mut costs = std::collections::HashMap::new();
+                for effect in &effects {
+                    budget::tally(effect, &mut costs);
+                }
+                ledger.charge(costs)?;
+            }
+
+            // Fast path: nothing to run or track, so skip the tracking channel,
+            // the causation id and the metadata pass
+            if effects.iter().all(Self::is_inert) {
+                for effect in &effects {
+                    self.record_inert(effect);
+                }
+                tracing::debug!("Action produced no effects, returning completed handle");
+                return Ok(self.idle_handle.clone());
+            }
+
+            // Create tracking for this action
+            let (handle, tracking) = EffectHandle::new(mode);
+
+            // Events produced by this action are caused by it: give the action
+            // an id unless the caller already supplied a causation id
+            let mut metadata = metadata.unwrap_or_default();
+            if metadata.causation_id.is_none() {
+                metadata.causation_id = Some(uuid::Uuid::new_v4().to_string());
+            }
+
+            // Post-process effects to inject metadata into AppendEvents and PublishEvent
+            let effects_with_metadata: Vec<_> = effects
+                .into_iter()
+                .map(|effect| Self::inject_metadata_into_effect(effect, &metadata))
+                .collect();
+            let metadata = Some(metadata);
+
             // Execute effects with tracking
-            tracing::trace!("Executing {} effects", effects.len());
-            for effect in effects {
-                self.execute_effect_internal(effect, tracking.clone(), None);
+            tracing::trace!("Executing {} effects", effects_with_metadata.len());
+            for effect in effects_with_metadata {
+                self.execute_effect_internal(effect, tracking.clone(), metadata.clone(), ledger.clone(), None);
             }
             tracing::debug!("Action processing completed, returning handle");
 
             Ok(handle)
         }
 
-        /// Read current state via a closure
-        ///
-        /// Access state through a closure to ensure the lock is released promptly:
-        ///
-        /// ```ignore
-        /// let order_count = store.state(|s| s.orders.len()).await;
-        /// ```
-        ///
-        /// # Arguments
-        ///
-        /// - `f`: Closure that receives a reference to state and returns a value
-        ///
-        /// # Returns
+        /// Run the reducer for a batch of admitted actions under one lock
+        /// acquisition and start all their effects under one handle
         ///
-        /// The value returned by the closure
-        pub async fn state<F, T>(&self, f: F) -> T
+        /// Each action keeps its own cost ledger and causation id.
+        async fn dispatch_batch(&self, actions: Vec<Envelope<A>>) -> Result<EffectHandle, StoreError>
         where
-            F: FnOnce(&S) -> T,
+            R: Clone,
+            E: Clone,
         {
-            let state = self.state.read().await;
-            f(&*state)
-        }
+            let count = actions.len();
+            self.metrics.increment_counter("store.commands.total", &[], count as u64);
+            for envelope in &actions {
+                self.envelope_observers.notify(envelope);
+            }
 
-        /// Retry an async operation according to the retry policy
-        ///
-        /// This wraps an async operation with exponential backoff retry logic.
-        /// Metrics are recorded for retry attempts.
-        ///
-        /// # Arguments
-        ///
-        /// - `operation_name`: Name for logging/metrics (e.g., "`append_events`")
-        /// - `f`: Async function to execute (will be called multiple times on failure)
-        ///
-        /// # Returns
+            let reduced: Vec<_> = {
+                let waiting = std::time::Instant::now();
+                let mut state = self.state.write().await;
+                self.warn_if_slow_lock_wait(None, waiting.elapsed());
+                tracing::trace!(count, "Acquired write lock on state for batch");
+
+                let span = tracing::debug_span!("reducer_execution", batch = count);
+                let _enter = span.enter();
+
+                let reduced = actions
+                    .into_iter()
+                    .map(|envelope| {
+                        let context = envelope.context();
+                        let Envelope { action, metadata, .. } = envelope;
+                        let ledger = self
+                            .budgets
+                            .as_ref()
+                            .map(|budgets| budgets.open(&action, Arc::clone(&self.metrics), Arc::clone(&self.labels)));
+
+                        let action_name = self.action_name(&action);
+                        let action_label = Self::action_metric_label(action_name);
+                        let diff = self.state_differ.as_ref().and_then(|differ| differ.begin(&state, &action));
+                        let start = std::time::Instant::now();
+                        let effects = self.reduce_supervised(&mut state, action, &context);
+                        let duration = start.elapsed();
+                        self.warn_if_slow_reduce(action_name, duration);
+                        if let (Some(differ), Some(diff)) = (&self.state_differ, diff) {
+                            differ.finish(diff, &state);
+                        }
+                        self.metrics.record_histogram(
+                            "store.reducer.duration_seconds",
+                            action_label.as_slice(),
+                            duration.as_secs_f64(),
+                        );
+                        #[allow(clippy::cast_precision_loss)]
+                        self.metrics.record_histogram(
+                            "store.effects.count",
+                            action_label.as_slice(),
+                            effects.len() as f64,
+                        );
+
+                        (effects, metadata, ledger)
+                    })
+                    .collect();
+                self.reductions.fetch_add(count as u64, Ordering::Release);
+                self.state_observers.notify(&state);
+                reduced
+            };
+
+            let (handle, tracking) = EffectHandle::new(TrackingMode::Direct);
+            let mut rejected = None;
+            for (effects, metadata, ledger) in reduced {
+                if let Some(ledger) = &ledger {
+                    let mut costs = std::collections::HashMap::new();
+                    for effect in &effects {
+                        budget::tally(effect, &mut costs);
+                    }
+                    if let Err(error) = ledger.charge(costs) {
+                        rejected.get_or_insert(error);
+                        continue;
+                    }
+                }
+
+                if effects.iter().all(Self::is_inert) {
+                    for effect in &effects {
+                        self.record_inert(effect);
+                    }
+                    continue;
+                }
+
+                let mut metadata = metadata.unwrap_or_default();
+                if metadata.causation_id.is_none() {
+                    metadata.causation_id = Some(uuid::Uuid::new_v4().to_string());
+                }
+                for effect in effects {
+                    let effect = Self::inject_metadata_into_effect(effect, &metadata);
+                    self.execute_effect_internal(effect, tracking.clone(), Some(metadata.clone()), ledger.clone(), None);
+                }
+            }
+
+            match rejected {
+                Some(error) => Err(error),
+                None => Ok(handle),
+            }
+        }
+
+        /// Merge request metadata into an event, giving it an id if it has none
         ///
-        /// Result from the operation, or the last error if all retries exhausted
-        async fn retry_operation<F, Fut, T, Err>(&self, operation_name: &str, mut f: F) -> Result<T, Err>
+        /// Request metadata takes precedence, except that an event keeps a
+        /// causation id it was created with.
+        fn stamp_event(
+            mut event: composable_rust_core::event::SerializedEvent,
+            metadata: &composable_rust_core::event::EventMetadata,
+        ) -> composable_rust_core::event::SerializedEvent {
+            let mut event_metadata = event.metadata.take().unwrap_or_default();
+            let own_causation = event_metadata.causation_id.take();
+            event_metadata.merge_from(metadata);
+            if own_causation.is_some() {
+                event_metadata.causation_id = own_causation;
+            }
+            if event_metadata.event_id.is_none() {
+                event_metadata.event_id = Some(uuid::Uuid::new_v4().to_string());
+            }
+            event.metadata = Some(event_metadata);
+            event
+        }
+
+        /// Recursively inject metadata into all `AppendEvents` and `PublishEvent` effects in an effect tree
+        #[allow(clippy::too_many_lines, clippy::cognitive_complexity)] // One arm per effect variant that carries events
+        fn inject_metadata_into_effect(effect: Effect<A>, metadata: &composable_rust_core::event::EventMetadata) -> Effect<A>
         where
-            F: FnMut() -> Fut,
-            Fut: std::future::Future<Output = Result<T, Err>>,
-            Err: std::fmt::Display,
+            A: Clone + Send + 'static,
         {
-            let mut attempt = 0;
+            use composable_rust_core::effect::{EventStoreOperation, EventBusOperation};
 
-            loop {
-                match f().await {
-                    Ok(result) => {
-                        // Success! Record metrics if this was a retry
-                        if attempt > 0 {
-                            metrics::counter!(
-                                "store.retry.success",
-                                "operation" => operation_name.to_string(),
-                                "attempts" => attempt.to_string()
-                            )
-                            .increment(1);
-                            tracing::info!(
-                                operation = operation_name,
-                                attempt = attempt,
-                                "Operation succeeded after retry"
-                            );
-                        }
-                        return Ok(result);
-                    }
-                    Err(error) => {
-                        // Check if we should retry
-                        if !self.retry_policy.should_retry(attempt + 1) {
-                            // Exhausted retries - push to DLQ
-                            let error_msg = format!("{error}");
-                            self.dlq.push(
-                                operation_name.to_string(),
-                                error_msg.clone(),
-                                (attempt + 1) as usize,
-                            );
+            match effect {
+                Effect::EventStore(EventStoreOperation::AppendEvents {
+                    event_store,
+                    stream_id,
+                    expected_version,
+                    events,
+                    metadata: existing_metadata,
+                    idempotency_key,
+                    on_success,
+                    on_error,
+                }) => {
+                    tracing::debug!(
+                        "inject_metadata_into_effect: AppendEvents stream_id={} event_count={} has_existing_metadata={} request_correlation_id={:?}",
+                        stream_id,
+                        events.len(),
+                        existing_metadata.is_some(),
+                        metadata.correlation_id
+                    );
 
-                            metrics::counter!(
-                                "store.retry.exhausted",
-                                "operation" => operation_name.to_string(),
-                                "attempts" => attempt.to_string()
-                            )
-                            .increment(1);
-                            tracing::error!(
-                                operation = operation_name,
-                                attempt = attempt,
-                                error = %error,
-                                "Operation failed after exhausting retries, added to DLQ"
-                            );
-                            return Err(error);
-                        }
+                    // Merge metadata: request metadata takes precedence
+                    let mut merged_metadata = existing_metadata.unwrap_or_default();
+                    merged_metadata.merge_from(metadata);
 
-                        // Calculate delay and retry
-                        let delay = self.retry_policy.delay_for_attempt(attempt);
-                        metrics::counter!(
-                            "store.retry.attempt",
-                            "operation" => operation_name.to_string(),
-                            "attempt" => attempt.to_string()
-                        )
-                        .increment(1);
-                        tracing::warn!(
-                            operation = operation_name,
-                            attempt = attempt,
-                            delay_ms = delay.as_millis(),
-                            error = %error,
-                            "Operation failed, retrying after delay"
-                        );
+                    // Also inject metadata into each SerializedEvent in the events vector
+                    let updated_events = events.into_iter().map(|event| Self::stamp_event(event, metadata)).collect();
 
-                        tokio::time::sleep(delay).await;
-                        attempt += 1;
-                    }
+                    Effect::EventStore(EventStoreOperation::AppendEvents {
+                        event_store,
+                        stream_id,
+                        expected_version,
+                        events: updated_events,
+                        metadata: Some(merged_metadata),
+                        idempotency_key,
+                        on_success,
+                        on_error,
+                    })
+                }
+                Effect::PublishEvent(EventBusOperation::Publish {
+                    event_bus,
+                    topic,
+                    event,
+                    options,
+                    on_success,
+                    on_error,
+                }) => {
+                    tracing::debug!(
+                        "inject_metadata_into_effect: PublishEvent topic={} had_metadata={} request_correlation_id={:?}",
+                        topic,
+                        event.metadata.is_some(),
+                        metadata.correlation_id
+                    );
+
+                    Effect::PublishEvent(EventBusOperation::Publish {
+                        event_bus,
+                        topic,
+                        event: Self::stamp_event(event, metadata),
+                        options,
+                        on_success,
+                        on_error,
+                    })
                 }
+                // Recursively process composed effects
+                Effect::Parallel(effects) => Effect::Parallel(
+                    effects
+                        .into_iter()
+                        .map(|e| Self::inject_metadata_into_effect(e, metadata))
+                        .collect(),
+                ),
+                Effect::Sequential(effects) => Effect::Sequential(
+                    effects
+                        .into_iter()
+                        .map(|e| Self::inject_metadata_into_effect(e, metadata))
+                        .collect(),
+                ),
+                Effect::Costed { cost, effect } => Effect::Costed {
+                    cost,
+                    effect: Box::new(Self::inject_metadata_into_effect(*effect, metadata)),
+                },
+                Effect::Retrying { retry, effect } => Effect::Retrying {
+                    retry,
+                    effect: Box::new(Self::inject_metadata_into_effect(*effect, metadata)),
+                },
+                // Other effect types pass through unchanged
+                other => other,
             }
         }
 
-        /// Execute an effect with tracking
+        /// Send an action and wait for a matching result action
         ///
-        /// Internal method that executes effects with completion tracking.
-        /// Uses [`DecrementGuard`] to ensure the effect counter is always
-        /// decremented, even if the effect panics.
+        /// This method is designed for request-response patterns (HTTP, RPC).
+        /// It subscribes to the action broadcast, sends the initial action,
+        /// then waits for an action matching the predicate.
         ///
-        /// # Effect Types
+        /// # How It Works
         ///
-        /// - `None`: No-op
-        /// - `Future`: Executes async computation, sends resulting action if `Some`
-        /// - `Delay`: Waits for duration, then sends action
-        /// - `Parallel`: Executes effects concurrently
-        /// - `Sequential`: Executes effects in order, waiting for each to complete
+        /// 1. Subscribe to action broadcast BEFORE sending (avoids race conditions)
+        /// 2. Send the initial action through the store
+        /// 3. Wait for actions produced by effects
+        /// 4. Return the first action matching the predicate
         ///
-        /// # Error Handling Strategy
+        /// # Arguments
         ///
-        /// **Reducer panics**: Propagate (fail fast). Reducers should be pure functions
-        /// that do not panic. If a reducer panics, the store will halt.
+        /// - `action`: The initial action to send
+        /// - `predicate`: Function to test if an action is the terminal result
+        /// - `timeout`: Maximum time to wait for matching action
         ///
-        /// **Effect execution failures**: Log and continue. Effects are fire-and-forget
-        /// operations. If an effect task panics during parallel execution, it's logged
-        /// but other effects continue. The [`DecrementGuard`] ensures the counter is
-        /// always updated even on panic.
+        /// # Returns
         ///
-        /// # Arguments
+        /// The first action matching the predicate, or timeout error.
         ///
-        /// - `effect`: The effect to execute
-        /// - `tracking`: The tracking context for this effect (passed by value to enable cloning)
-        #[allow(clippy::needless_pass_by_value)] // tracking is cloned, so pass by value is intentional
-        #[allow(clippy::cognitive_complexity)] // TODO: Refactor in Phase 4
-        #[allow(clippy::too_many_lines)] // TODO: Refactor in Phase 4
-        #[tracing::instrument(skip(self, effect, tracking), name = "execute_effect")]
-        fn execute_effect_internal(
+        /// # Errors
+        ///
+        /// - [`StoreError::Timeout`]: Timeout expired before matching action received
+        /// - [`StoreError::ChannelClosed`]: Action broadcast channel closed (store shutting down)
+        /// - [`StoreError::ShutdownInProgress`]: Store is shutting down
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// use std::time::Duration;
+        ///
+        /// let result = store.send_and_wait_for(
+        ///     OrderAction::PlaceOrder {
+        ///         correlation_id: Uuid::new_v4(),
+        ///         customer_id,
+        ///         items,
+        ///     },
+        ///     |a| matches!(a,
+        ///         OrderAction::OrderPlaced { .. } |
+        ///         OrderAction::OrderFailed { .. }
+        ///     ),
+        ///     Duration::from_secs(10),
+        /// ).await?;
+        ///
+        /// match result {
+        ///     OrderAction::OrderPlaced { order_id, .. } => {
+        ///         println!("Order placed: {}", order_id);
+        ///     }
+        ///     OrderAction::OrderFailed { reason, .. } => {
+        ///         eprintln!("Order failed: {}", reason);
+        ///     }
+        ///     _ => unreachable!("Predicate ensures only terminal actions"),
+        /// }
+        /// ```
+        ///
+        /// # Notes
+        ///
+        /// - Only actions produced by effects are broadcast (not the initial action)
+        /// - If the channel lags and drops actions, continues waiting (timeout catches it)
+        /// - Use correlation IDs to distinguish concurrent requests
+        pub async fn send_and_wait_for<F>(
             &self,
-            effect: Effect<A>,
-            tracking: EffectTracking<A>,
-            metadata: Option<composable_rust_core::event::EventMetadata>,
-        )
+            action: A,
+            predicate: F,
+            timeout: Duration,
+        ) -> Result<A, StoreError>
         where
             R: Clone,
             E: Clone,
-            A: Clone + Send + 'static,
+            A: Clone,
+            F: Fn(&A) -> bool,
         {
-            match effect {
-                Effect::None => {
-                    tracing::trace!("Executing Effect::None (no-op)");
-                    metrics::counter!("store.effects.executed", "type" => "none").increment(1);
-                },
-                Effect::Future(fut) => {
-                    tracing::trace!("Executing Effect::Future");
-                    metrics::counter!("store.effects.executed", "type" => "future").increment(1);
-                    tracking.increment();
-
-                    // Track global pending effects for shutdown
-                    self.pending_effects.fetch_add(1, Ordering::SeqCst);
-                    let pending_guard = AtomicCounterGuard(Arc::clone(&self.pending_effects));
-
-                    let tracking_clone = tracking.clone();
-                    let store = self.clone();
-                    let metadata_clone = metadata.clone();
-
-                    tokio::spawn(async move {
-                        let _guard = DecrementGuard(tracking_clone.clone());
-                        let _pending_guard = pending_guard; // Decrement on drop
+            // Subscribe BEFORE sending to avoid race condition
+            let rx = self.action_broadcast.subscribe();
 
-                        if let Some(action) = fut.await {
-                            tracing::trace!("Effect::Future produced an action, sending to store with metadata");
+            // Send the initial action
+            self.send(action).await?;
 
-                            // Broadcast to observers (HTTP handlers, WebSockets, metrics)
-                            let _ = store.action_broadcast.send(action.clone());
+            Self::wait_for_action(rx, predicate, timeout).await
+        }
 
-                            // Send action back to store with metadata (preserves correlation context)
-                            let _ = store.send_with_metadata(action, metadata_clone).await;
-                        } else {
-                            tracing::trace!("Effect::Future completed with no action");
-                        }
-                    });
-                },
-                Effect::Stream(stream) => {
-                    tracing::trace!("Executing Effect::Stream");
-                    metrics::counter!("store.effects.executed", "type" => "stream").increment(1);
-                    tracking.increment();
-
-                    // Track global pending effects for shutdown
-                    self.pending_effects.fetch_add(1, Ordering::SeqCst);
-                    let pending_guard = AtomicCounterGuard(Arc::clone(&self.pending_effects));
+        /// Send an action stamped with a correlation ID and wait for its reply
+        ///
+        /// Request-response without hand-written predicates:
+        /// 1. Uses the action's existing correlation ID, or generates a new one
+        /// 2. Stamps the action via [`Correlatable::with_correlation_id`]
+        /// 3. Sends it with `correlation_id` metadata, so persisted and published
+        ///    events carry the same ID
+        /// 4. Returns the first effect-produced action carrying that ID
+        ///
+        /// Unlike [`Self::send_and_wait_for`], concurrent requests cannot observe
+        /// each other's results.
+        ///
+        /// # Errors
+        ///
+        /// - [`StoreError::Timeout`]: No correlated action arrived before the timeout
+        /// - [`StoreError::ChannelClosed`]: Action broadcast channel closed
+        /// - Any error returned by [`Self::send_with_metadata`]
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// let reply = store
+        ///     .send_correlated(
+        ///         OrderAction::PlaceOrder { correlation_id: None, customer_id, items },
+        ///         Duration::from_secs(10),
+        ///     )
+        ///     .await?;
+        /// ```
+        pub async fn send_correlated(&self, action: A, timeout: Duration) -> Result<A, StoreError>
+        where
+            R: Clone,
+            E: Clone,
+            A: Correlatable,
+        {
+            let correlation_id = action.correlation_id().unwrap_or_else(uuid::Uuid::new_v4);
+            let action = action.with_correlation_id(correlation_id);
+            let metadata =
+                composable_rust_core::event::EventMetadata::with_correlation_id(correlation_id.to_string());
+
+            let rx = self.action_broadcast.subscribe();
+            self.send_with_metadata(action, Some(metadata)).await?;
+
+            Self::wait_for_action(
+                rx,
+                |a: &A| a.correlation_id() == Some(correlation_id),
+                timeout,
+            )
+            .await
+        }
 
-                    let tracking_clone = tracking.clone();
-                    let store = self.clone();
-                    let metadata_clone = metadata.clone();
+        /// Wait on a broadcast subscription for the first action matching `predicate`
+        async fn wait_for_action<F>(
+            mut rx: ActionSubscription<A>,
+            predicate: F,
+            timeout: Duration,
+        ) -> Result<A, StoreError>
+        where
+            F: Fn(&A) -> bool,
+        {
+            tokio::time::timeout(timeout, async {
+                loop {
+                    match rx.recv().await {
+                        Ok(action) if predicate(&action) => return Ok(action),
+                        Ok(_) => {} // Not the action we want, keep waiting
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            // Slow consumer, some actions were dropped
+                            // Continue waiting - if terminal action was dropped, timeout will catch it
+                            tracing::warn!(
+                                skipped,
+                                "Action observer lagged, {} actions skipped",
+                                skipped
+                            );
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {
+                            return Err(StoreError::ChannelClosed);
+                        }
+                    }
+                }
+            })
+            .await
+            .map_err(|_| StoreError::Timeout)?
+        }
 
-                    tokio::spawn(async move {
-                        use futures::StreamExt;
+        /// Subscribe to all actions from this store
+        ///
+        /// This method is designed for event streaming (`WebSockets`, SSE).
+        /// Returns a receiver that gets a clone of every action produced by effects.
+        ///
+        /// # Returns
+        ///
+        /// A subscription that receives all actions produced by effects. It
+        /// mirrors `broadcast::Receiver` and reports lag to [`Self::stats`].
+        ///
+        /// # Notes
+        ///
+        /// - Only actions produced by effects are broadcast (not initial actions sent via `send`)
+        /// - If the receiver lags, it will skip old actions and receive [`RecvError::Lagged`]
+        /// - The receiver must be consumed in a loop or it will block the channel
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// let mut rx = store.subscribe_actions();
+        ///
+        /// // Stream to WebSocket client
+        /// while let Ok(action) = rx.recv().await {
+        ///     let json = serde_json::to_string(&action)?;
+        ///     ws.send(json).await?;
+        /// }
+        /// ```
+        ///
+        /// # Filtered Streaming
+        ///
+        /// You can filter actions for specific users or criteria:
+        ///
+        /// ```ignore
+        /// let mut rx = store.subscribe_actions();
+        /// let user_id = session.user_id;
+        ///
+        /// while let Ok(action) = rx.recv().await {
+        ///     // Only stream actions for this user
+        ///     if action.user_id() == Some(&user_id) {
+        ///         ws.send(serde_json::to_string(&action)?).await?;
+        ///     }
+        /// }
+        /// ```
+        ///
+        /// [`ActionStreamer`](crate::streaming::ActionStreamer) wraps the
+        /// receiver with filtering, serialization and a lag policy.
+        #[must_use]
+        pub fn subscribe_actions(&self) -> ActionSubscription<A> {
+            self.action_broadcast.subscribe()
+        }
 
-                        let _guard = DecrementGuard(tracking_clone.clone());
-                        let _pending_guard = pending_guard; // Decrement on drop
+        /// Snapshot of runtime statistics
+        ///
+        /// Reports the pending effect count, the number of action broadcast
+        /// receivers, the broadcast capacity (which grows under sustained lag
+        /// when [`StoreConfig::with_broadcast_auto_resize`] is set) and lag
+        /// statistics for every live [`Self::subscribe_actions`] subscription.
+        ///
+        /// The same figures are exported continuously as the
+        /// `store.effects.pending` and `store.actions.subscribers` gauges and
+        /// the `store.actions.lagged` counter.
+        #[must_use]
+        pub fn stats(&self) -> StoreStats {
+            StoreStats {
+                pending_effects: self.pending_effects.count(),
+                broadcast_subscribers: self.action_broadcast.receiver_count(),
+                broadcast: self.action_broadcast.stats(),
+            }
+        }
 
-                        let mut stream = stream;
-                        let mut item_count = 0;
+        /// Internal send implementation with tracking control
+        ///
+        /// This method is used by both production `send()` and test `TestStore::send()`.
+        ///
+        /// # Arguments
+        ///
+        /// - `action`: The action to process
+        /// - `tracking_mode`: Whether to track effects directly or cascading
+        ///
+        /// # Returns
+        ///
+        /// An [`EffectHandle`] for waiting on effect completion
+        ///
+        /// # Errors
+        ///
+        /// Returns [`StoreError::ShutdownInProgress`] if the store is shutting down.
+        #[allow(clippy::cognitive_complexity)] // TODO: Refactor in Phase 4
+        #[allow(dead_code)] // Reserved for queued/cascading tracking (TestStore)
+        #[tracing::instrument(skip(self, action, tracking_mode), name = "store_send_internal")]
+        async fn send_internal(&self, action: A, tracking_mode: TrackingMode) -> Result<EffectHandle, StoreError>
+        where
+            R: Clone,
+            E: Clone,
+            A: Clone,
+        {
+            // Check if store is shutting down
+            if self.shutdown.load(Ordering::Acquire) {
+                tracing::warn!("Rejected action: store is shutting down");
+                self.metrics.increment_counter("store.shutdown.rejected_actions", &[], 1);
+                return Err(StoreError::ShutdownInProgress);
+            }
 
-                        tracing::trace!("Starting stream consumption");
+            self.check_rate_limit(1)?;
 
-                        while let Some(action) = stream.next().await {
-                            item_count += 1;
-                            tracing::trace!("Stream yielded item #{}", item_count);
-                            metrics::counter!("store.stream_items.processed").increment(1);
+            tracing::debug!("Processing action");
 
-                            // Broadcast to observers (HTTP handlers, WebSockets, metrics)
-                            let _ = store.action_broadcast.send(action.clone());
+            // Metrics: Increment command counter
+            self.metrics.increment_counter("store.commands.total", &[], 1);
 
-                            // Send action back to store with metadata (preserves correlation context)
-                            let _ = store.send_with_metadata(action, metadata_clone.clone()).await;
-                        }
+            // Create tracking for this action
+            let (handle, tracking) = EffectHandle::new(tracking_mode);
 
-                        tracing::trace!(
-                            "Effect::Stream completed, processed {} items",
-                            item_count
-                        );
-                        metrics::histogram!("store.stream_items.total")
-                            .record(f64::from(item_count));
-                    });
-                },
-                Effect::Delay { duration, action } => {
-                    tracing::trace!("Executing Effect::Delay (duration: {:?})", duration);
-                    metrics::counter!("store.effects.executed", "type" => "delay").increment(1);
-                    tracking.increment();
+            let effects = {
+                let mut state = self.state.write().await;
+                tracing::trace!("Acquired write lock on state");
 
-                    // Track global pending effects for shutdown
-                    self.pending_effects.fetch_add(1, Ordering::SeqCst);
-                    let pending_guard = AtomicCounterGuard(Arc::clone(&self.pending_effects));
+                // Create span for reducer execution
+                let span = tracing::debug_span!("reducer_execution");
+                let _enter = span.enter();
 
-                    let tracking_clone = tracking.clone();
-                    let store = self.clone();
+                // Metrics: Time reducer execution
+                let diff = self.state_differ.as_ref().and_then(|differ| differ.begin(&state, &action));
+                let start = std::time::Instant::now();
+                let effects = self.reduce_supervised(&mut state, action, &ReduceContext::live(None));
+                let duration = start.elapsed();
+                if let (Some(differ), Some(diff)) = (&self.state_differ, diff) {
+                    differ.finish(diff, &state);
+                }
+                self.reductions.fetch_add(1, Ordering::Release);
+                self.state_observers.notify(&state);
+                self.metrics.record_histogram("store.reducer.duration_seconds", &[], duration.as_secs_f64());
 
-                    tokio::spawn(async move {
-                        let _guard = DecrementGuard(tracking_clone.clone());
-                        let _pending_guard = pending_guard; // Decrement on drop
+                tracing::trace!("Reducer completed, returned {} effects", effects.len());
 
-                        tokio::time::sleep(duration).await;
-                        tracing::trace!("Effect::Delay completed, sending action");
+                // Metrics: Record number of effects produced
+                // Note: Precision loss acceptable for metrics (effect counts < 2^52)
+                #[allow(clippy::cast_precision_loss)]
+                self.metrics.record_histogram("store.effects.count", &[], effects.len() as f64);
 
-                        // Broadcast to observers
-                        let _ = store.action_broadcast.send((*action).clone());
+                effects
+            };
 
-                        let _ = store.send(*action).await;
-                    });
-                },
-                Effect::Parallel(effects) => {
-                    let effect_count = effects.len();
-                    tracing::trace!("Executing Effect::Parallel with {} effects", effect_count);
-                    metrics::counter!("store.effects.executed", "type" => "parallel").increment(1);
+            // Execute effects with tracking
+            tracing::trace!("Executing {} effects", effects.len());
+            for effect in effects {
+                self.execute_effect_internal(effect, tracking.clone(), None, None, None);
+            }
+            tracing::debug!("Action processing completed, returning handle");
 
-                    // Execute all effects concurrently, each with the same tracking and metadata
-                    let store = self.clone();
-                    for effect in effects {
-                        store.execute_effect_internal(effect, tracking.clone(), metadata.clone());
-                    }
-                },
-                Effect::Sequential(effects) => {
-                    let effect_count = effects.len();
-                    tracing::trace!("Executing Effect::Sequential with {} effects", effect_count);
-                    metrics::counter!("store.effects.executed", "type" => "sequential").increment(1);
+            Ok(handle)
+        }
 
-                    tracking.increment();
+        /// Read current state via a closure
+        ///
+        /// Access state through a closure to ensure the lock is released promptly:
+        ///
+        /// ```ignore
+        /// let order_count = store.state(|s| s.orders.len()).await;
+        /// ```
+        ///
+        /// # Arguments
+        ///
+        /// - `f`: Closure that receives a reference to state and returns a value
+        ///
+        /// # Returns
+        ///
+        /// The value returned by the closure
+        pub async fn state<F, T>(&self, f: F) -> T
+        where
+            F: FnOnce(&S) -> T,
+        {
+            let state = self.state.read().await;
+            f(&*state)
+        }
 
-                    // Track global pending effects for shutdown
-                    self.pending_effects.fetch_add(1, Ordering::SeqCst);
-                    let pending_guard = AtomicCounterGuard(Arc::clone(&self.pending_effects));
+        /// Take an owned, consistent snapshot of the current state
+        ///
+        /// Clones the state under a brief read lock, so long computations
+        /// (reports, exports) can run on the snapshot while the Store keeps
+        /// reducing actions. The snapshot is shared: until the next reduction,
+        /// further calls return the same `Arc` without cloning again.
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// let snapshot = store.snapshot_state().await;
+        /// let report = tokio::task::spawn_blocking(move || build_report(&snapshot)).await?;
+        /// ```
+        pub async fn snapshot_state(&self) -> Arc<S>
+        where
+            S: Clone,
+        {
+            let state = self.state.read().await;
+            let reductions = self.reductions.load(Ordering::Acquire);
 
-                    let tracking_clone = tracking.clone();
-                    let store = self.clone();
-                    let metadata_clone = metadata.clone();
+            let mut cached = self.snapshot.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            if let Some((taken_at, snapshot)) = cached.as_ref() {
+                if *taken_at == reductions {
+                    return Arc::clone(snapshot);
+                }
+            }
 
-                    tokio::spawn(async move {
-                        let _guard = DecrementGuard(tracking_clone.clone());
-                        let _pending_guard = pending_guard; // Decrement on drop
+            let snapshot = Arc::new(state.clone());
+            *cached = Some((reductions, Arc::clone(&snapshot)));
+            snapshot
+        }
 
-                        // Execute effects one by one, waiting for each to complete
-                        for (idx, effect) in effects.into_iter().enumerate() {
-                            tracing::trace!(
-                                "Executing sequential effect {} of {}",
-                                idx + 1,
-                                effect_count
-                            );
+        /// Serialize the current state with [`BincodeCodec`](crate::state_codec::BincodeCodec)
+        ///
+        /// The state is encoded under a read lock, so the export reflects a
+        /// single point between reductions. Hand the bytes to
+        /// [`import_state`](Self::import_state) on a Store in another process
+        /// to resume from this state. See the [`state_codec`](crate::state_codec)
+        /// module for other formats.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`StoreError::StateCodec`] if the state cannot be serialized.
+        pub async fn export_state(&self) -> Result<Vec<u8>, StoreError>
+        where
+            S: serde::Serialize + serde::de::DeserializeOwned,
+        {
+            self.export_state_with(&crate::state_codec::BincodeCodec).await
+        }
 
-                            // Create sub-tracking for this effect
-                            let (sub_tx, mut sub_rx) = watch::channel(());
-                            let sub_tracking = EffectTracking {
-                                mode: TrackingMode::Direct,
-                                counter: Arc::new(AtomicUsize::new(0)),
-                                notifier: sub_tx,
-                                feedback_dest: tracking_clone.feedback_dest.clone(),
-                            };
+        /// Serialize the current state with `codec`
+        ///
+        /// # Errors
+        ///
+        /// Returns [`StoreError::StateCodec`] if `codec` fails to encode the state.
+        pub async fn export_state_with<C>(&self, codec: &C) -> Result<Vec<u8>, StoreError>
+        where
+            C: crate::state_codec::StateCodec<S>,
+        {
+            let state = self.state.read().await;
+            Ok(codec.encode(&state)?)
+        }
 
-                            // Execute the effect with metadata
-                            store.execute_effect_internal(effect, sub_tracking.clone(), metadata_clone.clone());
+        /// Replace the state with one exported by [`export_state`](Self::export_state)
+        ///
+        /// The bytes are decoded before the write lock is taken; on failure the
+        /// current state is left untouched. State observers are notified as if
+        /// a reduction had happened. No effects run, so import before the Store
+        /// starts receiving actions.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`StoreError::StateCodec`] if `bytes` cannot be decoded.
+        pub async fn import_state(&self, bytes: &[u8]) -> Result<(), StoreError>
+        where
+            S: serde::Serialize + serde::de::DeserializeOwned,
+        {
+            self.import_state_with(&crate::state_codec::BincodeCodec, bytes).await
+        }
+
+        /// Replace the state with one decoded by `codec`
+        ///
+        /// # Errors
+        ///
+        /// Returns [`StoreError::StateCodec`] if `codec` fails to decode `bytes`.
+        pub async fn import_state_with<C>(&self, codec: &C, bytes: &[u8]) -> Result<(), StoreError>
+        where
+            C: crate::state_codec::StateCodec<S>,
+        {
+            let imported = codec.decode(bytes)?;
+
+            let mut state = self.state.write().await;
+            *state = imported;
+            self.reductions.fetch_add(1, Ordering::Release);
+            self.state_observers.notify(&state);
+            tracing::info!(bytes = bytes.len(), "Imported Store state");
+            Ok(())
+        }
+
+        /// Observe a slice of state selected by `selector`
+        ///
+        /// The selector runs after every reduction; the subscription sees a
+        /// new value whenever the result differs from the previous one. See
+        /// the [`observe`](crate::observe) module.
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// let mut total = store.subscribe_state(|s: &CartState| s.total()).await;
+        /// while let Some(total) = total.changed().await {
+        ///     render_total(total);
+        /// }
+        /// ```
+        pub async fn subscribe_state<T, F>(&self, selector: F) -> StateSubscription<T>
+        where
+            T: PartialEq + Send + Sync + 'static,
+            F: Fn(&S) -> T + Send + Sync + 'static,
+        {
+            // Seed under the read lock so no reduction is missed in between
+            let state = self.state.read().await;
+            self.state_observers.observe(&state, selector)
+        }
+
+        /// Blocking version of [`Self::send`] for non-async callers
+        ///
+        /// Runs `send()` to completion on the Store's runtime and returns once
+        /// the action is reduced. Intended for FFI entry points and blocking
+        /// dispatcher threads. With a current-thread runtime, some other
+        /// thread must be driving it (inside `Runtime::block_on`).
+        ///
+        /// # Errors
+        ///
+        /// - [`StoreError::BlockingInAsyncContext`] if called from async code
+        /// - [`StoreError::NoRuntime`] if the Store has no runtime handle
+        /// - Any error returned by [`Self::send`]
+        ///
+        /// # Example
+        ///
+        /// ```ignore
+        /// #[no_mangle]
+        /// pub extern "C" fn counter_increment(store: &CounterStore) -> bool {
+        ///     store.send_blocking(CounterAction::Increment).is_ok()
+        /// }
+        /// ```
+        pub fn send_blocking(&self, action: A) -> Result<EffectHandle, StoreError>
+        where
+            R: Clone,
+            E: Clone,
+        {
+            Self::ensure_blocking_allowed()?;
+            let runtime = self.runtime.as_ref().ok_or(StoreError::NoRuntime)?;
+            runtime.block_on(self.send(action))
+        }
+
+        /// Blocking version of [`Self::state`] for non-async callers
+        ///
+        /// # Errors
+        ///
+        /// Returns [`StoreError::BlockingInAsyncContext`] if called from async code.
+        pub fn state_blocking<F, T>(&self, f: F) -> Result<T, StoreError>
+        where
+            F: FnOnce(&S) -> T,
+        {
+            Self::ensure_blocking_allowed()?;
+            let state = self.state.blocking_read();
+            Ok(f(&*state))
+        }
+
+        /// Reject blocking calls on runtime threads, where they could deadlock
+        fn ensure_blocking_allowed() -> Result<(), StoreError> {
+            if tokio::runtime::Handle::try_current().is_ok() {
+                return Err(StoreError::BlockingInAsyncContext);
+            }
+            Ok(())
+        }
+
+        /// Retry an async operation according to the retry policy
+        ///
+        /// This wraps an async operation with exponential backoff retry logic.
+        /// Metrics are recorded for retry attempts.
+        ///
+        /// Errors that are not [retryable](RetryableError) (such as
+        /// concurrency conflicts) are returned immediately, without backoff
+        /// and without a DLQ entry, so the reducer can handle them.
+        ///
+        /// # Arguments
+        ///
+        /// - `operation_name`: Name for logging/metrics (e.g., "`append_events`")
+        /// - `scope`: Extra metric label (stream prefix or topic)
+        /// - `retry`: Policy overriding the operation's, from `Effect::Retrying`
+        /// - `target`: Stream id, topic or collection, recorded in the DLQ
+        /// - `payload`: Serialized operation data for the DLQ, computed only on exhaustion
+        /// - `f`: Async function to execute (will be called multiple times on failure)
+        ///
+        /// # Returns
+        ///
+        /// Result from the operation, or the last error if all retries exhausted
+        #[allow(clippy::cognitive_complexity, clippy::too_many_lines)] // Linear retry loop with per-outcome metrics
+        async fn retry_operation<F, Fut, T, Err>(
+            &self,
+            operation_name: &'static str,
+            scope: (&'static str, MetricLabel),
+            retry: Option<RetryPolicy>,
+            target: &str,
+            payload: impl FnOnce() -> Option<Vec<u8>>,
+            mut f: F,
+        ) -> Result<T, Err>
+        where
+            F: FnMut() -> Fut,
+            Fut: std::future::Future<Output = Result<T, Err>>,
+            Err: std::fmt::Display + RetryableError + Clone + Into<FailureError>,
+        {
+            let (retry_policy, dead_letter) = self.operation_policy(operation_name);
+            let retry_policy = retry.unwrap_or(retry_policy);
+            let operation = MetricLabel::from_static(operation_name);
+            let mut attempt = 0;
+            let mut first_failed_at = None;
+            let started = tokio::time::Instant::now();
+
+            loop {
+                match f().await {
+                    Ok(result) => {
+                        // Success! Record metrics if this was a retry
+                        if attempt > 0 {
+                            self.metrics.increment_counter(
+                                "store.retry.success",
+                                &[
+                                    ("operation", operation.clone()),
+                                    scope.clone(),
+                                    ("attempts", self.labels.attempt(attempt)),
+                                ],
+                                1,
+                            );
+                            tracing::info!(
+                                operation = operation_name,
+                                attempt = attempt,
+                                "Operation succeeded after retry"
+                            );
+                        }
+                        return Ok(result);
+                    }
+                    Err(error) if !error.is_retryable() => {
+                        let kind = error.clone().into().kind();
+                        self.metrics.increment_counter(
+                            "store.retry.non_retryable",
+                            &[
+                                ("operation", operation.clone()),
+                                scope.clone(),
+                                ("error", MetricLabel::from_static(kind)),
+                            ],
+                            1,
+                        );
+                        tracing::debug!(
+                            operation = operation_name,
+                            attempt = attempt,
+                            error = %error,
+                            "Operation failed with non-retryable error"
+                        );
+                        return Err(error);
+                    }
+                    Err(error) => {
+                        let first_failed_at = *first_failed_at.get_or_insert_with(std::time::SystemTime::now);
+
+                        // Check if we should retry
+                        let delay = match retry_policy.next_retry(attempt + 1, started.elapsed()) {
+                            Ok(delay) => delay,
+                            Err(exhausted) => {
+                                let failure = OperationFailure::new(operation_name, error.clone())
+                                    .with_target(target)
+                                    .with_attempts((attempt + 1) as usize, first_failed_at)
+                                    .with_exhausted(exhausted);
+                                let kind = failure.error.kind();
+
+                                // Exhausted retries - push to DLQ unless the operation opted out
+                                if dead_letter {
+                                    self.dlq.push(
+                                        failure.with_payload(payload()),
+                                        error.to_string(),
+                                        (attempt + 1) as usize,
+                                    );
+                                }
 
-                            // Wait for this effect to complete before continuing
-                            if sub_tracking.counter.load(Ordering::SeqCst) > 0 {
-                                let _ = sub_rx.changed().await;
+                                self.metrics.increment_counter(
+                                    "store.retry.exhausted",
+                                    &[
+                                        ("operation", operation.clone()),
+                                        scope.clone(),
+                                        ("attempts", self.labels.attempt(attempt)),
+                                        ("error", MetricLabel::from_static(kind)),
+                                        ("reason", MetricLabel::from_static(exhausted.kind())),
+                                    ],
+                                    1,
+                                );
+                                tracing::error!(
+                                    operation = operation_name,
+                                    attempt = attempt,
+                                    error = %error,
+                                    reason = %exhausted,
+                                    dead_lettered = dead_letter,
+                                    "Operation failed after exhausting retries"
+                                );
+                                return Err(error);
                             }
+                        };
+
+                        // Retry after the delay
+                        self.metrics.increment_counter(
+                            "store.retry.attempt",
+                            &[
+                                ("operation", operation.clone()),
+                                scope.clone(),
+                                ("attempt", self.labels.attempt(attempt)),
+                            ],
+                            1,
+                        );
+                        tracing::warn!(
+                            operation = operation_name,
+                            attempt = attempt,
+                            delay_ms = delay.as_millis(),
+                            error = %error,
+                            "Operation failed, retrying after delay"
+                        );
+
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                }
+            }
+        }
+
+        /// Execute an effect with tracking
+        ///
+        /// Internal method that executes effects with completion tracking.
+        /// Uses [`DecrementGuard`] to ensure the effect counter is always
+        /// decremented, even if the effect panics.
+        ///
+        /// # Effect Types
+        ///
+        /// - `None`: No-op
+        /// - `Future`: Executes async computation, sends resulting action if `Some`
+        /// - `Delay`: Waits for duration, then sends action
+        /// - `DelayKeyed`: Like `Delay`, cancelling any pending delay with the same key
+        /// - `Parallel`: Executes effects concurrently
+        /// - `Sequential`: Executes effects in order, waiting for each to complete, feedback included
+        ///
+        /// # Error Handling Strategy
+        ///
+        /// **Reducer panics**: Propagate (fail fast). Reducers should be pure functions
+        /// that do not panic. If a reducer panics, the store will halt.
+        ///
+        /// **Effect execution failures**: Log and continue. Effects are fire-and-forget
+        /// operations. If an effect task panics during parallel execution, it's logged
+        /// but other effects continue. The [`DecrementGuard`] ensures the counter is
+        /// always updated even on panic.
+        ///
+        /// # Arguments
+        ///
+        /// - `effect`: The effect to execute
+        /// - `tracking`: The tracking context for this effect (passed by value to enable cloning)
+        /// - `retry`: Retry policy set by an enclosing `Effect::Retrying`, overriding the Store's
+        #[allow(clippy::needless_pass_by_value)] // tracking is cloned, so pass by value is intentional
+        #[allow(clippy::cognitive_complexity)] // TODO: Refactor in Phase 4
+        #[allow(clippy::too_many_lines)] // TODO: Refactor in Phase 4
+        #[tracing::instrument(skip(self, effect, tracking, ledger, retry), name = "execute_effect")]
+        fn execute_effect_internal(
+            &self,
+            effect: Effect<A>,
+            tracking: EffectTracking,
+            metadata: Option<composable_rust_core::event::EventMetadata>,
+            ledger: Option<Arc<CostLedger>>,
+            retry: Option<RetryPolicy>,
+        )
+        where
+            R: Clone,
+            E: Clone,
+            A: Clone + Send + 'static,
+        {
+            match effect {
+                Effect::None => {
+                    tracing::trace!("Executing Effect::None (no-op)");
+                    self.record_effect("none");
+                },
+                Effect::Future(fut) => {
+                    tracing::trace!("Executing Effect::Future");
+                    self.record_effect("future");
+                    let Some(slot) = self.claim_effect_slot("future") else {
+                        return;
+                    };
+                    tracking.increment();
+
+                    // Track global pending effects for shutdown
+                    let pending_guard = PendingEffectGuard::new(&self.pending_effects, &self.metrics);
+
+                    let tracking_clone = tracking.clone();
+                    let store = self.downgrade();
+                    let metadata_clone = metadata.clone();
+                    let ledger_clone = ledger.clone();
+
+                    pending_guard.spawn("future", async move {
+                        let _guard = DecrementGuard(tracking_clone.clone());
+                        let _permit = slot.acquire().await;
+
+                        if let Some(action) = fut.await {
+                            tracing::trace!("Effect::Future produced an action, sending to store with metadata");
+
+                            // Send action back to store with metadata (preserves correlation context)
+                            store.feed_back(action, metadata_clone, ledger_clone, &tracking_clone).await;
+                        } else {
+                            tracing::trace!("Effect::Future completed with no action");
                         }
-                        tracing::trace!("Effect::Sequential completed");
                     });
                 },
-                Effect::EventStore(op) => {
-                    use composable_rust_core::effect::EventStoreOperation;
-
-                    tracing::trace!("Executing Effect::EventStore");
-                    metrics::counter!("store.effects.executed", "type" => "event_store").increment(1);
+                Effect::Stream(stream) => {
+                    tracing::trace!("Executing Effect::Stream");
+                    self.record_effect("stream");
+                    let Some(slot) = self.claim_effect_slot("stream") else {
+                        return;
+                    };
                     tracking.increment();
 
                     // Track global pending effects for shutdown
-                    self.pending_effects.fetch_add(1, Ordering::SeqCst);
-                    let pending_guard = AtomicCounterGuard(Arc::clone(&self.pending_effects));
+                    let pending_guard = PendingEffectGuard::new(&self.pending_effects, &self.metrics);
 
                     let tracking_clone = tracking.clone();
-                    let store = self.clone();
+                    let store = self.downgrade();
                     let metadata_clone = metadata.clone();
+                    let ledger_clone = ledger.clone();
+
+                    pending_guard.spawn("stream", async move {
+                        use futures::StreamExt;
 
-                    tokio::spawn(async move {
                         let _guard = DecrementGuard(tracking_clone.clone());
-                        let _pending_guard = pending_guard; // Decrement on drop
+                        let _permit = slot.acquire().await;
 
-                        let action = match op {
-                            EventStoreOperation::AppendEvents {
-                                event_store,
-                                stream_id,
-                                expected_version,
-                                events,
-                                metadata,
-                                on_success,
-                                on_error,
-                            } => {
-                                tracing::debug!(
-                                    stream_id = %stream_id,
-                                    expected_version = ?expected_version,
-                                    event_count = events.len(),
-                                    has_metadata = metadata.is_some(),
-                                    "Executing append_events"
-                                );
+                        let mut stream = stream;
+                        let mut item_count = 0;
 
-                                // Merge metadata into events if provided
-                                let events_with_metadata = if let Some(ref effect_metadata) = metadata {
-                                    events.into_iter().map(|mut event| {
-                                        // Merge effect metadata into event metadata
-                                        if let Some(event_meta) = event.metadata.as_mut() {
-                                            // Event already has metadata - merge (effect metadata takes precedence)
-                                            if effect_metadata.correlation_id.is_some() {
-                                                event_meta.correlation_id.clone_from(&effect_metadata.correlation_id);
-                                            }
-                                            if effect_metadata.causation_id.is_some() {
-                                                event_meta.causation_id.clone_from(&effect_metadata.causation_id);
-                                            }
-                                            if effect_metadata.user_id.is_some() {
-                                                event_meta.user_id.clone_from(&effect_metadata.user_id);
-                                            }
-                                            if effect_metadata.timestamp.is_some() {
-                                                event_meta.timestamp.clone_from(&effect_metadata.timestamp);
-                                            }
-                                        } else {
-                                            // Event has no metadata - use effect metadata
-                                            event.metadata = Some(effect_metadata.clone());
-                                        }
-                                        event
-                                    }).collect::<Vec<_>>()
-                                } else {
-                                    events
-                                };
+                        tracing::trace!("Starting stream consumption");
 
-                                // Wrap with retry logic
-                                let stream_id_clone = stream_id.clone();
-                                let result = store.retry_operation("append_events", || {
-                                    let event_store_clone = event_store.clone();
-                                    let stream_id_clone = stream_id_clone.clone();
-                                    let events_clone = events_with_metadata.clone();
-                                    async move {
-                                        event_store_clone
-                                            .append_events(stream_id_clone, expected_version, events_clone)
-                                            .await
-                                    }
-                                }).await;
+                        while let Some(action) = stream.next().await {
+                            if store.is_dropped() {
+                                tracing::debug!("Store dropped, abandoning Effect::Stream");
+                                break;
+                            }
+                            item_count += 1;
+                            tracing::trace!("Stream yielded item #{}", item_count);
+                            store.metrics.increment_counter("store.stream_items.processed", &[], 1);
+
+                            // Send action back to store with metadata (preserves correlation context)
+                            store.feed_back(action, metadata_clone.clone(), ledger_clone.clone(), &tracking_clone).await;
+                        }
+
+                        tracing::trace!(
+                            "Effect::Stream completed, processed {} items",
+                            item_count
+                        );
+                        store.metrics.record_histogram("store.stream_items.total", &[], f64::from(item_count));
+                    });
+                },
+                Effect::Delay { duration, action } => {
+                    tracing::trace!("Executing Effect::Delay (duration: {:?})", duration);
+                    self.record_effect("delay");
+                    tracking.increment();
+
+                    // Track global pending effects for shutdown
+                    let pending_guard = PendingEffectGuard::new(&self.pending_effects, &self.metrics);
+
+                    let tracking_clone = tracking.clone();
+                    let store = self.downgrade();
+                    let ledger_clone = ledger.clone();
+
+                    pending_guard.spawn("delay", async move {
+                        let _guard = DecrementGuard(tracking_clone.clone());
+
+                        tokio::time::sleep(duration).await;
+                        tracing::trace!("Effect::Delay completed, sending action");
+
+                        store.feed_back(*action, None, ledger_clone, &tracking_clone).await;
+                    });
+                },
+                Effect::DelayKeyed { key, duration, action } => {
+                    tracing::trace!(%key, "Executing Effect::DelayKeyed (duration: {:?})", duration);
+                    self.record_effect("delay_keyed");
+                    tracking.increment();
+
+                    // Track global pending effects for shutdown
+                    let pending_guard = PendingEffectGuard::new(&self.pending_effects, &self.metrics);
+
+                    // Created outside the task so that aborting a replaced timer,
+                    // even before it first runs, still completes its tracking
+                    let guard = DecrementGuard(tracking);
+
+                    let store = self.downgrade();
+                    let timer_key = key.clone();
+                    let replaced = self.keyed_delays.schedule(key, move |id| {
+                        pending_guard.spawn("delay_keyed", async move {
+                            let guard = guard;
+
+                            tokio::time::sleep(duration).await;
+                            if !store.keyed_delays.fire(&timer_key, id) {
+                                return;
+                            }
+                            tracing::trace!(key = %timer_key, "Effect::DelayKeyed completed, sending action");
+
+                            store.feed_back(*action, None, ledger, &guard.0).await;
+                        })
+                    });
+                    if replaced {
+                        tracing::trace!("Effect::DelayKeyed replaced a pending delay");
+                        self.metrics.increment_counter("store.effects.delay_replaced", &[], 1);
+                    }
+                },
+                Effect::Parallel(effects) => {
+                    let effect_count = effects.len();
+                    tracing::trace!("Executing Effect::Parallel with {} effects", effect_count);
+                    self.record_effect("parallel");
+
+                    // Execute all effects concurrently, each with the same tracking and metadata
+                    for effect in effects {
+                        self.execute_effect_internal(effect, tracking.clone(), metadata.clone(), ledger.clone(), retry.clone());
+                    }
+                },
+                Effect::Sequential(effects) => {
+                    let effect_count = effects.len();
+                    tracing::trace!("Executing Effect::Sequential with {} effects", effect_count);
+                    self.record_effect("sequential");
+
+                    tracking.increment();
+
+                    // Track global pending effects for shutdown
+                    let pending_guard = PendingEffectGuard::new(&self.pending_effects, &self.metrics);
+
+                    let tracking_clone = tracking.clone();
+                    let store = self.downgrade();
+                    let metadata_clone = metadata.clone();
+                    let ledger_clone = ledger.clone();
+
+                    pending_guard.spawn("sequential", async move {
+                        let _guard = DecrementGuard(tracking_clone.clone());
+
+                        // Execute effects one by one, waiting for each one's whole
+                        // effect tree (including feedback actions) to complete
+                        for (idx, effect) in effects.into_iter().enumerate() {
+                            tracing::trace!(
+                                "Executing sequential effect {} of {}",
+                                idx + 1,
+                                effect_count
+                            );
+
+                            let (mut step, step_tracking) = EffectHandle::new(TrackingMode::Cascading {
+                                children: Arc::new(Mutex::new(Vec::new())),
+                            });
+
+                            // Execute the effect with metadata
+                            store.execute_effect_internal(
+                                effect,
+                                step_tracking,
+                                metadata_clone.clone(),
+                                ledger_clone.clone(),
+                                retry.clone(),
+                            );
+
+                            let outcome = step.wait().await;
+                            tracking_clone.record_failures(outcome.failed);
+                        }
+                        tracing::trace!("Effect::Sequential completed");
+                    });
+                },
+                Effect::EventStore(op) => {
+                    use composable_rust_core::effect::EventStoreOperation;
+
+                    tracing::trace!("Executing Effect::EventStore");
+                    self.record_effect("event_store");
+                    tracking.increment();
+
+                    // Track global pending effects for shutdown
+                    let pending_guard = PendingEffectGuard::new(&self.pending_effects, &self.metrics);
+
+                    let operation = match &op {
+                        EventStoreOperation::AppendEvents { .. } => "event_store.append_events",
+                        EventStoreOperation::LoadEvents { .. } => "event_store.load_events",
+                        EventStoreOperation::LoadEventsPaged { .. } => "event_store.load_events_paged",
+                        EventStoreOperation::SaveSnapshot { .. } => "event_store.save_snapshot",
+                        EventStoreOperation::LoadSnapshot { .. } => "event_store.load_snapshot",
+                    };
+                    let tracking_clone = tracking.clone();
+                    let store = self.downgrade();
+                    let metadata_clone = metadata.clone();
+                    let ledger_clone = ledger.clone();
+
+                    pending_guard.spawn(operation, async move {
+                        let _guard = DecrementGuard(tracking_clone.clone());
+
+                        let action = match op {
+                            EventStoreOperation::AppendEvents {
+                                event_store,
+                                stream_id,
+                                expected_version,
+                                events,
+                                metadata,
+                                idempotency_key,
+                                on_success,
+                                on_error,
+                            } => {
+                                tracing::debug!(
+                                    stream_id = %stream_id,
+                                    expected_version = ?expected_version,
+                                    event_count = events.len(),
+                                    has_metadata = metadata.is_some(),
+                                    idempotency_key = ?idempotency_key,
+                                    "Executing append_events"
+                                );
+
+                                // Merge metadata into events if provided
+                                let events_with_metadata = if let Some(ref effect_metadata) = metadata {
+                                    events.into_iter().map(|mut event| {
+                                        // Merge effect metadata into event metadata
+                                        if let Some(event_meta) = event.metadata.as_mut() {
+                                            // Event already has metadata - merge (effect metadata takes precedence)
+                                            if effect_metadata.correlation_id.is_some() {
+                                                event_meta.correlation_id.clone_from(&effect_metadata.correlation_id);
+                                            }
+                                            if effect_metadata.causation_id.is_some() {
+                                                event_meta.causation_id.clone_from(&effect_metadata.causation_id);
+                                            }
+                                            if effect_metadata.user_id.is_some() {
+                                                event_meta.user_id.clone_from(&effect_metadata.user_id);
+                                            }
+                                            if effect_metadata.timestamp.is_some() {
+                                                event_meta.timestamp.clone_from(&effect_metadata.timestamp);
+                                            }
+                                        } else {
+                                            // Event has no metadata - use effect metadata
+                                            event.metadata = Some(effect_metadata.clone());
+                                        }
+                                        event
+                                    }).collect::<Vec<_>>()
+                                } else {
+                                    events
+                                };
+
+                                // Wrap with retry logic
+                                let stream_id_clone = stream_id.clone();
+                                let scope = ("stream", store.labels.interner.stream_prefix(stream_id.as_str()));
+                                let result = store.retry_operation("append_events", scope, retry.clone(), stream_id.as_str(), || bincode::serialize(&events_with_metadata).ok(), || {
+                                    let event_store_clone = event_store.clone();
+                                    let stream_id_clone = stream_id_clone.clone();
+                                    let events_clone = events_with_metadata.clone();
+                                    let idempotency_key = idempotency_key.clone();
+                                    async move {
+                                        match idempotency_key {
+                                            Some(key) => {
+                                                event_store_clone
+                                                    .append_events_idempotent(stream_id_clone, expected_version, events_clone, key)
+                                                    .await
+                                            },
+                                            None => {
+                                                event_store_clone
+                                                    .append_events(stream_id_clone, expected_version, events_clone)
+                                                    .await
+                                            },
+                                        }
+                                    }
+                                }).await;
+
+                                match result {
+                                    Ok(version) => {
+                                        tracing::debug!(new_version = ?version, "append_events succeeded");
+                                        on_success(version)
+                                    },
+                                    Err(error) => {
+                                        tracing::warn!(error = %error, "append_events failed");
+                                        tracking_clone.fail("event_store", "append_events", &error);
+                                        on_error(error)
+                                    },
+                                }
+                            },
+                            EventStoreOperation::LoadEvents {
+                                event_store,
+                                stream_id,
+                                from_version,
+                                on_success,
+                                on_error,
+                            } => {
+                                tracing::debug!(
+                                    stream_id = %stream_id,
+                                    from_version = ?from_version,
+                                    "Executing load_events"
+                                );
+
+                                // Wrap with retry logic
+                                let stream_id_clone = stream_id.clone();
+                                let scope = ("stream", store.labels.interner.stream_prefix(stream_id.as_str()));
+                                let result = store.retry_operation("load_events", scope, retry.clone(), stream_id.as_str(), || None, || {
+                                    let event_store_clone = event_store.clone();
+                                    let stream_id_clone = stream_id_clone.clone();
+                                    async move {
+                                        event_store_clone.load_events(stream_id_clone, from_version).await
+                                    }
+                                }).await;
+
+                                match result {
+                                    Ok(events) => {
+                                        tracing::debug!(
+                                            event_count = events.len(),
+                                            "load_events succeeded"
+                                        );
+                                        on_success(events)
+                                    },
+                                    Err(error) => {
+                                        tracing::warn!(error = %error, "load_events failed");
+                                        tracking_clone.fail("event_store", "load_events", &error);
+                                        on_error(error)
+                                    },
+                                }
+                            },
+                            EventStoreOperation::LoadEventsPaged {
+                                event_store,
+                                stream_id,
+                                from_version,
+                                page_size,
+                                on_page,
+                                on_complete,
+                                on_error,
+                            } => {
+                                tracing::debug!(
+                                    stream_id = %stream_id,
+                                    from_version = ?from_version,
+                                    page_size,
+                                    "Executing paged load_events"
+                                );
+
+                                let page_size = page_size.max(1);
+                                let scope = ("stream", store.labels.interner.stream_prefix(stream_id.as_str()));
+                                let mut next = from_version;
+                                loop {
+                                    // Each page is retried on its own, so a failure does not reload earlier pages
+                                    let result = store.retry_operation("load_events", scope.clone(), retry.clone(), stream_id.as_str(), || None, || {
+                                        let event_store_clone = event_store.clone();
+                                        let stream_id_clone = stream_id.clone();
+                                        async move {
+                                            event_store_clone.load_events_page(stream_id_clone, next, page_size).await
+                                        }
+                                    }).await;
+
+                                    match result {
+                                        Ok(events) if events.is_empty() => break on_complete(next),
+                                        Ok(events) => {
+                                            let loaded = events.len();
+                                            next = next + loaded as u64;
+                                            tracing::trace!(event_count = loaded, next_version = %next, "Loaded page of events");
+
+                                            // Reduce the page before loading the next one, keeping one page in memory
+                                            if let Some(action) = on_page(events) {
+                                                store.feed_back(action, metadata_clone.clone(), ledger_clone.clone(), &tracking_clone).await;
+                                            }
+                                            if loaded < page_size {
+                                                break on_complete(next);
+                                            }
+                                        },
+                                        Err(error) => {
+                                            tracing::warn!(error = %error, next_version = %next, "paged load_events failed");
+                                            tracking_clone.fail("event_store", "load_events", &error);
+                                            break on_error(error);
+                                        },
+                                    }
+                                }
+                            },
+                            EventStoreOperation::SaveSnapshot {
+                                event_store,
+                                stream_id,
+                                version,
+                                state,
+                                on_success,
+                                on_error,
+                            } => {
+                                tracing::debug!(
+                                    stream_id = %stream_id,
+                                    version = ?version,
+                                    state_size = state.len(),
+                                    "Executing save_snapshot"
+                                );
+
+                                // Wrap with retry logic
+                                let stream_id_clone = stream_id.clone();
+                                let state_clone = state.clone();
+                                let scope = ("stream", store.labels.interner.stream_prefix(stream_id.as_str()));
+                                let result = store.retry_operation("save_snapshot", scope, retry.clone(), stream_id.as_str(), || Some(state.clone()), || {
+                                    let event_store_clone = event_store.clone();
+                                    let stream_id_clone = stream_id_clone.clone();
+                                    let state_clone = state_clone.clone();
+                                    async move {
+                                        event_store_clone
+                                            .save_snapshot(stream_id_clone, version, state_clone)
+                                            .await
+                                    }
+                                }).await;
+
+                                match result {
+                                    Ok(()) => {
+                                        tracing::debug!("save_snapshot succeeded");
+                                        on_success(())
+                                    },
+                                    Err(error) => {
+                                        tracing::warn!(error = %error, "save_snapshot failed");
+                                        tracking_clone.fail("event_store", "save_snapshot", &error);
+                                        on_error(error)
+                                    },
+                                }
+                            },
+                            EventStoreOperation::LoadSnapshot {
+                                event_store,
+                                stream_id,
+                                on_success,
+                                on_error,
+                            } => {
+                                tracing::debug!(stream_id = %stream_id, "Executing load_snapshot");
+
+                                // Wrap with retry logic
+                                let stream_id_clone = stream_id.clone();
+                                let scope = ("stream", store.labels.interner.stream_prefix(stream_id.as_str()));
+                                let result = store.retry_operation("load_snapshot", scope, retry.clone(), stream_id.as_str(), || None, || {
+                                    let event_store_clone = event_store.clone();
+                                    let stream_id_clone = stream_id_clone.clone();
+                                    async move {
+                                        event_store_clone.load_snapshot(stream_id_clone).await
+                                    }
+                                }).await;
+
+                                match result {
+                                    Ok(snapshot) => {
+                                        tracing::debug!(
+                                            has_snapshot = snapshot.is_some(),
+                                            "load_snapshot succeeded"
+                                        );
+                                        on_success(snapshot)
+                                    },
+                                    Err(error) => {
+                                        tracing::warn!(error = %error, "load_snapshot failed");
+                                        tracking_clone.fail("event_store", "load_snapshot", &error);
+                                        on_error(error)
+                                    },
+                                }
+                            },
+                        };
+
+                        // Send action back to store if callback produced one
+                        if let Some(action) = action {
+                            tracing::trace!(
+                                "EventStore operation produced an action, sending to store with metadata"
+                            );
+                            store.feed_back(action, metadata_clone, ledger_clone, &tracking_clone).await;
+                        } else {
+                            tracing::trace!("EventStore operation completed with no action");
+                        }
+                    });
+                },
+                Effect::PublishEvent(op) => {
+                    use composable_rust_core::effect::EventBusOperation;
+
+                    tracing::trace!("Executing Effect::PublishEvent");
+                    self.record_effect("publish_event");
+                    tracking.increment();
+
+                    // Track global pending effects for shutdown
+                    let pending_guard = PendingEffectGuard::new(&self.pending_effects, &self.metrics);
+
+                    let tracking_clone = tracking.clone();
+                    let store = self.downgrade();
+                    let metadata_clone = metadata.clone();
+                    let ledger_clone = ledger.clone();
+
+                    pending_guard.spawn("publish_event.publish", async move {
+                        let _guard = DecrementGuard(tracking_clone.clone());
+
+                        let action = match op {
+                            EventBusOperation::Publish {
+                                event_bus,
+                                topic,
+                                event,
+                                options,
+                                on_success,
+                                on_error,
+                            } => {
+                                tracing::debug!(
+                                    topic = %topic,
+                                    event_type = %event.event_type,
+                                    "Executing publish"
+                                );
+
+                                // Wrap with retry logic
+                                let topic_clone = topic.clone();
+                                let event_clone = event.clone();
+                                let scope = ("topic", store.labels.interner.intern(&topic));
+                                let result = store.retry_operation("publish", scope, retry.clone(), &topic, || bincode::serialize(&event).ok(), || {
+                                    let event_bus_clone = event_bus.clone();
+                                    let topic_clone = topic_clone.clone();
+                                    let event_clone = event_clone.clone();
+                                    let options = options.clone();
+                                    async move {
+                                        let publish = event_bus_clone.publish_with_options(&topic_clone, &event_clone, &options);
+                                        match options.delivery_timeout {
+                                            Some(timeout) => tokio::time::timeout(timeout, publish).await.unwrap_or_else(|_| {
+                                                Err(composable_rust_core::event_bus::EventBusError::TransportError(format!(
+                                                    "delivery timed out after {timeout:?}"
+                                                )))
+                                            }),
+                                            None => publish.await,
+                                        }
+                                    }
+                                }).await;
+
+                                match result {
+                                    Ok(()) => {
+                                        tracing::debug!(topic = %topic, "publish succeeded");
+                                        on_success(())
+                                    },
+                                    Err(error) => {
+                                        tracing::warn!(
+                                            topic = %topic,
+                                            error = %error,
+                                            "publish failed"
+                                        );
+                                        tracking_clone.fail("publish_event", "publish", &error);
+                                        on_error(error)
+                                    },
+                                }
+                            },
+                        };
+
+                        // Send action back to store if callback produced one
+                        if let Some(action) = action {
+                            tracing::trace!(
+                                "PublishEvent operation produced an action, sending to store with metadata"
+                            );
+                            store.feed_back(action, metadata_clone, ledger_clone, &tracking_clone).await;
+                        } else {
+                            tracing::trace!("PublishEvent operation completed with no action");
+                        }
+                    });
+                },
+                Effect::Query(op) => {
+                    use composable_rust_core::effect::QueryOperation;
+
+                    tracing::trace!("Executing Effect::Query");
+                    self.record_effect("query");
+                    tracking.increment();
+
+                    // Track global pending effects for shutdown
+                    let pending_guard = PendingEffectGuard::new(&self.pending_effects, &self.metrics);
+
+                    let operation = match &op {
+                        QueryOperation::Get { .. } => "query.get",
+                        QueryOperation::Query { .. } => "query.query",
+                    };
+                    let tracking_clone = tracking.clone();
+                    let store = self.downgrade();
+                    let metadata_clone = metadata.clone();
+                    let ledger_clone = ledger.clone();
+
+                    pending_guard.spawn(operation, async move {
+                        let _guard = DecrementGuard(tracking_clone.clone());
+
+                        let action = match op {
+                            QueryOperation::Get {
+                                store: read_models,
+                                collection,
+                                key,
+                                on_success,
+                                on_error,
+                            } => {
+                                tracing::debug!(collection = %collection, key = %key, "Executing read model get");
+
+                                let scope = ("collection", store.labels.interner.intern(&collection));
+                                let result = store.retry_operation("query", scope, retry.clone(), &collection, || None, || {
+                                    let read_models = Arc::clone(&read_models);
+                                    let collection = collection.clone();
+                                    let key = key.clone();
+                                    async move { read_models.get(&collection, &key).await }
+                                }).await;
+
+                                match result {
+                                    Ok(value) => {
+                                        tracing::debug!(found = value.is_some(), "read model get succeeded");
+                                        on_success(value)
+                                    },
+                                    Err(error) => {
+                                        tracing::warn!(collection = %collection, error = %error, "read model get failed");
+                                        tracking_clone.fail("query", "get", &error);
+                                        on_error(error)
+                                    },
+                                }
+                            },
+                            QueryOperation::Query {
+                                store: read_models,
+                                query,
+                                on_success,
+                                on_error,
+                            } => {
+                                tracing::debug!(collection = %query.collection, filters = query.filters.len(), "Executing read model query");
+
+                                let scope = ("collection", store.labels.interner.intern(&query.collection));
+                                let result = store.retry_operation("query", scope, retry.clone(), &query.collection, || None, || {
+                                    let read_models = Arc::clone(&read_models);
+                                    let query = query.clone();
+                                    async move { read_models.query(&query).await }
+                                }).await;
+
+                                match result {
+                                    Ok(rows) => {
+                                        tracing::debug!(rows = rows.len(), "read model query succeeded");
+                                        on_success(rows)
+                                    },
+                                    Err(error) => {
+                                        tracing::warn!(collection = %query.collection, error = %error, "read model query failed");
+                                        tracking_clone.fail("query", "query", &error);
+                                        on_error(error)
+                                    },
+                                }
+                            },
+                        };
+
+                        // Send action back to store if callback produced one
+                        if let Some(action) = action {
+                            tracing::trace!("Query operation produced an action, sending to store with metadata");
+                            store.feed_back(action, metadata_clone, ledger_clone, &tracking_clone).await;
+                        } else {
+                            tracing::trace!("Query operation completed with no action");
+                        }
+                    });
+                },
+                Effect::Costed { cost, effect } => {
+                    // Cost was charged to the ledger when the reducer returned
+                    tracing::trace!(kind = %cost.kind, weight = cost.weight, "Executing Effect::Costed");
+                    self.execute_effect_internal(*effect, tracking, metadata, ledger, retry);
+                },
+                Effect::Retrying { retry, effect } => {
+                    tracing::trace!(max_attempts = retry.max_attempts, "Executing Effect::Retrying");
+                    self.execute_effect_internal(*effect, tracking, metadata, ledger, Some(retry.into()));
+                },
+                Effect::FutureWithCancel(start) => {
+                    tracing::trace!("Executing Effect::FutureWithCancel");
+                    let fut = start(self.cancellation.child_token());
+                    self.execute_effect_internal(Effect::Future(fut), tracking, metadata, ledger, retry);
+                },
+            }
+        }
+    }
+
+    impl<S, A, E, R> Clone for Store<S, A, E, R>
+    where
+        R: Reducer<State = S, Action = A, Environment = E> + Clone,
+        E: Clone,
+    {
+        fn clone(&self) -> Self {
+            Self {
+                state: Arc::clone(&self.state),
+                reducer: self.reducer.clone(),
+                environment: self.environment.clone(),
+                retry_policy: self.retry_policy.clone(),
+                operation_policies: Arc::clone(&self.operation_policies),
+                dlq: self.dlq.clone(),
+                shutdown: Arc::clone(&self.shutdown),
+                pending_effects: Arc::clone(&self.pending_effects),
+                action_broadcast: Arc::clone(&self.action_broadcast),
+                runtime_config: self.runtime_config.clone(),
+                rate_window: Arc::clone(&self.rate_window),
+                mailbox: self.mailbox.clone(),
+                budgets: self.budgets.clone(),
+                metrics: Arc::clone(&self.metrics),
+                degradation: self.degradation.clone(),
+                idempotency: self.idempotency.clone(),
+                state_differ: self.state_differ.clone(),
+                effect_limiter: self.effect_limiter.clone(),
+                tracker: Arc::clone(&self.tracker),
+                completion_action: self.completion_action.clone(),
+                runtime: self.runtime.clone(),
+                state_observers: Arc::clone(&self.state_observers),
+                snapshot: Arc::clone(&self.snapshot),
+                reductions: Arc::clone(&self.reductions),
+                keyed_delays: Arc::clone(&self.keyed_delays),
+                supervision: self.supervision,
+                checkpoint: self.checkpoint,
+                idle_handle: self.idle_handle.clone(),
+                cancellation: self.cancellation.clone(),
+                handle: self.handle.clone(),
+                clock: Arc::clone(&self.clock),
+                envelope_observers: Arc::clone(&self.envelope_observers),
+                action_label: self.action_label.clone(),
+                warn_if_reduce_exceeds: self.warn_if_reduce_exceeds,
+                warn_if_lock_wait_exceeds: self.warn_if_lock_wait_exceeds,
+                labels: Arc::clone(&self.labels),
+                shutdown_progress: Arc::clone(&self.shutdown_progress),
+            }
+        }
+    }
+}
+
+// Re-export for convenience
+pub use store::Store;
+
+// Test module
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use composable_rust_core::{effect::Effect, reducer::Reducer, smallvec, SmallVec};
+    use std::time::Duration;
+
+    // Test state
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    struct TestState {
+        value: i32,
+    }
+
+    // Test action
+    #[derive(Debug, Clone)]
+    enum TestAction {
+        Increment,
+        Decrement,
+        NoOp,
+        ProduceEffect,
+        ProduceDelayedAction,
+        ProduceChainedEffect,
+        ProduceKeyedDelay,
+        ProduceParallelEffects,
+        ProduceSequentialEffects,
+        ProducePanickingEffect,
+    }
+
+    // Test environment
+    #[derive(Debug, Clone)]
+    struct TestEnv;
+
+    // Test reducer
+    #[derive(Debug, Clone)]
+    struct TestReducer;
+
+    impl Reducer for TestReducer {
+        type State = TestState;
+        type Action = TestAction;
+        type Environment = TestEnv;
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            _env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            match action {
+                TestAction::Increment => {
+                    state.value += 1;
+                    smallvec![Effect::None]
+                },
+                TestAction::Decrement => {
+                    state.value -= 1;
+                    smallvec![Effect::None]
+                },
+                TestAction::NoOp => smallvec![Effect::None],
+                TestAction::ProduceEffect => {
+                    // Return an effect that produces another action
+                    smallvec![Effect::Future(Box::pin(async {
+                        Some(TestAction::Increment)
+                    }))]
+                },
+                TestAction::ProduceDelayedAction => {
+                    // Return a delayed effect
+                    smallvec![Effect::Delay {
+                        duration: Duration::from_millis(10),
+                        action: Box::new(TestAction::Increment),
+                    }]
+                },
+                TestAction::ProduceChainedEffect => {
+                    // Feed back an action that has a delayed effect of its own
+                    smallvec![Effect::Future(Box::pin(async {
+                        Some(TestAction::ProduceDelayedAction)
+                    }))]
+                },
+                TestAction::ProduceKeyedDelay => {
+                    // Re-arm the same keyed timer
+                    smallvec![Effect::DelayKeyed {
+                        key: "tick".into(),
+                        duration: Duration::from_millis(10),
+                        action: Box::new(TestAction::Increment),
+                    }]
+                },
+                TestAction::ProduceParallelEffects => {
+                    // Return parallel effects that each produce an increment
+                    smallvec![Effect::Parallel(vec![
+                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
+                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
+                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
+                    ])]
+                },
+                TestAction::ProduceSequentialEffects => {
+                    // Return sequential effects: increment, increment, decrement
+                    smallvec![Effect::Sequential(vec![
+                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
+                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
+                        Effect::Future(Box::pin(async { Some(TestAction::Decrement) })),
+                    ])]
+                },
+                TestAction::ProducePanickingEffect => {
+                    // Return an effect that will panic when executed
+                    #[allow(clippy::panic)] // Intentional panic for testing error handling
+                    {
+                        smallvec![Effect::Future(Box::pin(async {
+                            panic!("Intentional panic in effect for testing");
+                        }))]
+                    }
+                },
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_creation() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 0);
+    }
+
+    #[tokio::test]
+    async fn test_send_action() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        let _ = store.send(TestAction::Increment).await;
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 1);
+    }
+
+    #[tokio::test]
+    async fn test_multiple_actions() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        let _ = store.send(TestAction::Increment).await;
+        let _ = store.send(TestAction::Increment).await;
+        let _ = store.send(TestAction::Decrement).await;
+
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 1);
+    }
+
+    #[tokio::test]
+    async fn test_effect_none() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        let _ = store.send(TestAction::NoOp).await;
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 0);
+    }
+
+    #[tokio::test]
+    async fn test_effect_future() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        // Send action that produces an effect
+        let _ = store.send(TestAction::ProduceEffect).await;
+
+        // Give the spawned task time to complete
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // The effect should have produced an Increment action
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 1);
+    }
+
+    #[tokio::test]
+    async fn test_effect_delay() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        // Send action that produces a delayed effect
+        let _ = store.send(TestAction::ProduceDelayedAction).await;
+
+        // Value should still be 0 immediately
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 0);
+
+        // Wait for delay to complete
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Now value should be 1
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_effect_delay_keyed_replaces_pending_delay() {
+        let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+
+        let mut replaced = store.send(TestAction::ProduceKeyedDelay).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let mut latest = store.send(TestAction::ProduceKeyedDelay).await.unwrap();
+
+        // The replaced timer completes without firing
+        replaced.wait().await;
+        assert_eq!(store.state(|s| s.value).await, 0);
+
+        latest.wait().await;
+        assert_eq!(store.state(|s| s.value).await, 1);
+
+        // Once fired, the key can be scheduled again
+        store.send(TestAction::ProduceKeyedDelay).await.unwrap().wait().await;
+        assert_eq!(store.state(|s| s.value).await, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_send_cascading_waits_for_feedback_effects() {
+        // A direct handle settles once the feedback action is reduced
+        let direct = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+        direct.send(TestAction::ProduceChainedEffect).await.unwrap().wait().await;
+        assert_eq!(direct.state(|s| s.value).await, 0);
+
+        // A cascading handle also waits for the feedback action's delay
+        let cascading = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+        cascading.send_cascading(TestAction::ProduceChainedEffect).await.unwrap().wait().await;
+        assert_eq!(cascading.state(|s| s.value).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_tracks_every_action_under_one_handle() {
+        let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+
+        let mut handle = store
+            .send_batch(vec![
+                TestAction::ProduceEffect,
+                TestAction::Decrement,
+                TestAction::ProduceEffect,
+                TestAction::NoOp,
+            ])
+            .await
+            .unwrap();
+        let outcome = handle.wait().await;
+
+        // Both feedback increments were awaited by the single handle
+        assert_eq!(outcome.completed, 2);
+        assert_eq!(store.state(|s| s.value).await, 1);
+
+        let mut empty = store.send_batch(Vec::new()).await.unwrap();
+        assert_eq!(empty.wait().await, EffectOutcome::default());
+    }
+
+    #[tokio::test]
+    async fn test_effect_parallel() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        // Send action that produces parallel effects
+        let _ = store.send(TestAction::ProduceParallelEffects).await;
+
+        // Give the spawned tasks time to complete
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // All three increments should have completed
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 3);
+    }
+
+    #[tokio::test]
+    async fn test_effect_sequential() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        // Send action that produces sequential effects
+        let _ = store.send(TestAction::ProduceSequentialEffects).await;
+
+        // Give the spawned tasks time to complete
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Net result: +1 +1 -1 = 1
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_effect_sequential_waits_for_feedback_effects() {
+        #[derive(Debug, Clone)]
+        enum StepAction {
+            Start,
+            Schedule(&'static str),
+            Record(&'static str),
+        }
+
+        #[derive(Clone)]
+        struct StepReducer;
+
+        impl Reducer for StepReducer {
+            type State = Vec<&'static str>;
+            type Action = StepAction;
+            type Environment = TestEnv;
+
+            fn reduce(
+                &self,
+                log: &mut Self::State,
+                action: Self::Action,
+                _env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                match action {
+                    StepAction::Start => smallvec![Effect::Sequential(vec![
+                        Effect::Future(Box::pin(async { Some(StepAction::Schedule("first")) })),
+                        Effect::Future(Box::pin(async { Some(StepAction::Record("second")) })),
+                    ])],
+                    // The first step only settles once this delay has fed back
+                    StepAction::Schedule(entry) => smallvec![Effect::Delay {
+                        duration: Duration::from_millis(50),
+                        action: Box::new(StepAction::Record(entry)),
+                    }],
+                    StepAction::Record(entry) => {
+                        log.push(entry);
+                        smallvec![Effect::None]
+                    },
+                }
+            }
+        }
+
+        let store = Store::new(Vec::new(), StepReducer, TestEnv);
+        store.send(StepAction::Start).await.unwrap().wait().await;
+
+        assert_eq!(store.state(Clone::clone).await, ["first", "second"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_feedback_actions_stay_in_sender_span() {
+        use tracing::Instrument;
+        use tracing_subscriber::registry::{LookupSpan, Registry};
+
+        /// Name of the outermost span around the reducer
+        fn root_span() -> Option<&'static str> {
+            let id = tracing::Span::current().id()?;
+            tracing::dispatcher::get_default(|dispatch| {
+                let registry = dispatch.downcast_ref::<Registry>()?;
+                registry.span(&id)?.scope().last().map(|span| span.name())
+            })
+        }
+
+        #[derive(Clone)]
+        struct SpanReducer;
+
+        impl Reducer for SpanReducer {
+            type State = Vec<Option<&'static str>>;
+            type Action = TestAction;
+            type Environment = TestEnv;
+
+            fn reduce(
+                &self,
+                roots: &mut Self::State,
+                action: Self::Action,
+                env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                roots.push(root_span());
+                let mut state = TestState { value: 0 };
+                TestReducer.reduce(&mut state, action, env)
+            }
+        }
+
+        let _subscriber = tracing::subscriber::set_default(Registry::default());
+        let store = Store::new(Vec::new(), SpanReducer, TestEnv);
+        store
+            .send_cascading(TestAction::ProduceChainedEffect)
+            .instrument(tracing::info_span!("workflow"))
+            .await
+            .unwrap()
+            .wait()
+            .await;
+
+        // ProduceChainedEffect -> ProduceDelayedAction -> Increment
+        assert_eq!(store.state(Clone::clone).await, [Some("workflow"); 3]);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)] // Tests are allowed to panic on failures
+    async fn test_concurrent_sends() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        // Send multiple actions concurrently
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let store = store.clone();
+                tokio::spawn(async move {
+                    let _ = store.send(TestAction::Increment).await;
+                })
+            })
+            .collect();
+
+        // Wait for all to complete
+        for handle in handles {
+            if let Err(e) = handle.await {
+                panic!("concurrent send task panicked: {e}");
+            }
+        }
+
+        // All increments should have been applied
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 10);
+    }
+
+    #[tokio::test]
+    async fn test_state_read_during_execution() {
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        let _ = store.send(TestAction::Increment).await;
+
+        // Reading state should work while effects might be executing
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 1);
+    }
+
+    #[tokio::test]
+    async fn test_store_clone() {
+        let state = TestState { value: 0 };
+        let store1 = Store::new(state, TestReducer, TestEnv);
+        let store2 = store1.clone();
+
+        // Both stores should share the same state
+        let _ = store1.send(TestAction::Increment).await;
+        let value2 = store2.state(|s| s.value).await;
+        assert_eq!(value2, 1);
+
+        let _ = store2.send(TestAction::Increment).await;
+        let value1 = store1.state(|s| s.value).await;
+        assert_eq!(value1, 2);
+    }
+
+    #[tokio::test]
+    async fn test_effect_panic_isolation() -> Result<(), StoreError> {
+        // Test that a panic in an effect doesn't crash the Store
+        // This verifies our error handling strategy: effects fail gracefully
+        let state = TestState { value: 0 };
+        let store = Store::new(state, TestReducer, TestEnv);
+
+        // This action produces an effect that will panic
+        let mut handle = store.send(TestAction::ProducePanickingEffect).await?;
+
+        // Wait for the effect to complete (which includes the panic)
+        // The effect will panic, but it's isolated in the spawned task
+        handle.wait().await;
+
+        // Small delay to ensure the panicking task has finished
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Store should still be functional after effect panic
+        let _ = store.send(TestAction::Increment).await;
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 1);
+
+        // Can send multiple actions after panic
+        let _ = store.send(TestAction::Increment).await;
+        let value = store.state(|s| s.value).await;
+        assert_eq!(value, 2);
+
+        Ok(())
+    }
+
+    // EventStore effect tests
+    mod event_store_tests {
+        use super::*;
+        use composable_rust_core::effect::{Effect, EffectRetry, EventStoreOperation};
+        use composable_rust_core::effect_builders::EventStoreEffect;
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::{
+            BatchAppend, BatchAppendResults, EventStore, EventStoreError, Pagination, StreamMetadata,
+        };
+        use composable_rust_core::stream::{StreamId, Version};
+        use composable_rust_core::{smallvec, SmallVec};
+        use std::sync::Arc;
+
+        // Test action for EventStore effects
+        #[derive(Debug, Clone)]
+        enum EventStoreAction {
+            AppendEvents {
+                stream_id: String,
+                events: Vec<String>,
+            },
+            EventsAppended {
+                version: u64,
+            },
+            AppendFailed {
+                error: String,
+            },
+            LoadEvents {
+                stream_id: String,
+            },
+            EventsLoaded {
+                count: usize,
+            },
+            LoadFailed {
+                error: String,
+            },
+            SaveSnapshot {
+                stream_id: String,
+                version: u64,
+            },
+            SnapshotSaved,
+            LoadSnapshot {
+                stream_id: String,
+            },
+            SnapshotLoaded {
+                found: bool,
+            },
+        }
+
+        #[tokio::test]
+        async fn test_hydrate_from_events_replays_without_effects() {
+            use composable_rust_testing::mocks::InMemoryEventStore;
+
+            let event_store = InMemoryEventStore::new();
+            let events = ["Increment", "Increment", "ProduceEffect", "Renamed", "Decrement", "Increment"]
+                .map(|event_type| SerializedEvent::new(event_type.to_string(), vec![], None));
+            event_store
+                .append_events(StreamId::new("counter-1"), None, events.to_vec())
+                .await
+                .unwrap();
+
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+            let replayed = store
+                .hydrate_from_events(&event_store, StreamId::new("counter-1"), |event| {
+                    match event.event_type.as_str() {
+                        "Increment" => Some(TestAction::Increment),
+                        "Decrement" => Some(TestAction::Decrement),
+                        "ProduceEffect" => Some(TestAction::ProduceEffect),
+                        _ => None,
+                    }
+                })
+                .await
+                .unwrap();
+
+            assert_eq!(replayed, 5);
+            // ProduceEffect's feedback Increment was not run
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            assert_eq!(store.state(|s| s.value).await, 2);
+        }
+
+        /// `(is_replay, correlation_id)` of a reduction
+        type SeenContext = (bool, Option<String>);
+
+        /// Records the context of every reduction
+        #[derive(Clone, Default)]
+        struct ContextRecorder(Arc<std::sync::Mutex<Vec<SeenContext>>>);
+
+        impl Reducer for ContextRecorder {
+            type State = TestState;
+            type Action = TestAction;
+            type Environment = TestEnv;
+
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                TestReducer.reduce(state, action, env)
+            }
+
+            fn reduce_with_context(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                env: &Self::Environment,
+                context: &ReduceContext,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                let correlation_id = context.metadata.as_ref().and_then(|m| m.correlation_id.clone());
+                self.0.lock().unwrap().push((context.is_replay, correlation_id));
+                self.reduce(state, action, env)
+            }
+        }
+
+        #[tokio::test]
+        async fn test_reduce_context_marks_replay_and_carries_metadata() {
+            use composable_rust_core::event::EventMetadata;
+            use composable_rust_testing::mocks::InMemoryEventStore;
+
+            let event_store = InMemoryEventStore::new();
+            event_store
+                .append_events(
+                    StreamId::new("counter-1"),
+                    None,
+                    vec![SerializedEvent::new("Increment".to_string(), vec![], None)],
+                )
+                .await
+                .unwrap();
+
+            let recorder = ContextRecorder::default();
+            let store = Store::new(TestState { value: 0 }, recorder.clone(), TestEnv);
+            store
+                .hydrate_from_events(&event_store, StreamId::new("counter-1"), |_| Some(TestAction::Increment))
+                .await
+                .unwrap();
+            let metadata = EventMetadata {
+                correlation_id: Some("req-1".to_string()),
+                ..EventMetadata::new()
+            };
+            store.send_with_metadata(TestAction::Increment, Some(metadata)).await.unwrap();
+            store.send(TestAction::Increment).await.unwrap();
+
+            assert_eq!(
+                *recorder.0.lock().unwrap(),
+                [(true, None), (false, Some("req-1".to_string())), (false, None)]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_hydrate_from_events_reports_load_failure() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+            let result = store
+                .hydrate_from_events(&UnavailableEventStore::default(), StreamId::new("counter-1"), |_| {
+                    Some(TestAction::Increment)
+                })
+                .await;
+
+            assert!(matches!(result, Err(StoreError::ReplayFailed(EventStoreError::DatabaseError(_)))));
+            assert_eq!(store.state(|s| s.value).await, 0);
+        }
+
+        // Test state for EventStore
+        #[derive(Debug, Clone)]
+        struct EventStoreState {
+            last_version: Option<u64>,
+            event_count: usize,
+            snapshot_saved: bool,
+            snapshot_loaded: bool,
+            error: Option<String>,
+        }
+
+        // Test environment with EventStore
+        #[derive(Clone)]
+        struct EventStoreEnv {
+            event_store: Arc<dyn EventStore>,
+        }
+
+        // Test reducer for EventStore
+        #[derive(Clone)]
+        struct EventStoreReducer;
+
+        impl Reducer for EventStoreReducer {
+            type State = EventStoreState;
+            type Action = EventStoreAction;
+            type Environment = EventStoreEnv;
+
+            #[allow(clippy::too_many_lines)] // One arm per event store operation
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                match action {
+                    EventStoreAction::AppendEvents { stream_id, events } => {
+                        let serialized_events: Vec<SerializedEvent> = events
+                            .into_iter()
+                            .map(|data| {
+                                SerializedEvent::new(
+                                    "TestEvent.v1".to_string(),
+                                    data.into_bytes(),
+                                    None,
+                                )
+                            })
+                            .collect();
+
+                        smallvec![Effect::EventStore(EventStoreOperation::AppendEvents {
+                            event_store: Arc::clone(&env.event_store),
+                            stream_id: StreamId::new(&stream_id),
+                            expected_version: state.last_version.map(Version::new),
+                            events: serialized_events,
+                            metadata: None,
+                            idempotency_key: None,
+                            on_success: Box::new(|version| {
+                                Some(EventStoreAction::EventsAppended {
+                                    version: version.value(),
+                                })
+                            }),
+                            on_error: Box::new(|error| {
+                                Some(EventStoreAction::AppendFailed {
+                                    error: error.to_string(),
+                                })
+                            }),
+                        })]
+                    },
+                    EventStoreAction::EventsAppended { version } => {
+                        state.last_version = Some(version);
+                        smallvec![Effect::None]
+                    },
+                    EventStoreAction::AppendFailed { error }
+                    | EventStoreAction::LoadFailed { error } => {
+                        state.error = Some(error);
+                        smallvec![Effect::None]
+                    },
+                    EventStoreAction::LoadEvents { stream_id } => {
+                        smallvec![Effect::EventStore(EventStoreOperation::LoadEvents {
+                            event_store: Arc::clone(&env.event_store),
+                            stream_id: StreamId::new(&stream_id),
+                            from_version: None,
+                            on_success: Box::new(|events| {
+                                Some(EventStoreAction::EventsLoaded {
+                                    count: events.len(),
+                                })
+                            }),
+                            on_error: Box::new(|error| {
+                                Some(EventStoreAction::LoadFailed {
+                                    error: error.to_string(),
+                                })
+                            }),
+                        })]
+                    },
+                    EventStoreAction::EventsLoaded { count } => {
+                        state.event_count = count;
+                        smallvec![Effect::None]
+                    },
+                    EventStoreAction::SaveSnapshot { stream_id, version } => {
+                        let state_bytes = vec![1, 2, 3, 4]; // Mock state data
+                        smallvec![Effect::EventStore(EventStoreOperation::SaveSnapshot {
+                            event_store: Arc::clone(&env.event_store),
+                            stream_id: StreamId::new(&stream_id),
+                            version: Version::new(version),
+                            state: state_bytes,
+                            on_success: Box::new(|()| Some(EventStoreAction::SnapshotSaved)),
+                            on_error: Box::new(|error| {
+                                Some(EventStoreAction::AppendFailed {
+                                    error: error.to_string(),
+                                })
+                            }),
+                        })]
+                    },
+                    EventStoreAction::SnapshotSaved => {
+                        state.snapshot_saved = true;
+                        smallvec![Effect::None]
+                    },
+                    EventStoreAction::LoadSnapshot { stream_id } => {
+                        smallvec![Effect::EventStore(EventStoreOperation::LoadSnapshot {
+                            event_store: Arc::clone(&env.event_store),
+                            stream_id: StreamId::new(&stream_id),
+                            on_success: Box::new(|snapshot| {
+                                Some(EventStoreAction::SnapshotLoaded {
+                                    found: snapshot.is_some(),
+                                })
+                            }),
+                            on_error: Box::new(|error| {
+                                Some(EventStoreAction::LoadFailed {
+                                    error: error.to_string(),
+                                })
+                            }),
+                        })]
+                    },
+                    EventStoreAction::SnapshotLoaded { found } => {
+                        state.snapshot_loaded = found;
+                        smallvec![Effect::None]
+                    },
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn test_eventstore_append_success() -> Result<(), StoreError> {
+            use composable_rust_testing::mocks::InMemoryEventStore;
+
+            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
+            let env = EventStoreEnv {
+                event_store: Arc::clone(&event_store),
+            };
+            let state = EventStoreState {
+                last_version: None,
+                event_count: 0,
+                snapshot_saved: false,
+                snapshot_loaded: false,
+                error: None,
+            };
+
+            let store = Store::new(state, EventStoreReducer, env);
+
+            // Append events
+            let mut handle = store
+                .send(EventStoreAction::AppendEvents {
+                    stream_id: "test-stream".to_string(),
+                    events: vec!["event1".to_string(), "event2".to_string()],
+                })
+                .await?;
+
+            handle.wait().await;
+
+            // Check state was updated with version
+            let last_version = store.state(|s| s.last_version).await;
+            assert_eq!(last_version, Some(1)); // 2 events, version 0-1, returns last = 1
+
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn test_appended_events_get_ids_and_causation() -> Result<(), StoreError> {
+            use composable_rust_core::event::EventMetadata;
+            use composable_rust_testing::mocks::InMemoryEventStore;
+
+            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
+            let env = EventStoreEnv {
+                event_store: Arc::clone(&event_store),
+            };
+            let state = EventStoreState {
+                last_version: None,
+                event_count: 0,
+                snapshot_saved: false,
+                snapshot_loaded: false,
+                error: None,
+            };
+            let store = Store::new(state, EventStoreReducer, env);
+
+            let metadata = EventMetadata::with_correlation_id("corr-1").tenant_id("acme");
+            let mut handle = store
+                .send_with_metadata(
+                    EventStoreAction::AppendEvents {
+                        stream_id: "caused".to_string(),
+                        events: vec!["a".to_string(), "b".to_string()],
+                    },
+                    Some(metadata),
+                )
+                .await?;
+            handle.wait().await;
+
+            #[allow(clippy::unwrap_used)] // Tests can unwrap
+            let events = event_store
+                .load_events(StreamId::new("caused"), None)
+                .await
+                .unwrap();
+            let metadata: Vec<EventMetadata> = events.into_iter().filter_map(|e| e.metadata).collect();
+            assert_eq!(metadata.len(), 2);
+
+            // Both events were caused by the same (generated) action id
+            let causation = metadata[0].causation_id.clone();
+            assert!(causation.is_some());
+            assert_eq!(metadata[1].causation_id, causation);
+
+            for event in &metadata {
+                assert_eq!(event.correlation_id.as_deref(), Some("corr-1"));
+                assert_eq!(event.tenant_id.as_deref(), Some("acme"));
+                assert!(event.event_id.is_some());
+            }
+            assert_ne!(metadata[0].event_id, metadata[1].event_id);
+
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn test_eventstore_append_concurrency_conflict() -> Result<(), StoreError> {
+            use composable_rust_testing::mocks::InMemoryEventStore;
+
+            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
+            let env = EventStoreEnv {
+                event_store: Arc::clone(&event_store),
+            };
+
+            // Pre-populate event store with some events
+            let stream_id = StreamId::new("test-stream");
+            let events = vec![SerializedEvent::new(
+                "TestEvent.v1".to_string(),
+                b"data".to_vec(),
+                None,
+            )];
+            event_store
+                .append_events(stream_id.clone(), Some(Version::new(0)), events)
+                .await
+                .ok();
+
+            // Create store with wrong expected version
+            let state = EventStoreState {
+                last_version: Some(5), // Wrong version, actual is 0
+                event_count: 0,
+                snapshot_saved: false,
+                snapshot_loaded: false,
+                error: None,
+            };
+
+            let store = Store::new(state, EventStoreReducer, env);
+
+            // Try to append with wrong version
+            let mut handle = store
+                .send(EventStoreAction::AppendEvents {
+                    stream_id: "test-stream".to_string(),
+                    events: vec!["event".to_string()],
+                })
+                .await?;
+
+            handle.wait().await;
+
+            // Check error was captured
+            let error = store.state(|s| s.error.clone()).await;
+            assert!(error.is_some());
+            #[allow(clippy::unwrap_used)] // Panics: Test verified error is Some above
+            {
+                assert!(error.unwrap().contains("Concurrency"));
+            }
+            // Conflicts are not retried, so nothing reaches the DLQ
+            assert!(store.dlq().is_empty());
+
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn test_eventstore_load_events() -> Result<(), StoreError> {
+            use composable_rust_testing::mocks::InMemoryEventStore;
+
+            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
+            let env = EventStoreEnv {
+                event_store: Arc::clone(&event_store),
+            };
+
+            // Pre-populate with events
+            let stream_id = StreamId::new("test-stream");
+            let events = vec![
+                SerializedEvent::new("TestEvent.v1".to_string(), b"event1".to_vec(), None),
+                SerializedEvent::new("TestEvent.v1".to_string(), b"event2".to_vec(), None),
+                SerializedEvent::new("TestEvent.v1".to_string(), b"event3".to_vec(), None),
+            ];
+            event_store
+                .append_events(stream_id, Some(Version::new(0)), events)
+                .await
+                .ok();
+
+            let state = EventStoreState {
+                last_version: None,
+                event_count: 0,
+                snapshot_saved: false,
+                snapshot_loaded: false,
+                error: None,
+            };
+
+            let store = Store::new(state, EventStoreReducer, env);
+
+            // Load events
+            let mut handle = store
+                .send(EventStoreAction::LoadEvents {
+                    stream_id: "test-stream".to_string(),
+                })
+                .await?;
+
+            handle.wait().await;
+
+            // Check count was updated
+            let count = store.state(|s| s.event_count).await;
+            assert_eq!(count, 3);
+
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn test_eventstore_snapshot_roundtrip() -> Result<(), StoreError> {
+            use composable_rust_testing::mocks::InMemoryEventStore;
+
+            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
+            let env = EventStoreEnv {
+                event_store: Arc::clone(&event_store),
+            };
+            let state = EventStoreState {
+                last_version: None,
+                event_count: 0,
+                snapshot_saved: false,
+                snapshot_loaded: false,
+                error: None,
+            };
+
+            let store = Store::new(state, EventStoreReducer, env);
+
+            // Save snapshot
+            let mut handle = store
+                .send(EventStoreAction::SaveSnapshot {
+                    stream_id: "test-stream".to_string(),
+                    version: 10,
+                })
+                .await?;
+
+            handle.wait().await;
+
+            // Check snapshot was saved
+            let saved = store.state(|s| s.snapshot_saved).await;
+            assert!(saved);
+
+            // Load snapshot
+            let mut handle = store
+                .send(EventStoreAction::LoadSnapshot {
+                    stream_id: "test-stream".to_string(),
+                })
+                .await?;
+
+            handle.wait().await;
+
+            // Check snapshot was loaded
+            let loaded = store.state(|s| s.snapshot_loaded).await;
+            assert!(loaded);
+
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn test_eventstore_parallel_operations() -> Result<(), StoreError> {
+            use composable_rust_testing::mocks::InMemoryEventStore;
+
+            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
+            let env = EventStoreEnv {
+                event_store: Arc::clone(&event_store),
+            };
+            let state = EventStoreState {
+                last_version: None,
+                event_count: 0,
+                snapshot_saved: false,
+                snapshot_loaded: false,
+                error: None,
+            };
+
+            let store = Store::new(state, EventStoreReducer, env);
+
+            // Append to stream1
+            let mut h1 = store
+                .send(EventStoreAction::AppendEvents {
+                    stream_id: "stream-1".to_string(),
+                    events: vec!["event1".to_string()],
+                })
+                .await?;
+
+            // Append to stream2 (different stream, can run concurrently)
+            let mut h2 = store
+                .send(EventStoreAction::AppendEvents {
+                    stream_id: "stream-2".to_string(),
+                    events: vec!["event2".to_string()],
+                })
+                .await?;
+
+            // Wait for both
+            h1.wait().await;
+            h2.wait().await;
+
+            // Both should have succeeded (last_version reflects last operation)
+            let last_version = store.state(|s| s.last_version).await;
+            assert!(last_version.is_some());
+
+            Ok(())
+        }
+
+        // Reducer for testing parallel EventStore effects
+        #[derive(Clone)]
+        struct ParallelTestReducer;
+
+        impl Reducer for ParallelTestReducer {
+            type State = EventStoreState;
+            type Action = EventStoreAction;
+            type Environment = EventStoreEnv;
+
+            fn reduce(
+                &self,
+                _state: &mut Self::State,
+                _action: Self::Action,
+                env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                // Create parallel effects on each call
+                smallvec![Effect::Parallel(vec![
+                    Effect::EventStore(EventStoreOperation::AppendEvents {
+                        event_store: Arc::clone(&env.event_store),
+                        stream_id: StreamId::new("stream-1"),
+                        expected_version: Some(Version::new(0)),
+                        events: vec![SerializedEvent::new(
+                            "Test.v1".to_string(),
+                            b"data1".to_vec(),
+                            None,
+                        )],
+                        metadata: None,
+                        idempotency_key: None,
+                        on_success: Box::new(|_| None), // No feedback action
+                        on_error: Box::new(|_| None),
+                    }),
+                    Effect::EventStore(EventStoreOperation::AppendEvents {
+                        event_store: Arc::clone(&env.event_store),
+                        stream_id: StreamId::new("stream-2"),
+                        expected_version: Some(Version::new(0)),
+                        events: vec![SerializedEvent::new(
+                            "Test.v1".to_string(),
+                            b"data2".to_vec(),
+                            None,
+                        )],
+                        metadata: None,
+                        idempotency_key: None,
+                        on_success: Box::new(|_| None),
+                        on_error: Box::new(|_| None),
+                    }),
+                ])]
+            }
+        }
+
+        #[tokio::test]
+        async fn test_eventstore_effect_in_parallel_composition() -> Result<(), StoreError> {
+            use composable_rust_testing::mocks::InMemoryEventStore;
+
+            // Test that EventStore effects work inside Parallel effect composition
+            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
+            let env = EventStoreEnv {
+                event_store: Arc::clone(&event_store),
+            };
+
+            let test_store = Store::new(
+                EventStoreState {
+                    last_version: None,
+                    event_count: 0,
+                    snapshot_saved: false,
+                    snapshot_loaded: false,
+                    error: None,
+                },
+                ParallelTestReducer,
+                env.clone(),
+            );
+
+            // Trigger the parallel effect
+            let mut handle = test_store
+                .send(EventStoreAction::AppendEvents {
+                    stream_id: "dummy".to_string(),
+                    events: vec![],
+                })
+                .await?;
+
+            handle.wait().await;
+
+            // Verify both streams got events
+            let events1 = env
+                .event_store
+                .load_events(StreamId::new("stream-1"), None)
+                .await
+                .ok();
+            let events2 = env
+                .event_store
+                .load_events(StreamId::new("stream-2"), None)
+                .await
+                .ok();
+
+            #[allow(clippy::unwrap_used)] // Panics: Test verified events are Some above
+            {
+                assert!(events1.is_some());
+                assert!(events2.is_some());
+                assert_eq!(events1.unwrap().len(), 1);
+                assert_eq!(events2.unwrap().len(), 1);
+            }
+
+            Ok(())
+        }
+
+        /// Boxed future returned by [`EventStore`] methods
+        type StoreFuture<'a, T> =
+            std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, EventStoreError>> + Send + 'a>>;
+
+        /// Event store whose database is always down
+        #[derive(Default)]
+        struct UnavailableEventStore {
+            attempts: AtomicUsize,
+        }
+
+        impl UnavailableEventStore {
+            fn fail<T: Send + 'static>(&self) -> StoreFuture<'_, T> {
+                self.attempts.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async { Err(EventStoreError::DatabaseError("connection refused".to_string())) })
+            }
+        }
+
+        impl EventStore for UnavailableEventStore {
+            fn append_events(
+                &self,
+                _stream_id: StreamId,
+                _expected_version: Option<Version>,
+                _events: Vec<SerializedEvent>,
+            ) -> StoreFuture<'_, Version> {
+                self.fail()
+            }
+
+            fn load_events(
+                &self,
+                _stream_id: StreamId,
+                _from_version: Option<Version>,
+            ) -> StoreFuture<'_, Vec<SerializedEvent>> {
+                self.fail()
+            }
+
+            fn save_snapshot(
+                &self,
+                _stream_id: StreamId,
+                _version: Version,
+                _state: Vec<u8>,
+            ) -> StoreFuture<'_, ()> {
+                self.fail()
+            }
+
+            fn load_snapshot(&self, _stream_id: StreamId) -> StoreFuture<'_, Option<(Version, Vec<u8>)>> {
+                self.fail()
+            }
+
+            fn append_batch(&self, _batch: Vec<BatchAppend>) -> StoreFuture<'_, BatchAppendResults> {
+                self.fail()
+            }
+
+            fn delete_stream(&self, _stream_id: StreamId) -> StoreFuture<'_, ()> {
+                self.fail()
+            }
+
+            fn stream_metadata(&self, _stream_id: StreamId) -> StoreFuture<'_, Option<StreamMetadata>> {
+                self.fail()
+            }
+
+            fn list_streams(
+                &self,
+                _prefix: Option<String>,
+                _page: Pagination,
+            ) -> StoreFuture<'_, Vec<StreamMetadata>> {
+                self.fail()
+            }
+        }
+
+        type EventStoreStore = Store<EventStoreState, EventStoreAction, EventStoreEnv, EventStoreReducer>;
+
+        /// Append once against an unavailable event store
+        async fn append_with(config: StoreConfig) -> (Arc<UnavailableEventStore>, EventStoreStore) {
+            let event_store = Arc::new(UnavailableEventStore::default());
+            let env = EventStoreEnv {
+                event_store: Arc::clone(&event_store) as Arc<dyn EventStore>,
+            };
+            let state = EventStoreState {
+                last_version: None,
+                event_count: 0,
+                snapshot_saved: false,
+                snapshot_loaded: false,
+                error: None,
+            };
+            let store = Store::with_config(state, EventStoreReducer, env, config);
+
+            let mut handle = store
+                .send(EventStoreAction::AppendEvents {
+                    stream_id: "test-stream".to_string(),
+                    events: vec!["event".to_string()],
+                })
+                .await
+                .unwrap();
+            handle.wait().await;
+
+            (event_store, store)
+        }
+
+        fn quick_retries(attempts: u32) -> RetryPolicy {
+            RetryPolicy::new()
+                .with_max_attempts(attempts)
+                .with_initial_delay(Duration::from_millis(1))
+        }
+
+        #[tokio::test]
+        async fn test_operation_policy_overrides_default() {
+            let config = StoreConfig::default()
+                .with_retry_policy(quick_retries(5))
+                .with_operation_policy("append_events", OperationPolicy::new(quick_retries(2)));
+
+            let (event_store, store) = append_with(config).await;
+
+            assert_eq!(event_store.attempts.load(Ordering::SeqCst), 2);
+            assert_eq!(store.dlq().len(), 1);
+            assert!(store.state(|s| s.error.is_some()).await);
+
+            let failure = store.dlq().peek().unwrap().payload;
+            assert_eq!(failure.operation, "append_events");
+            assert_eq!(failure.target.as_deref(), Some("test-stream"));
+            assert_eq!(failure.attempts, 2);
+            assert_eq!(failure.exhausted, Some(RetryExhausted::Attempts { attempts: 2 }));
+            assert!(matches!(failure.error, FailureError::EventStore(EventStoreError::DatabaseError(_))));
+            assert!(failure.is_retryable());
+            let events: Vec<SerializedEvent> = bincode::deserialize(&failure.payload.unwrap()).unwrap();
+            assert_eq!(events[0].data, b"event");
+        }
+
+        #[tokio::test]
+        async fn test_total_delay_cap_stops_retries() {
+            let config = StoreConfig::default()
+                .with_retry_policy(quick_retries(5).with_max_total_delay(Duration::ZERO));
+
+            let (event_store, store) = append_with(config).await;
+
+            assert_eq!(event_store.attempts.load(Ordering::SeqCst), 1);
+            let failure = store.dlq().peek().unwrap().payload;
+            assert!(matches!(
+                failure.exhausted,
+                Some(RetryExhausted::DeadlineExceeded { max_total_delay: Duration::ZERO, .. })
+            ));
+        }
+
+        #[tokio::test]
+        async fn test_operation_policy_without_dead_letter() {
+            let config = StoreConfig::default()
+                .with_operation_policy("load_events", OperationPolicy::new(quick_retries(9)))
+                .with_operation_policy(
+                    "append_events",
+                    OperationPolicy::new(quick_retries(3)).without_dead_letter(),
+                );
+
+            let (event_store, store) = append_with(config).await;
+
+            assert_eq!(event_store.attempts.load(Ordering::SeqCst), 3);
+            assert!(store.dlq().is_empty());
+        }
+
+        /// `EventStoreReducer` with every effect wrapped in `Effect::with_retry`
+        #[derive(Clone)]
+        struct RetryingReducer(EffectRetry);
+
+        impl Reducer for RetryingReducer {
+            type State = EventStoreState;
+            type Action = EventStoreAction;
+            type Environment = EventStoreEnv;
+
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                EventStoreReducer
+                    .reduce(state, action, env)
+                    .into_iter()
+                    .map(|effect| effect.with_retry(self.0))
+                    .collect()
+            }
+        }
+
+        /// Number of append attempts made when the effect retries with `retry`
+        async fn append_attempts(retry: EffectRetry, config: StoreConfig) -> usize {
+            let event_store = Arc::new(UnavailableEventStore::default());
+            let env = EventStoreEnv {
+                event_store: Arc::clone(&event_store) as Arc<dyn EventStore>,
+            };
+            let state = EventStoreState {
+                last_version: None,
+                event_count: 0,
+                snapshot_saved: false,
+                snapshot_loaded: false,
+                error: None,
+            };
+            let store = Store::with_config(state, RetryingReducer(retry), env, config);
+
+            let mut handle = store
+                .send(EventStoreAction::AppendEvents {
+                    stream_id: "test-stream".to_string(),
+                    events: vec!["event".to_string()],
+                })
+                .await
+                .unwrap();
+            handle.wait().await;
+
+            event_store.attempts.load(Ordering::SeqCst)
+        }
+
+        #[tokio::test]
+        async fn test_effect_retry_overrides_store_policies() {
+            let config = StoreConfig::default()
+                .with_retry_policy(quick_retries(5))
+                .with_operation_policy("append_events", OperationPolicy::new(quick_retries(2)));
+
+            assert_eq!(append_attempts(EffectRetry::never(), config.clone()).await, 1);
+            assert_eq!(append_attempts(quick_retries(7).into(), config).await, 7);
+        }
+
+        /// In-memory event store whose first keyed append commits, then
+        /// reports a timeout
+        #[derive(Default)]
+        struct CommitThenTimeout {
+            inner: composable_rust_testing::mocks::InMemoryEventStore,
+            timed_out: std::sync::atomic::AtomicBool,
+        }
+
+        impl EventStore for CommitThenTimeout {
+            fn append_events(
+                &self,
+                stream_id: StreamId,
+                expected_version: Option<Version>,
+                events: Vec<SerializedEvent>,
+            ) -> StoreFuture<'_, Version> {
+                self.inner.append_events(stream_id, expected_version, events)
+            }
+
+            fn append_events_idempotent(
+                &self,
+                stream_id: StreamId,
+                expected_version: Option<Version>,
+                events: Vec<SerializedEvent>,
+                idempotency_key: String,
+            ) -> StoreFuture<'_, Version> {
+                Box::pin(async move {
+                    let version = self
+                        .inner
+                        .append_events_idempotent(stream_id, expected_version, events, idempotency_key)
+                        .await?;
+                    if self.timed_out.swap(true, Ordering::SeqCst) {
+                        Ok(version)
+                    } else {
+                        Err(EventStoreError::DatabaseError("timeout after commit".to_string()))
+                    }
+                })
+            }
+
+            fn load_events(
+                &self,
+                stream_id: StreamId,
+                from_version: Option<Version>,
+            ) -> StoreFuture<'_, Vec<SerializedEvent>> {
+                self.inner.load_events(stream_id, from_version)
+            }
+
+            fn save_snapshot(
+                &self,
+                stream_id: StreamId,
+                version: Version,
+                state: Vec<u8>,
+            ) -> StoreFuture<'_, ()> {
+                self.inner.save_snapshot(stream_id, version, state)
+            }
+
+            fn load_snapshot(&self, stream_id: StreamId) -> StoreFuture<'_, Option<(Version, Vec<u8>)>> {
+                self.inner.load_snapshot(stream_id)
+            }
+
+            fn append_batch(&self, batch: Vec<BatchAppend>) -> StoreFuture<'_, BatchAppendResults> {
+                self.inner.append_batch(batch)
+            }
+
+            fn delete_stream(&self, stream_id: StreamId) -> StoreFuture<'_, ()> {
+                self.inner.delete_stream(stream_id)
+            }
+
+            fn stream_metadata(&self, stream_id: StreamId) -> StoreFuture<'_, Option<StreamMetadata>> {
+                self.inner.stream_metadata(stream_id)
+            }
+
+            fn list_streams(
+                &self,
+                prefix: Option<String>,
+                page: Pagination,
+            ) -> StoreFuture<'_, Vec<StreamMetadata>> {
+                self.inner.list_streams(prefix, page)
+            }
+        }
+
+        #[derive(Debug, Clone)]
+        enum PlaceAction {
+            Place,
+            Placed(Version),
+            Failed(String),
+        }
+
+        /// Appends the first event of a stream, keyed by the command
+        #[derive(Clone)]
+        struct KeyedAppendReducer {
+            events: Arc<dyn EventStore>,
+        }
+
+        impl Reducer for KeyedAppendReducer {
+            type State = Option<Result<Version, String>>;
+            type Action = PlaceAction;
+            type Environment = TestEnv;
+
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                _env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                match action {
+                    PlaceAction::Place => {
+                        let event = SerializedEvent::new("OrderPlaced.v1".to_string(), vec![], None);
+                        smallvec![
+                            EventStoreEffect::append(&self.events, "order-1", vec![event])
+                                .expected_version(Version::new(0))
+                                .idempotency_key("place-order-1")
+                                .on_success(|version| Some(PlaceAction::Placed(version)))
+                                .on_error(|error| Some(PlaceAction::Failed(error.to_string())))
+                                .into_effect()
+                        ]
+                    },
+                    PlaceAction::Placed(version) => {
+                        *state = Some(Ok(version));
+                        smallvec![Effect::None]
+                    },
+                    PlaceAction::Failed(error) => {
+                        *state = Some(Err(error));
+                        smallvec![Effect::None]
+                    },
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn test_retried_keyed_append_is_not_duplicated() {
+            let event_store = Arc::new(CommitThenTimeout::default());
+            let config = StoreConfig::default().with_retry_policy(quick_retries(3));
+            let store = Store::with_config(
+                None,
+                KeyedAppendReducer {
+                    events: Arc::clone(&event_store) as Arc<dyn EventStore>,
+                },
+                TestEnv,
+                config,
+            );
+
+            // Without the key, the retry would conflict with the committed event
+            store.send(PlaceAction::Place).await.unwrap().wait().await;
+
+            assert_eq!(store.state(Clone::clone).await, Some(Ok(Version::new(0))));
+            assert_eq!(event_store.inner.event_count(&StreamId::new("order-1")), 1);
+        }
+    }
+
+    /// Tests for `RetryPolicy`
+    mod retry_policy_tests {
+        use super::*;
+
+        #[test]
+        fn test_retry_policy_default() {
+            let policy = RetryPolicy::default();
+            assert_eq!(policy.max_attempts(), 5);
+            assert!(policy.should_retry(0));
+            assert!(policy.should_retry(4));
+            assert!(!policy.should_retry(5));
+        }
+
+        #[test]
+        fn test_retry_policy_builder() {
+            let policy = RetryPolicy::new()
+                .with_max_attempts(3)
+                .with_initial_delay(Duration::from_millis(100))
+                .with_max_delay(Duration::from_secs(10))
+                .with_backoff_multiplier(3.0);
+
+            assert_eq!(policy.max_attempts(), 3);
+            assert!(policy.should_retry(2));
+            assert!(!policy.should_retry(3));
+        }
+
+        #[test]
+        fn test_delay_increases_exponentially() {
+            let policy = RetryPolicy::new()
+                .with_initial_delay(Duration::from_secs(1))
+                .with_backoff_multiplier(2.0)
+                .with_max_delay(Duration::from_secs(100));
+
+            let delay0 = policy.delay_for_attempt(0);
+            let delay1 = policy.delay_for_attempt(1);
+            let delay2 = policy.delay_for_attempt(2);
+
+            // Delays should generally increase (accounting for jitter 0.5-1.0)
+            // delay0 ~= 1s * (0.5-1.0)
+            // delay1 ~= 2s * (0.5-1.0)
+            // delay2 ~= 4s * (0.5-1.0)
+            assert!(delay0.as_millis() >= 500 && delay0.as_millis() <= 1000);
+            assert!(delay1.as_millis() >= 1000 && delay1.as_millis() <= 2000);
+            assert!(delay2.as_millis() >= 2000 && delay2.as_millis() <= 4000);
+        }
+
+        #[test]
+        fn test_delay_caps_at_max() {
+            let policy = RetryPolicy::new()
+                .with_initial_delay(Duration::from_secs(1))
+                .with_backoff_multiplier(2.0)
+                .with_max_delay(Duration::from_secs(5));
+
+            // Attempt 10 would normally be 2^10 = 1024 seconds
+            // But should be capped at 5 seconds (plus jitter 0.5-1.0)
+            let delay = policy.delay_for_attempt(10);
+            assert!(delay.as_millis() >= 2500 && delay.as_millis() <= 5000);
+        }
+
+        #[test]
+        fn test_next_retry_reports_why_it_stops() {
+            let policy = RetryPolicy::new()
+                .with_max_attempts(3)
+                .with_initial_delay(Duration::from_secs(1))
+                .with_max_total_delay(Duration::from_secs(2));
+
+            let delay = policy.next_retry(1, Duration::ZERO).unwrap();
+            assert!(delay <= Duration::from_secs(1));
+            assert_eq!(
+                policy.next_retry(2, Duration::from_millis(1500)),
+                Err(RetryExhausted::DeadlineExceeded {
+                    elapsed: Duration::from_millis(1500),
+                    max_total_delay: Duration::from_secs(2),
+                })
+            );
+            assert_eq!(
+                policy.next_retry(3, Duration::ZERO),
+                Err(RetryExhausted::Attempts { attempts: 3 })
+            );
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn test_retry_budget_is_shared_and_refills() {
+            let budget = RetryBudget::new(2, 1.0);
+            let first = RetryPolicy::new().with_budget(budget.clone());
+            let second = RetryPolicy::new().with_budget(budget.clone());
+
+            assert!(first.next_retry(1, Duration::ZERO).is_ok());
+            assert!(second.next_retry(1, Duration::ZERO).is_ok());
+            assert_eq!(
+                first.next_retry(2, Duration::ZERO),
+                Err(RetryExhausted::BudgetExhausted)
+            );
+            assert_eq!(budget.available(), 0);
+
+            tokio::time::advance(Duration::from_secs(1)).await;
+            assert_eq!(budget.available(), 1);
+            assert!(second.next_retry(2, Duration::ZERO).is_ok());
+        }
+
+        #[test]
+        fn test_jitter_strategies() {
+            let base = RetryPolicy::new()
+                .with_initial_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(100));
+
+            let exact = base.clone().with_jitter(JitterStrategy::None);
+            assert_eq!(exact.delay_for_attempt(0), Duration::from_secs(1));
+            assert_eq!(exact.delay_for_attempt(3), Duration::from_secs(8));
+
+            let full = base.with_jitter(JitterStrategy::Full);
+            for attempt in 0..5 {
+                assert!(full.delay_for_attempt(attempt) <= Duration::from_secs(1 << attempt));
+            }
+        }
+
+        #[test]
+        fn test_seeded_jitter_is_reproducible() {
+            let schedule = |policy: &RetryPolicy| (0..5).map(|n| policy.delay_for_attempt(n)).collect::<Vec<_>>();
+            let policy = RetryPolicy::new().with_jitter_seed(42);
+
+            assert_eq!(schedule(&policy), schedule(&RetryPolicy::new().with_jitter_seed(42)));
+            assert_ne!(schedule(&policy), schedule(&RetryPolicy::new().with_jitter_seed(7)));
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn test_exact_retry_schedule_with_paused_time() {
+            let policy = RetryPolicy::new()
+                .with_max_attempts(4)
+                .with_initial_delay(Duration::from_millis(100))
+                .with_jitter(JitterStrategy::None);
+
+            let started = tokio::time::Instant::now();
+            let mut attempted_at = Vec::new();
+            loop {
+                attempted_at.push(started.elapsed());
+                let attempts = u32::try_from(attempted_at.len()).unwrap();
+                match policy.next_retry(attempts, started.elapsed()) {
+                    Ok(delay) => tokio::time::sleep(delay).await,
+                    Err(exhausted) => {
+                        assert_eq!(exhausted, RetryExhausted::Attempts { attempts: 4 });
+                        break;
+                    }
+                }
+            }
+
+            assert_eq!(attempted_at, [0, 100, 300, 700].map(Duration::from_millis));
+        }
+
+        #[test]
+        fn test_jitter_variation() {
+            let policy = RetryPolicy::new()
+                .with_initial_delay(Duration::from_secs(1))
+                .with_backoff_multiplier(2.0);
+
+            // Run multiple times to ensure jitter provides variation
+            let mut delays = Vec::new();
+            for _ in 0..10 {
+                delays.push(policy.delay_for_attempt(1));
+            }
+
+            // Should have variation due to jitter
+            // Not all delays should be exactly the same
+            let first = delays[0];
+            let has_variation = delays.iter().any(|d| d != &first);
+            assert!(has_variation, "Jitter should produce variation in delays");
+        }
+    }
+
+    mod circuit_breaker_tests {
+        use super::*;
+
+        #[test]
+        fn test_circuit_breaker_initial_state() {
+            let breaker = CircuitBreaker::new();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+        }
+
+        #[test]
+        fn test_circuit_breaker_opens_after_threshold() {
+            let breaker = CircuitBreaker::new().with_failure_threshold(3);
+
+            assert_eq!(breaker.state(), CircuitState::Closed);
+
+            // Record failures
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+        }
+
+        #[test]
+        fn test_circuit_breaker_resets_on_success() {
+            let breaker = CircuitBreaker::new().with_failure_threshold(3);
+
+            // Record some failures
+            breaker.record_failure();
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+
+            // Success resets the count
+            breaker.record_success();
+
+            // Should need 3 more failures to open
+            breaker.record_failure();
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+        }
+
+        #[tokio::test]
+        async fn test_circuit_breaker_half_open_after_timeout() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_threshold(2)
+                .with_timeout(Duration::from_millis(100));
+
+            // Open the circuit
+            breaker.record_failure();
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+
+            // Check should fail immediately
+            assert!(breaker.check().is_err());
+
+            // Wait for timeout
+            tokio::time::sleep(Duration::from_millis(150)).await;
+
+            // Check should transition to HalfOpen
+            assert!(breaker.check().is_ok());
+            assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        }
+
+        #[test]
+        fn test_circuit_breaker_closes_after_success_threshold() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_threshold(2)
+                .with_success_threshold(2);
+
+            // Open the circuit
+            breaker.record_failure();
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+
+            // Manually transition to HalfOpen for testing
+            breaker
+                .state
+                .store(CircuitState::HalfOpen as u8, Ordering::Release);
+
+            // Record successes
+            breaker.record_success();
+            assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+            breaker.record_success();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+        }
+
+        #[test]
+        fn test_circuit_breaker_reopens_on_half_open_failure() {
+            let breaker = CircuitBreaker::new().with_failure_threshold(2);
+
+            // Open the circuit
+            breaker.record_failure();
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+
+            // Manually transition to HalfOpen
+            breaker
+                .state
+                .store(CircuitState::HalfOpen as u8, Ordering::Release);
+
+            // Any failure in HalfOpen should reopen circuit
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+        }
+
+        #[tokio::test]
+        async fn test_circuit_breaker_call_success() {
+            let breaker = CircuitBreaker::new();
+
+            let result = breaker
+                .call(|| async { Ok::<i32, String>(42) })
+                .await;
+
+            assert!(result.is_ok());
+            #[allow(clippy::unwrap_used)] // Safe: just asserted is_ok()
+            {
+                assert_eq!(result.unwrap(), 42);
+            }
+            assert_eq!(breaker.state(), CircuitState::Closed);
+        }
+
+        #[tokio::test]
+        async fn test_circuit_breaker_call_opens_on_failures() {
+            let breaker = CircuitBreaker::new().with_failure_threshold(3);
+
+            // Fail 3 times
+            for _ in 0..3 {
+                let result = breaker
+                    .call(|| async { Err::<i32, String>("error".to_string()) })
+                    .await;
+                assert!(result.is_err());
+            }
+
+            assert_eq!(breaker.state(), CircuitState::Open);
+
+            // Next call should fail fast
+            let result = breaker
+                .call(|| async { Ok::<i32, String>(42) })
+                .await;
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(Either::Left(_))));
+        }
+
+        #[tokio::test]
+        async fn test_circuit_breaker_concurrent_access() {
+            use std::sync::Arc;
+
+            let breaker = Arc::new(CircuitBreaker::new().with_failure_threshold(5));
+
+            // Record failures concurrently
+            let mut handles = vec![];
+            for _ in 0..10 {
+                let breaker_clone = Arc::clone(&breaker);
+                handles.push(tokio::spawn(async move {
+                    breaker_clone.record_failure();
+                }));
+            }
+
+            #[allow(clippy::unwrap_used)] // Test code: tasks should not panic
+            for handle in handles {
+                handle.await.unwrap();
+            }
+
+            // Circuit should be open (10 failures, threshold is 5)
+            assert_eq!(breaker.state(), CircuitState::Open);
+        }
+
+        #[test]
+        fn test_circuit_breaker_builder() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_threshold(10)
+                .with_timeout(Duration::from_secs(30))
+                .with_success_threshold(3);
+
+            // Verify by opening the circuit
+            for _ in 0..10 {
+                breaker.record_failure();
+            }
+            assert_eq!(breaker.state(), CircuitState::Open);
+        }
+    }
+
+    mod half_open_limit_tests {
+        use super::*;
+
+        async fn half_open(breaker: &CircuitBreaker) {
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+            tokio::time::sleep(Duration::from_millis(30)).await;
+        }
+
+        #[tokio::test]
+        async fn test_half_open_limits_concurrent_probes() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_threshold(1)
+                .with_timeout(Duration::from_millis(10))
+                .with_success_threshold(3)
+                .with_half_open_max_calls(2);
+            half_open(&breaker).await;
+
+            // Two probes in flight, the third fails fast
+            assert!(breaker.check().is_ok());
+            assert_eq!(breaker.state(), CircuitState::HalfOpen);
+            assert!(breaker.check().is_ok());
+            assert!(breaker.check().is_err());
+            assert!(breaker.is_rejecting());
+
+            // A completed probe frees its slot
+            breaker.record_success();
+            assert!(!breaker.is_rejecting());
+            assert!(breaker.check().is_ok());
+            breaker.record_success();
+            breaker.record_success();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+            assert!(breaker.check().is_ok());
+        }
+
+        #[tokio::test]
+        async fn test_half_open_limit_resets_on_reopen() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_threshold(1)
+                .with_timeout(Duration::from_millis(10))
+                .with_half_open_max_calls(1);
+            half_open(&breaker).await;
+            assert!(breaker.check().is_ok());
+            assert!(breaker.check().is_err());
+
+            // The probe fails: the circuit reopens and later allows a new probe
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            assert!(breaker.check().is_ok());
+            assert!(breaker.check().is_err());
+        }
+
+        #[tokio::test]
+        async fn test_half_open_unlimited_by_default() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_threshold(1)
+                .with_timeout(Duration::from_millis(10));
+            half_open(&breaker).await;
+            for _ in 0..10 {
+                assert!(breaker.check().is_ok());
+            }
+        }
+    }
+
+    mod circuit_stats_tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_circuit_breaker_publishes_transitions() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_threshold(2)
+                .with_timeout(Duration::from_millis(20))
+                .with_success_threshold(1);
+            let mut changes = breaker.subscribe();
+            assert_eq!(*changes.borrow(), CircuitState::Closed);
+            assert_eq!(breaker.stats().last_transition, None);
+
+            breaker.record_failure();
+            breaker.record_failure();
+            assert!(changes.has_changed().unwrap_or(false));
+            assert_eq!(*changes.borrow_and_update(), CircuitState::Open);
+
+            tokio::time::sleep(Duration::from_millis(40)).await;
+            assert!(breaker.check().is_ok());
+            assert_eq!(*changes.borrow_and_update(), CircuitState::HalfOpen);
+
+            breaker.record_success();
+            assert_eq!(*changes.borrow_and_update(), CircuitState::Closed);
+
+            let stats = breaker.stats();
+            assert_eq!(stats.state, CircuitState::Closed);
+            assert_eq!(stats.failure_count, 2);
+            assert_eq!(stats.consecutive_failures, 0);
+            assert_eq!(stats.open_count, 1);
+            assert!(stats.last_transition.is_some());
+        }
+
+        #[test]
+        fn test_circuit_breaker_stats_shared_by_clones() {
+            let breaker = CircuitBreaker::new().with_failure_threshold(1);
+            let changes = breaker.clone().subscribe();
+
+            breaker.record_failure();
+            assert_eq!(*changes.borrow(), CircuitState::Open);
+            assert_eq!(breaker.clone().stats().open_count, 1);
+        }
+    }
+
+    mod failure_rate_tests {
+        use super::*;
+
+        #[test]
+        fn test_failure_rate_over_call_window() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_rate(0.5, 4)
+                .with_minimum_calls(4);
+
+            // Alternating outcomes would never trip the consecutive mode
+            breaker.record_failure();
+            breaker.record_success();
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Closed, "below minimum volume");
+
+            // Only failures open the circuit
+            breaker.record_success();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+
+            // 2 of the last 4 calls failed
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+        }
+
+        #[test]
+        fn test_failure_rate_window_drops_old_calls() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_rate(0.5, 4)
+                .with_minimum_calls(4);
+
+            breaker.record_failure();
+            for _ in 0..4 {
+                breaker.record_success();
+            }
+            // The early failure has left the window: 1 of 4
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+        }
+
+        #[tokio::test]
+        async fn test_failure_rate_over_time_window() {
+            let breaker = CircuitBreaker::new()
+                .with_failure_rate(0.5, Duration::from_millis(50))
+                .with_minimum_calls(2);
+
+            breaker.record_failure();
+            tokio::time::sleep(Duration::from_millis(80)).await;
+
+            // The failure expired, so the window holds a single call
+            breaker.record_success();
+            assert_eq!(breaker.state(), CircuitState::Closed);
+
+            breaker.record_failure();
+            assert_eq!(breaker.state(), CircuitState::Open);
+        }
+    }
+
+    mod dlq_tests {
+        use super::*;
+
+        #[test]
+        fn test_dlq_new() {
+            let dlq: DeadLetterQueue<String> = DeadLetterQueue::new(100);
+            assert_eq!(dlq.len(), 0);
+            assert!(dlq.is_empty());
+            assert_eq!(dlq.max_size(), 100);
+        }
+
+        #[test]
+        fn test_dlq_push_and_len() {
+            let dlq = DeadLetterQueue::new(10);
+
+            dlq.push(
+                "operation1".to_string(),
+                "Connection timeout".to_string(),
+                5,
+            );
+            assert_eq!(dlq.len(), 1);
+            assert!(!dlq.is_empty());
+
+            dlq.push(
+                "operation2".to_string(),
+                "Database error".to_string(),
+                3,
+            );
+            assert_eq!(dlq.len(), 2);
+        }
+
+        #[test]
+        fn test_dlq_peek() {
+            let dlq = DeadLetterQueue::new(10);
+
+            dlq.push(
+                "first".to_string(),
+                "error1".to_string(),
+                1,
+            );
+            dlq.push(
+                "second".to_string(),
+                "error2".to_string(),
+                2,
+            );
+
+            // Peek should return first entry without removing it
+            #[allow(clippy::unwrap_used)] // Test code: just pushed entries
+            let entry = dlq.peek().unwrap();
+            assert_eq!(entry.payload, "first");
+            assert_eq!(entry.error_message, "error1");
+            assert_eq!(entry.retry_count, 1);
+            assert_eq!(dlq.len(), 2); // Still has 2 entries
+        }
+
+        #[test]
+        fn test_dlq_drain() {
+            let dlq = DeadLetterQueue::new(10);
+
+            dlq.push("op1".to_string(), "err1".to_string(), 1);
+            dlq.push("op2".to_string(), "err2".to_string(), 2);
+            dlq.push("op3".to_string(), "err3".to_string(), 3);
+
+            assert_eq!(dlq.len(), 3);
+
+            let entries = dlq.drain();
+            assert_eq!(entries.len(), 3);
+            assert_eq!(entries[0].payload, "op1");
+            assert_eq!(entries[1].payload, "op2");
+            assert_eq!(entries[2].payload, "op3");
+
+            // Queue should be empty after drain
+            assert_eq!(dlq.len(), 0);
+            assert!(dlq.is_empty());
+        }
+
+        #[test]
+        fn test_dlq_max_size_drops_oldest() {
+            let dlq = DeadLetterQueue::new(3);
+
+            dlq.push("op1".to_string(), "err".to_string(), 1);
+            dlq.push("op2".to_string(), "err".to_string(), 1);
+            dlq.push("op3".to_string(), "err".to_string(), 1);
+            assert_eq!(dlq.len(), 3);
+
+            // This should drop op1
+            dlq.push("op4".to_string(), "err".to_string(), 1);
+            assert_eq!(dlq.len(), 3);
+
+            // Peek should show op2 (op1 was dropped)
+            #[allow(clippy::unwrap_used)] // Test code: just pushed entries
+            let entry = dlq.peek().unwrap();
+            assert_eq!(entry.payload, "op2");
+        }
+
+        #[test]
+        fn test_dlq_fifo_ordering() {
+            let dlq = DeadLetterQueue::new(10);
+
+            dlq.push("first".to_string(), "err".to_string(), 1);
+            dlq.push("second".to_string(), "err".to_string(), 1);
+            dlq.push("third".to_string(), "err".to_string(), 1);
+
+            let entries = dlq.drain();
+            assert_eq!(entries[0].payload, "first");
+            assert_eq!(entries[1].payload, "second");
+            assert_eq!(entries[2].payload, "third");
+        }
+
+        #[test]
+        fn test_dlq_clone() {
+            let dlq1 = DeadLetterQueue::new(10);
+            dlq1.push("op1".to_string(), "err".to_string(), 1);
+
+            let dlq2 = dlq1.clone();
+            assert_eq!(dlq2.len(), 1);
+            assert_eq!(dlq2.max_size(), 10);
+
+            // Both should share the same queue
+            dlq2.push("op2".to_string(), "err".to_string(), 1);
+            assert_eq!(dlq1.len(), 2); // dlq1 sees the change
+        }
+
+        #[test]
+        fn test_dlq_default() {
+            let dlq: DeadLetterQueue<i32> = DeadLetterQueue::default();
+            assert_eq!(dlq.max_size(), 1000);
+            assert!(dlq.is_empty());
+        }
+
+        #[test]
+        fn test_dead_letter_metadata() {
+            let dlq = DeadLetterQueue::new(10);
+
+            dlq.push(
+                "operation".to_string(),
+                "Connection timeout".to_string(),
+                5,
+            );
+
+            #[allow(clippy::unwrap_used)] // Test code: just pushed entry
+            let entry = dlq.peek().unwrap();
+            assert_eq!(entry.retry_count, 5);
+            assert_eq!(entry.error_message, "Connection timeout");
+            assert!(entry.first_failed_at > 0);
+            assert!(entry.last_failed_at > 0);
+            assert_eq!(entry.first_failed_at, entry.last_failed_at); // Same for first failure
+        }
+
+        #[tokio::test]
+        async fn test_dlq_concurrent_push() {
+            use std::sync::Arc;
+
+            let dlq = Arc::new(DeadLetterQueue::new(100));
 
-                                match result {
-                                    Ok(version) => {
-                                        tracing::debug!(new_version = ?version, "append_events succeeded");
-                                        on_success(version)
-                                    },
-                                    Err(error) => {
-                                        tracing::warn!(error = %error, "append_events failed");
-                                        on_error(error)
-                                    },
-                                }
-                            },
-                            EventStoreOperation::LoadEvents {
-                                event_store,
-                                stream_id,
-                                from_version,
-                                on_success,
-                                on_error,
-                            } => {
-                                tracing::debug!(
-                                    stream_id = %stream_id,
-                                    from_version = ?from_version,
-                                    "Executing load_events"
-                                );
+            let mut handles = vec![];
+            for i in 0..10 {
+                let dlq_clone = Arc::clone(&dlq);
+                handles.push(tokio::spawn(async move {
+                    dlq_clone.push(
+                        format!("op{i}"),
+                        "error".to_string(),
+                        1,
+                    );
+                }));
+            }
 
-                                // Wrap with retry logic
-                                let stream_id_clone = stream_id.clone();
-                                let result = store.retry_operation("load_events", || {
-                                    let event_store_clone = event_store.clone();
-                                    let stream_id_clone = stream_id_clone.clone();
-                                    async move {
-                                        event_store_clone.load_events(stream_id_clone, from_version).await
-                                    }
-                                }).await;
+            #[allow(clippy::unwrap_used)] // Test code: tasks should not panic
+            for handle in handles {
+                handle.await.unwrap();
+            }
 
-                                match result {
-                                    Ok(events) => {
-                                        tracing::debug!(
-                                            event_count = events.len(),
-                                            "load_events succeeded"
-                                        );
-                                        on_success(events)
-                                    },
-                                    Err(error) => {
-                                        tracing::warn!(error = %error, "load_events failed");
-                                        on_error(error)
-                                    },
-                                }
-                            },
-                            EventStoreOperation::SaveSnapshot {
-                                event_store,
-                                stream_id,
-                                version,
-                                state,
-                                on_success,
-                                on_error,
-                            } => {
-                                tracing::debug!(
-                                    stream_id = %stream_id,
-                                    version = ?version,
-                                    state_size = state.len(),
-                                    "Executing save_snapshot"
-                                );
+            assert_eq!(dlq.len(), 10);
+        }
 
-                                // Wrap with retry logic
-                                let stream_id_clone = stream_id.clone();
-                                let state_clone = state.clone();
-                                let result = store.retry_operation("save_snapshot", || {
-                                    let event_store_clone = event_store.clone();
-                                    let stream_id_clone = stream_id_clone.clone();
-                                    let state_clone = state_clone.clone();
-                                    async move {
-                                        event_store_clone
-                                            .save_snapshot(stream_id_clone, version, state_clone)
-                                            .await
-                                    }
-                                }).await;
+        #[test]
+        fn test_dlq_empty_drain() {
+            let dlq: DeadLetterQueue<String> = DeadLetterQueue::new(10);
 
-                                match result {
-                                    Ok(()) => {
-                                        tracing::debug!("save_snapshot succeeded");
-                                        on_success(())
-                                    },
-                                    Err(error) => {
-                                        tracing::warn!(error = %error, "save_snapshot failed");
-                                        on_error(error)
-                                    },
-                                }
-                            },
-                            EventStoreOperation::LoadSnapshot {
-                                event_store,
-                                stream_id,
-                                on_success,
-                                on_error,
-                            } => {
-                                tracing::debug!(stream_id = %stream_id, "Executing load_snapshot");
+            let entries = dlq.drain();
+            assert_eq!(entries.len(), 0);
+            assert!(dlq.is_empty());
+        }
 
-                                // Wrap with retry logic
-                                let stream_id_clone = stream_id.clone();
-                                let result = store.retry_operation("load_snapshot", || {
-                                    let event_store_clone = event_store.clone();
-                                    let stream_id_clone = stream_id_clone.clone();
-                                    async move {
-                                        event_store_clone.load_snapshot(stream_id_clone).await
-                                    }
-                                }).await;
+        #[test]
+        fn test_dlq_on_push_hooks() {
+            let dlq = DeadLetterQueue::new(10);
+            let alerts = Arc::new(Mutex::new(Vec::new()));
+
+            let sink = Arc::clone(&alerts);
+            let observed = dlq.clone();
+            dlq.on_push(move |entry: &DeadLetter<String>| {
+                // Hooks may inspect the queue; the entry is not queued yet
+                sink.lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .push((entry.error_message.clone(), observed.len()));
+            });
 
-                                match result {
-                                    Ok(snapshot) => {
-                                        tracing::debug!(
-                                            has_snapshot = snapshot.is_some(),
-                                            "load_snapshot succeeded"
-                                        );
-                                        on_success(snapshot)
-                                    },
-                                    Err(error) => {
-                                        tracing::warn!(error = %error, "load_snapshot failed");
-                                        on_error(error)
-                                    },
-                                }
-                            },
-                        };
+            dlq.push("op1".to_string(), "Connection timeout".to_string(), 5);
+            // Registered on the original, seen by pushes through clones
+            dlq.clone().push("op2".to_string(), "Database error".to_string(), 3);
 
-                        // Send action back to store if callback produced one
-                        if let Some(action) = action {
-                            tracing::trace!(
-                                "EventStore operation produced an action, sending to store with metadata"
-                            );
-                            let _ = store.send_with_metadata(action, metadata_clone).await;
-                        } else {
-                            tracing::trace!("EventStore operation completed with no action");
-                        }
-                    });
-                },
-                Effect::PublishEvent(op) => {
-                    use composable_rust_core::effect::EventBusOperation;
+            let alerts = alerts
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .clone();
+            assert_eq!(
+                alerts,
+                [
+                    ("Connection timeout".to_string(), 0),
+                    ("Database error".to_string(), 1),
+                ]
+            );
+            assert_eq!(dlq.len(), 2);
+        }
 
-                    tracing::trace!("Executing Effect::PublishEvent");
-                    metrics::counter!("store.effects.executed", "type" => "publish_event").increment(1);
-                    tracking.increment();
-                    let tracking_clone = tracking.clone();
-                    let store = self.clone();
-                    let metadata_clone = metadata.clone();
+        #[test]
+        fn test_dlq_selective_removal() {
+            let dlq = DeadLetterQueue::new(10);
+            for (op, error) in [
+                ("append_events", "Database error: reset"),
+                ("http", "HTTP 503"),
+                ("load_events", "Database error: timeout"),
+                ("http", "HTTP 502"),
+            ] {
+                dlq.push(op.to_string(), error.to_string(), 3);
+            }
 
-                    tokio::spawn(async move {
-                        let _guard = DecrementGuard(tracking_clone.clone());
+            let database = dlq.remove_where(|entry| entry.error_message.starts_with("Database"));
+            let ops: Vec<_> = database.iter().map(|entry| entry.payload.as_str()).collect();
+            assert_eq!(ops, ["append_events", "load_events"]);
 
-                        let action = match op {
-                            EventBusOperation::Publish {
-                                event_bus,
-                                topic,
-                                event,
-                                on_success,
-                                on_error,
-                            } => {
-                                tracing::debug!(
-                                    topic = %topic,
-                                    event_type = %event.event_type,
-                                    "Executing publish"
-                                );
+            // HTTP failures stay parked, in order
+            let parked: Vec<_> = dlq.iter().map(|entry| entry.error_message).collect();
+            assert_eq!(parked, ["HTTP 503", "HTTP 502"]);
 
-                                // Wrap with retry logic
-                                let topic_clone = topic.clone();
-                                let event_clone = event.clone();
-                                let result = store.retry_operation("publish", || {
-                                    let event_bus_clone = event_bus.clone();
-                                    let topic_clone = topic_clone.clone();
-                                    let event_clone = event_clone.clone();
-                                    async move {
-                                        event_bus_clone.publish(&topic_clone, &event_clone).await
-                                    }
-                                }).await;
+            dlq.retain(|entry| entry.error_message.ends_with("502"));
+            assert_eq!(dlq.len(), 1);
+            assert_eq!(dlq.peek().map(|entry| entry.error_message).as_deref(), Some("HTTP 502"));
+        }
 
-                                match result {
-                                    Ok(()) => {
-                                        tracing::debug!(topic = %topic, "publish succeeded");
-                                        on_success(())
-                                    },
-                                    Err(error) => {
-                                        tracing::warn!(
-                                            topic = %topic,
-                                            error = %error,
-                                            "publish failed"
-                                        );
-                                        on_error(error)
-                                    },
-                                }
-                            },
-                        };
+        #[test]
+        fn test_dlq_pages() {
+            let dlq = DeadLetterQueue::new(10);
+            for i in 0..5 {
+                dlq.push(i, "error".to_string(), 1);
+            }
 
-                        // Send action back to store if callback produced one
-                        if let Some(action) = action {
-                            tracing::trace!(
-                                "PublishEvent operation produced an action, sending to store with metadata"
-                            );
-                            let _ = store.send_with_metadata(action, metadata_clone).await;
-                        } else {
-                            tracing::trace!("PublishEvent operation completed with no action");
-                        }
-                    });
-                },
+            let mut page = Pagination::first(2);
+            let mut pages = Vec::new();
+            loop {
+                let entries: Vec<_> = dlq.page(page).into_iter().map(|entry| entry.payload).collect();
+                if entries.is_empty() {
+                    break;
+                }
+                pages.push(entries);
+                page = page.next();
             }
+            assert_eq!(pages, [vec![0, 1], vec![2, 3], vec![4]]);
+            assert_eq!(dlq.len(), 5);
         }
     }
 
-    impl<S, A, E, R> Clone for Store<S, A, E, R>
-    where
-        R: Reducer<State = S, Action = A, Environment = E> + Clone,
-        E: Clone,
-    {
-        fn clone(&self) -> Self {
-            Self {
-                state: Arc::clone(&self.state),
-                reducer: self.reducer.clone(),
-                environment: self.environment.clone(),
-                retry_policy: self.retry_policy.clone(),
-                dlq: self.dlq.clone(),
-                shutdown: Arc::clone(&self.shutdown),
-                pending_effects: Arc::clone(&self.pending_effects),
-                action_broadcast: self.action_broadcast.clone(),
-            }
+    mod health_check_tests {
+        use super::*;
+
+        #[test]
+        fn test_health_status_ordering() {
+            assert!(HealthStatus::Healthy < HealthStatus::Degraded);
+            assert!(HealthStatus::Degraded < HealthStatus::Unhealthy);
+        }
+
+        #[test]
+        fn test_health_status_worst() {
+            assert_eq!(
+                HealthStatus::Healthy.worst(HealthStatus::Degraded),
+                HealthStatus::Degraded
+            );
+            assert_eq!(
+                HealthStatus::Degraded.worst(HealthStatus::Unhealthy),
+                HealthStatus::Unhealthy
+            );
+            assert_eq!(
+                HealthStatus::Healthy.worst(HealthStatus::Unhealthy),
+                HealthStatus::Unhealthy
+            );
         }
-    }
-}
-
-// Re-export for convenience
-pub use store::Store;
 
-// Test module
-#[cfg(test)]
-#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
-mod tests {
-    use super::*;
-    use composable_rust_core::{effect::Effect, reducer::Reducer, smallvec, SmallVec};
-    use std::time::Duration;
+        #[test]
+        fn test_health_check_healthy() {
+            let check = HealthCheck::healthy("store");
+            assert_eq!(check.component, "store");
+            assert_eq!(check.status, HealthStatus::Healthy);
+            assert!(check.message.is_none());
+        }
 
-    // Test state
-    #[derive(Debug, Clone)]
-    struct TestState {
-        value: i32,
-    }
+        #[test]
+        fn test_health_check_degraded() {
+            let check = HealthCheck::degraded("store", "High latency");
+            assert_eq!(check.component, "store");
+            assert_eq!(check.status, HealthStatus::Degraded);
+            assert_eq!(check.message, Some("High latency".to_string()));
+        }
 
-    // Test action
-    #[derive(Debug, Clone)]
-    enum TestAction {
-        Increment,
-        Decrement,
-        NoOp,
-        ProduceEffect,
-        ProduceDelayedAction,
-        ProduceParallelEffects,
-        ProduceSequentialEffects,
-        ProducePanickingEffect,
-    }
+        #[test]
+        fn test_health_check_unhealthy() {
+            let check = HealthCheck::unhealthy("database", "Connection failed");
+            assert_eq!(check.component, "database");
+            assert_eq!(check.status, HealthStatus::Unhealthy);
+            assert_eq!(check.message, Some("Connection failed".to_string()));
+        }
 
-    // Test environment
-    #[derive(Debug, Clone)]
-    struct TestEnv;
+        #[test]
+        fn test_health_check_with_metadata() {
+            let check = HealthCheck::healthy("store")
+                .with_metadata("requests", "1000")
+                .with_metadata("errors", "5");
 
-    // Test reducer
-    #[derive(Debug, Clone)]
-    struct TestReducer;
+            assert_eq!(check.metadata.len(), 2);
+            assert_eq!(check.metadata[0], ("requests".to_string(), "1000".to_string()));
+            assert_eq!(check.metadata[1], ("errors".to_string(), "5".to_string()));
+        }
 
-    impl Reducer for TestReducer {
-        type State = TestState;
-        type Action = TestAction;
-        type Environment = TestEnv;
+        #[test]
+        fn test_health_report_all_healthy() {
+            let checks = vec![
+                HealthCheck::healthy("store"),
+                HealthCheck::healthy("database"),
+                HealthCheck::healthy("cache"),
+            ];
 
-        fn reduce(
-            &self,
-            state: &mut Self::State,
-            action: Self::Action,
-            _env: &Self::Environment,
-        ) -> SmallVec<[Effect<Self::Action>; 4]> {
-            match action {
-                TestAction::Increment => {
-                    state.value += 1;
-                    smallvec![Effect::None]
-                },
-                TestAction::Decrement => {
-                    state.value -= 1;
-                    smallvec![Effect::None]
-                },
-                TestAction::NoOp => smallvec![Effect::None],
-                TestAction::ProduceEffect => {
-                    // Return an effect that produces another action
-                    smallvec![Effect::Future(Box::pin(async {
-                        Some(TestAction::Increment)
-                    }))]
-                },
-                TestAction::ProduceDelayedAction => {
-                    // Return a delayed effect
-                    smallvec![Effect::Delay {
-                        duration: Duration::from_millis(10),
-                        action: Box::new(TestAction::Increment),
-                    }]
-                },
-                TestAction::ProduceParallelEffects => {
-                    // Return parallel effects that each produce an increment
-                    smallvec![Effect::Parallel(vec![
-                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
-                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
-                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
-                    ])]
-                },
-                TestAction::ProduceSequentialEffects => {
-                    // Return sequential effects: increment, increment, decrement
-                    smallvec![Effect::Sequential(vec![
-                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
-                        Effect::Future(Box::pin(async { Some(TestAction::Increment) })),
-                        Effect::Future(Box::pin(async { Some(TestAction::Decrement) })),
-                    ])]
-                },
-                TestAction::ProducePanickingEffect => {
-                    // Return an effect that will panic when executed
-                    #[allow(clippy::panic)] // Intentional panic for testing error handling
-                    {
-                        smallvec![Effect::Future(Box::pin(async {
-                            panic!("Intentional panic in effect for testing");
-                        }))]
-                    }
-                },
-            }
+            let report = HealthReport::new(checks);
+            assert_eq!(report.status, HealthStatus::Healthy);
+            assert!(report.is_healthy());
+            assert!(!report.is_degraded());
+            assert!(!report.is_unhealthy());
         }
-    }
 
-    #[tokio::test]
-    async fn test_store_creation() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+        #[test]
+        fn test_health_report_one_degraded() {
+            let checks = vec![
+                HealthCheck::healthy("store"),
+                HealthCheck::degraded("database", "Slow queries"),
+                HealthCheck::healthy("cache"),
+            ];
 
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 0);
-    }
+            let report = HealthReport::new(checks);
+            assert_eq!(report.status, HealthStatus::Degraded);
+            assert!(!report.is_healthy());
+            assert!(report.is_degraded());
+        }
 
-    #[tokio::test]
-    async fn test_send_action() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+        #[test]
+        fn test_health_report_one_unhealthy() {
+            let checks = vec![
+                HealthCheck::healthy("store"),
+                HealthCheck::degraded("database", "Slow"),
+                HealthCheck::unhealthy("cache", "Disconnected"),
+            ];
 
-        let _ = store.send(TestAction::Increment).await;
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 1);
-    }
+            let report = HealthReport::new(checks);
+            assert_eq!(report.status, HealthStatus::Unhealthy);
+            assert!(report.is_unhealthy());
+        }
 
-    #[tokio::test]
-    async fn test_multiple_actions() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+        #[test]
+        fn test_store_health_empty_dlq() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
 
-        let _ = store.send(TestAction::Increment).await;
-        let _ = store.send(TestAction::Increment).await;
-        let _ = store.send(TestAction::Decrement).await;
+            let health = store.health();
+            assert_eq!(health.status, HealthStatus::Healthy);
+            assert_eq!(health.component, "store");
+        }
 
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 1);
-    }
+        #[test]
+        fn test_store_health_degraded_dlq() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
 
-    #[tokio::test]
-    async fn test_effect_none() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+            // Fill DLQ to 60% capacity (degraded threshold is 50%)
+            for i in 0..600 {
+                store.dlq().push(OperationFailure::new(format!("op_{i}"), FailureError::Rejected("error".to_string())), "error".to_string(), 5);
+            }
 
-        let _ = store.send(TestAction::NoOp).await;
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 0);
-    }
+            let health = store.health();
+            assert_eq!(health.status, HealthStatus::Degraded);
+            assert!(health.message.is_some());
+        }
 
-    #[tokio::test]
-    async fn test_effect_future() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+        #[test]
+        fn test_store_health_unhealthy_full_dlq() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
 
-        // Send action that produces an effect
-        let _ = store.send(TestAction::ProduceEffect).await;
+            // Fill DLQ to capacity
+            for i in 0..1000 {
+                store.dlq().push(OperationFailure::new(format!("op_{i}"), FailureError::Rejected("error".to_string())), "error".to_string(), 5);
+            }
 
-        // Give the spawned task time to complete
-        tokio::time::sleep(Duration::from_millis(50)).await;
+            let health = store.health();
+            assert_eq!(health.status, HealthStatus::Unhealthy);
+            assert_eq!(health.message, Some("Dead letter queue is full".to_string()));
+        }
 
-        // The effect should have produced an Increment action
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 1);
+        #[test]
+        fn test_health_status_display() {
+            assert_eq!(format!("{}", HealthStatus::Healthy), "healthy");
+            assert_eq!(format!("{}", HealthStatus::Degraded), "degraded");
+            assert_eq!(format!("{}", HealthStatus::Unhealthy), "unhealthy");
+        }
     }
 
-    #[tokio::test]
-    async fn test_effect_delay() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
-
-        // Send action that produces a delayed effect
-        let _ = store.send(TestAction::ProduceDelayedAction).await;
-
-        // Value should still be 0 immediately
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 0);
+    /// Tests for graceful shutdown
+    mod shutdown_tests {
+        use super::*;
+        use std::time::Duration;
 
-        // Wait for delay to complete
-        tokio::time::sleep(Duration::from_millis(50)).await;
+        #[tokio::test]
+        async fn test_shutdown_with_no_pending_effects() -> Result<(), StoreError> {
+            let state = TestState { value: 0 };
+            let store = Store::new(state, TestReducer, TestEnv);
 
-        // Now value should be 1
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 1);
-    }
+            // Shutdown immediately with no effects running
+            let result = store.shutdown(Duration::from_secs(5)).await;
+            assert!(result.is_ok());
 
-    #[tokio::test]
-    async fn test_effect_parallel() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+            Ok(())
+        }
 
-        // Send action that produces parallel effects
-        let _ = store.send(TestAction::ProduceParallelEffects).await;
+        #[tokio::test]
+        async fn test_shutdown_rejects_new_actions() -> Result<(), StoreError> {
+            let state = TestState { value: 0 };
+            let store = Store::new(state, TestReducer, TestEnv);
 
-        // Give the spawned tasks time to complete
-        tokio::time::sleep(Duration::from_millis(100)).await;
+            // Initiate shutdown
+            tokio::spawn({
+                let store = store.clone();
+                async move {
+                    let _ = store.shutdown(Duration::from_secs(10)).await;
+                }
+            });
 
-        // All three increments should have completed
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 3);
-    }
+            // Give shutdown time to set the flag
+            tokio::time::sleep(Duration::from_millis(50)).await;
 
-    #[tokio::test]
-    async fn test_effect_sequential() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+            // Try to send action during shutdown
+            let result = store.send(TestAction::Increment).await;
+            assert!(matches!(result, Err(StoreError::ShutdownInProgress)));
 
-        // Send action that produces sequential effects
-        let _ = store.send(TestAction::ProduceSequentialEffects).await;
+            Ok(())
+        }
 
-        // Give the spawned tasks time to complete
-        tokio::time::sleep(Duration::from_millis(100)).await;
+        #[tokio::test]
+        async fn test_shutdown_waits_for_effects() -> Result<(), StoreError> {
+            let state = TestState { value: 0 };
+            let store = Store::new(state, TestReducer, TestEnv);
 
-        // Net result: +1 +1 -1 = 1
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 1);
-    }
+            // Send action with delayed effect
+            let _handle = store.send(TestAction::ProduceDelayedAction).await?;
 
-    #[tokio::test]
-    #[allow(clippy::panic)] // Tests are allowed to panic on failures
-    async fn test_concurrent_sends() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+            // Start shutdown in background (should wait for effect)
+            let shutdown_store = store.clone();
+            let shutdown_handle = tokio::spawn(async move {
+                shutdown_store.shutdown(Duration::from_secs(5)).await
+            });
 
-        // Send multiple actions concurrently
-        let handles: Vec<_> = (0..10)
-            .map(|_| {
-                let store = store.clone();
-                tokio::spawn(async move {
-                    let _ = store.send(TestAction::Increment).await;
-                })
-            })
-            .collect();
+            // Give it a moment to start shutdown
+            tokio::time::sleep(Duration::from_millis(50)).await;
 
-        // Wait for all to complete
-        for handle in handles {
-            if let Err(e) = handle.await {
-                panic!("concurrent send task panicked: {e}");
+            // Wait for shutdown to complete
+            let result = shutdown_handle.await;
+            assert!(result.is_ok());
+            #[allow(clippy::unwrap_used)] // Test code: just asserted is_ok()
+            {
+                assert!(result.unwrap().is_ok());
             }
-        }
 
-        // All increments should have been applied
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 10);
-    }
+            Ok(())
+        }
 
-    #[tokio::test]
-    async fn test_state_read_during_execution() {
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+        #[tokio::test]
+        async fn test_shutdown_timeout() -> Result<(), StoreError> {
+            // Create a custom reducer that returns a long-running effect
+            #[derive(Clone)]
+            struct LongRunningReducer;
 
-        let _ = store.send(TestAction::Increment).await;
+            impl Reducer for LongRunningReducer {
+                type State = TestState;
+                type Action = TestAction;
+                type Environment = TestEnv;
 
-        // Reading state should work while effects might be executing
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 1);
-    }
+                fn reduce(
+                    &self,
+                    _state: &mut Self::State,
+                    _action: Self::Action,
+                    _env: &Self::Environment,
+                ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                    smallvec![Effect::Future(Box::pin(async {
+                        // Sleep for 200ms - longer than our timeout
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        Some(TestAction::Increment)
+                    }))]
+                }
+            }
 
-    #[tokio::test]
-    async fn test_store_clone() {
-        let state = TestState { value: 0 };
-        let store1 = Store::new(state, TestReducer, TestEnv);
-        let store2 = store1.clone();
+            let state = TestState { value: 0 };
+            let store = Store::new(state, LongRunningReducer, TestEnv);
 
-        // Both stores should share the same state
-        let _ = store1.send(TestAction::Increment).await;
-        let value2 = store2.state(|s| s.value).await;
-        assert_eq!(value2, 1);
+            // Send action that triggers long-running effect
+            let _handle = store.send(TestAction::Increment).await?;
 
-        let _ = store2.send(TestAction::Increment).await;
-        let value1 = store1.state(|s| s.value).await;
-        assert_eq!(value1, 2);
-    }
+            // Give the effect time to start running
+            tokio::time::sleep(Duration::from_millis(10)).await;
 
-    #[tokio::test]
-    async fn test_effect_panic_isolation() -> Result<(), StoreError> {
-        // Test that a panic in an effect doesn't crash the Store
-        // This verifies our error handling strategy: effects fail gracefully
-        let state = TestState { value: 0 };
-        let store = Store::new(state, TestReducer, TestEnv);
+            // Try to shutdown with short timeout (50ms - effect won't finish in time)
+            let result = store.shutdown(Duration::from_millis(50)).await;
 
-        // This action produces an effect that will panic
-        let mut handle = store.send(TestAction::ProducePanickingEffect).await?;
+            // Should time out because the effect takes 200ms, and abort it
+            assert!(
+                matches!(&result, Err(StoreError::EffectsAborted(operations)) if operations == &["future"]),
+                "Expected EffectsAborted, got: {result:?}"
+            );
+            assert_eq!(store.stats().pending_effects, 0);
 
-        // Wait for the effect to complete (which includes the panic)
-        // The effect will panic, but it's isolated in the spawned task
-        handle.wait().await;
+            Ok(())
+        }
 
-        // Small delay to ensure the panicking task has finished
-        tokio::time::sleep(Duration::from_millis(50)).await;
+        /// Reducer whose `Increment` runs a `Future` effect sleeping for the given time
+        #[derive(Clone)]
+        struct SleepingReducer(Duration);
 
-        // Store should still be functional after effect panic
-        let _ = store.send(TestAction::Increment).await;
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 1);
+        impl Reducer for SleepingReducer {
+            type State = TestState;
+            type Action = TestAction;
+            type Environment = TestEnv;
 
-        // Can send multiple actions after panic
-        let _ = store.send(TestAction::Increment).await;
-        let value = store.state(|s| s.value).await;
-        assert_eq!(value, 2);
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                _env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                state.value += 1;
+                let sleep = self.0;
+                if matches!(action, TestAction::Increment) {
+                    smallvec![Effect::Future(Box::pin(async move {
+                        tokio::time::sleep(sleep).await;
+                        Some(TestAction::NoOp)
+                    }))]
+                } else {
+                    smallvec![Effect::None]
+                }
+            }
+        }
 
-        Ok(())
-    }
+        #[tokio::test]
+        async fn test_shutdown_returns_when_last_effect_finishes() -> Result<(), StoreError> {
+            let store = Store::new(TestState { value: 0 }, SleepingReducer(Duration::from_millis(30)), TestEnv);
+            let _handle = store.send(TestAction::Increment).await?;
 
-    // EventStore effect tests
-    mod event_store_tests {
-        use super::*;
-        use composable_rust_core::effect::{Effect, EventStoreOperation};
-        use composable_rust_core::event::SerializedEvent;
-        use composable_rust_core::event_store::EventStore;
-        use composable_rust_core::stream::{StreamId, Version};
-        use composable_rust_core::{smallvec, SmallVec};
-        use std::sync::Arc;
+            let started = std::time::Instant::now();
+            store.shutdown(Duration::from_secs(5)).await?;
 
-        // Test action for EventStore effects
-        #[derive(Debug, Clone)]
-        enum EventStoreAction {
-            AppendEvents {
-                stream_id: String,
-                events: Vec<String>,
-            },
-            EventsAppended {
-                version: u64,
-            },
-            AppendFailed {
-                error: String,
-            },
-            LoadEvents {
-                stream_id: String,
-            },
-            EventsLoaded {
-                count: usize,
-            },
-            LoadFailed {
-                error: String,
-            },
-            SaveSnapshot {
-                stream_id: String,
-                version: u64,
-            },
-            SnapshotSaved,
-            LoadSnapshot {
-                stream_id: String,
-            },
-            SnapshotLoaded {
-                found: bool,
-            },
+            // Woken by the effect finishing, not by the next progress tick
+            assert!(started.elapsed() < Duration::from_millis(100), "took {:?}", started.elapsed());
+            assert_eq!(store.stats().pending_effects, 0);
+            Ok(())
         }
 
-        // Test state for EventStore
-        #[derive(Debug, Clone)]
-        struct EventStoreState {
-            last_version: Option<u64>,
-            event_count: usize,
-            snapshot_saved: bool,
-            snapshot_loaded: bool,
-            error: Option<String>,
+        #[tokio::test]
+        async fn test_shutdown_with_grace_aborts_running_effects() -> Result<(), StoreError> {
+            let store = Store::new(TestState { value: 0 }, SleepingReducer(Duration::from_secs(60)), TestEnv);
+            let mut handle = store.send(TestAction::Increment).await?;
+
+            let result = store
+                .shutdown_with_grace(Duration::from_millis(20), Duration::from_secs(1))
+                .await;
+
+            assert!(
+                matches!(&result, Err(StoreError::EffectsAborted(operations)) if operations == &["future"]),
+                "got {result:?}"
+            );
+            assert_eq!(store.stats().pending_effects, 0);
+            assert_eq!(store.shutdown_signal().borrow().phase, ShutdownPhase::Stopped);
+            // The aborted effect finished its handle without feeding back an action
+            handle.wait().await;
+            assert_eq!(store.state(|s| s.value).await, 1);
+            Ok(())
         }
 
-        // Test environment with EventStore
-        #[derive(Clone)]
-        struct EventStoreEnv {
-            event_store: Arc<dyn EventStore>,
+        #[tokio::test]
+        async fn test_shutdown_with_grace_drains_fast_effects() -> Result<(), StoreError> {
+            let store = Store::new(TestState { value: 0 }, SleepingReducer(Duration::from_millis(10)), TestEnv);
+            let _handle = store.send(TestAction::Increment).await?;
+
+            store
+                .shutdown_with_grace(Duration::from_secs(5), Duration::from_secs(1))
+                .await?;
+            assert_eq!(store.stats().pending_effects, 0);
+            Ok(())
         }
 
-        // Test reducer for EventStore
-        #[derive(Clone)]
-        struct EventStoreReducer;
+        #[tokio::test]
+        async fn test_dropped_store_discards_effect_feedback() -> Result<(), StoreError> {
+            #[derive(Debug, Clone)]
+            enum Action {
+                Start,
+                Tick,
+            }
 
-        impl Reducer for EventStoreReducer {
-            type State = EventStoreState;
-            type Action = EventStoreAction;
-            type Environment = EventStoreEnv;
+            /// Counts ticks reduced and cancellable effects cancelled
+            #[derive(Clone, Default)]
+            struct Counters {
+                ticks: Arc<AtomicU64>,
+                cancelled: Arc<AtomicU64>,
+            }
 
-            fn reduce(
-                &self,
-                state: &mut Self::State,
-                action: Self::Action,
-                env: &Self::Environment,
-            ) -> SmallVec<[Effect<Self::Action>; 4]> {
-                match action {
-                    EventStoreAction::AppendEvents { stream_id, events } => {
-                        let serialized_events: Vec<SerializedEvent> = events
-                            .into_iter()
-                            .map(|data| {
-                                SerializedEvent::new(
-                                    "TestEvent.v1".to_string(),
-                                    data.into_bytes(),
-                                    None,
-                                )
-                            })
-                            .collect();
+            #[derive(Clone)]
+            struct TickReducer;
 
-                        smallvec![Effect::EventStore(EventStoreOperation::AppendEvents {
-                            event_store: Arc::clone(&env.event_store),
-                            stream_id: StreamId::new(&stream_id),
-                            expected_version: state.last_version.map(Version::new),
-                            events: serialized_events,
-                            metadata: None,
-                            on_success: Box::new(|version| {
-                                Some(EventStoreAction::EventsAppended {
-                                    version: version.value(),
-                                })
-                            }),
-                            on_error: Box::new(|error| {
-                                Some(EventStoreAction::AppendFailed {
-                                    error: error.to_string(),
-                                })
-                            }),
-                        })]
-                    },
-                    EventStoreAction::EventsAppended { version } => {
-                        state.last_version = Some(version);
-                        smallvec![Effect::None]
-                    },
-                    EventStoreAction::AppendFailed { error }
-                    | EventStoreAction::LoadFailed { error } => {
-                        state.error = Some(error);
-                        smallvec![Effect::None]
-                    },
-                    EventStoreAction::LoadEvents { stream_id } => {
-                        smallvec![Effect::EventStore(EventStoreOperation::LoadEvents {
-                            event_store: Arc::clone(&env.event_store),
-                            stream_id: StreamId::new(&stream_id),
-                            from_version: None,
-                            on_success: Box::new(|events| {
-                                Some(EventStoreAction::EventsLoaded {
-                                    count: events.len(),
-                                })
-                            }),
-                            on_error: Box::new(|error| {
-                                Some(EventStoreAction::LoadFailed {
-                                    error: error.to_string(),
-                                })
-                            }),
-                        })]
-                    },
-                    EventStoreAction::EventsLoaded { count } => {
-                        state.event_count = count;
-                        smallvec![Effect::None]
-                    },
-                    EventStoreAction::SaveSnapshot { stream_id, version } => {
-                        let state_bytes = vec![1, 2, 3, 4]; // Mock state data
-                        smallvec![Effect::EventStore(EventStoreOperation::SaveSnapshot {
-                            event_store: Arc::clone(&env.event_store),
-                            stream_id: StreamId::new(&stream_id),
-                            version: Version::new(version),
-                            state: state_bytes,
-                            on_success: Box::new(|()| Some(EventStoreAction::SnapshotSaved)),
-                            on_error: Box::new(|error| {
-                                Some(EventStoreAction::AppendFailed {
-                                    error: error.to_string(),
-                                })
-                            }),
-                        })]
-                    },
-                    EventStoreAction::SnapshotSaved => {
-                        state.snapshot_saved = true;
-                        smallvec![Effect::None]
-                    },
-                    EventStoreAction::LoadSnapshot { stream_id } => {
-                        smallvec![Effect::EventStore(EventStoreOperation::LoadSnapshot {
-                            event_store: Arc::clone(&env.event_store),
-                            stream_id: StreamId::new(&stream_id),
-                            on_success: Box::new(|snapshot| {
-                                Some(EventStoreAction::SnapshotLoaded {
-                                    found: snapshot.is_some(),
-                                })
-                            }),
-                            on_error: Box::new(|error| {
-                                Some(EventStoreAction::LoadFailed {
-                                    error: error.to_string(),
-                                })
-                            }),
-                        })]
-                    },
-                    EventStoreAction::SnapshotLoaded { found } => {
-                        state.snapshot_loaded = found;
-                        smallvec![Effect::None]
-                    },
+            impl Reducer for TickReducer {
+                type State = ();
+                type Action = Action;
+                type Environment = Counters;
+
+                fn reduce(
+                    &self,
+                    _state: &mut (),
+                    action: Action,
+                    env: &Counters,
+                ) -> SmallVec<[Effect<Action>; 4]> {
+                    match action {
+                        Action::Start => {
+                            let cancelled = Arc::clone(&env.cancelled);
+                            smallvec![
+                                Effect::Delay {
+                                    duration: Duration::from_millis(20),
+                                    action: Box::new(Action::Tick),
+                                },
+                                Effect::future_with_cancel(move |token| async move {
+                                    token.cancelled().await;
+                                    cancelled.fetch_add(1, Ordering::SeqCst);
+                                    Some(Action::Tick)
+                                }),
+                            ]
+                        },
+                        Action::Tick => {
+                            env.ticks.fetch_add(1, Ordering::SeqCst);
+                            smallvec![Effect::None]
+                        },
+                    }
                 }
             }
+
+            let counters = Counters::default();
+            let store = Store::new((), TickReducer, counters.clone());
+            let mut handle = store.send(Action::Start).await?;
+            drop(store);
+
+            // Both effects finish without keeping the store alive
+            tokio::time::timeout(Duration::from_secs(1), handle.wait())
+                .await
+                .map_err(|_| StoreError::ShutdownTimeout(1))?;
+            assert_eq!(counters.cancelled.load(Ordering::SeqCst), 1);
+            assert_eq!(counters.ticks.load(Ordering::SeqCst), 0);
+            Ok(())
         }
 
         #[tokio::test]
-        async fn test_eventstore_append_success() -> Result<(), StoreError> {
-            use composable_rust_testing::mocks::InMemoryEventStore;
+        async fn test_shutdown_cancels_future_with_cancel() -> Result<(), StoreError> {
+            /// Reducer whose `Increment` waits for cancellation, for up to a minute
+            #[derive(Clone)]
+            struct CancellableReducer;
 
-            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
-            let env = EventStoreEnv {
-                event_store: Arc::clone(&event_store),
-            };
-            let state = EventStoreState {
-                last_version: None,
-                event_count: 0,
-                snapshot_saved: false,
-                snapshot_loaded: false,
-                error: None,
-            };
+            impl Reducer for CancellableReducer {
+                type State = TestState;
+                type Action = TestAction;
+                type Environment = TestEnv;
 
-            let store = Store::new(state, EventStoreReducer, env);
+                fn reduce(
+                    &self,
+                    state: &mut Self::State,
+                    action: Self::Action,
+                    _env: &Self::Environment,
+                ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                    state.value += 1;
+                    if !matches!(action, TestAction::Increment) {
+                        return smallvec![Effect::None];
+                    }
+                    smallvec![Effect::future_with_cancel(|token| async move {
+                        tokio::select! {
+                            () = token.cancelled() => None,
+                            () = tokio::time::sleep(Duration::from_secs(60)) => Some(TestAction::NoOp),
+                        }
+                    })]
+                }
+            }
 
-            // Append events
-            let mut handle = store
-                .send(EventStoreAction::AppendEvents {
-                    stream_id: "test-stream".to_string(),
-                    events: vec!["event1".to_string(), "event2".to_string()],
-                })
-                .await?;
+            let store = Store::new(TestState { value: 0 }, CancellableReducer, TestEnv);
+            let mut handle = store.send(TestAction::Increment).await?;
 
+            let started = std::time::Instant::now();
+            store.shutdown(Duration::from_secs(5)).await?;
+
+            // The effect exited on cancellation instead of being aborted at the timeout
+            assert!(started.elapsed() < Duration::from_secs(1), "took {:?}", started.elapsed());
             handle.wait().await;
+            assert_eq!(store.stats().pending_effects, 0);
+            assert_eq!(store.state(|s| s.value).await, 1);
+            Ok(())
+        }
 
-            // Check state was updated with version
-            let last_version = store.state(|s| s.last_version).await;
-            assert_eq!(last_version, Some(1)); // 2 events, version 0-1, returns last = 1
+        #[tokio::test]
+        async fn test_shutdown_timeout_reports_aborted_operations() -> Result<(), StoreError> {
+            #[derive(Clone)]
+            struct StuckReducer;
+
+            impl Reducer for StuckReducer {
+                type State = TestState;
+                type Action = TestAction;
+                type Environment = TestEnv;
+
+                fn reduce(
+                    &self,
+                    state: &mut Self::State,
+                    _action: Self::Action,
+                    _env: &Self::Environment,
+                ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                    state.value += 1;
+                    smallvec![Effect::Parallel(vec![
+                        Effect::Delay {
+                            duration: Duration::from_secs(60),
+                            action: Box::new(TestAction::Increment),
+                        },
+                        Effect::Future(Box::pin(futures::future::pending())),
+                        Effect::Future(Box::pin(async { Some(TestAction::NoOp) })),
+                    ])]
+                }
+            }
+
+            let store = Store::new(TestState { value: 0 }, StuckReducer, TestEnv);
+            let _handle = store.send(TestAction::Increment).await?;
+
+            let result = store.shutdown(Duration::from_millis(20)).await;
 
+            // The finished future is not reported
+            assert!(
+                matches!(&result, Err(StoreError::EffectsAborted(operations)) if operations == &["delay", "future"]),
+                "got {result:?}"
+            );
+            assert_eq!(store.stats().pending_effects, 0);
+            assert_eq!(store.state(|s| s.value).await, 1);
             Ok(())
         }
 
         #[tokio::test]
-        async fn test_eventstore_append_concurrency_conflict() -> Result<(), StoreError> {
-            use composable_rust_testing::mocks::InMemoryEventStore;
+        async fn test_shutdown_idempotent() -> Result<(), StoreError> {
+            let state = TestState { value: 0 };
+            let store = Store::new(state, TestReducer, TestEnv);
 
-            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
-            let env = EventStoreEnv {
-                event_store: Arc::clone(&event_store),
-            };
+            // First shutdown
+            let result1 = store.shutdown(Duration::from_secs(1)).await;
+            assert!(result1.is_ok());
 
-            // Pre-populate event store with some events
-            let stream_id = StreamId::new("test-stream");
-            let events = vec![SerializedEvent::new(
-                "TestEvent.v1".to_string(),
-                b"data".to_vec(),
-                None,
-            )];
-            event_store
-                .append_events(stream_id.clone(), Some(Version::new(0)), events)
-                .await
-                .ok();
+            // Second shutdown should also succeed (already shut down)
+            let result2 = store.shutdown(Duration::from_secs(1)).await;
+            assert!(result2.is_ok());
 
-            // Create store with wrong expected version
-            let state = EventStoreState {
-                last_version: Some(5), // Wrong version, actual is 0
-                event_count: 0,
-                snapshot_saved: false,
-                snapshot_loaded: false,
-                error: None,
-            };
+            Ok(())
+        }
+    }
+
+    mod config_tests {
+        use super::*;
+        use std::time::Duration;
+
+        #[test]
+        fn test_store_config_default() {
+            let config = StoreConfig::default();
+            assert_eq!(config.dlq_max_size, 1000);
+            assert_eq!(config.default_shutdown_timeout, Duration::from_secs(30));
+        }
+
+        #[test]
+        fn test_store_config_builder_pattern() {
+            let custom_retry = RetryPolicy::new()
+                .with_max_attempts(10)
+                .with_initial_delay(Duration::from_millis(100));
+
+            let config = StoreConfig::default()
+                .with_dlq_max_size(500)
+                .with_retry_policy(custom_retry.clone())
+                .with_shutdown_timeout(Duration::from_secs(60));
+
+            assert_eq!(config.dlq_max_size, 500);
+            assert_eq!(config.default_shutdown_timeout, Duration::from_secs(60));
+        }
+
+        #[test]
+        fn test_store_config_new() {
+            let retry_policy = RetryPolicy::new().with_max_attempts(5);
+            let config = StoreConfig::new(
+                2000,
+                retry_policy.clone(),
+                Duration::from_secs(45),
+            );
 
-            let store = Store::new(state, EventStoreReducer, env);
+            assert_eq!(config.dlq_max_size, 2000);
+            assert_eq!(config.default_shutdown_timeout, Duration::from_secs(45));
+        }
 
-            // Try to append with wrong version
-            let mut handle = store
-                .send(EventStoreAction::AppendEvents {
-                    stream_id: "test-stream".to_string(),
-                    events: vec!["event".to_string()],
-                })
-                .await?;
+        #[tokio::test]
+        async fn test_store_with_config_uses_custom_dlq_size() -> Result<(), StoreError> {
+            let config = StoreConfig::default().with_dlq_max_size(50);
 
-            handle.wait().await;
+            let state = TestState { value: 0 };
+            let store = Store::with_config(state, TestReducer, TestEnv, config);
 
-            // Check error was captured
-            let error = store.state(|s| s.error.clone()).await;
-            assert!(error.is_some());
-            #[allow(clippy::unwrap_used)] // Panics: Test verified error is Some above
-            {
-                assert!(error.unwrap().contains("Concurrency"));
-            }
+            // Verify DLQ has correct capacity
+            let dlq = store.dlq();
+            assert_eq!(dlq.max_size(), 50);
 
             Ok(())
         }
 
         #[tokio::test]
-        async fn test_eventstore_load_events() -> Result<(), StoreError> {
-            use composable_rust_testing::mocks::InMemoryEventStore;
+        async fn test_store_with_config_uses_custom_retry_policy() -> Result<(), StoreError> {
+            let custom_retry = RetryPolicy::new()
+                .with_max_attempts(2)
+                .with_initial_delay(Duration::from_millis(50));
 
-            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
-            let env = EventStoreEnv {
-                event_store: Arc::clone(&event_store),
-            };
+            let config = StoreConfig::default().with_retry_policy(custom_retry);
 
-            // Pre-populate with events
-            let stream_id = StreamId::new("test-stream");
-            let events = vec![
-                SerializedEvent::new("TestEvent.v1".to_string(), b"event1".to_vec(), None),
-                SerializedEvent::new("TestEvent.v1".to_string(), b"event2".to_vec(), None),
-                SerializedEvent::new("TestEvent.v1".to_string(), b"event3".to_vec(), None),
-            ];
-            event_store
-                .append_events(stream_id, Some(Version::new(0)), events)
-                .await
-                .ok();
+            let state = TestState { value: 0 };
+            let store = Store::with_config(state, TestReducer, TestEnv, config);
 
-            let state = EventStoreState {
-                last_version: None,
-                event_count: 0,
-                snapshot_saved: false,
-                snapshot_loaded: false,
-                error: None,
-            };
+            // Verify store was created successfully with custom config
+            // The retry policy is used internally by the store
+            // This test confirms the store accepts and uses the configuration
+            let _handle = store.send(TestAction::Increment).await?;
 
-            let store = Store::new(state, EventStoreReducer, env);
+            Ok(())
+        }
 
-            // Load events
-            let mut handle = store
-                .send(EventStoreAction::LoadEvents {
-                    stream_id: "test-stream".to_string(),
-                })
-                .await?;
+        #[tokio::test]
+        async fn test_store_with_config_independent_instances() -> Result<(), StoreError> {
+            // Create two stores with different configurations
+            let config1 = StoreConfig::default().with_dlq_max_size(100);
+            let config2 = StoreConfig::default().with_dlq_max_size(200);
 
-            handle.wait().await;
+            let store1 = Store::with_config(
+                TestState { value: 0 },
+                TestReducer,
+                TestEnv,
+                config1,
+            );
+            let store2 = Store::with_config(
+                TestState { value: 0 },
+                TestReducer,
+                TestEnv,
+                config2,
+            );
 
-            // Check count was updated
-            let count = store.state(|s| s.event_count).await;
-            assert_eq!(count, 3);
+            // Verify each has independent configuration
+            assert_eq!(store1.dlq().max_size(), 100);
+            assert_eq!(store2.dlq().max_size(), 200);
 
             Ok(())
         }
 
-        #[tokio::test]
-        async fn test_eventstore_snapshot_roundtrip() -> Result<(), StoreError> {
-            use composable_rust_testing::mocks::InMemoryEventStore;
+        #[test]
+        fn test_store_config_clone() {
+            let config1 = StoreConfig::default().with_dlq_max_size(300);
+            let config2 = config1.clone();
 
-            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
-            let env = EventStoreEnv {
-                event_store: Arc::clone(&event_store),
-            };
-            let state = EventStoreState {
-                last_version: None,
-                event_count: 0,
-                snapshot_saved: false,
-                snapshot_loaded: false,
-                error: None,
-            };
+            assert_eq!(config1.dlq_max_size, config2.dlq_max_size);
+            assert_eq!(config1.default_shutdown_timeout, config2.default_shutdown_timeout);
+        }
 
-            let store = Store::new(state, EventStoreReducer, env);
+        #[test]
+        fn test_store_config_chaining() {
+            // Test that builder methods can be chained
+            let config = StoreConfig::default()
+                .with_dlq_max_size(400)
+                .with_shutdown_timeout(Duration::from_secs(120))
+                .with_retry_policy(RetryPolicy::new().with_max_attempts(3));
 
-            // Save snapshot
-            let mut handle = store
-                .send(EventStoreAction::SaveSnapshot {
-                    stream_id: "test-stream".to_string(),
-                    version: 10,
-                })
-                .await?;
+            assert_eq!(config.dlq_max_size, 400);
+            assert_eq!(config.default_shutdown_timeout, Duration::from_secs(120));
+        }
 
-            handle.wait().await;
+        #[tokio::test]
+        async fn test_store_rate_limit_is_hot_reloaded() -> Result<(), StoreError> {
+            use crate::runtime_config::{ConfigHandle, StoreRuntimeConfig};
 
-            // Check snapshot was saved
-            let saved = store.state(|s| s.snapshot_saved).await;
-            assert!(saved);
+            let handle = ConfigHandle::new(StoreRuntimeConfig {
+                max_actions_per_second: Some(2),
+                ..StoreRuntimeConfig::default()
+            })
+            .unwrap();
+            let config = StoreConfig::default().with_config_handle(handle.clone());
+            let store = Store::with_config(TestState { value: 0 }, TestReducer, TestEnv, config);
+
+            store.send(TestAction::Increment).await?;
+            store.send(TestAction::Increment).await?;
+            assert!(matches!(
+                store.send(TestAction::Increment).await,
+                Err(StoreError::RateLimited(2))
+            ));
+
+            // Lifting the limit takes effect immediately, without a restart
+            handle.modify(|c| c.max_actions_per_second = None).unwrap();
+            store.send(TestAction::Increment).await?;
+            assert_eq!(store.state(|s| s.value).await, 3);
 
-            // Load snapshot
-            let mut handle = store
-                .send(EventStoreAction::LoadSnapshot {
-                    stream_id: "test-stream".to_string(),
-                })
-                .await?;
+            Ok(())
+        }
 
-            handle.wait().await;
+        #[tokio::test]
+        async fn test_rate_limit_does_not_throttle_feedback() -> Result<(), StoreError> {
+            use crate::runtime_config::{ConfigHandle, StoreRuntimeConfig};
 
-            // Check snapshot was loaded
-            let loaded = store.state(|s| s.snapshot_loaded).await;
-            assert!(loaded);
+            let handle = ConfigHandle::new(StoreRuntimeConfig {
+                max_actions_per_second: Some(1),
+                ..StoreRuntimeConfig::default()
+            })
+            .unwrap();
+            let config = StoreConfig::default().with_config_handle(handle);
+            let store = Store::with_config(TestState { value: 0 }, TestReducer, TestEnv, config);
+
+            // The only token of the window goes to the external action, so its
+            // effect's Increment is fed back while the limit is saturated
+            let mut effects = store.send(TestAction::ProduceEffect).await?;
+            effects.wait_with_timeout(Duration::from_secs(1)).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+
+            assert_eq!(store.state(|s| s.value).await, 1);
+            assert!(matches!(
+                store.send(TestAction::Increment).await,
+                Err(StoreError::RateLimited(1))
+            ));
 
             Ok(())
         }
 
-        #[tokio::test]
-        async fn test_eventstore_parallel_operations() -> Result<(), StoreError> {
-            use composable_rust_testing::mocks::InMemoryEventStore;
+        #[test]
+        fn test_store_health_uses_hot_dlq_threshold() {
+            use crate::runtime_config::{ConfigHandle, StoreRuntimeConfig};
 
-            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
-            let env = EventStoreEnv {
-                event_store: Arc::clone(&event_store),
-            };
-            let state = EventStoreState {
-                last_version: None,
-                event_count: 0,
-                snapshot_saved: false,
-                snapshot_loaded: false,
-                error: None,
-            };
+            let handle = ConfigHandle::new(StoreRuntimeConfig::default()).unwrap();
+            let config = StoreConfig::default()
+                .with_dlq_max_size(10)
+                .with_config_handle(handle.clone());
+            let store = Store::with_config(TestState { value: 0 }, TestReducer, TestEnv, config);
 
-            let store = Store::new(state, EventStoreReducer, env);
+            for i in 0..3 {
+                store.dlq().push(OperationFailure::new(format!("op-{i}"), FailureError::Rejected("error".to_string())), "error".to_string(), 1);
+            }
+            assert!(store.health().status.is_healthy());
 
-            // Append to stream1
-            let mut h1 = store
-                .send(EventStoreAction::AppendEvents {
-                    stream_id: "stream-1".to_string(),
-                    events: vec!["event1".to_string()],
-                })
-                .await?;
+            handle.modify(|c| c.dlq_degraded_ratio = 0.2).unwrap();
+            assert!(store.health().status.is_degraded());
+        }
 
-            // Append to stream2 (different stream, can run concurrently)
-            let mut h2 = store
-                .send(EventStoreAction::AppendEvents {
-                    stream_id: "stream-2".to_string(),
-                    events: vec!["event2".to_string()],
-                })
-                .await?;
+        #[test]
+        fn test_circuit_breaker_reads_hot_thresholds() {
+            use crate::runtime_config::{ConfigHandle, StoreRuntimeConfig};
 
-            // Wait for both
-            h1.wait().await;
-            h2.wait().await;
+            let handle = ConfigHandle::new(StoreRuntimeConfig::default()).unwrap();
+            let breaker = CircuitBreaker::new()
+                .with_failure_threshold(100)
+                .with_config_handle(handle.clone());
 
-            // Both should have succeeded (last_version reflects last operation)
-            let last_version = store.state(|s| s.last_version).await;
-            assert!(last_version.is_some());
+            handle
+                .modify(|c| c.circuit_breaker.failure_threshold = 2)
+                .unwrap();
+            breaker.record_failure();
+            breaker.record_failure();
 
-            Ok(())
+            assert_eq!(breaker.state(), CircuitState::Open);
         }
+    }
 
-        // Reducer for testing parallel EventStore effects
-        #[derive(Clone)]
-        struct ParallelTestReducer;
+    mod mailbox_tests {
+        use super::*;
+        use std::time::Duration;
 
-        impl Reducer for ParallelTestReducer {
-            type State = EventStoreState;
-            type Action = EventStoreAction;
-            type Environment = EventStoreEnv;
+        #[derive(Debug, Clone, Default)]
+        struct LogState {
+            log: Vec<(usize, usize)>,
+        }
+
+        #[derive(Debug, Clone)]
+        struct LogReducer;
+
+        impl Reducer for LogReducer {
+            type State = LogState;
+            type Action = (usize, usize);
+            type Environment = TestEnv;
 
             fn reduce(
                 &self,
-                _state: &mut Self::State,
-                _action: Self::Action,
-                env: &Self::Environment,
+                state: &mut Self::State,
+                action: Self::Action,
+                _env: &Self::Environment,
             ) -> SmallVec<[Effect<Self::Action>; 4]> {
-                // Create parallel effects on each call
-                smallvec![Effect::Parallel(vec![
-                    Effect::EventStore(EventStoreOperation::AppendEvents {
-                        event_store: Arc::clone(&env.event_store),
-                        stream_id: StreamId::new("stream-1"),
-                        expected_version: Some(Version::new(0)),
-                        events: vec![SerializedEvent::new(
-                            "Test.v1".to_string(),
-                            b"data1".to_vec(),
-                            None,
-                        )],
-                        metadata: None,
-                        on_success: Box::new(|_| None), // No feedback action
-                        on_error: Box::new(|_| None),
-                    }),
-                    Effect::EventStore(EventStoreOperation::AppendEvents {
-                        event_store: Arc::clone(&env.event_store),
-                        stream_id: StreamId::new("stream-2"),
-                        expected_version: Some(Version::new(0)),
-                        events: vec![SerializedEvent::new(
-                            "Test.v1".to_string(),
-                            b"data2".to_vec(),
-                            None,
-                        )],
-                        metadata: None,
-                        on_success: Box::new(|_| None),
-                        on_error: Box::new(|_| None),
-                    }),
-                ])]
+                state.log.push(action);
+                smallvec![Effect::None]
+            }
+        }
+
+        #[test]
+        fn test_with_mailbox_sets_capacity() {
+            let config = StoreConfig::default().with_mailbox(64);
+            assert_eq!(config.mailbox_capacity, Some(64));
+            assert_eq!(StoreConfig::default().mailbox_capacity, None);
+        }
+
+        #[tokio::test]
+        async fn test_mailbox_depth_none_without_mailbox() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+            assert_eq!(store.mailbox_depth(), None);
+
+            let config = StoreConfig::default().with_mailbox(8);
+            let store = Store::with_config(TestState { value: 0 }, TestReducer, TestEnv, config);
+            assert_eq!(store.mailbox_depth(), Some(0));
+        }
+
+        #[tokio::test]
+        async fn test_mailbox_preserves_fifo_order() {
+            let config = StoreConfig::default().with_mailbox(4);
+            let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config);
+
+            for i in 0..50 {
+                store.send((0, i)).await.unwrap();
             }
+
+            let log = store.state(|s| s.log.clone()).await;
+            assert_eq!(log, (0..50).map(|i| (0, i)).collect::<Vec<_>>());
         }
 
         #[tokio::test]
-        async fn test_eventstore_effect_in_parallel_composition() -> Result<(), StoreError> {
-            use composable_rust_testing::mocks::InMemoryEventStore;
+        async fn test_send_batch_through_mailbox_keeps_order() {
+            let config = StoreConfig::default().with_mailbox(4);
+            let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config);
 
-            // Test that EventStore effects work inside Parallel effect composition
-            let event_store = Arc::new(InMemoryEventStore::new()) as Arc<dyn EventStore>;
-            let env = EventStoreEnv {
-                event_store: Arc::clone(&event_store),
-            };
+            store.send((0, 0)).await.unwrap();
+            let mut handle = store.send_batch((1..10).map(|i| (0, i)).collect()).await.unwrap();
+            handle.wait().await;
 
-            let test_store = Store::new(
-                EventStoreState {
-                    last_version: None,
-                    event_count: 0,
-                    snapshot_saved: false,
-                    snapshot_loaded: false,
-                    error: None,
-                },
-                ParallelTestReducer,
-                env.clone(),
-            );
+            let log = store.state(|s| s.log.clone()).await;
+            assert_eq!(log, (0..10).map(|i| (0, i)).collect::<Vec<_>>());
+        }
 
-            // Trigger the parallel effect
-            let mut handle = test_store
-                .send(EventStoreAction::AppendEvents {
-                    stream_id: "dummy".to_string(),
-                    events: vec![],
+        #[tokio::test]
+        async fn test_send_batch_rate_limited_mid_batch_can_be_retried() {
+            use crate::idempotency::IdempotencyGuard;
+            use crate::runtime_config::{ConfigHandle, StoreRuntimeConfig};
+
+            for mailbox in [false, true] {
+                let handle = ConfigHandle::new(StoreRuntimeConfig {
+                    max_actions_per_second: Some(3),
+                    ..StoreRuntimeConfig::default()
                 })
-                .await?;
+                .unwrap();
+                let mut config = StoreConfig::default().with_config_handle(handle.clone());
+                if mailbox {
+                    config = config.with_mailbox(4);
+                }
+                let guard = IdempotencyGuard::new(|(sender, seq): &(usize, usize)| Some(format!("{sender}-{seq}")));
+                let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config).with_idempotency(guard);
 
-            handle.wait().await;
+                store.send((0, 0)).await.unwrap();
+                // Three more actions do not fit in the remaining two tokens
+                let batch: Vec<_> = (1..4).map(|i| (0, i)).collect();
+                let rejected = store.send_batch(batch.clone()).await;
+                assert!(matches!(rejected, Err(StoreError::RateLimited(3))), "mailbox: {mailbox}");
 
-            // Verify both streams got events
-            let events1 = env
-                .event_store
-                .load_events(StreamId::new("stream-1"), None)
-                .await
-                .ok();
-            let events2 = env
-                .event_store
-                .load_events(StreamId::new("stream-2"), None)
-                .await
-                .ok();
+                // Nothing was admitted, so a retry is not dropped as duplicates
+                handle.modify(|c| c.max_actions_per_second = None).unwrap();
+                store.send_batch(batch).await.unwrap().wait().await;
 
-            #[allow(clippy::unwrap_used)] // Panics: Test verified events are Some above
-            {
-                assert!(events1.is_some());
-                assert!(events2.is_some());
-                assert_eq!(events1.unwrap().len(), 1);
-                assert_eq!(events2.unwrap().len(), 1);
+                let log = store.state(|s| s.log.clone()).await;
+                assert_eq!(log, (0..4).map(|i| (0, i)).collect::<Vec<_>>(), "mailbox: {mailbox}");
             }
-
-            Ok(())
         }
-    }
 
-    /// Tests for `RetryPolicy`
-    mod retry_policy_tests {
-        use super::*;
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_mailbox_concurrent_senders_keep_per_sender_order() {
+            let config = StoreConfig::default().with_mailbox(8);
+            let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config);
 
-        #[test]
-        fn test_retry_policy_default() {
-            let policy = RetryPolicy::default();
-            assert_eq!(policy.max_attempts(), 5);
-            assert!(policy.should_retry(0));
-            assert!(policy.should_retry(4));
-            assert!(!policy.should_retry(5));
+            let mut tasks = Vec::new();
+            for sender in 0..8 {
+                let store = store.clone();
+                tasks.push(tokio::spawn(async move {
+                    for i in 0..25 {
+                        store.send((sender, i)).await.unwrap();
+                    }
+                }));
+            }
+            for task in tasks {
+                task.await.unwrap();
+            }
+
+            let log = store.state(|s| s.log.clone()).await;
+            assert_eq!(log.len(), 200);
+            for sender in 0..8 {
+                let sequence: Vec<usize> = log
+                    .iter()
+                    .filter(|(s, _)| *s == sender)
+                    .map(|(_, i)| *i)
+                    .collect();
+                assert_eq!(sequence, (0..25).collect::<Vec<_>>());
+            }
         }
 
-        #[test]
-        fn test_retry_policy_builder() {
-            let policy = RetryPolicy::new()
-                .with_max_attempts(3)
-                .with_initial_delay(Duration::from_millis(100))
-                .with_max_delay(Duration::from_secs(10))
-                .with_backoff_multiplier(3.0);
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_mailbox_applies_backpressure_when_full() {
+            let config = StoreConfig::default().with_mailbox(1);
+            let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config);
+
+            // Hold a read lock so the event loop blocks on its first action
+            let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+            let (held_tx, held_rx) = tokio::sync::oneshot::channel();
+            let reader = store.clone();
+            let lock_holder = tokio::spawn(async move {
+                reader
+                    .state(move |_| {
+                        let _ = held_tx.send(());
+                        let _ = release_rx.recv();
+                    })
+                    .await;
+            });
+            held_rx.await.unwrap();
 
-            assert_eq!(policy.max_attempts(), 3);
-            assert!(policy.should_retry(2));
-            assert!(!policy.should_retry(3));
-        }
+            // First action is taken by the event loop, second fills the queue
+            let mut senders = Vec::new();
+            for i in 0..2 {
+                let store = store.clone();
+                senders.push(tokio::spawn(async move { store.send((0, i)).await }));
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            assert_eq!(store.mailbox_depth(), Some(1));
 
-        #[test]
-        fn test_delay_increases_exponentially() {
-            let policy = RetryPolicy::new()
-                .with_initial_delay(Duration::from_secs(1))
-                .with_backoff_multiplier(2.0)
-                .with_max_delay(Duration::from_secs(100));
+            // Third send must wait for queue space
+            let blocked = tokio::time::timeout(Duration::from_millis(50), store.send((0, 2))).await;
+            assert!(blocked.is_err(), "send should wait while the mailbox is full");
 
-            let delay0 = policy.delay_for_attempt(0);
-            let delay1 = policy.delay_for_attempt(1);
-            let delay2 = policy.delay_for_attempt(2);
+            release_tx.send(()).unwrap();
+            lock_holder.await.unwrap();
+            for sender in senders {
+                sender.await.unwrap().unwrap();
+            }
+            store.send((0, 3)).await.unwrap();
 
-            // Delays should generally increase (accounting for jitter 0.5-1.0)
-            // delay0 ~= 1s * (0.5-1.0)
-            // delay1 ~= 2s * (0.5-1.0)
-            // delay2 ~= 4s * (0.5-1.0)
-            assert!(delay0.as_millis() >= 500 && delay0.as_millis() <= 1000);
-            assert!(delay1.as_millis() >= 1000 && delay1.as_millis() <= 2000);
-            assert!(delay2.as_millis() >= 2000 && delay2.as_millis() <= 4000);
+            let log = store.state(|s| s.log.clone()).await;
+            assert_eq!(log, vec![(0, 0), (0, 1), (0, 3)]);
         }
 
-        #[test]
-        fn test_delay_caps_at_max() {
-            let policy = RetryPolicy::new()
-                .with_initial_delay(Duration::from_secs(1))
-                .with_backoff_multiplier(2.0)
-                .with_max_delay(Duration::from_secs(5));
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_try_send_sheds_load_when_full() {
+            let config = StoreConfig::default().with_mailbox(1);
+            let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config);
 
-            // Attempt 10 would normally be 2^10 = 1024 seconds
-            // But should be capped at 5 seconds (plus jitter 0.5-1.0)
-            let delay = policy.delay_for_attempt(10);
-            assert!(delay.as_millis() >= 2500 && delay.as_millis() <= 5000);
-        }
+            // The event loop takes the first action and blocks on the lock,
+            // the second fills the queue
+            let (release, holder) = hold_state_lock(&store).await;
+            store.try_send((0, 0)).unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            store.try_send((0, 1)).unwrap();
 
-        #[test]
-        fn test_jitter_variation() {
-            let policy = RetryPolicy::new()
-                .with_initial_delay(Duration::from_secs(1))
-                .with_backoff_multiplier(2.0);
+            assert!(matches!(store.try_send((0, 2)), Err(StoreError::MailboxFull { capacity: 1 })));
+            assert_eq!(store.mailbox_depth(), Some(1));
 
-            // Run multiple times to ensure jitter provides variation
-            let mut delays = Vec::new();
-            for _ in 0..10 {
-                delays.push(policy.delay_for_attempt(1));
-            }
+            release.send(()).unwrap();
+            holder.await.unwrap();
+            store.send((0, 3)).await.unwrap();
 
-            // Should have variation due to jitter
-            // Not all delays should be exactly the same
-            let first = delays[0];
-            let has_variation = delays.iter().any(|d| d != &first);
-            assert!(has_variation, "Jitter should produce variation in delays");
+            let log = store.state(|s| s.log.clone()).await;
+            assert_eq!(log, vec![(0, 0), (0, 1), (0, 3)]);
         }
-    }
 
-    mod circuit_breaker_tests {
-        use super::*;
+        #[tokio::test]
+        async fn test_try_send_without_mailbox() {
+            let store = Store::new(LogState::default(), LogReducer, TestEnv);
+            store.try_send((0, 0)).unwrap();
 
-        #[test]
-        fn test_circuit_breaker_initial_state() {
-            let breaker = CircuitBreaker::new();
-            assert_eq!(breaker.state(), CircuitState::Closed);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            assert_eq!(store.state(|s| s.log.clone()).await, vec![(0, 0)]);
         }
 
-        #[test]
-        fn test_circuit_breaker_opens_after_threshold() {
-            let breaker = CircuitBreaker::new().with_failure_threshold(3);
+        /// Block the event loop by holding a state read lock until `release` fires
+        async fn hold_state_lock(
+            store: &Store<LogState, (usize, usize), TestEnv, LogReducer>,
+        ) -> (std::sync::mpsc::Sender<()>, tokio::task::JoinHandle<()>) {
+            let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+            let (held_tx, held_rx) = tokio::sync::oneshot::channel();
+            let reader = store.clone();
+            let holder = tokio::spawn(async move {
+                reader
+                    .state(move |_| {
+                        let _ = held_tx.send(());
+                        let _ = release_rx.recv();
+                    })
+                    .await;
+            });
+            held_rx.await.unwrap();
+            (release_tx, holder)
+        }
 
-            assert_eq!(breaker.state(), CircuitState::Closed);
+        fn send_all(
+            store: &Store<LogState, (usize, usize), TestEnv, LogReducer>,
+            count: usize,
+        ) -> Vec<tokio::task::JoinHandle<Result<EffectHandle, StoreError>>> {
+            (0..count)
+                .map(|i| {
+                    let store = store.clone();
+                    tokio::spawn(async move { store.send((0, i)).await })
+                })
+                .collect()
+        }
 
-            // Record failures
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Closed);
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_shutdown_drain_mailbox_reduces_queued_actions() {
+            let config = StoreConfig::default().with_mailbox(8);
+            let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config);
+            let (release, holder) = hold_state_lock(&store).await;
+            let senders = send_all(&store, 3);
+            tokio::time::sleep(Duration::from_millis(50)).await;
 
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Closed);
+            let mut progress = store.shutdown_signal();
+            let shutdown = tokio::spawn({
+                let store = store.clone();
+                async move {
+                    store
+                        .shutdown_with(ShutdownMode::DrainMailbox { timeout: Duration::from_secs(5) })
+                        .await
+                }
+            });
+            progress.changed().await.unwrap();
+            let draining = *progress.borrow_and_update();
+            assert_eq!(draining.phase, ShutdownPhase::DrainingMailbox);
+            assert_eq!(draining.queued_actions, 3);
 
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Open);
+            release.send(()).unwrap();
+            holder.await.unwrap();
+            shutdown.await.unwrap().unwrap();
+
+            for sender in senders {
+                sender.await.unwrap().unwrap();
+            }
+            assert_eq!(store.state(|s| s.log.len()).await, 3);
+            assert_eq!(progress.borrow().phase, ShutdownPhase::Stopped);
+            assert!(matches!(store.send((1, 0)).await, Err(StoreError::ShutdownInProgress)));
         }
 
-        #[test]
-        fn test_circuit_breaker_resets_on_success() {
-            let breaker = CircuitBreaker::new().with_failure_threshold(3);
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_shutdown_immediate_discards_queued_actions() {
+            let config = StoreConfig::default().with_mailbox(8);
+            let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config);
+            let (release, holder) = hold_state_lock(&store).await;
+            let senders = send_all(&store, 3);
+            tokio::time::sleep(Duration::from_millis(50)).await;
 
-            // Record some failures
-            breaker.record_failure();
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Closed);
+            store.shutdown_with(ShutdownMode::Immediate).await.unwrap();
+            release.send(()).unwrap();
+            holder.await.unwrap();
 
-            // Success resets the count
-            breaker.record_success();
+            let mut discarded = 0;
+            for sender in senders {
+                if matches!(sender.await.unwrap(), Err(StoreError::ShutdownInProgress)) {
+                    discarded += 1;
+                }
+            }
+            // The action the event loop was already reducing still completes
+            assert_eq!(discarded, 2);
+            assert_eq!(store.state(|s| s.log.len()).await, 1);
+            assert_eq!(store.shutdown_signal().borrow().phase, ShutdownPhase::Stopped);
+        }
 
-            // Should need 3 more failures to open
-            breaker.record_failure();
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Closed);
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_shutdown_drain_mailbox_times_out() {
+            let config = StoreConfig::default().with_mailbox(8);
+            let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config);
+            let (release, holder) = hold_state_lock(&store).await;
+            let _senders = send_all(&store, 2);
+            tokio::time::sleep(Duration::from_millis(50)).await;
 
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Open);
+            let result = store
+                .shutdown_with(ShutdownMode::DrainMailbox { timeout: Duration::from_millis(50) })
+                .await;
+
+            assert!(matches!(result, Err(StoreError::DrainTimeout(2))), "got {result:?}");
+            assert_eq!(store.shutdown_signal().borrow().phase, ShutdownPhase::TimedOut);
+            release.send(()).unwrap();
+            holder.await.unwrap();
+        }
+
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_cancelled_send_is_not_left_queued() {
+            let config = StoreConfig::default().with_mailbox(1);
+            let store = Store::with_config(LogState::default(), LogReducer, TestEnv, config);
+
+            // The event loop blocks on the first action, the second fills the queue
+            let (release, holder) = hold_state_lock(&store).await;
+            store.try_send((0, 0)).unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            store.try_send((0, 1)).unwrap();
+
+            // Gives up while waiting for queue space
+            let blocked = tokio::time::timeout(Duration::from_millis(20), store.send((0, 2))).await;
+            assert!(blocked.is_err());
+
+            release.send(()).unwrap();
+            holder.await.unwrap();
+            store
+                .shutdown_with(ShutdownMode::DrainMailbox { timeout: Duration::from_secs(1) })
+                .await
+                .unwrap();
+            assert_eq!(store.state(|s| s.log.clone()).await, vec![(0, 0), (0, 1)]);
         }
 
         #[tokio::test]
-        async fn test_circuit_breaker_half_open_after_timeout() {
-            let breaker = CircuitBreaker::new()
-                .with_failure_threshold(2)
-                .with_timeout(Duration::from_millis(100));
+        async fn test_mailbox_effects_feed_back_actions() {
+            let config = StoreConfig::default().with_mailbox(16);
+            let store = Store::with_config(TestState { value: 0 }, TestReducer, TestEnv, config);
 
-            // Open the circuit
-            breaker.record_failure();
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Open);
+            let mut handle = store.send(TestAction::ProduceEffect).await.unwrap();
+            handle.wait_with_timeout(Duration::from_secs(1)).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
 
-            // Check should fail immediately
-            assert!(breaker.check().is_err());
+            assert_eq!(store.state(|s| s.value).await, 1);
+        }
+    }
 
-            // Wait for timeout
-            tokio::time::sleep(Duration::from_millis(150)).await;
+    mod correlation_tests {
+        use super::*;
+        use crate::envelope::{ActionSource, Envelope};
+        use composable_rust_core::action::{Correlatable, Uuid};
+        use composable_rust_core::event::EventMetadata;
+        use std::time::Duration;
 
-            // Check should transition to HalfOpen
-            assert!(breaker.check().is_ok());
-            assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        #[derive(Debug, Clone, PartialEq)]
+        enum EchoAction {
+            Request { correlation_id: Option<Uuid>, value: u64 },
+            Reply { correlation_id: Option<Uuid>, doubled: u64 },
         }
 
-        #[test]
-        fn test_circuit_breaker_closes_after_success_threshold() {
-            let breaker = CircuitBreaker::new()
-                .with_failure_threshold(2)
-                .with_success_threshold(2);
+        impl Correlatable for EchoAction {
+            fn correlation_id(&self) -> Option<Uuid> {
+                match self {
+                    Self::Request { correlation_id, .. } | Self::Reply { correlation_id, .. } => {
+                        *correlation_id
+                    },
+                }
+            }
 
-            // Open the circuit
-            breaker.record_failure();
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Open);
+            fn with_correlation_id(mut self, id: Uuid) -> Self {
+                match &mut self {
+                    Self::Request { correlation_id, .. } | Self::Reply { correlation_id, .. } => {
+                        *correlation_id = Some(id);
+                    },
+                }
+                self
+            }
+        }
 
-            // Manually transition to HalfOpen for testing
-            breaker
-                .state
-                .store(CircuitState::HalfOpen as u8, Ordering::Release);
+        #[derive(Debug, Clone)]
+        struct EchoReducer;
 
-            // Record successes
-            breaker.record_success();
-            assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        impl Reducer for EchoReducer {
+            type State = ();
+            type Action = EchoAction;
+            type Environment = TestEnv;
+
+            fn reduce(
+                &self,
+                _state: &mut Self::State,
+                action: Self::Action,
+                _env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                match action {
+                    EchoAction::Request {
+                        correlation_id,
+                        value,
+                    } => smallvec![Effect::Future(Box::pin(async move {
+                        // Later requests reply sooner, so replies interleave
+                        tokio::time::sleep(Duration::from_millis(50 - value.min(50))).await;
+                        Some(EchoAction::Reply {
+                            correlation_id,
+                            doubled: value * 2,
+                        })
+                    }))],
+                    EchoAction::Reply { .. } => smallvec![Effect::None],
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn test_send_correlated_generates_and_matches_id() {
+            let store = Store::with_broadcast_capacity((), EchoReducer, TestEnv, 64);
+
+            let reply = store
+                .send_correlated(
+                    EchoAction::Request {
+                        correlation_id: None,
+                        value: 21,
+                    },
+                    Duration::from_secs(1),
+                )
+                .await
+                .unwrap();
+
+            assert!(reply.correlation_id().is_some());
+            assert!(matches!(reply, EchoAction::Reply { doubled: 42, .. }));
+        }
+
+        #[tokio::test]
+        async fn test_send_correlated_keeps_existing_id() {
+            let store = Store::with_broadcast_capacity((), EchoReducer, TestEnv, 64);
+            let id = Uuid::from_u128(42);
+
+            let reply = store
+                .send_correlated(
+                    EchoAction::Request {
+                        correlation_id: Some(id),
+                        value: 1,
+                    },
+                    Duration::from_secs(1),
+                )
+                .await
+                .unwrap();
 
-            breaker.record_success();
-            assert_eq!(breaker.state(), CircuitState::Closed);
+            assert_eq!(reply.correlation_id(), Some(id));
         }
 
-        #[test]
-        fn test_circuit_breaker_reopens_on_half_open_failure() {
-            let breaker = CircuitBreaker::new().with_failure_threshold(2);
-
-            // Open the circuit
-            breaker.record_failure();
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Open);
+        #[tokio::test]
+        async fn test_concurrent_send_correlated_get_their_own_replies() {
+            let store = Store::with_broadcast_capacity((), EchoReducer, TestEnv, 64);
 
-            // Manually transition to HalfOpen
-            breaker
-                .state
-                .store(CircuitState::HalfOpen as u8, Ordering::Release);
+            let mut tasks = Vec::new();
+            for value in 0..10 {
+                let store = store.clone();
+                tasks.push(tokio::spawn(async move {
+                    let reply = store
+                        .send_correlated(
+                            EchoAction::Request {
+                                correlation_id: None,
+                                value,
+                            },
+                            Duration::from_secs(1),
+                        )
+                        .await
+                        .unwrap();
+                    (value, reply)
+                }));
+            }
 
-            // Any failure in HalfOpen should reopen circuit
-            breaker.record_failure();
-            assert_eq!(breaker.state(), CircuitState::Open);
+            for task in tasks {
+                let (value, reply) = task.await.unwrap();
+                assert!(matches!(reply, EchoAction::Reply { doubled, .. } if doubled == value * 2));
+            }
         }
 
         #[tokio::test]
-        async fn test_circuit_breaker_call_success() {
-            let breaker = CircuitBreaker::new();
-
-            let result = breaker
-                .call(|| async { Ok::<i32, String>(42) })
+        async fn test_send_correlated_times_out_before_reply() {
+            let store = Store::with_broadcast_capacity((), EchoReducer, TestEnv, 64);
+
+            let result = store
+                .send_correlated(
+                    EchoAction::Request {
+                        correlation_id: None,
+                        value: 0,
+                    },
+                    Duration::from_millis(5),
+                )
                 .await;
 
-            assert!(result.is_ok());
-            #[allow(clippy::unwrap_used)] // Safe: just asserted is_ok()
-            {
-                assert_eq!(result.unwrap(), 42);
-            }
-            assert_eq!(breaker.state(), CircuitState::Closed);
+            assert!(matches!(result, Err(StoreError::Timeout)));
         }
 
         #[tokio::test]
-        async fn test_circuit_breaker_call_opens_on_failures() {
-            let breaker = CircuitBreaker::new().with_failure_threshold(3);
+        async fn test_envelope_observer_sees_source_and_correlation() {
+            let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&seen);
+            let store = Store::new((), EchoReducer, TestEnv).with_envelope_observer(
+                move |envelope: &Envelope<EchoAction>| {
+                    recorded
+                        .lock()
+                        .unwrap()
+                        .push((envelope.source, envelope.correlation_id().map(str::to_string)));
+                },
+            );
 
-            // Fail 3 times
-            for _ in 0..3 {
-                let result = breaker
-                    .call(|| async { Err::<i32, String>("error".to_string()) })
-                    .await;
-                assert!(result.is_err());
-            }
+            let mut handle = store
+                .send_with_metadata(
+                    EchoAction::Request {
+                        correlation_id: None,
+                        value: 50,
+                    },
+                    Some(EventMetadata::with_correlation_id("corr-7")),
+                )
+                .await
+                .unwrap();
+            handle.wait().await;
 
-            assert_eq!(breaker.state(), CircuitState::Open);
+            let seen = seen.lock().unwrap().clone();
+            assert_eq!(
+                seen,
+                vec![
+                    (ActionSource::External, Some("corr-7".to_string())),
+                    (ActionSource::Feedback, Some("corr-7".to_string())),
+                ]
+            );
+        }
+    }
 
-            // Next call should fail fast
-            let result = breaker
-                .call(|| async { Ok::<i32, String>(42) })
-                .await;
+    mod paged_load_tests {
+        use super::*;
+        use composable_rust_core::effect_builders::EventStoreEffect;
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::EventStore;
+        use composable_rust_core::stream::{StreamId, Version};
+        use composable_rust_testing::mocks::InMemoryEventStore;
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(Either::Left(_))));
+        #[derive(Debug, Clone, Default)]
+        struct Folded {
+            pages: Vec<usize>,
+            resume_at: Option<Version>,
         }
 
-        #[tokio::test]
-        async fn test_circuit_breaker_concurrent_access() {
-            use std::sync::Arc;
+        #[derive(Debug, Clone)]
+        enum FoldAction {
+            Load(Version),
+            Page(usize),
+            Done(Version),
+        }
 
-            let breaker = Arc::new(CircuitBreaker::new().with_failure_threshold(5));
+        /// Folds a stream in pages of three events, recording each page's size
+        #[derive(Clone)]
+        struct FoldReducer {
+            events: Arc<dyn EventStore>,
+        }
 
-            // Record failures concurrently
-            let mut handles = vec![];
-            for _ in 0..10 {
-                let breaker_clone = Arc::clone(&breaker);
-                handles.push(tokio::spawn(async move {
-                    breaker_clone.record_failure();
-                }));
-            }
+        impl Reducer for FoldReducer {
+            type State = Folded;
+            type Action = FoldAction;
+            type Environment = TestEnv;
 
-            #[allow(clippy::unwrap_used)] // Test code: tasks should not panic
-            for handle in handles {
-                handle.await.unwrap();
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                _env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                match action {
+                    FoldAction::Load(from) => smallvec![
+                        EventStoreEffect::load_paged(&self.events, "order-1", 3)
+                            .from_version(from)
+                            .on_page(|events| Some(FoldAction::Page(events.len())))
+                            .on_complete(|version| Some(FoldAction::Done(version)))
+                            .into_effect()
+                    ],
+                    FoldAction::Page(size) => {
+                        state.pages.push(size);
+                        smallvec![Effect::None]
+                    },
+                    FoldAction::Done(version) => {
+                        state.resume_at = Some(version);
+                        smallvec![Effect::None]
+                    },
+                }
             }
+        }
 
-            // Circuit should be open (10 failures, threshold is 5)
-            assert_eq!(breaker.state(), CircuitState::Open);
+        async fn store_with_events(count: u8) -> Store<Folded, FoldAction, TestEnv, FoldReducer> {
+            let events = Arc::new(InMemoryEventStore::new());
+            let batch = (0..count)
+                .map(|n| SerializedEvent::new("Item.v1".to_string(), vec![n], None))
+                .collect();
+            events.append_events(StreamId::new("order-1"), None, batch).await.unwrap();
+            Store::new(Folded::default(), FoldReducer { events }, TestEnv)
         }
 
-        #[test]
-        fn test_circuit_breaker_builder() {
-            let breaker = CircuitBreaker::new()
-                .with_failure_threshold(10)
-                .with_timeout(Duration::from_secs(30))
-                .with_success_threshold(3);
+        #[tokio::test]
+        async fn test_paged_load_folds_every_page_in_order() {
+            let store = store_with_events(8).await;
 
-            // Verify by opening the circuit
-            for _ in 0..10 {
-                breaker.record_failure();
-            }
-            assert_eq!(breaker.state(), CircuitState::Open);
+            store.send(FoldAction::Load(Version::new(0))).await.unwrap().wait().await;
+
+            let folded = store.state(Clone::clone).await;
+            assert_eq!(folded.pages, [3, 3, 2]);
+            assert_eq!(folded.resume_at, Some(Version::new(8)));
+        }
+
+        #[tokio::test]
+        async fn test_paged_load_resumes_and_completes_on_exact_multiple() {
+            let store = store_with_events(6).await;
+
+            store.send(FoldAction::Load(Version::new(3))).await.unwrap().wait().await;
+
+            // The full page is followed by an empty probe before completing
+            let folded = store.state(Clone::clone).await;
+            assert_eq!(folded.pages, [3]);
+            assert_eq!(folded.resume_at, Some(Version::new(6)));
         }
     }
 
-    mod dlq_tests {
+    mod publish_tests {
         use super::*;
+        use composable_rust_core::effect::EventBusOperation;
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_bus::{EventBus, EventBusError, EventStream, PublishOptions};
+        use composable_rust_testing::mocks::InMemoryEventBus;
+        use std::future::Future;
+        use std::pin::Pin;
 
-        #[test]
-        fn test_dlq_new() {
-            let dlq: DeadLetterQueue<String> = DeadLetterQueue::new(100);
-            assert_eq!(dlq.len(), 0);
-            assert!(dlq.is_empty());
-            assert_eq!(dlq.max_size(), 100);
+        /// Publishes one event with the configured options per action
+        #[derive(Clone)]
+        struct PublishReducer {
+            bus: Arc<dyn EventBus>,
+            options: PublishOptions,
         }
 
-        #[test]
-        fn test_dlq_push_and_len() {
-            let dlq = DeadLetterQueue::new(10);
+        impl Reducer for PublishReducer {
+            type State = ();
+            type Action = ();
+            type Environment = TestEnv;
 
-            dlq.push(
-                "operation1".to_string(),
-                "Connection timeout".to_string(),
-                5,
-            );
-            assert_eq!(dlq.len(), 1);
-            assert!(!dlq.is_empty());
+            fn reduce(
+                &self,
+                _state: &mut Self::State,
+                _action: Self::Action,
+                _env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                smallvec![Effect::PublishEvent(EventBusOperation::Publish {
+                    event_bus: Arc::clone(&self.bus),
+                    topic: "orders".to_string(),
+                    event: SerializedEvent::new("OrderPlaced.v1".to_string(), vec![], None),
+                    options: self.options.clone(),
+                    on_success: Box::new(|()| None),
+                    on_error: Box::new(|_| None),
+                })]
+            }
+        }
 
-            dlq.push(
-                "operation2".to_string(),
-                "Database error".to_string(),
-                3,
-            );
-            assert_eq!(dlq.len(), 2);
+        /// Event bus whose publishes never complete
+        struct StalledBus;
+
+        impl EventBus for StalledBus {
+            fn publish(
+                &self,
+                _topic: &str,
+                _event: &SerializedEvent,
+            ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+                Box::pin(std::future::pending())
+            }
+
+            fn subscribe(
+                &self,
+                _topics: &[&str],
+            ) -> Pin<Box<dyn Future<Output = Result<EventStream, EventBusError>> + Send + '_>> {
+                Box::pin(std::future::pending())
+            }
         }
 
-        #[test]
-        fn test_dlq_peek() {
-            let dlq = DeadLetterQueue::new(10);
+        #[tokio::test]
+        async fn test_publish_carries_partition_key_to_bus() {
+            let bus = InMemoryEventBus::new();
+            let reducer = PublishReducer {
+                bus: Arc::new(bus.clone()),
+                options: PublishOptions::new().with_partition_key("order-1"),
+            };
+            let store = Store::new((), reducer, TestEnv);
 
-            dlq.push(
-                "first".to_string(),
-                "error1".to_string(),
-                1,
-            );
-            dlq.push(
-                "second".to_string(),
-                "error2".to_string(),
-                2,
-            );
+            store.send(()).await.unwrap().wait().await;
 
-            // Peek should return first entry without removing it
-            #[allow(clippy::unwrap_used)] // Test code: just pushed entries
-            let entry = dlq.peek().unwrap();
-            assert_eq!(entry.payload, "first");
-            assert_eq!(entry.error_message, "error1");
-            assert_eq!(entry.retry_count, 1);
-            assert_eq!(dlq.len(), 2); // Still has 2 entries
+            assert_eq!(bus.published_keys("orders"), [Some("order-1".to_string())]);
         }
 
-        #[test]
-        fn test_dlq_drain() {
-            let dlq = DeadLetterQueue::new(10);
+        #[tokio::test(start_paused = true)]
+        async fn test_delivery_timeout_fails_stalled_publish() {
+            let reducer = PublishReducer {
+                bus: Arc::new(StalledBus),
+                options: PublishOptions::new().with_delivery_timeout(Duration::from_secs(2)),
+            };
+            let policy = RetryPolicy::new().with_max_attempts(1);
+            let store = Store::with_retry_policy((), reducer, TestEnv, policy);
 
-            dlq.push("op1".to_string(), "err1".to_string(), 1);
-            dlq.push("op2".to_string(), "err2".to_string(), 2);
-            dlq.push("op3".to_string(), "err3".to_string(), 3);
+            let outcome = store.send(()).await.unwrap().wait().await;
 
-            assert_eq!(dlq.len(), 3);
+            assert_eq!(outcome.failed.len(), 1);
+            assert!(outcome.failed[0].error.contains("delivery timed out after 2s"));
+        }
+    }
 
-            let entries = dlq.drain();
-            assert_eq!(entries.len(), 3);
-            assert_eq!(entries[0].payload, "op1");
-            assert_eq!(entries[1].payload, "op2");
-            assert_eq!(entries[2].payload, "op3");
+    mod budget_tests {
+        use super::*;
+        use crate::budget::{Budget, EffectBudgets, OverBudget};
+        use composable_rust_core::effect::EffectCost;
+        use std::time::Duration;
 
-            // Queue should be empty after drain
-            assert_eq!(dlq.len(), 0);
-            assert!(dlq.is_empty());
+        #[derive(Debug, Clone, Default)]
+        struct FetchState {
+            reduced: usize,
+            fetched: usize,
         }
 
-        #[test]
-        fn test_dlq_max_size_drops_oldest() {
-            let dlq = DeadLetterQueue::new(3);
+        #[derive(Debug, Clone)]
+        enum FetchAction {
+            /// Make `n` HTTP calls
+            Fetch(usize),
+            /// Make one HTTP call that feeds back `Chain(n - 1)`
+            Chain(usize),
+            Fetched,
+        }
 
-            dlq.push("op1".to_string(), "err".to_string(), 1);
-            dlq.push("op2".to_string(), "err".to_string(), 1);
-            dlq.push("op3".to_string(), "err".to_string(), 1);
-            assert_eq!(dlq.len(), 3);
+        #[derive(Debug, Clone)]
+        struct FetchReducer;
 
-            // This should drop op1
-            dlq.push("op4".to_string(), "err".to_string(), 1);
-            assert_eq!(dlq.len(), 3);
+        fn http_call(reply: Option<FetchAction>) -> Effect<FetchAction> {
+            Effect::Future(Box::pin(async move { reply })).with_cost(EffectCost::new("http", 1))
+        }
 
-            // Peek should show op2 (op1 was dropped)
-            #[allow(clippy::unwrap_used)] // Test code: just pushed entries
-            let entry = dlq.peek().unwrap();
-            assert_eq!(entry.payload, "op2");
+        impl Reducer for FetchReducer {
+            type State = FetchState;
+            type Action = FetchAction;
+            type Environment = TestEnv;
+
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                _env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                state.reduced += 1;
+                match action {
+                    FetchAction::Fetch(n) => (0..n).map(|_| http_call(Some(FetchAction::Fetched))).collect(),
+                    FetchAction::Chain(0) => smallvec![Effect::None],
+                    FetchAction::Chain(n) => smallvec![http_call(Some(FetchAction::Chain(n - 1)))],
+                    FetchAction::Fetched => {
+                        state.fetched += 1;
+                        smallvec![Effect::None]
+                    },
+                }
+            }
         }
 
-        #[test]
-        fn test_dlq_fifo_ordering() {
-            let dlq = DeadLetterQueue::new(10);
+        fn budgets(budget: Budget) -> EffectBudgets<FetchAction> {
+            EffectBudgets::new(|action: &FetchAction| match action {
+                FetchAction::Fetch(_) => "fetch",
+                FetchAction::Chain(_) => "chain",
+                FetchAction::Fetched => "fetched",
+            })
+            .with_budget("fetch", budget.clone())
+            .with_budget("chain", budget)
+        }
 
-            dlq.push("first".to_string(), "err".to_string(), 1);
-            dlq.push("second".to_string(), "err".to_string(), 1);
-            dlq.push("third".to_string(), "err".to_string(), 1);
+        #[tokio::test]
+        async fn test_send_rejects_action_over_budget() {
+            let store = Store::new(FetchState::default(), FetchReducer, TestEnv)
+                .with_effect_budgets(budgets(Budget::new().with_limit("http", 2)));
+
+            let result = store.send(FetchAction::Fetch(3)).await;
+
+            assert!(matches!(
+                result,
+                Err(StoreError::BudgetExceeded { action: "fetch", limit: 2, total: 3, .. })
+            ));
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            // The reducer ran, but none of its effects did
+            assert_eq!(store.state(|s| (s.reduced, s.fetched)).await, (1, 0));
+        }
 
-            let entries = dlq.drain();
-            assert_eq!(entries[0].payload, "first");
-            assert_eq!(entries[1].payload, "second");
-            assert_eq!(entries[2].payload, "third");
+        #[tokio::test]
+        async fn test_send_within_budget_runs_effects() {
+            let store = Store::new(FetchState::default(), FetchReducer, TestEnv)
+                .with_effect_budgets(budgets(Budget::new().with_limit("http", 2)));
+
+            let mut handle = store.send(FetchAction::Fetch(2)).await.unwrap();
+            handle.wait().await;
+
+            assert_eq!(store.state(|s| s.fetched).await, 2);
         }
 
-        #[test]
-        fn test_dlq_clone() {
-            let dlq1 = DeadLetterQueue::new(10);
-            dlq1.push("op1".to_string(), "err".to_string(), 1);
+        #[tokio::test]
+        async fn test_flag_policy_runs_effects_over_budget() {
+            let budget = Budget::new().with_limit("http", 1).with_on_exceed(OverBudget::Flag);
+            let store = Store::new(FetchState::default(), FetchReducer, TestEnv)
+                .with_effect_budgets(budgets(budget));
 
-            let dlq2 = dlq1.clone();
-            assert_eq!(dlq2.len(), 1);
-            assert_eq!(dlq2.max_size(), 10);
+            let mut handle = store.send(FetchAction::Fetch(3)).await.unwrap();
+            handle.wait().await;
 
-            // Both should share the same queue
-            dlq2.push("op2".to_string(), "err".to_string(), 1);
-            assert_eq!(dlq1.len(), 2); // dlq1 sees the change
+            assert_eq!(store.state(|s| s.fetched).await, 3);
         }
 
-        #[test]
-        fn test_dlq_default() {
-            let dlq: DeadLetterQueue<i32> = DeadLetterQueue::default();
-            assert_eq!(dlq.max_size(), 1000);
-            assert!(dlq.is_empty());
+        #[tokio::test]
+        async fn test_budget_spans_feedback_cascade() {
+            let store = Store::new(FetchState::default(), FetchReducer, TestEnv)
+                .with_effect_budgets(budgets(Budget::new().with_limit("http", 2)));
+
+            store.send(FetchAction::Chain(5)).await.unwrap();
+
+            // Chain(5) and Chain(4) each make a call; Chain(3) is reduced but its call is rejected
+            tokio::time::timeout(Duration::from_secs(1), async {
+                while store.state(|s| s.reduced).await < 3 {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+            })
+            .await
+            .unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            assert_eq!(store.state(|s| s.reduced).await, 3);
+
+            // A new root action gets a fresh budget
+            store.send(FetchAction::Chain(1)).await.unwrap();
         }
 
-        #[test]
-        fn test_dead_letter_metadata() {
-            let dlq = DeadLetterQueue::new(10);
+        #[tokio::test]
+        async fn test_costs_ignored_without_budgets() {
+            let store = Store::new(FetchState::default(), FetchReducer, TestEnv);
 
-            dlq.push(
-                "operation".to_string(),
-                "Connection timeout".to_string(),
-                5,
-            );
+            let mut handle = store.send(FetchAction::Fetch(10)).await.unwrap();
+            handle.wait().await;
 
-            #[allow(clippy::unwrap_used)] // Test code: just pushed entry
-            let entry = dlq.peek().unwrap();
-            assert_eq!(entry.retry_count, 5);
-            assert_eq!(entry.error_message, "Connection timeout");
-            assert!(entry.first_failed_at > 0);
-            assert!(entry.last_failed_at > 0);
-            assert_eq!(entry.first_failed_at, entry.last_failed_at); // Same for first failure
+            assert_eq!(store.state(|s| s.fetched).await, 10);
         }
+    }
+
+    mod metrics_recorder_tests {
+        use super::*;
+        use crate::metrics::CapturingRecorder;
 
         #[tokio::test]
-        async fn test_dlq_concurrent_push() {
-            use std::sync::Arc;
+        async fn test_store_reports_to_injected_recorder() {
+            let recorder = CapturingRecorder::new();
+            let config = StoreConfig::default().with_metrics_recorder(Arc::new(recorder.clone()));
+            let store = Store::with_config(TestState { value: 0 }, TestReducer, TestEnv, config);
 
-            let dlq = Arc::new(DeadLetterQueue::new(100));
+            let mut handle = store.send(TestAction::ProduceEffect).await.unwrap();
+            handle.wait().await;
+            tokio::time::sleep(Duration::from_millis(10)).await;
 
-            let mut handles = vec![];
-            for i in 0..10 {
-                let dlq_clone = Arc::clone(&dlq);
-                handles.push(tokio::spawn(async move {
-                    dlq_clone.push(
-                        format!("op{i}"),
-                        "error".to_string(),
-                        1,
-                    );
-                }));
-            }
+            assert_eq!(recorder.counter("store.commands.total"), 2);
+            let futures: Vec<_> = recorder
+                .events_named("store.effects.executed")
+                .into_iter()
+                .filter(|event| event.label("type") == Some("future"))
+                .collect();
+            assert_eq!(futures.len(), 1);
+            assert_eq!(recorder.events_named("store.reducer.duration_seconds").len(), 2);
+        }
 
-            #[allow(clippy::unwrap_used)] // Test code: tasks should not panic
-            for handle in handles {
-                handle.await.unwrap();
-            }
+        #[tokio::test]
+        async fn test_action_label_tags_reducer_metrics() {
+            let recorder = CapturingRecorder::new();
+            let config = StoreConfig::default().with_metrics_recorder(Arc::new(recorder.clone()));
+            let store = Store::with_config(TestState { value: 0 }, TestReducer, TestEnv, config)
+                .with_action_label(|action: &TestAction| match action {
+                    TestAction::ProduceEffect => "produce_effect",
+                    _ => "other",
+                });
+
+            let mut handle = store.send(TestAction::ProduceEffect).await.unwrap();
+            handle.wait().await;
 
-            assert_eq!(dlq.len(), 10);
+            let labels = |name| -> Vec<_> {
+                recorder
+                    .events_named(name)
+                    .iter()
+                    .map(|event| event.label("action").map(str::to_string))
+                    .collect()
+            };
+            let expected = vec![Some("produce_effect".to_string()), Some("other".to_string())];
+            assert_eq!(labels("store.reducer.duration_seconds"), expected);
+            assert_eq!(labels("store.effects.count"), expected);
         }
 
-        #[test]
-        fn test_dlq_empty_drain() {
-            let dlq: DeadLetterQueue<String> = DeadLetterQueue::new(10);
+        #[tokio::test(start_paused = true)]
+        async fn test_pending_effects_gauge_and_stats() {
+            let recorder = CapturingRecorder::new();
+            let config = StoreConfig::default().with_metrics_recorder(Arc::new(recorder.clone()));
+            let store = Store::with_config(TestState { value: 0 }, TestReducer, TestEnv, config);
+            let _subscription = store.subscribe_actions();
 
-            let entries = dlq.drain();
-            assert_eq!(entries.len(), 0);
-            assert!(dlq.is_empty());
+            let mut handle = store.send(TestAction::ProduceDelayedAction).await.unwrap();
+            assert_eq!(recorder.gauge("store.effects.pending"), Some(1.0));
+            let stats = store.stats();
+            assert_eq!((stats.pending_effects, stats.broadcast_subscribers), (1, 1));
+
+            handle.wait().await;
+            assert_eq!(recorder.gauge("store.effects.pending"), Some(0.0));
+            assert_eq!(recorder.gauge("store.actions.subscribers"), Some(1.0));
+            assert_eq!(store.stats().pending_effects, 0);
         }
     }
 
-    mod health_check_tests {
+    mod slow_warning_tests {
         use super::*;
+        use std::sync::atomic::AtomicBool;
+        use tracing_subscriber::layer::{Context, SubscriberExt};
 
-        #[test]
-        fn test_health_status_ordering() {
-            assert!(HealthStatus::Healthy < HealthStatus::Degraded);
-            assert!(HealthStatus::Degraded < HealthStatus::Unhealthy);
+        /// Collects the `action` field and message of every warning
+        #[derive(Clone, Default)]
+        struct Warnings(Arc<std::sync::Mutex<Vec<(String, String)>>>);
+
+        #[derive(Default)]
+        struct Fields {
+            action: String,
+            message: String,
         }
 
-        #[test]
-        fn test_health_status_worst() {
-            assert_eq!(
-                HealthStatus::Healthy.worst(HealthStatus::Degraded),
-                HealthStatus::Degraded
-            );
-            assert_eq!(
-                HealthStatus::Degraded.worst(HealthStatus::Unhealthy),
-                HealthStatus::Unhealthy
-            );
-            assert_eq!(
-                HealthStatus::Healthy.worst(HealthStatus::Unhealthy),
-                HealthStatus::Unhealthy
-            );
+        impl tracing::field::Visit for Fields {
+            fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+                if field.name() == "action" {
+                    self.action = value.to_string();
+                }
+            }
+
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.message = format!("{value:?}");
+                }
+            }
         }
 
-        #[test]
-        fn test_health_check_healthy() {
-            let check = HealthCheck::healthy("store");
-            assert_eq!(check.component, "store");
-            assert_eq!(check.status, HealthStatus::Healthy);
-            assert!(check.message.is_none());
+        impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for Warnings {
+            fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+                if *event.metadata().level() == tracing::Level::WARN {
+                    let mut fields = Fields::default();
+                    event.record(&mut fields);
+                    self.0.lock().unwrap().push((fields.action, fields.message));
+                }
+            }
         }
 
-        #[test]
-        fn test_health_check_degraded() {
-            let check = HealthCheck::degraded("store", "High latency");
-            assert_eq!(check.component, "store");
-            assert_eq!(check.status, HealthStatus::Degraded);
-            assert_eq!(check.message, Some("High latency".to_string()));
+        #[derive(Clone)]
+        struct SlowReducer;
+
+        impl Reducer for SlowReducer {
+            type State = ();
+            type Action = TestAction;
+            type Environment = TestEnv;
+
+            fn reduce(
+                &self,
+                _state: &mut Self::State,
+                action: Self::Action,
+                _env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                if matches!(action, TestAction::ProduceEffect) {
+                    std::thread::sleep(Duration::from_millis(30));
+                }
+                smallvec![Effect::None]
+            }
         }
 
-        #[test]
-        fn test_health_check_unhealthy() {
-            let check = HealthCheck::unhealthy("database", "Connection failed");
-            assert_eq!(check.component, "database");
-            assert_eq!(check.status, HealthStatus::Unhealthy);
-            assert_eq!(check.message, Some("Connection failed".to_string()));
+        fn store(config: StoreConfig) -> Store<(), TestAction, TestEnv, SlowReducer> {
+            Store::with_config((), SlowReducer, TestEnv, config).with_action_label(|action: &TestAction| match action {
+                TestAction::ProduceEffect => "slow",
+                _ => "fast",
+            })
         }
 
-        #[test]
-        fn test_health_check_with_metadata() {
-            let check = HealthCheck::healthy("store")
-                .with_metadata("requests", "1000")
-                .with_metadata("errors", "5");
+        #[tokio::test]
+        async fn test_slow_reducer_is_reported_with_action_label() {
+            let warnings = Warnings::default();
+            let _subscriber =
+                tracing::subscriber::set_default(tracing_subscriber::registry().with(warnings.clone()));
+            let store = store(StoreConfig::default().with_slow_reduce_warning(Duration::from_millis(10)));
 
-            assert_eq!(check.metadata.len(), 2);
-            assert_eq!(check.metadata[0], ("requests".to_string(), "1000".to_string()));
-            assert_eq!(check.metadata[1], ("errors".to_string(), "5".to_string()));
+            store.send(TestAction::Increment).await.unwrap();
+            store.send(TestAction::ProduceEffect).await.unwrap();
+
+            let warnings = warnings.0.lock().unwrap().clone();
+            assert_eq!(
+                warnings,
+                [("slow".to_string(), "Slow reducer held the state lock".to_string())]
+            );
         }
 
-        #[test]
-        fn test_health_report_all_healthy() {
-            let checks = vec![
-                HealthCheck::healthy("store"),
-                HealthCheck::healthy("database"),
-                HealthCheck::healthy("cache"),
-            ];
+        #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+        async fn test_contended_lock_is_reported() {
+            let warnings = Warnings::default();
+            let _subscriber =
+                tracing::subscriber::set_default(tracing_subscriber::registry().with(warnings.clone()));
+            let store = store(StoreConfig::default().with_lock_wait_warning(Duration::from_millis(10)));
 
-            let report = HealthReport::new(checks);
-            assert_eq!(report.status, HealthStatus::Healthy);
-            assert!(report.is_healthy());
-            assert!(!report.is_degraded());
-            assert!(!report.is_unhealthy());
+            // Hold the state lock from another worker while the action is sent
+            let locked = Arc::new(AtomicBool::new(false));
+            let reader = tokio::spawn({
+                let store = store.clone();
+                let locked = Arc::clone(&locked);
+                async move {
+                    store
+                        .state(|()| {
+                            locked.store(true, Ordering::Release);
+                            std::thread::sleep(Duration::from_millis(50));
+                        })
+                        .await;
+                }
+            });
+            while !locked.load(Ordering::Acquire) {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            store.send(TestAction::Increment).await.unwrap();
+            reader.await.unwrap();
+
+            let warnings = warnings.0.lock().unwrap().clone();
+            assert_eq!(
+                warnings,
+                [("fast".to_string(), "Action waited long for the state lock".to_string())]
+            );
         }
+    }
 
-        #[test]
-        fn test_health_report_one_degraded() {
-            let checks = vec![
-                HealthCheck::healthy("store"),
-                HealthCheck::degraded("database", "Slow queries"),
-                HealthCheck::healthy("cache"),
-            ];
+    mod tracking_tests {
+        use super::*;
+        use crate::tracking::{CompletionStatus, TrackingId};
 
-            let report = HealthReport::new(checks);
-            assert_eq!(report.status, HealthStatus::Degraded);
-            assert!(!report.is_healthy());
-            assert!(report.is_degraded());
+        #[tokio::test]
+        async fn test_send_tracked_reports_completion() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+
+            let (id, mut handle) = store.send_tracked(TestAction::ProduceDelayedAction).await.unwrap();
+            assert!(matches!(
+                store.completion_status(id),
+                Some(CompletionStatus::Pending { .. })
+            ));
+
+            handle.wait().await;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+
+            assert!(store.completion_status(id).unwrap().is_completed());
+            assert_eq!(store.completion_status(TrackingId::new()), None);
         }
 
-        #[test]
-        fn test_health_report_one_unhealthy() {
-            let checks = vec![
-                HealthCheck::healthy("store"),
-                HealthCheck::degraded("database", "Slow"),
-                HealthCheck::unhealthy("cache", "Disconnected"),
-            ];
+        #[tokio::test]
+        async fn test_completion_action_fed_back() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv)
+                .with_completion_action(|_| Some(TestAction::Decrement));
 
-            let report = HealthReport::new(checks);
-            assert_eq!(report.status, HealthStatus::Unhealthy);
-            assert!(report.is_unhealthy());
+            let (_, mut handle) = store.send_tracked(TestAction::ProduceEffect).await.unwrap();
+            handle.wait().await;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+
+            // Effect incremented, completion action decremented
+            assert_eq!(store.state(|s| s.value).await, 0);
         }
 
-        #[test]
-        fn test_store_health_empty_dlq() {
+        #[tokio::test]
+        async fn test_rejected_action_is_not_tracked() {
             let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+            store.shutdown(Duration::from_millis(10)).await.unwrap();
 
-            let health = store.health();
-            assert_eq!(health.status, HealthStatus::Healthy);
-            assert_eq!(health.component, "store");
+            assert!(matches!(
+                store.send_tracked(TestAction::NoOp).await,
+                Err(StoreError::ShutdownInProgress)
+            ));
         }
+    }
+
+    mod blocking_tests {
+        use super::*;
 
         #[test]
-        fn test_store_health_degraded_dlq() {
-            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+        fn test_send_blocking_from_plain_thread() {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .enable_all()
+                .build()
+                .unwrap();
+            let store = {
+                let _guard = runtime.enter();
+                Store::new(TestState { value: 0 }, TestReducer, TestEnv)
+            };
 
-            // Fill DLQ to 60% capacity (degraded threshold is 50%)
-            for i in 0..600 {
-                store.dlq().push(format!("op_{i}"), "error".to_string(), 5);
-            }
+            store.send_blocking(TestAction::Increment).unwrap();
 
-            let health = store.health();
-            assert_eq!(health.status, HealthStatus::Degraded);
-            assert!(health.message.is_some());
+            assert_eq!(store.state_blocking(|s| s.value).unwrap(), 1);
         }
 
         #[test]
-        fn test_store_health_unhealthy_full_dlq() {
+        fn test_send_blocking_without_runtime() {
             let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
 
-            // Fill DLQ to capacity
-            for i in 0..1000 {
-                store.dlq().push(format!("op_{i}"), "error".to_string(), 5);
-            }
-
-            let health = store.health();
-            assert_eq!(health.status, HealthStatus::Unhealthy);
-            assert_eq!(health.message, Some("Dead letter queue is full".to_string()));
+            assert!(matches!(
+                store.send_blocking(TestAction::Increment),
+                Err(StoreError::NoRuntime)
+            ));
+            // Reading state needs no runtime
+            assert_eq!(store.state_blocking(|s| s.value).unwrap(), 0);
         }
 
-        #[test]
-        fn test_health_status_display() {
-            assert_eq!(format!("{}", HealthStatus::Healthy), "healthy");
-            assert_eq!(format!("{}", HealthStatus::Degraded), "degraded");
-            assert_eq!(format!("{}", HealthStatus::Unhealthy), "unhealthy");
+        #[tokio::test]
+        async fn test_blocking_calls_rejected_in_async_context() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+
+            assert!(matches!(
+                store.send_blocking(TestAction::Increment),
+                Err(StoreError::BlockingInAsyncContext)
+            ));
+            assert!(matches!(
+                store.state_blocking(|s| s.value),
+                Err(StoreError::BlockingInAsyncContext)
+            ));
         }
     }
 
-    /// Tests for graceful shutdown
-    mod shutdown_tests {
+    mod state_subscription_tests {
         use super::*;
-        use std::time::Duration;
 
         #[tokio::test]
-        async fn test_shutdown_with_no_pending_effects() -> Result<(), StoreError> {
-            let state = TestState { value: 0 };
-            let store = Store::new(state, TestReducer, TestEnv);
+        async fn test_subscribe_state_follows_reductions() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+            let mut positive = store.subscribe_state(|s: &TestState| s.value > 0).await;
+            assert!(!positive.get());
 
-            // Shutdown immediately with no effects running
-            let result = store.shutdown(Duration::from_secs(5)).await;
-            assert!(result.is_ok());
+            store.send(TestAction::Increment).await.unwrap();
+            assert_eq!(positive.changed().await, Some(true));
 
-            Ok(())
+            // Still positive: no new value
+            store.send(TestAction::Increment).await.unwrap();
+            assert!(tokio::time::timeout(Duration::from_millis(20), positive.changed())
+                .await
+                .is_err());
         }
+    }
+
+    mod snapshot_tests {
+        use super::*;
 
         #[tokio::test]
-        async fn test_shutdown_rejects_new_actions() -> Result<(), StoreError> {
-            let state = TestState { value: 0 };
-            let store = Store::new(state, TestReducer, TestEnv);
+        async fn test_snapshot_is_owned_and_reused_until_next_reduction() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
 
-            // Initiate shutdown
-            tokio::spawn({
-                let store = store.clone();
-                async move {
-                    let _ = store.shutdown(Duration::from_secs(10)).await;
-                }
-            });
+            let first = store.snapshot_state().await;
+            let again = store.snapshot_state().await;
+            assert!(Arc::ptr_eq(&first, &again));
 
-            // Give shutdown time to set the flag
-            tokio::time::sleep(Duration::from_millis(50)).await;
+            store.send(TestAction::Increment).await.unwrap();
+            let after = store.snapshot_state().await;
+
+            assert_eq!(first.value, 0);
+            assert_eq!(after.value, 1);
+            assert!(!Arc::ptr_eq(&first, &after));
+        }
+    }
 
-            // Try to send action during shutdown
-            let result = store.send(TestAction::Increment).await;
-            assert!(matches!(result, Err(StoreError::ShutdownInProgress)));
+    mod state_export_tests {
+        use super::*;
+        use crate::state_codec::{JsonCodec, StateCodecError};
 
-            Ok(())
+        #[tokio::test]
+        async fn test_exported_state_resumes_in_new_store() {
+            let old = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+            old.send(TestAction::Increment).await.unwrap();
+            old.send(TestAction::Increment).await.unwrap();
+            let bytes = old.export_state().await.unwrap();
+
+            let new = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+            let stale = new.snapshot_state().await;
+            let mut value = new.subscribe_state(|s: &TestState| s.value).await;
+            new.import_state(&bytes).await.unwrap();
+
+            assert_eq!(value.changed().await, Some(2));
+            assert_eq!(new.snapshot_state().await.value, 2);
+            assert_eq!(stale.value, 0);
+
+            new.send(TestAction::Increment).await.unwrap();
+            assert_eq!(new.state(|s| s.value).await, 3);
         }
 
         #[tokio::test]
-        async fn test_shutdown_waits_for_effects() -> Result<(), StoreError> {
-            let state = TestState { value: 0 };
-            let store = Store::new(state, TestReducer, TestEnv);
+        async fn test_import_with_invalid_bytes_keeps_state() {
+            let store = Store::new(TestState { value: 7 }, TestReducer, TestEnv);
+            let json = store.export_state_with(&JsonCodec).await.unwrap();
+            assert_eq!(json, br#"{"value":7}"#);
+
+            let result = store.import_state_with(&JsonCodec, b"not json").await;
+            assert!(matches!(
+                result,
+                Err(StoreError::StateCodec(StateCodecError::Decode(_)))
+            ));
+            assert_eq!(store.state(|s| s.value).await, 7);
+        }
+    }
 
-            // Send action with delayed effect
-            let _handle = store.send(TestAction::ProduceDelayedAction).await?;
+    mod idempotency_tests {
+        use super::*;
+        use crate::idempotency::IdempotencyGuard;
+        use crate::metrics::CapturingRecorder;
 
-            // Start shutdown in background (should wait for effect)
-            let shutdown_store = store.clone();
-            let shutdown_handle = tokio::spawn(async move {
-                shutdown_store.shutdown(Duration::from_secs(5)).await
+        #[tokio::test]
+        async fn test_duplicate_actions_skip_the_reducer() {
+            let recorder = CapturingRecorder::new();
+            let config = StoreConfig::default().with_metrics_recorder(Arc::new(recorder.clone()));
+            let guard = IdempotencyGuard::new(|action: &TestAction| {
+                matches!(action, TestAction::Increment).then(|| "increment-1".to_string())
             });
+            let store = Store::with_config(TestState { value: 0 }, TestReducer, TestEnv, config)
+                .with_idempotency(guard);
 
-            // Give it a moment to start shutdown
-            tokio::time::sleep(Duration::from_millis(50)).await;
+            store.send(TestAction::Increment).await.unwrap();
+            store.send(TestAction::Increment).await.unwrap();
+            store.send(TestAction::Decrement).await.unwrap();
+            store.send(TestAction::Decrement).await.unwrap();
 
-            // Wait for shutdown to complete
-            let result = shutdown_handle.await;
-            assert!(result.is_ok());
-            #[allow(clippy::unwrap_used)] // Test code: just asserted is_ok()
-            {
-                assert!(result.unwrap().is_ok());
-            }
+            assert_eq!(store.state(|s| s.value).await, -1);
+            assert_eq!(recorder.counter("store.actions.duplicates"), 1);
+        }
+    }
 
-            Ok(())
+    mod effect_limit_tests {
+        use super::*;
+        use crate::metrics::CapturingRecorder;
+
+        #[derive(Debug, Clone, Default)]
+        struct JobState {
+            done: usize,
         }
 
-        #[tokio::test]
-        async fn test_shutdown_timeout() -> Result<(), StoreError> {
-            // Create a custom reducer that returns a long-running effect
-            #[derive(Clone)]
-            struct LongRunningReducer;
+        #[derive(Debug, Clone)]
+        enum JobAction {
+            /// Start `n` jobs
+            Spawn(usize),
+            Done,
+        }
 
-            impl Reducer for LongRunningReducer {
-                type State = TestState;
-                type Action = TestAction;
-                type Environment = TestEnv;
+        /// Number of jobs running now and at most
+        #[derive(Debug, Clone, Default)]
+        struct JobEnv {
+            running: Arc<AtomicUsize>,
+            peak: Arc<AtomicUsize>,
+        }
 
-                fn reduce(
-                    &self,
-                    _state: &mut Self::State,
-                    _action: Self::Action,
-                    _env: &Self::Environment,
-                ) -> SmallVec<[Effect<Self::Action>; 4]> {
-                    smallvec![Effect::Future(Box::pin(async {
-                        // Sleep for 200ms - longer than our timeout
-                        tokio::time::sleep(Duration::from_millis(200)).await;
-                        Some(TestAction::Increment)
-                    }))]
+        #[derive(Debug, Clone)]
+        struct JobReducer;
+
+        impl Reducer for JobReducer {
+            type State = JobState;
+            type Action = JobAction;
+            type Environment = JobEnv;
+
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                match action {
+                    JobAction::Spawn(n) => (0..n)
+                        .map(|_| {
+                            let env = env.clone();
+                            Effect::Future(Box::pin(async move {
+                                let running = env.running.fetch_add(1, Ordering::SeqCst) + 1;
+                                env.peak.fetch_max(running, Ordering::SeqCst);
+                                tokio::time::sleep(Duration::from_millis(10)).await;
+                                env.running.fetch_sub(1, Ordering::SeqCst);
+                                Some(JobAction::Done)
+                            }))
+                        })
+                        .collect(),
+                    JobAction::Done => {
+                        state.done += 1;
+                        smallvec![Effect::None]
+                    },
                 }
             }
+        }
 
-            let state = TestState { value: 0 };
-            let store = Store::new(state, LongRunningReducer, TestEnv);
-
-            // Send action that triggers long-running effect
-            let _handle = store.send(TestAction::Increment).await?;
+        #[tokio::test(start_paused = true)]
+        async fn test_queued_effects_respect_limit() {
+            let recorder = CapturingRecorder::new();
+            let config = StoreConfig::default()
+                .with_max_concurrent_effects(3)
+                .with_metrics_recorder(Arc::new(recorder.clone()));
+            let env = JobEnv::default();
+            let store = Store::with_config(JobState::default(), JobReducer, env.clone(), config);
 
-            // Give the effect time to start running
-            tokio::time::sleep(Duration::from_millis(10)).await;
+            let mut handle = store.send(JobAction::Spawn(10)).await.unwrap();
+            handle.wait().await;
 
-            // Try to shutdown with short timeout (50ms - effect won't finish in time)
-            let result = store.shutdown(Duration::from_millis(50)).await;
+            assert_eq!(store.state(|s| s.done).await, 10);
+            assert_eq!(env.peak.load(Ordering::SeqCst), 3);
+            assert_eq!(recorder.counter("store.effects.queued"), 7);
+            assert_eq!(recorder.gauge("store.effects.waiting"), Some(0.0));
+        }
 
-            // Should timeout because the effect takes 200ms
-            assert!(matches!(result, Err(StoreError::ShutdownTimeout(_))), "Expected ShutdownTimeout, got: {result:?}");
+        #[tokio::test(start_paused = true)]
+        async fn test_rejected_effects_are_dead_lettered() {
+            let recorder = CapturingRecorder::new();
+            let config = StoreConfig::default()
+                .with_max_concurrent_effects(2)
+                .with_effect_limit_policy(EffectLimitPolicy::Reject)
+                .with_metrics_recorder(Arc::new(recorder.clone()));
+            let store = Store::with_config(JobState::default(), JobReducer, JobEnv::default(), config);
 
-            if let Err(StoreError::ShutdownTimeout(pending)) = result {
-                assert!(pending > 0, "Should report pending effects");
-            }
+            let mut handle = store.send(JobAction::Spawn(5)).await.unwrap();
+            handle.wait().await;
 
-            Ok(())
+            assert_eq!(store.state(|s| s.done).await, 2);
+            assert_eq!(recorder.counter("store.effects.throttled"), 3);
+            assert_eq!(store.dlq().len(), 3);
         }
+    }
 
-        #[tokio::test]
-        async fn test_shutdown_idempotent() -> Result<(), StoreError> {
-            let state = TestState { value: 0 };
-            let store = Store::new(state, TestReducer, TestEnv);
+    mod state_diff_tests {
+        use super::*;
+        use crate::state_diff::{Change, Diffable, StateDiffer};
+        use serde_json::json;
 
-            // First shutdown
-            let result1 = store.shutdown(Duration::from_secs(1)).await;
-            assert!(result1.is_ok());
+        impl Diffable for TestState {
+            fn diff(&self, before: &Self) -> Vec<Change> {
+                if self.value == before.value {
+                    return Vec::new();
+                }
+                vec![Change::modified("value", json!(before.value), json!(self.value))]
+            }
+        }
 
-            // Second shutdown should also succeed (already shut down)
-            let result2 = store.shutdown(Duration::from_secs(1)).await;
-            assert!(result2.is_ok());
+        #[tokio::test]
+        async fn test_serde_diff_broadcast_per_changing_action() {
+            let store = Store::new(TestState { value: 0 }, TestReducer, TestEnv)
+                .with_state_diff(StateDiffer::serde().describe_actions(|a: &TestAction| format!("{a:?}")));
+            let mut diffs = store.subscribe_state_diffs().unwrap();
+
+            store.send(TestAction::Increment).await.unwrap();
+            store.send(TestAction::NoOp).await.unwrap();
+            store.send(TestAction::Decrement).await.unwrap();
+
+            let first = diffs.recv().await.unwrap();
+            assert_eq!(first.action.as_deref(), Some("Increment"));
+            assert_eq!(first.changes, vec![Change::modified("value", json!(0), json!(1))]);
+            // NoOp changed nothing, so the next diff is the decrement
+            let second = diffs.recv().await.unwrap();
+            assert_eq!(second.action.as_deref(), Some("Decrement"));
+            assert!(diffs.try_recv().is_err());
+        }
 
-            Ok(())
+        #[tokio::test]
+        async fn test_diffable_strategy_and_disabled_store() {
+            let store = Store::new(TestState { value: 5 }, TestReducer, TestEnv)
+                .with_state_diff(StateDiffer::diffable());
+            let mut diffs = store.subscribe_state_diffs().unwrap();
+
+            store.send(TestAction::Decrement).await.unwrap();
+            let diff = diffs.recv().await.unwrap();
+            assert_eq!(diff.action, None);
+            assert_eq!(diff.changes, vec![Change::modified("value", json!(5), json!(4))]);
+
+            let plain = Store::new(TestState { value: 0 }, TestReducer, TestEnv);
+            assert!(plain.subscribe_state_diffs().is_none());
         }
     }
 
-    mod config_tests {
+    mod query_tests {
         use super::*;
-        use std::time::Duration;
+        use composable_rust_core::effect::QueryOperation;
+        use composable_rust_core::read_model::{
+            Filter, ReadModelError, ReadModelQuery, ReadModelRow, ReadModelStore,
+        };
+        use composable_rust_testing::InMemoryReadModelStore;
+        use serde_json::json;
+        use std::future::Future;
+        use std::pin::Pin;
 
-        #[test]
-        fn test_store_config_default() {
-            let config = StoreConfig::default();
-            assert_eq!(config.dlq_max_size, 1000);
-            assert_eq!(config.default_shutdown_timeout, Duration::from_secs(30));
+        #[derive(Debug, Clone)]
+        enum ReadAction {
+            LoadOrder(String),
+            LoadOpenOrders,
+            OrderLoaded(Option<serde_json::Value>),
+            OrdersLoaded(Vec<String>),
+            QueryFailed(String),
         }
 
-        #[test]
-        fn test_store_config_builder_pattern() {
-            let custom_retry = RetryPolicy::new()
-                .with_max_attempts(10)
-                .with_initial_delay(Duration::from_millis(100));
-
-            let config = StoreConfig::default()
-                .with_dlq_max_size(500)
-                .with_retry_policy(custom_retry.clone())
-                .with_shutdown_timeout(Duration::from_secs(60));
+        #[derive(Debug, Clone, Default)]
+        struct ReadState {
+            order: Option<serde_json::Value>,
+            open_orders: Vec<String>,
+            error: Option<String>,
+        }
 
-            assert_eq!(config.dlq_max_size, 500);
-            assert_eq!(config.default_shutdown_timeout, Duration::from_secs(60));
+        #[derive(Clone)]
+        struct ReadEnv {
+            read_models: Arc<dyn ReadModelStore>,
         }
 
-        #[test]
-        fn test_store_config_new() {
-            let retry_policy = RetryPolicy::new().with_max_attempts(5);
-            let config = StoreConfig::new(
-                2000,
-                retry_policy.clone(),
-                Duration::from_secs(45),
-            );
+        #[derive(Clone)]
+        struct ReadReducer;
 
-            assert_eq!(config.dlq_max_size, 2000);
-            assert_eq!(config.default_shutdown_timeout, Duration::from_secs(45));
+        impl Reducer for ReadReducer {
+            type State = ReadState;
+            type Action = ReadAction;
+            type Environment = ReadEnv;
+
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                match action {
+                    ReadAction::LoadOrder(key) => smallvec![Effect::Query(QueryOperation::Get {
+                        store: Arc::clone(&env.read_models),
+                        collection: "orders".to_string(),
+                        key,
+                        on_success: Box::new(|value| Some(ReadAction::OrderLoaded(value))),
+                        on_error: Box::new(|error| Some(ReadAction::QueryFailed(error.to_string()))),
+                    })],
+                    ReadAction::LoadOpenOrders => smallvec![Effect::Query(QueryOperation::Query {
+                        store: Arc::clone(&env.read_models),
+                        query: ReadModelQuery::new("orders")
+                            .filter(Filter::eq("status", "open"))
+                            .order_by_desc("total"),
+                        on_success: Box::new(|rows| {
+                            Some(ReadAction::OrdersLoaded(rows.into_iter().map(|row| row.key).collect()))
+                        }),
+                        on_error: Box::new(|error| Some(ReadAction::QueryFailed(error.to_string()))),
+                    })],
+                    ReadAction::OrderLoaded(order) => {
+                        state.order = order;
+                        smallvec![Effect::None]
+                    },
+                    ReadAction::OrdersLoaded(keys) => {
+                        state.open_orders = keys;
+                        smallvec![Effect::None]
+                    },
+                    ReadAction::QueryFailed(error) => {
+                        state.error = Some(error);
+                        smallvec![Effect::None]
+                    },
+                }
+            }
         }
 
         #[tokio::test]
-        async fn test_store_with_config_uses_custom_dlq_size() -> Result<(), StoreError> {
-            let config = StoreConfig::default().with_dlq_max_size(50);
-
-            let state = TestState { value: 0 };
-            let store = Store::with_config(state, TestReducer, TestEnv, config);
+        async fn test_query_effects_feed_read_models_back() {
+            let read_models = InMemoryReadModelStore::new();
+            read_models.insert("orders", "o-1", json!({"status": "open", "total": 10}));
+            read_models.insert("orders", "o-2", json!({"status": "shipped", "total": 50}));
+            read_models.insert("orders", "o-3", json!({"status": "open", "total": 30}));
+            let env = ReadEnv {
+                read_models: Arc::new(read_models),
+            };
+            let store = Store::new(ReadState::default(), ReadReducer, env);
 
-            // Verify DLQ has correct capacity
-            let dlq = store.dlq();
-            assert_eq!(dlq.max_size(), 50);
+            let mut handle = store.send(ReadAction::LoadOrder("o-2".to_string())).await.unwrap();
+            handle.wait().await;
+            let mut handle = store.send(ReadAction::LoadOpenOrders).await.unwrap();
+            handle.wait().await;
 
-            Ok(())
+            let state = store.state(Clone::clone).await;
+            assert_eq!(state.order, Some(json!({"status": "shipped", "total": 50})));
+            assert_eq!(state.open_orders, ["o-3", "o-1"]);
+            assert_eq!(state.error, None);
         }
 
-        #[tokio::test]
-        async fn test_store_with_config_uses_custom_retry_policy() -> Result<(), StoreError> {
-            let custom_retry = RetryPolicy::new()
-                .with_max_attempts(2)
-                .with_initial_delay(Duration::from_millis(50));
+        /// Rejects every query as one it cannot express
+        struct RejectingReadModels;
 
-            let config = StoreConfig::default().with_retry_policy(custom_retry);
+        impl ReadModelStore for RejectingReadModels {
+            fn get(
+                &self,
+                _collection: &str,
+                _key: &str,
+            ) -> Pin<Box<dyn Future<Output = Result<Option<serde_json::Value>, ReadModelError>> + Send + '_>>
+            {
+                Box::pin(async { Ok(None) })
+            }
 
-            let state = TestState { value: 0 };
-            let store = Store::with_config(state, TestReducer, TestEnv, config);
+            fn put(
+                &self,
+                _collection: &str,
+                _key: &str,
+                _value: serde_json::Value,
+            ) -> Pin<Box<dyn Future<Output = Result<(), ReadModelError>> + Send + '_>> {
+                Box::pin(async { Ok(()) })
+            }
 
-            // Verify store was created successfully with custom config
-            // The retry policy is used internally by the store
-            // This test confirms the store accepts and uses the configuration
-            let _handle = store.send(TestAction::Increment).await?;
+            fn delete(
+                &self,
+                _collection: &str,
+                _key: &str,
+            ) -> Pin<Box<dyn Future<Output = Result<bool, ReadModelError>> + Send + '_>> {
+                Box::pin(async { Ok(false) })
+            }
 
-            Ok(())
+            fn query(
+                &self,
+                _query: &ReadModelQuery,
+            ) -> Pin<Box<dyn Future<Output = Result<Vec<ReadModelRow>, ReadModelError>> + Send + '_>> {
+                Box::pin(async { Err(ReadModelError::InvalidQuery("filters unsupported".to_string())) })
+            }
         }
 
         #[tokio::test]
-        async fn test_store_with_config_independent_instances() -> Result<(), StoreError> {
-            // Create two stores with different configurations
-            let config1 = StoreConfig::default().with_dlq_max_size(100);
-            let config2 = StoreConfig::default().with_dlq_max_size(200);
+        async fn test_handle_outcome_reports_failed_effects() {
+            let env = ReadEnv {
+                read_models: Arc::new(RejectingReadModels),
+            };
+            let store = Store::new(ReadState::default(), ReadReducer, env);
 
-            let store1 = Store::with_config(
-                TestState { value: 0 },
-                TestReducer,
-                TestEnv,
-                config1,
-            );
-            let store2 = Store::with_config(
-                TestState { value: 0 },
-                TestReducer,
-                TestEnv,
-                config2,
+            let mut handle = store.send(ReadAction::LoadOrder("o-1".to_string())).await.unwrap();
+            let outcome = handle.wait().await;
+            assert!(outcome.is_success());
+            assert_eq!(outcome.completed, 1);
+
+            let mut handle = store.send(ReadAction::LoadOpenOrders).await.unwrap();
+            let outcome = handle.wait().await;
+            assert_eq!(outcome.completed, 0);
+            assert_eq!(
+                outcome.failed,
+                [EffectFailure {
+                    effect: "query",
+                    operation: "query",
+                    error: "Invalid read model query: filters unsupported".to_string(),
+                }]
             );
+            assert!(matches!(outcome.into_result(), Err(StoreError::EffectsFailed(failed)) if failed.len() == 1));
 
-            // Verify each has independent configuration
-            assert_eq!(store1.dlq().max_size(), 100);
-            assert_eq!(store2.dlq().max_size(), 200);
+            // The on_error callback still ran
+            assert!(store.state(|s| s.error.is_some()).await);
+        }
+    }
 
-            Ok(())
+    mod supervision_tests {
+        use super::*;
+        use crate::supervision::SupervisionStrategy;
+
+        #[derive(Debug, Clone)]
+        enum CounterAction {
+            Add(u32),
+            AddThenPanic(u32),
         }
 
-        #[test]
-        fn test_store_config_clone() {
-            let config1 = StoreConfig::default().with_dlq_max_size(300);
-            let config2 = config1.clone();
+        #[derive(Clone)]
+        struct PanickyReducer;
 
-            assert_eq!(config1.dlq_max_size, config2.dlq_max_size);
-            assert_eq!(config1.default_shutdown_timeout, config2.default_shutdown_timeout);
+        impl Reducer for PanickyReducer {
+            type State = u32;
+            type Action = CounterAction;
+            type Environment = ();
+
+            #[allow(clippy::panic)] // Intentional panic to exercise supervision
+            fn reduce(
+                &self,
+                state: &mut Self::State,
+                action: Self::Action,
+                _env: &Self::Environment,
+            ) -> SmallVec<[Effect<Self::Action>; 4]> {
+                match action {
+                    CounterAction::Add(n) => *state += n,
+                    CounterAction::AddThenPanic(n) => {
+                        *state += n;
+                        panic!("counter overflow");
+                    },
+                }
+                smallvec![Effect::None]
+            }
         }
 
-        #[test]
-        fn test_store_config_chaining() {
-            // Test that builder methods can be chained
-            let config = StoreConfig::default()
-                .with_dlq_max_size(400)
-                .with_shutdown_timeout(Duration::from_secs(120))
-                .with_retry_policy(RetryPolicy::new().with_max_attempts(3));
+        fn supervised(strategy: SupervisionStrategy) -> Store<u32, CounterAction, (), PanickyReducer> {
+            let config = StoreConfig::default().with_supervision(strategy);
+            Store::with_config(0, PanickyReducer, (), config)
+        }
 
-            assert_eq!(config.dlq_max_size, 400);
-            assert_eq!(config.default_shutdown_timeout, Duration::from_secs(120));
+        #[tokio::test]
+        async fn test_restart_with_state_keeps_partial_update_and_serves() {
+            let store = supervised(SupervisionStrategy::RestartWithState);
+
+            assert!(store.send(CounterAction::AddThenPanic(5)).await.is_ok());
+            assert!(store.send(CounterAction::Add(1)).await.is_ok());
+
+            assert_eq!(store.state(|s| *s).await, 6);
+            let dead = store.dlq().drain();
+            assert_eq!(dead.len(), 1);
+            assert_eq!(dead[0].payload.operation, "reducer");
+            assert!(dead[0].payload.target.as_deref().is_some_and(|target| target.ends_with("CounterAction")));
+            assert!(matches!(dead[0].payload.error, FailureError::ReducerPanic(_)));
+            assert_eq!(dead[0].error_message, "counter overflow");
+        }
+
+        #[tokio::test]
+        async fn test_restart_from_snapshot_restores_last_good_state() {
+            let store = supervised(SupervisionStrategy::RestartFromSnapshot).with_state_checkpoints();
+
+            assert!(store.send(CounterAction::Add(2)).await.is_ok());
+            assert!(store.send(CounterAction::AddThenPanic(5)).await.is_ok());
+            assert_eq!(store.state(|s| *s).await, 2);
+
+            assert!(store.send(CounterAction::Add(1)).await.is_ok());
+            assert_eq!(store.state(|s| *s).await, 3);
+            assert_eq!(store.dlq().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_unsupervised_panic_propagates() {
+            let store = Store::new(0, PanickyReducer, ());
+            let result = tokio::spawn(async move { store.send(CounterAction::AddThenPanic(1)).await }).await;
+            assert!(result.is_err_and(|error| error.is_panic()));
         }
     }
 }
@@ -0,0 +1,685 @@
+//! Event bus subscriber that feeds external topics into a Store.
+//!
+//! [`EventBusListener`] owns the subscription loop that would otherwise be
+//! hand-written around [`EventBus::subscribe`]:
+//!
+//! - **Decoding**: a user-provided decoder, or an [`EventRegistry`] of typed
+//!   events, maps each [`SerializedEvent`] to an action (`Ok(None)` skips
+//!   events the Store does not care about)
+//! - **Reconnects**: failed subscriptions and ended streams are retried with
+//!   the backoff of a [`RetryPolicy`]
+//! - **Poison messages**: events that fail to decode, or whose action the Store
+//!   rejects, are pushed to a [`DeadLetterQueue`] instead of stopping the loop
+//! - **Graceful shutdown**: the event in flight is finished before the listener
+//!   stops; the listener also stops once the Store is shutting down
+//! - **Acknowledgment**: events come from [`EventBus::subscribe_with_ack`];
+//!   each is acked once delivered, skipped or dead-lettered, and nacked for
+//!   redelivery if the Store stopped before taking it
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::listener::EventBusListener;
+//!
+//! let listener = EventBusListener::new(event_bus, store.clone(), &["payment-events"], |event| {
+//!     match event.event_type.as_str() {
+//!         "PaymentCompleted" => bincode::deserialize(&event.data)
+//!             .map(|e| Some(SagaAction::PaymentCompleted(e)))
+//!             .map_err(|e| e.to_string()),
+//!         _ => Ok(None),
+//!     }
+//! });
+//!
+//! let handle = listener.spawn();
+//! // ...
+//! let stats = handle.shutdown().await?;
+//! ```
+
+use crate::{DeadLetterQueue, RetryPolicy, Store, StoreError};
+use composable_rust_core::event::{EventMetadata, SerializedEvent};
+use composable_rust_core::event_bus::{EventBus, EventBusError};
+use composable_rust_core::event_registry::EventRegistry;
+use composable_rust_core::reducer::Reducer;
+use futures::StreamExt;
+use std::future::Future;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+/// Errors that stop an [`EventBusListener`]
+#[derive(Error, Debug)]
+pub enum ListenerError {
+    /// Subscribing failed on every attempt allowed by the reconnect policy
+    #[error("Subscription failed after {attempts} attempts: {source}")]
+    Subscribe {
+        /// Number of consecutive failed attempts
+        attempts: u32,
+        /// Error from the last attempt
+        #[source]
+        source: EventBusError,
+    },
+
+    /// The listener task panicked or was cancelled
+    #[error("Listener task failed: {0}")]
+    TaskFailed(#[from] tokio::task::JoinError),
+}
+
+/// Counters describing what a listener has processed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListenerStats {
+    /// Events received from the bus
+    pub received: u64,
+    /// Events decoded and accepted by the Store
+    pub delivered: u64,
+    /// Events the decoder chose to ignore
+    pub skipped: u64,
+    /// Events routed to the dead letter queue
+    pub dead_lettered: u64,
+    /// Times the subscription was re-established
+    pub reconnects: u64,
+}
+
+/// Decoder turning bus events into Store actions
+type Decoder<A> = Arc<dyn Fn(&SerializedEvent) -> Result<Option<A>, String> + Send + Sync>;
+
+/// Subscribes to event bus topics and sends decoded events into a Store
+pub struct EventBusListener<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    event_bus: Arc<dyn EventBus>,
+    store: Store<S, A, E, R>,
+    topics: Vec<String>,
+    decoder: Decoder<A>,
+    reconnect_policy: RetryPolicy,
+    dlq: DeadLetterQueue<SerializedEvent>,
+}
+
+impl<S, A, E, R> EventBusListener<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E> + Clone + Send + Sync + 'static,
+    A: Send + Clone + 'static,
+    S: Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+{
+    /// Create a listener for `topics`
+    ///
+    /// The decoder returns `Ok(Some(action))` to deliver an event, `Ok(None)`
+    /// to skip it, and `Err(reason)` to dead-letter it.
+    ///
+    /// Defaults: the Store's default [`RetryPolicy`] for reconnects, and a
+    /// dead letter queue holding up to 1000 events.
+    pub fn new<F, Err>(
+        event_bus: Arc<dyn EventBus>,
+        store: Store<S, A, E, R>,
+        topics: &[&str],
+        decoder: F,
+    ) -> Self
+    where
+        F: Fn(&SerializedEvent) -> Result<Option<A>, Err> + Send + Sync + 'static,
+        Err: std::fmt::Display,
+    {
+        Self {
+            event_bus,
+            store,
+            topics: topics.iter().map(|topic| (*topic).to_string()).collect(),
+            decoder: Arc::new(move |event| decoder(event).map_err(|e| e.to_string())),
+            reconnect_policy: RetryPolicy::default(),
+            dlq: DeadLetterQueue::default(),
+        }
+    }
+
+    /// Create a listener that decodes events with a typed [`EventRegistry`]
+    ///
+    /// Registered event types are decoded into actions; events of any other
+    /// type are skipped, and events that fail to decode are dead-lettered.
+    pub fn with_registry(
+        event_bus: Arc<dyn EventBus>,
+        store: Store<S, A, E, R>,
+        topics: &[&str],
+        registry: EventRegistry<A>,
+    ) -> Self {
+        Self::new(event_bus, store, topics, move |event| registry.decode(event))
+    }
+
+    /// Set the backoff used between subscription attempts
+    ///
+    /// The listener gives up after `max_attempts` consecutive failed
+    /// subscriptions. A stream that ends is re-subscribed without counting
+    /// as a failure.
+    #[must_use]
+    pub fn with_reconnect_policy(mut self, policy: RetryPolicy) -> Self {
+        self.reconnect_policy = policy;
+        self
+    }
+
+    /// Route poison messages to `dlq` (e.g. one shared between listeners)
+    #[must_use]
+    pub fn with_dlq(mut self, dlq: DeadLetterQueue<SerializedEvent>) -> Self {
+        self.dlq = dlq;
+        self
+    }
+
+    /// Dead letter queue receiving poison messages
+    #[must_use]
+    pub fn dlq(&self) -> DeadLetterQueue<SerializedEvent> {
+        self.dlq.clone()
+    }
+
+    /// Run the listener on a background task
+    ///
+    /// Dropping the returned handle detaches the listener; use
+    /// [`ListenerHandle::shutdown`] to stop it.
+    #[must_use]
+    pub fn spawn(self) -> ListenerHandle {
+        let (shutdown, signal) = oneshot::channel::<()>();
+        let task = tokio::spawn(self.run_until(async move {
+            if signal.await.is_err() {
+                // Handle dropped without a shutdown request: keep running
+                std::future::pending::<()>().await;
+            }
+        }));
+
+        ListenerHandle { shutdown, task }
+    }
+
+    /// Run the listener until `shutdown` completes or the Store shuts down
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ListenerError::Subscribe`] when subscribing fails on every
+    /// attempt allowed by the reconnect policy.
+    #[allow(clippy::cognitive_complexity)] // Tracing and metrics macros inflate the score
+    pub async fn run_until<F>(self, shutdown: F) -> Result<ListenerStats, ListenerError>
+    where
+        F: Future<Output = ()>,
+    {
+        tokio::pin!(shutdown);
+        let topics: Vec<&str> = self.topics.iter().map(String::as_str).collect();
+        let mut stats = ListenerStats::default();
+        let mut failures: u32 = 0;
+
+        tracing::info!(?topics, "Event bus listener started");
+
+        loop {
+            let subscription = tokio::select! {
+                () = &mut shutdown => break,
+                result = self.event_bus.subscribe_with_ack(&topics) => result,
+            };
+
+            let mut stream = match subscription {
+                Ok(stream) => {
+                    failures = 0;
+                    stream
+                },
+                Err(error) => {
+                    failures += 1;
+                    if failures >= self.reconnect_policy.max_attempts() {
+                        tracing::error!(?topics, attempts = failures, error = %error, "Giving up on subscription");
+                        return Err(ListenerError::Subscribe {
+                            attempts: failures,
+                            source: error,
+                        });
+                    }
+                    tracing::warn!(?topics, attempt = failures, error = %error, "Subscription failed, retrying");
+                    let delay = self.reconnect_policy.delay_for_attempt(failures - 1);
+                    tokio::select! {
+                        () = &mut shutdown => break,
+                        () = tokio::time::sleep(delay) => continue,
+                    }
+                },
+            };
+
+            loop {
+                let item = tokio::select! {
+                    () = &mut shutdown => {
+                        tracing::info!(?stats, "Event bus listener stopped");
+                        return Ok(stats);
+                    },
+                    item = stream.next() => item,
+                };
+
+                match item {
+                    Some(Ok(delivery)) => {
+                        if !self.handle(&delivery.event, &mut stats).await {
+                            Self::settle(delivery.nack(true).await);
+                            tracing::info!(?stats, "Store shutting down, event bus listener stopped");
+                            return Ok(stats);
+                        }
+                        Self::settle(delivery.ack().await);
+                    },
+                    Some(Err(EventBusError::DeserializationFailed(reason))) => {
+                        // No event to dead-letter; the stream itself is still healthy
+                        tracing::warn!(reason = %reason, "Skipping undeliverable message");
+                        metrics::counter!("event_bus_listener.events", "outcome" => "undeliverable")
+                            .increment(1);
+                    },
+                    Some(Err(error)) => {
+                        tracing::warn!(error = %error, "Event stream failed, reconnecting");
+                        break;
+                    },
+                    None => {
+                        tracing::warn!("Event stream ended, reconnecting");
+                        break;
+                    },
+                }
+            }
+
+            stats.reconnects += 1;
+            metrics::counter!("event_bus_listener.reconnects").increment(1);
+            tokio::select! {
+                () = &mut shutdown => break,
+                () = tokio::time::sleep(self.reconnect_policy.delay_for_attempt(0)) => {},
+            }
+        }
+
+        tracing::info!(?stats, "Event bus listener stopped");
+        Ok(stats)
+    }
+
+    /// Log an acknowledgment the broker failed to record; the event may be redelivered
+    fn settle(result: Result<(), EventBusError>) {
+        if let Err(error) = result {
+            tracing::warn!(error = %error, "Failed to acknowledge event (it may be redelivered)");
+            metrics::counter!("event_bus_listener.ack_failures").increment(1);
+        }
+    }
+
+    /// Decode and deliver one event, returning `false` once the Store stops accepting actions
+    async fn handle(&self, event: &SerializedEvent, stats: &mut ListenerStats) -> bool {
+        stats.received += 1;
+
+        let reason = match (self.decoder)(event) {
+            Ok(None) => {
+                stats.skipped += 1;
+                metrics::counter!("event_bus_listener.events", "outcome" => "skipped").increment(1);
+                return true;
+            },
+            // Actions triggered by an event inherit its correlation and are caused by it
+            Ok(Some(action)) => match self
+                .store
+                .send_with_metadata(action, event.metadata.as_ref().map(EventMetadata::caused_by))
+                .await
+            {
+                Ok(_) => {
+                    stats.delivered += 1;
+                    metrics::counter!("event_bus_listener.events", "outcome" => "delivered")
+                        .increment(1);
+                    return true;
+                },
+                Err(StoreError::ShutdownInProgress | StoreError::MailboxClosed) => return false,
+                Err(error) => error.to_string(),
+            },
+            Err(reason) => reason,
+        };
+
+        tracing::warn!(event_type = %event.event_type, reason = %reason, "Routing event to dead letter queue");
+        metrics::counter!("event_bus_listener.events", "outcome" => "dead_lettered").increment(1);
+        stats.dead_lettered += 1;
+        self.dlq.push(event.clone(), reason, 0);
+        true
+    }
+}
+
+/// Handle to a listener started with [`EventBusListener::spawn`]
+#[derive(Debug)]
+pub struct ListenerHandle {
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<Result<ListenerStats, ListenerError>>,
+}
+
+impl ListenerHandle {
+    /// Whether the listener has stopped (on its own or after an error)
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Stop the listener after the event in flight and return its counters
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that stopped the listener, if it stopped on its own
+    /// with one, or [`ListenerError::TaskFailed`] if its task panicked.
+    pub async fn shutdown(self) -> Result<ListenerStats, ListenerError> {
+        // The listener may already have stopped on its own
+        let _ = self.shutdown.send(());
+        self.task.await?
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use composable_rust_core::event_bus::{Acknowledger, Delivery, DeliveryStream, EventStream};
+    use composable_rust_core::{effect::Effect, smallvec, SmallVec};
+    use composable_rust_testing::mocks::InMemoryEventBus;
+    use std::collections::VecDeque;
+    use std::pin::Pin;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    const TOPIC: &str = "numbers";
+
+    #[derive(Debug, Clone)]
+    struct SumReducer;
+
+    impl Reducer for SumReducer {
+        type State = Vec<u8>;
+        type Action = u8;
+        type Environment = ();
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            _env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            state.push(action);
+            smallvec![Effect::None]
+        }
+    }
+
+    /// Delivers `Number` events, skips `Noise`, rejects anything else
+    fn decode(event: &SerializedEvent) -> Result<Option<u8>, String> {
+        match event.event_type.as_str() {
+            "Number" => Ok(event.data.first().copied()),
+            "Noise" => Ok(None),
+            other => Err(format!("unknown event type {other}")),
+        }
+    }
+
+    fn event(event_type: &str, value: u8) -> SerializedEvent {
+        SerializedEvent::new(event_type.to_string(), vec![value], None)
+    }
+
+    fn fast_reconnects(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::new()
+            .with_max_attempts(max_attempts)
+            .with_initial_delay(Duration::from_millis(1))
+            .with_max_delay(Duration::from_millis(1))
+    }
+
+    async fn wait_for_subscriber(bus: &InMemoryEventBus) {
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while bus.subscriber_count(TOPIC) == 0 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    /// Bus whose subscriptions are scripted: each call pops the next outcome
+    struct ScriptedBus {
+        subscriptions: Mutex<VecDeque<Result<Vec<SerializedEvent>, EventBusError>>>,
+    }
+
+    impl EventBus for ScriptedBus {
+        fn publish(
+            &self,
+            _topic: &str,
+            _event: &SerializedEvent,
+        ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn subscribe(
+            &self,
+            _topics: &[&str],
+        ) -> Pin<Box<dyn Future<Output = Result<EventStream, EventBusError>> + Send + '_>> {
+            let next = self.subscriptions.lock().unwrap().pop_front();
+            Box::pin(async move {
+                match next {
+                    Some(Ok(events)) => {
+                        let stream: EventStream = Box::pin(futures::stream::iter(events.into_iter().map(Ok)));
+                        Ok(stream)
+                    },
+                    Some(Err(error)) => Err(error),
+                    // Script exhausted: a subscription that never yields
+                    None => {
+                        let stream: EventStream = Box::pin(futures::stream::pending());
+                        Ok(stream)
+                    },
+                }
+            })
+        }
+    }
+
+    /// Records how each delivery was settled
+    struct RecordingAcker {
+        event_type: String,
+        settled: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Acknowledger for RecordingAcker {
+        fn ack(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send>> {
+            self.settled.lock().unwrap().push(format!("ack {}", self.event_type));
+            Box::pin(async { Ok(()) })
+        }
+
+        fn nack(self: Box<Self>, requeue: bool) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send>> {
+            self.settled
+                .lock()
+                .unwrap()
+                .push(format!("nack {} requeue={requeue}", self.event_type));
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    /// Bus handing out its events once, as deliveries that must be acknowledged
+    struct AckingBus {
+        events: Mutex<Vec<SerializedEvent>>,
+        settled: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl AckingBus {
+        fn new(events: Vec<SerializedEvent>) -> Self {
+            Self {
+                events: Mutex::new(events),
+                settled: Arc::default(),
+            }
+        }
+    }
+
+    impl EventBus for AckingBus {
+        fn publish(
+            &self,
+            _topic: &str,
+            _event: &SerializedEvent,
+        ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn subscribe(
+            &self,
+            _topics: &[&str],
+        ) -> Pin<Box<dyn Future<Output = Result<EventStream, EventBusError>> + Send + '_>> {
+            Box::pin(async { Err(EventBusError::Other("acknowledged subscriptions only".to_string())) })
+        }
+
+        fn subscribe_with_ack(
+            &self,
+            _topics: &[&str],
+        ) -> Pin<Box<dyn Future<Output = Result<DeliveryStream, EventBusError>> + Send + '_>> {
+            let deliveries: Vec<_> = std::mem::take(&mut *self.events.lock().unwrap())
+                .into_iter()
+                .map(|event| {
+                    let acker = RecordingAcker {
+                        event_type: event.event_type.clone(),
+                        settled: Arc::clone(&self.settled),
+                    };
+                    Ok(Delivery::new(event, acker))
+                })
+                .collect();
+            Box::pin(async move {
+                let stream: DeliveryStream =
+                    Box::pin(futures::stream::iter(deliveries).chain(futures::stream::pending()));
+                Ok(stream)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delivers_decoded_events_in_order() {
+        let bus = Arc::new(InMemoryEventBus::new());
+        let store = Store::new(Vec::new(), SumReducer, ());
+        let handle = EventBusListener::new(bus.clone(), store.clone(), &[TOPIC], decode).spawn();
+        wait_for_subscriber(&bus).await;
+
+        for value in 1..=3 {
+            bus.publish(TOPIC, &event("Number", value)).await.unwrap();
+        }
+        bus.publish(TOPIC, &event("Noise", 9)).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let stats = handle.shutdown().await.unwrap();
+
+        assert_eq!(store.state(Clone::clone).await, vec![1, 2, 3]);
+        assert_eq!(stats.received, 4);
+        assert_eq!(stats.delivered, 3);
+        assert_eq!(stats.skipped, 1);
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Deposited(u8);
+
+    impl composable_rust_core::event::DomainEvent for Deposited {
+        const NAME: &'static str = "Deposited";
+        type Codec = composable_rust_core::codec::JsonCodec;
+    }
+
+    #[tokio::test]
+    async fn test_registry_decodes_typed_events() {
+        let bus = Arc::new(InMemoryEventBus::new());
+        let store = Store::new(Vec::new(), SumReducer, ());
+        let registry = EventRegistry::new().register(|deposit: Deposited| deposit.0).unwrap();
+        let listener = EventBusListener::with_registry(bus.clone(), store.clone(), &[TOPIC], registry);
+        let dlq = listener.dlq();
+        let handle = listener.spawn();
+        wait_for_subscriber(&bus).await;
+
+        let deposit = SerializedEvent::encode(&Deposited(4), None).unwrap();
+        bus.publish(TOPIC, &deposit).await.unwrap();
+        bus.publish(TOPIC, &event("Noise", 9)).await.unwrap();
+        bus.publish(TOPIC, &event("Deposited.v1", 1)).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let stats = handle.shutdown().await.unwrap();
+
+        assert_eq!(store.state(Clone::clone).await, vec![4]);
+        assert_eq!(stats.delivered, 1);
+        assert_eq!(stats.skipped, 1);
+        assert_eq!(stats.dead_lettered, 1);
+        assert_eq!(dlq.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_poison_message_goes_to_dlq_and_loop_continues() {
+        let bus = Arc::new(InMemoryEventBus::new());
+        let store = Store::new(Vec::new(), SumReducer, ());
+        let listener = EventBusListener::new(bus.clone(), store.clone(), &[TOPIC], decode);
+        let dlq = listener.dlq();
+        let handle = listener.spawn();
+        wait_for_subscriber(&bus).await;
+
+        bus.publish(TOPIC, &event("Garbage", 0)).await.unwrap();
+        bus.publish(TOPIC, &event("Number", 7)).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let stats = handle.shutdown().await.unwrap();
+
+        assert_eq!(store.state(Clone::clone).await, vec![7]);
+        assert_eq!(stats.dead_lettered, 1);
+        let dead = dlq.peek().unwrap();
+        assert_eq!(dead.payload.event_type, "Garbage");
+        assert!(dead.error_message.contains("unknown event type"));
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_failures_and_ended_streams() {
+        let bus = Arc::new(ScriptedBus {
+            subscriptions: Mutex::new(VecDeque::from([
+                Err(EventBusError::ConnectionFailed("broker down".to_string())),
+                Ok(vec![event("Number", 1)]),
+                Ok(vec![event("Number", 2)]),
+            ])),
+        });
+        let store = Store::new(Vec::new(), SumReducer, ());
+        let handle = EventBusListener::new(bus, store.clone(), &[TOPIC], decode)
+            .with_reconnect_policy(fast_reconnects(3))
+            .spawn();
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let stats = handle.shutdown().await.unwrap();
+
+        assert_eq!(store.state(Clone::clone).await, vec![1, 2]);
+        assert_eq!(stats.reconnects, 2);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let failure = || Err(EventBusError::ConnectionFailed("broker down".to_string()));
+        let bus = Arc::new(ScriptedBus {
+            subscriptions: Mutex::new(VecDeque::from([failure(), failure()])),
+        });
+        let store = Store::new(Vec::new(), SumReducer, ());
+        let listener = EventBusListener::new(bus, store, &[TOPIC], decode)
+            .with_reconnect_policy(fast_reconnects(2));
+
+        let result = listener.run_until(std::future::pending()).await;
+
+        assert!(matches!(result, Err(ListenerError::Subscribe { attempts: 2, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_stops_when_store_shuts_down() {
+        let bus = Arc::new(InMemoryEventBus::new());
+        let store = Store::new(Vec::new(), SumReducer, ());
+        let handle = EventBusListener::new(bus.clone(), store.clone(), &[TOPIC], decode).spawn();
+        wait_for_subscriber(&bus).await;
+
+        store.shutdown(Duration::from_secs(1)).await.unwrap();
+        bus.publish(TOPIC, &event("Number", 1)).await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while !handle.is_finished() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .unwrap();
+        let stats = handle.shutdown().await.unwrap();
+        assert_eq!(stats.delivered, 0);
+    }
+
+    #[tokio::test]
+    async fn test_acks_handled_events_and_nacks_on_shutdown() {
+        let bus = Arc::new(AckingBus::new(vec![
+            event("Number", 1),
+            event("Garbage", 0),
+            event("Noise", 9),
+        ]));
+        let store = Store::new(Vec::new(), SumReducer, ());
+        let handle = EventBusListener::new(bus.clone(), store.clone(), &[TOPIC], decode).spawn();
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        handle.shutdown().await.unwrap();
+
+        // Delivered, dead-lettered and skipped events are all done with
+        assert_eq!(*bus.settled.lock().unwrap(), ["ack Number", "ack Garbage", "ack Noise"]);
+
+        // An event the stopped Store never took goes back to the broker
+        let bus = Arc::new(AckingBus::new(vec![event("Number", 2)]));
+        store.shutdown(Duration::from_secs(1)).await.unwrap();
+        let stats = EventBusListener::new(bus.clone(), store, &[TOPIC], decode)
+            .run_until(std::future::pending())
+            .await
+            .unwrap();
+
+        assert_eq!(stats.delivered, 0);
+        assert_eq!(*bus.settled.lock().unwrap(), ["nack Number requeue=true"]);
+    }
+}
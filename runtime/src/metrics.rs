@@ -7,6 +7,19 @@
 //! - Reducer execution
 //! - Effect handling
 //!
+//! The Store emits its metrics through a pluggable [`MetricsRecorder`]:
+//! [`MetricsRsRecorder`] (default) forwards to the global `metrics` facade,
+//! [`NoopRecorder`] discards everything, and [`CapturingRecorder`] keeps
+//! samples in memory for assertions.
+//!
+//! To export over OTLP instead of Prometheus, enable the `otel` feature and
+//! call `otel::init_telemetry` rather than starting a [`MetricsServer`].
+//!
+//! Label values are [`MetricLabel`] handles (static strings or interned
+//! `Arc<str>`s), so hot paths such as retries and effect counters do not
+//! allocate per sample. Each Store pre-registers its retry attempt labels and
+//! interns stream prefixes, topics and cost kinds on first use.
+//!
 //! # Example
 //!
 //! ```rust,no_run
@@ -24,7 +37,10 @@
 
 use metrics::{describe_counter, describe_gauge, describe_histogram};
 use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use thiserror::Error;
 
@@ -131,7 +147,7 @@ impl MetricsServer {
 }
 
 /// Register all metric descriptions.
-fn register_metrics() {
+pub(crate) fn register_metrics() {
     // Event Store Metrics
     describe_counter!(
         "event_store_events_appended_total",
@@ -373,6 +389,381 @@ impl RetryMetrics {
     }
 }
 
+/// Label key/value pairs attached to a metric sample
+pub type Labels<'a> = &'a [(&'static str, MetricLabel)];
+
+/// Cheaply cloneable metric label value
+///
+/// Either a `&'static str` or an `Arc<str>` handed out by a
+/// [`LabelInterner`], so emitting a metric never allocates the label value.
+#[derive(Clone)]
+pub struct MetricLabel(LabelRepr);
+
+#[derive(Clone)]
+enum LabelRepr {
+    Static(&'static str),
+    Shared(Arc<str>),
+}
+
+impl MetricLabel {
+    /// Label backed by a static string
+    #[must_use]
+    pub const fn from_static(value: &'static str) -> Self {
+        Self(LabelRepr::Static(value))
+    }
+
+    /// The label value
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            LabelRepr::Static(value) => value,
+            LabelRepr::Shared(value) => value,
+        }
+    }
+
+    fn to_shared_string(&self) -> metrics::SharedString {
+        match &self.0 {
+            LabelRepr::Static(value) => metrics::SharedString::const_str(value),
+            LabelRepr::Shared(value) => metrics::SharedString::from(Arc::clone(value)),
+        }
+    }
+}
+
+impl From<&'static str> for MetricLabel {
+    fn from(value: &'static str) -> Self {
+        Self::from_static(value)
+    }
+}
+
+impl PartialEq for MetricLabel {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for MetricLabel {}
+
+impl std::hash::Hash for MetricLabel {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl std::fmt::Debug for MetricLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl std::fmt::Display for MetricLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Label value used once a [`LabelInterner`] is full
+pub const OVERFLOW_LABEL: MetricLabel = MetricLabel::from_static("other");
+
+/// Interns dynamic label values into shared [`MetricLabel`] handles
+///
+/// The first use of a value allocates; later uses clone an `Arc`. The number
+/// of distinct values is capped (1024 by default) to bound both memory and
+/// metric cardinality: values beyond the cap are reported as
+/// [`OVERFLOW_LABEL`].
+#[derive(Debug)]
+pub struct LabelInterner {
+    labels: RwLock<HashSet<Arc<str>>>,
+    max_labels: usize,
+}
+
+impl LabelInterner {
+    /// Create an interner holding at most `max_labels` distinct values
+    #[must_use]
+    pub fn new(max_labels: usize) -> Self {
+        Self {
+            labels: RwLock::new(HashSet::new()),
+            max_labels,
+        }
+    }
+
+    /// Shared handle for `value`
+    pub fn intern(&self, value: &str) -> MetricLabel {
+        let labels = self.labels.read().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(label) = labels.get(value) {
+            return MetricLabel(LabelRepr::Shared(Arc::clone(label)));
+        }
+        drop(labels);
+
+        let mut labels = self.labels.write().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(label) = labels.get(value) {
+            return MetricLabel(LabelRepr::Shared(Arc::clone(label)));
+        }
+        if labels.len() >= self.max_labels {
+            return OVERFLOW_LABEL;
+        }
+        let label: Arc<str> = Arc::from(value);
+        labels.insert(Arc::clone(&label));
+        MetricLabel(LabelRepr::Shared(label))
+    }
+
+    /// Handle for a `Cow` value, interning only owned strings
+    #[allow(clippy::ptr_arg)] // The variant decides whether to intern
+    pub fn intern_cow(&self, value: &Cow<'static, str>) -> MetricLabel {
+        match value {
+            Cow::Borrowed(value) => MetricLabel::from_static(value),
+            Cow::Owned(value) => self.intern(value),
+        }
+    }
+
+    /// Handle for the category of a stream id
+    ///
+    /// Stream ids conventionally look like `order-123`; the prefix up to the
+    /// first `-` (`order`) is a bounded label, the full id is not.
+    pub fn stream_prefix(&self, stream_id: &str) -> MetricLabel {
+        self.intern(stream_id.split_once('-').map_or(stream_id, |(prefix, _)| prefix))
+    }
+
+    /// Number of interned values
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.read().unwrap_or_else(std::sync::PoisonError::into_inner).len()
+    }
+
+    /// Whether no value has been interned yet
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for LabelInterner {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+/// Label handles a Store pre-registers at construction
+#[derive(Debug, Default)]
+pub(crate) struct StoreLabels {
+    /// Dynamic values: stream prefixes, topics, cost kinds
+    pub(crate) interner: LabelInterner,
+    /// Retry attempt numbers up to the configured maximum
+    attempts: Vec<MetricLabel>,
+}
+
+impl StoreLabels {
+    /// Pre-register attempt labels `0..=max_attempts`
+    pub(crate) fn new(max_attempts: u32) -> Self {
+        let interner = LabelInterner::default();
+        let attempts = (0..=max_attempts)
+            .map(|attempt| interner.intern(&attempt.to_string()))
+            .collect();
+        Self { interner, attempts }
+    }
+
+    /// Label for a retry attempt number
+    ///
+    /// Falls back to the interner when the retry policy was reloaded with
+    /// more attempts than were pre-registered.
+    pub(crate) fn attempt(&self, attempt: u32) -> MetricLabel {
+        usize::try_from(attempt)
+            .ok()
+            .and_then(|index| self.attempts.get(index))
+            .cloned()
+            .unwrap_or_else(|| self.interner.intern(&attempt.to_string()))
+    }
+}
+
+/// Destination for the metrics emitted by a Store.
+///
+/// The Store reports through a recorder instead of calling the `metrics`
+/// macros directly, so embedded users can route metrics elsewhere and tests
+/// can capture and assert on them. Inject one with
+/// [`StoreConfig::with_metrics_recorder`](crate::StoreConfig::with_metrics_recorder);
+/// the default is [`MetricsRsRecorder`].
+pub trait MetricsRecorder: Send + Sync + std::fmt::Debug {
+    /// Add `value` to a counter
+    fn increment_counter(&self, name: &'static str, labels: Labels<'_>, value: u64);
+
+    /// Record one histogram observation
+    fn record_histogram(&self, name: &'static str, labels: Labels<'_>, value: f64);
+
+    /// Set a gauge to `value`
+    fn set_gauge(&self, name: &'static str, labels: Labels<'_>, value: f64);
+}
+
+fn to_labels(labels: Labels<'_>) -> Vec<metrics::Label> {
+    labels
+        .iter()
+        .map(|(key, value)| metrics::Label::new(*key, value.to_shared_string()))
+        .collect()
+}
+
+/// Recorder forwarding to the global `metrics` facade (default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsRsRecorder;
+
+impl MetricsRecorder for MetricsRsRecorder {
+    fn increment_counter(&self, name: &'static str, labels: Labels<'_>, value: u64) {
+        metrics::counter!(name, to_labels(labels)).increment(value);
+    }
+
+    fn record_histogram(&self, name: &'static str, labels: Labels<'_>, value: f64) {
+        metrics::histogram!(name, to_labels(labels)).record(value);
+    }
+
+    fn set_gauge(&self, name: &'static str, labels: Labels<'_>, value: f64) {
+        metrics::gauge!(name, to_labels(labels)).set(value);
+    }
+}
+
+/// Recorder discarding every sample
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopRecorder;
+
+impl MetricsRecorder for NoopRecorder {
+    fn increment_counter(&self, _name: &'static str, _labels: Labels<'_>, _value: u64) {}
+
+    fn record_histogram(&self, _name: &'static str, _labels: Labels<'_>, _value: f64) {}
+
+    fn set_gauge(&self, _name: &'static str, _labels: Labels<'_>, _value: f64) {}
+}
+
+/// Kind of a captured metric sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    /// Counter increment
+    Counter,
+    /// Histogram observation
+    Histogram,
+    /// Gauge update
+    Gauge,
+}
+
+/// A metric sample captured by [`CapturingRecorder`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricEvent {
+    /// Kind of sample
+    pub kind: MetricKind,
+    /// Metric name
+    pub name: &'static str,
+    /// Labels attached to the sample
+    pub labels: Vec<(&'static str, MetricLabel)>,
+    /// Increment, observation, or new gauge value
+    pub value: f64,
+}
+
+impl MetricEvent {
+    /// Value of the label `key`, if present
+    #[must_use]
+    pub fn label(&self, key: &str) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Recorder keeping every sample in memory for inspection
+///
+/// Clones share the same buffer, so a clone can be injected into the Store
+/// while the test keeps another to assert on.
+///
+/// # Example
+///
+/// ```ignore
+/// let recorder = CapturingRecorder::new();
+/// let config = StoreConfig::default().with_metrics_recorder(Arc::new(recorder.clone()));
+/// let store = Store::with_config(state, reducer, env, config);
+///
+/// store.send(action).await?;
+/// assert_eq!(recorder.counter("store.commands.total"), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CapturingRecorder {
+    events: std::sync::Arc<std::sync::Mutex<Vec<MetricEvent>>>,
+}
+
+impl CapturingRecorder {
+    /// Create an empty recorder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All samples captured so far, in emission order
+    #[must_use]
+    pub fn events(&self) -> Vec<MetricEvent> {
+        self.lock().clone()
+    }
+
+    /// Samples of the metric `name`
+    #[must_use]
+    pub fn events_named(&self, name: &str) -> Vec<MetricEvent> {
+        self.lock()
+            .iter()
+            .filter(|event| event.name == name)
+            .cloned()
+            .collect()
+    }
+
+    /// Sum of all increments of the counter `name`, across labels
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Counter increments are u64
+    pub fn counter(&self, name: &str) -> u64 {
+        self.lock()
+            .iter()
+            .filter(|event| event.kind == MetricKind::Counter && event.name == name)
+            .map(|event| event.value as u64)
+            .sum()
+    }
+
+    /// Latest value of the gauge `name`, if it was ever set
+    #[must_use]
+    pub fn gauge(&self, name: &str) -> Option<f64> {
+        self.lock()
+            .iter()
+            .rev()
+            .find(|event| event.kind == MetricKind::Gauge && event.name == name)
+            .map(|event| event.value)
+    }
+
+    /// Discard all captured samples
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<MetricEvent>> {
+        self.events
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn push(&self, kind: MetricKind, name: &'static str, labels: Labels<'_>, value: f64) {
+        self.lock().push(MetricEvent {
+            kind,
+            name,
+            labels: labels.to_vec(),
+            value,
+        });
+    }
+}
+
+impl MetricsRecorder for CapturingRecorder {
+    #[allow(clippy::cast_precision_loss)] // Increments are far below 2^52
+    fn increment_counter(&self, name: &'static str, labels: Labels<'_>, value: u64) {
+        self.push(MetricKind::Counter, name, labels, value as f64);
+    }
+
+    fn record_histogram(&self, name: &'static str, labels: Labels<'_>, value: f64) {
+        self.push(MetricKind::Histogram, name, labels, value);
+    }
+
+    fn set_gauge(&self, name: &'static str, labels: Labels<'_>, value: f64) {
+        self.push(MetricKind::Gauge, name, labels, value);
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
 mod tests {
@@ -451,4 +842,40 @@ mod tests {
             assert!(rendered.contains("circuit_breaker_calls_total"));
         }
     }
+
+    #[test]
+    fn test_interner_shares_values_and_caps_cardinality() {
+        let interner = LabelInterner::new(2);
+
+        let first = interner.intern("orders");
+        let second = interner.intern("orders");
+        interner.intern("payments");
+
+        assert_eq!(first, second);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.intern("inventory"), OVERFLOW_LABEL);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_stream_prefix_and_cow_labels() {
+        let interner = LabelInterner::default();
+
+        assert_eq!(interner.stream_prefix("order-123").as_str(), "order");
+        assert_eq!(interner.stream_prefix("ledger").as_str(), "ledger");
+        assert_eq!(interner.intern_cow(&Cow::Borrowed("http")).as_str(), "http");
+        assert_eq!(interner.intern_cow(&Cow::Owned("llm".to_string())).as_str(), "llm");
+        // Static values bypass the interner
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    fn test_store_labels_preregister_attempts() {
+        let labels = StoreLabels::new(3);
+
+        assert_eq!(labels.interner.len(), 4);
+        assert_eq!(labels.attempt(2).as_str(), "2");
+        assert_eq!(labels.attempt(7).as_str(), "7");
+        assert_eq!(labels.interner.len(), 5);
+    }
 }
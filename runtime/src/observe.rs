@@ -0,0 +1,171 @@
+//! Observing derived state.
+//!
+//! [`Store::subscribe_actions`](crate::Store::subscribe_actions) reports what
+//! happened; UIs and caches usually care about what the state *is*.
+//! [`Store::subscribe_state`](crate::Store::subscribe_state) runs a selector
+//! after every reduction and publishes the selected slice through a watch
+//! channel whenever it changes (compared with `PartialEq`), like TCA's
+//! `store.publisher`.
+//!
+//! Selectors run while the reducer's write lock is held, so they should be
+//! cheap projections (a field, a count, a small struct).
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut open_orders = store.subscribe_state(|s: &OrderState| s.open_orders().count()).await;
+//!
+//! while let Some(count) = open_orders.changed().await {
+//!     badge.set(count);
+//! }
+//! ```
+
+use futures::{Stream, StreamExt};
+use std::sync::Mutex;
+use tokio::sync::watch;
+
+type Observer<S> = Box<dyn Fn(&S) -> bool + Send + Sync>;
+
+/// Selectors registered on a Store
+pub(crate) struct StateObservers<S> {
+    observers: Mutex<Vec<Observer<S>>>,
+}
+
+impl<S> StateObservers<S> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            observers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Register `selector`, returning a subscription seeded from `state`
+    pub(crate) fn observe<T, F>(&self, state: &S, selector: F) -> StateSubscription<T>
+    where
+        T: PartialEq + Send + Sync + 'static,
+        F: Fn(&S) -> T + Send + Sync + 'static,
+    {
+        let (sender, receiver) = watch::channel(selector(state));
+        self.lock().push(Box::new(move |state| {
+            if sender.is_closed() {
+                return false;
+            }
+            let next = selector(state);
+            sender.send_if_modified(|current| {
+                let changed = *current != next;
+                if changed {
+                    *current = next;
+                }
+                changed
+            });
+            true
+        }));
+        StateSubscription { receiver }
+    }
+
+    /// Run every selector against the new state, dropping unsubscribed ones
+    pub(crate) fn notify(&self, state: &S) {
+        let mut observers = self.lock();
+        if !observers.is_empty() {
+            observers.retain(|observer| observer(state));
+        }
+    }
+
+    /// Number of live subscriptions (as of the last notification)
+    pub(crate) fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Observer<S>>> {
+        self.observers.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<S> std::fmt::Debug for StateObservers<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateObservers")
+            .field("observers", &self.len())
+            .finish()
+    }
+}
+
+/// Receiver of a selected slice of Store state
+///
+/// Created by [`Store::subscribe_state`](crate::Store::subscribe_state).
+/// Dropping it unregisters the selector.
+#[derive(Debug)]
+pub struct StateSubscription<T> {
+    receiver: watch::Receiver<T>,
+}
+
+impl<T: Clone> StateSubscription<T> {
+    /// The latest selected value
+    #[must_use]
+    pub fn get(&self) -> T {
+        self.receiver.borrow().clone()
+    }
+
+    /// Wait until the selected value changes and return it
+    ///
+    /// Returns `None` once every Store handle has been dropped. Changes
+    /// made while the caller was not waiting are coalesced into the latest
+    /// value.
+    pub async fn changed(&mut self) -> Option<T> {
+        self.receiver.changed().await.ok()?;
+        Some(self.receiver.borrow_and_update().clone())
+    }
+
+    /// Convert into a [`Stream`] yielding the current value, then each change
+    pub fn into_stream(mut self) -> impl Stream<Item = T> + Send
+    where
+        T: Send + Sync + 'static,
+    {
+        let current = self.receiver.borrow_and_update().clone();
+        futures::stream::once(async move { current }).chain(futures::stream::unfold(
+            self,
+            |mut subscription| async move {
+                subscription.changed().await.map(|value| (value, subscription))
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_emits_only_on_change() {
+        let observers = StateObservers::new();
+        let mut parity = observers.observe(&0_u32, |n: &u32| n % 2);
+
+        observers.notify(&2);
+        observers.notify(&3);
+
+        assert_eq!(parity.changed().await, Some(1));
+        observers.notify(&5);
+        assert!(!parity.receiver.has_changed().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_subscription_is_unregistered() {
+        let observers = StateObservers::new();
+        let subscription = observers.observe(&0_u32, |n: &u32| *n);
+        assert_eq!(observers.len(), 1);
+
+        drop(subscription);
+        observers.notify(&1);
+
+        assert_eq!(observers.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_starts_with_current_value() {
+        let observers = StateObservers::new();
+        let stream = observers.observe(&7_u32, |n: &u32| *n).into_stream();
+        observers.notify(&8);
+        drop(observers);
+
+        assert_eq!(stream.collect::<Vec<_>>().await, vec![7, 8]);
+    }
+}
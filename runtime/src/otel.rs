@@ -0,0 +1,502 @@
+//! OpenTelemetry (OTLP) export of runtime metrics.
+//!
+//! Enabled by the `otel` feature. [`init_telemetry`] builds an OTLP metrics
+//! pipeline and installs an [`OtelRecorder`] as the global `metrics`
+//! recorder, so everything the runtime emits through the `metrics` facade is
+//! exported as OpenTelemetry instruments: Store metrics (through the default
+//! [`MetricsRsRecorder`](crate::metrics::MetricsRsRecorder)) as well as
+//! effect, retry, circuit breaker and DLQ metrics.
+//!
+//! # Mapping
+//!
+//! - Counters become monotonic `u64` counters, histograms `f64` histograms
+//!   and gauges observable `f64` gauges
+//! - `_total` / `.total` suffixes are dropped and a `_seconds` suffix becomes
+//!   the unit `s` (`store.reducer.duration_seconds` is exported as
+//!   `store.reducer.duration`, in seconds)
+//! - Labels become attributes; well-known labels get semantic names
+//!   (`type` → `effect.type`, `topic` → `messaging.destination.name`, ...),
+//!   others keep their key
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use composable_rust_runtime::otel::{init_telemetry, TelemetryConfig};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let telemetry = init_telemetry(
+//!     TelemetryConfig::new("checkout-service").with_endpoint("http://otel-collector:4317"),
+//! )?;
+//!
+//! // ... run stores ...
+//!
+//! telemetry.shutdown()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Meter, MeterProvider, MetricsError, ObservableGauge};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Name of the meter the runtime's instruments are created on
+const METER_NAME: &str = "composable-rust-runtime";
+
+/// Settings for [`init_telemetry`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    /// Reported as the `service.name` resource attribute
+    pub service_name: String,
+    /// OTLP gRPC endpoint of the collector
+    pub endpoint: String,
+    /// How often metrics are exported
+    pub export_interval: Duration,
+    /// Additional resource attributes (e.g. `deployment.environment`)
+    pub resource_attributes: Vec<(String, String)>,
+}
+
+impl TelemetryConfig {
+    /// Export to a collector on `localhost:4317` every 60 seconds
+    #[must_use]
+    pub fn new(service_name: impl Into<String>) -> Self {
+        Self {
+            service_name: service_name.into(),
+            endpoint: "http://localhost:4317".to_string(),
+            export_interval: Duration::from_secs(60),
+            resource_attributes: Vec::new(),
+        }
+    }
+
+    /// Set the OTLP gRPC endpoint
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    /// Set how often metrics are exported
+    #[must_use]
+    pub const fn with_export_interval(mut self, interval: Duration) -> Self {
+        self.export_interval = interval;
+        self
+    }
+
+    /// Add a resource attribute
+    #[must_use]
+    pub fn with_resource_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.resource_attributes.push((key.into(), value.into()));
+        self
+    }
+}
+
+/// Errors from [`init_telemetry`] and [`Telemetry::shutdown`]
+#[derive(Error, Debug)]
+pub enum TelemetryError {
+    /// The OTLP pipeline could not be built, flushed or shut down
+    #[error("OTLP metrics pipeline error: {0}")]
+    Pipeline(#[from] MetricsError),
+
+    /// Another global `metrics` recorder was installed first
+    #[error("A global metrics recorder is already installed")]
+    RecorderAlreadyInstalled,
+}
+
+/// Running OTLP metrics export, returned by [`init_telemetry`]
+///
+/// Dropping it keeps exporting; call [`Self::shutdown`] on exit so the last
+/// interval is flushed.
+#[derive(Debug)]
+pub struct Telemetry {
+    provider: SdkMeterProvider,
+}
+
+impl Telemetry {
+    /// The meter provider, for creating application instruments
+    #[must_use]
+    pub const fn meter_provider(&self) -> &SdkMeterProvider {
+        &self.provider
+    }
+
+    /// Flush pending metrics and stop exporting
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TelemetryError::Pipeline`] if the final export fails.
+    pub fn shutdown(self) -> Result<(), TelemetryError> {
+        self.provider.force_flush()?;
+        self.provider.shutdown()?;
+        Ok(())
+    }
+}
+
+/// Export runtime metrics over OTLP
+///
+/// Builds the OTLP metrics pipeline, sets it as the global OpenTelemetry
+/// meter provider and installs an [`OtelRecorder`] as the global `metrics`
+/// recorder. Must be called from within a Tokio runtime, and replaces
+/// [`MetricsServer`](crate::metrics::MetricsServer) (both install a global
+/// recorder).
+///
+/// # Errors
+///
+/// - [`TelemetryError::Pipeline`]: the exporter could not be created
+/// - [`TelemetryError::RecorderAlreadyInstalled`]: a global `metrics`
+///   recorder was installed earlier
+pub fn init_telemetry(config: TelemetryConfig) -> Result<Telemetry, TelemetryError> {
+    let mut attributes = vec![KeyValue::new("service.name", config.service_name)];
+    attributes.extend(
+        config
+            .resource_attributes
+            .into_iter()
+            .map(|(key, value)| KeyValue::new(key, value)),
+    );
+
+    let provider = opentelemetry_otlp::new_pipeline()
+        .metrics(opentelemetry_sdk::runtime::Tokio)
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(config.endpoint),
+        )
+        .with_resource(Resource::new(attributes))
+        .with_period(config.export_interval)
+        .build()?;
+
+    opentelemetry::global::set_meter_provider(provider.clone());
+    metrics::set_global_recorder(OtelRecorder::new(provider.meter(METER_NAME)))
+        .map_err(|_| TelemetryError::RecorderAlreadyInstalled)?;
+    crate::metrics::register_metrics();
+
+    tracing::info!(interval = ?config.export_interval, "OTLP metrics export started");
+    Ok(Telemetry { provider })
+}
+
+/// `metrics` recorder that forwards to OpenTelemetry instruments
+///
+/// Installed globally by [`init_telemetry`]; construct it directly to export
+/// through a meter provider configured elsewhere. Instruments are created on
+/// first use and shared by every label set of the same metric.
+pub struct OtelRecorder {
+    meter: Meter,
+    /// Descriptions and units registered via `describe_*`, by metric name
+    descriptions: Mutex<HashMap<String, (Option<Unit>, SharedString)>>,
+    counters: Mutex<HashMap<String, opentelemetry::metrics::Counter<u64>>>,
+    histograms: Mutex<HashMap<String, opentelemetry::metrics::Histogram<f64>>>,
+    gauges: Mutex<Gauges>,
+}
+
+/// Gauge values, observed by one observable gauge per metric name
+#[derive(Default)]
+struct Gauges {
+    values: HashMap<Key, Arc<GaugeValue>>,
+    series: HashMap<String, Arc<Mutex<Vec<Arc<GaugeValue>>>>>,
+    /// Kept alive so their callbacks stay registered
+    instruments: Vec<ObservableGauge<f64>>,
+}
+
+impl OtelRecorder {
+    /// Forward metrics to instruments created on `meter`
+    #[must_use]
+    pub fn new(meter: Meter) -> Self {
+        Self {
+            meter,
+            descriptions: Mutex::new(HashMap::new()),
+            counters: Mutex::new(HashMap::new()),
+            histograms: Mutex::new(HashMap::new()),
+            gauges: Mutex::new(Gauges::default()),
+        }
+    }
+
+    fn describe(&self, key: &KeyName, unit: Option<Unit>, description: SharedString) {
+        lock(&self.descriptions).insert(key.as_str().to_string(), (unit, description));
+    }
+
+    /// OpenTelemetry name, unit and description of the metric `name`
+    fn instrument(&self, name: &str) -> (String, Option<String>, Option<String>) {
+        let (otel_name, unit) = instrument_name(name);
+        let described = lock(&self.descriptions).get(name).cloned();
+        let (described_unit, description) = match described {
+            Some((unit, description)) => (unit, Some(description.to_string())),
+            None => (None, None),
+        };
+        let unit = unit
+            .map(str::to_string)
+            .or_else(|| described_unit.map(|unit| unit.as_canonical_label().to_string()))
+            .filter(|unit| !unit.is_empty());
+        (otel_name, unit, description)
+    }
+}
+
+impl std::fmt::Debug for OtelRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OtelRecorder").finish_non_exhaustive()
+    }
+}
+
+impl Recorder for OtelRecorder {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(&key, unit, description);
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(&key, unit, description);
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(&key, unit, description);
+    }
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        let counter = lock(&self.counters)
+            .entry(key.name().to_string())
+            .or_insert_with(|| {
+                let (name, unit, description) = self.instrument(key.name());
+                let mut builder = self.meter.u64_counter(name);
+                if let Some(unit) = unit {
+                    builder = builder.with_unit(opentelemetry::metrics::Unit::new(unit));
+                }
+                if let Some(description) = description {
+                    builder = builder.with_description(description);
+                }
+                builder.init()
+            })
+            .clone();
+        Counter::from_arc(Arc::new(OtelCounter {
+            counter,
+            attributes: attributes(key),
+        }))
+    }
+
+    fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        let mut gauges = lock(&self.gauges);
+        if let Some(value) = gauges.values.get(key) {
+            return Gauge::from_arc(Arc::clone(value));
+        }
+
+        let value = Arc::new(GaugeValue {
+            bits: AtomicU64::new(0f64.to_bits()),
+            attributes: attributes(key),
+        });
+        gauges.values.insert(key.clone(), Arc::clone(&value));
+
+        if let Some(series) = gauges.series.get(key.name()) {
+            lock(series).push(Arc::clone(&value));
+        } else {
+            let series = Arc::new(Mutex::new(vec![Arc::clone(&value)]));
+            let observed = Arc::clone(&series);
+            let (name, unit, description) = self.instrument(key.name());
+            let mut builder = self
+                .meter
+                .f64_observable_gauge(name)
+                .with_callback(move |observer| {
+                    for value in lock(&observed).iter() {
+                        observer.observe(value.get(), &value.attributes);
+                    }
+                });
+            if let Some(unit) = unit {
+                builder = builder.with_unit(opentelemetry::metrics::Unit::new(unit));
+            }
+            if let Some(description) = description {
+                builder = builder.with_description(description);
+            }
+            gauges.instruments.push(builder.init());
+            gauges.series.insert(key.name().to_string(), series);
+        }
+
+        Gauge::from_arc(value)
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        let histogram = lock(&self.histograms)
+            .entry(key.name().to_string())
+            .or_insert_with(|| {
+                let (name, unit, description) = self.instrument(key.name());
+                let mut builder = self.meter.f64_histogram(name);
+                if let Some(unit) = unit {
+                    builder = builder.with_unit(opentelemetry::metrics::Unit::new(unit));
+                }
+                if let Some(description) = description {
+                    builder = builder.with_description(description);
+                }
+                builder.init()
+            })
+            .clone();
+        Histogram::from_arc(Arc::new(OtelHistogram {
+            histogram,
+            attributes: attributes(key),
+        }))
+    }
+}
+
+/// A counter instrument bound to one label set
+struct OtelCounter {
+    counter: opentelemetry::metrics::Counter<u64>,
+    attributes: Vec<KeyValue>,
+}
+
+impl CounterFn for OtelCounter {
+    fn increment(&self, value: u64) {
+        self.counter.add(value, &self.attributes);
+    }
+
+    /// OpenTelemetry counters only accumulate, so absolute values are not exported
+    fn absolute(&self, _value: u64) {}
+}
+
+/// A histogram instrument bound to one label set
+struct OtelHistogram {
+    histogram: opentelemetry::metrics::Histogram<f64>,
+    attributes: Vec<KeyValue>,
+}
+
+impl HistogramFn for OtelHistogram {
+    fn record(&self, value: f64) {
+        self.histogram.record(value, &self.attributes);
+    }
+}
+
+/// Latest value of a gauge for one label set
+struct GaugeValue {
+    bits: AtomicU64,
+    attributes: Vec<KeyValue>,
+}
+
+impl GaugeValue {
+    fn get(&self) -> f64 {
+        f64::from_bits(self.bits.load(Ordering::Acquire))
+    }
+
+    fn update(&self, f: impl Fn(f64) -> f64) {
+        let _ = self
+            .bits
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |bits| {
+                Some(f(f64::from_bits(bits)).to_bits())
+            });
+    }
+}
+
+impl GaugeFn for GaugeValue {
+    fn increment(&self, value: f64) {
+        self.update(|current| current + value);
+    }
+
+    fn decrement(&self, value: f64) {
+        self.update(|current| current - value);
+    }
+
+    fn set(&self, value: f64) {
+        self.bits.store(value.to_bits(), Ordering::Release);
+    }
+}
+
+/// OpenTelemetry instrument name and unit for a `metrics` name
+fn instrument_name(name: &str) -> (String, Option<&'static str>) {
+    let name = name
+        .strip_suffix("_total")
+        .or_else(|| name.strip_suffix(".total"))
+        .unwrap_or(name);
+    match name.strip_suffix("_seconds") {
+        Some(base) => (base.to_string(), Some("s")),
+        None => (name.to_string(), None),
+    }
+}
+
+/// Semantic attribute key for a `metrics` label key
+fn attribute_key(label: &str) -> &str {
+    match label {
+        "type" => "effect.type",
+        "operation" => "effect.operation",
+        "topic" => "messaging.destination.name",
+        "stream" => "event_store.stream",
+        "action" => "store.action",
+        "attempt" => "retry.attempt",
+        "from" => "circuit_breaker.state.from",
+        "to" => "circuit_breaker.state.to",
+        other => other,
+    }
+}
+
+fn attributes(key: &Key) -> Vec<KeyValue> {
+    key.labels()
+        .map(|label| KeyValue::new(attribute_key(label.key()).to_string(), label.value().to_string()))
+        .collect()
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use opentelemetry_sdk::metrics::PeriodicReader;
+    use opentelemetry_sdk::metrics::data::{Gauge as GaugeData, Histogram as HistogramData, Sum};
+    use opentelemetry_sdk::testing::metrics::InMemoryMetricsExporter;
+
+    #[test]
+    fn test_instrument_names_follow_otel_conventions() {
+        assert_eq!(instrument_name("store.commands.total"), ("store.commands".to_string(), None));
+        assert_eq!(
+            instrument_name("circuit_breaker_calls_total"),
+            ("circuit_breaker_calls".to_string(), None)
+        );
+        assert_eq!(
+            instrument_name("store.reducer.duration_seconds"),
+            ("store.reducer.duration".to_string(), Some("s"))
+        );
+        assert_eq!(attribute_key("topic"), "messaging.destination.name");
+        assert_eq!(attribute_key("dependency"), "dependency");
+    }
+
+    // `force_flush` blocks until the reader's export task has run
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_recorder_exports_instruments() {
+        let exporter = InMemoryMetricsExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone(), opentelemetry_sdk::runtime::Tokio).build())
+            .build();
+        let recorder = OtelRecorder::new(provider.meter(METER_NAME));
+
+        metrics::with_local_recorder(&recorder, || {
+            metrics::counter!("store.effects.executed", "type" => "future").increment(2);
+            metrics::gauge!("store.effects.pending").set(3.0);
+            metrics::histogram!("store.reducer.duration_seconds").record(0.5);
+        });
+        provider.force_flush().unwrap();
+
+        let finished = exporter.get_finished_metrics().unwrap();
+        let metrics: Vec<_> = finished
+            .iter()
+            .flat_map(|resource| &resource.scope_metrics)
+            .flat_map(|scope| &scope.metrics)
+            .collect();
+        let find = |name: &str| metrics.iter().find(|metric| metric.name == name).expect(name);
+
+        let executed = find("store.effects.executed");
+        let sum = executed.data.as_any().downcast_ref::<Sum<u64>>().unwrap();
+        assert_eq!(sum.data_points[0].value, 2);
+        let (key, value) = sum.data_points[0].attributes.iter().next().unwrap();
+        assert_eq!((key.as_str(), value.as_str().as_ref()), ("effect.type", "future"));
+
+        let pending = find("store.effects.pending");
+        let gauge = pending.data.as_any().downcast_ref::<GaugeData<f64>>().unwrap();
+        assert!((gauge.data_points[0].value - 3.0).abs() < f64::EPSILON);
+
+        let duration = find("store.reducer.duration");
+        assert_eq!(duration.unit.as_str(), "s");
+        let histogram = duration.data.as_any().downcast_ref::<HistogramData<f64>>().unwrap();
+        assert_eq!(histogram.data_points[0].count, 1);
+    }
+}
@@ -0,0 +1,330 @@
+//! Relay publishing outbox entries to the event bus.
+//!
+//! [`OutboxRelay`] is the second half of the transactional outbox (see
+//! [`composable_rust_core::outbox`]): it polls an [`OutboxStore`] for pending
+//! entries, publishes them to the [`EventBus`] in order and marks them
+//! published.
+//!
+//! Delivery is **at-least-once**. Entries are only marked after the publish
+//! succeeds, so a crash or failure in between republishes them. To let
+//! consumers de-duplicate, the published event's `causation_id` is set to the
+//! entry's [`message_id`](OutboxEntry::message_id) (`{stream_id}@{version}`):
+//! the event bus copy is caused by the stored event. The stored event itself is
+//! left untouched.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_core::outbox::append_and_publish;
+//! use composable_rust_runtime::outbox::OutboxRelay;
+//!
+//! // Reducer: append + enqueue in one transaction
+//! smallvec![append_and_publish(outbox, stream_id, Some(version), events, "order-events", on_ok, on_err)]
+//!
+//! // Wiring: relay to the bus until shutdown
+//! let relay = OutboxRelay::new(event_store, event_bus).with_poll_interval(Duration::from_millis(200));
+//! tokio::spawn(relay.run_until(shutdown_signal()));
+//! ```
+
+use composable_rust_core::event::EventMetadata;
+use composable_rust_core::event_bus::{EventBus, EventBusError};
+use composable_rust_core::event_store::EventStoreError;
+use composable_rust_core::outbox::{OutboxEntry, OutboxStore};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Errors from a relay pass
+#[derive(Error, Debug)]
+pub enum OutboxRelayError {
+    /// Reading or updating the outbox failed
+    #[error("Outbox store error: {0}")]
+    Store(#[from] EventStoreError),
+
+    /// Publishing an entry failed; earlier entries of the batch were relayed
+    #[error("Failed to publish outbox message {message_id}: {source}")]
+    Publish {
+        /// De-duplication key of the entry that failed
+        message_id: String,
+        /// Error from the event bus
+        #[source]
+        source: EventBusError,
+    },
+}
+
+/// Polls an outbox and publishes pending entries to an event bus
+pub struct OutboxRelay {
+    store: Arc<dyn OutboxStore>,
+    bus: Arc<dyn EventBus>,
+    batch_size: usize,
+    poll_interval: Duration,
+}
+
+impl OutboxRelay {
+    /// Create a relay with a batch size of 100 and a 1 second poll interval
+    #[must_use]
+    pub fn new(store: Arc<dyn OutboxStore>, bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            store,
+            bus,
+            batch_size: 100,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the maximum number of entries relayed per pass
+    #[must_use]
+    pub const fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set how long to wait when the outbox is drained or a pass fails
+    #[must_use]
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Relay one batch of pending entries, returning how many were published
+    ///
+    /// Entries are published in sequence order and the pass stops at the first
+    /// failure, so ordering per stream is preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OutboxRelayError::Publish`] if an entry could not be published
+    /// (entries before it are still marked), or [`OutboxRelayError::Store`] if
+    /// the outbox could not be read or updated.
+    pub async fn relay_once(&self) -> Result<usize, OutboxRelayError> {
+        let entries = self.store.pending_outbox(self.batch_size).await?;
+
+        let mut published = Vec::with_capacity(entries.len());
+        let mut failure = None;
+        for entry in entries {
+            if let Err(source) = self.publish(&entry).await {
+                failure = Some(OutboxRelayError::Publish {
+                    message_id: entry.message_id(),
+                    source,
+                });
+                break;
+            }
+            published.push(entry.sequence);
+        }
+
+        let count = published.len();
+        if count > 0 {
+            self.store.mark_published(published).await?;
+            metrics::counter!("outbox.relay.published").increment(count as u64);
+        }
+
+        match failure {
+            Some(error) => {
+                metrics::counter!("outbox.relay.failures").increment(1);
+                Err(error)
+            },
+            None => Ok(count),
+        }
+    }
+
+    async fn publish(&self, entry: &OutboxEntry) -> Result<(), EventBusError> {
+        let mut event = entry.event.clone();
+        let metadata = event.metadata.get_or_insert_with(EventMetadata::default);
+        metadata.causation_id = Some(entry.message_id());
+
+        self.bus.publish(&entry.topic, &event).await
+    }
+
+    /// Relay continuously until `shutdown` completes
+    ///
+    /// Full batches are followed immediately by the next pass; otherwise the
+    /// relay waits for the poll interval. Failed passes are logged and retried
+    /// after the poll interval. Returns the total number of entries published.
+    pub async fn run_until<F>(self, shutdown: F) -> u64
+    where
+        F: Future<Output = ()>,
+    {
+        tokio::pin!(shutdown);
+        let mut total = 0u64;
+
+        loop {
+            let drained = match self.relay_once().await {
+                Ok(count) => {
+                    total += count as u64;
+                    count < self.batch_size
+                },
+                Err(error) => {
+                    tracing::warn!(error = %error, "Outbox relay pass failed");
+                    true
+                },
+            };
+
+            if drained {
+                tokio::select! {
+                    () = &mut shutdown => break,
+                    () = tokio::time::sleep(self.poll_interval) => {},
+                }
+            } else if futures::FutureExt::now_or_never(&mut shutdown).is_some() {
+                break;
+            }
+        }
+
+        tracing::info!(total, "Outbox relay stopped");
+        total
+    }
+}
+
+impl std::fmt::Debug for OutboxRelay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutboxRelay")
+            .field("batch_size", &self.batch_size)
+            .field("poll_interval", &self.poll_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use composable_rust_core::event::SerializedEvent;
+    use composable_rust_core::event_bus::EventStream;
+    use composable_rust_core::event_store::EventStore;
+    use composable_rust_core::stream::{StreamId, Version};
+    use composable_rust_testing::mocks::{InMemoryEventBus, InMemoryEventStore};
+    use futures::StreamExt;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn event(name: &str) -> SerializedEvent {
+        SerializedEvent::new(name.to_string(), vec![1, 2, 3], None)
+    }
+
+    /// Bus that fails every publish after the first `ok` ones
+    struct FailingBus {
+        ok: usize,
+        calls: AtomicUsize,
+    }
+
+    impl EventBus for FailingBus {
+        fn publish(
+            &self,
+            _topic: &str,
+            _event: &SerializedEvent,
+        ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                if call < self.ok {
+                    Ok(())
+                } else {
+                    Err(EventBusError::PublishFailed {
+                        topic: "orders".to_string(),
+                        reason: "broker down".to_string(),
+                    })
+                }
+            })
+        }
+
+        fn subscribe(
+            &self,
+            _topics: &[&str],
+        ) -> Pin<Box<dyn Future<Output = Result<EventStream, EventBusError>> + Send + '_>> {
+            Box::pin(async { Ok(Box::pin(futures::stream::empty()) as EventStream) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_with_outbox_is_atomic_with_events() {
+        let store = InMemoryEventStore::new();
+        let stream = StreamId::new("order-1");
+
+        store
+            .append_with_outbox(stream.clone(), Some(Version::new(0)), vec![event("A"), event("B")], "orders".into())
+            .await
+            .unwrap();
+        let conflict = store
+            .append_with_outbox(stream.clone(), Some(Version::new(0)), vec![event("C")], "orders".into())
+            .await;
+
+        assert!(matches!(conflict, Err(EventStoreError::ConcurrencyConflict { .. })));
+        assert_eq!(store.load_events(stream, None).await.unwrap().len(), 2);
+        let pending = store.pending_outbox(10).await.unwrap();
+        let ids: Vec<_> = pending.iter().map(OutboxEntry::message_id).collect();
+        assert_eq!(ids, vec!["order-1@0", "order-1@1"]);
+    }
+
+    #[tokio::test]
+    async fn test_relay_publishes_with_dedup_metadata_and_marks() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let bus = Arc::new(InMemoryEventBus::new());
+        let mut subscription = bus.subscribe(&["orders"]).await.unwrap();
+        store
+            .append_with_outbox(StreamId::new("order-1"), None, vec![event("A"), event("B")], "orders".into())
+            .await
+            .unwrap();
+
+        let relay = OutboxRelay::new(store.clone(), bus);
+        assert_eq!(relay.relay_once().await.unwrap(), 2);
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+
+        let first = subscription.next().await.unwrap().unwrap();
+        assert_eq!(first.event_type, "A");
+        assert_eq!(
+            first.metadata.unwrap().causation_id.as_deref(),
+            Some("order-1@0")
+        );
+        assert!(store.pending_outbox(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_relay_stops_at_first_failure_and_retries_rest() {
+        let store = Arc::new(InMemoryEventStore::new());
+        store
+            .append_with_outbox(
+                StreamId::new("order-1"),
+                None,
+                vec![event("A"), event("B"), event("C")],
+                "orders".into(),
+            )
+            .await
+            .unwrap();
+        let bus = Arc::new(FailingBus { ok: 1, calls: AtomicUsize::new(0) });
+
+        let result = OutboxRelay::new(store.clone(), bus).relay_once().await;
+
+        assert!(matches!(
+            result,
+            Err(OutboxRelayError::Publish { ref message_id, .. }) if message_id == "order-1@1"
+        ));
+        let pending: Vec<_> = store
+            .pending_outbox(10)
+            .await
+            .unwrap()
+            .iter()
+            .map(OutboxEntry::message_id)
+            .collect();
+        assert_eq!(pending, vec!["order-1@1", "order-1@2"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_run_until_drains_in_batches_and_stops() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let bus = Arc::new(InMemoryEventBus::new());
+        let events = (0..5).map(|i| event(&format!("E{i}"))).collect();
+        store
+            .append_with_outbox(StreamId::new("order-1"), None, events, "orders".into())
+            .await
+            .unwrap();
+
+        let relay = OutboxRelay::new(store.clone(), bus)
+            .with_batch_size(2)
+            .with_poll_interval(Duration::from_millis(50));
+        let total = relay
+            .run_until(tokio::time::sleep(Duration::from_millis(120)))
+            .await;
+
+        assert_eq!(total, 5);
+        assert!(store.pending_outbox(10).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,360 @@
+//! Keyed state split across independently locked partitions.
+//!
+//! A [`Store`] reduces every action under one `RwLock` over its whole state.
+//! When the state is a map of independent entities (accounts, carts,
+//! aggregates) that lock serializes reducers that never touch the same data.
+//! A [`PartitionedStore`] runs `N` Stores instead, each with its own state,
+//! lock and mailbox, and routes every action to one of them by a key the
+//! caller extracts from the action:
+//!
+//! - actions with the same key always reach the same partition, and its
+//!   mailbox reduces them in the order they were sent
+//! - actions with different keys may be reduced in parallel
+//! - actions fed back by effects are reduced by the partition whose reducer
+//!   produced the effect
+//!
+//! Each partition holds only the entities routed to it, so a reducer must
+//! not rely on seeing state for keys of other partitions.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::partition::PartitionedStore;
+//!
+//! let accounts = PartitionedStore::new(
+//!     16,
+//!     |action: &AccountAction| action.account_id().clone(),
+//!     |_partition| AccountsState::default(),
+//!     AccountReducer,
+//!     environment,
+//! );
+//!
+//! accounts.send(AccountAction::Deposit { account_id, amount }).await?;
+//! let balance = accounts
+//!     .state_for(&account_id, |state| state.balance(&account_id))
+//!     .await;
+//! ```
+
+use crate::{EffectHandle, Store, StoreConfig, StoreError};
+use composable_rust_core::reducer::Reducer;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Mailbox capacity given to partitions when the configuration sets none
+pub const DEFAULT_PARTITION_MAILBOX: usize = 1024;
+
+/// Extracts the routing key of an action
+type KeyExtractor<A, K> = Arc<dyn Fn(&A) -> K + Send + Sync>;
+
+/// `N` Stores sharing a reducer, with actions routed by key; see the [module documentation](self)
+pub struct PartitionedStore<S, A, E, R, K>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    partitions: Vec<Store<S, A, E, R>>,
+    key: KeyExtractor<A, K>,
+}
+
+impl<S, A, E, R, K> Clone for PartitionedStore<S, A, E, R, K>
+where
+    R: Reducer<State = S, Action = A, Environment = E> + Clone,
+    E: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            partitions: self.partitions.clone(),
+            key: Arc::clone(&self.key),
+        }
+    }
+}
+
+impl<S, A, E, R, K> std::fmt::Debug for PartitionedStore<S, A, E, R, K>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PartitionedStore")
+            .field("partitions", &self.partitions.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, A, E, R, K> PartitionedStore<S, A, E, R, K>
+where
+    R: Reducer<State = S, Action = A, Environment = E> + Clone + Send + Sync + 'static,
+    A: Send + Clone + 'static,
+    S: Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+    K: Hash,
+{
+    /// Create `partitions` Stores with the default configuration
+    ///
+    /// `initial_state` builds the state of each partition from its index.
+    /// A partition count of 0 is treated as 1.
+    #[must_use]
+    pub fn new(
+        partitions: usize,
+        key: impl Fn(&A) -> K + Send + Sync + 'static,
+        initial_state: impl FnMut(usize) -> S,
+        reducer: R,
+        environment: E,
+    ) -> Self {
+        Self::with_config(
+            partitions,
+            key,
+            initial_state,
+            reducer,
+            environment,
+            StoreConfig::default(),
+        )
+    }
+
+    /// Create `partitions` Stores sharing `config`
+    ///
+    /// Every partition gets its own dead letter queue, metrics and mailbox
+    /// from `config`. Partitions always run a mailbox, so that actions for
+    /// one key are reduced in send order; without `mailbox_capacity` it
+    /// holds [`DEFAULT_PARTITION_MAILBOX`] actions.
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)] // Taken by value like `Store::with_config`
+    pub fn with_config(
+        partitions: usize,
+        key: impl Fn(&A) -> K + Send + Sync + 'static,
+        mut initial_state: impl FnMut(usize) -> S,
+        reducer: R,
+        environment: E,
+        config: StoreConfig,
+    ) -> Self {
+        let config = if config.mailbox_capacity.is_some() {
+            config
+        } else {
+            config.with_mailbox(DEFAULT_PARTITION_MAILBOX)
+        };
+        let partitions = (0..partitions.max(1))
+            .map(|index| {
+                Store::with_config(
+                    initial_state(index),
+                    reducer.clone(),
+                    environment.clone(),
+                    config.clone(),
+                )
+            })
+            .collect();
+
+        Self {
+            partitions,
+            key: Arc::new(key),
+        }
+    }
+
+    /// Number of partitions
+    #[must_use]
+    pub fn partition_count(&self) -> usize {
+        self.partitions.len()
+    }
+
+    /// Index of the partition owning `key`
+    #[must_use]
+    pub fn partition_of(&self, key: &K) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        // The remainder is below the partition count, which fits in usize
+        #[allow(clippy::cast_possible_truncation)]
+        let index = (hasher.finish() % self.partitions.len() as u64) as usize;
+        index
+    }
+
+    /// The Store backing partition `index`, if it exists
+    #[must_use]
+    pub fn partition(&self, index: usize) -> Option<&Store<S, A, E, R>> {
+        self.partitions.get(index)
+    }
+
+    /// All partitions, by index
+    #[must_use]
+    pub fn partitions(&self) -> &[Store<S, A, E, R>] {
+        &self.partitions
+    }
+
+    /// Send an action to the partition owning its key
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Store::send`] from the owning partition.
+    pub async fn send(&self, action: A) -> Result<EffectHandle, StoreError> {
+        let index = self.partition_of(&(self.key)(&action));
+        self.partitions[index].send(action).await
+    }
+
+    /// Send an action with request-scoped metadata to the partition owning its key
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Store::send_with_metadata`] from the owning partition.
+    pub async fn send_with_metadata(
+        &self,
+        action: A,
+        metadata: Option<composable_rust_core::event::EventMetadata>,
+    ) -> Result<EffectHandle, StoreError> {
+        let index = self.partition_of(&(self.key)(&action));
+        self.partitions[index]
+            .send_with_metadata(action, metadata)
+            .await
+    }
+
+    /// Read the state of the partition owning `key`
+    pub async fn state_for<F, T>(&self, key: &K, f: F) -> T
+    where
+        F: FnOnce(&S) -> T,
+    {
+        self.partitions[self.partition_of(key)].state(f).await
+    }
+
+    /// Shut down every partition, waiting up to `timeout` for each
+    ///
+    /// Partitions shut down concurrently.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by a partition; the others still
+    /// shut down.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<(), StoreError> {
+        let results = futures::future::join_all(
+            self.partitions
+                .iter()
+                .map(|partition| partition.shutdown(timeout)),
+        )
+        .await;
+        results.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+mod tests {
+    use super::*;
+    use composable_rust_core::{SmallVec, effect::Effect, smallvec};
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone)]
+    enum AccountAction {
+        Deposit { account: String, amount: u64 },
+    }
+
+    #[derive(Debug, Default)]
+    struct Accounts {
+        balances: HashMap<String, u64>,
+        /// Amounts in the order they were applied, per account
+        history: HashMap<String, Vec<u64>>,
+    }
+
+    #[derive(Clone)]
+    struct AccountReducer;
+
+    impl Reducer for AccountReducer {
+        type State = Accounts;
+        type Action = AccountAction;
+        type Environment = ();
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            _env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            let AccountAction::Deposit { account, amount } = action;
+            *state.balances.entry(account.clone()).or_default() += amount;
+            state.history.entry(account).or_default().push(amount);
+            smallvec![Effect::None]
+        }
+    }
+
+    fn accounts(
+        partitions: usize,
+    ) -> PartitionedStore<Accounts, AccountAction, (), AccountReducer, String> {
+        PartitionedStore::new(
+            partitions,
+            |action: &AccountAction| match action {
+                AccountAction::Deposit { account, .. } => account.clone(),
+            },
+            |_| Accounts::default(),
+            AccountReducer,
+            (),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_actions_route_to_the_partition_owning_their_key() {
+        let store = accounts(4);
+        let keys: Vec<String> = (0..32).map(|i| format!("acct-{i}")).collect();
+
+        for key in &keys {
+            store
+                .send(AccountAction::Deposit {
+                    account: key.clone(),
+                    amount: 5,
+                })
+                .await
+                .unwrap();
+        }
+
+        for key in &keys {
+            let owner = store.partition_of(key);
+            for (index, partition) in store.partitions().iter().enumerate() {
+                let balance = partition.state(|s| s.balances.get(key).copied()).await;
+                assert_eq!(
+                    balance.is_some(),
+                    index == owner,
+                    "{key} in partition {index}"
+                );
+            }
+            assert_eq!(store.state_for(key, |s| s.balances[key]).await, 5);
+        }
+        // 32 keys over 4 partitions leave none of them empty
+        for partition in store.partitions() {
+            assert!(partition.state(|s| !s.balances.is_empty()).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_senders_keep_per_key_order() {
+        let store = accounts(3);
+        let senders: Vec<_> = (0..6)
+            .map(|sender| {
+                let store = store.clone();
+                tokio::spawn(async move {
+                    let account = format!("acct-{sender}");
+                    for amount in 1..=50 {
+                        store
+                            .send(AccountAction::Deposit {
+                                account: account.clone(),
+                                amount,
+                            })
+                            .await
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for sender in senders {
+            sender.await.unwrap();
+        }
+
+        for sender in 0..6 {
+            let account = format!("acct-{sender}");
+            let history = store
+                .state_for(&account, |s| s.history[&account].clone())
+                .await;
+            assert_eq!(history, (1..=50).collect::<Vec<_>>());
+        }
+        store.shutdown(Duration::from_secs(1)).await.unwrap();
+    }
+
+    #[test]
+    fn test_zero_partitions_is_one() {
+        let store = accounts(0);
+        assert_eq!(store.partition_count(), 1);
+        assert_eq!(store.partition_of(&"any".to_string()), 0);
+    }
+}
@@ -0,0 +1,479 @@
+//! Dead letter queue persisted in an event stream.
+//!
+//! [`DeadLetterQueue`](crate::DeadLetterQueue) lives in memory and is lost on
+//! restart. [`PersistentDeadLetterQueue`] records the same entries as events
+//! in a dedicated stream of an [`EventStore`], so failures survive restarts
+//! and can be inspected from any process sharing the store.
+//!
+//! The stream is append-only: pushing, acknowledging, scheduling a retry and
+//! a failed reprocessing attempt each append one event, and the pending
+//! entries are rebuilt by replaying the stream. Acknowledged entries no longer
+//! show up in [`page`](PersistentDeadLetterQueue::page) but remain in the
+//! stream as an audit trail.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::persistent_dlq::PersistentDeadLetterQueue;
+//!
+//! let dlq = PersistentDeadLetterQueue::<OrderCommand>::new(event_store, "dead-letters.orders");
+//! let id = dlq.push(command, error.to_string(), attempts).await?;
+//! dlq.schedule_retry(id, Duration::from_secs(300)).await?;
+//!
+//! // Periodically reprocess entries whose retry is due
+//! let mut interval = tokio::time::interval(Duration::from_secs(60));
+//! loop {
+//!     interval.tick().await;
+//!     dlq.reprocess_due(|command| async move { submit(command).await.map_err(|e| e.to_string()) })
+//!         .await?;
+//! }
+//! ```
+
+use crate::DeadLetter;
+use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::event_store::{EventStore, EventStoreError};
+use composable_rust_core::stream::StreamId;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Identifier of a persisted dead letter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DeadLetterId(uuid::Uuid);
+
+impl DeadLetterId {
+    fn new() -> Self {
+        Self(uuid::Uuid::new_v4())
+    }
+}
+
+impl std::fmt::Display for DeadLetterId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A pending entry of a [`PersistentDeadLetterQueue`]
+#[derive(Debug, Clone)]
+pub struct PersistedDeadLetter<T> {
+    /// Identifier used to acknowledge or reschedule the entry
+    pub id: DeadLetterId,
+    /// The failed payload and its failure history
+    pub letter: DeadLetter<T>,
+    /// When the entry is due for reprocessing (nanoseconds since epoch)
+    pub retry_at: Option<u64>,
+}
+
+/// Outcome of [`PersistentDeadLetterQueue::reprocess_due`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReprocessReport {
+    /// Entries handled successfully and acknowledged
+    pub succeeded: usize,
+    /// Entries that failed again (their retry is cleared)
+    pub failed: usize,
+}
+
+/// Event recorded in the dead letter stream
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Record<T> {
+    Recorded {
+        id: DeadLetterId,
+        payload: T,
+        error_message: String,
+        retry_count: usize,
+        failed_at: u64,
+    },
+    Failed {
+        id: DeadLetterId,
+        error_message: String,
+        failed_at: u64,
+    },
+    Rescheduled {
+        id: DeadLetterId,
+        retry_at: u64,
+    },
+    Acknowledged {
+        id: DeadLetterId,
+    },
+}
+
+impl<T> Record<T> {
+    const fn event_type(&self) -> &'static str {
+        match self {
+            Self::Recorded { .. } => "DeadLetterRecorded.v1",
+            Self::Failed { .. } => "DeadLetterFailed.v1",
+            Self::Rescheduled { .. } => "DeadLetterRescheduled.v1",
+            Self::Acknowledged { .. } => "DeadLetterAcknowledged.v1",
+        }
+    }
+}
+
+/// Dead letter queue stored as events in an [`EventStore`] stream
+pub struct PersistentDeadLetterQueue<T> {
+    event_store: Arc<dyn EventStore>,
+    stream_id: StreamId,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<T> PersistentDeadLetterQueue<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    /// Create a queue stored in `stream_id`
+    ///
+    /// Use one stream per payload type; every process opening the same
+    /// stream sees the same entries.
+    pub fn new(event_store: Arc<dyn EventStore>, stream_id: impl Into<StreamId>) -> Self {
+        Self {
+            event_store,
+            stream_id: stream_id.into(),
+            _payload: PhantomData,
+        }
+    }
+
+    /// Record a failed operation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload cannot be serialized or the append fails.
+    pub async fn push(
+        &self,
+        payload: T,
+        error_message: String,
+        retry_count: usize,
+    ) -> Result<DeadLetterId, EventStoreError> {
+        let id = DeadLetterId::new();
+        self.append(Record::Recorded {
+            id,
+            payload,
+            error_message,
+            retry_count,
+            failed_at: now_nanos(),
+        })
+        .await?;
+
+        metrics::counter!("dlq.persistent.pushed").increment(1);
+        tracing::warn!(%id, stream_id = %self.stream_id, retry_count, "Operation added to persistent dead letter queue");
+        Ok(id)
+    }
+
+    /// Remove an entry from the pending entries
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the append fails.
+    pub async fn acknowledge(&self, id: DeadLetterId) -> Result<(), EventStoreError> {
+        self.append(Record::<T>::Acknowledged { id }).await?;
+        metrics::counter!("dlq.persistent.acknowledged").increment(1);
+        Ok(())
+    }
+
+    /// Mark an entry as due for reprocessing `after` from now
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the append fails.
+    pub async fn schedule_retry(
+        &self,
+        id: DeadLetterId,
+        after: Duration,
+    ) -> Result<(), EventStoreError> {
+        #[allow(clippy::cast_possible_truncation)] // Delays are far below 584 years
+        let retry_at = now_nanos().saturating_add(after.as_nanos() as u64);
+        self.append(Record::<T>::Rescheduled { id, retry_at }).await
+    }
+
+    /// Pending entries, oldest first, skipping `offset` and returning at most `limit`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream cannot be loaded or decoded.
+    pub async fn page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<PersistedDeadLetter<T>>, EventStoreError> {
+        Ok(self
+            .pending()
+            .await?
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect())
+    }
+
+    /// Number of pending entries
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream cannot be loaded or decoded.
+    pub async fn len(&self) -> Result<usize, EventStoreError> {
+        Ok(self.pending().await?.len())
+    }
+
+    /// Whether there are no pending entries
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream cannot be loaded or decoded.
+    pub async fn is_empty(&self) -> Result<bool, EventStoreError> {
+        Ok(self.pending().await?.is_empty())
+    }
+
+    /// Pending entries whose scheduled retry is due
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream cannot be loaded or decoded.
+    pub async fn due(&self) -> Result<Vec<PersistedDeadLetter<T>>, EventStoreError> {
+        let now = now_nanos();
+        let mut pending = self.pending().await?;
+        pending.retain(|entry| entry.retry_at.is_some_and(|at| at <= now));
+        Ok(pending)
+    }
+
+    /// Run `handler` on every due entry
+    ///
+    /// Entries the handler succeeds on are acknowledged. Failures are
+    /// recorded (incrementing `retry_count`) and the entry stays pending
+    /// without a scheduled retry until [`schedule_retry`](Self::schedule_retry)
+    /// is called again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream cannot be read or an outcome cannot be
+    /// recorded; entries processed before the error keep their outcome.
+    pub async fn reprocess_due<F, Fut>(
+        &self,
+        mut handler: F,
+    ) -> Result<ReprocessReport, EventStoreError>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+    {
+        let mut report = ReprocessReport::default();
+        for entry in self.due().await? {
+            match handler(entry.letter.payload).await {
+                Ok(()) => {
+                    self.acknowledge(entry.id).await?;
+                    report.succeeded += 1;
+                },
+                Err(error_message) => {
+                    tracing::warn!(id = %entry.id, error = %error_message, "Dead letter reprocessing failed");
+                    self.append(Record::<T>::Failed {
+                        id: entry.id,
+                        error_message,
+                        failed_at: now_nanos(),
+                    })
+                    .await?;
+                    report.failed += 1;
+                },
+            }
+        }
+        metrics::counter!("dlq.persistent.reprocessed", "outcome" => "succeeded")
+            .increment(report.succeeded as u64);
+        metrics::counter!("dlq.persistent.reprocessed", "outcome" => "failed")
+            .increment(report.failed as u64);
+        Ok(report)
+    }
+
+    /// Rebuild the pending entries by replaying the stream
+    async fn pending(&self) -> Result<Vec<PersistedDeadLetter<T>>, EventStoreError> {
+        let events = self
+            .event_store
+            .load_events(self.stream_id.clone(), None)
+            .await?;
+
+        let mut entries: Vec<Option<PersistedDeadLetter<T>>> = Vec::new();
+        let mut index: HashMap<DeadLetterId, usize> = HashMap::new();
+        for event in events {
+            let record: Record<T> = serde_json::from_slice(&event.data)
+                .map_err(|e| EventStoreError::SerializationError(e.to_string()))?;
+            match record {
+                Record::Recorded {
+                    id,
+                    payload,
+                    error_message,
+                    retry_count,
+                    failed_at,
+                } => {
+                    index.insert(id, entries.len());
+                    entries.push(Some(PersistedDeadLetter {
+                        id,
+                        letter: DeadLetter {
+                            payload,
+                            retry_count,
+                            error_message,
+                            first_failed_at: failed_at,
+                            last_failed_at: failed_at,
+                        },
+                        retry_at: None,
+                    }));
+                },
+                Record::Failed {
+                    id,
+                    error_message,
+                    failed_at,
+                } => {
+                    if let Some(entry) = index.get(&id).and_then(|&i| entries[i].as_mut()) {
+                        entry.letter.retry_count += 1;
+                        entry.letter.error_message = error_message;
+                        entry.letter.last_failed_at = failed_at;
+                        entry.retry_at = None;
+                    }
+                },
+                Record::Rescheduled { id, retry_at } => {
+                    if let Some(entry) = index.get(&id).and_then(|&i| entries[i].as_mut()) {
+                        entry.retry_at = Some(retry_at);
+                    }
+                },
+                Record::Acknowledged { id } => {
+                    if let Some(i) = index.remove(&id) {
+                        entries[i] = None;
+                    }
+                },
+            }
+        }
+        Ok(entries.into_iter().flatten().collect())
+    }
+
+    async fn append(&self, record: Record<T>) -> Result<(), EventStoreError> {
+        let data = serde_json::to_vec(&record)
+            .map_err(|e| EventStoreError::SerializationError(e.to_string()))?;
+        let event = SerializedEvent::new(record.event_type().to_string(), data, None);
+        self.event_store
+            .append_events(self.stream_id.clone(), None, vec![event])
+            .await?;
+        Ok(())
+    }
+}
+
+impl<T> Clone for PersistentDeadLetterQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            event_store: Arc::clone(&self.event_store),
+            stream_id: self.stream_id.clone(),
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for PersistentDeadLetterQueue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistentDeadLetterQueue")
+            .field("stream_id", &self.stream_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Current time in nanoseconds since the epoch, as used by [`DeadLetter`]
+fn now_nanos() -> u64 {
+    // Note: Truncation acceptable for nanosecond timestamps (wraps every ~584 years)
+    #[allow(clippy::cast_possible_truncation)]
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_nanos() as u64;
+    nanos
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use composable_rust_testing::mocks::InMemoryEventStore;
+
+    fn queue(store: &Arc<dyn EventStore>) -> PersistentDeadLetterQueue<String> {
+        PersistentDeadLetterQueue::new(Arc::clone(store), "dead-letters")
+    }
+
+    #[tokio::test]
+    async fn test_entries_survive_reopening_and_page() {
+        let store: Arc<dyn EventStore> = Arc::new(InMemoryEventStore::new());
+        let dlq = queue(&store);
+        for n in 0..5 {
+            dlq.push(format!("op-{n}"), "timeout".to_string(), 3)
+                .await
+                .unwrap();
+        }
+
+        // A new handle over the same stream sees the same entries
+        let reopened = queue(&store);
+        let page = reopened.page(1, 2).await.unwrap();
+
+        let payloads: Vec<_> = page.iter().map(|e| e.letter.payload.as_str()).collect();
+        assert_eq!(payloads, ["op-1", "op-2"]);
+        assert_eq!(page[0].letter.retry_count, 3);
+        assert_eq!(reopened.len().await.unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_acknowledge_removes_entry() {
+        let store: Arc<dyn EventStore> = Arc::new(InMemoryEventStore::new());
+        let dlq = queue(&store);
+        let first = dlq
+            .push("a".to_string(), "boom".to_string(), 1)
+            .await
+            .unwrap();
+        dlq.push("b".to_string(), "boom".to_string(), 1)
+            .await
+            .unwrap();
+
+        dlq.acknowledge(first).await.unwrap();
+
+        let pending = dlq.page(0, 10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].letter.payload, "b");
+    }
+
+    #[tokio::test]
+    async fn test_reprocess_due_acknowledges_successes_and_records_failures() {
+        let store: Arc<dyn EventStore> = Arc::new(InMemoryEventStore::new());
+        let dlq = queue(&store);
+        let ok = dlq
+            .push("ok".to_string(), "boom".to_string(), 1)
+            .await
+            .unwrap();
+        let bad = dlq
+            .push("bad".to_string(), "boom".to_string(), 1)
+            .await
+            .unwrap();
+        dlq.push("later".to_string(), "boom".to_string(), 1)
+            .await
+            .unwrap();
+        dlq.schedule_retry(ok, Duration::ZERO).await.unwrap();
+        dlq.schedule_retry(bad, Duration::ZERO).await.unwrap();
+
+        let report = dlq
+            .reprocess_due(|payload| async move {
+                if payload == "ok" {
+                    Ok(())
+                } else {
+                    Err("still failing".to_string())
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            ReprocessReport {
+                succeeded: 1,
+                failed: 1
+            }
+        );
+        let pending = dlq.page(0, 10).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        let bad = pending.iter().find(|e| e.id == bad).unwrap();
+        assert_eq!(bad.letter.retry_count, 2);
+        assert_eq!(bad.letter.error_message, "still failing");
+        assert!(bad.retry_at.is_none());
+        assert!(dlq.due().await.unwrap().is_empty());
+    }
+}
@@ -346,7 +346,7 @@ mod tests {
     #[test]
     fn test_retry_policy_max_delay_cap() {
         let policy = RetryPolicy::builder()
-            .initial_delay(Duration::from_millis(1000))
+            .initial_delay(Duration::from_secs(1))
             .multiplier(10.0)
             .max_delay(Duration::from_secs(2))
             .build();
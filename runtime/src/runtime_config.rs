@@ -0,0 +1,536 @@
+//! Hot-reloadable runtime configuration for Store instances.
+//!
+//! [`StoreConfig`](crate::StoreConfig) is consumed once at construction time. The knobs
+//! operators most often need to tune in production (retry policy, rate limits, circuit
+//! breaker thresholds, DLQ alerting thresholds) live in a [`StoreRuntimeConfig`] that is
+//! published through a [`ConfigHandle`]. The Store samples the handle on every operation,
+//! so an update takes effect for the next action or retry without a restart.
+//!
+//! # Example
+//!
+//! ```rust
+//! use composable_rust_runtime::runtime_config::{ConfigHandle, StoreRuntimeConfig};
+//! use composable_rust_runtime::StoreConfig;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let handle = ConfigHandle::new(StoreRuntimeConfig::default())?;
+//! let config = StoreConfig::default().with_config_handle(handle.clone());
+//!
+//! // Later, from an admin endpoint or a watcher task:
+//! handle.modify(|c| c.max_actions_per_second = Some(500))?;
+//! assert_eq!(handle.current().max_actions_per_second, Some(500));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Watchers
+//!
+//! [`spawn_file_watcher`] and [`spawn_env_watcher`] poll a JSON file or a set of
+//! environment variables and push validated updates into a handle. Invalid updates are
+//! logged and discarded; the previous configuration stays in effect.
+
+use crate::RetryPolicy;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Errors produced when validating or loading a runtime configuration.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// A configuration value is outside its allowed range.
+    #[error("Invalid configuration value for '{field}': {reason}")]
+    InvalidValue {
+        /// Name of the offending field
+        field: &'static str,
+        /// Why the value was rejected
+        reason: String,
+    },
+
+    /// The configuration source could not be read.
+    #[error("Failed to read configuration: {0}")]
+    Io(String),
+
+    /// The configuration source could not be parsed.
+    #[error("Failed to parse configuration: {0}")]
+    Parse(String),
+}
+
+/// Circuit breaker thresholds that can be changed at runtime.
+///
+/// Applied to any [`CircuitBreaker`](crate::CircuitBreaker) created with
+/// [`CircuitBreaker::with_config_handle`](crate::CircuitBreaker::with_config_handle).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerSettings {
+    /// Consecutive failures before the circuit opens
+    pub failure_threshold: usize,
+    /// Time to wait before moving from `Open` to `HalfOpen`
+    pub timeout: Duration,
+    /// Consecutive successes in `HalfOpen` required to close the circuit
+    pub success_threshold: usize,
+}
+
+impl Default for CircuitBreakerSettings {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            timeout: Duration::from_secs(60),
+            success_threshold: 2,
+        }
+    }
+}
+
+/// Runtime knobs that can be adjusted without restarting a Store.
+///
+/// Always validated before being published through a [`ConfigHandle`].
+#[derive(Debug, Clone)]
+pub struct StoreRuntimeConfig {
+    /// Retry policy used for `EventStore` and `EventBus` effects
+    pub retry_policy: RetryPolicy,
+    /// Circuit breaker thresholds
+    pub circuit_breaker: CircuitBreakerSettings,
+    /// Maximum actions accepted per second by `send()` (`None` = unlimited)
+    ///
+    /// Only external actions count; actions fed back by effects are exempt.
+    pub max_actions_per_second: Option<u32>,
+    /// DLQ usage ratio (0.0-1.0) above which the Store reports `Degraded`
+    pub dlq_degraded_ratio: f64,
+}
+
+impl Default for StoreRuntimeConfig {
+    fn default() -> Self {
+        Self {
+            retry_policy: RetryPolicy::default(),
+            circuit_breaker: CircuitBreakerSettings::default(),
+            max_actions_per_second: None,
+            dlq_degraded_ratio: 0.5,
+        }
+    }
+}
+
+impl StoreRuntimeConfig {
+    /// Validate all values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::InvalidValue`] for the first value that is out of range.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |field, reason: &str| {
+            Err(ConfigError::InvalidValue {
+                field,
+                reason: reason.to_string(),
+            })
+        };
+
+        if self.retry_policy.max_attempts() == 0 {
+            return invalid("retry_max_attempts", "must be at least 1");
+        }
+        if self.retry_policy.initial_delay() > self.retry_policy.max_delay() {
+            return invalid("retry_initial_delay", "must not exceed retry_max_delay");
+        }
+        let multiplier = self.retry_policy.backoff_multiplier();
+        if !multiplier.is_finite() || multiplier < 1.0 {
+            return invalid("retry_backoff_multiplier", "must be a finite value >= 1.0");
+        }
+        if self.circuit_breaker.failure_threshold == 0 {
+            return invalid("circuit_breaker_failure_threshold", "must be at least 1");
+        }
+        if self.circuit_breaker.success_threshold == 0 {
+            return invalid("circuit_breaker_success_threshold", "must be at least 1");
+        }
+        if self.max_actions_per_second == Some(0) {
+            return invalid("max_actions_per_second", "must be at least 1 (use None to disable)");
+        }
+        if !(0.0..=1.0).contains(&self.dlq_degraded_ratio) {
+            return invalid("dlq_degraded_ratio", "must be between 0.0 and 1.0");
+        }
+        Ok(())
+    }
+}
+
+/// Shared, validated handle to a [`StoreRuntimeConfig`].
+///
+/// Cloning a handle is cheap; all clones observe the same configuration.
+/// Readers call [`current()`](Self::current) per operation, writers call
+/// [`update()`](Self::update) or [`modify()`](Self::modify).
+#[derive(Debug, Clone)]
+pub struct ConfigHandle {
+    sender: Arc<watch::Sender<Arc<StoreRuntimeConfig>>>,
+}
+
+impl ConfigHandle {
+    /// Create a handle from an initial configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError`] if the initial configuration is invalid.
+    pub fn new(config: StoreRuntimeConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        let (sender, _) = watch::channel(Arc::new(config));
+        Ok(Self {
+            sender: Arc::new(sender),
+        })
+    }
+
+    /// Get the configuration currently in effect.
+    #[must_use]
+    pub fn current(&self) -> Arc<StoreRuntimeConfig> {
+        Arc::clone(&self.sender.borrow())
+    }
+
+    /// Replace the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError`] if the new configuration is invalid. The previous
+    /// configuration remains in effect.
+    pub fn update(&self, config: StoreRuntimeConfig) -> Result<(), ConfigError> {
+        config.validate()?;
+        self.sender.send_replace(Arc::new(config));
+        metrics::counter!("store.config.reloaded").increment(1);
+        tracing::info!("Store runtime configuration updated");
+        Ok(())
+    }
+
+    /// Modify a copy of the current configuration and publish it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError`] if the modified configuration is invalid.
+    pub fn modify<F>(&self, f: F) -> Result<(), ConfigError>
+    where
+        F: FnOnce(&mut StoreRuntimeConfig),
+    {
+        let mut config = (*self.current()).clone();
+        f(&mut config);
+        self.update(config)
+    }
+
+    /// Subscribe to configuration changes.
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<Arc<StoreRuntimeConfig>> {
+        self.sender.subscribe()
+    }
+}
+
+/// Partial configuration loaded from a file or the environment.
+///
+/// Every field is optional; only the fields that are present override the
+/// current configuration. Durations are expressed in milliseconds.
+///
+/// JSON example:
+///
+/// ```json
+/// { "retry_max_attempts": 3, "max_actions_per_second": 1000 }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeConfigOverrides {
+    /// Overrides the retry policy's maximum attempts
+    pub retry_max_attempts: Option<u32>,
+    /// Overrides the retry policy's initial delay (ms)
+    pub retry_initial_delay_ms: Option<u64>,
+    /// Overrides the retry policy's maximum delay (ms)
+    pub retry_max_delay_ms: Option<u64>,
+    /// Overrides the retry policy's backoff multiplier
+    pub retry_backoff_multiplier: Option<f64>,
+    /// Overrides the circuit breaker failure threshold
+    pub circuit_breaker_failure_threshold: Option<usize>,
+    /// Overrides the circuit breaker open timeout (ms)
+    pub circuit_breaker_timeout_ms: Option<u64>,
+    /// Overrides the circuit breaker success threshold
+    pub circuit_breaker_success_threshold: Option<usize>,
+    /// Overrides the action rate limit (0 disables the limit)
+    pub max_actions_per_second: Option<u32>,
+    /// Overrides the DLQ degraded ratio
+    pub dlq_degraded_ratio: Option<f64>,
+}
+
+impl RuntimeConfigOverrides {
+    /// Parse overrides from a JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Parse`] if the document is not valid JSON or
+    /// contains unknown fields.
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        serde_json::from_str(json).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+
+    /// Read overrides from environment variables named `{prefix}_{FIELD}`.
+    ///
+    /// For example, with prefix `ORDERS` the retry attempts are read from
+    /// `ORDERS_RETRY_MAX_ATTEMPTS`. Unset variables are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Parse`] if a variable is set but cannot be parsed.
+    pub fn from_env(prefix: &str) -> Result<Self, ConfigError> {
+        fn var<T: std::str::FromStr>(prefix: &str, name: &str) -> Result<Option<T>, ConfigError> {
+            let key = format!("{prefix}_{name}");
+            match std::env::var(&key) {
+                Ok(value) => value
+                    .trim()
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| ConfigError::Parse(format!("{key}={value}"))),
+                Err(_) => Ok(None),
+            }
+        }
+
+        Ok(Self {
+            retry_max_attempts: var(prefix, "RETRY_MAX_ATTEMPTS")?,
+            retry_initial_delay_ms: var(prefix, "RETRY_INITIAL_DELAY_MS")?,
+            retry_max_delay_ms: var(prefix, "RETRY_MAX_DELAY_MS")?,
+            retry_backoff_multiplier: var(prefix, "RETRY_BACKOFF_MULTIPLIER")?,
+            circuit_breaker_failure_threshold: var(prefix, "CIRCUIT_BREAKER_FAILURE_THRESHOLD")?,
+            circuit_breaker_timeout_ms: var(prefix, "CIRCUIT_BREAKER_TIMEOUT_MS")?,
+            circuit_breaker_success_threshold: var(prefix, "CIRCUIT_BREAKER_SUCCESS_THRESHOLD")?,
+            max_actions_per_second: var(prefix, "MAX_ACTIONS_PER_SECOND")?,
+            dlq_degraded_ratio: var(prefix, "DLQ_DEGRADED_RATIO")?,
+        })
+    }
+
+    /// Apply the overrides on top of a base configuration.
+    #[must_use]
+    pub fn apply_to(&self, base: &StoreRuntimeConfig) -> StoreRuntimeConfig {
+        let mut config = base.clone();
+        let mut retry = config.retry_policy.clone();
+        if let Some(attempts) = self.retry_max_attempts {
+            retry = retry.with_max_attempts(attempts);
+        }
+        if let Some(ms) = self.retry_initial_delay_ms {
+            retry = retry.with_initial_delay(Duration::from_millis(ms));
+        }
+        if let Some(ms) = self.retry_max_delay_ms {
+            retry = retry.with_max_delay(Duration::from_millis(ms));
+        }
+        if let Some(multiplier) = self.retry_backoff_multiplier {
+            retry = retry.with_backoff_multiplier(multiplier);
+        }
+        config.retry_policy = retry;
+
+        if let Some(threshold) = self.circuit_breaker_failure_threshold {
+            config.circuit_breaker.failure_threshold = threshold;
+        }
+        if let Some(ms) = self.circuit_breaker_timeout_ms {
+            config.circuit_breaker.timeout = Duration::from_millis(ms);
+        }
+        if let Some(threshold) = self.circuit_breaker_success_threshold {
+            config.circuit_breaker.success_threshold = threshold;
+        }
+        if let Some(limit) = self.max_actions_per_second {
+            config.max_actions_per_second = (limit > 0).then_some(limit);
+        }
+        if let Some(ratio) = self.dlq_degraded_ratio {
+            config.dlq_degraded_ratio = ratio;
+        }
+        config
+    }
+
+    /// Apply the overrides to the configuration held by `handle`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError`] if the resulting configuration is invalid.
+    pub fn apply(&self, handle: &ConfigHandle) -> Result<(), ConfigError> {
+        if *self == Self::default() {
+            return Ok(());
+        }
+        handle.update(self.apply_to(&handle.current()))
+    }
+}
+
+/// Watch a JSON file and apply its contents whenever it changes.
+///
+/// The file is polled every `interval`; its modification time is used to detect
+/// changes. The file contains [`RuntimeConfigOverrides`], which are applied on top
+/// of the configuration that was in effect when the watcher started, so removing a
+/// field from the file restores its original value.
+///
+/// Abort the returned task to stop watching.
+#[must_use]
+pub fn spawn_file_watcher(
+    handle: ConfigHandle,
+    path: impl Into<PathBuf>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    let path = path.into();
+    let base = handle.current();
+
+    tokio::spawn(async move {
+        let mut last_modified: Option<SystemTime> = None;
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            let modified = match tokio::fs::metadata(&path).await.and_then(|m| m.modified()) {
+                Ok(modified) => modified,
+                Err(error) => {
+                    tracing::debug!(path = %path.display(), %error, "Config file not readable");
+                    continue;
+                },
+            };
+            if last_modified == Some(modified) {
+                continue;
+            }
+            last_modified = Some(modified);
+
+            let result = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|e| ConfigError::Io(e.to_string()))
+                .and_then(|contents| RuntimeConfigOverrides::from_json(&contents))
+                .and_then(|overrides| handle.update(overrides.apply_to(&base)));
+
+            if let Err(error) = result {
+                metrics::counter!("store.config.reload_failed").increment(1);
+                tracing::warn!(path = %path.display(), %error, "Rejected config file update");
+            }
+        }
+    })
+}
+
+/// Watch environment variables with the given prefix and apply changes.
+///
+/// See [`RuntimeConfigOverrides::from_env`] for variable naming. Useful when the
+/// process environment is updated in place (e.g., by a sidecar).
+///
+/// Abort the returned task to stop watching.
+#[must_use]
+pub fn spawn_env_watcher(
+    handle: ConfigHandle,
+    prefix: impl Into<String>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    let prefix = prefix.into();
+    let base = handle.current();
+
+    tokio::spawn(async move {
+        let mut last: Option<RuntimeConfigOverrides> = None;
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            let overrides = match RuntimeConfigOverrides::from_env(&prefix) {
+                Ok(overrides) => overrides,
+                Err(error) => {
+                    metrics::counter!("store.config.reload_failed").increment(1);
+                    tracing::warn!(%prefix, %error, "Rejected environment config update");
+                    continue;
+                },
+            };
+            if last.as_ref() == Some(&overrides) {
+                continue;
+            }
+
+            match handle.update(overrides.apply_to(&base)) {
+                Ok(()) => last = Some(overrides),
+                Err(error) => {
+                    metrics::counter!("store.config.reload_failed").increment(1);
+                    tracing::warn!(%prefix, %error, "Rejected environment config update");
+                    last = Some(overrides);
+                },
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(StoreRuntimeConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_update_keeps_previous_config() {
+        let handle = ConfigHandle::new(StoreRuntimeConfig::default()).unwrap();
+
+        let result = handle.modify(|c| c.dlq_degraded_ratio = 2.0);
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidValue { field: "dlq_degraded_ratio", .. })
+        ));
+        assert!((handle.current().dlq_degraded_ratio - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_update_is_visible_to_clones_and_subscribers() {
+        let handle = ConfigHandle::new(StoreRuntimeConfig::default()).unwrap();
+        let clone = handle.clone();
+        let mut rx = handle.subscribe();
+
+        handle.modify(|c| c.max_actions_per_second = Some(10)).unwrap();
+
+        assert_eq!(clone.current().max_actions_per_second, Some(10));
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(rx.borrow_and_update().max_actions_per_second, Some(10));
+    }
+
+    #[test]
+    fn test_overrides_from_json_apply_only_present_fields() {
+        let overrides = RuntimeConfigOverrides::from_json(
+            r#"{ "retry_max_attempts": 2, "circuit_breaker_timeout_ms": 1500 }"#,
+        )
+        .unwrap();
+        let config = overrides.apply_to(&StoreRuntimeConfig::default());
+
+        assert_eq!(config.retry_policy.max_attempts(), 2);
+        assert_eq!(config.circuit_breaker.timeout, Duration::from_millis(1500));
+        assert_eq!(config.circuit_breaker.failure_threshold, 5);
+        assert_eq!(config.max_actions_per_second, None);
+    }
+
+    #[test]
+    fn test_overrides_reject_unknown_fields() {
+        let result = RuntimeConfigOverrides::from_json(r#"{ "retry_attempts": 2 }"#);
+        assert!(matches!(result, Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn test_zero_rate_limit_override_disables_limit() {
+        let base = StoreRuntimeConfig {
+            max_actions_per_second: Some(100),
+            ..StoreRuntimeConfig::default()
+        };
+        let overrides = RuntimeConfigOverrides {
+            max_actions_per_second: Some(0),
+            ..RuntimeConfigOverrides::default()
+        };
+        assert_eq!(overrides.apply_to(&base).max_actions_per_second, None);
+    }
+
+    #[tokio::test]
+    async fn test_file_watcher_applies_changes() {
+        let dir = std::env::temp_dir().join(format!(
+            "composable-rust-config-{}",
+            std::process::id()
+        ));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("runtime.json");
+        tokio::fs::write(&path, r#"{ "max_actions_per_second": 42 }"#)
+            .await
+            .unwrap();
+
+        let handle = ConfigHandle::new(StoreRuntimeConfig::default()).unwrap();
+        let mut rx = handle.subscribe();
+        let watcher = spawn_file_watcher(handle.clone(), &path, Duration::from_millis(10));
+
+        tokio::time::timeout(Duration::from_secs(5), rx.changed())
+            .await
+            .expect("watcher should publish the file contents")
+            .unwrap();
+        assert_eq!(handle.current().max_actions_per_second, Some(42));
+
+        watcher.abort();
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+}
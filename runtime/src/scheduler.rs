@@ -0,0 +1,641 @@
+//! Recurring actions dispatched to a Store on a schedule.
+//!
+//! A [`Scheduler`] sends actions into a Store at fixed intervals or on cron
+//! expressions. All timing decisions read the injected [`Clock`], so tests
+//! drive the scheduler with a `FixedClock`: advance the clock, then call
+//! [`Scheduler::tick`].
+//!
+//! - **Jitter**: [`ScheduledJob::with_jitter`] delays each occurrence by a
+//!   random amount up to a bound, spreading load across instances
+//! - **Catch-up**: when ticks are late (process paused, clock jumped),
+//!   [`CatchUp::Skip`] fires once and drops the other missed occurrences,
+//!   while [`CatchUp::FireAll`] fires once per missed occurrence
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::scheduler::{Schedule, ScheduledJob, Scheduler};
+//!
+//! let scheduler = Scheduler::new(store.clone(), Arc::new(SystemClock))
+//!     .with_job(ScheduledJob::new(
+//!         "expire-reservations",
+//!         Schedule::every(Duration::from_secs(60))?,
+//!         ReservationAction::ExpireStale,
+//!     ))
+//!     .with_job(
+//!         ScheduledJob::new("nightly-report", Schedule::cron("0 2 * * *")?, ReportAction::Generate)
+//!             .with_jitter(Duration::from_secs(300)),
+//!     );
+//!
+//! let handle = scheduler.spawn();
+//! // ...
+//! let stats = handle.shutdown().await?;
+//! ```
+
+use crate::{Store, StoreError};
+use chrono::{DateTime, Utc};
+use composable_rust_core::environment::Clock;
+use composable_rust_core::reducer::Reducer;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+/// Errors building a [`Schedule`]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// The cron expression could not be parsed
+    #[error("Invalid cron expression '{expression}': {reason}")]
+    InvalidCron {
+        /// The rejected expression
+        expression: String,
+        /// Parser error message
+        reason: String,
+    },
+
+    /// A fixed interval of zero would fire continuously
+    #[error("Schedule interval must be greater than zero")]
+    ZeroInterval,
+}
+
+/// When a job's occurrences fall
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// Every `interval`, counted from when the job is added to the scheduler
+    Interval(chrono::Duration),
+    /// On the occurrences of a cron expression, in UTC
+    Cron(Box<cron::Schedule>),
+}
+
+impl Schedule {
+    /// Fire every `interval`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScheduleError::ZeroInterval`] for a zero interval.
+    pub fn every(interval: Duration) -> Result<Self, ScheduleError> {
+        if interval.is_zero() {
+            return Err(ScheduleError::ZeroInterval);
+        }
+        Ok(Self::Interval(
+            chrono::Duration::from_std(interval).unwrap_or(chrono::Duration::MAX),
+        ))
+    }
+
+    /// Fire on a cron expression, evaluated in UTC
+    ///
+    /// Standard five-field expressions (`minute hour day month weekday`) fire
+    /// at second 0. Six- and seven-field expressions add a leading seconds
+    /// field and a trailing year field.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScheduleError::InvalidCron`] if the expression does not parse.
+    pub fn cron(expression: &str) -> Result<Self, ScheduleError> {
+        let full = if expression.split_whitespace().count() == 5 {
+            format!("0 {expression}")
+        } else {
+            expression.to_string()
+        };
+
+        cron::Schedule::from_str(&full)
+            .map(|schedule| Self::Cron(Box::new(schedule)))
+            .map_err(|e| ScheduleError::InvalidCron {
+                expression: expression.to_string(),
+                reason: e.to_string(),
+            })
+    }
+
+    /// First occurrence strictly after `after`, for a job anchored at `anchor`
+    fn next_after(&self, anchor: DateTime<Utc>, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Interval(interval) => {
+                if after < anchor {
+                    return anchor.checked_add_signed(*interval);
+                }
+                let interval_ns = interval.num_nanoseconds()?;
+                let elapsed_ns = (after - anchor).num_nanoseconds()?;
+                let periods = elapsed_ns / interval_ns + 1;
+                anchor.checked_add_signed(chrono::Duration::nanoseconds(
+                    periods.checked_mul(interval_ns)?,
+                ))
+            },
+            Self::Cron(schedule) => schedule.after(&after).next(),
+        }
+    }
+}
+
+/// What to do with occurrences missed because a tick came late
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CatchUp {
+    /// Fire once for the most recent missed occurrence and drop the rest
+    #[default]
+    Skip,
+    /// Fire once for every missed occurrence, oldest first
+    FireAll,
+}
+
+/// Builds the action for an occurrence, given its scheduled time
+type ActionFactory<A> = Arc<dyn Fn(DateTime<Utc>) -> A + Send + Sync>;
+
+/// A named action and the schedule it is sent on
+pub struct ScheduledJob<A> {
+    name: String,
+    schedule: Schedule,
+    action: ActionFactory<A>,
+    jitter: Duration,
+    catch_up: CatchUp,
+}
+
+impl<A> std::fmt::Debug for ScheduledJob<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScheduledJob")
+            .field("name", &self.name)
+            .field("schedule", &self.schedule)
+            .field("jitter", &self.jitter)
+            .field("catch_up", &self.catch_up)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A> ScheduledJob<A> {
+    /// Send a clone of `action` on every occurrence
+    ///
+    /// Defaults: no jitter, [`CatchUp::Skip`].
+    #[must_use]
+    pub fn new(name: impl Into<String>, schedule: Schedule, action: A) -> Self
+    where
+        A: Clone + Send + Sync + 'static,
+    {
+        Self::from_fn(name, schedule, move |_| action.clone())
+    }
+
+    /// Build the action for each occurrence from its scheduled time
+    #[must_use]
+    pub fn from_fn<F>(name: impl Into<String>, schedule: Schedule, action: F) -> Self
+    where
+        F: Fn(DateTime<Utc>) -> A + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            schedule,
+            action: Arc::new(action),
+            jitter: Duration::ZERO,
+            catch_up: CatchUp::default(),
+        }
+    }
+
+    /// Delay each occurrence by a random duration in `0..=max`
+    #[must_use]
+    pub const fn with_jitter(mut self, max: Duration) -> Self {
+        self.jitter = max;
+        self
+    }
+
+    /// Set how occurrences missed by late ticks are handled
+    #[must_use]
+    pub const fn with_catch_up(mut self, catch_up: CatchUp) -> Self {
+        self.catch_up = catch_up;
+        self
+    }
+}
+
+/// A job with its position in its schedule
+struct JobState<A> {
+    job: ScheduledJob<A>,
+    /// When the job was added (start of an interval schedule)
+    anchor: DateTime<Utc>,
+    /// Next occurrence and the jittered time it becomes due, `None` once exhausted
+    next: Option<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+/// Counters describing what a scheduler has dispatched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchedulerStats {
+    /// Actions accepted by the Store
+    pub fired: u64,
+    /// Actions the Store rejected (rate limit, budget, degradation)
+    pub rejected: u64,
+}
+
+/// Sends scheduled actions into a Store
+pub struct Scheduler<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    store: Store<S, A, E, R>,
+    clock: Arc<dyn Clock>,
+    jobs: Mutex<Vec<JobState<A>>>,
+    rng: Mutex<StdRng>,
+    poll_interval: Duration,
+    stats: Mutex<SchedulerStats>,
+}
+
+impl<S, A, E, R> Scheduler<S, A, E, R>
+where
+    R: Reducer<State = S, Action = A, Environment = E> + Clone + Send + Sync + 'static,
+    A: Send + Clone + 'static,
+    S: Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+{
+    /// Create a scheduler for `store` that reads time from `clock`
+    ///
+    /// Defaults: no jobs, a poll interval of one second, and jitter seeded
+    /// from system entropy.
+    pub fn new(store: Store<S, A, E, R>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            store,
+            clock,
+            jobs: Mutex::new(Vec::new()),
+            rng: Mutex::new(StdRng::from_entropy()),
+            poll_interval: Duration::from_secs(1),
+            stats: Mutex::new(SchedulerStats::default()),
+        }
+    }
+
+    /// Add a job; its first occurrence is computed from the clock's current time
+    #[must_use]
+    pub fn with_job(self, job: ScheduledJob<A>) -> Self {
+        let anchor = self.clock.now();
+        let mut state = JobState {
+            job,
+            anchor,
+            next: None,
+        };
+        let first = state.job.schedule.next_after(anchor, anchor);
+        state.next = first.map(|occurrence| (occurrence, self.jittered(&state.job, occurrence)));
+
+        self.jobs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(state);
+        self
+    }
+
+    /// Seed the jitter so runs are reproducible
+    #[must_use]
+    pub fn with_seed(self, seed: u64) -> Self {
+        *self
+            .rng
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// How often [`run_until`](Self::run_until) checks the clock (default: 1 second)
+    #[must_use]
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// When the named job next becomes due (including jitter)
+    #[must_use]
+    pub fn next_due(&self, name: &str) -> Option<DateTime<Utc>> {
+        self.jobs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .find(|state| state.job.name == name)
+            .and_then(|state| state.next.map(|(_, due)| due))
+    }
+
+    /// Counters accumulated so far
+    #[must_use]
+    pub fn stats(&self) -> SchedulerStats {
+        *self
+            .stats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Send the actions of every occurrence due at the clock's current time
+    ///
+    /// Actions are sent in order of their scheduled time. Returns the number
+    /// of actions the Store accepted; rejected actions are logged and counted
+    /// in [`stats`](Self::stats).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StoreError::ShutdownInProgress`] or [`StoreError::MailboxClosed`]
+    /// once the Store stops accepting actions; the remaining due actions are dropped.
+    pub async fn tick(&self) -> Result<usize, StoreError> {
+        let due = self.collect_due(self.clock.now());
+        let mut fired = 0;
+
+        for (name, occurrence, action) in due {
+            match self.store.send(action).await {
+                Ok(_) => {
+                    fired += 1;
+                    self.stats
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .fired += 1;
+                    tracing::debug!(job = %name, %occurrence, "Scheduled action sent");
+                    metrics::counter!("scheduler.actions", "outcome" => "fired").increment(1);
+                },
+                Err(error @ (StoreError::ShutdownInProgress | StoreError::MailboxClosed)) => {
+                    return Err(error);
+                },
+                Err(error) => {
+                    self.stats
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .rejected += 1;
+                    tracing::warn!(job = %name, %occurrence, error = %error, "Scheduled action rejected");
+                    metrics::counter!("scheduler.actions", "outcome" => "rejected").increment(1);
+                },
+            }
+        }
+
+        Ok(fired)
+    }
+
+    /// Advance every job past `now`, returning the occurrences to fire
+    fn collect_due(&self, now: DateTime<Utc>) -> Vec<(String, DateTime<Utc>, A)> {
+        let mut jobs = self
+            .jobs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut due = Vec::new();
+
+        for state in jobs.iter_mut() {
+            let mut missed = Vec::new();
+            while let Some((occurrence, at)) = state.next {
+                if at > now {
+                    break;
+                }
+                missed.push(occurrence);
+                let next = state.job.schedule.next_after(state.anchor, occurrence);
+                state.next =
+                    next.map(|occurrence| (occurrence, self.jittered(&state.job, occurrence)));
+            }
+
+            if state.job.catch_up == CatchUp::Skip && missed.len() > 1 {
+                tracing::info!(job = %state.job.name, skipped = missed.len() - 1, "Skipping missed occurrences");
+                missed.drain(..missed.len() - 1);
+            }
+
+            due.extend(missed.into_iter().map(|occurrence| {
+                (
+                    state.job.name.clone(),
+                    occurrence,
+                    (state.job.action)(occurrence),
+                )
+            }));
+        }
+
+        due.sort_by_key(|(_, occurrence, _)| *occurrence);
+        due
+    }
+
+    /// The time `occurrence` of `job` becomes due, after jitter
+    fn jittered(&self, job: &ScheduledJob<A>, occurrence: DateTime<Utc>) -> DateTime<Utc> {
+        if job.jitter.is_zero() {
+            return occurrence;
+        }
+        let max_ns = u64::try_from(job.jitter.as_nanos()).unwrap_or(u64::MAX);
+        let delay_ns = self
+            .rng
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .gen_range(0..=max_ns);
+        let delay = chrono::Duration::from_std(Duration::from_nanos(delay_ns))
+            .unwrap_or(chrono::Duration::MAX);
+        occurrence.checked_add_signed(delay).unwrap_or(occurrence)
+    }
+
+    /// Run the scheduler on a background task
+    ///
+    /// Dropping the returned handle detaches the scheduler; use
+    /// [`SchedulerHandle::shutdown`] to stop it.
+    #[must_use]
+    pub fn spawn(self) -> SchedulerHandle {
+        let (shutdown, signal) = oneshot::channel::<()>();
+        let task = tokio::spawn(self.run_until(async move {
+            if signal.await.is_err() {
+                // Handle dropped without a shutdown request: keep running
+                std::future::pending::<()>().await;
+            }
+        }));
+
+        SchedulerHandle { shutdown, task }
+    }
+
+    /// Tick every poll interval until `shutdown` completes or the Store shuts down
+    #[allow(clippy::cognitive_complexity)] // Tracing macros inflate the score
+    pub async fn run_until<F>(self, shutdown: F) -> SchedulerStats
+    where
+        F: Future<Output = ()>,
+    {
+        tokio::pin!(shutdown);
+        tracing::info!(poll_interval = ?self.poll_interval, "Scheduler started");
+
+        loop {
+            tokio::select! {
+                () = &mut shutdown => break,
+                () = tokio::time::sleep(self.poll_interval) => {},
+            }
+
+            if self.tick().await.is_err() {
+                tracing::info!("Store shutting down, scheduler stopped");
+                return self.stats();
+            }
+        }
+
+        tracing::info!(stats = ?self.stats(), "Scheduler stopped");
+        self.stats()
+    }
+}
+
+/// Handle to a scheduler started with [`Scheduler::spawn`]
+#[derive(Debug)]
+pub struct SchedulerHandle {
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<SchedulerStats>,
+}
+
+impl SchedulerHandle {
+    /// Whether the scheduler has stopped (after the Store shut down)
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Stop the scheduler after the tick in flight and return its counters
+    ///
+    /// # Errors
+    ///
+    /// Returns the join error if the scheduler task panicked.
+    pub async fn shutdown(self) -> Result<SchedulerStats, tokio::task::JoinError> {
+        // The scheduler may already have stopped on its own
+        let _ = self.shutdown.send(());
+        self.task.await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use composable_rust_core::{SmallVec, effect::Effect, smallvec};
+    use composable_rust_testing::mocks::FixedClock;
+
+    #[derive(Debug, Clone, Default)]
+    struct Ticks {
+        fired: Vec<String>,
+    }
+
+    #[derive(Debug, Clone)]
+    enum TickAction {
+        Fire(String),
+    }
+
+    #[derive(Debug, Clone)]
+    struct TickReducer;
+
+    impl Reducer for TickReducer {
+        type State = Ticks;
+        type Action = TickAction;
+        type Environment = ();
+
+        fn reduce(
+            &self,
+            state: &mut Ticks,
+            action: TickAction,
+            (): &(),
+        ) -> SmallVec<[Effect<TickAction>; 4]> {
+            let TickAction::Fire(label) = action;
+            state.fired.push(label);
+            smallvec![Effect::None]
+        }
+    }
+
+    fn start() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn setup() -> (Store<Ticks, TickAction, (), TickReducer>, Arc<FixedClock>) {
+        (
+            Store::new(Ticks::default(), TickReducer, ()),
+            Arc::new(FixedClock::new(start())),
+        )
+    }
+
+    fn labelled(name: &str, schedule: Schedule) -> ScheduledJob<TickAction> {
+        let name = name.to_string();
+        ScheduledJob::from_fn(name.clone(), schedule, move |at| {
+            TickAction::Fire(format!("{name}@{}", at.format("%H:%M")))
+        })
+    }
+
+    #[test]
+    fn test_schedule_rejects_invalid_definitions() {
+        assert_eq!(
+            Schedule::every(Duration::ZERO).unwrap_err(),
+            ScheduleError::ZeroInterval
+        );
+        assert!(matches!(
+            Schedule::cron("every tuesday"),
+            Err(ScheduleError::InvalidCron { .. })
+        ));
+        assert!(Schedule::cron("*/15 * * * *").is_ok());
+        assert!(Schedule::cron("30 */15 * * * *").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_interval_job_fires_as_clock_advances() {
+        let (store, clock) = setup();
+        let scheduler = Scheduler::new(store.clone(), clock.clone()).with_job(labelled(
+            "poll",
+            Schedule::every(Duration::from_secs(600)).unwrap(),
+        ));
+
+        assert_eq!(scheduler.tick().await.unwrap(), 0);
+        clock.advance(chrono::Duration::minutes(9));
+        assert_eq!(scheduler.tick().await.unwrap(), 0);
+        clock.advance(chrono::Duration::minutes(1));
+        assert_eq!(scheduler.tick().await.unwrap(), 1);
+        clock.advance(chrono::Duration::minutes(10));
+        assert_eq!(scheduler.tick().await.unwrap(), 1);
+
+        let fired = store.state(|s| s.fired.clone()).await;
+        assert_eq!(fired, ["poll@00:10", "poll@00:20"]);
+        assert_eq!(scheduler.stats().fired, 2);
+    }
+
+    #[tokio::test]
+    async fn test_cron_job_catch_up_policies() {
+        let (store, clock) = setup();
+        let cron = || Schedule::cron("*/15 * * * *").unwrap();
+        let scheduler = Scheduler::new(store.clone(), clock.clone())
+            .with_job(labelled("skip", cron()))
+            .with_job(labelled("all", cron()).with_catch_up(CatchUp::FireAll));
+
+        // Ticks stalled for 50 minutes: 00:15, 00:30 and 00:45 were missed
+        clock.advance(chrono::Duration::minutes(50));
+        assert_eq!(scheduler.tick().await.unwrap(), 4);
+
+        let fired = store.state(|s| s.fired.clone()).await;
+        assert_eq!(fired, ["all@00:15", "all@00:30", "skip@00:45", "all@00:45"]);
+        assert_eq!(
+            scheduler.next_due("skip"),
+            Some(start() + chrono::Duration::hours(1))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_jitter_delays_within_bound_and_is_seeded() {
+        let (store, clock) = setup();
+        let jitter = Duration::from_secs(30);
+        let due = |seed| {
+            Scheduler::new(store.clone(), clock.clone())
+                .with_seed(seed)
+                .with_job(
+                    labelled(
+                        "jittered",
+                        Schedule::every(Duration::from_secs(60)).unwrap(),
+                    )
+                    .with_jitter(jitter),
+                )
+                .next_due("jittered")
+                .unwrap()
+        };
+
+        let first = due(42);
+        assert_eq!(first, due(42));
+        let occurrence = start() + chrono::Duration::minutes(1);
+        assert!(first >= occurrence && first <= occurrence + chrono::Duration::seconds(30));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_stops_when_store_shuts_down() {
+        let (store, clock) = setup();
+        let scheduler = Scheduler::new(store.clone(), clock.clone())
+            .with_job(labelled(
+                "poll",
+                Schedule::every(Duration::from_secs(1)).unwrap(),
+            ))
+            .with_poll_interval(Duration::from_millis(5));
+        let handle = scheduler.spawn();
+
+        store.shutdown(Duration::from_secs(1)).await.unwrap();
+        clock.advance(chrono::Duration::seconds(1));
+
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while !handle.is_finished() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("scheduler should stop after the store shuts down");
+        assert_eq!(handle.shutdown().await.unwrap(), SchedulerStats::default());
+    }
+}
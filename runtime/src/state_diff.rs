@@ -0,0 +1,319 @@
+//! Structural diffs of Store state around each reduction.
+//!
+//! When a reducer misbehaves, the question is usually "what did this action
+//! actually change?". A Store configured with
+//! [`Store::with_state_diff`](crate::Store::with_state_diff) captures the
+//! state before every reduction, compares it with the state afterwards, and
+//! reports the changed paths: each non-empty [`StateDiff`] is logged at
+//! `debug` level and broadcast to
+//! [`Store::subscribe_state_diffs`](crate::Store::subscribe_state_diffs).
+//!
+//! Two strategies are available:
+//!
+//! - [`StateDiffer::serde`] serializes the state to JSON before and after and
+//!   compares the trees. Any `Serialize` state works, at the cost of two
+//!   serializations per action.
+//! - [`StateDiffer::diffable`] clones the state and calls a hand-written
+//!   [`Diffable`] implementation, for states that are expensive to serialize
+//!   or where only some fields matter.
+//!
+//! Diffing is meant for debug builds and tests: in release builds (without
+//! `debug_assertions`) it is skipped unless enabled with
+//! [`StateDiffer::in_release_builds`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::state_diff::StateDiffer;
+//!
+//! let store = Store::new(state, reducer, env)
+//!     .with_state_diff(StateDiffer::serde().describe_actions(|a: &OrderAction| format!("{a:?}")));
+//! let mut diffs = store.subscribe_state_diffs().expect("diffing enabled");
+//!
+//! store.send(OrderAction::Cancel { id }).await?;
+//! let diff = diffs.recv().await?;
+//! // diff.changes: [Change { path: "orders[0].status", before: Some("Placed"), after: Some("Cancelled") }]
+//! ```
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Write as _;
+use tokio::sync::broadcast;
+
+/// Number of diffs buffered per subscriber before the slowest one lags
+const DIFF_CHANNEL_CAPACITY: usize = 256;
+
+/// One changed value in the state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Location of the value, e.g. `orders[2].status`
+    pub path: String,
+    /// Value before the reduction (`None` if it was added)
+    pub before: Option<Value>,
+    /// Value after the reduction (`None` if it was removed)
+    pub after: Option<Value>,
+}
+
+impl Change {
+    /// A value present both before and after, with different contents
+    #[must_use]
+    pub fn modified(path: impl Into<String>, before: Value, after: Value) -> Self {
+        Self {
+            path: path.into(),
+            before: Some(before),
+            after: Some(after),
+        }
+    }
+
+    /// A value that did not exist before the reduction
+    #[must_use]
+    pub fn added(path: impl Into<String>, after: Value) -> Self {
+        Self {
+            path: path.into(),
+            before: None,
+            after: Some(after),
+        }
+    }
+
+    /// A value that no longer exists after the reduction
+    #[must_use]
+    pub fn removed(path: impl Into<String>, before: Value) -> Self {
+        Self {
+            path: path.into(),
+            before: Some(before),
+            after: None,
+        }
+    }
+}
+
+/// The changes made by one reduction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateDiff {
+    /// Description of the reduced action, if the differ describes actions
+    pub action: Option<String>,
+    /// Changed values, in path order for serde diffs
+    pub changes: Vec<Change>,
+}
+
+/// States that compute their own diffs
+///
+/// Implement this when serializing the whole state on every action is too
+/// expensive, or to report only the fields that matter. [`diff_values`] is
+/// available for comparing serializable fields.
+pub trait Diffable {
+    /// Changes from `before` to `self`
+    fn diff(&self, before: &Self) -> Vec<Change>;
+}
+
+/// Compare two JSON trees, reporting the paths whose values differ
+///
+/// Objects are compared key by key and arrays index by index; any other
+/// difference (including a change of type) is reported at the enclosing path.
+#[must_use]
+pub fn diff_values(before: &Value, after: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_into(&mut String::new(), before, after, &mut changes);
+    changes
+}
+
+fn diff_into(path: &mut String, before: &Value, after: &Value, changes: &mut Vec<Change>) {
+    match (before, after) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut keys: Vec<&String> = old
+                .keys()
+                .chain(new.keys().filter(|k| !old.contains_key(*k)))
+                .collect();
+            keys.sort();
+            for key in keys {
+                let len = path.len();
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+                match (old.get(key), new.get(key)) {
+                    (Some(old), Some(new)) => diff_into(path, old, new, changes),
+                    (Some(old), None) => changes.push(Change::removed(path.clone(), old.clone())),
+                    (None, Some(new)) => changes.push(Change::added(path.clone(), new.clone())),
+                    (None, None) => {},
+                }
+                path.truncate(len);
+            }
+        },
+        (Value::Array(old), Value::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                let len = path.len();
+                let _ = write!(path, "[{index}]");
+                match (old.get(index), new.get(index)) {
+                    (Some(old), Some(new)) => diff_into(path, old, new, changes),
+                    (Some(old), None) => changes.push(Change::removed(path.clone(), old.clone())),
+                    (None, Some(new)) => changes.push(Change::added(path.clone(), new.clone())),
+                    (None, None) => {},
+                }
+                path.truncate(len);
+            }
+        },
+        _ if before != after => changes.push(Change::modified(
+            path.clone(),
+            before.clone(),
+            after.clone(),
+        )),
+        _ => {},
+    }
+}
+
+/// Finishes a diff once the reducer has run
+type Pending<S> = Box<dyn FnOnce(&S) -> Vec<Change> + Send>;
+/// Captures what a diff needs from the state before the reducer runs
+type Capture<S> = Box<dyn Fn(&S) -> Pending<S> + Send + Sync>;
+/// Describes an action for [`StateDiff::action`]
+type Describe<A> = Box<dyn Fn(&A) -> String + Send + Sync>;
+
+/// How a Store diffs its state; see the [module documentation](self)
+pub struct StateDiffer<S, A> {
+    capture: Capture<S>,
+    describe: Option<Describe<A>>,
+    in_release_builds: bool,
+    sender: broadcast::Sender<StateDiff>,
+}
+
+impl<S, A> std::fmt::Debug for StateDiffer<S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateDiffer")
+            .field("describes_actions", &self.describe.is_some())
+            .field("in_release_builds", &self.in_release_builds)
+            .field("subscribers", &self.sender.receiver_count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: 'static, A> StateDiffer<S, A> {
+    /// Diff by comparing the state's JSON serialization before and after
+    ///
+    /// A state that fails to serialize is reported as `null`.
+    #[must_use]
+    pub fn serde() -> Self
+    where
+        S: Serialize,
+    {
+        Self::from_capture(Box::new(|before: &S| {
+            let before = serde_json::to_value(before).unwrap_or(Value::Null);
+            Box::new(move |after: &S| {
+                diff_values(&before, &serde_json::to_value(after).unwrap_or(Value::Null))
+            })
+        }))
+    }
+
+    /// Diff with the state's own [`Diffable`] implementation
+    ///
+    /// The state is cloned before every reduction.
+    #[must_use]
+    pub fn diffable() -> Self
+    where
+        S: Diffable + Clone + Send,
+    {
+        Self::from_capture(Box::new(|before: &S| {
+            let before = before.clone();
+            Box::new(move |after: &S| after.diff(&before))
+        }))
+    }
+
+    fn from_capture(capture: Capture<S>) -> Self {
+        let (sender, _) = broadcast::channel(DIFF_CHANNEL_CAPACITY);
+        Self {
+            capture,
+            describe: None,
+            in_release_builds: false,
+            sender,
+        }
+    }
+
+    /// Record a description of each action (typically its `Debug` output)
+    #[must_use]
+    pub fn describe_actions<F>(mut self, describe: F) -> Self
+    where
+        F: Fn(&A) -> String + Send + Sync + 'static,
+    {
+        self.describe = Some(Box::new(describe));
+        self
+    }
+
+    /// Diff in release builds too (skipped without `debug_assertions` by default)
+    #[must_use]
+    pub const fn in_release_builds(mut self) -> Self {
+        self.in_release_builds = true;
+        self
+    }
+
+    /// Start diffing one reduction; `None` when diffing is disabled in this build
+    pub(crate) fn begin(&self, state: &S, action: &A) -> Option<PendingDiff<S>> {
+        if !cfg!(debug_assertions) && !self.in_release_builds {
+            return None;
+        }
+        Some(PendingDiff {
+            action: self.describe.as_ref().map(|describe| describe(action)),
+            finish: (self.capture)(state),
+        })
+    }
+
+    /// Complete a diff, logging and broadcasting it if anything changed
+    pub(crate) fn finish(&self, pending: PendingDiff<S>, state: &S) {
+        let changes = (pending.finish)(state);
+        if changes.is_empty() {
+            return;
+        }
+        tracing::debug!(
+            action = pending.action.as_deref().unwrap_or("<undescribed>"),
+            changes = ?changes.iter().map(|c| c.path.as_str()).collect::<Vec<_>>(),
+            "State changed"
+        );
+        // No subscribers is not an error
+        let _ = self.sender.send(StateDiff {
+            action: pending.action,
+            changes,
+        });
+    }
+
+    /// Receive every subsequent non-empty diff
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<StateDiff> {
+        self.sender.subscribe()
+    }
+}
+
+/// A diff started before a reduction
+pub(crate) struct PendingDiff<S> {
+    action: Option<String>,
+    finish: Pending<S>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_values_reports_nested_paths() {
+        let before = json!({"count": 1, "orders": [{"status": "placed"}], "note": "x"});
+        let after = json!({"count": 2, "orders": [{"status": "shipped"}, {"status": "placed"}], "tag": true});
+
+        assert_eq!(
+            diff_values(&before, &after),
+            vec![
+                Change::modified("count", json!(1), json!(2)),
+                Change::removed("note", json!("x")),
+                Change::modified("orders[0].status", json!("placed"), json!("shipped")),
+                Change::added("orders[1]", json!({"status": "placed"})),
+                Change::added("tag", json!(true)),
+            ]
+        );
+        assert!(diff_values(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_type_change_reported_at_enclosing_path() {
+        let changes = diff_values(&json!({"value": [1]}), &json!({"value": 1}));
+        assert_eq!(
+            changes,
+            vec![Change::modified("value", json!([1]), json!(1))]
+        );
+    }
+}
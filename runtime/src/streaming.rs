@@ -0,0 +1,270 @@
+//! Streaming store actions to WebSocket (or other) connections.
+//!
+//! [`Store::subscribe_actions`](crate::Store::subscribe_actions) returns a
+//! raw [`ActionSubscription`]. Forwarding it to a client means filtering,
+//! serializing and deciding what to do when the client falls behind and the
+//! channel reports `Lagged`. [`ActionStreamer`] packages that per connection:
+//! it yields ready-to-send frames of whatever type the serializer produces
+//! (an axum `Message`, a tungstenite `Message`, a `String`, ...).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use axum::extract::ws::Message;
+//! use composable_rust_runtime::streaming::{ActionStreamer, LagPolicy};
+//!
+//! let mut frames = ActionStreamer::new(store.subscribe_actions(), |action: &OrderAction| {
+//!     serde_json::to_string(action).map(Message::Text)
+//! })
+//! .with_filter(move |action| action.customer_id() == Some(customer_id))
+//! .with_lag_policy(LagPolicy::resync(|skipped| async move {
+//!     Some(Message::Text(format!(r#"{{"type":"resync","skipped":{skipped}}}"#)))
+//! }));
+//!
+//! while let Some(frame) = frames.next_frame().await {
+//!     if sink.send(frame).await.is_err() {
+//!         break;
+//!     }
+//! }
+//! ```
+
+use futures::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use crate::subscription::ActionSubscription;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Future producing a resync frame
+pub type ResyncFuture<T> = Pin<Box<dyn Future<Output = Option<T>> + Send>>;
+
+/// What an [`ActionStreamer`] does when its receiver lagged behind
+pub enum LagPolicy<T> {
+    /// Drop the missed actions and continue with the next one
+    Skip,
+    /// End the stream, so the caller closes the connection
+    Close,
+    /// Emit a frame produced from the number of missed actions, then continue
+    ///
+    /// Typically a "resync" message prompting the client to refetch state, or
+    /// a snapshot of the state itself. Returning `None` emits nothing.
+    Resync(Arc<dyn Fn(u64) -> ResyncFuture<T> + Send + Sync>),
+}
+
+impl<T> LagPolicy<T> {
+    /// Resync with an async frame builder
+    pub fn resync<F, Fut>(build: F) -> Self
+    where
+        F: Fn(u64) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<T>> + Send + 'static,
+    {
+        Self::Resync(Arc::new(move |skipped| Box::pin(build(skipped))))
+    }
+}
+
+impl<T> Clone for LagPolicy<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Skip => Self::Skip,
+            Self::Close => Self::Close,
+            Self::Resync(build) => Self::Resync(Arc::clone(build)),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for LagPolicy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Skip => f.write_str("Skip"),
+            Self::Close => f.write_str("Close"),
+            Self::Resync(_) => f.write_str("Resync(..)"),
+        }
+    }
+}
+
+type Filter<A> = Box<dyn Fn(&A) -> bool + Send + Sync>;
+type Serializer<A, T> = Box<dyn Fn(&A) -> Result<T, String> + Send + Sync>;
+
+/// Filters, serializes and lag-handles one connection's action stream
+///
+/// Actions failing the filter are dropped; actions the serializer rejects
+/// are logged and dropped. The stream ends when the store's broadcast
+/// channel closes or, with [`LagPolicy::Close`], when the receiver lags.
+pub struct ActionStreamer<A, T> {
+    receiver: ActionSubscription<A>,
+    filter: Option<Filter<A>>,
+    serializer: Serializer<A, T>,
+    lag_policy: LagPolicy<T>,
+    skipped: u64,
+    closed: bool,
+}
+
+impl<A, T> ActionStreamer<A, T>
+where
+    A: Clone + Send + 'static,
+    T: Send + 'static,
+{
+    /// Stream `receiver`, turning each action into a frame with `serializer`
+    ///
+    /// Accepts a Store subscription or any `broadcast::Receiver`. Lagged
+    /// actions are skipped unless another policy is set.
+    pub fn new<F, E>(receiver: impl Into<ActionSubscription<A>>, serializer: F) -> Self
+    where
+        F: Fn(&A) -> Result<T, E> + Send + Sync + 'static,
+        E: std::fmt::Display,
+    {
+        Self {
+            receiver: receiver.into(),
+            filter: None,
+            serializer: Box::new(move |action| serializer(action).map_err(|e| e.to_string())),
+            lag_policy: LagPolicy::Skip,
+            skipped: 0,
+            closed: false,
+        }
+    }
+
+    /// Only stream actions for which `filter` returns `true`
+    #[must_use]
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&A) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Set what happens when the receiver lags
+    #[must_use]
+    pub fn with_lag_policy(mut self, lag_policy: LagPolicy<T>) -> Self {
+        self.lag_policy = lag_policy;
+        self
+    }
+
+    /// Total number of actions missed because the receiver lagged
+    #[must_use]
+    pub const fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Wait for the next frame
+    ///
+    /// Returns `None` once the stream has ended.
+    pub async fn next_frame(&mut self) -> Option<T> {
+        while !self.closed {
+            match self.receiver.recv().await {
+                Ok(action) => {
+                    if self.filter.as_ref().is_some_and(|filter| !filter(&action)) {
+                        continue;
+                    }
+                    match (self.serializer)(&action) {
+                        Ok(frame) => return Some(frame),
+                        Err(error) => {
+                            tracing::error!(%error, "Failed to serialize streamed action");
+                        },
+                    }
+                },
+                Err(RecvError::Lagged(missed)) => {
+                    self.skipped += missed;
+                    tracing::warn!(missed, policy = ?self.lag_policy, "Action stream lagged");
+                    crate::metrics::counter!("store.action_stream.lagged").increment(missed);
+                    match &self.lag_policy {
+                        LagPolicy::Skip => {},
+                        LagPolicy::Close => self.closed = true,
+                        LagPolicy::Resync(build) => {
+                            if let Some(frame) = build(missed).await {
+                                return Some(frame);
+                            }
+                        },
+                    }
+                },
+                Err(RecvError::Closed) => self.closed = true,
+            }
+        }
+        None
+    }
+
+    /// Convert into a [`Stream`] of frames
+    pub fn into_stream(self) -> impl Stream<Item = T> + Send
+    where
+        A: Sync,
+    {
+        futures::stream::unfold(self, |mut streamer| async move {
+            streamer.next_frame().await.map(|frame| (frame, streamer))
+        })
+    }
+}
+
+impl<A, T> std::fmt::Debug for ActionStreamer<A, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActionStreamer")
+            .field("filtered", &self.filter.is_some())
+            .field("lag_policy", &self.lag_policy)
+            .field("skipped", &self.skipped)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use tokio::sync::broadcast;
+
+    #[allow(clippy::trivially_copy_pass_by_ref, clippy::unnecessary_wraps)] // Serializer signature
+    fn text(n: &u32) -> Result<String, std::convert::Infallible> {
+        Ok(format!("action {n}"))
+    }
+
+    #[tokio::test]
+    async fn test_filters_and_serializes() {
+        let (tx, rx) = broadcast::channel(16);
+        let streamer = ActionStreamer::new(rx, text).with_filter(|n| n % 2 == 0);
+        for n in 0..5 {
+            tx.send(n).unwrap();
+        }
+        drop(tx);
+
+        let frames: Vec<_> = streamer.into_stream().collect().await;
+
+        assert_eq!(frames, vec!["action 0", "action 2", "action 4"]);
+    }
+
+    #[tokio::test]
+    async fn test_serializer_errors_are_dropped() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut streamer = ActionStreamer::new(rx, |n: &u32| {
+            if *n == 1 { Err("unsupported") } else { Ok(*n) }
+        });
+        for n in 0..3 {
+            tx.send(n).unwrap();
+        }
+
+        assert_eq!(streamer.next_frame().await, Some(0));
+        assert_eq!(streamer.next_frame().await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_lag_policies() {
+        let lagged = |policy: LagPolicy<String>| {
+            let (tx, rx) = broadcast::channel(2);
+            let streamer = ActionStreamer::new(rx, text).with_lag_policy(policy);
+            for n in 0..5 {
+                tx.send(n).unwrap();
+            }
+            (tx, streamer)
+        };
+
+        let (_tx, mut skip) = lagged(LagPolicy::Skip);
+        assert_eq!(skip.next_frame().await.as_deref(), Some("action 3"));
+        assert_eq!(skip.skipped(), 3);
+
+        let (_tx, mut close) = lagged(LagPolicy::Close);
+        assert_eq!(close.next_frame().await, None);
+
+        let (_tx, mut resync) =
+            lagged(LagPolicy::resync(|missed| async move { Some(format!("resync {missed}")) }));
+        assert_eq!(resync.next_frame().await.as_deref(), Some("resync 3"));
+        assert_eq!(resync.next_frame().await.as_deref(), Some("action 3"));
+    }
+}
@@ -0,0 +1,476 @@
+//! Action broadcast subscriptions with lag statistics and adaptive capacity.
+//!
+//! The Store broadcasts every effect-produced action on a bounded channel.
+//! Subscribers that fall more than `capacity` actions behind lose the oldest
+//! ones (`RecvError::Lagged`). Picking the capacity up front is guesswork, so
+//! every [`ActionSubscription`] reports its lag back to the Store:
+//!
+//! - [`Store::stats`](crate::Store::stats) exposes per-subscriber and
+//!   channel-wide lag statistics ([`BroadcastStats`])
+//! - with [`StoreConfig::with_broadcast_auto_resize`](crate::StoreConfig::with_broadcast_auto_resize),
+//!   sustained lag grows the channel: a larger channel is created and every
+//!   live subscription migrates to it once it has drained the old one, so no
+//!   action is lost or reordered by the switch
+//!
+//! Subscriptions created from a plain `broadcast::Receiver` (via `From`) are
+//! untracked and never migrate.
+//!
+//! The channel also reports to the Store's metrics recorder:
+//! `store.actions.subscribers` (gauge, receivers at the last broadcast) and
+//! `store.actions.lagged` (counter, actions missed by tracked subscriptions).
+
+use crate::metrics::MetricsRecorder;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::{
+    self,
+    error::{RecvError, TryRecvError},
+};
+
+/// When to grow the action broadcast channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BroadcastAutoResize {
+    /// Largest capacity the channel may grow to
+    pub max_capacity: usize,
+    /// Lag events (across subscribers) that count as sustained lag
+    pub lag_events: u32,
+    /// Window in which `lag_events` must occur
+    pub window: Duration,
+}
+
+impl BroadcastAutoResize {
+    /// Double the capacity, up to `max_capacity`, after 3 lag events within 10 seconds
+    #[must_use]
+    pub const fn new(max_capacity: usize) -> Self {
+        Self {
+            max_capacity,
+            lag_events: 3,
+            window: Duration::from_secs(10),
+        }
+    }
+
+    /// Set how many lag events within `window` trigger a resize
+    #[must_use]
+    pub const fn with_threshold(mut self, lag_events: u32, window: Duration) -> Self {
+        self.lag_events = lag_events;
+        self.window = window;
+        self
+    }
+}
+
+/// Lag statistics of one subscription
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubscriberLagStats {
+    /// Subscription id, unique per Store
+    pub id: u64,
+    /// Number of times the subscription lagged
+    pub lag_events: u64,
+    /// Actions missed in total
+    pub total_skipped: u64,
+    /// Most actions missed in a single lag
+    pub max_skipped: u64,
+    /// When the subscription last lagged
+    pub last_lag_at: Option<DateTime<Utc>>,
+}
+
+impl SubscriberLagStats {
+    const fn new(id: u64) -> Self {
+        Self {
+            id,
+            lag_events: 0,
+            total_skipped: 0,
+            max_skipped: 0,
+            last_lag_at: None,
+        }
+    }
+
+    fn record(&mut self, skipped: u64, at: DateTime<Utc>) {
+        self.lag_events += 1;
+        self.total_skipped += skipped;
+        self.max_skipped = self.max_skipped.max(skipped);
+        self.last_lag_at = Some(at);
+    }
+
+    /// Mean actions missed per lag event (0 if it never lagged)
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Counts are far below 2^52
+    pub fn mean_skipped(&self) -> f64 {
+        if self.lag_events == 0 {
+            0.0
+        } else {
+            self.total_skipped as f64 / self.lag_events as f64
+        }
+    }
+}
+
+/// Snapshot of the action broadcast channel
+#[derive(Debug, Clone, PartialEq)]
+pub struct BroadcastStats {
+    /// Current channel capacity
+    pub capacity: usize,
+    /// Times the channel was grown by auto-resize
+    pub resizes: u32,
+    /// Totals across all subscriptions, including dropped ones (`id` is 0)
+    pub totals: SubscriberLagStats,
+    /// Live tracked subscriptions, in subscription order
+    pub subscribers: Vec<SubscriberLagStats>,
+}
+
+/// State shared between a subscription and the channel
+#[derive(Debug)]
+struct Subscriber<A> {
+    /// Receivers on channels created by later resizes, oldest first
+    migrations: Mutex<VecDeque<broadcast::Receiver<A>>>,
+    stats: Mutex<SubscriberLagStats>,
+}
+
+#[derive(Debug)]
+struct LagWindow {
+    capacity: usize,
+    resizes: u32,
+    totals: SubscriberLagStats,
+    recent: VecDeque<Instant>,
+}
+
+/// The Store's action broadcast channel
+pub(crate) struct ActionBroadcast<A> {
+    /// The only sender; replaced (and the old one dropped) on resize
+    sender: RwLock<broadcast::Sender<A>>,
+    subscribers: Mutex<Vec<Weak<Subscriber<A>>>>,
+    window: Mutex<LagWindow>,
+    auto_resize: Option<BroadcastAutoResize>,
+    next_id: AtomicU64,
+    metrics: Arc<dyn MetricsRecorder>,
+}
+
+impl<A: Clone> ActionBroadcast<A> {
+    pub(crate) fn new(
+        capacity: usize,
+        auto_resize: Option<BroadcastAutoResize>,
+        metrics: Arc<dyn MetricsRecorder>,
+    ) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender: RwLock::new(sender),
+            subscribers: Mutex::new(Vec::new()),
+            window: Mutex::new(LagWindow {
+                capacity,
+                resizes: 0,
+                totals: SubscriberLagStats::new(0),
+                recent: VecDeque::new(),
+            }),
+            auto_resize,
+            next_id: AtomicU64::new(1),
+            metrics,
+        }
+    }
+
+    /// Broadcast an action; having no subscribers is not an error
+    pub(crate) fn send(&self, action: A) {
+        let receivers = self.read_sender().send(action).unwrap_or(0);
+        #[allow(clippy::cast_precision_loss)] // Receiver counts are far below 2^52
+        self.metrics.set_gauge("store.actions.subscribers", &[], receivers as f64);
+    }
+
+    /// Number of live receivers, tracked or not
+    pub(crate) fn receiver_count(&self) -> usize {
+        self.read_sender().receiver_count()
+    }
+
+    /// Subscribe with lag tracking and migration across resizes
+    pub(crate) fn subscribe(self: &Arc<Self>) -> ActionSubscription<A> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let subscriber = Arc::new(Subscriber {
+            migrations: Mutex::new(VecDeque::new()),
+            stats: Mutex::new(SubscriberLagStats::new(id)),
+        });
+
+        // Register while holding the sender so a concurrent resize either
+        // sees this subscriber or hands it the new channel
+        let sender = self.read_sender();
+        let receiver = sender.subscribe();
+        let mut subscribers = lock(&self.subscribers);
+        subscribers.retain(|weak| weak.strong_count() > 0);
+        subscribers.push(Arc::downgrade(&subscriber));
+        drop(subscribers);
+        drop(sender);
+
+        ActionSubscription {
+            receiver,
+            tracking: Some((subscriber, Arc::downgrade(self))),
+        }
+    }
+
+    pub(crate) fn stats(&self) -> BroadcastStats {
+        let subscribers = lock(&self.subscribers)
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|subscriber| lock(&subscriber.stats).clone())
+            .collect();
+        let window = lock(&self.window);
+        BroadcastStats {
+            capacity: window.capacity,
+            resizes: window.resizes,
+            totals: window.totals.clone(),
+            subscribers,
+        }
+    }
+
+    fn record_lag(&self, subscriber: &Subscriber<A>, skipped: u64) {
+        let now = Utc::now();
+        lock(&subscriber.stats).record(skipped, now);
+        self.metrics.increment_counter("store.actions.lagged", &[], skipped);
+
+        let mut window = lock(&self.window);
+        window.totals.record(skipped, now);
+        let Some(policy) = self.auto_resize else {
+            return;
+        };
+
+        let instant = Instant::now();
+        window.recent.push_back(instant);
+        while window
+            .recent
+            .front()
+            .is_some_and(|at| instant.duration_since(*at) > policy.window)
+        {
+            window.recent.pop_front();
+        }
+
+        let sustained = window.recent.len() >= usize::try_from(policy.lag_events).unwrap_or(usize::MAX);
+        if sustained && window.capacity < policy.max_capacity {
+            let capacity = window.capacity.saturating_mul(2).min(policy.max_capacity);
+            self.resize(capacity);
+            window.capacity = capacity;
+            window.resizes += 1;
+            window.recent.clear();
+            tracing::warn!(capacity, "Sustained action broadcast lag, grew channel");
+        }
+    }
+
+    /// Replace the channel with one of `capacity`, migrating subscribers
+    fn resize(&self, capacity: usize) {
+        let mut sender = self.sender.write().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let (resized, _) = broadcast::channel(capacity);
+        for subscriber in lock(&self.subscribers).iter().filter_map(Weak::upgrade) {
+            lock(&subscriber.migrations).push_back(resized.subscribe());
+        }
+        // Dropping the old sender closes the old channel once drained
+        *sender = resized;
+    }
+
+    fn read_sender(&self) -> std::sync::RwLockReadGuard<'_, broadcast::Sender<A>> {
+        self.sender.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<A> std::fmt::Debug for ActionBroadcast<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActionBroadcast")
+            .field("auto_resize", &self.auto_resize)
+            .finish_non_exhaustive()
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Receiver of the actions broadcast by a Store
+///
+/// Behaves like a `tokio::sync::broadcast::Receiver`: `recv` returns
+/// `RecvError::Lagged` when actions were missed and `RecvError::Closed` once
+/// every Store handle is dropped. Lag is additionally reported to the Store's
+/// [`BroadcastStats`].
+pub struct ActionSubscription<A> {
+    receiver: broadcast::Receiver<A>,
+    tracking: Option<Tracking<A>>,
+}
+
+/// A subscription's lag record and the channel it reports to
+type Tracking<A> = (Arc<Subscriber<A>>, Weak<ActionBroadcast<A>>);
+
+impl<A: Clone> ActionSubscription<A> {
+    /// Receive the next action
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvError::Lagged`] with the number of missed actions if the
+    /// subscription fell behind, or [`RecvError::Closed`] once the Store is
+    /// gone.
+    pub async fn recv(&mut self) -> Result<A, RecvError> {
+        loop {
+            match self.receiver.recv().await {
+                Err(RecvError::Lagged(skipped)) => {
+                    self.record_lag(skipped);
+                    return Err(RecvError::Lagged(skipped));
+                },
+                Err(RecvError::Closed) if self.migrate() => {},
+                result => return result,
+            }
+        }
+    }
+
+    /// Receive an action if one is ready
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryRecvError::Empty`] if no action is ready, otherwise as
+    /// [`recv`](Self::recv).
+    pub fn try_recv(&mut self) -> Result<A, TryRecvError> {
+        loop {
+            match self.receiver.try_recv() {
+                Err(TryRecvError::Lagged(skipped)) => {
+                    self.record_lag(skipped);
+                    return Err(TryRecvError::Lagged(skipped));
+                },
+                Err(TryRecvError::Closed) if self.migrate() => {},
+                result => return result,
+            }
+        }
+    }
+
+    /// Id of this subscription in [`BroadcastStats`] (`None` if untracked)
+    #[must_use]
+    pub fn id(&self) -> Option<u64> {
+        self.tracking
+            .as_ref()
+            .map(|(subscriber, _)| lock(&subscriber.stats).id)
+    }
+
+    /// Switch to the next channel after the current one was resized away
+    fn migrate(&mut self) -> bool {
+        let next = self
+            .tracking
+            .as_ref()
+            .and_then(|(subscriber, _)| lock(&subscriber.migrations).pop_front());
+        match next {
+            Some(receiver) => {
+                self.receiver = receiver;
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn record_lag(&self, skipped: u64) {
+        if let Some((subscriber, channel)) = &self.tracking {
+            if let Some(channel) = channel.upgrade() {
+                channel.record_lag(subscriber, skipped);
+            }
+        }
+    }
+}
+
+impl<A: Clone> From<broadcast::Receiver<A>> for ActionSubscription<A> {
+    fn from(receiver: broadcast::Receiver<A>) -> Self {
+        Self {
+            receiver,
+            tracking: None,
+        }
+    }
+}
+
+impl<A> std::fmt::Debug for ActionSubscription<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActionSubscription")
+            .field("tracked", &self.tracking.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use crate::metrics::{CapturingRecorder, NoopRecorder};
+
+    #[tokio::test]
+    async fn test_lag_recorded_per_subscriber() {
+        let channel = Arc::new(ActionBroadcast::new(2, None, Arc::new(NoopRecorder)));
+        let mut slow = channel.subscribe();
+        let _idle = channel.subscribe();
+        for n in 0..5 {
+            channel.send(n);
+        }
+
+        assert!(matches!(slow.recv().await, Err(RecvError::Lagged(3))));
+        assert_eq!(slow.recv().await.unwrap(), 3);
+
+        let stats = channel.stats();
+        assert_eq!(stats.subscribers.len(), 2);
+        let slow_stats = &stats.subscribers[0];
+        assert_eq!(slow_stats.id, slow.id().unwrap());
+        assert_eq!((slow_stats.lag_events, slow_stats.max_skipped), (1, 3));
+        assert!(slow_stats.last_lag_at.is_some());
+        assert_eq!(stats.subscribers[1].lag_events, 0);
+        assert!((stats.totals.mean_skipped() - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_subscribers_leave_totals() {
+        let channel = Arc::new(ActionBroadcast::new(1, None, Arc::new(NoopRecorder)));
+        let mut subscription = channel.subscribe();
+        channel.send(1);
+        channel.send(2);
+        let _ = subscription.try_recv();
+        drop(subscription);
+
+        let stats = channel.stats();
+        assert!(stats.subscribers.is_empty());
+        assert_eq!(stats.totals.total_skipped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_sustained_lag_grows_channel_without_losing_actions() {
+        let policy = BroadcastAutoResize::new(8).with_threshold(1, Duration::from_secs(60));
+        let channel = Arc::new(ActionBroadcast::new(2, Some(policy), Arc::new(NoopRecorder)));
+        let mut subscription = channel.subscribe();
+        for n in 0..3 {
+            channel.send(n);
+        }
+
+        // Lag triggers a resize to capacity 4
+        assert!(matches!(subscription.recv().await, Err(RecvError::Lagged(1))));
+        for n in 3..7 {
+            channel.send(n);
+        }
+
+        // Old channel drains first, then the subscription migrates
+        let mut received = Vec::new();
+        while let Ok(n) = subscription.try_recv() {
+            received.push(n);
+        }
+        assert_eq!(received, vec![1, 2, 3, 4, 5, 6]);
+
+        let stats = channel.stats();
+        assert_eq!((stats.capacity, stats.resizes), (4, 1));
+    }
+
+    #[tokio::test]
+    async fn test_lag_and_subscribers_reported_to_metrics() {
+        let recorder = CapturingRecorder::new();
+        let channel = Arc::new(ActionBroadcast::new(1, None, Arc::new(recorder.clone())));
+        let mut subscription = channel.subscribe();
+        let _idle = channel.subscribe();
+        for n in 0..3 {
+            channel.send(n);
+        }
+        assert_eq!(recorder.gauge("store.actions.subscribers"), Some(2.0));
+
+        assert!(matches!(subscription.recv().await, Err(RecvError::Lagged(2))));
+        assert_eq!(recorder.counter("store.actions.lagged"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_closed_when_channel_dropped() {
+        let channel = Arc::new(ActionBroadcast::<u32>::new(4, None, Arc::new(NoopRecorder)));
+        let mut subscription = channel.subscribe();
+        drop(channel);
+
+        assert!(matches!(subscription.recv().await, Err(RecvError::Closed)));
+    }
+}
@@ -0,0 +1,69 @@
+//! Keeping a Store alive when its reducer panics.
+//!
+//! Without supervision a reducer panic unwinds through `send()` into the
+//! caller, and the action that caused it is lost without a trace. With
+//! [`StoreConfig::with_supervision`](crate::StoreConfig::with_supervision)
+//! the Store catches the panic instead:
+//!
+//! - the poison action is recorded in the dead letter queue under
+//!   `action:<action type>`, with the panic message as the error
+//! - the panic is counted in the `store.reducer.panics` metric
+//! - the action produces no effects, and `send()` returns normally
+//! - subsequent actions are reduced as usual
+//!
+//! The [`SupervisionStrategy`] decides what happens to the state the reducer
+//! was mutating when it panicked.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_runtime::supervision::SupervisionStrategy;
+//!
+//! let config = StoreConfig::default()
+//!     .with_supervision(SupervisionStrategy::RestartFromSnapshot);
+//!
+//! // Restoring the state needs a copy of it taken before each reduction
+//! let store = Store::with_config(state, reducer, env, config).with_state_checkpoints();
+//! ```
+
+use std::any::Any;
+
+/// What a supervised Store does with the state after a reducer panic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupervisionStrategy {
+    /// Keep the state as the reducer left it, including partial updates
+    ///
+    /// Cheap, and correct for reducers that validate before they mutate.
+    RestartWithState,
+    /// Restore the state from before the poison action
+    ///
+    /// The Store clones the state before every reduction, which requires
+    /// [`Store::with_state_checkpoints`](crate::Store::with_state_checkpoints).
+    /// Without checkpoints the state is kept as with
+    /// [`RestartWithState`](Self::RestartWithState).
+    RestartFromSnapshot,
+}
+
+/// Copies the state before a reduction
+pub(crate) type Checkpoint<S> = fn(&S) -> S;
+
+/// The message of a caught panic, if it carried one
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "reducer panicked".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panic_message() {
+        assert_eq!(panic_message(&"boom"), "boom");
+        assert_eq!(panic_message(&"boom".to_string()), "boom");
+        assert_eq!(panic_message(&42), "reducer panicked");
+    }
+}
@@ -0,0 +1,193 @@
+//! Accepted-vs-completed tracking for asynchronous request patterns.
+//!
+//! HTTP integrations often answer `202 Accepted` with a tracking id as soon
+//! as an action is reduced, and let the client poll (or get notified) once
+//! its effects have finished. [`Store::send_tracked`](crate::Store::send_tracked)
+//! returns such an id, [`Store::completion_status`](crate::Store::completion_status)
+//! answers the poll, and
+//! [`Store::with_completion_action`](crate::Store::with_completion_action)
+//! feeds an action back when a tracked request completes (for example one
+//! whose reducer emits a webhook effect).
+//!
+//! Completion means the action's direct effects have finished, as with
+//! [`EffectHandle::wait`](crate::EffectHandle::wait). Statuses of completed
+//! requests are kept for the most recent
+//! [`StoreConfig::tracking_retention`](crate::StoreConfig::tracking_retention)
+//! requests and then forgotten.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Identifier of a request sent with `send_tracked`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TrackingId(Uuid);
+
+impl TrackingId {
+    /// Generate a new random id
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Wrap an existing UUID, e.g. one parsed from a request path
+    #[must_use]
+    pub const fn from_uuid(id: Uuid) -> Self {
+        Self(id)
+    }
+
+    /// The underlying UUID
+    #[must_use]
+    pub const fn as_uuid(&self) -> Uuid {
+        self.0
+    }
+}
+
+impl Default for TrackingId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for TrackingId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Progress of a tracked request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CompletionStatus {
+    /// Reduced; effects still running
+    Pending {
+        /// When the action was accepted
+        accepted_at: DateTime<Utc>,
+    },
+    /// All effects finished
+    Completed {
+        /// When the action was accepted
+        accepted_at: DateTime<Utc>,
+        /// When the last effect finished
+        completed_at: DateTime<Utc>,
+    },
+}
+
+impl CompletionStatus {
+    /// Whether all effects have finished
+    #[must_use]
+    pub const fn is_completed(&self) -> bool {
+        matches!(self, Self::Completed { .. })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Statuses {
+    by_id: HashMap<TrackingId, CompletionStatus>,
+    /// Completed ids, oldest first, for retention
+    completed: VecDeque<TrackingId>,
+}
+
+/// Status table shared by all handles of a Store
+#[derive(Debug)]
+pub(crate) struct CompletionTracker {
+    statuses: Mutex<Statuses>,
+    retention: usize,
+}
+
+impl CompletionTracker {
+    pub(crate) fn new(retention: usize) -> Self {
+        Self {
+            statuses: Mutex::new(Statuses::default()),
+            retention,
+        }
+    }
+
+    pub(crate) fn accept(&self, id: TrackingId) {
+        let status = CompletionStatus::Pending {
+            accepted_at: Utc::now(),
+        };
+        self.lock().by_id.insert(id, status);
+    }
+
+    /// Forget a request the Store rejected
+    pub(crate) fn reject(&self, id: TrackingId) {
+        self.lock().by_id.remove(&id);
+    }
+
+    pub(crate) fn complete(&self, id: TrackingId) {
+        let mut statuses = self.lock();
+        let Some(status) = statuses.by_id.get_mut(&id) else {
+            return;
+        };
+        if let CompletionStatus::Pending { accepted_at } = *status {
+            *status = CompletionStatus::Completed {
+                accepted_at,
+                completed_at: Utc::now(),
+            };
+        }
+
+        statuses.completed.push_back(id);
+        while statuses.completed.len() > self.retention {
+            if let Some(expired) = statuses.completed.pop_front() {
+                statuses.by_id.remove(&expired);
+            }
+        }
+    }
+
+    pub(crate) fn status(&self, id: TrackingId) -> Option<CompletionStatus> {
+        self.lock().by_id.get(&id).copied()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Statuses> {
+        self.statuses.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifecycle() {
+        let tracker = CompletionTracker::new(10);
+        let id = TrackingId::new();
+
+        assert_eq!(tracker.status(id), None);
+        tracker.accept(id);
+        assert!(matches!(tracker.status(id), Some(CompletionStatus::Pending { .. })));
+        tracker.complete(id);
+        assert!(tracker.status(id).unwrap().is_completed());
+    }
+
+    #[test]
+    fn test_retention_forgets_oldest_completed() {
+        let tracker = CompletionTracker::new(2);
+        let ids: Vec<_> = (0..3).map(|_| TrackingId::new()).collect();
+        let pending = TrackingId::new();
+        tracker.accept(pending);
+        for id in &ids {
+            tracker.accept(*id);
+            tracker.complete(*id);
+        }
+
+        assert_eq!(tracker.status(ids[0]), None);
+        assert!(tracker.status(ids[2]).is_some());
+        // Pending requests are never evicted
+        assert!(tracker.status(pending).is_some());
+    }
+
+    #[test]
+    fn test_status_serializes_with_tag() {
+        let json = serde_json::to_value(CompletionStatus::Pending {
+            accepted_at: Utc::now(),
+        })
+        .unwrap();
+
+        assert_eq!(json["status"], "pending");
+    }
+}
@@ -7,7 +7,8 @@
 #![allow(clippy::needless_continue, clippy::match_same_arms, clippy::collapsible_if, clippy::collapsible_match)] // Test code - allow pedantic warnings
 
 use composable_rust_core::{effect::Effect, reducer::Reducer, smallvec, SmallVec};
-use composable_rust_runtime::Store;
+use composable_rust_runtime::subscription::{ActionSubscription, BroadcastAutoResize};
+use composable_rust_runtime::{Store, StoreConfig};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
@@ -743,6 +744,50 @@ async fn test_custom_broadcast_capacity() {
     assert!(lagged || received < 5, "Should lag or miss actions with small buffer");
 }
 
+/// Test lag statistics and broadcast auto-resize
+///
+/// Verifies that a lagging subscriber shows up in `Store::stats()` and that
+/// sustained lag grows the channel, after which the subscriber keeps up.
+#[tokio::test]
+async fn test_lag_stats_and_auto_resize() {
+    let config = StoreConfig::default().with_broadcast_auto_resize(
+        BroadcastAutoResize::new(64).with_threshold(1, Duration::from_secs(60)),
+    );
+    let store = Store::with_config(TestState::default(), TestReducer, TestEnvironment, config);
+    let mut rx = store.subscribe_actions();
+
+    // Overflow the default capacity of 16
+    for _ in 0..20 {
+        store.send(TestAction::Increment).await.ok();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert!(matches!(
+        rx.recv().await,
+        Err(tokio::sync::broadcast::error::RecvError::Lagged(4))
+    ));
+    while rx.try_recv().is_ok() {}
+
+    let stats = store.stats().broadcast;
+    assert_eq!((stats.capacity, stats.resizes), (32, 1));
+    assert_eq!(stats.subscribers.len(), 1);
+    assert_eq!(stats.subscribers[0].id, rx.id().unwrap());
+    assert_eq!(stats.subscribers[0].max_skipped, 4);
+
+    // 20 more actions fit the resized channel
+    for _ in 0..20 {
+        store.send(TestAction::Increment).await.ok();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut received = 0;
+    while rx.try_recv().is_ok() {
+        received += 1;
+    }
+    assert_eq!(received, 20);
+    assert_eq!(store.stats().broadcast.totals.lag_events, 1);
+}
+
 /// Test saga failure scenario
 ///
 /// Verifies that error actions (`SagaFailed`) are also broadcast correctly.
@@ -801,12 +846,69 @@ async fn test_saga_failure_broadcasting() {
     }
 }
 
+/// Test `send_and_wait_for` with a reply produced by an event bus effect
+///
+/// Verifies that actions fed back by `PublishEvent` callbacks are broadcast
+/// like those of `Effect::Future`.
+#[tokio::test]
+async fn test_send_and_wait_for_publish_reply() {
+    use composable_rust_core::effect_builders::EventBusEffect;
+    use composable_rust_core::event::SerializedEvent;
+    use composable_rust_core::event_bus::EventBus;
+    use composable_rust_testing::mocks::InMemoryEventBus;
+
+    #[derive(Debug, Clone)]
+    enum PublishAction {
+        Publish,
+        Published,
+    }
+
+    #[derive(Clone)]
+    struct PublishReducer;
+
+    impl Reducer for PublishReducer {
+        type State = ();
+        type Action = PublishAction;
+        type Environment = Arc<dyn EventBus>;
+
+        fn reduce(
+            &self,
+            (): &mut (),
+            action: PublishAction,
+            bus: &Arc<dyn EventBus>,
+        ) -> SmallVec<[Effect<PublishAction>; 4]> {
+            match action {
+                PublishAction::Publish => {
+                    let event = SerializedEvent::new("Pinged.v1".to_string(), vec![1], None);
+                    smallvec![EventBusEffect::publish(bus, "pings", event)
+                        .on_success(|| Some(PublishAction::Published))
+                        .into_effect()]
+                }
+                PublishAction::Published => smallvec![Effect::None],
+            }
+        }
+    }
+
+    let bus: Arc<dyn EventBus> = Arc::new(InMemoryEventBus::new());
+    let store = Store::new((), PublishReducer, bus);
+
+    let reply = store
+        .send_and_wait_for(
+            PublishAction::Publish,
+            |action| matches!(action, PublishAction::Published),
+            Duration::from_secs(1),
+        )
+        .await;
+
+    assert!(matches!(reply, Ok(PublishAction::Published)));
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
 /// Count available actions in receiver without blocking
-fn count_available_actions(rx: &mut tokio::sync::broadcast::Receiver<TestAction>) -> usize {
+fn count_available_actions(rx: &mut ActionSubscription<TestAction>) -> usize {
     let mut count = 0;
     loop {
         match rx.try_recv() {
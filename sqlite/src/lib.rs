@@ -0,0 +1,801 @@
+//! `SQLite` event store implementation for Composable Rust.
+//!
+//! Not every deployment has a `PostgreSQL` server. [`SqliteEventStore`]
+//! implements the [`EventStore`] trait from `composable-rust-core` on a single
+//! local database file, for CLI tools, edge devices, desktop apps and
+//! integration tests that should run without containers. It has the same
+//! semantics as `PostgresEventStore`:
+//!
+//! - Stream versions start at 1; `expected_version` is the version of the
+//!   stream's last event (0 for a new stream)
+//! - Optimistic concurrency, enforced by a `(stream_id, version)` primary key
+//! - One snapshot per stream, replaced on save
+//! - Atomic batch appends with per-operation results
+//!
+//! The schema is created when the store is opened. Writes take the database
+//! write lock up front (`BEGIN IMMEDIATE`), so several processes may share a
+//! file; each waits up to the busy timeout for the others.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use composable_rust_sqlite::SqliteEventStore;
+//!
+//! async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//!     let event_store = SqliteEventStore::open("./events.db").await?;
+//!     Ok(())
+//! }
+//! ```
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use composable_rust_core::event::{EventMetadata, SerializedEvent};
+use composable_rust_core::event_store::{
+    BatchAppend, BatchAppendResults, EventStore, EventStoreError, Pagination, StreamMetadata,
+};
+use composable_rust_core::stream::{StreamId, Version};
+use sqlx::Row;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+    SqliteRow,
+};
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::Instrument;
+
+/// Boxed future returned by [`EventStore`] methods
+type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, EventStoreError>> + Send + 'a>>;
+
+/// Schema, created on open if missing
+const SCHEMA: &str = r"
+CREATE TABLE IF NOT EXISTS events (
+    stream_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    event_type TEXT NOT NULL,
+    event_version INTEGER NOT NULL DEFAULT 1,
+    event_data BLOB NOT NULL,
+    metadata TEXT,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (stream_id, version)
+);
+CREATE INDEX IF NOT EXISTS idx_events_created ON events(created_at);
+CREATE INDEX IF NOT EXISTS idx_events_type ON events(event_type);
+CREATE TABLE IF NOT EXISTS snapshots (
+    stream_id TEXT PRIMARY KEY,
+    version INTEGER NOT NULL,
+    state_data BLOB NOT NULL,
+    created_at TEXT NOT NULL
+);
+";
+
+/// How long a write waits for another connection's write lock
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn db_error(e: impl std::fmt::Display) -> EventStoreError {
+    EventStoreError::DatabaseError(e.to_string())
+}
+
+fn to_i64(version: Version) -> Result<i64, EventStoreError> {
+    i64::try_from(version.value())
+        .map_err(|e| EventStoreError::DatabaseError(format!("Version overflow: {e}")))
+}
+
+/// Build a [`SerializedEvent`] from a row of the `events` table
+fn event_from_row(row: &SqliteRow) -> SerializedEvent {
+    let metadata: Option<String> = row.get("metadata");
+    SerializedEvent {
+        event_type: row.get("event_type"),
+        event_version: row.get("event_version"),
+        data: row.get("event_data"),
+        metadata: metadata
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .and_then(|json| EventMetadata::from_json(&json).ok()),
+    }
+}
+
+fn to_version(version: i64) -> Result<Version, EventStoreError> {
+    u64::try_from(version).map(Version::new).map_err(|e| {
+        EventStoreError::DatabaseError(format!(
+            "Invalid negative version {version} in database: {e}"
+        ))
+    })
+}
+
+/// `SQLite`-based event store implementation.
+///
+/// Cloning is cheap; clones share the connection pool.
+///
+/// # Example
+///
+/// ```no_run
+/// use composable_rust_sqlite::SqliteEventStore;
+/// use composable_rust_core::stream::{StreamId, Version};
+/// use composable_rust_core::event_store::EventStore;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let store = SqliteEventStore::in_memory().await?;
+///
+/// let stream_id = StreamId::new("order-123");
+/// let events = vec![/* SerializedEvent instances */];
+///
+/// let version = store.append_events(stream_id, Some(Version::new(0)), events).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SqliteEventStore {
+    pool: SqlitePool,
+}
+
+impl SqliteEventStore {
+    /// Open (creating if needed) the database file at `path`.
+    ///
+    /// Uses write-ahead logging, so readers do not block the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventStoreError::DatabaseError`] if the file cannot be opened
+    /// or the schema cannot be created.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, EventStoreError> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(BUSY_TIMEOUT);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_with(options)
+            .await
+            .map_err(db_error)?;
+        Self::from_pool(pool).await
+    }
+
+    /// Open a database from a `sqlite:` URL, e.g. `sqlite://events.db?mode=rwc`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventStoreError::DatabaseError`] if the URL is invalid, the
+    /// database cannot be opened or the schema cannot be created.
+    pub async fn new(database_url: &str) -> Result<Self, EventStoreError> {
+        let options = SqliteConnectOptions::from_str(database_url)
+            .map_err(db_error)?
+            .busy_timeout(BUSY_TIMEOUT);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_with(options)
+            .await
+            .map_err(db_error)?;
+        Self::from_pool(pool).await
+    }
+
+    /// Create a private in-memory database, discarded when the store is dropped.
+    ///
+    /// Intended for tests: the pool holds a single connection that is never
+    /// recycled, since every in-memory connection is a separate database.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventStoreError::DatabaseError`] if the schema cannot be created.
+    pub async fn in_memory() -> Result<Self, EventStoreError> {
+        let options = SqliteConnectOptions::from_str("sqlite::memory:").map_err(db_error)?;
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await
+            .map_err(db_error)?;
+        Self::from_pool(pool).await
+    }
+
+    /// Use an existing pool, creating the schema if missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventStoreError::DatabaseError`] if the schema cannot be created.
+    pub async fn from_pool(pool: SqlitePool) -> Result<Self, EventStoreError> {
+        sqlx::raw_sql(SCHEMA)
+            .execute(&pool)
+            .await
+            .map_err(|e| EventStoreError::DatabaseError(format!("Schema creation failed: {e}")))?;
+        Ok(Self { pool })
+    }
+
+    /// Get the underlying connection pool.
+    #[must_use]
+    pub const fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+}
+
+/// Version of the last event in `stream_id` (0 for an empty stream)
+async fn current_version(
+    conn: &mut SqliteConnection,
+    stream_id: &StreamId,
+) -> Result<Version, EventStoreError> {
+    let version: i64 =
+        sqlx::query_scalar("SELECT COALESCE(MAX(version), 0) FROM events WHERE stream_id = ?")
+            .bind(stream_id.as_str())
+            .fetch_one(conn)
+            .await
+            .map_err(db_error)?;
+    to_version(version)
+}
+
+/// Check `expected` and insert `events` after the stream's last event
+///
+/// Returns the version of the last inserted event.
+async fn append_in_tx(
+    conn: &mut SqliteConnection,
+    stream_id: &StreamId,
+    expected_version: Option<Version>,
+    events: Vec<SerializedEvent>,
+) -> Result<Version, EventStoreError> {
+    if events.is_empty() {
+        return Err(EventStoreError::DatabaseError(
+            "Cannot append empty event list".to_string(),
+        ));
+    }
+
+    let current = current_version(&mut *conn, stream_id).await?;
+    if let Some(expected) = expected_version {
+        if current != expected {
+            tracing::warn!(
+                stream_id = %stream_id,
+                expected = ?expected,
+                actual = ?current,
+                "Optimistic concurrency conflict detected"
+            );
+            return Err(EventStoreError::ConcurrencyConflict {
+                stream_id: stream_id.clone(),
+                expected,
+                actual: current,
+            });
+        }
+    }
+
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let mut version = current;
+    for event in events {
+        version = version.next();
+        sqlx::query(
+            r"
+            INSERT INTO events (stream_id, version, event_type, event_version, event_data, metadata, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(stream_id.as_str())
+        .bind(to_i64(version)?)
+        .bind(&event.event_type)
+        .bind(event.event_version)
+        .bind(&event.data)
+        .bind(event.metadata.as_ref().map(|m| m.to_json().to_string()))
+        .bind(&created_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            // Another process appended between our read and write
+            if e.as_database_error().is_some_and(sqlx::error::DatabaseError::is_unique_violation) {
+                EventStoreError::ConcurrencyConflict {
+                    stream_id: stream_id.clone(),
+                    expected: expected_version.unwrap_or(current),
+                    actual: version,
+                }
+            } else {
+                db_error(e)
+            }
+        })?;
+    }
+    Ok(version)
+}
+
+/// Build [`StreamMetadata`] from a row of the per-stream aggregate query
+fn stream_metadata_from_row(row: &SqliteRow) -> Result<StreamMetadata, EventStoreError> {
+    let stream_id: String = row.get("stream_id");
+    let created_at: String = row.get("created_at");
+    let event_count: i64 = row.get("event_count");
+    let last_version: i64 = row.get("last_version");
+
+    Ok(StreamMetadata {
+        stream_id: StreamId::new(stream_id),
+        created_at: chrono::DateTime::parse_from_rfc3339(&created_at)
+            .map_err(|e| {
+                EventStoreError::DatabaseError(format!("Invalid timestamp {created_at}: {e}"))
+            })?
+            .with_timezone(&chrono::Utc),
+        event_count: u64::try_from(event_count).map_err(|e| {
+            EventStoreError::DatabaseError(format!("Invalid event count {event_count}: {e}"))
+        })?,
+        last_version: to_version(last_version)?,
+    })
+}
+
+impl EventStore for SqliteEventStore {
+    fn append_events(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+    ) -> StoreFuture<'_, Version> {
+        let span = tracing::info_span!(
+            "event_store.append_events",
+            stream_id = %stream_id,
+            expected_version = ?expected_version,
+            event_count = events.len(),
+        );
+
+        Box::pin(
+            async move {
+                let start = std::time::Instant::now();
+                let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await.map_err(db_error)?;
+                let version = append_in_tx(&mut tx, &stream_id, expected_version, events).await?;
+                tx.commit().await.map_err(db_error)?;
+
+                tracing::debug!(stream_id = %stream_id, final_version = ?version, "Successfully appended events");
+                metrics::histogram!("event_store.append.duration_seconds")
+                    .record(start.elapsed().as_secs_f64());
+                metrics::counter!("event_store.append.total", "result" => "success").increment(1);
+                Ok(version)
+            }
+            .instrument(span),
+        )
+    }
+
+    fn load_events(
+        &self,
+        stream_id: StreamId,
+        from_version: Option<Version>,
+    ) -> StoreFuture<'_, Vec<SerializedEvent>> {
+        let span = tracing::info_span!(
+            "event_store.load_events",
+            stream_id = %stream_id,
+            from_version = ?from_version,
+        );
+
+        Box::pin(
+            async move {
+                let start = std::time::Instant::now();
+                let from = from_version.map_or(Ok(0), to_i64)?;
+                let rows = sqlx::query(
+                    r"
+                    SELECT event_type, event_version, event_data, metadata
+                    FROM events
+                    WHERE stream_id = ? AND version >= ?
+                    ORDER BY version ASC
+                    ",
+                )
+                .bind(stream_id.as_str())
+                .bind(from)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+                let events: Vec<SerializedEvent> = rows.iter().map(event_from_row).collect();
+
+                tracing::debug!(stream_id = %stream_id, event_count = events.len(), "Loaded events from stream");
+                metrics::histogram!("event_store.load.duration_seconds")
+                    .record(start.elapsed().as_secs_f64());
+                metrics::counter!("event_store.load.total", "result" => "success").increment(1);
+                Ok(events)
+            }
+            .instrument(span),
+        )
+    }
+
+    fn load_events_page(
+        &self,
+        stream_id: StreamId,
+        from_version: Version,
+        limit: usize,
+    ) -> StoreFuture<'_, Vec<SerializedEvent>> {
+        let span = tracing::info_span!(
+            "event_store.load_events_page",
+            stream_id = %stream_id,
+            from_version = %from_version,
+            limit,
+        );
+
+        Box::pin(
+            async move {
+                let rows = sqlx::query(
+                    r"
+                    SELECT event_type, event_version, event_data, metadata
+                    FROM events
+                    WHERE stream_id = ? AND version >= ?
+                    ORDER BY version ASC
+                    LIMIT ?
+                    ",
+                )
+                .bind(stream_id.as_str())
+                .bind(to_i64(from_version)?)
+                .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+                .fetch_all(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+                Ok(rows.iter().map(event_from_row).collect())
+            }
+            .instrument(span),
+        )
+    }
+
+    fn save_snapshot(
+        &self,
+        stream_id: StreamId,
+        version: Version,
+        state: Vec<u8>,
+    ) -> StoreFuture<'_, ()> {
+        let span = tracing::info_span!(
+            "event_store.save_snapshot",
+            stream_id = %stream_id,
+            version = ?version,
+            state_size = state.len(),
+        );
+
+        Box::pin(
+            async move {
+                sqlx::query(
+                    r"
+                    INSERT INTO snapshots (stream_id, version, state_data, created_at)
+                    VALUES (?, ?, ?, ?)
+                    ON CONFLICT (stream_id) DO UPDATE
+                    SET version = excluded.version,
+                        state_data = excluded.state_data,
+                        created_at = excluded.created_at
+                    ",
+                )
+                .bind(stream_id.as_str())
+                .bind(to_i64(version)?)
+                .bind(&state)
+                .bind(chrono::Utc::now().to_rfc3339())
+                .execute(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+                tracing::debug!(stream_id = %stream_id, version = ?version, "Snapshot saved successfully");
+                Ok(())
+            }
+            .instrument(span),
+        )
+    }
+
+    fn load_snapshot(&self, stream_id: StreamId) -> StoreFuture<'_, Option<(Version, Vec<u8>)>> {
+        let span = tracing::info_span!("event_store.load_snapshot", stream_id = %stream_id);
+
+        Box::pin(
+            async move {
+                let row =
+                    sqlx::query("SELECT version, state_data FROM snapshots WHERE stream_id = ?")
+                        .bind(stream_id.as_str())
+                        .fetch_optional(&self.pool)
+                        .await
+                        .map_err(db_error)?;
+
+                row.map(|row| Ok((to_version(row.get("version"))?, row.get("state_data"))))
+                    .transpose()
+            }
+            .instrument(span),
+        )
+    }
+
+    fn append_batch(&self, batch: Vec<BatchAppend>) -> StoreFuture<'_, BatchAppendResults> {
+        let span = tracing::info_span!("event_store.append_batch", batch_size = batch.len());
+
+        Box::pin(
+            async move {
+                if batch.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let start = std::time::Instant::now();
+                let mut tx = self
+                    .pool
+                    .begin_with("BEGIN IMMEDIATE")
+                    .await
+                    .map_err(db_error)?;
+                let mut results = Vec::with_capacity(batch.len());
+                for operation in batch {
+                    // A savepoint per operation keeps a failed one from leaving partial rows
+                    sqlx::query("SAVEPOINT batch_operation")
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(db_error)?;
+                    let result = append_in_tx(
+                        &mut tx,
+                        &operation.stream_id,
+                        operation.expected_version,
+                        operation.events,
+                    )
+                    .await;
+                    if result.is_err() {
+                        sqlx::query("ROLLBACK TO batch_operation")
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(db_error)?;
+                    }
+                    sqlx::query("RELEASE batch_operation")
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(db_error)?;
+                    results.push(result);
+                }
+                tx.commit().await.map_err(|e| {
+                    EventStoreError::DatabaseError(format!("Failed to commit batch: {e}"))
+                })?;
+
+                metrics::histogram!("event_store.batch.duration")
+                    .record(start.elapsed().as_secs_f64());
+                Ok(results)
+            }
+            .instrument(span),
+        )
+    }
+
+    fn delete_stream(&self, stream_id: StreamId) -> StoreFuture<'_, ()> {
+        let span = tracing::info_span!("event_store.delete_stream", stream_id = %stream_id);
+
+        Box::pin(
+            async move {
+                let mut tx = self
+                    .pool
+                    .begin_with("BEGIN IMMEDIATE")
+                    .await
+                    .map_err(db_error)?;
+                let deleted = sqlx::query("DELETE FROM events WHERE stream_id = ?")
+                    .bind(stream_id.as_str())
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(db_error)?
+                    .rows_affected();
+                if deleted == 0 {
+                    return Err(EventStoreError::StreamNotFound(stream_id));
+                }
+
+                sqlx::query("DELETE FROM snapshots WHERE stream_id = ?")
+                    .bind(stream_id.as_str())
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(db_error)?;
+                tx.commit().await.map_err(db_error)?;
+
+                tracing::info!(stream_id = %stream_id, event_count = deleted, "Stream deleted");
+                metrics::counter!("event_store.delete_stream.total").increment(1);
+                Ok(())
+            }
+            .instrument(span),
+        )
+    }
+
+    fn stream_metadata(&self, stream_id: StreamId) -> StoreFuture<'_, Option<StreamMetadata>> {
+        let span = tracing::info_span!("event_store.stream_metadata", stream_id = %stream_id);
+
+        Box::pin(
+            async move {
+                let row = sqlx::query(
+                    r"
+                    SELECT stream_id, MIN(created_at) AS created_at,
+                           COUNT(*) AS event_count, MAX(version) AS last_version
+                    FROM events
+                    WHERE stream_id = ?
+                    GROUP BY stream_id
+                    ",
+                )
+                .bind(stream_id.as_str())
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+                row.as_ref().map(stream_metadata_from_row).transpose()
+            }
+            .instrument(span),
+        )
+    }
+
+    fn list_streams(
+        &self,
+        prefix: Option<String>,
+        page: Pagination,
+    ) -> StoreFuture<'_, Vec<StreamMetadata>> {
+        let span = tracing::info_span!(
+            "event_store.list_streams",
+            prefix = ?prefix,
+            offset = page.offset,
+            limit = page.limit,
+        );
+
+        Box::pin(
+            async move {
+                let overflow = |e: std::num::TryFromIntError| {
+                    EventStoreError::DatabaseError(format!("Pagination overflow: {e}"))
+                };
+
+                // substr() rather than LIKE, so `%` and `_` in the prefix are literal
+                let rows = sqlx::query(
+                    r"
+                    SELECT stream_id, MIN(created_at) AS created_at,
+                           COUNT(*) AS event_count, MAX(version) AS last_version
+                    FROM events
+                    WHERE ?1 IS NULL OR substr(stream_id, 1, length(?1)) = ?1
+                    GROUP BY stream_id
+                    ORDER BY stream_id
+                    LIMIT ?2 OFFSET ?3
+                    ",
+                )
+                .bind(prefix.as_deref())
+                .bind(i64::try_from(page.limit).map_err(overflow)?)
+                .bind(i64::try_from(page.offset).map_err(overflow)?)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+                rows.iter().map(stream_metadata_from_row).collect()
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+mod tests {
+    use super::*;
+
+    fn event(event_type: &str) -> SerializedEvent {
+        SerializedEvent::new(event_type.to_string(), vec![1, 2, 3], None)
+    }
+
+    #[tokio::test]
+    async fn test_append_load_and_concurrency_conflict() {
+        let store = SqliteEventStore::in_memory().await.unwrap();
+        let stream = StreamId::new("order-1");
+
+        let version = store
+            .append_events(
+                stream.clone(),
+                Some(Version::new(0)),
+                vec![event("Placed.v1"), event("Paid.v1")],
+            )
+            .await
+            .unwrap();
+        assert_eq!(version, Version::new(2));
+
+        let conflict = store
+            .append_events(
+                stream.clone(),
+                Some(Version::new(1)),
+                vec![event("Shipped.v1")],
+            )
+            .await;
+        assert!(matches!(
+            conflict,
+            Err(EventStoreError::ConcurrencyConflict { actual, .. }) if actual == Version::new(2)
+        ));
+
+        let tail = store
+            .load_events(stream.clone(), Some(Version::new(2)))
+            .await
+            .unwrap();
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail[0].event_type, "Paid.v1");
+        assert_eq!(store.load_events(stream, None).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_events_page_limits_rows() {
+        let store = SqliteEventStore::in_memory().await.unwrap();
+        let stream = StreamId::new("order-1");
+        let events = ["A.v1", "B.v1", "C.v1", "D.v1", "E.v1"].map(event).to_vec();
+        store
+            .append_events(stream.clone(), None, events)
+            .await
+            .unwrap();
+
+        let first = store
+            .load_events_page(stream.clone(), Version::new(0), 2)
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].event_type, "A.v1");
+
+        let rest = store
+            .load_events_page(stream, Version::new(3), 10)
+            .await
+            .unwrap();
+        let types: Vec<&str> = rest.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, ["C.v1", "D.v1", "E.v1"]);
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_replace_and_delete_with_stream() {
+        let store = SqliteEventStore::in_memory().await.unwrap();
+        let stream = StreamId::new("order-1");
+        store
+            .append_events(stream.clone(), None, vec![event("Placed.v1")])
+            .await
+            .unwrap();
+
+        store
+            .save_snapshot(stream.clone(), Version::new(1), b"v1".to_vec())
+            .await
+            .unwrap();
+        store
+            .save_snapshot(stream.clone(), Version::new(1), b"v1b".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            store.load_snapshot(stream.clone()).await.unwrap(),
+            Some((Version::new(1), b"v1b".to_vec()))
+        );
+
+        store.delete_stream(stream.clone()).await.unwrap();
+        assert_eq!(store.load_snapshot(stream.clone()).await.unwrap(), None);
+        assert!(matches!(
+            store.delete_stream(stream).await,
+            Err(EventStoreError::StreamNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_batch_failure_rolls_back_only_its_operation() {
+        let store = SqliteEventStore::in_memory().await.unwrap();
+        let results = store
+            .append_batch(vec![
+                BatchAppend::new(
+                    StreamId::new("a"),
+                    Some(Version::new(0)),
+                    vec![event("A.v1")],
+                ),
+                BatchAppend::new(
+                    StreamId::new("b"),
+                    Some(Version::new(3)),
+                    vec![event("B.v1")],
+                ),
+                BatchAppend::new(StreamId::new("c"), None, vec![]),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(results[0].as_ref().unwrap(), &Version::new(1));
+        assert!(matches!(
+            results[1],
+            Err(EventStoreError::ConcurrencyConflict { .. })
+        ));
+        assert!(results[2].is_err());
+
+        let streams = store
+            .list_streams(None, Pagination::first(10))
+            .await
+            .unwrap();
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].stream_id, StreamId::new("a"));
+        assert_eq!(streams[0].last_version, Version::new(1));
+    }
+
+    #[tokio::test]
+    async fn test_file_database_persists_across_opens() {
+        let path =
+            std::env::temp_dir().join(format!("composable-rust-sqlite-{}.db", std::process::id()));
+        let stream = StreamId::new("order-1");
+        {
+            let store = SqliteEventStore::open(&path).await.unwrap();
+            store
+                .append_events(stream.clone(), None, vec![event("Placed.v1")])
+                .await
+                .unwrap();
+            store.pool().close().await;
+        }
+
+        let reopened = SqliteEventStore::open(&path).await.unwrap();
+        let metadata = reopened.stream_metadata(stream).await.unwrap().unwrap();
+        assert_eq!(metadata.event_count, 1);
+        reopened.pool().close().await;
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
+    }
+}
@@ -0,0 +1,783 @@
+//! Seeded fault injection across a whole environment
+//!
+//! The flaky wrappers fail calls on a fixed script. [`Chaos`] instead makes
+//! every dependency of a [`BaseEnvironment`] misbehave at random, driven by a
+//! single seed so a failing run can be replayed exactly:
+//! - Event store and event bus calls fail with a configurable probability
+//!   (overall or per operation), with retryable errors
+//! - Calls are delayed by a [`LatencyProfile`], whose jitter reorders
+//!   concurrent calls
+//! - Publishes can be held back and delivered after later ones
+//! - Clock readings are skewed by a random offset
+//!
+//! Reducers written against the `Has*` traits run unchanged on a
+//! `Chaos<E>`, which makes it a drop-in environment for testing sagas and
+//! retry logic. There is no HTTP client trait to wrap; hand-written mocks can
+//! draw from the same seeded sequence through [`ChaosInjector::inject`].
+//!
+//! Delays use `tokio::time::sleep`; run chaos tests with paused time
+//! (`#[tokio::test(start_paused = true)]`) on the current-thread runtime to
+//! keep the interleaving, and therefore every random draw, reproducible.
+
+use crate::latency::{LatencyProfile, split_mix64};
+use chrono::{DateTime, Utc};
+use composable_rust_core::base_environment::{
+    BaseEnvironment, HasClock, HasEventBus, HasEventStore,
+};
+use composable_rust_core::environment::Clock;
+use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::event_bus::{EventBus, EventBusError, EventStream, PublishOptions};
+use composable_rust_core::event_store::{
+    BatchAppend, BatchAppendResults, EventStore, EventStoreError, Pagination, StreamMetadata,
+};
+use composable_rust_core::stream::{StreamId, Version};
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How much chaos to inject, and the seed that makes it reproducible.
+///
+/// Rates are probabilities between 0.0 and 1.0; values outside that range
+/// are clamped. Everything is off by default.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_testing::mocks::{ChaosConfig, Latency, LatencyProfile};
+/// use std::time::Duration;
+///
+/// let config = ChaosConfig::new(42)
+///     .with_failure_rate(0.1)
+///     .with_operation_failure_rate("publish", 0.3)
+///     .with_latency(LatencyProfile::new().with_default(Latency::Uniform {
+///         min: Duration::from_millis(1),
+///         max: Duration::from_millis(50),
+///     }))
+///     .with_reorder_rate(0.2)
+///     .with_clock_skew(Duration::from_secs(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChaosConfig {
+    /// Seed of every random draw
+    seed: u64,
+    /// Failure probability of operations without their own rate
+    failure_rate: f64,
+    /// Failure probability by operation name
+    operation_rates: HashMap<String, f64>,
+    /// Delays, reseeded from `seed`
+    latency: Option<LatencyProfile>,
+    /// Probability that a publish is held back
+    reorder_rate: f64,
+    /// How long a held-back publish waits
+    reorder_delay: Duration,
+    /// Largest clock offset, in either direction
+    clock_skew: Duration,
+}
+
+impl ChaosConfig {
+    /// Create a configuration that injects nothing, seeded with `seed`.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            failure_rate: 0.0,
+            operation_rates: HashMap::new(),
+            latency: None,
+            reorder_rate: 0.0,
+            reorder_delay: Duration::from_millis(50),
+            clock_skew: Duration::ZERO,
+        }
+    }
+
+    /// Fail operations without their own rate with probability `rate`.
+    #[must_use]
+    pub const fn with_failure_rate(mut self, rate: f64) -> Self {
+        self.failure_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Fail one operation, named after its trait method, with probability `rate`.
+    #[must_use]
+    pub fn with_operation_failure_rate(mut self, operation: impl Into<String>, rate: f64) -> Self {
+        self.operation_rates
+            .insert(operation.into(), rate.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Delay calls by `profile`, keyed by operation name.
+    ///
+    /// The profile is reseeded from the chaos seed, so one seed reproduces
+    /// the whole run.
+    #[must_use]
+    pub fn with_latency(mut self, profile: LatencyProfile) -> Self {
+        self.latency = Some(profile);
+        self
+    }
+
+    /// Hold back publishes with probability `rate`, delivering them after
+    /// the reorder delay so later publishes overtake them.
+    #[must_use]
+    pub const fn with_reorder_rate(mut self, rate: f64) -> Self {
+        self.reorder_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// How long a held-back publish waits before it is delivered (50ms by default).
+    #[must_use]
+    pub const fn with_reorder_delay(mut self, delay: Duration) -> Self {
+        self.reorder_delay = delay;
+        self
+    }
+
+    /// Offset every clock reading by a random amount within `±max`.
+    ///
+    /// Each reading draws its own offset, so consecutive readings can go
+    /// backwards, as with unsynchronized clocks across machines.
+    #[must_use]
+    pub const fn with_clock_skew(mut self, max: Duration) -> Self {
+        self.clock_skew = max;
+        self
+    }
+
+    fn failure_rate(&self, operation: &str) -> f64 {
+        self.operation_rates
+            .get(operation)
+            .copied()
+            .unwrap_or(self.failure_rate)
+    }
+}
+
+/// What a [`ChaosInjector`] has done so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChaosStats {
+    /// Calls that went through the injector
+    pub calls: u64,
+    /// Calls that were failed on purpose
+    pub failures: u64,
+    /// Publishes that were held back
+    pub reordered: u64,
+}
+
+/// Random state and counters shared by every wrapper of one [`Chaos`]
+#[derive(Debug)]
+struct InjectorState {
+    config: ChaosConfig,
+    /// `SplitMix64` state for failure, reorder and skew draws
+    rng: Mutex<u64>,
+    calls: AtomicU64,
+    failures: AtomicU64,
+    reordered: AtomicU64,
+}
+
+/// Seeded source of faults, shared by the wrappers of a [`Chaos`] environment.
+///
+/// Clones share the random sequence and the counters. Hand-written mocks,
+/// such as a stub HTTP client, call [`inject`](Self::inject) to take part in
+/// the same reproducible run:
+///
+/// ```
+/// use composable_rust_testing::mocks::{ChaosConfig, ChaosInjector};
+///
+/// # async fn example() {
+/// let injector = ChaosInjector::new(ChaosConfig::new(7).with_operation_failure_rate("http.get", 0.5));
+///
+/// // In the mock's `get`:
+/// if injector.inject("http.get").await {
+///     // return a 503
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChaosInjector {
+    state: Arc<InjectorState>,
+}
+
+impl ChaosInjector {
+    /// Create an injector drawing from `config`'s seed.
+    #[must_use]
+    pub fn new(mut config: ChaosConfig) -> Self {
+        let seed = config.seed;
+        config.latency = config.latency.take().map(|profile| profile.with_seed(seed));
+        Self {
+            state: Arc::new(InjectorState {
+                config,
+                rng: Mutex::new(seed),
+                calls: AtomicU64::new(0),
+                failures: AtomicU64::new(0),
+                reordered: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Apply the latency of `operation`, then report whether the call must fail.
+    pub async fn inject(&self, operation: &str) -> bool {
+        self.state.calls.fetch_add(1, Ordering::SeqCst);
+        if let Some(profile) = &self.state.config.latency {
+            profile.delay(operation).await;
+        }
+
+        let fail = self.chance(self.state.config.failure_rate(operation));
+        if fail {
+            self.state.failures.fetch_add(1, Ordering::SeqCst);
+        }
+        fail
+    }
+
+    /// Whether the next publish should be held back.
+    #[must_use]
+    pub fn reorder(&self) -> bool {
+        let reorder = self.chance(self.state.config.reorder_rate);
+        if reorder {
+            self.state.reordered.fetch_add(1, Ordering::SeqCst);
+        }
+        reorder
+    }
+
+    /// Draw the offset for the next clock reading.
+    #[must_use]
+    pub fn skew(&self) -> chrono::Duration {
+        let max = i64::try_from(self.state.config.clock_skew.as_nanos()).unwrap_or(i64::MAX / 2);
+        if max == 0 {
+            return chrono::Duration::zero();
+        }
+        let span = max.unsigned_abs() * 2 + 1;
+        let offset = i64::try_from(self.next_random() % span).unwrap_or(max) - max;
+        chrono::Duration::nanoseconds(offset)
+    }
+
+    /// Counters of everything injected so far.
+    #[must_use]
+    pub fn stats(&self) -> ChaosStats {
+        ChaosStats {
+            calls: self.state.calls.load(Ordering::SeqCst),
+            failures: self.state.failures.load(Ordering::SeqCst),
+            reordered: self.state.reordered.load(Ordering::SeqCst),
+        }
+    }
+
+    fn chance(&self, rate: f64) -> bool {
+        if rate <= 0.0 {
+            return false;
+        }
+        // 53 random bits map exactly onto the doubles in [0, 1)
+        #[allow(clippy::cast_precision_loss)]
+        let draw = (self.next_random() >> 11) as f64 / (1_u64 << 53) as f64;
+        draw < rate
+    }
+
+    fn next_random(&self) -> u64 {
+        let mut state = self
+            .state
+            .rng
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        split_mix64(&mut state)
+    }
+}
+
+/// Event store wrapper that fails and delays calls at random.
+///
+/// Failures are retryable [`EventStoreError::DatabaseError`]s, named after
+/// the operation (`append_events`, `load_events`, ...).
+#[derive(Clone)]
+pub struct ChaosEventStore {
+    inner: Arc<dyn EventStore>,
+    injector: ChaosInjector,
+}
+
+impl std::fmt::Debug for ChaosEventStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChaosEventStore")
+            .field("injector", &self.injector)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChaosEventStore {
+    /// Wrap `inner`, drawing faults from `injector`.
+    #[must_use]
+    pub fn new(inner: Arc<dyn EventStore>, injector: ChaosInjector) -> Self {
+        Self { inner, injector }
+    }
+
+    fn injected(operation: &str) -> EventStoreError {
+        EventStoreError::DatabaseError(format!("chaos: injected failure in {operation}"))
+    }
+}
+
+impl EventStore for ChaosEventStore {
+    fn append_events(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+    ) -> Pin<Box<dyn Future<Output = Result<Version, EventStoreError>> + Send + '_>> {
+        Box::pin(async move {
+            if self.injector.inject("append_events").await {
+                return Err(Self::injected("append_events"));
+            }
+            self.inner
+                .append_events(stream_id, expected_version, events)
+                .await
+        })
+    }
+
+    fn append_events_idempotent(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+        idempotency_key: String,
+    ) -> Pin<Box<dyn Future<Output = Result<Version, EventStoreError>> + Send + '_>> {
+        Box::pin(async move {
+            // Scripted under the same name: a keyed append is still an append
+            if self.injector.inject("append_events").await {
+                return Err(Self::injected("append_events"));
+            }
+            self.inner
+                .append_events_idempotent(stream_id, expected_version, events, idempotency_key)
+                .await
+        })
+    }
+
+    fn load_events(
+        &self,
+        stream_id: StreamId,
+        from_version: Option<Version>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<SerializedEvent>, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.inject("load_events").await {
+                return Err(Self::injected("load_events"));
+            }
+            self.inner.load_events(stream_id, from_version).await
+        })
+    }
+
+    fn load_events_page(
+        &self,
+        stream_id: StreamId,
+        from_version: Version,
+        limit: usize,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<SerializedEvent>, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.inject("load_events_page").await {
+                return Err(Self::injected("load_events_page"));
+            }
+            self.inner
+                .load_events_page(stream_id, from_version, limit)
+                .await
+        })
+    }
+
+    fn save_snapshot(
+        &self,
+        stream_id: StreamId,
+        version: Version,
+        state: Vec<u8>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventStoreError>> + Send + '_>> {
+        Box::pin(async move {
+            if self.injector.inject("save_snapshot").await {
+                return Err(Self::injected("save_snapshot"));
+            }
+            self.inner.save_snapshot(stream_id, version, state).await
+        })
+    }
+
+    fn load_snapshot(
+        &self,
+        stream_id: StreamId,
+    ) -> Pin<
+        Box<dyn Future<Output = Result<Option<(Version, Vec<u8>)>, EventStoreError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            if self.injector.inject("load_snapshot").await {
+                return Err(Self::injected("load_snapshot"));
+            }
+            self.inner.load_snapshot(stream_id).await
+        })
+    }
+
+    fn append_batch(
+        &self,
+        batch: Vec<BatchAppend>,
+    ) -> Pin<Box<dyn Future<Output = Result<BatchAppendResults, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.inject("append_batch").await {
+                return Err(Self::injected("append_batch"));
+            }
+            self.inner.append_batch(batch).await
+        })
+    }
+
+    fn delete_stream(
+        &self,
+        stream_id: StreamId,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventStoreError>> + Send + '_>> {
+        Box::pin(async move {
+            if self.injector.inject("delete_stream").await {
+                return Err(Self::injected("delete_stream"));
+            }
+            self.inner.delete_stream(stream_id).await
+        })
+    }
+
+    fn stream_metadata(
+        &self,
+        stream_id: StreamId,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<StreamMetadata>, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.inject("stream_metadata").await {
+                return Err(Self::injected("stream_metadata"));
+            }
+            self.inner.stream_metadata(stream_id).await
+        })
+    }
+
+    fn list_streams(
+        &self,
+        prefix: Option<String>,
+        page: Pagination,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<StreamMetadata>, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.inject("list_streams").await {
+                return Err(Self::injected("list_streams"));
+            }
+            self.inner.list_streams(prefix, page).await
+        })
+    }
+}
+
+/// Event bus wrapper that fails, delays and reorders calls at random.
+///
+/// Failed publishes and subscribes return retryable
+/// [`EventBusError::PublishFailed`] and [`EventBusError::SubscriptionFailed`].
+/// A held-back publish returns `Ok` at once and reaches the inner bus after
+/// the reorder delay, from a spawned task; if that late publish fails, the
+/// event is lost, as with an unacknowledged broker write.
+#[derive(Clone)]
+pub struct ChaosEventBus {
+    inner: Arc<dyn EventBus>,
+    injector: ChaosInjector,
+}
+
+impl std::fmt::Debug for ChaosEventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChaosEventBus")
+            .field("injector", &self.injector)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChaosEventBus {
+    /// Wrap `inner`, drawing faults from `injector`.
+    #[must_use]
+    pub fn new(inner: Arc<dyn EventBus>, injector: ChaosInjector) -> Self {
+        Self { inner, injector }
+    }
+}
+
+impl EventBus for ChaosEventBus {
+    fn publish(
+        &self,
+        topic: &str,
+        event: &SerializedEvent,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+        self.publish_with_options(topic, event, &PublishOptions::default())
+    }
+
+    fn publish_with_options(
+        &self,
+        topic: &str,
+        event: &SerializedEvent,
+        options: &PublishOptions,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+        let topic = topic.to_string();
+        let event = event.clone();
+        let options = options.clone();
+
+        Box::pin(async move {
+            if self.injector.inject("publish").await {
+                return Err(EventBusError::PublishFailed {
+                    topic,
+                    reason: "chaos: injected failure".to_string(),
+                });
+            }
+            if self.injector.reorder() {
+                let inner = Arc::clone(&self.inner);
+                let delay = self.injector.state.config.reorder_delay;
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    let _ = inner.publish_with_options(&topic, &event, &options).await;
+                });
+                return Ok(());
+            }
+            self.inner
+                .publish_with_options(&topic, &event, &options)
+                .await
+        })
+    }
+
+    fn subscribe(
+        &self,
+        topics: &[&str],
+    ) -> Pin<Box<dyn Future<Output = Result<EventStream, EventBusError>> + Send + '_>> {
+        let topics: Vec<String> = topics.iter().map(|s| (*s).to_string()).collect();
+
+        Box::pin(async move {
+            if self.injector.inject("subscribe").await {
+                return Err(EventBusError::SubscriptionFailed {
+                    topics,
+                    reason: "chaos: injected failure".to_string(),
+                });
+            }
+            let topics: Vec<&str> = topics.iter().map(String::as_str).collect();
+            self.inner.subscribe(&topics).await
+        })
+    }
+}
+
+/// Clock that skews the readings of an environment's clock at random.
+pub struct ChaosClock<E> {
+    env: Arc<E>,
+    injector: ChaosInjector,
+}
+
+impl<E> std::fmt::Debug for ChaosClock<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChaosClock")
+            .field("injector", &self.injector)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E: AsRef<BaseEnvironment> + Send + Sync> Clock for ChaosClock<E> {
+    fn now(&self) -> DateTime<Utc> {
+        self.env.clock().now() + self.injector.skew()
+    }
+}
+
+/// An environment whose clock, event store and event bus misbehave at random.
+///
+/// Wraps any environment that implements `AsRef<BaseEnvironment>` and
+/// implements it itself, so the `Has*` accessors return the chaotic
+/// dependencies; other fields of the wrapped environment are reached through
+/// `Deref`. See the [module documentation](self) for what gets injected.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_core::base_environment::HasEventStore;
+/// use composable_rust_core::stream::StreamId;
+/// use composable_rust_testing::mocks::{Chaos, ChaosConfig, test_environment};
+///
+/// # async fn example() {
+/// let env = Chaos::new(test_environment(), ChaosConfig::new(42).with_failure_rate(0.25));
+///
+/// let mut failed = 0_u64;
+/// for _ in 0..20 {
+///     if env.event_store().append_events(StreamId::new("order-1"), None, vec![]).await.is_err() {
+///         failed += 1;
+///     }
+/// }
+/// assert_eq!(failed, env.stats().failures);
+/// # }
+/// ```
+pub struct Chaos<E> {
+    inner: Arc<E>,
+    base: BaseEnvironment,
+    injector: ChaosInjector,
+}
+
+impl<E> std::fmt::Debug for Chaos<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Chaos")
+            .field("injector", &self.injector)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E: AsRef<BaseEnvironment> + Send + Sync + 'static> Chaos<E> {
+    /// Wrap `env`, injecting faults as configured by `config`.
+    #[must_use]
+    pub fn new(env: E, config: ChaosConfig) -> Self {
+        let injector = ChaosInjector::new(config);
+        let inner = Arc::new(env);
+        let base = inner
+            .as_ref()
+            .as_ref()
+            .with_overrides()
+            .clock(ChaosClock {
+                env: Arc::clone(&inner),
+                injector: injector.clone(),
+            })
+            .event_store(Arc::new(ChaosEventStore::new(
+                Arc::clone(inner.event_store()),
+                injector.clone(),
+            )))
+            .event_bus(Arc::new(ChaosEventBus::new(
+                Arc::clone(inner.event_bus()),
+                injector.clone(),
+            )))
+            .build();
+        Self {
+            inner,
+            base,
+            injector,
+        }
+    }
+}
+
+impl<E> Chaos<E> {
+    /// The wrapped environment, without chaos.
+    #[must_use]
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// The injector, for hand-written mocks that should share the run's seed.
+    #[must_use]
+    pub const fn injector(&self) -> &ChaosInjector {
+        &self.injector
+    }
+
+    /// Counters of everything injected so far.
+    #[must_use]
+    pub fn stats(&self) -> ChaosStats {
+        self.injector.stats()
+    }
+}
+
+impl<E> AsRef<BaseEnvironment> for Chaos<E> {
+    fn as_ref(&self) -> &BaseEnvironment {
+        &self.base
+    }
+}
+
+impl<E> Deref for Chaos<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+mod tests {
+    use super::*;
+    use crate::mocks::{FixedClock, InMemoryEventBus, InMemoryEventStore, test_clock};
+    use futures::StreamExt;
+
+    fn event(n: u8) -> SerializedEvent {
+        SerializedEvent::new("Placed".to_string(), vec![n], None)
+    }
+
+    async fn append_outcomes(seed: u64) -> Vec<bool> {
+        let env = Chaos::new(
+            crate::mocks::test_environment(),
+            ChaosConfig::new(seed).with_failure_rate(0.5),
+        );
+        let mut outcomes = Vec::new();
+        for n in 0..32 {
+            let result = env
+                .event_store()
+                .append_events(StreamId::new("order-1"), None, vec![event(n)])
+                .await;
+            outcomes.push(result.is_ok());
+        }
+        outcomes
+    }
+
+    #[tokio::test]
+    async fn test_same_seed_injects_same_failures() {
+        let first = append_outcomes(7).await;
+        assert_eq!(first, append_outcomes(7).await);
+        assert_ne!(first, append_outcomes(8).await);
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_held_back_publishes_arrive_out_of_order() {
+        let env = Chaos::new(
+            crate::mocks::test_environment(),
+            ChaosConfig::new(3)
+                .with_reorder_rate(0.5)
+                .with_reorder_delay(Duration::from_millis(100)),
+        );
+        let mut events = env
+            .inner()
+            .event_bus()
+            .subscribe(&["orders"])
+            .await
+            .unwrap();
+
+        for n in 0..10 {
+            env.event_bus().publish("orders", &event(n)).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let mut received = Vec::new();
+        for _ in 0..10 {
+            received.push(events.next().await.unwrap().unwrap().data[0]);
+        }
+        assert_ne!(received, (0..10).collect::<Vec<u8>>());
+        received.sort_unstable();
+        assert_eq!(received, (0..10).collect::<Vec<u8>>());
+        assert!(env.stats().reordered > 0);
+    }
+
+    #[tokio::test]
+    async fn test_operation_rates_and_clock_skew() {
+        let clock = test_clock();
+        let base = BaseEnvironment::new(
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemoryEventBus::new()),
+        )
+        .with_overrides()
+        .clock(FixedClock::new(clock.now()))
+        .build();
+        let env = Chaos::new(
+            base,
+            ChaosConfig::new(1)
+                .with_operation_failure_rate("load_events", 1.0)
+                .with_clock_skew(Duration::from_secs(5)),
+        );
+
+        let stream = StreamId::new("order-1");
+        env.event_store()
+            .append_events(stream.clone(), None, vec![event(0)])
+            .await
+            .unwrap();
+        let error = env
+            .event_store()
+            .load_events(stream, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, EventStoreError::DatabaseError(_)));
+        assert_eq!(
+            env.stats(),
+            ChaosStats {
+                calls: 2,
+                failures: 1,
+                reordered: 0,
+            }
+        );
+
+        let readings: Vec<_> = (0..20).map(|_| env.clock().now()).collect();
+        assert!(
+            readings
+                .iter()
+                .all(|reading| { (*reading - clock.now()).abs() <= chrono::Duration::seconds(5) })
+        );
+        assert!(readings.iter().any(|reading| *reading != clock.now()));
+    }
+}
@@ -0,0 +1,186 @@
+//! Feature flags with per-test overrides and a record of every check
+//!
+//! [`MockFeatureFlags`] answers from overrides set by the test, falling back
+//! to another [`FeatureFlags`] implementation (all flags off by default). It
+//! records every check, so a test can assert which flags a reducer consulted
+//! and for whom.
+
+use composable_rust_core::feature_flags::{FeatureFlags, FlagContext, StaticFeatureFlags};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Feature flags a test can toggle while the code under test runs.
+///
+/// Overrides apply to every context. Clones share overrides and recorded
+/// checks, so keep a clone after handing one to the environment.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_core::base_environment::HasFeatureFlags;
+/// use composable_rust_core::feature_flags::FlagContext;
+/// use composable_rust_testing::mocks::{MockFeatureFlags, test_environment};
+/// use std::sync::Arc;
+///
+/// let flags = MockFeatureFlags::new();
+/// let env = test_environment()
+///     .with_overrides()
+///     .feature_flags(Arc::new(flags.clone()))
+///     .build();
+///
+/// assert!(!env.feature_flags().is_enabled("express-checkout", &FlagContext::new()));
+/// flags.enable("express-checkout");
+/// assert!(env.feature_flags().is_enabled("express-checkout", &FlagContext::new()));
+/// assert_eq!(flags.checks_of("express-checkout").len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct MockFeatureFlags {
+    /// Answers for flags without an override
+    fallback: Arc<dyn FeatureFlags>,
+    /// Forced answers by flag name
+    overrides: Arc<RwLock<HashMap<String, bool>>>,
+    /// Every check made, in order
+    checks: Arc<Mutex<Vec<FlagCheck>>>,
+}
+
+/// One call to [`FeatureFlags::is_enabled`] on a [`MockFeatureFlags`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagCheck {
+    /// Flag that was checked
+    pub flag: String,
+    /// Context it was checked for
+    pub context: FlagContext,
+    /// The answer given
+    pub enabled: bool,
+}
+
+impl std::fmt::Debug for MockFeatureFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockFeatureFlags")
+            .field("overrides", &self.overrides)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for MockFeatureFlags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockFeatureFlags {
+    /// Flags that are all off until enabled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_fallback(Arc::new(StaticFeatureFlags::new()))
+    }
+
+    /// Answer flags without an override from `fallback`, e.g. the
+    /// production rules.
+    #[must_use]
+    pub fn with_fallback(fallback: Arc<dyn FeatureFlags>) -> Self {
+        Self {
+            fallback,
+            overrides: Arc::new(RwLock::new(HashMap::new())),
+            checks: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Force `flag` on for every context.
+    pub fn enable(&self, flag: impl Into<String>) {
+        self.set(flag, true);
+    }
+
+    /// Force `flag` off for every context.
+    pub fn disable(&self, flag: impl Into<String>) {
+        self.set(flag, false);
+    }
+
+    /// Force `flag` to `enabled` for every context.
+    pub fn set(&self, flag: impl Into<String>, enabled: bool) {
+        if let Ok(mut overrides) = self.overrides.write() {
+            overrides.insert(flag.into(), enabled);
+        }
+    }
+
+    /// Remove the override of `flag`, answering it from the fallback again.
+    pub fn reset(&self, flag: &str) {
+        if let Ok(mut overrides) = self.overrides.write() {
+            overrides.remove(flag);
+        }
+    }
+
+    /// Every check made so far, in order.
+    #[must_use]
+    pub fn checks(&self) -> Vec<FlagCheck> {
+        self.checks
+            .lock()
+            .map_or_else(|_| Vec::new(), |checks| checks.clone())
+    }
+
+    /// The checks made of `flag`, in order.
+    #[must_use]
+    pub fn checks_of(&self, flag: &str) -> Vec<FlagCheck> {
+        self.checks()
+            .into_iter()
+            .filter(|check| check.flag == flag)
+            .collect()
+    }
+
+    /// Forget the recorded checks.
+    pub fn clear_checks(&self) {
+        if let Ok(mut checks) = self.checks.lock() {
+            checks.clear();
+        }
+    }
+}
+
+impl FeatureFlags for MockFeatureFlags {
+    fn is_enabled(&self, flag: &str, context: &FlagContext) -> bool {
+        let forced = self
+            .overrides
+            .read()
+            .ok()
+            .and_then(|overrides| overrides.get(flag).copied());
+        let enabled = forced.unwrap_or_else(|| self.fallback.is_enabled(flag, context));
+
+        if let Ok(mut checks) = self.checks.lock() {
+            checks.push(FlagCheck {
+                flag: flag.to_string(),
+                context: context.clone(),
+                enabled,
+            });
+        }
+        enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_take_precedence_over_fallback() {
+        let flags = MockFeatureFlags::with_fallback(Arc::new(
+            StaticFeatureFlags::new().with_users("beta", ["alice"]),
+        ));
+        let alice = FlagContext::new().with_user("alice");
+        let bob = FlagContext::new().with_user("bob");
+
+        assert!(flags.is_enabled("beta", &alice));
+        assert!(!flags.is_enabled("beta", &bob));
+
+        flags.disable("beta");
+        assert!(!flags.is_enabled("beta", &alice));
+
+        flags.reset("beta");
+        assert!(flags.is_enabled("beta", &alice));
+
+        let answers: Vec<bool> = flags.checks_of("beta").iter().map(|c| c.enabled).collect();
+        assert_eq!(answers, [true, false, false, true]);
+        assert_eq!(flags.checks()[1].context, bob);
+
+        flags.clear_checks();
+        assert!(flags.checks().is_empty());
+    }
+}
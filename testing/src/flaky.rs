@@ -0,0 +1,648 @@
+//! Failure-injecting wrappers for exercising resilience paths
+//!
+//! Wrap any [`EventStore`] or [`EventBus`] to make it fail on demand:
+//! - [`FailureScript`]: Which calls fail (first N, every Kth) and how long each call takes
+//! - [`FlakyEventStore`]: Scripted failures for event store operations
+//! - [`FlakyEventBus`]: Scripted failures for publish and subscribe
+//!
+//! Latency is injected with `tokio::time::sleep`, so tests running with paused
+//! time (`#[tokio::test(start_paused = true)]`) stay fast and deterministic. For
+//! per-operation or jittered delays, attach a [`LatencyProfile`].
+
+use crate::latency::LatencyProfile;
+use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::event_bus::{EventBus, EventBusError, EventStream, PublishOptions};
+use composable_rust_core::event_store::{
+    BatchAppend, BatchAppendResults, EventStore, EventStoreError, Pagination, StreamMetadata,
+};
+use composable_rust_core::stream::{StreamId, Version};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Script deciding which calls to a flaky wrapper fail.
+///
+/// Calls are numbered from 1. A call fails if it is among the first
+/// `fail_first` calls or if its number is a multiple of `fail_every`. Every
+/// call, failing or not, first waits for the configured latency.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_testing::mocks::FailureScript;
+/// use std::time::Duration;
+///
+/// // Fail the first two calls, then every third call, each taking 50ms
+/// let script = FailureScript::new()
+///     .fail_first(2)
+///     .fail_every(3)
+///     .with_latency(Duration::from_millis(50));
+/// assert!(script.fails_on(1));
+/// assert!(!script.fails_on(4));
+/// assert!(script.fails_on(6));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FailureScript {
+    /// Number of initial calls that fail
+    fail_first: u64,
+    /// Fail every call whose number is a multiple of this
+    fail_every: Option<u64>,
+    /// Delay applied before every call
+    latency: Option<Duration>,
+}
+
+impl FailureScript {
+    /// Create a script under which every call succeeds immediately.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            fail_first: 0,
+            fail_every: None,
+            latency: None,
+        }
+    }
+
+    /// Fail the first `calls` calls.
+    #[must_use]
+    pub const fn fail_first(mut self, calls: u64) -> Self {
+        self.fail_first = calls;
+        self
+    }
+
+    /// Fail every `k`th call (calls `k`, `2k`, ...). A `k` of 0 disables this.
+    #[must_use]
+    pub const fn fail_every(mut self, k: u64) -> Self {
+        self.fail_every = if k == 0 { None } else { Some(k) };
+        self
+    }
+
+    /// Delay every call by `latency` before it runs.
+    #[must_use]
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Whether the call with the given (1-based) number fails under this script.
+    #[must_use]
+    pub const fn fails_on(&self, call: u64) -> bool {
+        if call <= self.fail_first {
+            return true;
+        }
+        match self.fail_every {
+            Some(k) => call % k == 0,
+            None => false,
+        }
+    }
+}
+
+/// Call counters and scripts shared by clones of a flaky wrapper
+#[derive(Debug, Default)]
+struct Injector {
+    /// Script for operations without an override
+    default: FailureScript,
+    /// Per-operation scripts, keyed by trait method name
+    overrides: HashMap<&'static str, FailureScript>,
+    /// Additional per-operation latency
+    profile: Option<LatencyProfile>,
+    /// Calls made so far, by operation
+    calls: RwLock<HashMap<&'static str, u64>>,
+    /// Calls that were failed on purpose
+    failures: AtomicU64,
+}
+
+impl Injector {
+    /// Count a call to `operation`, apply latency, and report whether it must fail.
+    async fn before(&self, operation: &'static str) -> bool {
+        let script = self
+            .overrides
+            .get(operation)
+            .copied()
+            .unwrap_or(self.default);
+
+        // Count the call before sleeping so concurrent callers keep their arrival order
+        let call = self.calls.write().map_or(1, |mut calls| {
+            let count = calls.entry(operation).or_default();
+            *count += 1;
+            *count
+        });
+
+        if let Some(latency) = script.latency {
+            tokio::time::sleep(latency).await;
+        }
+        if let Some(profile) = &self.profile {
+            profile.delay(operation).await;
+        }
+
+        let fail = script.fails_on(call);
+        if fail {
+            self.failures.fetch_add(1, Ordering::SeqCst);
+        }
+        fail
+    }
+
+    fn calls(&self, operation: &str) -> u64 {
+        self.calls
+            .read()
+            .map_or(0, |calls| calls.get(operation).copied().unwrap_or(0))
+    }
+
+    fn total_calls(&self) -> u64 {
+        self.calls.read().map_or(0, |calls| calls.values().sum())
+    }
+}
+
+/// Event store wrapper that fails calls according to a [`FailureScript`].
+///
+/// Calls are counted per operation (`append_events`, `load_events`, ...), so a
+/// script of `fail_first(1)` fails the first call of *each* operation. Use
+/// [`with_operation_script`](Self::with_operation_script) to target a single
+/// operation. Injected failures are [`EventStoreError::DatabaseError`]s; other
+/// calls are forwarded to the inner store.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_testing::mocks::{FailureScript, FlakyEventStore, InMemoryEventStore};
+/// use composable_rust_core::event_store::EventStore;
+/// use composable_rust_core::stream::StreamId;
+/// use std::sync::Arc;
+///
+/// # async fn example() {
+/// let store = FlakyEventStore::new(Arc::new(InMemoryEventStore::new()))
+///     .with_operation_script("append_events", FailureScript::new().fail_first(2));
+///
+/// assert!(store.append_events(StreamId::new("order-1"), None, vec![]).await.is_err());
+/// assert_eq!(store.failures(), 1);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FlakyEventStore {
+    /// Store that handles calls which are not failed
+    inner: Arc<dyn EventStore>,
+    /// Scripts and counters
+    injector: Arc<Injector>,
+}
+
+impl std::fmt::Debug for FlakyEventStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlakyEventStore")
+            .field("injector", &self.injector)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FlakyEventStore {
+    /// Wrap `inner` with a script under which every call succeeds.
+    #[must_use]
+    pub fn new(inner: Arc<dyn EventStore>) -> Self {
+        Self {
+            inner,
+            injector: Arc::new(Injector::default()),
+        }
+    }
+
+    /// Apply `script` to every operation without its own script.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the wrapper has been cloned.
+    #[must_use]
+    pub fn with_script(mut self, script: FailureScript) -> Self {
+        injector_mut(&mut self.injector).default = script;
+        self
+    }
+
+    /// Apply `script` to one operation, named after its [`EventStore`] method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the wrapper has been cloned.
+    #[must_use]
+    pub fn with_operation_script(mut self, operation: &'static str, script: FailureScript) -> Self {
+        injector_mut(&mut self.injector)
+            .overrides
+            .insert(operation, script);
+        self
+    }
+
+    /// Delay calls by `profile`, keyed by operation name, on top of any script latency.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the wrapper has been cloned.
+    #[must_use]
+    pub fn with_latency_profile(mut self, profile: LatencyProfile) -> Self {
+        injector_mut(&mut self.injector).profile = Some(profile);
+        self
+    }
+
+    /// Number of calls made to `operation`, including failed ones.
+    #[must_use]
+    pub fn calls(&self, operation: &str) -> u64 {
+        self.injector.calls(operation)
+    }
+
+    /// Number of calls made to any operation, including failed ones.
+    #[must_use]
+    pub fn total_calls(&self) -> u64 {
+        self.injector.total_calls()
+    }
+
+    /// Number of calls that were failed by the script.
+    #[must_use]
+    pub fn failures(&self) -> u64 {
+        self.injector.failures.load(Ordering::SeqCst)
+    }
+
+    fn injected(operation: &str) -> EventStoreError {
+        EventStoreError::DatabaseError(format!("injected failure in {operation}"))
+    }
+}
+
+/// Get mutable access to an injector while a wrapper is still being configured
+#[allow(clippy::expect_used)] // Misuse of the builder is a bug in the test itself
+fn injector_mut(injector: &mut Arc<Injector>) -> &mut Injector {
+    Arc::get_mut(injector).expect("flaky wrappers must be configured before they are cloned")
+}
+
+impl EventStore for FlakyEventStore {
+    fn append_events(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+    ) -> Pin<Box<dyn Future<Output = Result<Version, EventStoreError>> + Send + '_>> {
+        Box::pin(async move {
+            if self.injector.before("append_events").await {
+                return Err(Self::injected("append_events"));
+            }
+            self.inner
+                .append_events(stream_id, expected_version, events)
+                .await
+        })
+    }
+
+    fn append_events_idempotent(
+        &self,
+        stream_id: StreamId,
+        expected_version: Option<Version>,
+        events: Vec<SerializedEvent>,
+        idempotency_key: String,
+    ) -> Pin<Box<dyn Future<Output = Result<Version, EventStoreError>> + Send + '_>> {
+        Box::pin(async move {
+            // Scripted under the same name: a keyed append is still an append
+            if self.injector.before("append_events").await {
+                return Err(Self::injected("append_events"));
+            }
+            self.inner
+                .append_events_idempotent(stream_id, expected_version, events, idempotency_key)
+                .await
+        })
+    }
+
+    fn load_events(
+        &self,
+        stream_id: StreamId,
+        from_version: Option<Version>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<SerializedEvent>, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.before("load_events").await {
+                return Err(Self::injected("load_events"));
+            }
+            self.inner.load_events(stream_id, from_version).await
+        })
+    }
+
+    fn load_events_page(
+        &self,
+        stream_id: StreamId,
+        from_version: Version,
+        limit: usize,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<SerializedEvent>, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.before("load_events_page").await {
+                return Err(Self::injected("load_events_page"));
+            }
+            self.inner
+                .load_events_page(stream_id, from_version, limit)
+                .await
+        })
+    }
+
+    fn save_snapshot(
+        &self,
+        stream_id: StreamId,
+        version: Version,
+        state: Vec<u8>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventStoreError>> + Send + '_>> {
+        Box::pin(async move {
+            if self.injector.before("save_snapshot").await {
+                return Err(Self::injected("save_snapshot"));
+            }
+            self.inner.save_snapshot(stream_id, version, state).await
+        })
+    }
+
+    fn load_snapshot(
+        &self,
+        stream_id: StreamId,
+    ) -> Pin<
+        Box<dyn Future<Output = Result<Option<(Version, Vec<u8>)>, EventStoreError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            if self.injector.before("load_snapshot").await {
+                return Err(Self::injected("load_snapshot"));
+            }
+            self.inner.load_snapshot(stream_id).await
+        })
+    }
+
+    fn append_batch(
+        &self,
+        batch: Vec<BatchAppend>,
+    ) -> Pin<Box<dyn Future<Output = Result<BatchAppendResults, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.before("append_batch").await {
+                return Err(Self::injected("append_batch"));
+            }
+            self.inner.append_batch(batch).await
+        })
+    }
+
+    fn delete_stream(
+        &self,
+        stream_id: StreamId,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventStoreError>> + Send + '_>> {
+        Box::pin(async move {
+            if self.injector.before("delete_stream").await {
+                return Err(Self::injected("delete_stream"));
+            }
+            self.inner.delete_stream(stream_id).await
+        })
+    }
+
+    fn stream_metadata(
+        &self,
+        stream_id: StreamId,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<StreamMetadata>, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.before("stream_metadata").await {
+                return Err(Self::injected("stream_metadata"));
+            }
+            self.inner.stream_metadata(stream_id).await
+        })
+    }
+
+    fn list_streams(
+        &self,
+        prefix: Option<String>,
+        page: Pagination,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<StreamMetadata>, EventStoreError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if self.injector.before("list_streams").await {
+                return Err(Self::injected("list_streams"));
+            }
+            self.inner.list_streams(prefix, page).await
+        })
+    }
+}
+
+/// Event bus wrapper that fails calls according to a [`FailureScript`].
+///
+/// `publish` and `subscribe` are counted separately. Injected failures are
+/// [`EventBusError::PublishFailed`] and [`EventBusError::SubscriptionFailed`];
+/// other calls are forwarded to the inner bus. Streams returned by a successful
+/// `subscribe` are the inner bus's streams, unaffected by the script.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_testing::mocks::{FailureScript, FlakyEventBus, InMemoryEventBus};
+/// use composable_rust_core::event_bus::EventBus;
+/// use composable_rust_core::event::SerializedEvent;
+/// use std::sync::Arc;
+///
+/// # async fn example() {
+/// let bus = FlakyEventBus::new(Arc::new(InMemoryEventBus::new()))
+///     .with_operation_script("publish", FailureScript::new().fail_every(2));
+///
+/// let event = SerializedEvent::new("OrderPlaced".to_string(), vec![], None);
+/// assert!(bus.publish("orders", &event).await.is_ok());
+/// assert!(bus.publish("orders", &event).await.is_err());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FlakyEventBus {
+    /// Bus that handles calls which are not failed
+    inner: Arc<dyn EventBus>,
+    /// Scripts and counters
+    injector: Arc<Injector>,
+}
+
+impl std::fmt::Debug for FlakyEventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlakyEventBus")
+            .field("injector", &self.injector)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FlakyEventBus {
+    /// Wrap `inner` with a script under which every call succeeds.
+    #[must_use]
+    pub fn new(inner: Arc<dyn EventBus>) -> Self {
+        Self {
+            inner,
+            injector: Arc::new(Injector::default()),
+        }
+    }
+
+    /// Apply `script` to both `publish` and `subscribe`, unless overridden.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the wrapper has been cloned.
+    #[must_use]
+    pub fn with_script(mut self, script: FailureScript) -> Self {
+        injector_mut(&mut self.injector).default = script;
+        self
+    }
+
+    /// Apply `script` to one operation: `"publish"` or `"subscribe"`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the wrapper has been cloned.
+    #[must_use]
+    pub fn with_operation_script(mut self, operation: &'static str, script: FailureScript) -> Self {
+        injector_mut(&mut self.injector)
+            .overrides
+            .insert(operation, script);
+        self
+    }
+
+    /// Delay calls by `profile`, keyed by operation name, on top of any script latency.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the wrapper has been cloned.
+    #[must_use]
+    pub fn with_latency_profile(mut self, profile: LatencyProfile) -> Self {
+        injector_mut(&mut self.injector).profile = Some(profile);
+        self
+    }
+
+    /// Number of calls made to `operation`, including failed ones.
+    #[must_use]
+    pub fn calls(&self, operation: &str) -> u64 {
+        self.injector.calls(operation)
+    }
+
+    /// Number of calls made to either operation, including failed ones.
+    #[must_use]
+    pub fn total_calls(&self) -> u64 {
+        self.injector.total_calls()
+    }
+
+    /// Number of calls that were failed by the script.
+    #[must_use]
+    pub fn failures(&self) -> u64 {
+        self.injector.failures.load(Ordering::SeqCst)
+    }
+}
+
+impl EventBus for FlakyEventBus {
+    fn publish(
+        &self,
+        topic: &str,
+        event: &SerializedEvent,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+        self.publish_with_options(topic, event, &PublishOptions::default())
+    }
+
+    fn publish_with_options(
+        &self,
+        topic: &str,
+        event: &SerializedEvent,
+        options: &PublishOptions,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EventBusError>> + Send + '_>> {
+        let topic = topic.to_string();
+        let event = event.clone();
+        let options = options.clone();
+
+        Box::pin(async move {
+            if self.injector.before("publish").await {
+                return Err(EventBusError::PublishFailed {
+                    topic,
+                    reason: "injected failure".to_string(),
+                });
+            }
+            self.inner.publish_with_options(&topic, &event, &options).await
+        })
+    }
+
+    fn subscribe(
+        &self,
+        topics: &[&str],
+    ) -> Pin<Box<dyn Future<Output = Result<EventStream, EventBusError>> + Send + '_>> {
+        let topics: Vec<String> = topics.iter().map(|s| (*s).to_string()).collect();
+
+        Box::pin(async move {
+            if self.injector.before("subscribe").await {
+                return Err(EventBusError::SubscriptionFailed {
+                    topics,
+                    reason: "injected failure".to_string(),
+                });
+            }
+            let topics: Vec<&str> = topics.iter().map(String::as_str).collect();
+            self.inner.subscribe(&topics).await
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+mod tests {
+    use super::*;
+    use crate::mocks::{InMemoryEventBus, InMemoryEventStore};
+
+    fn event() -> SerializedEvent {
+        SerializedEvent::new("Placed".to_string(), vec![], None)
+    }
+
+    #[test]
+    fn test_failure_script_fails_first_and_every_kth_call() {
+        let script = FailureScript::new().fail_first(2).fail_every(4);
+        let failing: Vec<u64> = (1..=12).filter(|call| script.fails_on(*call)).collect();
+        assert_eq!(failing, [1, 2, 4, 8, 12]);
+        assert!(!FailureScript::new().fail_every(0).fails_on(5));
+    }
+
+    #[tokio::test]
+    async fn test_flaky_event_store_recovers_after_scripted_failures() {
+        let inner = Arc::new(InMemoryEventStore::new());
+        let store = FlakyEventStore::new(inner.clone())
+            .with_operation_script("append_events", FailureScript::new().fail_first(2));
+        let stream = StreamId::new("order-1");
+
+        for _ in 0..2 {
+            let result = store
+                .append_events(stream.clone(), None, vec![event()])
+                .await;
+            assert!(matches!(result, Err(EventStoreError::DatabaseError(_))));
+        }
+        store
+            .append_events(stream.clone(), None, vec![event()])
+            .await
+            .unwrap();
+
+        // Loads have no script of their own and fall back to the default, which never fails
+        assert_eq!(
+            store.load_events(stream.clone(), None).await.unwrap().len(),
+            1
+        );
+        assert_eq!(inner.load_events(stream, None).await.unwrap().len(), 1);
+        assert_eq!(store.calls("append_events"), 3);
+        assert_eq!(store.total_calls(), 4);
+        assert_eq!(store.failures(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_flaky_event_bus_fails_every_kth_publish() {
+        let bus = FlakyEventBus::new(Arc::new(InMemoryEventBus::new()))
+            .with_script(FailureScript::new().fail_every(3));
+
+        let mut outcomes = Vec::new();
+        for _ in 0..6 {
+            outcomes.push(bus.publish("orders", &event()).await.is_ok());
+        }
+        assert_eq!(outcomes, [true, true, false, true, true, false]);
+        assert!(bus.subscribe(&["orders"]).await.is_ok());
+        assert_eq!(bus.calls("subscribe"), 1);
+        assert_eq!(bus.failures(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_flaky_wrappers_inject_latency() {
+        let latency = Duration::from_millis(250);
+        let bus = FlakyEventBus::new(Arc::new(InMemoryEventBus::new()))
+            .with_script(FailureScript::new().with_latency(latency));
+
+        let started = tokio::time::Instant::now();
+        bus.publish("orders", &event()).await.unwrap();
+        assert_eq!(started.elapsed(), latency);
+
+        let timed_out =
+            tokio::time::timeout(Duration::from_millis(100), bus.publish("orders", &event())).await;
+        assert!(timed_out.is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! Artificial latency for reproducing interleavings and timeouts
+//!
+//! A [`LatencyProfile`] maps operation names to delays. The delays are applied
+//! with `tokio::time::sleep`, so under paused time (`#[tokio::test(start_paused = true)]`)
+//! they cost nothing in wall-clock time and the resulting interleaving of
+//! concurrent calls is identical on every run.
+//!
+//! Apply a profile to an event store or bus with
+//! [`FlakyEventStore::with_latency_profile`](crate::mocks::FlakyEventStore::with_latency_profile)
+//! and [`FlakyEventBus::with_latency_profile`](crate::mocks::FlakyEventBus::with_latency_profile),
+//! or call [`LatencyProfile::delay`] from any hand-written mock, such as a stub
+//! HTTP client.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Delay applied to one kind of call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Latency {
+    /// No delay
+    #[default]
+    None,
+    /// The same delay on every call
+    Fixed(Duration),
+    /// A delay drawn uniformly from `min..=max` on every call, using the profile's seed
+    Uniform {
+        /// Shortest delay
+        min: Duration,
+        /// Longest delay
+        max: Duration,
+    },
+}
+
+/// Per-operation artificial latency with seeded, reproducible jitter.
+///
+/// Operations are identified by name; the flaky wrappers use their trait
+/// method names (`"append_events"`, `"publish"`, ...). Operations without an
+/// entry use the default latency. Clones share the random state, so a profile
+/// shared between several mocks draws a single reproducible sequence.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_testing::mocks::{Latency, LatencyProfile};
+/// use std::time::Duration;
+///
+/// # async fn example() {
+/// let profile = LatencyProfile::new()
+///     .with_default(Latency::Fixed(Duration::from_millis(5)))
+///     .with_operation("append_events", Latency::Fixed(Duration::from_millis(200)))
+///     .with_operation("http.get", Latency::Uniform {
+///         min: Duration::from_millis(10),
+///         max: Duration::from_millis(90),
+///     });
+///
+/// // In a hand-written HTTP mock:
+/// profile.delay("http.get").await;
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LatencyProfile {
+    /// Latency for operations without an entry
+    default: Latency,
+    /// Latency by operation name
+    operations: HashMap<String, Latency>,
+    /// `SplitMix64` state for `Latency::Uniform`
+    rng: Arc<Mutex<u64>>,
+}
+
+impl Default for LatencyProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatencyProfile {
+    /// Create a profile with no latency and a seed of 0.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            default: Latency::None,
+            operations: HashMap::new(),
+            rng: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// Seed the jitter of [`Latency::Uniform`] entries.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(Mutex::new(seed));
+        self
+    }
+
+    /// Set the latency of operations without their own entry.
+    #[must_use]
+    pub const fn with_default(mut self, latency: Latency) -> Self {
+        self.default = latency;
+        self
+    }
+
+    /// Set the latency of one operation.
+    #[must_use]
+    pub fn with_operation(mut self, operation: impl Into<String>, latency: Latency) -> Self {
+        self.operations.insert(operation.into(), latency);
+        self
+    }
+
+    /// Draw the delay for the next call to `operation`.
+    #[must_use]
+    pub fn delay_for(&self, operation: &str) -> Duration {
+        match self
+            .operations
+            .get(operation)
+            .copied()
+            .unwrap_or(self.default)
+        {
+            Latency::None => Duration::ZERO,
+            Latency::Fixed(latency) => latency,
+            Latency::Uniform { min, max } => {
+                let (min, max) = if min <= max { (min, max) } else { (max, min) };
+                let span = u64::try_from(max.saturating_sub(min).as_nanos()).unwrap_or(u64::MAX);
+                let offset = match span.checked_add(1) {
+                    Some(range) => self.next_random() % range,
+                    None => self.next_random(),
+                };
+                min + Duration::from_nanos(offset)
+            },
+        }
+    }
+
+    /// Sleep for the next delay of `operation`.
+    pub async fn delay(&self, operation: &str) {
+        let latency = self.delay_for(operation);
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+    }
+
+    fn next_random(&self) -> u64 {
+        let mut state = self
+            .rng
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        split_mix64(&mut state)
+    }
+}
+
+/// Advance a `SplitMix64` generator and return its next value
+pub(crate) const fn split_mix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+mod tests {
+    use super::*;
+    use crate::mocks::{FlakyEventStore, InMemoryEventStore};
+    use composable_rust_core::event::SerializedEvent;
+    use composable_rust_core::event_store::{EventStore, EventStoreError};
+    use composable_rust_core::stream::{StreamId, Version};
+
+    #[test]
+    fn test_uniform_latency_is_bounded_and_reproducible() {
+        let uniform = Latency::Uniform {
+            min: Duration::from_millis(10),
+            max: Duration::from_millis(20),
+        };
+        let draw = |seed| {
+            let profile = LatencyProfile::new().with_seed(seed).with_default(uniform);
+            (0..50).map(|_| profile.delay_for("op")).collect::<Vec<_>>()
+        };
+
+        let first = draw(7);
+        assert_eq!(first, draw(7));
+        assert_ne!(first, draw(8));
+        assert!(
+            first
+                .iter()
+                .all(|d| (Duration::from_millis(10)..=Duration::from_millis(20)).contains(d))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_latency_profile_reproduces_append_race() {
+        // The slow writer issues its append first but reaches the store last,
+        // so it deterministically loses the optimistic concurrency race
+        let inner = Arc::new(InMemoryEventStore::new());
+        let profile = LatencyProfile::new()
+            .with_operation("append_events", Latency::Fixed(Duration::from_millis(100)));
+        let slow = FlakyEventStore::new(inner.clone()).with_latency_profile(profile);
+        let stream = StreamId::new("order-1");
+        let event = || SerializedEvent::new("Placed".to_string(), vec![], None);
+
+        let slow_append = slow.append_events(stream.clone(), Some(Version::new(0)), vec![event()]);
+        let fast_append = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            inner
+                .append_events(stream.clone(), Some(Version::new(0)), vec![event()])
+                .await
+        };
+        let (slow_result, fast_result) = tokio::join!(slow_append, fast_append);
+
+        assert_eq!(fast_result.unwrap(), Version::new(0));
+        assert!(matches!(
+            slow_result,
+            Err(EventStoreError::ConcurrencyConflict { .. })
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_latency_profile_triggers_timeouts() {
+        let profile = LatencyProfile::new()
+            .with_operation("http.get", Latency::Fixed(Duration::from_secs(5)));
+
+        let timed_out =
+            tokio::time::timeout(Duration::from_secs(1), profile.delay("http.get")).await;
+        assert!(timed_out.is_err());
+        assert!(
+            tokio::time::timeout(Duration::from_secs(1), profile.delay("http.post"))
+                .await
+                .is_ok()
+        );
+    }
+}
@@ -32,12 +32,30 @@
 use chrono::{DateTime, Utc};
 use composable_rust_core::environment::Clock;
 
+// Seeded fault injection across whole environments
+mod chaos;
+
+// Feature flags with per-test overrides
+mod feature_flags;
+
+// Failure-injecting EventStore/EventBus wrappers
+mod flaky;
+
+// Artificial latency profiles
+mod latency;
+
 // Projection testing utilities
 mod projection_mocks;
 
 // Reducer testing utilities
 mod reducer_test;
 
+// Given-When-Then harness for sagas
+mod saga;
+
+// Soak and stress testing of Stores
+mod stress;
+
 /// Mock implementations of Environment traits
 ///
 /// # Phase 1 Implementation
@@ -53,7 +71,17 @@ mod reducer_test;
 pub mod mocks {
     use super::{Clock, DateTime, Utc};
     use chrono::Duration;
+    use composable_rust_core::base_environment::BaseEnvironment;
+    use composable_rust_core::environment::{TimeProvider, Timestamp};
     use std::sync::{Arc, RwLock};
+    use std::time::{Instant, SystemTime};
+
+    pub use crate::chaos::{
+        Chaos, ChaosClock, ChaosConfig, ChaosEventBus, ChaosEventStore, ChaosInjector, ChaosStats,
+    };
+    pub use crate::feature_flags::{FlagCheck, MockFeatureFlags};
+    pub use crate::flaky::{FailureScript, FlakyEventBus, FlakyEventStore};
+    pub use crate::latency::{Latency, LatencyProfile};
 
     /// Fixed clock for deterministic tests
     ///
@@ -77,9 +105,14 @@ pub mod mocks {
     /// let time3 = clock.now();
     /// assert_eq!(time3, time1 + Duration::hours(1));
     /// ```
+    ///
+    /// As a [`TimeProvider`], `instant()` starts at the clock's creation and
+    /// moves forward by however far the time is past its initial value.
     #[derive(Debug, Clone)]
     pub struct FixedClock {
         time: Arc<RwLock<DateTime<Utc>>>,
+        initial: DateTime<Utc>,
+        origin: Instant,
     }
 
     impl FixedClock {
@@ -88,6 +121,8 @@ pub mod mocks {
         pub fn new(time: DateTime<Utc>) -> Self {
             Self {
                 time: Arc::new(RwLock::new(time)),
+                initial: time,
+                origin: Instant::now(),
             }
         }
 
@@ -183,6 +218,17 @@ pub mod mocks {
         }
     }
 
+    impl TimeProvider for FixedClock {
+        fn system_time(&self) -> SystemTime {
+            self.now().into()
+        }
+
+        fn instant(&self) -> Instant {
+            let elapsed = (self.now() - self.initial).to_std().unwrap_or_default();
+            self.origin + elapsed
+        }
+    }
+
     /// Create a default fixed clock for tests (2025-01-01 00:00:00 UTC)
     ///
     /// # Panics
@@ -199,16 +245,230 @@ pub mod mocks {
         )
     }
 
+    /// A [`BaseEnvironment`] for tests: [`test_clock`], an empty
+    /// [`InMemoryEventStore`] and an [`InMemoryEventBus`]
+    ///
+    /// Swap individual dependencies with
+    /// [`with_overrides`](BaseEnvironment::with_overrides).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use composable_rust_core::base_environment::HasClock;
+    /// use composable_rust_core::environment::Clock;
+    /// use composable_rust_testing::{test_clock, test_environment};
+    ///
+    /// let clock = test_clock();
+    /// let env = test_environment().with_overrides().clock(clock.clone()).build();
+    /// assert_eq!(env.clock().now(), clock.now());
+    /// ```
+    #[must_use]
+    pub fn test_environment() -> BaseEnvironment {
+        BaseEnvironment::new(
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemoryEventBus::new()),
+        )
+        .with_overrides()
+        .clock(test_clock())
+        .build()
+    }
+
     /// Type alias for snapshot storage: maps `stream_id` to `(version, state_bytes)`
     type SnapshotMap =
         std::collections::HashMap<String, (composable_rust_core::stream::Version, Vec<u8>)>;
 
+    /// Outbox row: `(stream_id, version, topic, published)`, indexed by sequence
+    type OutboxRow = (
+        composable_rust_core::stream::StreamId,
+        composable_rust_core::stream::Version,
+        String,
+        bool,
+    );
+
+    /// Events of one stream in version order, behind the stream's own lock
+    type StreamEvents = Arc<RwLock<Vec<composable_rust_core::event::SerializedEvent>>>;
+
+    /// Event storage: maps `stream_id` to its events
+    type EventMap = std::collections::HashMap<String, StreamEvents>;
+
+    /// Access to the stream map held by an append
+    enum MapGuard<'a> {
+        /// Appends to other streams proceed concurrently
+        Shared(#[allow(dead_code)] std::sync::RwLockReadGuard<'a, EventMap>), // Held for its lock
+        /// Needed to evict streams
+        Exclusive(std::sync::RwLockWriteGuard<'a, EventMap>),
+    }
+
+    impl MapGuard<'_> {
+        /// The map, if it is held exclusively
+        fn exclusive(&mut self) -> Option<&mut EventMap> {
+            match self {
+                Self::Shared(_) => None,
+                Self::Exclusive(map) => Some(map),
+            }
+        }
+    }
+
+    /// Error for a poisoned `InMemoryEventStore` lock
+    fn lock_poisoned(error: impl std::fmt::Display) -> composable_rust_core::event_store::EventStoreError {
+        composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
+            "Lock poisoned: {error}"
+        ))
+    }
+
+    /// What `InMemoryEventStore` does when an append would exceed
+    /// [`with_max_bytes`](InMemoryEventStore::with_max_bytes)
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum CapacityPolicy {
+        /// Fail the append with a `DatabaseError`, like a full disk
+        #[default]
+        Reject,
+        /// Delete whole streams, least recently written first, until the
+        /// append fits
+        ///
+        /// Streams are evicted entirely so the versions of the remaining
+        /// streams stay valid. The stream being appended to is never evicted.
+        EvictLeastRecentlyWritten,
+    }
+
+    /// Versions returned by keyed appends: maps `stream_id` to key to version
+    type IdempotencyKeys = std::collections::HashMap<
+        String,
+        std::collections::HashMap<String, composable_rust_core::stream::Version>,
+    >;
+
+    /// Storage limits of an `InMemoryEventStore`
+    #[derive(Debug, Clone, Copy, Default)]
+    struct Capacity {
+        /// Most events a single stream may hold
+        max_events_per_stream: Option<usize>,
+        /// Most bytes all streams together may hold
+        max_bytes: Option<usize>,
+        /// What to do when `max_bytes` would be exceeded
+        policy: CapacityPolicy,
+    }
+
+    /// Running storage totals of an `InMemoryEventStore`
+    #[derive(Debug, Default)]
+    struct Usage {
+        /// Bytes held by all streams
+        bytes: usize,
+        /// Bytes held and last write tick, by stream ID
+        streams: std::collections::HashMap<String, (usize, u64)>,
+        /// Appends accounted so far; orders streams by recency
+        writes: u64,
+        /// Streams evicted to make room
+        evicted: u64,
+    }
+
+    impl Capacity {
+        /// Whether appends may evict other streams
+        fn evicts(&self) -> bool {
+            self.max_bytes.is_some() && self.policy == CapacityPolicy::EvictLeastRecentlyWritten
+        }
+    }
+
+    impl Usage {
+        /// Account `bytes` appended to `stream_id`
+        fn record(&mut self, stream_id: &str, bytes: usize) {
+            self.writes += 1;
+            self.bytes += bytes;
+            let entry = self.streams.entry(stream_id.to_string()).or_default();
+            entry.0 += bytes;
+            entry.1 = self.writes;
+        }
+
+        /// Stop accounting a removed stream
+        fn forget(&mut self, stream_id: &str) {
+            if let Some((bytes, _)) = self.streams.remove(stream_id) {
+                self.bytes -= bytes;
+            }
+        }
+
+        /// The least recently written stream other than `except`
+        fn least_recent(&self, except: &str) -> Option<String> {
+            self.streams
+                .iter()
+                .filter(|(id, _)| id.as_str() != except)
+                .min_by_key(|(_, (_, written))| *written)
+                .map(|(id, _)| id.clone())
+        }
+    }
+
+    /// Approximate bytes an event occupies: type name, payload and metadata
+    fn event_size(event: &composable_rust_core::event::SerializedEvent) -> usize {
+        let metadata = event
+            .metadata
+            .as_ref()
+            .and_then(|metadata| serde_json::to_vec(metadata).ok())
+            .map_or(0, |json| json.len());
+        event.event_type.len() + event.data.len() + metadata
+    }
+
+    /// Artificial contention applied to `InMemoryEventStore::append_events`
+    #[derive(Debug, Clone, Default)]
+    struct Contention {
+        /// Delay before each append takes the lock
+        latency: Option<std::time::Duration>,
+        /// Stream ID to the (1-based) append that is rejected with a conflict
+        forced_conflicts: std::collections::HashMap<String, u64>,
+        /// Whether concurrent appends are reordered with seeded yields
+        shuffle: bool,
+    }
+
+    /// Counters driving `Contention`, shared by clones of a store
+    #[derive(Debug, Default)]
+    struct ContentionState {
+        /// Appends attempted so far, by stream ID
+        appends: std::collections::HashMap<String, u64>,
+        /// `SplitMix64` state for shuffling
+        rng: u64,
+    }
+
+    impl ContentionState {
+        const fn next_random(&mut self) -> u64 {
+            self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = self.rng;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+    }
+
     /// In-memory event store for fast, deterministic unit tests.
     ///
     /// This implementation uses `HashMap` for storage and provides the same
     /// optimistic concurrency semantics as `PostgresEventStore`, making it perfect
     /// for testing event-sourced aggregates without requiring a database.
     ///
+    /// # Simulating contention
+    ///
+    /// Optimistic-retry logic can be exercised deterministically by injecting
+    /// contention into `append_events`:
+    ///
+    /// - [`with_append_latency`](Self::with_append_latency) delays every append
+    /// - [`with_conflict_on_append`](Self::with_conflict_on_append) rejects the
+    ///   Nth append to a stream with `ConcurrencyConflict`
+    /// - [`with_shuffled_appends`](Self::with_shuffled_appends) reorders
+    ///   concurrent appends from a seed (reproducible on a current-thread runtime)
+    ///
+    /// Each stream has its own lock, as rows of different streams do in
+    /// Postgres: appends to unrelated streams run in parallel on a
+    /// multi-threaded runtime, while appends to the same stream serialize.
+    /// Batches, deletion and eviction lock the whole store.
+    ///
+    /// # Bounding storage
+    ///
+    /// Long-running simulations can cap how much the store holds:
+    ///
+    /// - [`with_max_events_per_stream`](Self::with_max_events_per_stream)
+    ///   rejects appends that would grow a stream past the limit
+    /// - [`with_max_bytes`](Self::with_max_bytes) caps the total size of all
+    ///   events, handled by the [`CapacityPolicy`]
+    ///
+    /// [`total_events`](Self::total_events) and
+    /// [`memory_usage`](Self::memory_usage) report storage growth for assertions.
+    ///
     /// # Example
     ///
     /// ```
@@ -236,17 +496,29 @@ pub mod mocks {
     /// ```
     #[derive(Debug, Clone, Default)]
     pub struct InMemoryEventStore {
-        /// Events indexed by `stream_id`, stored in version order
-        events: Arc<
-            RwLock<
-                std::collections::HashMap<
-                    String,
-                    Vec<composable_rust_core::event::SerializedEvent>,
-                >,
-            >,
-        >,
+        /// Events indexed by `stream_id`, each stream behind its own lock
+        ///
+        /// Stream locks are only taken while holding this map's lock, shared
+        /// for single-stream operations and exclusively for operations that
+        /// add or remove streams across the store.
+        events: Arc<RwLock<EventMap>>,
         /// Snapshots indexed by `stream_id`
         snapshots: Arc<RwLock<SnapshotMap>>,
+        /// Outbox rows in sequence order (written under the stream's lock)
+        outbox: Arc<RwLock<Vec<OutboxRow>>>,
+        /// When each stream received its first event (written under the stream's lock)
+        created_at: Arc<RwLock<std::collections::HashMap<String, Timestamp>>>,
+        /// Artificial contention settings
+        contention: Arc<Contention>,
+        /// Counters for artificial contention
+        contention_state: Arc<std::sync::Mutex<ContentionState>>,
+        /// Storage limits
+        capacity: Capacity,
+        /// Storage totals (written under the stream's lock)
+        usage: Arc<std::sync::Mutex<Usage>>,
+        /// Versions returned by keyed appends, by stream ID then idempotency key
+        /// (written under the stream's lock)
+        idempotency_keys: Arc<std::sync::Mutex<IdempotencyKeys>>,
     }
 
     impl InMemoryEventStore {
@@ -256,7 +528,325 @@ pub mod mocks {
             Self {
                 events: Arc::new(RwLock::new(std::collections::HashMap::new())),
                 snapshots: Arc::new(RwLock::new(std::collections::HashMap::new())),
+                outbox: Arc::new(RwLock::new(Vec::new())),
+                created_at: Arc::new(RwLock::new(std::collections::HashMap::new())),
+                contention: Arc::new(Contention::default()),
+                contention_state: Arc::new(std::sync::Mutex::new(ContentionState::default())),
+                capacity: Capacity::default(),
+                usage: Arc::new(std::sync::Mutex::new(Usage::default())),
+                idempotency_keys: Arc::new(std::sync::Mutex::new(IdempotencyKeys::new())),
+            }
+        }
+
+        /// Delay every `append_events` call by `latency` before it takes the lock
+        ///
+        /// Widens the window between a caller loading a stream and appending to
+        /// it, so concurrent writers actually collide.
+        #[must_use]
+        pub fn with_append_latency(mut self, latency: std::time::Duration) -> Self {
+            Arc::make_mut(&mut self.contention).latency = Some(latency);
+            self
+        }
+
+        /// Reject the `nth` (1-based) `append_events` call to `stream_id`
+        ///
+        /// The append fails with `ConcurrencyConflict` as if another writer had
+        /// just appended, without modifying the stream. A retry succeeds.
+        #[must_use]
+        pub fn with_conflict_on_append(
+            mut self,
+            stream_id: &composable_rust_core::stream::StreamId,
+            nth: u64,
+        ) -> Self {
+            Arc::make_mut(&mut self.contention)
+                .forced_conflicts
+                .insert(stream_id.as_str().to_string(), nth);
+            self
+        }
+
+        /// Randomize the order of concurrent `append_events` calls from `seed`
+        ///
+        /// Each append yields to the scheduler a seeded number of times before
+        /// taking the lock. On a current-thread runtime the same seed always
+        /// produces the same interleaving.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the contention lock is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn with_shuffled_appends(mut self, seed: u64) -> Self {
+            Arc::make_mut(&mut self.contention).shuffle = true;
+            self.contention_state
+                .lock()
+                .expect("InMemoryEventStore contention lock poisoned")
+                .rng = seed;
+            self
+        }
+
+        /// Reject appends that would grow a stream past `max` events
+        ///
+        /// The append fails with a `DatabaseError` and leaves the stream unchanged.
+        #[must_use]
+        pub const fn with_max_events_per_stream(mut self, max: usize) -> Self {
+            self.capacity.max_events_per_stream = Some(max);
+            self
+        }
+
+        /// Cap the total size of all stored events at `max` bytes
+        ///
+        /// An event's size is its type name, payload and JSON-encoded metadata.
+        /// Appends that would exceed the cap are handled by the
+        /// [`CapacityPolicy`] (rejected by default).
+        #[must_use]
+        pub const fn with_max_bytes(mut self, max: usize) -> Self {
+            self.capacity.max_bytes = Some(max);
+            self
+        }
+
+        /// Choose what happens when an append would exceed
+        /// [`with_max_bytes`](Self::with_max_bytes)
+        #[must_use]
+        pub const fn with_capacity_policy(mut self, policy: CapacityPolicy) -> Self {
+            self.capacity.policy = policy;
+            self
+        }
+
+        /// Lock the map and `stream_id` in it for an append, creating the
+        /// stream if needed
+        ///
+        /// The map is held exclusively only when appends may evict streams.
+        fn lock_for_append(
+            &self,
+            stream_id: &str,
+        ) -> Result<(MapGuard<'_>, StreamEvents), composable_rust_core::event_store::EventStoreError>
+        {
+            if self.capacity.evicts() {
+                let mut map = self.events.write().map_err(lock_poisoned)?;
+                let stream = Arc::clone(map.entry(stream_id.to_string()).or_default());
+                return Ok((MapGuard::Exclusive(map), stream));
+            }
+
+            loop {
+                let map = self.events.read().map_err(lock_poisoned)?;
+                if let Some(stream) = map.get(stream_id) {
+                    let stream = Arc::clone(stream);
+                    return Ok((MapGuard::Shared(map), stream));
+                }
+                drop(map);
+                self.events
+                    .write()
+                    .map_err(lock_poisoned)?
+                    .entry(stream_id.to_string())
+                    .or_default();
+            }
+        }
+
+        /// Run `read` over the events of `stream_id`, `None` if it has no entry
+        fn read_stream<R>(
+            &self,
+            stream_id: &str,
+            read: impl FnOnce(&[composable_rust_core::event::SerializedEvent]) -> R,
+        ) -> Result<Option<R>, composable_rust_core::event_store::EventStoreError> {
+            let map = self.events.read().map_err(lock_poisoned)?;
+            let Some(stream) = map.get(stream_id) else {
+                return Ok(None);
+            };
+            let events = stream.read().map_err(lock_poisoned)?;
+            Ok(Some(read(&events)))
+        }
+
+        /// Check an append of `events` to `stream_id`, which holds `held`
+        /// events, against the capacity limits and account for it
+        ///
+        /// Streams are evicted from `evict_from` when the policy requires it.
+        /// Callers hold the stream's write lock but not the `outbox` lock, and
+        /// append `events` once this succeeds.
+        fn admit(
+            &self,
+            mut evict_from: Option<&mut EventMap>,
+            stream_id: &str,
+            held: usize,
+            events: &[composable_rust_core::event::SerializedEvent],
+        ) -> Result<(), composable_rust_core::event_store::EventStoreError> {
+            let capacity_error = |reason: String| {
+                composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
+                    "Capacity exceeded: {reason}"
+                ))
+            };
+
+            if let Some(max) = self.capacity.max_events_per_stream {
+                if held + events.len() > max {
+                    return Err(capacity_error(format!(
+                        "stream {stream_id} would hold {} events, limit is {max}",
+                        held + events.len()
+                    )));
+                }
+            }
+
+            let mut usage = self.usage.lock().map_err(lock_poisoned)?;
+            let incoming: usize = events.iter().map(event_size).sum();
+            if let Some(max) = self.capacity.max_bytes {
+                while usage.bytes + incoming > max {
+                    let victim = match (self.capacity.policy, evict_from.as_deref_mut()) {
+                        (CapacityPolicy::EvictLeastRecentlyWritten, Some(map)) => usage
+                            .least_recent(stream_id)
+                            .map(|victim| (map, victim)),
+                        _ => None,
+                    };
+                    let Some((map, victim)) = victim else {
+                        return Err(capacity_error(format!(
+                            "store would hold {} bytes, limit is {max}",
+                            usage.bytes + incoming
+                        )));
+                    };
+                    map.remove(&victim);
+                    self.purge(&victim)?;
+                    usage.forget(&victim);
+                    usage.evicted += 1;
+                }
+            }
+            usage.record(stream_id, incoming);
+            Ok(())
+        }
+
+        /// Drop what is kept alongside the events of a removed stream
+        ///
+        /// Callers hold the map's write lock but not the `outbox` lock.
+        fn purge(
+            &self,
+            stream_id: &str,
+        ) -> Result<(), composable_rust_core::event_store::EventStoreError> {
+            // Retire pending outbox rows so a recreated stream does not publish them
+            let mut outbox = self.outbox.write().map_err(lock_poisoned)?;
+            for row in outbox.iter_mut().filter(|row| row.0.as_str() == stream_id) {
+                row.3 = true;
+            }
+            drop(outbox);
+
+            self.created_at.write().map_err(lock_poisoned)?.remove(stream_id);
+            self.snapshots.write().map_err(lock_poisoned)?.remove(stream_id);
+            self.idempotency_keys.lock().map_err(lock_poisoned)?.remove(stream_id);
+            Ok(())
+        }
+
+        /// Apply the configured contention to an append to `stream_id`
+        async fn contend(
+            &self,
+            stream_id: &composable_rust_core::stream::StreamId,
+            expected_version: Option<composable_rust_core::stream::Version>,
+        ) -> Result<(), composable_rust_core::event_store::EventStoreError> {
+            if let Some(latency) = self.contention.latency {
+                tokio::time::sleep(latency).await;
+            }
+
+            let (attempt, yields) = {
+                let mut state = self.contention_state.lock().map_err(|e| {
+                    composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
+                        "Lock poisoned: {e}"
+                    ))
+                })?;
+                let attempt = state.appends.entry(stream_id.as_str().to_string()).or_insert(0);
+                *attempt += 1;
+                let attempt = *attempt;
+                let yields = if self.contention.shuffle { state.next_random() % 8 } else { 0 };
+                (attempt, yields)
+            };
+
+            for _ in 0..yields {
+                tokio::task::yield_now().await;
+            }
+
+            if self.contention.forced_conflicts.get(stream_id.as_str()) == Some(&attempt) {
+                let current = self.current_version(stream_id);
+                return Err(composable_rust_core::event_store::EventStoreError::ConcurrencyConflict {
+                    stream_id: stream_id.clone(),
+                    expected: expected_version.unwrap_or(current),
+                    actual: current + 1,
+                });
+            }
+            Ok(())
+        }
+
+        /// Version returned by the append to `stream_id` that recorded `key`
+        fn committed(
+            &self,
+            stream_id: &composable_rust_core::stream::StreamId,
+            key: Option<&str>,
+        ) -> Result<Option<composable_rust_core::stream::Version>, composable_rust_core::event_store::EventStoreError>
+        {
+            let Some(key) = key else {
+                return Ok(None);
+            };
+            let keys = self.idempotency_keys.lock().map_err(lock_poisoned)?;
+            Ok(keys.get(stream_id.as_str()).and_then(|keys| keys.get(key)).copied())
+        }
+
+        /// Append `events` to `stream_id`, deduplicating by `idempotency_key` if given
+        async fn append(
+            &self,
+            stream_id: composable_rust_core::stream::StreamId,
+            expected_version: Option<composable_rust_core::stream::Version>,
+            mut events: Vec<composable_rust_core::event::SerializedEvent>,
+            idempotency_key: Option<String>,
+        ) -> Result<composable_rust_core::stream::Version, composable_rust_core::event_store::EventStoreError>
+        {
+            if events.is_empty() {
+                return Err(
+                    composable_rust_core::event_store::EventStoreError::DatabaseError(
+                        "Cannot append empty event list".to_string(),
+                    ),
+                );
+            }
+
+            // A repeated key returns the original result, whatever the stream's
+            // version now, and without going through the injected contention:
+            // the retry of a committed write must not see a conflict
+            if let Some(version) = self.committed(&stream_id, idempotency_key.as_deref())? {
+                return Ok(version);
             }
+
+            self.contend(&stream_id, expected_version).await?;
+
+            let (mut map, stream) = self.lock_for_append(stream_id.as_str())?;
+            let mut stream_events = stream.write().map_err(lock_poisoned)?;
+
+            // Checked again under the lock for a concurrent append with the same key
+            if let Some(version) = self.committed(&stream_id, idempotency_key.as_deref())? {
+                return Ok(version);
+            }
+
+            let current_version =
+                composable_rust_core::stream::Version::new(stream_events.len() as u64);
+
+            // Check optimistic concurrency
+            if let Some(expected) = expected_version {
+                if current_version != expected {
+                    return Err(composable_rust_core::event_store::EventStoreError::ConcurrencyConflict {
+                        stream_id,
+                        expected,
+                        actual: current_version,
+                    });
+                }
+            }
+
+            self.admit(map.exclusive(), stream_id.as_str(), stream_events.len(), &events)?;
+
+            // Append events
+            stream_events.append(&mut events);
+            let new_version =
+                composable_rust_core::stream::Version::new(stream_events.len() as u64);
+            self.mark_created(stream_id.as_str())?;
+
+            if let Some(key) = idempotency_key {
+                self.idempotency_keys
+                    .lock()
+                    .map_err(lock_poisoned)?
+                    .entry(stream_id.as_str().to_string())
+                    .or_default()
+                    .insert(key, new_version - 1);
+            }
+            Ok(new_version - 1)
         }
 
         /// Reset the event store to empty state.
@@ -276,6 +866,67 @@ pub mod mocks {
                 .write()
                 .expect("InMemoryEventStore lock poisoned")
                 .clear();
+            self.outbox
+                .write()
+                .expect("InMemoryEventStore lock poisoned")
+                .clear();
+            self.created_at
+                .write()
+                .expect("InMemoryEventStore lock poisoned")
+                .clear();
+            *self.usage.lock().expect("InMemoryEventStore lock poisoned") = Usage::default();
+            self.idempotency_keys
+                .lock()
+                .expect("InMemoryEventStore lock poisoned")
+                .clear();
+        }
+
+        /// Record the creation time of `stream_id` if it has none yet
+        ///
+        /// Callers hold the stream's write lock.
+        fn mark_created(
+            &self,
+            stream_id: &str,
+        ) -> Result<(), composable_rust_core::event_store::EventStoreError> {
+            self.created_at
+                .write()
+                .map_err(|e| {
+                    composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
+                        "Lock poisoned: {e}"
+                    ))
+                })?
+                .entry(stream_id.to_string())
+                .or_insert_with(composable_rust_core::environment::now);
+            Ok(())
+        }
+
+        /// Metadata of a stream, if it has events
+        fn metadata_of(
+            &self,
+            stream_id: &str,
+            events: &[composable_rust_core::event::SerializedEvent],
+        ) -> Result<
+            Option<composable_rust_core::event_store::StreamMetadata>,
+            composable_rust_core::event_store::EventStoreError,
+        > {
+            if events.is_empty() {
+                return Ok(None);
+            }
+            let created_at = self.created_at.read().map_err(|e| {
+                composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
+                    "Lock poisoned: {e}"
+                ))
+            })?;
+            Ok(created_at.get(stream_id).map(|created_at| {
+                composable_rust_core::event_store::StreamMetadata {
+                    stream_id: composable_rust_core::stream::StreamId::new(stream_id),
+                    created_at: *created_at,
+                    event_count: events.len() as u64,
+                    last_version: composable_rust_core::stream::Version::new(
+                        events.len() as u64 - 1,
+                    ),
+                }
+            }))
         }
 
         /// Get the current version for a stream.
@@ -291,16 +942,11 @@ pub mod mocks {
             &self,
             stream_id: &composable_rust_core::stream::StreamId,
         ) -> composable_rust_core::stream::Version {
-            let events = self
-                .events
-                .read()
+            let held = self
+                .read_stream(stream_id.as_str(), <[_]>::len)
                 .expect("InMemoryEventStore lock poisoned");
 
-            events
-                .get(stream_id.as_str())
-                .map_or(composable_rust_core::stream::Version::new(0), |v| {
-                    composable_rust_core::stream::Version::new(v.len() as u64)
-                })
+            composable_rust_core::stream::Version::new(held.unwrap_or(0) as u64)
         }
 
         /// Get the total number of events in a stream.
@@ -313,28 +959,76 @@ pub mod mocks {
         #[must_use]
         #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
         pub fn event_count(&self, stream_id: &composable_rust_core::stream::StreamId) -> usize {
+            self.read_stream(stream_id.as_str(), <[_]>::len)
+                .expect("InMemoryEventStore lock poisoned")
+                .unwrap_or(0)
+        }
+
+        /// Check if a stream exists.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn stream_exists(&self, stream_id: &composable_rust_core::stream::StreamId) -> bool {
             let events = self
                 .events
                 .read()
                 .expect("InMemoryEventStore lock poisoned");
 
-            events.get(stream_id.as_str()).map_or(0, Vec::len)
+            events.contains_key(stream_id.as_str())
         }
 
-        /// Check if a stream exists.
+        /// Get the total number of events across all streams.
         ///
         /// # Panics
         ///
         /// Panics if the `RwLock` is poisoned.
         #[must_use]
         #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
-        pub fn stream_exists(&self, stream_id: &composable_rust_core::stream::StreamId) -> bool {
+        pub fn total_events(&self) -> usize {
             let events = self
                 .events
                 .read()
                 .expect("InMemoryEventStore lock poisoned");
 
-            events.contains_key(stream_id.as_str())
+            events
+                .values()
+                .map(|stream| stream.read().expect("InMemoryEventStore lock poisoned").len())
+                .sum()
+        }
+
+        /// Get the approximate bytes held by all stored events.
+        ///
+        /// Counts what [`with_max_bytes`](Self::with_max_bytes) limits: each
+        /// event's type name, payload and JSON-encoded metadata.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `Mutex` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn memory_usage(&self) -> usize {
+            self.usage
+                .lock()
+                .expect("InMemoryEventStore lock poisoned")
+                .bytes
+        }
+
+        /// Get the number of streams evicted to stay within
+        /// [`with_max_bytes`](Self::with_max_bytes).
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `Mutex` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn evicted_streams(&self) -> u64 {
+            self.usage
+                .lock()
+                .expect("InMemoryEventStore lock poisoned")
+                .evicted
         }
     }
 
@@ -343,7 +1037,7 @@ pub mod mocks {
             &self,
             stream_id: composable_rust_core::stream::StreamId,
             expected_version: Option<composable_rust_core::stream::Version>,
-            mut events: Vec<composable_rust_core::event::SerializedEvent>,
+            events: Vec<composable_rust_core::event::SerializedEvent>,
         ) -> std::pin::Pin<
             Box<
                 dyn std::future::Future<
@@ -355,49 +1049,67 @@ pub mod mocks {
                     + '_,
             >,
         > {
-            Box::pin(async move {
-                if events.is_empty() {
-                    return Err(
-                        composable_rust_core::event_store::EventStoreError::DatabaseError(
-                            "Cannot append empty event list".to_string(),
-                        ),
-                    );
-                }
-
-                let mut store = self.events.write().map_err(|e| {
-                    composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
-                        "Lock poisoned: {e}"
-                    ))
-                })?;
-
-                let stream_events = store.entry(stream_id.as_str().to_string()).or_default();
-                let current_version =
-                    composable_rust_core::stream::Version::new(stream_events.len() as u64);
+            Box::pin(self.append(stream_id, expected_version, events, None))
+        }
 
-                // Check optimistic concurrency
-                if let Some(expected) = expected_version {
-                    if current_version != expected {
-                        return Err(composable_rust_core::event_store::EventStoreError::ConcurrencyConflict {
-                            stream_id,
-                            expected,
-                            actual: current_version,
-                        });
-                    }
-                }
+        fn append_events_idempotent(
+            &self,
+            stream_id: composable_rust_core::stream::StreamId,
+            expected_version: Option<composable_rust_core::stream::Version>,
+            events: Vec<composable_rust_core::event::SerializedEvent>,
+            idempotency_key: String,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<
+                            composable_rust_core::stream::Version,
+                            composable_rust_core::event_store::EventStoreError,
+                        >,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            Box::pin(self.append(stream_id, expected_version, events, Some(idempotency_key)))
+        }
 
-                // Append events
-                stream_events.append(&mut events);
-                let new_version =
-                    composable_rust_core::stream::Version::new(stream_events.len() as u64);
+        fn load_events(
+            &self,
+            stream_id: composable_rust_core::stream::StreamId,
+            from_version: Option<composable_rust_core::stream::Version>,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<
+                            Vec<composable_rust_core::event::SerializedEvent>,
+                            composable_rust_core::event_store::EventStoreError,
+                        >,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            Box::pin(async move {
+                let start_idx = match from_version {
+                    Some(from_ver) => usize::try_from(from_ver.value()).map_err(|e| {
+                        composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
+                            "Version too large for usize: {e}"
+                        ))
+                    })?,
+                    None => 0,
+                };
 
-                Ok(new_version - 1)
+                Ok(self
+                    .read_stream(stream_id.as_str(), |events| {
+                        events.get(start_idx..).unwrap_or(&[]).to_vec()
+                    })?
+                    .unwrap_or_default())
             })
         }
 
-        fn load_events(
+        fn load_events_page(
             &self,
             stream_id: composable_rust_core::stream::StreamId,
-            from_version: Option<composable_rust_core::stream::Version>,
+            from_version: composable_rust_core::stream::Version,
+            limit: usize,
         ) -> std::pin::Pin<
             Box<
                 dyn std::future::Future<
@@ -410,26 +1122,19 @@ pub mod mocks {
             >,
         > {
             Box::pin(async move {
-                let store = self.events.read().map_err(|e| {
+                let start_idx = usize::try_from(from_version.value()).map_err(|e| {
                     composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
-                        "Lock poisoned: {e}"
+                        "Version too large for usize: {e}"
                     ))
                 })?;
-
-                let stream_events = store.get(stream_id.as_str());
-
-                match (stream_events, from_version) {
-                    (Some(events), Some(from_ver)) => {
-                        let start_idx = usize::try_from(from_ver.value()).map_err(|e| {
-                            composable_rust_core::event_store::EventStoreError::DatabaseError(
-                                format!("Version too large for usize: {e}"),
-                            )
-                        })?;
-                        Ok(events.get(start_idx..).unwrap_or(&[]).to_vec())
-                    },
-                    (Some(events), None) => Ok(events.clone()),
-                    (None, _) => Ok(vec![]),
-                }
+                Ok(self
+                    .read_stream(stream_id.as_str(), |events| {
+                        events
+                            .get(start_idx..)
+                            .map(|page| page.iter().take(limit).cloned().collect())
+                            .unwrap_or_default()
+                    })?
+                    .unwrap_or_default())
             })
         }
 
@@ -507,12 +1212,8 @@ pub mod mocks {
                     return Ok(Vec::new());
                 }
 
-                // CRITICAL: Take write lock once for entire batch (atomicity)
-                let mut store = self.events.write().map_err(|e| {
-                    composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
-                        "Lock poisoned: {e}"
-                    ))
-                })?;
+                // CRITICAL: Take the map's write lock once for entire batch (atomicity)
+                let mut store = self.events.write().map_err(lock_poisoned)?;
 
                 let mut results = Vec::with_capacity(batch.len());
 
@@ -531,11 +1232,11 @@ pub mod mocks {
                     }
 
                     // Get current version WITHOUT mutating (use get, not entry)
-                    let current_version = store
-                        .get(operation.stream_id.as_str())
-                        .map_or(composable_rust_core::stream::Version::new(0), |stream_events| {
-                            composable_rust_core::stream::Version::new(stream_events.len() as u64)
-                        });
+                    let held = match store.get(operation.stream_id.as_str()) {
+                        Some(stream) => stream.read().map_err(lock_poisoned)?.len(),
+                        None => 0,
+                    };
+                    let current_version = composable_rust_core::stream::Version::new(held as u64);
 
                     // Check optimistic concurrency
                     if let Some(expected) = operation.expected_version {
@@ -563,20 +1264,323 @@ pub mod mocks {
                     results.push(Ok(new_version));
                 }
 
-                // Phase 2: Apply all validated changes atomically
-                for (stream_id, mut events, _new_version) in
-                    validated_operations.into_iter().flatten()
-                {
-                    let stream_events = store
-                        .entry(stream_id.as_str().to_string())
-                        .or_default();
+                // Phase 2: Apply all validated changes atomically; an operation
+                // over capacity fails alone
+                for (index, operation) in validated_operations.into_iter().enumerate() {
+                    let Some((stream_id, mut events, _new_version)) = operation else {
+                        continue;
+                    };
+                    let stream = Arc::clone(store.entry(stream_id.as_str().to_string()).or_default());
+                    let mut stream_events = stream.write().map_err(lock_poisoned)?;
+                    let evict_from = if self.capacity.evicts() { Some(&mut *store) } else { None };
+                    if let Err(error) =
+                        self.admit(evict_from, stream_id.as_str(), stream_events.len(), &events)
+                    {
+                        results[index] = Err(error);
+                        continue;
+                    }
                     stream_events.append(&mut events);
+                    self.mark_created(stream_id.as_str())?;
                 }
 
                 // Lock released here automatically when store is dropped
                 Ok(results)
             })
         }
+
+        fn delete_stream(
+            &self,
+            stream_id: composable_rust_core::stream::StreamId,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<(), composable_rust_core::event_store::EventStoreError>,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            Box::pin(async move {
+                // Exclusive, so no append to the stream is in flight
+                let mut store = self.events.write().map_err(lock_poisoned)?;
+                let removed = match store.remove(stream_id.as_str()) {
+                    Some(stream) => stream.read().map_err(lock_poisoned)?.len(),
+                    None => 0,
+                };
+                if removed == 0 {
+                    return Err(composable_rust_core::event_store::EventStoreError::StreamNotFound(
+                        stream_id,
+                    ));
+                }
+
+                self.purge(stream_id.as_str())?;
+                self.usage.lock().map_err(lock_poisoned)?.forget(stream_id.as_str());
+                Ok(())
+            })
+        }
+
+        fn stream_metadata(
+            &self,
+            stream_id: composable_rust_core::stream::StreamId,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<
+                            Option<composable_rust_core::event_store::StreamMetadata>,
+                            composable_rust_core::event_store::EventStoreError,
+                        >,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            Box::pin(async move {
+                self.read_stream(stream_id.as_str(), |events| {
+                    self.metadata_of(stream_id.as_str(), events)
+                })?
+                .unwrap_or(Ok(None))
+            })
+        }
+
+        fn list_streams(
+            &self,
+            prefix: Option<String>,
+            page: composable_rust_core::event_store::Pagination,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<
+                            Vec<composable_rust_core::event_store::StreamMetadata>,
+                            composable_rust_core::event_store::EventStoreError,
+                        >,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            Box::pin(async move {
+                let store = self.events.read().map_err(lock_poisoned)?;
+
+                // Failed appends can leave empty entries behind; they are not streams
+                let mut matching: Vec<(&String, std::sync::RwLockReadGuard<'_, _>)> = Vec::new();
+                for (id, stream) in store.iter() {
+                    if prefix.as_deref().is_none_or(|prefix| id.starts_with(prefix)) {
+                        let events = stream.read().map_err(lock_poisoned)?;
+                        if !events.is_empty() {
+                            matching.push((id, events));
+                        }
+                    }
+                }
+                matching.sort_by_key(|(id, _)| *id);
+
+                let mut streams = Vec::new();
+                for (stream_id, events) in matching.into_iter().skip(page.offset).take(page.limit) {
+                    if let Some(metadata) = self.metadata_of(stream_id, &events)? {
+                        streams.push(metadata);
+                    }
+                }
+                Ok(streams)
+            })
+        }
+    }
+
+    impl composable_rust_core::outbox::OutboxStore for InMemoryEventStore {
+        fn append_with_outbox(
+            &self,
+            stream_id: composable_rust_core::stream::StreamId,
+            expected_version: Option<composable_rust_core::stream::Version>,
+            mut events: Vec<composable_rust_core::event::SerializedEvent>,
+            topic: String,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<
+                            composable_rust_core::stream::Version,
+                            composable_rust_core::event_store::EventStoreError,
+                        >,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            Box::pin(async move {
+                if events.is_empty() {
+                    return Err(
+                        composable_rust_core::event_store::EventStoreError::DatabaseError(
+                            "Cannot append empty event list".to_string(),
+                        ),
+                    );
+                }
+
+                // Hold the stream's lock while writing the outbox so both are
+                // observed together
+                let (mut map, stream) = self.lock_for_append(stream_id.as_str())?;
+                let mut stream_events = stream.write().map_err(lock_poisoned)?;
+                let current_version =
+                    composable_rust_core::stream::Version::new(stream_events.len() as u64);
+
+                if let Some(expected) = expected_version {
+                    if current_version != expected {
+                        return Err(composable_rust_core::event_store::EventStoreError::ConcurrencyConflict {
+                            stream_id,
+                            expected,
+                            actual: current_version,
+                        });
+                    }
+                }
+
+                // Eviction retires outbox rows, so admit before taking the outbox lock
+                self.admit(map.exclusive(), stream_id.as_str(), stream_events.len(), &events)?;
+                let mut outbox = self.outbox.write().map_err(lock_poisoned)?;
+
+                let first = stream_events.len() as u64;
+                stream_events.append(&mut events);
+                let last = stream_events.len() as u64;
+                self.mark_created(stream_id.as_str())?;
+
+                outbox.extend((first..last).map(|version| {
+                    (
+                        stream_id.clone(),
+                        composable_rust_core::stream::Version::new(version),
+                        topic.clone(),
+                        false,
+                    )
+                }));
+
+                Ok(composable_rust_core::stream::Version::new(last - 1))
+            })
+        }
+
+        fn pending_outbox(
+            &self,
+            limit: usize,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<
+                            Vec<composable_rust_core::outbox::OutboxEntry>,
+                            composable_rust_core::event_store::EventStoreError,
+                        >,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            Box::pin(async move {
+                // Copy the pending rows out first: appends take a stream's lock
+                // before the outbox lock. A row is written after its event, so
+                // the event is there unless the stream was deleted since.
+                let pending: Vec<(usize, OutboxRow)> = self
+                    .outbox
+                    .read()
+                    .map_err(lock_poisoned)?
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, _, _, published))| !published)
+                    .map(|(sequence, row)| (sequence, row.clone()))
+                    .collect();
+
+                let mut entries = Vec::new();
+                for (sequence, (stream_id, version, topic, _)) in pending {
+                    if entries.len() == limit {
+                        break;
+                    }
+                    #[allow(clippy::cast_possible_truncation)] // Versions index in-memory vectors
+                    let event = self
+                        .read_stream(stream_id.as_str(), |events| {
+                            events.get(version.value() as usize).cloned()
+                        })?
+                        .flatten();
+                    if let Some(event) = event {
+                        entries.push(composable_rust_core::outbox::OutboxEntry {
+                            sequence: sequence as u64,
+                            stream_id,
+                            version,
+                            topic,
+                            event,
+                        });
+                    }
+                }
+                Ok(entries)
+            })
+        }
+
+        fn mark_published(
+            &self,
+            sequences: Vec<u64>,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<(), composable_rust_core::event_store::EventStoreError>,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            Box::pin(async move {
+                let mut outbox = self.outbox.write().map_err(|e| {
+                    composable_rust_core::event_store::EventStoreError::DatabaseError(format!(
+                        "Lock poisoned: {e}"
+                    ))
+                })?;
+
+                for sequence in sequences {
+                    #[allow(clippy::cast_possible_truncation)] // Sequences index the outbox vector
+                    if let Some(row) = outbox.get_mut(sequence as usize) {
+                        row.3 = true;
+                    }
+                }
+                Ok(())
+            })
+        }
+    }
+
+    /// Delivery channel for a single `InMemoryEventBus` subscription
+    type BusSender = tokio::sync::mpsc::UnboundedSender<
+        Result<composable_rust_core::event::SerializedEvent, composable_rust_core::event_bus::EventBusError>,
+    >;
+
+    /// Members of one consumer group on one topic
+    #[derive(Debug, Default)]
+    struct ConsumerGroup {
+        /// Live member channels, in join order
+        members: Vec<BusSender>,
+        /// Round-robin cursor into `members`
+        next: usize,
+    }
+
+    impl ConsumerGroup {
+        /// Deliver an event to exactly one live member, pruning closed ones.
+        fn deliver(&mut self, event: &composable_rust_core::event::SerializedEvent) {
+            self.members.retain(|member| !member.is_closed());
+            while !self.members.is_empty() {
+                let index = self.next % self.members.len();
+                self.next = index + 1;
+                if self.members[index].send(Ok(event.clone())).is_ok() {
+                    return;
+                }
+                // Member left between the prune and the send: rebalance and retry
+                self.members.remove(index);
+            }
+        }
+    }
+
+    /// Shared state behind an `InMemoryEventBus`
+    #[derive(Debug, Default)]
+    struct BusState {
+        /// Fan-out subscribers indexed by topic
+        subscribers: std::collections::HashMap<String, Vec<BusSender>>,
+        /// Consumer groups indexed by topic, then group name
+        groups: std::collections::HashMap<
+            String,
+            std::collections::BTreeMap<String, ConsumerGroup>,
+        >,
+        /// Every event published, indexed by topic; the position is the offset
+        history: std::collections::HashMap<
+            String,
+            Vec<composable_rust_core::event::SerializedEvent>,
+        >,
+        /// Options each event in `history` was published with
+        options: std::collections::HashMap<
+            String,
+            Vec<composable_rust_core::event_bus::PublishOptions>,
+        >,
+        /// Whether the simulated broker is reachable
+        disconnected: bool,
     }
 
     /// In-memory event bus for fast, deterministic unit tests.
@@ -589,6 +1593,12 @@ pub mod mocks {
     ///
     /// - **Synchronous delivery**: Events delivered immediately when published
     /// - **Multiple subscribers**: Each topic can have multiple concurrent subscribers
+    /// - **Consumer groups**: [`subscribe_group`](Self::subscribe_group) delivers each
+    ///   event once per group, round-robin across the group's live members
+    /// - **Replay**: Every published event is retained per topic, and
+    ///   [`subscribe_from`](Self::subscribe_from) replays from an offset before going live
+    /// - **Broker failures**: [`disconnect`](Self::disconnect) ends every stream with a
+    ///   transport error and rejects operations until [`reconnect`](Self::reconnect)
     /// - **Test inspection**: Methods to inspect topic and subscriber counts
     /// - **Thread-safe**: Safe to use across async tasks
     ///
@@ -622,20 +1632,8 @@ pub mod mocks {
     /// ```
     #[derive(Debug, Clone, Default)]
     pub struct InMemoryEventBus {
-        /// Subscribers indexed by topic
-        /// Each topic has a list of channels to deliver events to
-        subscribers: Arc<
-            RwLock<
-                std::collections::HashMap<
-                    String,
-                    Vec<
-                        tokio::sync::mpsc::UnboundedSender<
-                            composable_rust_core::event::SerializedEvent,
-                        >,
-                    >,
-                >,
-            >,
-        >,
+        /// Subscribers, consumer groups and retained history
+        state: Arc<RwLock<BusState>>,
     }
 
     impl InMemoryEventBus {
@@ -643,7 +1641,7 @@ pub mod mocks {
         #[must_use]
         pub fn new() -> Self {
             Self {
-                subscribers: Arc::new(RwLock::new(std::collections::HashMap::new())),
+                state: Arc::new(RwLock::new(BusState::default())),
             }
         }
 
@@ -655,15 +1653,17 @@ pub mod mocks {
         #[must_use]
         #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
         pub fn topic_count(&self) -> usize {
-            self.subscribers
+            self.state
                 .read()
                 .expect("InMemoryEventBus lock poisoned")
+                .subscribers
                 .len()
         }
 
         /// Get the number of active subscribers for a topic.
         ///
-        /// Returns 0 if the topic doesn't exist or has no subscribers.
+        /// Consumer group members count individually. Returns 0 if the topic
+        /// doesn't exist or has no subscribers.
         ///
         /// # Panics
         ///
@@ -671,31 +1671,604 @@ pub mod mocks {
         #[must_use]
         #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
         pub fn subscriber_count(&self, topic: &str) -> usize {
-            let subscribers = self
-                .subscribers
+            let state = self.state.read().expect("InMemoryEventBus lock poisoned");
+
+            let fan_out = state.subscribers.get(topic).map_or(0, Vec::len);
+            let grouped = state.groups.get(topic).map_or(0, |groups| {
+                groups.values().map(|group| group.members.len()).sum()
+            });
+            fan_out + grouped
+        }
+
+        /// Get the number of live members of a consumer group on a topic.
+        ///
+        /// Members whose streams have been dropped are not counted.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn group_member_count(&self, group: &str, topic: &str) -> usize {
+            let state = self.state.read().expect("InMemoryEventBus lock poisoned");
+
+            state
+                .groups
+                .get(topic)
+                .and_then(|groups| groups.get(group))
+                .map_or(0, |group| {
+                    group.members.iter().filter(|member| !member.is_closed()).count()
+                })
+        }
+
+        /// Get the offset the next event published to `topic` will receive.
+        ///
+        /// This is also the number of events retained for the topic, so
+        /// `subscribe_from(&[topic], bus.end_offset(topic))` only sees new events.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn end_offset(&self, topic: &str) -> u64 {
+            let state = self.state.read().expect("InMemoryEventBus lock poisoned");
+
+            state.history.get(topic).map_or(0, |events| events.len() as u64)
+        }
+
+        /// Get every event published to a topic, in offset order.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn history(&self, topic: &str) -> Vec<composable_rust_core::event::SerializedEvent> {
+            let state = self.state.read().expect("InMemoryEventBus lock poisoned");
+
+            state.history.get(topic).cloned().unwrap_or_default()
+        }
+
+        /// Get the partition key of every event published to a topic, in offset order.
+        ///
+        /// Events published without a key (including through plain `publish`)
+        /// yield `None`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[must_use]
+        pub fn published_keys(&self, topic: &str) -> Vec<Option<String>> {
+            self.published_options(topic)
+                .into_iter()
+                .map(|options| options.partition_key)
+                .collect()
+        }
+
+        /// Get the options every event published to a topic carried, in offset order.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn published_options(
+            &self,
+            topic: &str,
+        ) -> Vec<composable_rust_core::event_bus::PublishOptions> {
+            let state = self.state.read().expect("InMemoryEventBus lock poisoned");
+
+            state.options.get(topic).cloned().unwrap_or_default()
+        }
+
+        /// Simulate losing the connection to the broker.
+        ///
+        /// Every open stream yields [`EventBusError::TransportError`] and then
+        /// ends, and subsequent `publish` and `subscribe` calls fail until
+        /// [`reconnect`](Self::reconnect) is called. Retained history is kept,
+        /// so consumers can resume with [`subscribe_from`](Self::subscribe_from).
+        ///
+        /// [`EventBusError::TransportError`]: composable_rust_core::event_bus::EventBusError::TransportError
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn disconnect(&self) {
+            let mut state = self.state.write().expect("InMemoryEventBus lock poisoned");
+            state.disconnected = true;
+
+            let fan_out = std::mem::take(&mut state.subscribers)
+                .into_values()
+                .flatten();
+            let grouped = std::mem::take(&mut state.groups)
+                .into_values()
+                .flat_map(std::collections::BTreeMap::into_values)
+                .flat_map(|group| group.members);
+            for sender in fan_out.chain(grouped) {
+                // A stream subscribed to several topics is notified once per topic;
+                // it ends after the first error, so the rest are never observed
+                let _ = sender.send(Err(
+                    composable_rust_core::event_bus::EventBusError::TransportError(
+                        "broker disconnected".to_string(),
+                    ),
+                ));
+            }
+        }
+
+        /// Restore the connection after [`disconnect`](Self::disconnect).
+        ///
+        /// Streams closed by the disconnect stay closed; consumers must subscribe again.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn reconnect(&self) {
+            self.state
+                .write()
+                .expect("InMemoryEventBus lock poisoned")
+                .disconnected = false;
+        }
+
+        /// Whether the simulated broker is currently reachable.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn is_connected(&self) -> bool {
+            !self
+                .state
+                .read()
+                .expect("InMemoryEventBus lock poisoned")
+                .disconnected
+        }
+
+        /// Reset the event bus by closing all subscriptions.
+        ///
+        /// Also drops consumer groups and retained history and reconnects the
+        /// broker. Useful for test isolation when reusing an event bus instance.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn reset(&self) {
+            *self.state.write().expect("InMemoryEventBus lock poisoned") = BusState::default();
+        }
+
+        /// Subscribe to topics, replaying retained events from `offset` first.
+        ///
+        /// For each topic, events at positions `offset..` are delivered before any
+        /// live event, topic by topic in the order given. An offset past the end of
+        /// a topic replays nothing for it. Registration and replay happen atomically
+        /// with respect to `publish`, so no event is missed or duplicated.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`EventBusError::SubscriptionFailed`] if the broker is
+        /// disconnected or the lock is poisoned.
+        ///
+        /// [`EventBusError::SubscriptionFailed`]: composable_rust_core::event_bus::EventBusError::SubscriptionFailed
+        #[allow(clippy::unused_async)] // Async to match `EventBus::subscribe`
+        pub async fn subscribe_from(
+            &self,
+            topics: &[&str],
+            offset: u64,
+        ) -> Result<
+            composable_rust_core::event_bus::EventStream,
+            composable_rust_core::event_bus::EventBusError,
+        > {
+            let topics: Vec<String> = topics.iter().map(|s| (*s).to_string()).collect();
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+            {
+                let mut state = self.lock_for_subscribe(&topics)?;
+
+                for topic in &topics {
+                    let retained = state.history.get(topic).map_or(&[][..], Vec::as_slice);
+                    let start = usize::try_from(offset).unwrap_or(usize::MAX);
+                    for event in retained.iter().skip(start) {
+                        let _ = tx.send(Ok(event.clone()));
+                    }
+                }
+
+                for topic in &topics {
+                    state
+                        .subscribers
+                        .entry(topic.clone())
+                        .or_default()
+                        .push(tx.clone());
+                }
+            }
+
+            Ok(Self::into_stream(rx))
+        }
+
+        /// Join a consumer group for the given topics.
+        ///
+        /// Each event published to a topic is delivered to exactly one live member
+        /// of every group subscribed to it, rotating through members in join order.
+        /// Dropping a member's stream removes it from the rotation, so the remaining
+        /// members absorb its share (events already buffered for it are lost, as
+        /// with an uncommitted rebalance). Groups only see events published after
+        /// they join; use [`subscribe_from`](Self::subscribe_from) to replay.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`EventBusError::SubscriptionFailed`] if the broker is
+        /// disconnected or the lock is poisoned.
+        ///
+        /// [`EventBusError::SubscriptionFailed`]: composable_rust_core::event_bus::EventBusError::SubscriptionFailed
+        #[allow(clippy::unused_async)] // Async to match `EventBus::subscribe`
+        pub async fn subscribe_group(
+            &self,
+            group: &str,
+            topics: &[&str],
+        ) -> Result<
+            composable_rust_core::event_bus::EventStream,
+            composable_rust_core::event_bus::EventBusError,
+        > {
+            let topics: Vec<String> = topics.iter().map(|s| (*s).to_string()).collect();
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+            {
+                let mut state = self.lock_for_subscribe(&topics)?;
+
+                for topic in &topics {
+                    state
+                        .groups
+                        .entry(topic.clone())
+                        .or_default()
+                        .entry(group.to_string())
+                        .or_default()
+                        .members
+                        .push(tx.clone());
+                }
+            }
+
+            Ok(Self::into_stream(rx))
+        }
+
+        /// Take the write lock for a new subscription, failing while disconnected.
+        fn lock_for_subscribe(
+            &self,
+            topics: &[String],
+        ) -> Result<
+            std::sync::RwLockWriteGuard<'_, BusState>,
+            composable_rust_core::event_bus::EventBusError,
+        > {
+            let state = self.state.write().map_err(|e| {
+                composable_rust_core::event_bus::EventBusError::SubscriptionFailed {
+                    topics: topics.to_vec(),
+                    reason: format!("Lock poisoned: {e}"),
+                }
+            })?;
+
+            if state.disconnected {
+                return Err(
+                    composable_rust_core::event_bus::EventBusError::SubscriptionFailed {
+                        topics: topics.to_vec(),
+                        reason: "broker disconnected".to_string(),
+                    },
+                );
+            }
+
+            Ok(state)
+        }
+
+        /// Turn a subscription channel into a stream that ends after the first error.
+        fn into_stream(
+            mut rx: tokio::sync::mpsc::UnboundedReceiver<
+                Result<
+                    composable_rust_core::event::SerializedEvent,
+                    composable_rust_core::event_bus::EventBusError,
+                >,
+            >,
+        ) -> composable_rust_core::event_bus::EventStream {
+            let stream = async_stream::stream! {
+                while let Some(item) = rx.recv().await {
+                    let failed = item.is_err();
+                    yield item;
+                    if failed {
+                        break;
+                    }
+                }
+            };
+
+            Box::pin(stream)
+        }
+    }
+
+    impl composable_rust_core::event_bus::EventBus for InMemoryEventBus {
+        fn publish(
+            &self,
+            topic: &str,
+            event: &composable_rust_core::event::SerializedEvent,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<(), composable_rust_core::event_bus::EventBusError>,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            self.publish_with_options(
+                topic,
+                event,
+                &composable_rust_core::event_bus::PublishOptions::default(),
+            )
+        }
+
+        fn publish_with_options(
+            &self,
+            topic: &str,
+            event: &composable_rust_core::event::SerializedEvent,
+            options: &composable_rust_core::event_bus::PublishOptions,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<(), composable_rust_core::event_bus::EventBusError>,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            // Clone data before moving into async block
+            let topic = topic.to_string();
+            let event = event.clone();
+            let options = options.clone();
+
+            Box::pin(async move {
+                let mut state = self.state.write().map_err(|e| {
+                    composable_rust_core::event_bus::EventBusError::PublishFailed {
+                        topic: topic.clone(),
+                        reason: format!("Lock poisoned: {e}"),
+                    }
+                })?;
+
+                if state.disconnected {
+                    return Err(composable_rust_core::event_bus::EventBusError::PublishFailed {
+                        topic,
+                        reason: "broker disconnected".to_string(),
+                    });
+                }
+
+                if let Some(topic_subscribers) = state.subscribers.get(&topic) {
+                    // Send to all subscribers (at-least-once semantics)
+                    for sender in topic_subscribers {
+                        // Ignore send errors - subscriber might have dropped
+                        // This mirrors real event bus behavior where subscribers can disconnect
+                        let _ = sender.send(Ok(event.clone()));
+                    }
+                }
+
+                if let Some(groups) = state.groups.get_mut(&topic) {
+                    for group in groups.values_mut() {
+                        group.deliver(&event);
+                    }
+                }
+
+                state
+                    .options
+                    .entry(topic.clone())
+                    .or_default()
+                    .push(options);
+                state.history.entry(topic).or_default().push(event);
+
+                Ok(())
+            })
+        }
+
+        fn subscribe(
+            &self,
+            topics: &[&str],
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<
+                            composable_rust_core::event_bus::EventStream,
+                            composable_rust_core::event_bus::EventBusError,
+                        >,
+                    > + Send
+                    + '_,
+            >,
+        > {
+            // Clone topics before moving into async block
+            let topics: Vec<String> = topics.iter().map(|s| (*s).to_string()).collect();
+
+            Box::pin(async move {
+                // Create a channel for this subscription
+                let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+                // Register this subscriber for all requested topics
+                {
+                    let mut state = self.lock_for_subscribe(&topics)?;
+
+                    for topic in &topics {
+                        state
+                            .subscribers
+                            .entry(topic.clone())
+                            .or_default()
+                            .push(tx.clone());
+                    }
+                }
+
+                Ok(Self::into_stream(rx))
+            })
+        }
+    }
+
+    /// A publish observed by [`MockEventPublisher`]
+    #[derive(Debug, Clone)]
+    pub struct PublishedEvent {
+        /// Topic the event was published to
+        pub topic: String,
+        /// The published event
+        pub event: composable_rust_core::event::SerializedEvent,
+        /// Partition key, headers and delivery timeout of the publish
+        pub options: composable_rust_core::event_bus::PublishOptions,
+        /// When the publish completed, according to the publisher's clock
+        pub published_at: DateTime<Utc>,
+    }
+
+    /// Event bus that records every publish for assertions.
+    ///
+    /// Wraps another [`EventBus`] (an [`InMemoryEventBus`] by default) and
+    /// records each successful publish with its topic and a timestamp, so sagas
+    /// and aggregates can assert on their outbound events. Subscriptions are
+    /// delegated to the inner bus unchanged. Clones share the same record.
+    ///
+    /// [`EventBus`]: composable_rust_core::event_bus::EventBus
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use composable_rust_testing::mocks::{test_clock, MockEventPublisher};
+    /// use composable_rust_core::event_bus::EventBus;
+    /// use composable_rust_core::event::SerializedEvent;
+    /// use std::sync::Arc;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let publisher = MockEventPublisher::new().with_clock(Arc::new(test_clock()));
+    ///
+    /// let event = SerializedEvent::new("OrderPlaced".to_string(), vec![], None);
+    /// publisher.publish("order-events", &event).await?;
+    ///
+    /// assert_eq!(publisher.published_to("order-events").len(), 1);
+    /// publisher.assert_published_matching("order-events", |p| {
+    ///     p.event.event_type == "OrderPlaced"
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Clone)]
+    pub struct MockEventPublisher {
+        /// Bus that actually delivers events
+        inner: Arc<dyn composable_rust_core::event_bus::EventBus>,
+        /// Source of `published_at` timestamps
+        clock: Arc<dyn Clock>,
+        /// Successful publishes, in completion order
+        published: Arc<RwLock<Vec<PublishedEvent>>>,
+    }
+
+    impl std::fmt::Debug for MockEventPublisher {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("MockEventPublisher")
+                .field("published", &self.published)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl Default for MockEventPublisher {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl MockEventPublisher {
+        /// Create a publisher backed by a fresh [`InMemoryEventBus`] and the system clock.
+        #[must_use]
+        pub fn new() -> Self {
+            Self::wrapping(Arc::new(InMemoryEventBus::new()))
+        }
+
+        /// Create a publisher that records publishes before delegating to `inner`.
+        #[must_use]
+        pub fn wrapping(inner: Arc<dyn composable_rust_core::event_bus::EventBus>) -> Self {
+            Self {
+                inner,
+                clock: Arc::new(composable_rust_core::environment::SystemClock),
+                published: Arc::new(RwLock::new(Vec::new())),
+            }
+        }
+
+        /// Use `clock` to timestamp publishes, e.g. a [`FixedClock`] for deterministic tests.
+        #[must_use]
+        pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+            self.clock = clock;
+            self
+        }
+
+        /// Get every recorded publish, in the order the publishes completed.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn published(&self) -> Vec<PublishedEvent> {
+            self.published
+                .read()
+                .expect("MockEventPublisher lock poisoned")
+                .clone()
+        }
+
+        /// Get the events published to `topic`, in order.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the `RwLock` is poisoned.
+        #[must_use]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        pub fn published_to(&self, topic: &str) -> Vec<composable_rust_core::event::SerializedEvent> {
+            self.published
+                .read()
+                .expect("MockEventPublisher lock poisoned")
+                .iter()
+                .filter(|published| published.topic == topic)
+                .map(|published| published.event.clone())
+                .collect()
+        }
+
+        /// Assert that at least one event published to `topic` satisfies `predicate`.
+        ///
+        /// Returns the first matching publish so callers can make further assertions.
+        ///
+        /// # Panics
+        ///
+        /// Panics if no publish to `topic` matches, listing the event types that
+        /// were published there, or if the `RwLock` is poisoned.
+        #[track_caller]
+        #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
+        #[allow(clippy::panic)] // Intentional panic for test assertions
+        pub fn assert_published_matching<F>(&self, topic: &str, predicate: F) -> PublishedEvent
+        where
+            F: Fn(&PublishedEvent) -> bool,
+        {
+            let published = self
+                .published
                 .read()
-                .expect("InMemoryEventBus lock poisoned");
+                .expect("MockEventPublisher lock poisoned");
+            let on_topic = || published.iter().filter(|p| p.topic == topic);
+
+            if let Some(found) = on_topic().find(|p| predicate(p)) {
+                return found.clone();
+            }
 
-            subscribers.get(topic).map_or(0, Vec::len)
+            let event_types: Vec<&str> = on_topic().map(|p| p.event.event_type.as_str()).collect();
+            panic!(
+                "no event published to '{topic}' matched the predicate; published event types: {event_types:?}"
+            );
         }
 
-        /// Reset the event bus by closing all subscriptions.
-        ///
-        /// Useful for test isolation when reusing an event bus instance.
+        /// Forget all recorded publishes.
         ///
         /// # Panics
         ///
         /// Panics if the `RwLock` is poisoned.
         #[allow(clippy::expect_used)] // Test infrastructure, lock poison is unrecoverable
-        pub fn reset(&self) {
-            self.subscribers
+        pub fn clear(&self) {
+            self.published
                 .write()
-                .expect("InMemoryEventBus lock poisoned")
+                .expect("MockEventPublisher lock poisoned")
                 .clear();
         }
     }
 
-    impl composable_rust_core::event_bus::EventBus for InMemoryEventBus {
+    impl composable_rust_core::event_bus::EventBus for MockEventPublisher {
         fn publish(
             &self,
             topic: &str,
@@ -708,26 +2281,48 @@ pub mod mocks {
                     + '_,
             >,
         > {
-            // Clone data before moving into async block
+            self.publish_with_options(
+                topic,
+                event,
+                &composable_rust_core::event_bus::PublishOptions::default(),
+            )
+        }
+
+        fn publish_with_options(
+            &self,
+            topic: &str,
+            event: &composable_rust_core::event::SerializedEvent,
+            options: &composable_rust_core::event_bus::PublishOptions,
+        ) -> std::pin::Pin<
+            Box<
+                dyn std::future::Future<
+                        Output = Result<(), composable_rust_core::event_bus::EventBusError>,
+                    > + Send
+                    + '_,
+            >,
+        > {
             let topic = topic.to_string();
             let event = event.clone();
+            let options = options.clone();
 
             Box::pin(async move {
-                let subscribers_lock = self.subscribers.read().map_err(|e| {
-                    composable_rust_core::event_bus::EventBusError::PublishFailed {
-                        topic: topic.clone(),
+                self.inner
+                    .publish_with_options(&topic, &event, &options)
+                    .await?;
+
+                let record = PublishedEvent {
+                    topic: topic.clone(),
+                    event,
+                    options,
+                    published_at: self.clock.now(),
+                };
+                self.published
+                    .write()
+                    .map_err(|e| composable_rust_core::event_bus::EventBusError::PublishFailed {
+                        topic,
                         reason: format!("Lock poisoned: {e}"),
-                    }
-                })?;
-
-                if let Some(topic_subscribers) = subscribers_lock.get(&topic) {
-                    // Send to all subscribers (at-least-once semantics)
-                    for sender in topic_subscribers {
-                        // Ignore send errors - subscriber might have dropped
-                        // This mirrors real event bus behavior where subscribers can disconnect
-                        let _ = sender.send(event.clone());
-                    }
-                }
+                    })?
+                    .push(record);
 
                 Ok(())
             })
@@ -747,51 +2342,7 @@ pub mod mocks {
                     + '_,
             >,
         > {
-            // Clone topics before moving into async block
-            let topics: Vec<String> = topics.iter().map(|s| (*s).to_string()).collect();
-
-            Box::pin(async move {
-                // Create a channel for this subscription
-                let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<
-                    composable_rust_core::event::SerializedEvent,
-                >();
-
-                // Register this subscriber for all requested topics
-                {
-                    let mut subscribers = self.subscribers.write().map_err(|e| {
-                        composable_rust_core::event_bus::EventBusError::SubscriptionFailed {
-                            topics: topics.clone(),
-                            reason: format!("Lock poisoned: {e}"),
-                        }
-                    })?;
-
-                    for topic in &topics {
-                        subscribers
-                            .entry(topic.clone())
-                            .or_default()
-                            .push(tx.clone());
-                    }
-                }
-
-                // Create a stream from the receiver
-                let stream = async_stream::stream! {
-                    while let Some(event) = rx.recv().await {
-                        yield Ok(event);
-                    }
-                };
-
-                Ok(Box::pin(stream)
-                    as std::pin::Pin<
-                        Box<
-                            dyn futures::Stream<
-                                    Item = Result<
-                                        composable_rust_core::event::SerializedEvent,
-                                        composable_rust_core::event_bus::EventBusError,
-                                    >,
-                                > + Send,
-                        >,
-                    >)
-            })
+            self.inner.subscribe(topics)
         }
     }
 }
@@ -1224,13 +2775,33 @@ pub mod test_store {
 }
 
 // Re-export commonly used items
-pub use mocks::{FixedClock, test_clock};
+pub use mocks::{FixedClock, test_clock, test_environment};
 pub use projection_mocks::{
-    InMemoryProjectionCheckpoint, InMemoryProjectionStore, ProjectionTestHarness,
+    InMemoryProjectionCheckpoint, InMemoryProjectionStore, InMemoryReadModelStore,
+    ProjectionTestHarness,
 };
-pub use reducer_test::{assertions, ReducerTest};
+pub use reducer_test::{assertions, ReducerTest, ReducerTester};
+pub use saga::{SagaCommand, SagaTestHarness};
+pub use stress::{LatencySummary, StressError, StressHarness, StressReport};
 pub use test_store::{ExpectedActions, TestStore, TestStoreError};
 
+/// Common imports for tests: the runtime prelude plus test stores and mocks
+///
+/// ```ignore
+/// use composable_rust_testing::prelude::*;
+/// ```
+pub mod prelude {
+    pub use crate::mocks::{
+        test_clock, test_environment, FixedClock, InMemoryEventBus, InMemoryEventStore,
+        MockEventPublisher,
+    };
+    pub use crate::{
+        assertions, ExpectedActions, InMemoryProjectionStore, InMemoryReadModelStore,
+        ReducerTest, ReducerTester, TestStore, TestStoreError,
+    };
+    pub use composable_rust_runtime::prelude::*;
+}
+
 // Placeholder test module
 #[cfg(test)]
 #[allow(clippy::unwrap_used)] // Tests can unwrap
@@ -1274,6 +2845,19 @@ mod tests {
         assert_eq!(after_backwards, start + Duration::hours(1));
     }
 
+    #[test]
+    fn test_fixed_clock_as_time_provider() {
+        use composable_rust_core::environment::TimeProvider;
+
+        let clock = test_clock();
+        let started = clock.instant();
+
+        clock.advance(chrono::Duration::seconds(5));
+
+        assert_eq!(clock.instant() - started, std::time::Duration::from_secs(5));
+        assert_eq!(DateTime::<Utc>::from(clock.system_time()), clock.now());
+    }
+
     #[test]
     fn test_fixed_clock_set() {
         use chrono::DateTime;
@@ -1301,6 +2885,75 @@ mod tests {
         assert_eq!(clock.now(), another_time);
     }
 
+    #[test]
+    fn test_environment_overrides_replace_one_dependency() {
+        use composable_rust_core::base_environment::{
+            BaseEnvironment, HasClock, HasEventBus, HasEventStore,
+        };
+        use std::sync::Arc;
+
+        /// A feature environment composed over the base one
+        struct OrderEnvironment {
+            base: BaseEnvironment,
+        }
+
+        impl AsRef<BaseEnvironment> for OrderEnvironment {
+            fn as_ref(&self) -> &BaseEnvironment {
+                &self.base
+            }
+        }
+
+        let base = test_environment();
+        let later = test_clock();
+        later.advance(chrono::Duration::days(1));
+
+        let env = OrderEnvironment {
+            base: base.with_overrides().clock(later.clone()).build(),
+        };
+
+        assert_eq!(env.clock().now(), later.now());
+        assert_eq!(base.clock().now(), test_clock().now());
+        // Dependencies that were not overridden are shared
+        assert!(Arc::ptr_eq(env.event_store(), base.event_store()));
+        assert!(Arc::ptr_eq(env.event_bus(), base.event_bus()));
+    }
+
+    #[test]
+    fn test_positional_append_events_macro() {
+        use composable_rust_core::append_events;
+        use composable_rust_core::effect::EventStoreOperation;
+        use composable_rust_core::effect_description::EffectDescription;
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::{EventStore, EventStoreError};
+        use composable_rust_core::stream::Version;
+        use std::sync::Arc;
+
+        let store: Arc<dyn EventStore> = Arc::new(mocks::InMemoryEventStore::new());
+        let events = || vec![SerializedEvent::new("OrderPlaced.v1".to_string(), vec![], None)];
+
+        let effect: Effect<TestAction> = append_events!(store, "order-1", Some(Version::new(3)), events());
+        assert_eq!(
+            effect.describe(),
+            EffectDescription::AppendEvents {
+                stream_id: "order-1".to_string(),
+                expected_version: Some(3),
+                event_types: vec!["OrderPlaced.v1".to_string()],
+            }
+        );
+
+        let effect = append_events!(store, "order-1", None, events(),
+            on_error: |_| Some(TestAction::Action2));
+        let Effect::EventStore(EventStoreOperation::AppendEvents { on_success, on_error, .. }) = effect
+        else {
+            panic!("Expected AppendEvents effect");
+        };
+        assert_eq!(on_success(Version::new(1)), None);
+        assert_eq!(
+            on_error(EventStoreError::DatabaseError("down".to_string())),
+            Some(TestAction::Action2)
+        );
+    }
+
     // TestStore tests
     #[derive(Debug, Clone, PartialEq)]
     enum TestAction {
@@ -1661,6 +3314,103 @@ mod tests {
         }
     }
 
+    // ========== InMemoryEventStore Contention Tests ==========
+
+    #[tokio::test]
+    async fn test_inmemory_forced_conflict_on_nth_append() {
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::{EventStore, EventStoreError};
+        use composable_rust_core::stream::{StreamId, Version};
+
+        let stream = StreamId::new("contended");
+        let store = mocks::InMemoryEventStore::new().with_conflict_on_append(&stream, 2);
+        let event = || vec![SerializedEvent::new("Event".to_string(), vec![], None)];
+
+        store.append_events(stream.clone(), Some(Version::new(0)), event()).await.unwrap();
+
+        let conflict = store.append_events(stream.clone(), Some(Version::new(1)), event()).await;
+        assert!(matches!(
+            conflict,
+            Err(EventStoreError::ConcurrencyConflict { expected, actual, .. })
+                if expected == Version::new(1) && actual == Version::new(2)
+        ));
+        assert_eq!(store.event_count(&stream), 1);
+
+        // The retry goes through
+        store.append_events(stream.clone(), Some(Version::new(1)), event()).await.unwrap();
+        assert_eq!(store.event_count(&stream), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_inmemory_shuffled_appends_are_reproducible() {
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::EventStore;
+        use composable_rust_core::stream::{StreamId, Version};
+
+        async fn winner(seed: u64) -> String {
+            let store = mocks::InMemoryEventStore::new()
+                .with_append_latency(std::time::Duration::from_millis(1))
+                .with_shuffled_appends(seed);
+            let stream = StreamId::new("race");
+
+            let appends = (0..4).map(|writer| {
+                let store = store.clone();
+                let stream = stream.clone();
+                async move {
+                    let event = SerializedEvent::new(format!("Writer{writer}"), vec![], None);
+                    store.append_events(stream, Some(Version::new(0)), vec![event]).await
+                }
+            });
+            let results = futures::future::join_all(appends).await;
+
+            // Exactly one writer wins the optimistic race
+            assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+            store.load_events(stream, None).await.unwrap()[0].event_type.clone()
+        }
+
+        for seed in [1, 7, 42] {
+            assert_eq!(winner(seed).await, winner(seed).await);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_inmemory_parallel_appends_keep_streams_consistent() {
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::{EventStore, EventStoreError};
+        use composable_rust_core::stream::{StreamId, Version};
+
+        let store = mocks::InMemoryEventStore::new();
+        let mut writers = Vec::new();
+        for writer in 0..16_u64 {
+            let store = store.clone();
+            writers.push(tokio::spawn(async move {
+                // Writers share streams in pairs and race with optimistic retries
+                let stream = StreamId::new(format!("account-{}", writer % 8));
+                for _ in 0..25 {
+                    loop {
+                        let expected = store.current_version(&stream);
+                        let event = SerializedEvent::new("Deposited".to_string(), vec![], None);
+                        match store.append_events(stream.clone(), Some(expected), vec![event]).await {
+                            Ok(_) => break,
+                            Err(EventStoreError::ConcurrencyConflict { .. }) => {},
+                            Err(error) => panic!("unexpected error: {error}"),
+                        }
+                        tokio::task::yield_now().await;
+                    }
+                }
+            }));
+        }
+        for writer in writers {
+            writer.await.unwrap();
+        }
+
+        assert_eq!(store.total_events(), 400);
+        for stream in 0..8 {
+            let stream = StreamId::new(format!("account-{stream}"));
+            assert_eq!(store.current_version(&stream), Version::new(50));
+        }
+    }
+
     // ========== InMemoryEventStore append_batch Atomicity Tests ==========
 
     #[tokio::test]
@@ -1804,4 +3554,430 @@ mod tests {
             Err(EventStoreError::DatabaseError(_))
         ));
     }
+
+    // ========== InMemoryEventStore Stream Administration Tests ==========
+
+    #[tokio::test]
+    async fn test_inmemory_stream_metadata_and_listing() {
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::{EventStore, Pagination};
+        use composable_rust_core::stream::{StreamId, Version};
+
+        let store = mocks::InMemoryEventStore::new();
+        let event = || SerializedEvent::new("Placed".to_string(), vec![], None);
+        for id in ["order-2", "customer-1", "order-1", "order-3"] {
+            store.append_events(StreamId::new(id), None, vec![event()]).await.unwrap();
+        }
+        store
+            .append_events(StreamId::new("order-1"), None, vec![event(), event()])
+            .await
+            .unwrap();
+
+        let metadata = store.stream_metadata(StreamId::new("order-1")).await.unwrap().unwrap();
+        assert_eq!(metadata.event_count, 3);
+        assert_eq!(metadata.last_version, Version::new(2));
+        assert!(store.stream_metadata(StreamId::new("missing")).await.unwrap().is_none());
+
+        let page = Pagination::first(2);
+        let first = store.list_streams(Some("order-".to_string()), page).await.unwrap();
+        let second = store.list_streams(Some("order-".to_string()), page.next()).await.unwrap();
+        let ids = |streams: &[composable_rust_core::event_store::StreamMetadata]| {
+            streams.iter().map(|s| s.stream_id.as_str().to_string()).collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&first), ["order-1", "order-2"]);
+        assert_eq!(ids(&second), ["order-3"]);
+        assert_eq!(store.list_streams(None, Pagination::first(10)).await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_delete_stream() {
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::{EventStore, EventStoreError};
+        use composable_rust_core::stream::{StreamId, Version};
+
+        let store = mocks::InMemoryEventStore::new();
+        let stream = StreamId::new("customer-42");
+        store
+            .append_events(stream.clone(), None, vec![SerializedEvent::new("Registered".to_string(), vec![], None)])
+            .await
+            .unwrap();
+        store.save_snapshot(stream.clone(), Version::new(0), vec![1]).await.unwrap();
+
+        store.delete_stream(stream.clone()).await.unwrap();
+
+        assert!(store.load_events(stream.clone(), None).await.unwrap().is_empty());
+        assert!(store.load_snapshot(stream.clone()).await.unwrap().is_none());
+        assert!(store.stream_metadata(stream.clone()).await.unwrap().is_none());
+        assert!(matches!(
+            store.delete_stream(stream).await,
+            Err(EventStoreError::StreamNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_idempotent_append_returns_committed_version() {
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::EventStore;
+        use composable_rust_core::stream::{StreamId, Version};
+
+        let store = mocks::InMemoryEventStore::new();
+        let order = StreamId::new("order-1");
+        let placed = || vec![SerializedEvent::new("Placed".to_string(), vec![], None)];
+        let append = |stream: &StreamId, expected: u64, key: &str| {
+            store.append_events_idempotent(
+                stream.clone(),
+                Some(Version::new(expected)),
+                placed(),
+                key.to_string(),
+            )
+        };
+
+        assert_eq!(append(&order, 0, "cmd-1").await.unwrap(), Version::new(0));
+        // The retry's expected version is stale, but the key identifies the write
+        assert_eq!(append(&order, 0, "cmd-1").await.unwrap(), Version::new(0));
+        assert_eq!(store.event_count(&order), 1);
+
+        assert_eq!(append(&order, 1, "cmd-2").await.unwrap(), Version::new(1));
+        // Keys are scoped to their stream
+        assert_eq!(append(&StreamId::new("order-2"), 0, "cmd-1").await.unwrap(), Version::new(0));
+
+        store.delete_stream(order.clone()).await.unwrap();
+        assert_eq!(append(&order, 0, "cmd-1").await.unwrap(), Version::new(0));
+        assert_eq!(store.event_count(&order), 1);
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_idempotent_retry_skips_forced_conflict() {
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::EventStore;
+        use composable_rust_core::stream::{StreamId, Version};
+
+        let order = StreamId::new("order-1");
+        let store = mocks::InMemoryEventStore::new().with_conflict_on_append(&order, 2);
+        let append = || {
+            store.append_events_idempotent(
+                order.clone(),
+                Some(Version::new(0)),
+                vec![SerializedEvent::new("Placed".to_string(), vec![], None)],
+                "cmd-1".to_string(),
+            )
+        };
+
+        assert_eq!(append().await.unwrap(), Version::new(0));
+        // The retry of the committed write would be the conflicting second append
+        assert_eq!(append().await.unwrap(), Version::new(0));
+        assert_eq!(store.event_count(&order), 1);
+    }
+
+    // ========== InMemoryEventStore Capacity Tests ==========
+
+    #[tokio::test]
+    async fn test_inmemory_capacity_rejects_and_accounts_size() {
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::{EventStore, EventStoreError};
+        use composable_rust_core::stream::StreamId;
+
+        // "Placed" (6 bytes) + 4 bytes of payload
+        let event = || SerializedEvent::new("Placed".to_string(), vec![0; 4], None);
+        let store = mocks::InMemoryEventStore::new()
+            .with_max_events_per_stream(2)
+            .with_max_bytes(50);
+
+        store.append_events(StreamId::new("a"), None, vec![event(), event()]).await.unwrap();
+        assert!(matches!(
+            store.append_events(StreamId::new("a"), None, vec![event()]).await,
+            Err(EventStoreError::DatabaseError(_))
+        ));
+        store.append_events(StreamId::new("b"), None, vec![event(), event()]).await.unwrap();
+        assert_eq!(store.memory_usage(), 40);
+
+        assert!(store.append_events(StreamId::new("c"), None, vec![event(), event()]).await.is_err());
+        assert_eq!(store.total_events(), 4);
+        assert_eq!(store.event_count(&StreamId::new("c")), 0);
+
+        store.delete_stream(StreamId::new("a")).await.unwrap();
+        assert_eq!(store.memory_usage(), 20);
+        store.append_events(StreamId::new("c"), None, vec![event(), event()]).await.unwrap();
+        assert_eq!((store.total_events(), store.memory_usage()), (4, 40));
+
+        store.reset();
+        assert_eq!((store.total_events(), store.memory_usage()), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_capacity_evicts_least_recently_written() {
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_store::EventStore;
+        use composable_rust_core::stream::{StreamId, Version};
+
+        let event = || SerializedEvent::new("Placed".to_string(), vec![0; 4], None);
+        let store = mocks::InMemoryEventStore::new()
+            .with_max_bytes(30)
+            .with_capacity_policy(mocks::CapacityPolicy::EvictLeastRecentlyWritten);
+
+        for id in ["a", "b", "a"] {
+            store.append_events(StreamId::new(id), None, vec![event()]).await.unwrap();
+        }
+        store.save_snapshot(StreamId::new("b"), Version::new(0), vec![1]).await.unwrap();
+
+        store.append_events(StreamId::new("c"), None, vec![event()]).await.unwrap();
+
+        assert!(!store.stream_exists(&StreamId::new("b")));
+        assert!(store.load_snapshot(StreamId::new("b")).await.unwrap().is_none());
+        assert_eq!(store.event_count(&StreamId::new("a")), 2);
+        assert_eq!(store.evicted_streams(), 1);
+        assert_eq!(store.memory_usage(), 30);
+
+        // A single stream larger than the cap cannot be made to fit
+        assert!(store.append_events(StreamId::new("c"), None, vec![event(); 3]).await.is_err());
+    }
+
+    // ========== InMemoryEventBus Tests ==========
+
+    fn bus_event(name: &str) -> composable_rust_core::event::SerializedEvent {
+        composable_rust_core::event::SerializedEvent::new(name.to_string(), vec![], None)
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_bus_consumer_group_delivers_once_per_group() {
+        use composable_rust_core::event_bus::EventBus;
+        use futures::StreamExt;
+
+        let bus = mocks::InMemoryEventBus::new();
+        let mut member_a = bus.subscribe_group("billing", &["orders"]).await.unwrap();
+        let mut member_b = bus.subscribe_group("billing", &["orders"]).await.unwrap();
+        let mut other = bus.subscribe_group("shipping", &["orders"]).await.unwrap();
+        assert_eq!(bus.group_member_count("billing", "orders"), 2);
+        assert_eq!(bus.subscriber_count("orders"), 3);
+
+        for name in ["E1", "E2", "E3", "E4"] {
+            bus.publish("orders", &bus_event(name)).await.unwrap();
+        }
+
+        let mut billing = Vec::new();
+        for _ in 0..2 {
+            billing.push(member_a.next().await.unwrap().unwrap().event_type);
+            billing.push(member_b.next().await.unwrap().unwrap().event_type);
+        }
+        billing.sort();
+        assert_eq!(billing, ["E1", "E2", "E3", "E4"]);
+
+        let mut shipping = Vec::new();
+        for _ in 0..4 {
+            shipping.push(other.next().await.unwrap().unwrap().event_type);
+        }
+        assert_eq!(shipping, ["E1", "E2", "E3", "E4"]);
+
+        // Rebalance: the remaining member absorbs the departed member's share
+        drop(member_b);
+        assert_eq!(bus.group_member_count("billing", "orders"), 1);
+        bus.publish("orders", &bus_event("E5")).await.unwrap();
+        bus.publish("orders", &bus_event("E6")).await.unwrap();
+        assert_eq!(member_a.next().await.unwrap().unwrap().event_type, "E5");
+        assert_eq!(member_a.next().await.unwrap().unwrap().event_type, "E6");
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_bus_subscribe_from_offset_replays_then_goes_live() {
+        use composable_rust_core::event_bus::EventBus;
+        use futures::StreamExt;
+
+        let bus = mocks::InMemoryEventBus::new();
+        for name in ["E0", "E1", "E2"] {
+            bus.publish("orders", &bus_event(name)).await.unwrap();
+        }
+        assert_eq!(bus.end_offset("orders"), 3);
+        assert_eq!(bus.history("orders").len(), 3);
+
+        let mut stream = bus.subscribe_from(&["orders"], 1).await.unwrap();
+        let mut caught_up = bus.subscribe_from(&["orders"], bus.end_offset("orders")).await.unwrap();
+        bus.publish("orders", &bus_event("E3")).await.unwrap();
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push(stream.next().await.unwrap().unwrap().event_type);
+        }
+        assert_eq!(seen, ["E1", "E2", "E3"]);
+        assert_eq!(caught_up.next().await.unwrap().unwrap().event_type, "E3");
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_bus_records_partition_keys() {
+        use composable_rust_core::event_bus::{EventBus, PublishOptions};
+
+        let bus = mocks::InMemoryEventBus::new();
+        let keyed = PublishOptions::new()
+            .with_partition_key("order-1")
+            .with_header("tenant", "acme");
+        bus.publish_with_options("orders", &bus_event("E0"), &keyed)
+            .await
+            .unwrap();
+        bus.publish("orders", &bus_event("E1")).await.unwrap();
+
+        assert_eq!(bus.published_keys("orders"), [Some("order-1".to_string()), None]);
+        assert_eq!(bus.published_options("orders")[0], keyed);
+        assert!(bus.published_keys("payments").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_bus_deliveries_are_auto_acked() {
+        use composable_rust_core::event_bus::EventBus;
+        use futures::StreamExt;
+
+        let bus = mocks::InMemoryEventBus::new();
+        let mut deliveries = bus.subscribe_with_ack(&["orders"]).await.unwrap();
+        bus.publish("orders", &bus_event("E0")).await.unwrap();
+        bus.publish("orders", &bus_event("E1")).await.unwrap();
+
+        let first = deliveries.next().await.unwrap().unwrap();
+        assert_eq!(first.event.event_type, "E0");
+        assert!(!first.requires_ack());
+        first.ack().await.unwrap();
+
+        // Rejecting an auto-acked delivery does not redeliver it
+        let second = deliveries.next().await.unwrap().unwrap();
+        second.nack(true).await.unwrap();
+        bus.publish("orders", &bus_event("E2")).await.unwrap();
+        assert_eq!(deliveries.next().await.unwrap().unwrap().event.event_type, "E2");
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_bus_disconnect_and_reconnect() {
+        use composable_rust_core::event_bus::{EventBus, EventBusError};
+        use futures::StreamExt;
+
+        let bus = mocks::InMemoryEventBus::new();
+        let mut plain = bus.subscribe(&["orders", "payments"]).await.unwrap();
+        let mut grouped = bus.subscribe_group("billing", &["orders"]).await.unwrap();
+        bus.publish("orders", &bus_event("E0")).await.unwrap();
+
+        bus.disconnect();
+        assert!(!bus.is_connected());
+
+        assert_eq!(plain.next().await.unwrap().unwrap().event_type, "E0");
+        assert!(matches!(plain.next().await, Some(Err(EventBusError::TransportError(_)))));
+        assert!(plain.next().await.is_none());
+        assert_eq!(grouped.next().await.unwrap().unwrap().event_type, "E0");
+        assert!(matches!(grouped.next().await, Some(Err(EventBusError::TransportError(_)))));
+        assert!(grouped.next().await.is_none());
+
+        assert!(matches!(
+            bus.publish("orders", &bus_event("E1")).await,
+            Err(EventBusError::PublishFailed { .. })
+        ));
+        assert!(matches!(
+            bus.subscribe(&["orders"]).await,
+            Err(EventBusError::SubscriptionFailed { .. })
+        ));
+        assert_eq!(bus.subscriber_count("orders"), 0);
+
+        // Resume from the last processed offset after the broker comes back
+        bus.reconnect();
+        bus.publish("orders", &bus_event("E1")).await.unwrap();
+        let mut resumed = bus.subscribe_from(&["orders"], 1).await.unwrap();
+        assert_eq!(resumed.next().await.unwrap().unwrap().event_type, "E1");
+    }
+
+    #[tokio::test]
+    async fn test_mock_event_publisher_records_publishes() {
+        use composable_rust_core::event_bus::EventBus;
+        use futures::StreamExt;
+        use std::sync::Arc;
+
+        let clock = Arc::new(mocks::test_clock());
+        let publisher = mocks::MockEventPublisher::new().with_clock(clock.clone());
+        let mut stream = publisher.subscribe(&["orders"]).await.unwrap();
+
+        publisher.publish("orders", &bus_event("OrderPlaced")).await.unwrap();
+        clock.advance(chrono::Duration::seconds(5));
+        publisher.publish("payments", &bus_event("PaymentTaken")).await.unwrap();
+
+        // Publishes still reach subscribers of the inner bus
+        assert_eq!(stream.next().await.unwrap().unwrap().event_type, "OrderPlaced");
+
+        let records = publisher.published();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].topic, "orders");
+        assert_eq!(records[1].published_at - records[0].published_at, chrono::Duration::seconds(5));
+        assert_eq!(publisher.published_to("payments").len(), 1);
+
+        let found =
+            publisher.assert_published_matching("payments", |p| p.event.event_type == "PaymentTaken");
+        assert_eq!(found.published_at, clock.now());
+
+        publisher.clear();
+        assert!(publisher.published().is_empty());
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "no event published to 'orders' matched the predicate")]
+    async fn test_mock_event_publisher_assert_published_matching_panics() {
+        use composable_rust_core::event_bus::EventBus;
+
+        let publisher = mocks::MockEventPublisher::new();
+        publisher.publish("orders", &bus_event("OrderPlaced")).await.unwrap();
+        publisher.assert_published_matching("orders", |p| p.event.event_type == "OrderCancelled");
+    }
+
+    #[tokio::test]
+    async fn test_mock_event_publisher_skips_failed_publishes() {
+        use composable_rust_core::event_bus::EventBus;
+        use std::sync::Arc;
+
+        let bus = mocks::InMemoryEventBus::new();
+        let publisher = mocks::MockEventPublisher::wrapping(Arc::new(bus.clone()));
+        bus.disconnect();
+
+        assert!(publisher.publish("orders", &bus_event("OrderPlaced")).await.is_err());
+        assert!(publisher.published().is_empty());
+    }
+
+    // ========== Effect Builder Tests ==========
+
+    #[tokio::test]
+    async fn test_effect_builders_produce_operations() {
+        use composable_rust_core::effect::{Effect, EventBusOperation, EventStoreOperation};
+        use composable_rust_core::effect_builders::{EventBusEffect, EventStoreEffect};
+        use composable_rust_core::event::SerializedEvent;
+        use composable_rust_core::event_bus::EventBus;
+        use composable_rust_core::event_store::EventStore;
+        use composable_rust_core::stream::Version;
+        use std::sync::Arc;
+
+        let event_store: Arc<dyn EventStore> = Arc::new(mocks::InMemoryEventStore::new());
+        let event_bus: Arc<dyn EventBus> = Arc::new(mocks::InMemoryEventBus::new());
+        let event = || SerializedEvent::new("Placed".to_string(), vec![], None);
+
+        let append: Effect<u64> = EventStoreEffect::append(&event_store, "order-1", vec![event()])
+            .expected_version(Some(Version::new(3)))
+            .on_success(|version| Some(version.value()))
+            .into_effect();
+        let Effect::EventStore(EventStoreOperation::AppendEvents {
+            stream_id,
+            expected_version,
+            events,
+            metadata,
+            on_success,
+            on_error,
+            ..
+        }) = append
+        else {
+            panic!("expected AppendEvents");
+        };
+        assert_eq!(stream_id.as_str(), "order-1");
+        assert_eq!(expected_version, Some(Version::new(3)));
+        assert_eq!(events.len(), 1);
+        assert!(metadata.is_none());
+        assert_eq!(on_success(Version::new(4)), Some(4));
+        assert_eq!(on_error(composable_rust_core::event_store::EventStoreError::DatabaseError(String::from("down"))), None);
+
+        let publish: Effect<&str> = EventBusEffect::publish(&event_bus, "orders", event())
+            .on_success(|| Some("published"))
+            .into();
+        let Effect::PublishEvent(EventBusOperation::Publish { topic, on_success, .. }) = publish else {
+            panic!("expected Publish");
+        };
+        assert_eq!(topic, "orders");
+        assert_eq!(on_success(()), Some("published"));
+    }
 }
@@ -3,6 +3,7 @@
 //! Provides fast, deterministic testing infrastructure for projections:
 //! - [`InMemoryProjectionStore`]: HashMap-based projection storage
 //! - [`InMemoryProjectionCheckpoint`]: In-memory checkpoint tracking
+//! - [`InMemoryReadModelStore`]: BTreeMap-based read model storage for `Effect::Query`
 //! - [`ProjectionTestHarness`]: Fluent API for projection tests
 
 #![allow(clippy::unwrap_used)] // Test infrastructure uses unwrap for simplicity
@@ -11,7 +12,9 @@
 use composable_rust_core::projection::{
     EventPosition, Projection, ProjectionCheckpoint, ProjectionStore, Result,
 };
-use std::collections::HashMap;
+use composable_rust_core::read_model::{ReadModelError, ReadModelQuery, ReadModelRow, ReadModelStore};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::{Arc, RwLock};
@@ -217,6 +220,135 @@ impl ProjectionCheckpoint for InMemoryProjectionCheckpoint {
     }
 }
 
+/// In-memory read model store for testing `Effect::Query` reducers.
+///
+/// Documents are kept per collection in key order, and queries are
+/// evaluated with [`ReadModelQuery::apply`], so unordered queries return
+/// documents sorted by key.
+///
+/// # Example
+///
+/// ```
+/// use composable_rust_testing::InMemoryReadModelStore;
+/// use composable_rust_core::read_model::{Filter, ReadModelQuery, ReadModelStore};
+/// use serde_json::json;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let store = InMemoryReadModelStore::new();
+/// store.insert("orders", "o-1", json!({"status": "open"}));
+///
+/// let rows = store
+///     .query(&ReadModelQuery::new("orders").filter(Filter::eq("status", "open")))
+///     .await?;
+/// assert_eq!(rows.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryReadModelStore {
+    collections: Arc<RwLock<HashMap<String, BTreeMap<String, Value>>>>,
+}
+
+impl InMemoryReadModelStore {
+    /// Create a new empty read model store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a document synchronously (for test setup)
+    pub fn insert(&self, collection: &str, key: &str, value: Value) {
+        self.collections
+            .write()
+            .unwrap()
+            .entry(collection.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+    }
+
+    /// Number of documents in `collection`
+    #[must_use]
+    pub fn len(&self, collection: &str) -> usize {
+        self.collections
+            .read()
+            .unwrap()
+            .get(collection)
+            .map_or(0, BTreeMap::len)
+    }
+
+    /// Clear all collections
+    ///
+    /// Useful for test isolation.
+    pub fn clear(&self) {
+        self.collections.write().unwrap().clear();
+    }
+}
+
+impl ReadModelStore for InMemoryReadModelStore {
+    fn get(
+        &self,
+        collection: &str,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<Option<Value>, ReadModelError>> + Send + '_>>
+    {
+        let value = self
+            .collections
+            .read()
+            .unwrap()
+            .get(collection)
+            .and_then(|documents| documents.get(key).cloned());
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn put(
+        &self,
+        collection: &str,
+        key: &str,
+        value: Value,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<(), ReadModelError>> + Send + '_>> {
+        self.insert(collection, key, value);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn delete(
+        &self,
+        collection: &str,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<bool, ReadModelError>> + Send + '_>> {
+        let existed = self
+            .collections
+            .write()
+            .unwrap()
+            .get_mut(collection)
+            .is_some_and(|documents| documents.remove(key).is_some());
+        Box::pin(async move { Ok(existed) })
+    }
+
+    fn query(
+        &self,
+        query: &ReadModelQuery,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<Vec<ReadModelRow>, ReadModelError>> + Send + '_>>
+    {
+        let rows: Vec<ReadModelRow> = self
+            .collections
+            .read()
+            .unwrap()
+            .get(&query.collection)
+            .map(|documents| {
+                documents
+                    .iter()
+                    .map(|(key, value)| ReadModelRow {
+                        key: key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let rows = query.apply(rows);
+        Box::pin(async move { Ok(rows) })
+    }
+}
+
 /// Test harness for projections providing a fluent testing API.
 ///
 /// This helper makes projection tests more readable and easier to write
@@ -1,10 +1,15 @@
 //! Ergonomic testing utilities for reducers
 //!
-//! This module provides a fluent API for testing reducers with readable Given-When-Then syntax.
+//! This module provides a fluent API for testing reducers with readable Given-When-Then syntax,
+//! and [`ReducerTester`] for driving a reducer through a sequence of actions.
 
 #![allow(clippy::module_name_repetitions)] // ReducerTest is the natural name
 
-use composable_rust_core::{effect::Effect, reducer::Reducer};
+use composable_rust_core::effect_description::EffectDescription;
+use composable_rust_core::{
+    effect::Effect,
+    reducer::{ReduceContext, Reducer},
+};
 
 /// Type alias for state assertion functions
 type StateAssertion<S> = Box<dyn FnOnce(&S)>;
@@ -137,6 +142,162 @@ where
     }
 }
 
+/// Synchronous harness that runs a reducer without a Store
+///
+/// Each [`send`](Self::send) reduces one action against the current state and
+/// keeps the returned effects for inspection. Effects are never executed:
+/// assertions pattern-match on the `Effect` values themselves, including the
+/// parameters of `EventStore` and `PublishEvent` operations.
+///
+/// # Example
+///
+/// ```ignore
+/// use composable_rust_core::effect::{Effect, EventStoreOperation};
+/// use composable_rust_testing::ReducerTester;
+///
+/// ReducerTester::new(OrderReducer, test_environment(), OrderState::default())
+///     .send(OrderAction::PlaceOrder { id: "o-1".into() })
+///     .assert_state(|state| assert_eq!(state.orders.len(), 1))
+///     .assert_effects(|effects| {
+///         assert!(matches!(
+///             effects,
+///             [Effect::EventStore(EventStoreOperation::AppendEvents { stream_id, .. })]
+///                 if stream_id.as_str() == "order-o-1"
+///         ));
+///     });
+/// ```
+pub struct ReducerTester<R, S, A, E>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    reducer: R,
+    environment: E,
+    state: S,
+    effects: Vec<Effect<A>>,
+}
+
+impl<R, S, A, E> ReducerTester<R, S, A, E>
+where
+    R: Reducer<State = S, Action = A, Environment = E>,
+{
+    /// Create a tester starting from `state`
+    #[must_use]
+    pub const fn new(reducer: R, environment: E, state: S) -> Self {
+        Self {
+            reducer,
+            environment,
+            state,
+            effects: Vec::new(),
+        }
+    }
+
+    /// Reduce `action`, replacing the effects kept from the previous send
+    pub fn send(&mut self, action: A) -> &mut Self {
+        self.effects = self
+            .reducer
+            .reduce(&mut self.state, action, &self.environment)
+            .into_vec();
+        self
+    }
+
+    /// Reduce `action` with an explicit [`ReduceContext`], e.g.
+    /// [`ReduceContext::replay`] to test how the reducer handles replayed events
+    pub fn send_with_context(&mut self, action: A, context: &ReduceContext) -> &mut Self {
+        self.effects = self
+            .reducer
+            .reduce_with_context(&mut self.state, action, &self.environment, context)
+            .into_vec();
+        self
+    }
+
+    /// Reduce each action in order, keeping the effects of the last one
+    pub fn send_all(&mut self, actions: impl IntoIterator<Item = A>) -> &mut Self {
+        for action in actions {
+            self.send(action);
+        }
+        self
+    }
+
+    /// Assert on the current state
+    pub fn assert_state<F>(&mut self, assertion: F) -> &mut Self
+    where
+        F: FnOnce(&S),
+    {
+        assertion(&self.state);
+        self
+    }
+
+    /// Assert on the effects returned by the last send
+    pub fn assert_effects<F>(&mut self, assertion: F) -> &mut Self
+    where
+        F: FnOnce(&[Effect<A>]),
+    {
+        assertion(&self.effects);
+        self
+    }
+
+    /// Assert that the last send returned no effects (or only `Effect::None`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the last send returned any other effect.
+    pub fn assert_no_effects(&mut self) -> &mut Self
+    where
+        A: std::fmt::Debug,
+    {
+        assertions::assert_no_effects(&self.effects);
+        self
+    }
+
+    /// Assert that the last send returned effects matching `expected`
+    ///
+    /// Compares [`EffectDescription`]s, so callbacks and the stores or buses
+    /// the operations would run against are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the descriptions differ.
+    pub fn assert_described(&mut self, expected: &[EffectDescription]) -> &mut Self
+    where
+        A: std::fmt::Debug,
+    {
+        assert_eq!(self.describe_effects(), expected, "unexpected effects");
+        self
+    }
+
+    /// Descriptions of the effects returned by the last send, e.g. for snapshots
+    #[must_use]
+    pub fn describe_effects(&self) -> Vec<EffectDescription>
+    where
+        A: std::fmt::Debug,
+    {
+        self.effects.iter().map(Effect::describe).collect()
+    }
+
+    /// The current state
+    #[must_use]
+    pub const fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// The effects returned by the last send
+    #[must_use]
+    pub fn effects(&self) -> &[Effect<A>] {
+        &self.effects
+    }
+
+    /// Take the effects returned by the last send, e.g. to run their callbacks
+    pub fn take_effects(&mut self) -> Vec<Effect<A>> {
+        std::mem::take(&mut self.effects)
+    }
+
+    /// Consume the tester, returning the final state
+    #[must_use]
+    pub fn into_state(self) -> S {
+        self.state
+    }
+}
+
 /// Helper assertions for effects
 pub mod assertions {
     use composable_rust_core::effect::Effect;
@@ -297,4 +458,119 @@ mod tests {
         assertions::assert_effects_count(&[Effect::<TestAction>::None], 1);
         assertions::assert_effects_count::<TestAction>(&[], 0);
     }
+
+    #[derive(Debug)]
+    enum LedgerAction {
+        Deposit(u64),
+        Recorded,
+    }
+
+    struct LedgerEnv {
+        event_store: std::sync::Arc<dyn composable_rust_core::event_store::EventStore>,
+    }
+
+    struct LedgerReducer;
+
+    impl Reducer for LedgerReducer {
+        type State = u64;
+        type Action = LedgerAction;
+        type Environment = LedgerEnv;
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            env: &Self::Environment,
+        ) -> smallvec::SmallVec<[Effect<Self::Action>; 4]> {
+            use composable_rust_core::effect::EventStoreOperation;
+            use composable_rust_core::event::SerializedEvent;
+            use composable_rust_core::stream::{StreamId, Version};
+
+            match action {
+                LedgerAction::Deposit(amount) => {
+                    *state += amount;
+                    smallvec::smallvec![Effect::EventStore(EventStoreOperation::AppendEvents {
+                        event_store: std::sync::Arc::clone(&env.event_store),
+                        stream_id: StreamId::new("ledger-1"),
+                        expected_version: Some(Version::new(*state)),
+                        events: vec![SerializedEvent::new(
+                            "Deposited.v1".to_string(),
+                            amount.to_be_bytes().to_vec(),
+                            None,
+                        )],
+                        metadata: None,
+                        idempotency_key: None,
+                        on_success: Box::new(|_| Some(LedgerAction::Recorded)),
+                        on_error: Box::new(|_| None),
+                    })]
+                }
+                LedgerAction::Recorded => smallvec::smallvec![Effect::None],
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::panic)] // Tests can panic
+    fn test_reducer_tester_inspects_event_store_operations() {
+        use composable_rust_core::effect::EventStoreOperation;
+        use composable_rust_core::stream::Version;
+
+        let env = LedgerEnv {
+            event_store: std::sync::Arc::new(crate::mocks::InMemoryEventStore::new()),
+        };
+
+        let mut tester = ReducerTester::new(LedgerReducer, env, 0);
+        tester
+            .send(LedgerAction::Deposit(5))
+            .assert_state(|balance| assert_eq!(*balance, 5))
+            .assert_effects(|effects| match effects {
+                [
+                    Effect::EventStore(EventStoreOperation::AppendEvents {
+                        stream_id,
+                        expected_version,
+                        events,
+                        ..
+                    }),
+                ] => {
+                    assert_eq!(stream_id.as_str(), "ledger-1");
+                    assert_eq!(*expected_version, Some(Version::new(5)));
+                    assert_eq!(events[0].event_type, "Deposited.v1");
+                }
+                _ => panic!("expected a single append, got {effects:?}"),
+            })
+            .send_all([LedgerAction::Deposit(2), LedgerAction::Recorded])
+            .assert_no_effects();
+
+        assert_eq!(tester.into_state(), 7);
+    }
+
+    #[test]
+    fn test_reducer_tester_describes_effects() {
+        let env = LedgerEnv {
+            event_store: std::sync::Arc::new(crate::mocks::InMemoryEventStore::new()),
+        };
+
+        let mut tester = ReducerTester::new(LedgerReducer, env, 1);
+        tester.send(LedgerAction::Deposit(2)).assert_described(&[
+            EffectDescription::AppendEvents {
+                stream_id: "ledger-1".to_string(),
+                expected_version: Some(3),
+                event_types: vec!["Deposited.v1".to_string()],
+            },
+        ]);
+
+        insta::assert_debug_snapshot!(tester.describe_effects(), @r#"
+        [
+            AppendEvents {
+                stream_id: "ledger-1",
+                expected_version: Some(
+                    3,
+                ),
+                event_types: [
+                    "Deposited.v1",
+                ],
+            },
+        ]
+        "#);
+    }
 }
@@ -0,0 +1,464 @@
+//! Given-When-Then harness for saga reducers
+//!
+//! A saga reacts to events from other aggregates by issuing commands, and to
+//! failures by issuing compensating commands. [`SagaTestHarness`] runs a saga
+//! reducer to quiescence with a FIFO feedback queue, so actions are reduced
+//! in a known order, and records every command the saga publishes:
+//!
+//! - `given` events build up the saga's history; their commands are discarded
+//! - the `when` action is the event under test
+//! - `then_expect_commands` / `then_expect_compensation` assert the exact
+//!   sequence of commands it caused, split by
+//!   [`with_compensation`](SagaTestHarness::with_compensation)
+//!
+//! Effects are executed as follows:
+//!
+//! - `PublishEvent` is published on the operation's event bus (usually an
+//!   `InMemoryEventBus` from the environment) and recorded as a [`SagaCommand`]
+//! - `Future` is awaited and its action fed back
+//! - `EventStore` appends run against the operation's event store; other
+//!   event store operations are not run
+//! - `Delay`, `DelayKeyed`, `Stream` and `Query` are not run. Test a timeout
+//!   by sending the timeout action itself with `when`
+
+#![allow(clippy::module_name_repetitions)] // SagaTestHarness is the natural name
+
+use composable_rust_core::effect::{
+    CancellationToken, Effect, EventBusOperation, EventStoreOperation,
+};
+use composable_rust_core::event::SerializedEvent;
+use composable_rust_core::reducer::Reducer;
+use std::collections::VecDeque;
+
+/// Most actions reduced for one `given` or `when` before the saga is
+/// considered to loop
+const MAX_ACTIONS: usize = 1_000;
+
+/// Type alias for state assertion functions
+type StateAssertion<S> = Box<dyn FnOnce(&S)>;
+
+/// A command published by the saga under test
+#[derive(Debug, Clone)]
+pub struct SagaCommand {
+    /// Topic the command was published to
+    pub topic: String,
+    /// The published command
+    pub event: SerializedEvent,
+}
+
+impl SagaCommand {
+    /// Type of the published command (e.g. `"ChargeCard.v1"`)
+    #[must_use]
+    pub fn event_type(&self) -> &str {
+        &self.event.event_type
+    }
+}
+
+/// Given-When-Then harness asserting the commands a saga issues
+///
+/// Expected commands are `(topic, event_type)` pairs, compared in order.
+/// For assertions on payloads, [`run`](Self::run) returns the commands
+/// caused by the `when` action.
+///
+/// See the [module documentation](self) for how effects are executed.
+///
+/// # Example
+///
+/// ```ignore
+/// use composable_rust_testing::SagaTestHarness;
+///
+/// SagaTestHarness::new(CheckoutSaga, env, CheckoutState::default())
+///     .with_compensation(|command| command.event_type().starts_with("Cancel"))
+///     .given([CheckoutEvent::OrderPlaced { order_id: "o-1".into() }])
+///     .when(CheckoutEvent::PaymentFailed { order_id: "o-1".into() })
+///     .then_expect_commands(Vec::<(&str, &str)>::new())
+///     .then_expect_compensation([("orders", "CancelOrder.v1")])
+///     .run()
+///     .await;
+/// ```
+pub struct SagaTestHarness<R>
+where
+    R: Reducer,
+{
+    reducer: R,
+    environment: R::Environment,
+    state: R::State,
+    given: Vec<R::Action>,
+    when: Option<R::Action>,
+    is_compensation: Box<dyn Fn(&SagaCommand) -> bool>,
+    expected_commands: Option<Vec<(String, String)>>,
+    expected_compensation: Option<Vec<(String, String)>>,
+    state_assertions: Vec<StateAssertion<R::State>>,
+}
+
+impl<R> SagaTestHarness<R>
+where
+    R: Reducer,
+    R::Action: std::fmt::Debug,
+{
+    /// Create a harness for `reducer` starting from `state`
+    #[must_use]
+    pub fn new(reducer: R, environment: R::Environment, state: R::State) -> Self {
+        Self {
+            reducer,
+            environment,
+            state,
+            given: Vec::new(),
+            when: None,
+            is_compensation: Box::new(|_| false),
+            expected_commands: None,
+            expected_compensation: None,
+            state_assertions: Vec::new(),
+        }
+    }
+
+    /// Classify commands matching `predicate` as compensation
+    ///
+    /// Without a classifier every command is a forward command.
+    #[must_use]
+    pub fn with_compensation<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&SagaCommand) -> bool + 'static,
+    {
+        self.is_compensation = Box::new(predicate);
+        self
+    }
+
+    /// Events that happened before the one under test (Given)
+    ///
+    /// Each is reduced in order, with its feedback; the commands they cause
+    /// are discarded.
+    #[must_use]
+    pub fn given(mut self, events: impl IntoIterator<Item = R::Action>) -> Self {
+        self.given.extend(events);
+        self
+    }
+
+    /// The event under test (When)
+    #[must_use]
+    pub fn when(mut self, action: R::Action) -> Self {
+        self.when = Some(action);
+        self
+    }
+
+    /// Expect exactly these forward commands, in order (Then)
+    #[must_use]
+    pub fn then_expect_commands<I, T, E>(mut self, commands: I) -> Self
+    where
+        I: IntoIterator<Item = (T, E)>,
+        T: Into<String>,
+        E: Into<String>,
+    {
+        self.expected_commands = Some(pairs(commands));
+        self
+    }
+
+    /// Expect exactly these compensating commands, in order (Then)
+    #[must_use]
+    pub fn then_expect_compensation<I, T, E>(mut self, commands: I) -> Self
+    where
+        I: IntoIterator<Item = (T, E)>,
+        T: Into<String>,
+        E: Into<String>,
+    {
+        self.expected_compensation = Some(pairs(commands));
+        self
+    }
+
+    /// Add an assertion about the saga's final state (Then)
+    #[must_use]
+    pub fn then_state<F>(mut self, assertion: F) -> Self
+    where
+        F: FnOnce(&R::State) + 'static,
+    {
+        self.state_assertions.push(Box::new(assertion));
+        self
+    }
+
+    /// Run the scenario, check the expectations and return the commands the
+    /// `when` action caused
+    ///
+    /// # Panics
+    ///
+    /// Panics if no `when` action is set, if the saga keeps producing actions
+    /// without settling, or if any expectation fails.
+    #[allow(clippy::expect_used)] // Test code can use expect
+    pub async fn run(mut self) -> Vec<SagaCommand> {
+        let when = self
+            .when
+            .take()
+            .expect("Event under test must be set with when()");
+
+        for event in std::mem::take(&mut self.given) {
+            self.settle(event).await;
+        }
+        let commands = self.settle(when).await;
+
+        let (compensation, forward): (Vec<&SagaCommand>, Vec<&SagaCommand>) = commands
+            .iter()
+            .partition(|command| (self.is_compensation)(command));
+        if let Some(expected) = &self.expected_commands {
+            assert_sequence("commands", expected, &forward);
+        }
+        if let Some(expected) = &self.expected_compensation {
+            assert_sequence("compensation", expected, &compensation);
+        }
+        for assertion in self.state_assertions.drain(..) {
+            assertion(&self.state);
+        }
+        commands
+    }
+
+    /// Reduce `action` and its feedback until the queue is empty, returning
+    /// the commands published meanwhile
+    async fn settle(&mut self, action: R::Action) -> Vec<SagaCommand> {
+        let mut queue = VecDeque::from([action]);
+        let mut commands = Vec::new();
+        let mut reduced = 0;
+
+        while let Some(action) = queue.pop_front() {
+            reduced += 1;
+            assert!(
+                reduced <= MAX_ACTIONS,
+                "saga did not settle after {MAX_ACTIONS} actions; last: {action:?}"
+            );
+            let effects = self
+                .reducer
+                .reduce(&mut self.state, action, &self.environment);
+            let mut pending: VecDeque<Effect<R::Action>> = effects.into_iter().collect();
+            while let Some(effect) = pending.pop_front() {
+                run_effect(effect, &mut pending, &mut queue, &mut commands).await;
+            }
+        }
+        commands
+    }
+}
+
+/// Execute one effect, queueing nested effects and fed-back actions
+async fn run_effect<A>(
+    effect: Effect<A>,
+    pending: &mut VecDeque<Effect<A>>,
+    queue: &mut VecDeque<A>,
+    commands: &mut Vec<SagaCommand>,
+) {
+    match effect {
+        Effect::Parallel(effects) | Effect::Sequential(effects) => {
+            // Run nested effects next, in declaration order
+            for effect in effects.into_iter().rev() {
+                pending.push_front(effect);
+            }
+        },
+        Effect::Costed { effect, .. } | Effect::Retrying { effect, .. } => {
+            pending.push_front(*effect);
+        },
+        Effect::Future(future) => queue.extend(future.await),
+        // The harness never shuts down, so the token is never cancelled
+        Effect::FutureWithCancel(start) => queue.extend(start(CancellationToken::new()).await),
+        Effect::PublishEvent(EventBusOperation::Publish {
+            event_bus,
+            topic,
+            event,
+            options,
+            on_success,
+            on_error,
+        }) => {
+            let feedback = match event_bus
+                .publish_with_options(&topic, &event, &options)
+                .await
+            {
+                Ok(()) => on_success(()),
+                Err(error) => on_error(error),
+            };
+            commands.push(SagaCommand { topic, event });
+            queue.extend(feedback);
+        },
+        Effect::EventStore(EventStoreOperation::AppendEvents {
+            event_store,
+            stream_id,
+            expected_version,
+            events,
+            on_success,
+            on_error,
+            ..
+        }) => {
+            let feedback = match event_store
+                .append_events(stream_id, expected_version, events)
+                .await
+            {
+                Ok(version) => on_success(version),
+                Err(error) => on_error(error),
+            };
+            queue.extend(feedback);
+        },
+        Effect::None
+        | Effect::Delay { .. }
+        | Effect::DelayKeyed { .. }
+        | Effect::Stream(_)
+        | Effect::EventStore(_)
+        | Effect::Query(_) => {},
+    }
+}
+
+/// Collect expected `(topic, event_type)` pairs
+fn pairs<I, T, E>(commands: I) -> Vec<(String, String)>
+where
+    I: IntoIterator<Item = (T, E)>,
+    T: Into<String>,
+    E: Into<String>,
+{
+    commands
+        .into_iter()
+        .map(|(topic, event_type)| (topic.into(), event_type.into()))
+        .collect()
+}
+
+/// Assert `actual` commands match `expected` `(topic, event_type)` pairs
+fn assert_sequence(kind: &str, expected: &[(String, String)], actual: &[&SagaCommand]) {
+    let actual: Vec<(String, String)> = actual
+        .iter()
+        .map(|command| (command.topic.clone(), command.event_type().to_string()))
+        .collect();
+    assert!(
+        actual == expected,
+        "saga {kind} mismatch\n  expected: {expected:?}\n    actual: {actual:?}"
+    );
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Test code can use unwrap
+mod tests {
+    use super::*;
+    use crate::mocks::InMemoryEventBus;
+    use composable_rust_core::event_bus::EventBus;
+    use composable_rust_core::{SmallVec, smallvec};
+    use std::sync::Arc;
+
+    #[derive(Debug, Clone)]
+    enum CheckoutEvent {
+        OrderPlaced,
+        PaymentCompleted,
+        PaymentFailed,
+        ShippingScheduled,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    enum Stage {
+        #[default]
+        Started,
+        Paying,
+        Shipping,
+        Done,
+        Compensated,
+    }
+
+    #[derive(Clone)]
+    struct CheckoutSaga {
+        bus: Arc<dyn EventBus>,
+    }
+
+    impl CheckoutSaga {
+        fn command(&self, topic: &str, event_type: &str) -> Effect<CheckoutEvent> {
+            Effect::PublishEvent(EventBusOperation::Publish {
+                event_bus: Arc::clone(&self.bus),
+                topic: topic.to_string(),
+                event: SerializedEvent::new(event_type.to_string(), vec![], None),
+                options: composable_rust_core::event_bus::PublishOptions::default(),
+                on_success: Box::new(|()| None),
+                on_error: Box::new(|_| None),
+            })
+        }
+    }
+
+    impl Reducer for CheckoutSaga {
+        type State = Stage;
+        type Action = CheckoutEvent;
+        type Environment = ();
+
+        fn reduce(
+            &self,
+            state: &mut Stage,
+            action: CheckoutEvent,
+            (): &(),
+        ) -> SmallVec<[Effect<CheckoutEvent>; 4]> {
+            match action {
+                CheckoutEvent::OrderPlaced => {
+                    *state = Stage::Paying;
+                    smallvec![self.command("payments", "ChargeCard.v1")]
+                },
+                CheckoutEvent::PaymentCompleted => {
+                    *state = Stage::Shipping;
+                    // Shipping answers immediately in this test
+                    smallvec![Effect::Sequential(vec![
+                        self.command("shipping", "ScheduleShipping.v1"),
+                        Effect::Future(Box::pin(async { Some(CheckoutEvent::ShippingScheduled) })),
+                    ])]
+                },
+                CheckoutEvent::PaymentFailed => {
+                    *state = Stage::Compensated;
+                    smallvec![
+                        self.command("orders", "CancelOrder.v1"),
+                        self.command("notifications", "NotifyCustomer.v1"),
+                    ]
+                },
+                CheckoutEvent::ShippingScheduled => {
+                    *state = Stage::Done;
+                    smallvec![self.command("orders", "CompleteOrder.v1")]
+                },
+            }
+        }
+    }
+
+    fn harness(bus: &InMemoryEventBus) -> SagaTestHarness<CheckoutSaga> {
+        SagaTestHarness::new(
+            CheckoutSaga {
+                bus: Arc::new(bus.clone()),
+            },
+            (),
+            Stage::default(),
+        )
+        .with_compensation(|command| command.event_type() == "CancelOrder.v1")
+    }
+
+    #[tokio::test]
+    async fn test_happy_path_issues_commands_through_feedback() {
+        let bus = InMemoryEventBus::new();
+
+        let commands = harness(&bus)
+            .given([CheckoutEvent::OrderPlaced])
+            .when(CheckoutEvent::PaymentCompleted)
+            .then_expect_commands([
+                ("shipping", "ScheduleShipping.v1"),
+                ("orders", "CompleteOrder.v1"),
+            ])
+            .then_expect_compensation(Vec::<(&str, &str)>::new())
+            .then_state(|stage| assert_eq!(*stage, Stage::Done))
+            .run()
+            .await;
+
+        assert_eq!(commands.len(), 2);
+        // Given commands were published too, but are not reported
+        assert_eq!(bus.history("payments").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failure_path_is_split_into_compensation() {
+        harness(&InMemoryEventBus::new())
+            .given([CheckoutEvent::OrderPlaced])
+            .when(CheckoutEvent::PaymentFailed)
+            .then_expect_commands([("notifications", "NotifyCustomer.v1")])
+            .then_expect_compensation([("orders", "CancelOrder.v1")])
+            .then_state(|stage| assert_eq!(*stage, Stage::Compensated))
+            .run()
+            .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "saga compensation mismatch")]
+    async fn test_missing_compensation_fails() {
+        harness(&InMemoryEventBus::new())
+            .when(CheckoutEvent::OrderPlaced)
+            .then_expect_compensation([("orders", "CancelOrder.v1")])
+            .run()
+            .await;
+    }
+}
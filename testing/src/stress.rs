@@ -0,0 +1,538 @@
+//! Soak and stress tests for a Store
+//!
+//! A [`StressHarness`] sends a weighted mix of actions to a Store at a target
+//! rate for a fixed duration, then reports how the runtime kept up:
+//!
+//! - **Reducer latency**: time for `send()` to return, i.e. waiting for the
+//!   state lock plus running the reducer
+//! - **Effect lag**: time from `send()` returning until the action's effects
+//!   have finished
+//! - **DLQ growth**: entries added to the Store's dead letter queue
+//!
+//! Thresholds turn the report into a pass/fail check, so performance
+//! regressions in the runtime fail a test instead of going unnoticed.
+//!
+//! Actions are sent from their own tasks, so a slow Store does not lower the
+//! rate it is driven at. Under paused time (`#[tokio::test(start_paused = true)]`)
+//! the schedule is exact and a run costs no wall-clock time.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_testing::StressHarness;
+//!
+//! let report = StressHarness::new()
+//!     .with_action(9, |_| CounterAction::Increment)
+//!     .with_action(1, |n| CounterAction::Reset { to: n })
+//!     .with_rate(2_000)
+//!     .with_duration(Duration::from_secs(10))
+//!     .with_max_reducer_p99(Duration::from_millis(5))
+//!     .with_max_dlq_growth(0)
+//!     .run(&store)
+//!     .await?;
+//! println!("{report}");
+//! ```
+
+use crate::latency::split_mix64;
+use composable_rust_core::reducer::Reducer;
+use composable_rust_runtime::Store;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::time::Instant;
+
+/// Builds the `n`th action sent by a run
+type ActionFactory<A> = Arc<dyn Fn(u64) -> A + Send + Sync>;
+
+/// Errors reported by [`StressHarness::run`]
+#[derive(Error, Debug)]
+pub enum StressError {
+    /// No action with a non-zero weight was added
+    #[error("Stress harness has no actions to send")]
+    NoActions,
+
+    /// The run finished but broke at least one threshold
+    #[error("Stress thresholds exceeded: {}", .violations.join("; "))]
+    ThresholdsExceeded {
+        /// One message per broken threshold
+        violations: Vec<String>,
+        /// The full report of the run
+        report: Box<StressReport>,
+    },
+}
+
+/// Percentiles of a set of latency samples
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencySummary {
+    /// Number of samples
+    pub samples: usize,
+    /// Median
+    pub p50: Duration,
+    /// 95th percentile
+    pub p95: Duration,
+    /// 99th percentile
+    pub p99: Duration,
+    /// Slowest sample
+    pub max: Duration,
+}
+
+impl LatencySummary {
+    /// Summarize `samples` (nearest-rank percentiles)
+    #[must_use]
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let percentile = |p: usize| {
+            let rank = (samples.len() * p).div_ceil(100).max(1);
+            samples.get(rank - 1).copied().unwrap_or_default()
+        };
+        Self {
+            samples: samples.len(),
+            p50: percentile(50),
+            p95: percentile(95),
+            p99: percentile(99),
+            max: samples.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+impl std::fmt::Display for LatencySummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "p50 {:?}, p95 {:?}, p99 {:?}, max {:?}",
+            self.p50, self.p95, self.p99, self.max
+        )
+    }
+}
+
+/// What a [`StressHarness`] run measured
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StressReport {
+    /// Actions the Store accepted
+    pub sent: u64,
+    /// Actions the Store rejected (rate limit, shutdown, budgets, ...)
+    pub rejected: u64,
+    /// Time for `send()` to return for each accepted action
+    pub reducer_latency: LatencySummary,
+    /// Time from `send()` returning until the action's effects finished
+    pub effect_lag: LatencySummary,
+    /// Accepted actions whose effects were still running at the effect timeout
+    pub effects_timed_out: u64,
+    /// Entries added to the Store's dead letter queue during the run
+    pub dlq_growth: usize,
+    /// Time spent sending actions
+    pub elapsed: Duration,
+}
+
+impl StressReport {
+    /// Accepted actions per second
+    #[must_use]
+    pub fn throughput(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)] // Action counts are far below 2^52
+        let sent = self.sent as f64;
+        sent / seconds
+    }
+}
+
+impl std::fmt::Display for StressReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} sent, {} rejected in {:?} ({:.0}/s)",
+            self.sent,
+            self.rejected,
+            self.elapsed,
+            self.throughput()
+        )?;
+        writeln!(f, "reducer latency: {}", self.reducer_latency)?;
+        writeln!(
+            f,
+            "effect lag: {} ({} timed out)",
+            self.effect_lag, self.effects_timed_out
+        )?;
+        write!(f, "dlq growth: {}", self.dlq_growth)
+    }
+}
+
+/// Limits a run must stay within
+#[derive(Debug, Clone, Copy, Default)]
+struct Thresholds {
+    reducer_p99: Option<Duration>,
+    effect_lag_p99: Option<Duration>,
+    dlq_growth: Option<usize>,
+    rejected: Option<u64>,
+}
+
+/// How one sent action went
+enum Outcome {
+    Accepted {
+        reducer: Duration,
+        /// `None` if its effects outlived the effect timeout
+        lag: Option<Duration>,
+    },
+    Rejected,
+}
+
+/// Drives a Store with a weighted action mix at a target rate.
+///
+/// See the [module documentation](self) for an overview.
+pub struct StressHarness<A> {
+    mix: Vec<(u32, ActionFactory<A>)>,
+    rate: u32,
+    duration: Duration,
+    seed: u64,
+    effect_timeout: Duration,
+    thresholds: Thresholds,
+}
+
+impl<A> Default for StressHarness<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> StressHarness<A> {
+    /// A harness sending 100 actions per second for one second, with no
+    /// actions and no thresholds.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            mix: Vec::new(),
+            rate: 100,
+            duration: Duration::from_secs(1),
+            seed: 0,
+            effect_timeout: Duration::from_secs(5),
+            thresholds: Thresholds::default(),
+        }
+    }
+
+    /// Add an action to the mix, picked with probability proportional to
+    /// `weight`; `make` receives the sequence number of the send.
+    #[must_use]
+    pub fn with_action<F>(mut self, weight: u32, make: F) -> Self
+    where
+        F: Fn(u64) -> A + Send + Sync + 'static,
+    {
+        self.mix.push((weight, Arc::new(make)));
+        self
+    }
+
+    /// Send `per_second` actions per second (at least 1).
+    #[must_use]
+    pub const fn with_rate(mut self, per_second: u32) -> Self {
+        self.rate = if per_second == 0 { 1 } else { per_second };
+        self
+    }
+
+    /// Keep sending for `duration`.
+    #[must_use]
+    pub const fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Seed the choice of actions from the mix.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Stop waiting for an action's effects after `timeout` (default 5s).
+    #[must_use]
+    pub const fn with_effect_timeout(mut self, timeout: Duration) -> Self {
+        self.effect_timeout = timeout;
+        self
+    }
+
+    /// Fail the run if the 99th percentile reducer latency exceeds `max`.
+    #[must_use]
+    pub const fn with_max_reducer_p99(mut self, max: Duration) -> Self {
+        self.thresholds.reducer_p99 = Some(max);
+        self
+    }
+
+    /// Fail the run if the 99th percentile effect lag exceeds `max`.
+    #[must_use]
+    pub const fn with_max_effect_lag_p99(mut self, max: Duration) -> Self {
+        self.thresholds.effect_lag_p99 = Some(max);
+        self
+    }
+
+    /// Fail the run if more than `max` entries reach the dead letter queue.
+    #[must_use]
+    pub const fn with_max_dlq_growth(mut self, max: usize) -> Self {
+        self.thresholds.dlq_growth = Some(max);
+        self
+    }
+
+    /// Fail the run if the Store rejects more than `max` actions.
+    #[must_use]
+    pub const fn with_max_rejected(mut self, max: u64) -> Self {
+        self.thresholds.rejected = Some(max);
+        self
+    }
+
+    /// Drive `store` for the configured duration and check the thresholds.
+    ///
+    /// Waits for the effects of every accepted action (up to the effect
+    /// timeout) before reporting. Effects that outlive the timeout always
+    /// fail the run.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StressError::NoActions`] if the mix is empty, and
+    /// [`StressError::ThresholdsExceeded`] with the report if the run broke
+    /// a threshold.
+    ///
+    /// # Panics
+    ///
+    /// Propagates a panic from a task sending an action.
+    #[allow(clippy::panic)] // Re-raises a panic from a send task
+    pub async fn run<S, E, R>(&self, store: &Store<S, A, E, R>) -> Result<StressReport, StressError>
+    where
+        R: Reducer<State = S, Action = A, Environment = E> + Clone + Send + Sync + 'static,
+        A: Send + Clone + 'static,
+        S: Send + Sync + 'static,
+        E: Clone + Send + Sync + 'static,
+    {
+        let total_weight: u64 = self.mix.iter().map(|(weight, _)| u64::from(*weight)).sum();
+        if total_weight == 0 {
+            return Err(StressError::NoActions);
+        }
+
+        let dlq_before = store.dlq().len();
+        let mut ticker = tokio::time::interval(Duration::from_secs(1) / self.rate);
+        let mut rng = self.seed;
+        let mut tasks = Vec::new();
+        let started = Instant::now();
+
+        loop {
+            ticker.tick().await;
+            if started.elapsed() >= self.duration {
+                break;
+            }
+
+            let action = self.pick(split_mix64(&mut rng) % total_weight)(tasks.len() as u64);
+            let store = store.clone();
+            let effect_timeout = self.effect_timeout;
+            tasks.push(tokio::spawn(async move {
+                let start = Instant::now();
+                let Ok(mut handle) = store.send(action).await else {
+                    return Outcome::Rejected;
+                };
+                let reduced = Instant::now();
+                let lag = tokio::time::timeout(effect_timeout, handle.wait())
+                    .await
+                    .ok()
+                    .map(|_| reduced.elapsed());
+                Outcome::Accepted {
+                    reducer: reduced - start,
+                    lag,
+                }
+            }));
+        }
+        let elapsed = started.elapsed();
+
+        let mut reducer_latency = Vec::with_capacity(tasks.len());
+        let mut effect_lag = Vec::with_capacity(tasks.len());
+        let mut rejected = 0;
+        let mut effects_timed_out = 0;
+        for task in tasks {
+            match task.await {
+                Ok(Outcome::Accepted { reducer, lag }) => {
+                    reducer_latency.push(reducer);
+                    match lag {
+                        Some(lag) => effect_lag.push(lag),
+                        None => effects_timed_out += 1,
+                    }
+                },
+                Ok(Outcome::Rejected) => rejected += 1,
+                Err(error) => match error.try_into_panic() {
+                    Ok(payload) => std::panic::resume_unwind(payload),
+                    Err(error) => panic!("Stress send task failed: {error}"),
+                },
+            }
+        }
+
+        let report = StressReport {
+            sent: reducer_latency.len() as u64,
+            rejected,
+            reducer_latency: LatencySummary::from_samples(reducer_latency),
+            effect_lag: LatencySummary::from_samples(effect_lag),
+            effects_timed_out,
+            dlq_growth: store.dlq().len().saturating_sub(dlq_before),
+            elapsed,
+        };
+        let violations = self.violations(&report);
+        if violations.is_empty() {
+            Ok(report)
+        } else {
+            Err(StressError::ThresholdsExceeded {
+                violations,
+                report: Box::new(report),
+            })
+        }
+    }
+
+    /// The factory whose cumulative weight range contains `draw`
+    fn pick(&self, mut draw: u64) -> &ActionFactory<A> {
+        for (weight, make) in &self.mix {
+            let weight = u64::from(*weight);
+            if draw < weight {
+                return make;
+            }
+            draw -= weight;
+        }
+        // Unreachable while `draw` is below the total weight
+        &self.mix[self.mix.len() - 1].1
+    }
+
+    fn violations(&self, report: &StressReport) -> Vec<String> {
+        let mut violations = Vec::new();
+        if let Some(max) = self.thresholds.reducer_p99
+            && report.reducer_latency.p99 > max
+        {
+            violations.push(format!(
+                "reducer latency p99 {:?} above {max:?}",
+                report.reducer_latency.p99
+            ));
+        }
+        if let Some(max) = self.thresholds.effect_lag_p99
+            && report.effect_lag.p99 > max
+        {
+            violations.push(format!(
+                "effect lag p99 {:?} above {max:?}",
+                report.effect_lag.p99
+            ));
+        }
+        if let Some(max) = self.thresholds.dlq_growth
+            && report.dlq_growth > max
+        {
+            violations.push(format!("dlq grew by {} (max {max})", report.dlq_growth));
+        }
+        if let Some(max) = self.thresholds.rejected
+            && report.rejected > max
+        {
+            violations.push(format!("{} actions rejected (max {max})", report.rejected));
+        }
+        if report.effects_timed_out > 0 {
+            violations.push(format!(
+                "{} actions' effects outlived the {:?} effect timeout",
+                report.effects_timed_out, self.effect_timeout
+            ));
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can unwrap
+#[allow(clippy::panic)] // Tests can panic
+mod tests {
+    use super::*;
+    use composable_rust_core::effect::Effect;
+    use composable_rust_core::{SmallVec, smallvec};
+
+    #[derive(Debug, Clone)]
+    enum Load {
+        Count,
+        Slow(u64),
+    }
+
+    #[derive(Debug, Clone)]
+    struct LoadReducer;
+
+    impl Reducer for LoadReducer {
+        type State = (u64, u64);
+        type Action = Load;
+        type Environment = ();
+
+        fn reduce(
+            &self,
+            state: &mut Self::State,
+            action: Self::Action,
+            _env: &Self::Environment,
+        ) -> SmallVec<[Effect<Self::Action>; 4]> {
+            match action {
+                Load::Count => {
+                    state.0 += 1;
+                    smallvec![Effect::None]
+                },
+                Load::Slow(millis) => {
+                    state.1 += 1;
+                    smallvec![Effect::Future(Box::pin(async move {
+                        tokio::time::sleep(Duration::from_millis(millis)).await;
+                        None
+                    }))]
+                },
+            }
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sends_weighted_mix_at_target_rate() {
+        let store = Store::new((0, 0), LoadReducer, ());
+
+        let report = StressHarness::new()
+            .with_action(3, |_| Load::Count)
+            .with_action(1, |_| Load::Slow(20))
+            .with_rate(200)
+            .with_duration(Duration::from_secs(2))
+            .with_seed(7)
+            .with_max_effect_lag_p99(Duration::from_millis(25))
+            .with_max_dlq_growth(0)
+            .run(&store)
+            .await
+            .unwrap();
+
+        assert_eq!(report.sent, 400);
+        assert_eq!(report.rejected, 0);
+        assert_eq!(report.effect_lag.max, Duration::from_millis(20));
+        let (counted, slow) = store.state(|s| *s).await;
+        assert_eq!(counted + slow, 400);
+        assert!((80..=120).contains(&slow), "slow share was {slow}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fails_when_thresholds_are_exceeded() {
+        let store = Store::new((0, 0), LoadReducer, ());
+
+        let error = StressHarness::new()
+            .with_action(1, |n| Load::Slow(if n % 10 == 0 { 500 } else { 1 }))
+            .with_rate(50)
+            .with_max_effect_lag_p99(Duration::from_millis(100))
+            .with_effect_timeout(Duration::from_millis(400))
+            .run(&store)
+            .await
+            .unwrap_err();
+
+        let StressError::ThresholdsExceeded { violations, report } = error else {
+            panic!("expected ThresholdsExceeded, got {error}");
+        };
+        assert_eq!(report.effects_timed_out, 5);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("outlived"));
+
+        let empty = StressHarness::<Load>::new().run(&store).await;
+        assert!(matches!(empty, Err(StressError::NoActions)));
+    }
+
+    #[test]
+    fn test_latency_summary_uses_nearest_rank() {
+        let summary =
+            LatencySummary::from_samples((1..=100).rev().map(Duration::from_millis).collect());
+        assert_eq!(summary.samples, 100);
+        assert_eq!(summary.p50, Duration::from_millis(50));
+        assert_eq!(summary.p99, Duration::from_millis(99));
+        assert_eq!(summary.max, Duration::from_millis(100));
+        assert_eq!(
+            LatencySummary::from_samples(Vec::new()),
+            LatencySummary::default()
+        );
+    }
+}
@@ -246,8 +246,8 @@ mod tests {
 
     #[test]
     fn test_tool_config_with_timeout() {
-        let config = ToolConfig::default().with_timeout(Duration::from_secs(60));
-        assert_eq!(config.timeout, Duration::from_secs(60));
+        let config = ToolConfig::default().with_timeout(Duration::from_secs(45));
+        assert_eq!(config.timeout, Duration::from_secs(45));
     }
 
     #[tokio::test]
@@ -0,0 +1,354 @@
+//! Machine-readable API contracts (`OpenAPI` and `AsyncAPI`).
+//!
+//! An [`ApiCatalog`] lists the command endpoints a service exposes over HTTP
+//! and the events it publishes on the event bus. From it, the catalog
+//! generates:
+//!
+//! - an **`OpenAPI` 3.1** document describing each command endpoint
+//! - an **`AsyncAPI` 2.6** document describing each topic and its event types
+//!
+//! Both documents can be served at runtime with [`ApiCatalog::router`], or
+//! written to disk from a build step via [`ApiCatalog::openapi`] and
+//! [`ApiCatalog::asyncapi`], so external teams can discover action and event
+//! contracts without reading Rust code.
+//!
+//! Contracts are registered explicitly, and schemas are plain JSON Schema
+//! values supplied at registration time.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use composable_rust_web::api_docs::{ApiCatalog, CommandContract, EventContract};
+//! use serde_json::json;
+//!
+//! let catalog = ApiCatalog::new("Orders", "1.0.0")
+//!     .with_command(
+//!         CommandContract::post("/api/v1/orders", "PlaceOrder", json!({
+//!             "type": "object",
+//!             "required": ["customer_id"],
+//!             "properties": { "customer_id": { "type": "string" } }
+//!         }))
+//!         .with_summary("Place a new order"),
+//!     )
+//!     .with_event(EventContract::new("order-events", "OrderPlaced.v1", json!({ "type": "object" })));
+//!
+//! let app = Router::new()
+//!     .route("/api/v1/orders", post(place_order))
+//!     .merge(catalog.router()); // GET /openapi.json, GET /asyncapi.json
+//! ```
+
+use axum::{http::Method, routing::get, Json, Router};
+use serde_json::{json, Map, Value};
+
+/// An HTTP endpoint that dispatches a command action
+#[derive(Debug, Clone)]
+pub struct CommandContract {
+    /// HTTP method of the endpoint
+    pub method: Method,
+    /// Route path (e.g. `/api/v1/orders/{id}`)
+    pub path: String,
+    /// Name of the action the endpoint dispatches (used as `operationId`)
+    pub action: String,
+    /// One-line description
+    pub summary: Option<String>,
+    /// JSON Schema of the request body
+    pub request_schema: Value,
+    /// JSON Schema of the success response body, if any
+    pub response_schema: Option<Value>,
+}
+
+impl CommandContract {
+    /// Describe an endpoint dispatching `action`
+    #[must_use]
+    pub fn new(method: Method, path: impl Into<String>, action: impl Into<String>, request_schema: Value) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            action: action.into(),
+            summary: None,
+            request_schema,
+            response_schema: None,
+        }
+    }
+
+    /// Describe a `POST` endpoint dispatching `action`
+    #[must_use]
+    pub fn post(path: impl Into<String>, action: impl Into<String>, request_schema: Value) -> Self {
+        Self::new(Method::POST, path, action, request_schema)
+    }
+
+    /// Set the one-line description
+    #[must_use]
+    pub fn with_summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Set the JSON Schema of the success response body
+    #[must_use]
+    pub fn with_response_schema(mut self, schema: Value) -> Self {
+        self.response_schema = Some(schema);
+        self
+    }
+
+    fn operation(&self) -> Value {
+        let mut response = json!({ "description": "Command accepted" });
+        if let Some(schema) = &self.response_schema {
+            response["content"] = json!({ "application/json": { "schema": schema } });
+        }
+
+        let mut operation = json!({
+            "operationId": self.action,
+            "x-action": self.action,
+            "requestBody": {
+                "required": true,
+                "content": { "application/json": { "schema": self.request_schema } }
+            },
+            "responses": { "200": response }
+        });
+        if let Some(summary) = &self.summary {
+            operation["summary"] = json!(summary);
+        }
+        operation
+    }
+}
+
+/// An event type published to an event bus topic
+#[derive(Debug, Clone)]
+pub struct EventContract {
+    /// Topic the event is published to
+    pub topic: String,
+    /// Event type name as carried in `SerializedEvent::event_type`
+    pub event_type: String,
+    /// One-line description
+    pub summary: Option<String>,
+    /// JSON Schema of the event payload
+    pub payload_schema: Value,
+}
+
+impl EventContract {
+    /// Describe `event_type` published on `topic`
+    #[must_use]
+    pub fn new(topic: impl Into<String>, event_type: impl Into<String>, payload_schema: Value) -> Self {
+        Self {
+            topic: topic.into(),
+            event_type: event_type.into(),
+            summary: None,
+            payload_schema,
+        }
+    }
+
+    /// Set the one-line description
+    #[must_use]
+    pub fn with_summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    fn message(&self) -> Value {
+        let mut message = json!({
+            "name": self.event_type,
+            "payload": self.payload_schema,
+        });
+        if let Some(summary) = &self.summary {
+            message["summary"] = json!(summary);
+        }
+        message
+    }
+}
+
+/// The command and event contracts of a service
+#[derive(Debug, Clone)]
+pub struct ApiCatalog {
+    title: String,
+    version: String,
+    description: Option<String>,
+    commands: Vec<CommandContract>,
+    events: Vec<EventContract>,
+}
+
+impl ApiCatalog {
+    /// Create an empty catalog for a service
+    #[must_use]
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            commands: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Set the service description
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Register a command endpoint
+    #[must_use]
+    pub fn with_command(mut self, command: CommandContract) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// Register a published event type
+    #[must_use]
+    pub fn with_event(mut self, event: EventContract) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Registered command endpoints
+    #[must_use]
+    pub fn commands(&self) -> &[CommandContract] {
+        &self.commands
+    }
+
+    /// Registered event types
+    #[must_use]
+    pub fn events(&self) -> &[EventContract] {
+        &self.events
+    }
+
+    fn info(&self) -> Value {
+        let mut info = json!({ "title": self.title, "version": self.version });
+        if let Some(description) = &self.description {
+            info["description"] = json!(description);
+        }
+        info
+    }
+
+    /// Generate the `OpenAPI` 3.1 document for the command endpoints
+    #[must_use]
+    pub fn openapi(&self) -> Value {
+        let mut paths = Map::new();
+        for command in &self.commands {
+            let item = paths
+                .entry(command.path.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+            item[command.method.as_str().to_lowercase()] = command.operation();
+        }
+
+        json!({
+            "openapi": "3.1.0",
+            "info": self.info(),
+            "paths": paths,
+        })
+    }
+
+    /// Generate the `AsyncAPI` 2.6 document for the published events
+    ///
+    /// Each topic becomes a channel whose `subscribe` operation lists the
+    /// event types a consumer can receive on it.
+    #[must_use]
+    pub fn asyncapi(&self) -> Value {
+        let mut channels = Map::new();
+        for event in &self.events {
+            let channel = channels
+                .entry(event.topic.clone())
+                .or_insert_with(|| json!({ "subscribe": { "message": { "oneOf": [] } } }));
+            if let Some(messages) = channel["subscribe"]["message"]["oneOf"].as_array_mut() {
+                messages.push(event.message());
+            }
+        }
+
+        json!({
+            "asyncapi": "2.6.0",
+            "info": self.info(),
+            "channels": channels,
+        })
+    }
+
+    /// Router serving `GET /openapi.json` and `GET /asyncapi.json`
+    ///
+    /// The documents are generated once, when the router is built.
+    pub fn router<S>(&self) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let openapi = self.openapi();
+        let asyncapi = self.asyncapi();
+
+        Router::new()
+            .route("/openapi.json", get(move || async move { Json(openapi) }))
+            .route("/asyncapi.json", get(move || async move { Json(asyncapi) }))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    fn catalog() -> ApiCatalog {
+        ApiCatalog::new("Orders", "1.0.0")
+            .with_description("Order service")
+            .with_command(
+                CommandContract::post("/orders", "PlaceOrder", json!({ "type": "object" }))
+                    .with_summary("Place an order")
+                    .with_response_schema(json!({ "type": "string" })),
+            )
+            .with_command(CommandContract::new(
+                Method::DELETE,
+                "/orders",
+                "CancelAllOrders",
+                json!({ "type": "null" }),
+            ))
+            .with_event(EventContract::new("order-events", "OrderPlaced.v1", json!({ "type": "object" })))
+            .with_event(EventContract::new("order-events", "OrderCancelled.v1", json!({ "type": "object" })))
+            .with_event(EventContract::new("payment-events", "PaymentCaptured.v1", json!({ "type": "object" })))
+    }
+
+    #[test]
+    fn test_openapi_merges_methods_per_path() {
+        let doc = catalog().openapi();
+
+        assert_eq!(doc["openapi"], "3.1.0");
+        assert_eq!(doc["info"]["description"], "Order service");
+        let path = &doc["paths"]["/orders"];
+        assert_eq!(path["post"]["operationId"], "PlaceOrder");
+        assert_eq!(path["post"]["summary"], "Place an order");
+        assert_eq!(
+            path["post"]["responses"]["200"]["content"]["application/json"]["schema"]["type"],
+            "string"
+        );
+        assert_eq!(path["delete"]["operationId"], "CancelAllOrders");
+    }
+
+    #[test]
+    fn test_asyncapi_groups_events_by_topic() {
+        let doc = catalog().asyncapi();
+
+        assert_eq!(doc["asyncapi"], "2.6.0");
+        let orders = doc["channels"]["order-events"]["subscribe"]["message"]["oneOf"]
+            .as_array()
+            .unwrap();
+        let names: Vec<_> = orders.iter().map(|m| m["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["OrderPlaced.v1", "OrderCancelled.v1"]);
+        assert!(doc["channels"]["payment-events"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_router_serves_documents() {
+        let app: Router = catalog().router();
+
+        for (uri, key) in [("/openapi.json", "openapi"), ("/asyncapi.json", "asyncapi")] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let doc: Value = serde_json::from_slice(&body).unwrap();
+            assert!(doc.get(key).is_some());
+        }
+    }
+}
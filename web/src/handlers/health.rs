@@ -5,7 +5,8 @@
 
 use axum::{extract::State, http::StatusCode, Json};
 use composable_rust_core::reducer::Reducer;
-use composable_rust_runtime::{HealthCheck, HealthStatus, Store};
+use composable_rust_runtime::health::HealthRegistry;
+use composable_rust_runtime::{HealthCheck, HealthReport, HealthStatus, Store};
 use std::sync::Arc;
 
 /// Simple health check endpoint (for basic liveness).
@@ -52,9 +53,9 @@ pub async fn health_check() -> (StatusCode, &'static str) {
 /// ```json
 /// {
 ///   "component": "store",
-///   "status": "Healthy",
-///   "message": "Store is healthy",
-///   "metadata": null
+///   "status": "healthy",
+///   "message": null,
+///   "metadata": { "dlq_size": "0" }
 /// }
 /// ```
 #[allow(clippy::unused_async)] // Axum handler signature requires async
@@ -77,6 +78,45 @@ where
     (status, Json(health))
 }
 
+/// Aggregated health of every component in a [`HealthRegistry`].
+///
+/// Runs all registered checks and returns the combined report.
+///
+/// # Status Codes
+///
+/// - 200 OK: Healthy or Degraded
+/// - 503 Service Unavailable: any component Unhealthy
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /healthz
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "status": "healthy",
+///   "checks": [
+///     { "component": "orders", "status": "healthy", "message": null, "metadata": { "dlq_size": "0" } }
+///   ],
+///   "timestamp": "2025-01-01T00:00:00Z"
+/// }
+/// ```
+pub async fn health_report(
+    State(registry): State<Arc<HealthRegistry>>,
+) -> (StatusCode, Json<HealthReport>) {
+    let report = registry.report().await;
+
+    let status = match report.status {
+        HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    (status, Json(report))
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)] // Test code can use unwrap/expect
 mod tests {
@@ -127,4 +167,25 @@ mod tests {
         assert_eq!(status, StatusCode::OK);
         assert_eq!(health.status, HealthStatus::Healthy);
     }
+
+    #[tokio::test]
+    async fn test_health_report_unhealthy_returns_503() {
+        struct Down;
+
+        impl composable_rust_runtime::health::HealthCheckProvider for Down {
+            fn check(
+                &self,
+            ) -> std::pin::Pin<Box<dyn std::future::Future<Output = HealthCheck> + Send + '_>> {
+                Box::pin(async { HealthCheck::unhealthy("db", "connection refused") })
+            }
+        }
+
+        let registry = Arc::new(HealthRegistry::new());
+        registry.register("db", Arc::new(Down));
+
+        let (status, Json(report)) = health_report(State(registry)).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(report.checks[0].component, "db");
+    }
 }
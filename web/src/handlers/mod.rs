@@ -7,6 +7,6 @@ pub mod websocket;
 pub mod websocket_topics;
 
 // Re-export common handler utilities
-pub use health::health_check;
+pub use health::{health_check, health_report};
 pub use websocket::WsMessage;
 pub use websocket_topics::TopicBroadcaster;
@@ -53,6 +53,7 @@ use axum::{
     response::Response,
 };
 use composable_rust_core::reducer::Reducer;
+use composable_rust_runtime::streaming::ActionStreamer;
 use composable_rust_runtime::Store;
 use futures::{stream::StreamExt, SinkExt};
 use serde::{Deserialize, Serialize};
@@ -164,24 +165,18 @@ where
     // Split socket into sender and receiver
     let (mut sender, mut receiver) = socket.split();
 
-    // Subscribe to action broadcasts from store
-    let mut action_rx = store.subscribe_actions();
+    // Subscribe to action broadcasts from store; lagged actions are skipped
+    let mut frames = ActionStreamer::new(store.subscribe_actions(), |action: &A| {
+        serde_json::to_string(&WsMessage::Event {
+            action: action.clone(),
+            topic: "default".to_string(),
+        })
+        .map(Message::Text)
+    });
 
     // Spawn task to send action broadcasts to client
     let mut send_task = tokio::spawn(async move {
-        while let Ok(action) = action_rx.recv().await {
-            // Serialize action to JSON
-            let message = match serde_json::to_string(&WsMessage::Event {
-                action,
-                topic: "default".to_string(),
-            }) {
-                Ok(json) => Message::Text(json),
-                Err(e) => {
-                    error!(error = %e, "Failed to serialize action");
-                    continue;
-                }
-            };
-
+        while let Some(message) = frames.next_frame().await {
             // Send to client
             if sender.send(message).await.is_err() {
                 // Client disconnected
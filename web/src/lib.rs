@@ -58,6 +58,7 @@
 #![warn(missing_docs, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod api_docs;
 pub mod error;
 pub mod extractors;
 pub mod handlers;